@@ -112,6 +112,72 @@ impl CompiledKernelArtifact {
     }
 }
 
+/// Scalar types an exported entry point may take and return across the C ABI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExportType {
+    Int,
+    Float,
+    Bool,
+}
+
+impl ExportType {
+    const fn primitive(self) -> PrimitiveType {
+        match self {
+            Self::Int => PrimitiveType::Int,
+            Self::Float => PrimitiveType::Float,
+            Self::Bool => PrimitiveType::Bool,
+        }
+    }
+
+    const fn clif_type(self) -> Type {
+        match self {
+            Self::Int => types::I64,
+            Self::Float => types::F64,
+            Self::Bool => types::I8,
+        }
+    }
+
+    const fn c_type(self) -> &'static str {
+        match self {
+            Self::Int => "int64_t",
+            Self::Float => "double",
+            Self::Bool => "bool",
+        }
+    }
+}
+
+/// One `extern "C"` entry point to emit for a top-level item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExportSpec {
+    /// Name of the top-level value or function in the backend program.
+    pub item: Box<str>,
+    /// Exported symbol name; must be a C identifier.
+    pub symbol: Box<str>,
+    pub parameters: Vec<ExportType>,
+    pub result: ExportType,
+}
+
+/// Object bytes with exported entry points, plus a C header declaring them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExportedObject {
+    object: Vec<u8>,
+    header: Box<str>,
+}
+
+impl ExportedObject {
+    pub fn object(&self) -> &[u8] {
+        &self.object
+    }
+
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    pub fn into_parts(self) -> (Vec<u8>, Box<str>) {
+        (self.object, self.header)
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct JitDataSlot {
     pub(crate) item: ItemId,
@@ -584,6 +584,13 @@ impl<'a, M: Module> CraneliftCompiler<'a, M> {
                             work.push(entry.value);
                         }
                     }
+                    KernelExprKind::Char(_) => {
+                        errors.push(self.unsupported_expression(
+                            kernel_id,
+                            expr_id,
+                            "Char literals are not supported by Cranelift compilation yet",
+                        ));
+                    }
                     KernelExprKind::SuffixedInteger(_) => {
                         // Suffixed integer literals (e.g. `5sec`) are always
                         // representational domain values over Int.  The suffix
@@ -986,6 +993,9 @@ impl<'a, M: Module> CraneliftCompiler<'a, M> {
                         collect_pattern_binding_subjects(p, callback);
                     }
                 }
+                crate::InlinePipePatternKind::TextPrefix { rest, .. } => {
+                    collect_pattern_binding_subjects(rest, callback);
+                }
                 crate::InlinePipePatternKind::Tuple(pats) => {
                     for p in pats {
                         collect_pattern_binding_subjects(p, callback);
@@ -10146,6 +10156,13 @@ impl<'a, M: Module> CraneliftCompiler<'a, M> {
                     }),
                 }
             }
+            crate::InlinePipePatternKind::TextPrefix { .. } => {
+                Err(CodegenError::UnsupportedLayout {
+                    kernel: kernel_id,
+                    layout: input_layout,
+                    detail: "lazy JIT does not lower text prefix patterns yet".into(),
+                })
+            }
             crate::InlinePipePatternKind::Constructor {
                 constructor,
                 arguments,
@@ -10889,7 +10906,8 @@ impl<'a, M: Module> CraneliftCompiler<'a, M> {
             }
             crate::InlinePipePatternKind::Wildcard
             | crate::InlinePipePatternKind::Integer(_)
-            | crate::InlinePipePatternKind::Text(_) => {}
+            | crate::InlinePipePatternKind::Text(_)
+            | crate::InlinePipePatternKind::TextPrefix { .. } => {}
         }
     }
 
@@ -11085,7 +11103,7 @@ impl<'a, M: Module> CraneliftCompiler<'a, M> {
                 Some(StaticMaterializationPlan::Bool(*value))
             }
             (LayoutKind::Primitive(PrimitiveType::Text), RuntimeValue::Text(value)) => {
-                Some(StaticMaterializationPlan::Text(value.clone()))
+                Some(StaticMaterializationPlan::Text(Box::from(value.as_ref())))
             }
             (LayoutKind::Primitive(PrimitiveType::Bytes), RuntimeValue::Bytes(value)) => {
                 Some(StaticMaterializationPlan::Bytes(value.clone()))
@@ -11493,6 +11511,9 @@ impl<'a, M: Module> CraneliftCompiler<'a, M> {
                                 suffix: integer.suffix.clone(),
                             });
                         }
+                        KernelExprKind::Char(literal) => {
+                            values.push(RuntimeValue::Char(literal.value));
+                        }
                         KernelExprKind::Text(text) => {
                             tasks.push(Task::BuildText {
                                 segments: &text.segments,
@@ -11837,7 +11858,7 @@ impl<'a, M: Module> CraneliftCompiler<'a, M> {
                             }
                         }
                     }
-                    values.push(RuntimeValue::Text(rendered.into_boxed_str()));
+                    values.push(RuntimeValue::Text(rendered.into()));
                 }
                 Task::BuildTuple { len } => {
                     let elements = drain_tail(&mut values, len);
@@ -75,6 +75,10 @@ pub enum CodegenError {
     ObjectEmission {
         message: Box<str>,
     },
+    InvalidExport {
+        symbol: Box<str>,
+        detail: Box<str>,
+    },
 }
 
 impl fmt::Display for CodegenError {
@@ -181,6 +185,9 @@ impl fmt::Display for CodegenError {
             Self::ObjectEmission { message } => {
                 write!(f, "Cranelift object emission failed: {message}")
             }
+            Self::InvalidExport { symbol, detail } => {
+                write!(f, "cannot export `{symbol}`: {detail}")
+            }
         }
     }
 }
@@ -215,6 +222,22 @@ pub fn compile_program(program: &Program) -> Result<CompiledProgram, CodegenErro
     compiler.compile()
 }
 
+/// Compile the items named by `exports` into an object with one exported `extern "C"` wrapper
+/// each, so the object can be linked into another native program.
+///
+/// The object holds only the exported body kernels and the kernels they call. Each wrapper calls
+/// its body kernel directly. Only items whose kernel already takes and
+/// returns the requested scalars by value can be exported; anything that needs runtime-owned
+/// values (`Text`, aggregates, signals) is rejected with [`CodegenError::InvalidExport`].
+pub fn compile_program_with_exports(
+    program: &Program,
+    exports: &[ExportSpec],
+) -> Result<ExportedObject, CodegenErrors> {
+    validate_backend_program(program)?;
+    let compiler = CraneliftCompiler::new(program).map_err(wrap_one)?;
+    compiler.compile_with_exports(exports)
+}
+
 /// Compile a single backend kernel into a standalone object artifact while leaving interpreter
 /// execution as the active runtime path.
 pub fn compile_kernel(
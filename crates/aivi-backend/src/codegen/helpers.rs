@@ -211,6 +211,7 @@ fn static_structural_eq(left: &RuntimeValue, right: &RuntimeValue) -> bool {
         (RuntimeValue::Decimal(left), RuntimeValue::Decimal(right)) => left == right,
         (RuntimeValue::BigInt(left), RuntimeValue::BigInt(right)) => left == right,
         (RuntimeValue::Text(left), RuntimeValue::Text(right)) => left == right,
+        (RuntimeValue::Char(left), RuntimeValue::Char(right)) => left == right,
         (RuntimeValue::Bytes(left), RuntimeValue::Bytes(right)) => left == right,
         (RuntimeValue::Int(left), RuntimeValue::SuffixedInteger { raw, .. })
         | (RuntimeValue::SuffixedInteger { raw, .. }, RuntimeValue::Int(left)) => {
@@ -240,8 +241,14 @@ fn static_structural_eq(left: &RuntimeValue, right: &RuntimeValue) -> bool {
         (RuntimeValue::Map(left), RuntimeValue::Map(right)) => static_unordered_map_eq(left, right),
         (RuntimeValue::Record(left), RuntimeValue::Record(right)) => {
             left.len() == right.len()
-                && left.iter().zip(right.iter()).all(|(left, right)| {
-                    left.label == right.label && static_structural_eq(&left.value, &right.value)
+                && left.iter().enumerate().all(|(index, left_field)| {
+                    let right_field = match right.get(index) {
+                        Some(field) if field.label == left_field.label => Some(field),
+                        _ => right.iter().find(|field| field.label == left_field.label),
+                    };
+                    right_field.is_some_and(|right_field| {
+                        static_structural_eq(&left_field.value, &right_field.value)
+                    })
                 })
         }
         (RuntimeValue::Sum(left), RuntimeValue::Sum(right)) => {
@@ -53,6 +53,179 @@ impl<'a> CraneliftCompiler<'a, ObjectModule> {
         self.finish_object_compilation(built_kernels)
     }
 
+    fn compile_with_exports(
+        mut self,
+        exports: &[ExportSpec],
+    ) -> Result<ExportedObject, CodegenErrors> {
+        let resolved = self.resolve_exports(exports)?;
+        // Only the exported kernels and what they call go into the object, so unrelated items
+        // cannot pull runtime-only imports into the link.
+        let mut kernel_ids = BTreeSet::new();
+        let mut seen_items = BTreeSet::new();
+        for kernel_id in &resolved {
+            collect_jit_kernel_dependencies(
+                self.program,
+                *kernel_id,
+                &mut kernel_ids,
+                &mut seen_items,
+            );
+        }
+        self.prevalidate_kernels(kernel_ids.iter().copied())?;
+        self.declare_kernels(kernel_ids.iter().copied(), KernelLinkage::Local)?;
+        let built_kernels = self.build_kernels(kernel_ids.iter().copied())?;
+        for (export, kernel_id) in exports.iter().zip(resolved) {
+            self.define_export_wrapper(export, kernel_id)
+                .map_err(wrap_one)?;
+        }
+        let compiled = self.finish_object_compilation(built_kernels)?;
+        Ok(ExportedObject {
+            object: compiled.object,
+            header: export_header(exports).into_boxed_str(),
+        })
+    }
+
+    /// Find the body kernel behind each export and check that its convention matches the
+    /// requested C signature exactly.
+    fn resolve_exports(&self, exports: &[ExportSpec]) -> Result<Vec<KernelId>, CodegenErrors> {
+        let mut resolved = Vec::with_capacity(exports.len());
+        let mut errors = Vec::new();
+        let mut symbols = BTreeSet::new();
+        for export in exports {
+            let invalid = |detail: String| CodegenError::InvalidExport {
+                symbol: export.symbol.clone(),
+                detail: detail.into_boxed_str(),
+            };
+            if !is_c_identifier(&export.symbol) {
+                errors.push(invalid("the symbol is not a C identifier".to_owned()));
+                continue;
+            }
+            if !symbols.insert(export.symbol.as_ref()) {
+                errors.push(invalid("the symbol is exported more than once".to_owned()));
+                continue;
+            }
+            let Some(item) = self
+                .program
+                .items()
+                .iter()
+                .map(|(_, item)| item)
+                .find(|item| item.name == export.item)
+            else {
+                errors.push(invalid(format!(
+                    "the program has no item named `{}`",
+                    export.item
+                )));
+                continue;
+            };
+            let Some(kernel_id) = item.body else {
+                errors.push(invalid(format!(
+                    "item `{}` has no body kernel",
+                    export.item
+                )));
+                continue;
+            };
+            let convention = &self.program.kernels()[kernel_id].convention;
+            if convention.parameters.len() != export.parameters.len() {
+                errors.push(invalid(format!(
+                    "item `{}` takes {} parameter(s) but the export declares {}",
+                    export.item,
+                    convention.parameters.len(),
+                    export.parameters.len()
+                )));
+                continue;
+            }
+            let mismatch = convention
+                .parameters
+                .iter()
+                .map(|parameter| (parameter.layout, parameter.pass_mode))
+                .zip(&export.parameters)
+                .enumerate()
+                .map(|(index, (actual, expected))| {
+                    (format!("parameter {index}"), actual, *expected)
+                })
+                .chain([(
+                    "the result".to_owned(),
+                    (convention.result.layout, convention.result.pass_mode),
+                    export.result,
+                )])
+                .find(|(_, (layout, pass_mode), expected)| {
+                    *pass_mode != AbiPassMode::ByValue
+                        || self.program.layouts()[*layout].kind
+                            != LayoutKind::Primitive(expected.primitive())
+                });
+            if let Some((position, (layout, _), expected)) = mismatch {
+                errors.push(invalid(format!(
+                    "{position} of `{}` is `{}`, not a by-value `{}`",
+                    export.item,
+                    self.program.layouts()[layout],
+                    expected.primitive()
+                )));
+                continue;
+            }
+            resolved.push(kernel_id);
+        }
+        if errors.is_empty() {
+            Ok(resolved)
+        } else {
+            Err(CodegenErrors::new(errors))
+        }
+    }
+
+    fn define_export_wrapper(
+        &mut self,
+        export: &ExportSpec,
+        kernel_id: KernelId,
+    ) -> Result<(), CodegenError> {
+        let mut signature = self.module.make_signature();
+        signature.params.extend(
+            export
+                .parameters
+                .iter()
+                .map(|parameter| AbiParam::new(parameter.clif_type())),
+        );
+        signature
+            .returns
+            .push(AbiParam::new(export.result.clif_type()));
+        let module_error = |error: cranelift_module::ModuleError| CodegenError::CraneliftModule {
+            kernel: Some(kernel_id),
+            message: error.to_string().into_boxed_str(),
+        };
+        let func_id = self
+            .module
+            .declare_function(&export.symbol, Linkage::Export, &signature)
+            .map_err(module_error)?;
+        let kernel_func_id = self.declared_functions[&kernel_id];
+
+        let mut ctx = self.module.make_context();
+        ctx.func.signature = signature;
+        ctx.func.name = UserFuncName::user(0, func_id.as_u32());
+        let mut function_builder_ctx = std::mem::take(&mut self.function_builder_ctx);
+        {
+            let mut builder = FunctionBuilder::new(&mut ctx.func, &mut function_builder_ctx);
+            let entry = builder.create_block();
+            builder.switch_to_block(entry);
+            builder.append_block_params_for_function_params(entry);
+            let arguments = builder.block_params(entry).to_vec();
+            let callee = self
+                .module
+                .declare_func_in_func(kernel_func_id, builder.func);
+            let call = builder.ins().call(callee, &arguments);
+            let result = builder.inst_results(call)[0];
+            builder.ins().return_(&[result]);
+            builder.seal_all_blocks();
+            builder.finalize();
+        }
+        self.function_builder_ctx = function_builder_ctx;
+        if let Err(error) = verify_function(&ctx.func, self.module.isa()) {
+            return Err(CodegenError::CraneliftVerifier {
+                kernel: kernel_id,
+                message: pretty_verifier_error(&ctx.func, None, error).into_boxed_str(),
+            });
+        }
+        self.module
+            .define_function(func_id, &mut ctx)
+            .map_err(module_error)
+    }
+
     fn compile_kernel(
         mut self,
         kernel_id: KernelId,
@@ -1837,3 +2010,56 @@ fn replay_imported_item_slot_symbol(item: ItemId) -> String {
 fn replay_callable_descriptor_symbol(item: ItemId) -> String {
     format!("aivi_replay_callable_item_{}", item.as_raw())
 }
+
+fn is_c_identifier(symbol: &str) -> bool {
+    let mut chars = symbol.chars();
+    chars
+        .next()
+        .is_some_and(|first| first == '_' || first.is_ascii_alphabetic())
+        && chars.all(|rest| rest == '_' || rest.is_ascii_alphanumeric())
+}
+
+/// C declarations for the wrappers emitted by [`compile_program_with_exports`].
+fn export_header(exports: &[ExportSpec]) -> String {
+    let mut header = String::from(
+        "/* Generated by the AIVI backend. Do not edit. */\n\
+         #ifndef AIVI_EXPORTS_H\n\
+         #define AIVI_EXPORTS_H\n\
+         \n\
+         #include <stdbool.h>\n\
+         #include <stdint.h>\n\
+         \n\
+         #ifdef __cplusplus\n\
+         extern \"C\" {\n\
+         #endif\n\
+         \n",
+    );
+    for export in exports {
+        let parameters = if export.parameters.is_empty() {
+            "void".to_owned()
+        } else {
+            export
+                .parameters
+                .iter()
+                .enumerate()
+                .map(|(index, parameter)| format!("{} arg{index}", parameter.c_type()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        header.push_str(&format!(
+            "/* {} */\n{} {}({parameters});\n",
+            export.item,
+            export.result.c_type(),
+            export.symbol
+        ));
+    }
+    header.push_str(
+        "\n\
+         #ifdef __cplusplus\n\
+         }\n\
+         #endif\n\
+         \n\
+         #endif /* AIVI_EXPORTS_H */\n",
+    );
+    header
+}
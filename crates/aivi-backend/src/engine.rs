@@ -191,6 +191,13 @@ pub struct BackendExecutionOptions {
     /// Force the backend to use the interpreter even when the executable program could create a
     /// lazy JIT engine.
     pub prefer_interpreter: bool,
+    /// Report evaluation errors as [`EvaluationError::WithTrace`] with the chain of items and
+    /// function calls that led to them. Only interpreter engines record traces.
+    pub trace_errors: bool,
+    /// Fail an evaluation with [`EvaluationError::AllocationBudgetExceeded`] once the results it
+    /// builds add up to more than this many estimated bytes, whether or not they are still alive.
+    /// Only interpreter engines enforce the budget.
+    pub allocation_budget: Option<usize>,
 }
 
 impl BackendExecutionEngine for KernelEvaluator<'_> {
@@ -393,7 +400,12 @@ impl<'a> BackendExecutableProgram<'a> {
         match self.backend {
             BackendRuntimeView::Program(program) => {
                 if self.execution_options.prefer_interpreter {
-                    Box::new(KernelEvaluator::new(program))
+                    let mut evaluator = KernelEvaluator::new(program);
+                    evaluator.set_error_traces(self.execution_options.trace_errors);
+                    if let Some(bytes) = self.execution_options.allocation_budget {
+                        evaluator.set_allocation_budget(bytes);
+                    }
+                    Box::new(evaluator)
                 } else if let Some(native_kernels) = self.native_kernels {
                     Box::new(LazyJitExecutionEngine::new_with_native_artifacts(
                         program,
@@ -423,7 +435,12 @@ impl<'a> BackendExecutableProgram<'a> {
         match self.backend {
             BackendRuntimeView::Program(program) => {
                 if self.execution_options.prefer_interpreter {
-                    Box::new(KernelEvaluator::new_profiled(program))
+                    let mut evaluator = KernelEvaluator::new_profiled(program);
+                    evaluator.set_error_traces(self.execution_options.trace_errors);
+                    if let Some(bytes) = self.execution_options.allocation_budget {
+                        evaluator.set_allocation_budget(bytes);
+                    }
+                    Box::new(evaluator)
                 } else if let Some(native_kernels) = self.native_kernels {
                     Box::new(LazyJitExecutionEngine::new_profiled_with_native_artifacts(
                         program,
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use crate::{
+    KernelExprId, KernelId, Program,
+    kernel::{
+        BuiltinTerm, FloatLiteral, IntegerLiteral, Kernel, KernelExprKind, TextLiteral, TextSegment,
+    },
+    layout::{LayoutKind, PrimitiveType},
+    numeric::RuntimeFloat,
+    runtime::{KernelEvaluator, RuntimeValue},
+};
+
+/// How much backend-IR rewriting to do between lowering and validation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OptimizationLevel {
+    /// Keep kernels exactly as lowering produced them.
+    #[default]
+    None,
+    /// Run [`fold_constants`] over every kernel.
+    Fold,
+}
+
+impl OptimizationLevel {
+    /// Map a numeric `--opt-level` value onto a level, or `None` when it is out of range.
+    pub fn from_level(level: u8) -> Option<Self> {
+        match level {
+            0 => Some(Self::None),
+            1 => Some(Self::Fold),
+            _ => None,
+        }
+    }
+
+    pub fn apply(self, program: Program) -> Program {
+        match self {
+            Self::None => program,
+            Self::Fold => fold_constants(&program),
+        }
+    }
+}
+
+/// Evaluate constant subexpressions of every kernel at compile time.
+///
+/// Folding covers `Int`/`Float` arithmetic, comparisons, and `Bool` logic whose operands are
+/// literals, plus text interpolations of such constants, which become plain fragments. Each
+/// operator is applied through the interpreter's own arithmetic, so anything the interpreter
+/// would reject at runtime (overflow, division by zero, a non-finite float) is left in place to
+/// fail there. Applications, item references, class evidence, and domain-typed expressions are
+/// never folded. Folded operands stay in the expression arena unreferenced.
+///
+/// List literals are not replaced as a whole: kernel IR has no constant-value expression, and a
+/// `List` runtime value is a fresh vector that each evaluation builds anyway. Their elements fold
+/// like any other expression, so `[1 + 1, 2]` becomes `[2, 2]`.
+pub fn fold_constants(program: &Program) -> Program {
+    let evaluator = KernelEvaluator::new(program);
+    let mut folded = program.clone();
+    let kernel_ids = program
+        .kernels()
+        .iter()
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    for kernel_id in kernel_ids {
+        let kernel = folded
+            .kernels_mut()
+            .get_mut(kernel_id)
+            .expect("kernel ids come from the same program");
+        fold_kernel(program, &evaluator, kernel_id, kernel);
+    }
+    folded
+}
+
+fn fold_kernel(
+    program: &Program,
+    evaluator: &KernelEvaluator<'_>,
+    kernel_id: KernelId,
+    kernel: &mut Kernel,
+) {
+    let expr_ids = kernel.exprs().iter().map(|(id, _)| id).collect::<Vec<_>>();
+    let mut constants = HashMap::<KernelExprId, Option<RuntimeValue>>::new();
+    // Post-order walk on an explicit stack so long operator chains do not recurse.
+    let mut stack = Vec::new();
+    for expr_id in expr_ids {
+        stack.push((expr_id, false));
+        while let Some((expr_id, operands_done)) = stack.pop() {
+            if constants.contains_key(&expr_id) {
+                continue;
+            }
+            if !operands_done {
+                stack.push((expr_id, true));
+                for operand in foldable_operands(&kernel.exprs()[expr_id].kind) {
+                    if !constants.contains_key(&operand) {
+                        stack.push((operand, false));
+                    }
+                }
+                continue;
+            }
+            let constant = fold_expr(program, evaluator, kernel_id, kernel, expr_id, &constants);
+            constants.insert(expr_id, constant);
+        }
+    }
+}
+
+fn foldable_operands(kind: &KernelExprKind) -> Vec<KernelExprId> {
+    match kind {
+        KernelExprKind::Unary { expr, .. } => vec![*expr],
+        KernelExprKind::Binary { left, right, .. } => vec![*left, *right],
+        KernelExprKind::Text(text) => text
+            .segments
+            .iter()
+            .filter_map(|segment| match segment {
+                TextSegment::Interpolation { expr, .. } => Some(*expr),
+                TextSegment::Fragment { .. } => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Rewrite `expr_id` in place when its operands are constant, and return its constant value.
+fn fold_expr(
+    program: &Program,
+    evaluator: &KernelEvaluator<'_>,
+    kernel_id: KernelId,
+    kernel: &mut Kernel,
+    expr_id: KernelExprId,
+    constants: &HashMap<KernelExprId, Option<RuntimeValue>>,
+) -> Option<RuntimeValue> {
+    let constant_of = |operand: &KernelExprId| constants.get(operand).cloned().flatten();
+    let expr = &kernel.exprs()[expr_id];
+    if !is_foldable_layout(program, expr) {
+        return None;
+    }
+    let value = match &expr.kind {
+        KernelExprKind::Integer(integer) => {
+            return integer.raw.parse::<i64>().ok().map(RuntimeValue::Int);
+        }
+        KernelExprKind::Float(float) => {
+            return RuntimeFloat::parse_literal(&float.raw).map(RuntimeValue::Float);
+        }
+        KernelExprKind::Builtin(BuiltinTerm::True) => return Some(RuntimeValue::Bool(true)),
+        KernelExprKind::Builtin(BuiltinTerm::False) => return Some(RuntimeValue::Bool(false)),
+        KernelExprKind::Unary {
+            operator,
+            expr: operand,
+        } => {
+            let operand = constant_of(operand)?;
+            evaluator
+                .apply_unary(kernel_id, expr_id, *operator, operand)
+                .ok()?
+        }
+        KernelExprKind::Binary {
+            left,
+            operator,
+            right,
+        } => {
+            let (left, right) = (constant_of(left)?, constant_of(right)?);
+            evaluator
+                .apply_binary(kernel_id, expr_id, *operator, left, right)
+                .ok()?
+        }
+        KernelExprKind::Text(text) => {
+            let text = fold_text_segments(text, &constant_of);
+            let value = match text.segments.as_slice() {
+                [] => Some(RuntimeValue::Text("".into())),
+                [TextSegment::Fragment { raw, .. }] => {
+                    Some(RuntimeValue::Text(raw.as_ref().into()))
+                }
+                _ => None,
+            };
+            kernel.exprs_mut().get_mut(expr_id)?.kind = KernelExprKind::Text(text);
+            return value;
+        }
+        _ => return None,
+    };
+    let kind = literal_kind(&value, kernel.exprs()[expr_id].span)?;
+    kernel.exprs_mut().get_mut(expr_id)?.kind = kind;
+    Some(value)
+}
+
+/// Only primitive scalar results fold; domain carriers keep their own operator semantics.
+fn is_foldable_layout(program: &Program, expr: &crate::KernelExpr) -> bool {
+    matches!(
+        program
+            .layouts()
+            .get(expr.layout)
+            .map(|layout| &layout.kind),
+        Some(LayoutKind::Primitive(
+            PrimitiveType::Int | PrimitiveType::Float | PrimitiveType::Bool | PrimitiveType::Text
+        ))
+    )
+}
+
+/// Render constant interpolations into fragments and merge neighbouring fragments.
+fn fold_text_segments(
+    text: &TextLiteral,
+    constant_of: &impl Fn(&KernelExprId) -> Option<RuntimeValue>,
+) -> TextLiteral {
+    let mut segments: Vec<TextSegment> = Vec::with_capacity(text.segments.len());
+    for segment in &text.segments {
+        let (rendered, span) = match segment {
+            TextSegment::Fragment { raw, span } => (raw.to_string(), *span),
+            TextSegment::Interpolation { expr, span } => match constant_of(expr) {
+                Some(value) => {
+                    let mut rendered = String::new();
+                    value
+                        .write_display_text(&mut rendered)
+                        .expect("writing into a String should not fail");
+                    (rendered, *span)
+                }
+                None => {
+                    segments.push(segment.clone());
+                    continue;
+                }
+            },
+        };
+        match segments.last_mut() {
+            Some(TextSegment::Fragment {
+                raw: previous,
+                span: previous_span,
+            }) => {
+                *previous = format!("{previous}{rendered}").into_boxed_str();
+                *previous_span = previous_span.join(span).unwrap_or(*previous_span);
+            }
+            _ => segments.push(TextSegment::Fragment {
+                raw: rendered.into_boxed_str(),
+                span,
+            }),
+        }
+    }
+    TextLiteral { segments }
+}
+
+fn literal_kind(value: &RuntimeValue, span: aivi_base::SourceSpan) -> Option<KernelExprKind> {
+    Some(match value {
+        RuntimeValue::Int(value) => KernelExprKind::Integer(IntegerLiteral {
+            raw: value.to_string().into_boxed_str(),
+        }),
+        RuntimeValue::Float(value) => KernelExprKind::Float(FloatLiteral {
+            raw: value.to_string().into_boxed_str(),
+        }),
+        RuntimeValue::Bool(true) => KernelExprKind::Builtin(BuiltinTerm::True),
+        RuntimeValue::Bool(false) => KernelExprKind::Builtin(BuiltinTerm::False),
+        RuntimeValue::Text(value) => KernelExprKind::Text(TextLiteral {
+            segments: vec![TextSegment::Fragment {
+                raw: value.as_ref().into(),
+                span,
+            }],
+        }),
+        _ => return None,
+    })
+}
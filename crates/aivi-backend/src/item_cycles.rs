@@ -0,0 +1,146 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Find item dependency cycles that force a value item to evaluate itself.
+///
+/// A function body only runs once the function is applied, so a cycle made of functions alone is
+/// ordinary (mutual) recursion and is not reported. Every strongly connected component that
+/// contains a value item is reported once, as a concrete path that starts and ends at its
+/// smallest value item. Components are found with an iterative Tarjan walk so deep dependency
+/// chains cannot overflow the stack.
+pub(crate) fn value_dependency_cycles<Id>(
+    dependencies: &HashMap<Id, Vec<Id>>,
+    is_value: impl Fn(Id) -> bool,
+) -> Vec<Vec<Id>>
+where
+    Id: Copy + Eq + Hash + Ord,
+{
+    strongly_connected_components(dependencies)
+        .into_iter()
+        .filter_map(|component| {
+            let value = component.iter().copied().filter(|id| is_value(*id)).min()?;
+            let members = component.iter().copied().collect::<HashSet<_>>();
+            cycle_through(dependencies, &members, value)
+        })
+        .collect()
+}
+
+fn neighbours<'a, Id: Eq + Hash>(dependencies: &'a HashMap<Id, Vec<Id>>, node: &Id) -> &'a [Id] {
+    dependencies.get(node).map(Vec::as_slice).unwrap_or(&[])
+}
+
+fn strongly_connected_components<Id>(dependencies: &HashMap<Id, Vec<Id>>) -> Vec<Vec<Id>>
+where
+    Id: Copy + Eq + Hash + Ord,
+{
+    let mut roots = dependencies.keys().copied().collect::<Vec<_>>();
+    roots.sort();
+    let mut walk = TarjanWalk {
+        index: HashMap::new(),
+        lowlink: HashMap::new(),
+        on_stack: HashSet::new(),
+        component_stack: Vec::new(),
+        components: Vec::new(),
+    };
+    for root in roots {
+        if !walk.index.contains_key(&root) {
+            walk.run(dependencies, root);
+        }
+    }
+    walk.components
+}
+
+struct TarjanWalk<Id> {
+    index: HashMap<Id, usize>,
+    lowlink: HashMap<Id, usize>,
+    on_stack: HashSet<Id>,
+    component_stack: Vec<Id>,
+    components: Vec<Vec<Id>>,
+}
+
+impl<Id: Copy + Eq + Hash> TarjanWalk<Id> {
+    fn visit(&mut self, node: Id) {
+        let next = self.index.len();
+        self.index.insert(node, next);
+        self.lowlink.insert(node, next);
+        self.on_stack.insert(node);
+        self.component_stack.push(node);
+    }
+
+    fn lower(&mut self, node: Id, candidate: usize) {
+        let low = self.lowlink[&node].min(candidate);
+        self.lowlink.insert(node, low);
+    }
+
+    fn run(&mut self, dependencies: &HashMap<Id, Vec<Id>>, root: Id) {
+        self.visit(root);
+        // Each frame is (node, index of the next outgoing edge to follow).
+        let mut frames = vec![(root, 0_usize)];
+        while let Some(&(node, edge)) = frames.last() {
+            if let Some(&next) = neighbours(dependencies, &node).get(edge) {
+                frames.last_mut().expect("frame was just read").1 += 1;
+                if !self.index.contains_key(&next) {
+                    self.visit(next);
+                    frames.push((next, 0));
+                } else if self.on_stack.contains(&next) {
+                    self.lower(node, self.index[&next]);
+                }
+                continue;
+            }
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                self.lower(parent, self.lowlink[&node]);
+            }
+            if self.lowlink[&node] == self.index[&node] {
+                let mut component = Vec::new();
+                loop {
+                    let member = self
+                        .component_stack
+                        .pop()
+                        .expect("a component root is still on the component stack");
+                    self.on_stack.remove(&member);
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+}
+
+/// Shortest path from `start` back to itself that stays inside `members`, or `None` when the
+/// component is a single item without a self-edge.
+fn cycle_through<Id>(
+    dependencies: &HashMap<Id, Vec<Id>>,
+    members: &HashSet<Id>,
+    start: Id,
+) -> Option<Vec<Id>>
+where
+    Id: Copy + Eq + Hash,
+{
+    let mut parents = HashMap::<Id, Id>::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        for &next in neighbours(dependencies, &node) {
+            if next == start {
+                let mut cycle = vec![start, node];
+                let mut current = node;
+                while current != start {
+                    current = parents[&current];
+                    cycle.push(current);
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+            if members.contains(&next) && !parents.contains_key(&next) {
+                parents.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
@@ -957,6 +957,8 @@ signal derived = base
             BackendExecutionOptions {
                 eagerly_compile_signals: true,
                 prefer_interpreter: false,
+                trace_errors: false,
+                allocation_budget: None,
             },
         );
 
@@ -3433,7 +3435,7 @@ enum CompiledKernelFailure {
 fn decode_text(pointer: *const c_void) -> Option<RuntimeValue> {
     let bytes = decode_len_prefixed_bytes(pointer)?;
     let text = String::from_utf8(bytes.into_vec()).ok()?;
-    Some(RuntimeValue::Text(text.into_boxed_str()))
+    Some(RuntimeValue::Text(text.into()))
 }
 
 fn strip_signal_wrappers(mut value: &RuntimeValue) -> &RuntimeValue {
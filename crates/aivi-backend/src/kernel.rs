@@ -104,6 +104,11 @@ pub struct FloatLiteral {
     pub raw: Box<str>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CharLiteral {
+    pub value: char,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct DecimalLiteral {
     pub raw: Box<str>,
@@ -446,6 +451,7 @@ pub enum KernelExprKind {
     BigInt(BigIntLiteral),
     SuffixedInteger(SuffixedIntegerLiteral),
     Text(TextLiteral),
+    Char(CharLiteral),
     Tuple(Vec<KernelExprId>),
     List(Vec<KernelExprId>),
     Map(Vec<MapEntry>),
@@ -586,6 +592,10 @@ pub enum InlinePipePatternKind {
     },
     Integer(IntegerLiteral),
     Text(Box<str>),
+    TextPrefix {
+        prefix: Box<str>,
+        rest: Box<InlinePipePattern>,
+    },
     Tuple(Vec<InlinePipePattern>),
     List {
         elements: Vec<InlinePipePattern>,
@@ -635,6 +645,7 @@ pub fn describe_expr_kind(kind: &KernelExprKind) -> String {
         KernelExprKind::BigInt(bigint) => bigint.raw.to_string(),
         KernelExprKind::SuffixedInteger(integer) => format!("{}{}", integer.raw, integer.suffix),
         KernelExprKind::Text(text) => format!("text segments={}", text.segments.len()),
+        KernelExprKind::Char(literal) => format!("{:?}", literal.value),
         KernelExprKind::Tuple(elements) => format!("tuple elems={}", elements.len()),
         KernelExprKind::List(elements) => format!("list elems={}", elements.len()),
         KernelExprKind::Map(entries) => format!("map entries={}", entries.len()),
@@ -29,6 +29,7 @@ pub enum PrimitiveType {
     Text,
     Unit,
     Bytes,
+    Char,
     List,
     Map,
     Set,
@@ -50,6 +51,7 @@ impl PrimitiveType {
             BuiltinType::Text => Self::Text,
             BuiltinType::Unit => Self::Unit,
             BuiltinType::Bytes => Self::Bytes,
+            BuiltinType::Char => Self::Char,
             BuiltinType::List => Self::List,
             BuiltinType::Map => Self::Map,
             BuiltinType::Set => Self::Set,
@@ -63,7 +65,7 @@ impl PrimitiveType {
 
     pub const fn default_abi(self) -> AbiPassMode {
         match self {
-            Self::Int | Self::Float | Self::Bool | Self::Unit => AbiPassMode::ByValue,
+            Self::Int | Self::Float | Self::Bool | Self::Unit | Self::Char => AbiPassMode::ByValue,
             Self::Decimal | Self::BigInt => AbiPassMode::ByReference,
             Self::Text
             | Self::Bytes
@@ -90,6 +92,7 @@ impl fmt::Display for PrimitiveType {
             Self::Text => f.write_str("Text"),
             Self::Unit => f.write_str("Unit"),
             Self::Bytes => f.write_str("Bytes"),
+            Self::Char => f.write_str("Char"),
             Self::List => f.write_str("List"),
             Self::Map => f.write_str("Map"),
             Self::Set => f.write_str("Set"),
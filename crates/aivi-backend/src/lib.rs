@@ -19,8 +19,10 @@
 pub mod cache;
 mod codegen;
 mod engine;
+mod fold;
 mod gc;
 mod ids;
+mod item_cycles;
 mod jit;
 mod kernel;
 mod layout;
@@ -42,13 +44,15 @@ pub use cache::{
 };
 pub use codegen::{
     CodegenError, CodegenErrors, CompiledKernel, CompiledKernelArtifact, CompiledProgram,
-    KernelFingerprint, compile_kernel, compile_program, compute_kernel_fingerprint, kernel_symbol,
+    ExportSpec, ExportType, ExportedObject, KernelFingerprint, compile_kernel, compile_program,
+    compile_program_with_exports, compute_kernel_fingerprint, kernel_symbol,
 };
 pub use engine::{
     BackendExecutableProgram, BackendExecutionEngine, BackendExecutionEngineHandle,
     BackendExecutionEngineKind, BackendExecutionOptions, BackendRuntimeKernelRef,
     BackendRuntimeView,
 };
+pub use fold::{OptimizationLevel, fold_constants};
 pub use gc::{
     CommittedValueStore, InlineCommittedValueStore, MovingRuntimeValueStore, RuntimeGcHandle,
 };
@@ -65,13 +69,13 @@ pub use kernel::{
     BuiltinApplicativeCarrier, BuiltinApplyCarrier, BuiltinBifunctorCarrier,
     BuiltinClassMemberIntrinsic, BuiltinFilterableCarrier, BuiltinFoldableCarrier,
     BuiltinFunctorCarrier, BuiltinMonadCarrier, BuiltinOrdSubject, BuiltinTerm,
-    BuiltinTraversableCarrier, CallingConvention, CallingConventionKind, DecimalLiteral,
-    ExecutableEvidence, FloatLiteral, InlinePipeCaseArm, InlinePipeConstructor, InlinePipeExpr,
-    InlinePipePattern, InlinePipePatternKind, InlinePipeRecordPatternField, InlinePipeStage,
-    InlinePipeStageKind, InlinePipeTruthyFalsyBranch, IntegerLiteral, Kernel, KernelExpr,
-    KernelExprKind, KernelOrigin, KernelOriginKind, MapEntry, ParameterRole, ProjectionBase,
-    RecordExprField, SubjectRef, SuffixedIntegerLiteral, TextLiteral, TextSegment, UnaryOperator,
-    describe_expr_kind,
+    BuiltinTraversableCarrier, CallingConvention, CallingConventionKind, CharLiteral,
+    DecimalLiteral, ExecutableEvidence, FloatLiteral, InlinePipeCaseArm, InlinePipeConstructor,
+    InlinePipeExpr, InlinePipePattern, InlinePipePatternKind, InlinePipeRecordPatternField,
+    InlinePipeStage, InlinePipeStageKind, InlinePipeTruthyFalsyBranch, IntegerLiteral, Kernel,
+    KernelExpr, KernelExprKind, KernelOrigin, KernelOriginKind, MapEntry, ParameterRole,
+    ProjectionBase, RecordExprField, SubjectRef, SuffixedIntegerLiteral, TextLiteral, TextSegment,
+    UnaryOperator, describe_expr_kind,
 };
 pub use layout::{
     AbiPassMode, Layout, LayoutKind, PrimitiveType, RecordFieldLayout, VariantLayout,
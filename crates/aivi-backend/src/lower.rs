@@ -23,22 +23,23 @@ use aivi_typing::{
 
 use crate::{
     AbiParameter, AbiPassMode, AbiResult, BigIntLiteral, BinaryOperator, BuiltinTerm,
-    CallingConvention, CallingConventionKind, DecimalLiteral, DecodeExtraFieldPolicy, DecodeField,
-    DecodeFieldRequirement, DecodeMode, DecodePlan, DecodePlanId, DecodeStep, DecodeStepId,
-    DecodeStepKind, DecodeSumStrategy, DecodeVariant, DomainDecodeSurface, DomainDecodeSurfaceKind,
-    EnvSlotId, FanoutCarrier, FanoutFilter, FanoutJoin, FanoutStage, FloatLiteral, GateStage,
-    InlinePipeCaseArm, InlinePipeConstructor, InlinePipeExpr, InlinePipePattern,
-    InlinePipePatternKind, InlinePipeRecordPatternField, InlinePipeStage, InlinePipeStageKind,
-    InlinePipeTruthyFalsyBranch, InlineSubjectId, IntegerLiteral, Item, ItemId, ItemKind, Kernel,
-    KernelExpr, KernelExprId, KernelExprKind, KernelId, KernelOrigin, KernelOriginKind, Layout,
-    LayoutId, LayoutKind, LoweringError::*, MapEntry, NonSourceWakeup, NonSourceWakeupCause,
-    ParameterRole, Pipeline, PipelineId, PipelineOrigin, PrimitiveType, Program, ProjectionBase,
-    RecordExprField, RecordFieldLayout, Recurrence, RecurrenceStage, RecurrenceTarget,
-    RecurrenceWakeupKind, SignalInfo, SourceArgumentKernel, SourceCancellationPolicy,
-    SourceInstanceId, SourceOptionBinding, SourceOptionKernel, SourcePlan, SourceProvider,
-    SourceReplacementPolicy, SourceStaleWorkPolicy, SourceTeardownPolicy, Stage, StageKind,
-    SubjectRef, SuffixedIntegerLiteral, TemporalStage, TextLiteral, TextSegment, TruthyFalsyBranch,
-    TruthyFalsyStage, UnaryOperator, ValidationError, VariantLayout, validate_program,
+    CallingConvention, CallingConventionKind, CharLiteral, DecimalLiteral, DecodeExtraFieldPolicy,
+    DecodeField, DecodeFieldRequirement, DecodeMode, DecodePlan, DecodePlanId, DecodeStep,
+    DecodeStepId, DecodeStepKind, DecodeSumStrategy, DecodeVariant, DomainDecodeSurface,
+    DomainDecodeSurfaceKind, EnvSlotId, FanoutCarrier, FanoutFilter, FanoutJoin, FanoutStage,
+    FloatLiteral, GateStage, InlinePipeCaseArm, InlinePipeConstructor, InlinePipeExpr,
+    InlinePipePattern, InlinePipePatternKind, InlinePipeRecordPatternField, InlinePipeStage,
+    InlinePipeStageKind, InlinePipeTruthyFalsyBranch, InlineSubjectId, IntegerLiteral, Item,
+    ItemId, ItemKind, Kernel, KernelExpr, KernelExprId, KernelExprKind, KernelId, KernelOrigin,
+    KernelOriginKind, Layout, LayoutId, LayoutKind, LoweringError::*, MapEntry, NonSourceWakeup,
+    NonSourceWakeupCause, ParameterRole, Pipeline, PipelineId, PipelineOrigin, PrimitiveType,
+    Program, ProjectionBase, RecordExprField, RecordFieldLayout, Recurrence, RecurrenceStage,
+    RecurrenceTarget, RecurrenceWakeupKind, SignalInfo, SourceArgumentKernel,
+    SourceCancellationPolicy, SourceInstanceId, SourceOptionBinding, SourceOptionKernel,
+    SourcePlan, SourceProvider, SourceReplacementPolicy, SourceStaleWorkPolicy,
+    SourceTeardownPolicy, Stage, StageKind, SubjectRef, SuffixedIntegerLiteral, TemporalStage,
+    TextLiteral, TextSegment, TruthyFalsyBranch, TruthyFalsyStage, UnaryOperator, ValidationError,
+    VariantLayout, item_cycles::value_dependency_cycles, validate_program,
 };
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -422,60 +423,21 @@ impl<'a> ProgramLowerer<'a> {
             adjacency.insert(core_id, deps);
         }
 
-        // DFS cycle detection using three-color marking:
-        //   white (absent) = not yet visited
-        //   grey (true)    = currently on the DFS stack (in-progress)
-        //   black (false)  = fully explored, no cycle through this node
-        let mut color: HashMap<core::ItemId, bool> = HashMap::new();
-        let all_nodes: Vec<core::ItemId> = adjacency.keys().copied().collect();
-        for start in all_nodes {
-            if color.contains_key(&start) {
-                continue;
-            }
-            // Iterative DFS; stack entries are (node, iterator_index, backend_item_id_for_error).
-            let mut stack: Vec<(core::ItemId, usize)> = vec![(start, 0)];
-            color.insert(start, true); // grey
-            while let Some((node, idx)) = stack.last_mut() {
-                let node = *node;
-                let deps = adjacency.get(&node).map(Vec::as_slice).unwrap_or(&[]);
-                if *idx < deps.len() {
-                    let neighbor = deps[*idx];
-                    *idx += 1;
-                    match color.get(&neighbor) {
-                        Some(true) => {
-                            // Back-edge: neighbor is grey (on stack) — cycle detected.
-                            let backend_item = self.item_map.get(&neighbor).copied();
-                            let item = backend_item.unwrap_or_else(|| {
-                                // Fallback to the node currently being processed if the
-                                // neighbor was somehow not mapped (should not happen after
-                                // seed_items, but be defensive).
-                                self.item_map.get(&node).copied().unwrap_or(
-                                    // Use a safe sentinel; require_item would have failed
-                                    // earlier for a truly unknown item.
-                                    self.item_map
-                                        .values()
-                                        .copied()
-                                        .next()
-                                        .unwrap_or(ItemId::from_raw(0)),
-                                )
-                            });
-                            return Err(LoweringError::GlobalItemCycle { item });
-                        }
-                        Some(false) => {
-                            // Black: already fully explored, skip.
-                        }
-                        None => {
-                            // White: push onto stack and mark grey.
-                            color.insert(neighbor, true);
-                            stack.push((neighbor, 0));
-                        }
-                    }
-                } else {
-                    // All neighbours explored; mark black and pop.
-                    color.insert(node, false);
-                    stack.pop();
-                }
-            }
+        // Functions may recurse through each other; only cycles that make a value item depend
+        // on itself are rejected.
+        let items = self.lambda.items();
+        let cycles = value_dependency_cycles(&adjacency, |core_id| {
+            items
+                .get(core_id)
+                .is_some_and(|item| item.parameters.is_empty())
+        });
+        if let Some(cycle) = cycles.first() {
+            let item = self
+                .item_map
+                .get(&cycle[0])
+                .copied()
+                .expect("cycle members are seeded backend items");
+            return Err(LoweringError::GlobalItemCycle { item });
         }
         Ok(())
     }
@@ -1053,6 +1015,13 @@ impl<'a> ProgramLowerer<'a> {
             }
         }
         for (layout_id, collected) in variants_by_layout {
+            let declared_order = match &self.program.layouts()[layout_id].kind {
+                LayoutKind::Opaque { item, name, .. } => item
+                    .or(collected.item)
+                    .zip(self.hir)
+                    .and_then(|(item, hir)| declared_sum_variant_names(hir, item, name)),
+                _ => None,
+            };
             let mut lowered_variants = collected
                 .variants
                 .into_iter()
@@ -1076,6 +1045,16 @@ impl<'a> ProgramLowerer<'a> {
                 })
                 .collect::<Result<Vec<_>, LoweringError>>()?;
             lowered_variants.sort_by(|left, right| left.name.cmp(&right.name));
+            // Keep same-module sums in declaration order so compiler-derived `Ord` can rank
+            // constructors from the layout alone.
+            if let Some(declared_order) = declared_order {
+                lowered_variants.sort_by_key(|variant| {
+                    declared_order
+                        .iter()
+                        .position(|name| *name == variant.name.as_ref())
+                        .unwrap_or(usize::MAX)
+                });
+            }
             let layout = self
                 .program
                 .layouts_mut()
@@ -1234,6 +1213,9 @@ impl<'a> ProgramLowerer<'a> {
             | core::PatternKind::Binding(_)
             | core::PatternKind::Integer(_)
             | core::PatternKind::Text(_) => {}
+            core::PatternKind::TextPrefix { rest, .. } => {
+                self.collect_opaque_pattern_variants(rest, subject_ty, collected);
+            }
             core::PatternKind::Tuple(elements) => {
                 let core::Type::Tuple(field_types) = subject_ty else {
                     return;
@@ -2190,6 +2172,18 @@ impl<'a> ProgramLowerer<'a> {
                                 },
                             )?);
                         }
+                        core::ExprKind::Char(literal) => {
+                            values.push(alloc_kernel_expr(
+                                &mut exprs,
+                                KernelExpr {
+                                    span: expr.span,
+                                    layout,
+                                    kind: KernelExprKind::Char(CharLiteral {
+                                        value: literal.value,
+                                    }),
+                                },
+                            )?);
+                        }
                         core::ExprKind::Text(text) => {
                             tasks.push(Task::BuildText {
                                 span: expr.span,
@@ -2834,6 +2828,7 @@ impl<'a> ProgramLowerer<'a> {
                 | core::ExprKind::Decimal(_)
                 | core::ExprKind::BigInt(_)
                 | core::ExprKind::SuffixedInteger(_)
+                | core::ExprKind::Char(_)
                 | core::ExprKind::Reference(_) => {}
                 core::ExprKind::OptionSome { payload } => work.push((*payload, subject)),
                 core::ExprKind::Text(text) => {
@@ -3009,6 +3004,25 @@ impl<'a> ProgramLowerer<'a> {
                 };
                 InlinePipePatternKind::Text(raw.clone())
             }
+            core::PatternKind::TextPrefix { prefix, rest } => {
+                let LayoutKind::Primitive(PrimitiveType::Text) =
+                    &self.program.layouts()[layout].kind
+                else {
+                    return Err(unsupported_inline_pipe_pattern(
+                        pattern.span,
+                        "text prefix patterns require a Text subject",
+                    ));
+                };
+                InlinePipePatternKind::TextPrefix {
+                    prefix: prefix.clone(),
+                    rest: Box::new(self.lower_inline_pipe_pattern(
+                        rest,
+                        layout,
+                        inline_subjects,
+                        locals,
+                    )?),
+                }
+            }
             core::PatternKind::Tuple(elements) => {
                 let layouts = match &self.program.layouts()[layout].kind {
                     LayoutKind::Tuple(layouts) => layouts.clone(),
@@ -3743,6 +3757,7 @@ fn hir_gate_type_for_layout(
             PrimitiveType::Text => aivi_hir::BuiltinType::Text,
             PrimitiveType::Unit => aivi_hir::BuiltinType::Unit,
             PrimitiveType::Bytes => aivi_hir::BuiltinType::Bytes,
+            PrimitiveType::Char => aivi_hir::BuiltinType::Char,
             PrimitiveType::List => aivi_hir::BuiltinType::List,
             PrimitiveType::Map => aivi_hir::BuiltinType::Map,
             PrimitiveType::Set => aivi_hir::BuiltinType::Set,
@@ -3827,6 +3842,23 @@ struct CollectedOpaqueLayout {
     variants: HashMap<Box<str>, Vec<core::Type>>,
 }
 
+fn declared_sum_variant_names<'hir>(
+    hir: &'hir aivi_hir::Module,
+    item: aivi_hir::ItemId,
+    name: &str,
+) -> Option<Vec<&'hir str>> {
+    let Some(aivi_hir::Item::Type(item_ty)) = hir.items().get(item) else {
+        return None;
+    };
+    if item_ty.name.text() != name {
+        return None;
+    }
+    let aivi_hir::TypeItemBody::Sum(variants) = &item_ty.body else {
+        return None;
+    };
+    Some(variants.iter().map(|variant| variant.name.text()).collect())
+}
+
 fn record_opaque_variant(
     collected: &mut HashMap<core::Type, CollectedOpaqueLayout>,
     ty: &core::Type,
@@ -4096,6 +4128,7 @@ fn map_decode_primitive(primitive: TypingPrimitiveType) -> PrimitiveType {
         TypingPrimitiveType::Text => PrimitiveType::Text,
         TypingPrimitiveType::Unit => PrimitiveType::Unit,
         TypingPrimitiveType::Bytes => PrimitiveType::Bytes,
+        TypingPrimitiveType::Char => PrimitiveType::Char,
     }
 }
 
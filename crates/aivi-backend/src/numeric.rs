@@ -1,7 +1,7 @@
 use std::hash::{Hash, Hasher};
 
 use num_bigint::{BigInt, Sign};
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 
 #[derive(Clone, Copy, Debug, PartialOrd, serde::Serialize, serde::Deserialize)]
 pub struct RuntimeFloat(f64);
@@ -54,6 +54,8 @@ pub struct RuntimeDecimal(Decimal);
 
 impl RuntimeDecimal {
     pub(crate) const ENCODED_BYTES: usize = 20;
+    /// Largest number of fractional digits a `Decimal` can carry.
+    pub(crate) const MAX_SCALE: u32 = 28;
 
     pub fn parse_literal(raw: &str) -> Option<Self> {
         let digits = raw.strip_suffix('d')?;
@@ -80,6 +82,90 @@ impl RuntimeDecimal {
     pub(crate) fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.0.cmp(&other.0)
     }
+
+    pub(crate) fn from_i64(n: i64) -> Self {
+        Self(Decimal::from(n))
+    }
+
+    pub(crate) fn from_decimal_str(s: &str) -> Option<Self> {
+        s.trim().parse::<Decimal>().ok().map(Self)
+    }
+
+    /// Plain decimal text without the literal `d` suffix, keeping trailing zeros.
+    pub(crate) fn to_decimal_str(&self) -> Box<str> {
+        self.0.to_string().into_boxed_str()
+    }
+
+    /// `None` when the sum overflows or would have to be rounded to fit.
+    pub(crate) fn decimal_add(&self, other: &Self) -> Option<Self> {
+        let sum = self.0.checked_add(other.0)?;
+        let scale = self.0.scale().max(other.0.scale());
+        let exact = scaled_mantissa(&self.0, scale) + scaled_mantissa(&other.0, scale);
+        represents_exactly(&sum, &exact, scale).then_some(Self(sum))
+    }
+
+    /// `None` when the difference overflows or would have to be rounded to fit.
+    pub(crate) fn decimal_sub(&self, other: &Self) -> Option<Self> {
+        let difference = self.0.checked_sub(other.0)?;
+        let scale = self.0.scale().max(other.0.scale());
+        let exact = scaled_mantissa(&self.0, scale) - scaled_mantissa(&other.0, scale);
+        represents_exactly(&difference, &exact, scale).then_some(Self(difference))
+    }
+
+    /// `None` when the product overflows or would have to be rounded to fit.
+    pub(crate) fn decimal_mul(&self, other: &Self) -> Option<Self> {
+        let product = self.0.checked_mul(other.0)?;
+        let exact = BigInt::from(self.0.mantissa()) * BigInt::from(other.0.mantissa());
+        let scale = self.0.scale() + other.0.scale();
+        represents_exactly(&product, &exact, scale).then_some(Self(product))
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Divide and round the quotient to `places` fractional digits. `None` on division by
+    /// zero or overflow.
+    pub(crate) fn decimal_div(
+        &self,
+        other: &Self,
+        places: u32,
+        strategy: RoundingStrategy,
+    ) -> Option<Self> {
+        let quotient = self.0.checked_div(other.0)?;
+        Some(Self(quotient.round_dp_with_strategy(places, strategy)))
+    }
+
+    pub(crate) fn decimal_round(&self, places: u32, strategy: RoundingStrategy) -> Self {
+        Self(self.0.round_dp_with_strategy(places, strategy))
+    }
+}
+
+/// Map an `aivi.decimal` rounding-mode name onto the `rust_decimal` strategy.
+pub(crate) fn decimal_rounding_strategy(name: &str) -> Option<RoundingStrategy> {
+    match name {
+        "halfEven" => Some(RoundingStrategy::MidpointNearestEven),
+        "halfUp" => Some(RoundingStrategy::MidpointAwayFromZero),
+        "halfDown" => Some(RoundingStrategy::MidpointTowardZero),
+        "up" => Some(RoundingStrategy::AwayFromZero),
+        "down" => Some(RoundingStrategy::ToZero),
+        "ceiling" => Some(RoundingStrategy::ToPositiveInfinity),
+        "floor" => Some(RoundingStrategy::ToNegativeInfinity),
+        _ => None,
+    }
+}
+
+fn scaled_mantissa(value: &Decimal, scale: u32) -> BigInt {
+    BigInt::from(value.mantissa()) * BigInt::from(10u32).pow(scale - value.scale())
+}
+
+/// Whether `value` equals `mantissa / 10^scale` exactly, i.e. no digits were rounded away.
+fn represents_exactly(value: &Decimal, mantissa: &BigInt, scale: u32) -> bool {
+    if value.scale() <= scale {
+        scaled_mantissa(value, scale) == *mantissa
+    } else {
+        mantissa * BigInt::from(10u32).pow(value.scale() - scale) == BigInt::from(value.mantissa())
+    }
 }
 
 impl std::fmt::Display for RuntimeDecimal {
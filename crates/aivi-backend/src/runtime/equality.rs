@@ -44,6 +44,7 @@ pub(crate) fn value_matches_layout(program: &Program, value: &RuntimeValue, layo
         (LayoutKind::Primitive(PrimitiveType::Decimal), RuntimeValue::Decimal(_)) => true,
         (LayoutKind::Primitive(PrimitiveType::BigInt), RuntimeValue::BigInt(_)) => true,
         (LayoutKind::Primitive(PrimitiveType::Text), RuntimeValue::Text(_)) => true,
+        (LayoutKind::Primitive(PrimitiveType::Char), RuntimeValue::Char(_)) => true,
         (LayoutKind::Primitive(PrimitiveType::Bytes), RuntimeValue::Bytes(_)) => true,
         (LayoutKind::Primitive(PrimitiveType::Task), RuntimeValue::Task(_))
         | (LayoutKind::Primitive(PrimitiveType::Task), RuntimeValue::DbTask(_))
@@ -62,11 +63,14 @@ pub(crate) fn value_matches_layout(program: &Program, value: &RuntimeValue, layo
         (LayoutKind::List { .. }, RuntimeValue::List(_))
         | (LayoutKind::Set { .. }, RuntimeValue::Set(_)) => true,
         (LayoutKind::Map { .. }, RuntimeValue::Map(_)) => true,
+        // Like record equality, fields are matched by label: records of one type may still list
+        // their fields in different orders, for example an untyped literal compared with a value
+        // of a declared type.
         (LayoutKind::Record(expected), RuntimeValue::Record(fields)) => {
             expected.len() == fields.len()
-                && expected.iter().zip(fields.iter()).all(|(layout, field)| {
-                    layout.name.as_ref() == field.label.as_ref()
-                        && value_matches_layout(program, &field.value, layout.layout)
+                && expected.iter().all(|layout| {
+                    record_field(fields, &layout.name)
+                        .is_some_and(|value| value_matches_layout(program, value, layout.layout))
                 })
         }
         (LayoutKind::Sum(variants), RuntimeValue::Sum(value)) => variants
@@ -164,6 +168,7 @@ fn structural_eq(
         (RuntimeValue::Decimal(left), RuntimeValue::Decimal(right)) => left == right,
         (RuntimeValue::BigInt(left), RuntimeValue::BigInt(right)) => left == right,
         (RuntimeValue::Text(left), RuntimeValue::Text(right)) => left == right,
+        (RuntimeValue::Char(left), RuntimeValue::Char(right)) => left == right,
         (RuntimeValue::Bytes(left), RuntimeValue::Bytes(right)) => left == right,
         (RuntimeValue::Int(left), RuntimeValue::SuffixedInteger { raw, .. })
         | (RuntimeValue::SuffixedInteger { raw, .. }, RuntimeValue::Int(left)) => {
@@ -199,18 +204,7 @@ fn structural_eq(
             unordered_runtime_map_eq(kernel, expr, left, right)?
         }
         (RuntimeValue::Record(left), RuntimeValue::Record(right)) => {
-            if left.len() != right.len() {
-                false
-            } else {
-                for (left, right) in left.iter().zip(right.iter()) {
-                    if left.label != right.label
-                        || !structural_eq(kernel, expr, &left.value, &right.value)?
-                    {
-                        return Ok(false);
-                    }
-                }
-                true
-            }
+            runtime_record_fields_eq(kernel, expr, left, right)?
         }
         (RuntimeValue::Sum(left), RuntimeValue::Sum(right)) => {
             if left.item != right.item
@@ -292,8 +286,10 @@ fn unordered_runtime_map_eq(
         return Ok(false);
     }
     // Use O(1) key lookup on `right` to drive the comparison in O(n) rather
-    // than the previous O(n²) linear scan.  Both sides must agree on every
-    // key, and the associated values must be structurally equal.
+    // than the previous O(n²) linear scan.  Key identity ignores field order in
+    // record keys and element order in set keys, so a miss means the maps
+    // differ.  Both sides must agree on every key, and the associated values
+    // must be structurally equal.
     for (left_key, left_value) in left {
         let Some(right_value) = right.get(left_key) else {
             return Ok(false);
@@ -305,6 +301,64 @@ fn unordered_runtime_map_eq(
     Ok(true)
 }
 
+/// Records compare by label, so two records built with their fields in different orders
+/// (for example by a patch or merge) are equal when every label carries an equal value.
+fn runtime_record_fields_eq(
+    kernel: KernelId,
+    expr: KernelExprId,
+    left: &[RuntimeRecordField],
+    right: &[RuntimeRecordField],
+) -> Result<bool, EvaluationError> {
+    if left.len() != right.len() {
+        return Ok(false);
+    }
+    for (index, left_field) in left.iter().enumerate() {
+        let right_field = match right.get(index) {
+            Some(field) if field.label == left_field.label => field,
+            _ => match right.iter().find(|field| field.label == left_field.label) {
+                Some(field) => field,
+                None => return Ok(false),
+            },
+        };
+        if !structural_eq(kernel, expr, &left_field.value, &right_field.value)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Put the fields of a freshly built record into the order of its record layout, so a literal
+/// that lists the fields in another order still matches the declared type.
+fn order_record_fields(
+    program: &Program,
+    mut fields: Vec<RuntimeRecordField>,
+    layout: LayoutId,
+) -> Vec<RuntimeRecordField> {
+    let Some(LayoutKind::Record(expected)) = program.layouts().get(layout).map(|def| &def.kind)
+    else {
+        return fields;
+    };
+    if expected.len() != fields.len()
+        || expected
+            .iter()
+            .zip(fields.iter())
+            .all(|(layout, field)| layout.name == field.label)
+    {
+        return fields;
+    }
+    let mut ordered = Vec::with_capacity(fields.len());
+    for layout in expected {
+        match fields.iter().position(|field| field.label == layout.name) {
+            Some(index) => ordered.push(fields.swap_remove(index)),
+            None => {
+                ordered.append(&mut fields);
+                return ordered;
+            }
+        }
+    }
+    ordered
+}
+
 fn runtime_values_may_match(left: &RuntimeValue, right: &RuntimeValue) -> bool {
     match (left, right) {
         (RuntimeValue::Signal(left), right) => runtime_values_may_match(left, right),
@@ -316,6 +370,7 @@ fn runtime_values_may_match(left: &RuntimeValue, right: &RuntimeValue) -> bool {
         | (RuntimeValue::Decimal(_), RuntimeValue::Decimal(_))
         | (RuntimeValue::BigInt(_), RuntimeValue::BigInt(_))
         | (RuntimeValue::Text(_), RuntimeValue::Text(_))
+        | (RuntimeValue::Char(_), RuntimeValue::Char(_))
         | (RuntimeValue::Bytes(_), RuntimeValue::Bytes(_))
         | (RuntimeValue::Tuple(_), RuntimeValue::Tuple(_))
         | (RuntimeValue::List(_), RuntimeValue::List(_))
@@ -164,6 +164,19 @@ pub enum EvaluationError {
     UnsupportedNativeOnlyRuntimeOperation {
         detail: Box<str>,
     },
+    FuelExhausted {
+        kernel: KernelId,
+    },
+    AllocationBudgetExceeded {
+        kernel: KernelId,
+        budget: usize,
+    },
+    /// `source` together with the names of the items and functions being evaluated when it was
+    /// raised, innermost first. Only produced when error traces are enabled.
+    WithTrace {
+        source: Box<EvaluationError>,
+        frames: Box<[Box<str>]>,
+    },
 }
 
 impl fmt::Display for EvaluationError {
@@ -357,6 +370,20 @@ impl fmt::Display for EvaluationError {
                 "kernel {kernel} cannot compare `{left}` and `{right}` structurally in the current runtime slice"
             ),
             Self::UnsupportedNativeOnlyRuntimeOperation { detail } => f.write_str(detail),
+            Self::FuelExhausted { kernel } => {
+                write!(f, "evaluation ran out of fuel before calling kernel {kernel}")
+            }
+            Self::AllocationBudgetExceeded { kernel, budget } => write!(
+                f,
+                "evaluation allocated more than its budget of {budget} bytes in kernel {kernel}"
+            ),
+            Self::WithTrace { source, frames } => {
+                write!(f, "{source}")?;
+                for frame in frames {
+                    write!(f, "\n    in `{frame}`")?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -379,6 +406,18 @@ struct LastKernelCall {
     result_layout: LayoutId,
 }
 
+/// How an expression in a kernel's tail position finished evaluating.
+enum TailOutcome {
+    Value(RuntimeValue),
+    /// A saturated call to an item body, deferred so that the running kernel can be replaced by
+    /// the callee instead of recursing into it.
+    TailCall {
+        item: ItemId,
+        kernel: KernelId,
+        arguments: Vec<RuntimeValue>,
+    },
+}
+
 /// A lightweight frame in the evaluation trace.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct EvalFrame {
@@ -7,6 +7,21 @@ pub struct KernelEvaluator<'a> {
     eval_trace: Vec<EvalFrame>,
     last_kernel_call: Option<LastKernelCall>,
     profile: Option<KernelEvaluationProfile>,
+    /// Kernel calls left before evaluation fails with [`EvaluationError::FuelExhausted`];
+    /// `None` means unlimited.
+    fuel: Option<u64>,
+    /// Bytes that kernel results may add up to before evaluation fails with
+    /// [`EvaluationError::AllocationBudgetExceeded`]; `None` means unlimited.
+    allocation_budget: Option<usize>,
+    /// Estimated bytes of the kernel results produced by the current outermost kernel call.
+    allocated_bytes: usize,
+    /// Kernel calls currently running; the allocation count restarts when this is zero.
+    kernel_depth: usize,
+    /// Wrap errors in [`EvaluationError::WithTrace`] and record function calls in `eval_trace`.
+    error_traces: bool,
+    /// Storage for interpolation-free text literals. Identical constant literals evaluate to the
+    /// same `Arc`; text built at runtime is never added here.
+    text_literals: BTreeSet<Arc<str>>,
 }
 
 /// Sentinel `KernelId` used when applying a closure during task composition (map/chain/join).
@@ -56,6 +71,12 @@ impl<'a> KernelEvaluator<'a> {
             eval_trace: Vec::new(),
             last_kernel_call: None,
             profile: None,
+            fuel: None,
+            allocation_budget: None,
+            allocated_bytes: 0,
+            kernel_depth: 0,
+            error_traces: false,
+            text_literals: BTreeSet::new(),
         }
     }
 
@@ -77,6 +98,59 @@ impl<'a> KernelEvaluator<'a> {
         self.profile.clone()
     }
 
+    /// Allow at most `fuel` further kernel calls. Each call, including one answered from the call
+    /// cache, spends one unit; the call after the last unit fails with
+    /// [`EvaluationError::FuelExhausted`]. Embedders use this to bound untrusted or interactive
+    /// evaluation without relying on OS signals.
+    pub fn set_fuel(&mut self, fuel: u64) {
+        self.fuel = Some(fuel);
+    }
+
+    /// Fuel left for kernel calls, or `None` when evaluation is unlimited.
+    pub fn remaining_fuel(&self) -> Option<u64> {
+        self.fuel
+    }
+
+    /// Fail with [`EvaluationError::AllocationBudgetExceeded`] once the kernel results produced by
+    /// one outermost evaluation add up to more than `bytes`. Each result is charged its
+    /// [`RuntimeValue::shallow_size`], so values nested inside a result are only charged when a
+    /// kernel call of their own produced them; the elements an intrinsic builds and the parts of
+    /// constants are not. Nothing is ever given back, so this is a budget for how much one
+    /// evaluation allocates in total, not a limit on how much memory it holds at once: a loop that
+    /// builds and drops many small values spends the budget like one that keeps them. The count
+    /// restarts at zero whenever the embedder starts a new evaluation, so a long-running program
+    /// is not charged for evaluations that have already finished.
+    pub fn set_allocation_budget(&mut self, bytes: usize) {
+        self.allocation_budget = Some(bytes);
+    }
+
+    /// Estimated bytes of the kernel results produced by the latest outermost evaluation.
+    pub fn allocated_bytes(&self) -> usize {
+        self.allocated_bytes
+    }
+
+    fn charge_allocation(
+        &mut self,
+        kernel: KernelId,
+        value: &RuntimeValue,
+    ) -> Result<(), EvaluationError> {
+        let Some(budget) = self.allocation_budget else {
+            return Ok(());
+        };
+        self.allocated_bytes = self.allocated_bytes.saturating_add(value.shallow_size());
+        if self.allocated_bytes > budget {
+            return Err(EvaluationError::AllocationBudgetExceeded { kernel, budget });
+        }
+        Ok(())
+    }
+
+    /// Report errors as [`EvaluationError::WithTrace`], naming every item and function call that
+    /// was still being evaluated when the error was raised. Off by default because recording
+    /// function-call frames costs a push and pop per call.
+    pub fn set_error_traces(&mut self, enabled: bool) {
+        self.error_traces = enabled;
+    }
+
     /// Return the current evaluation trace (items visited, in entry order).
     ///
     /// Useful for error rendering: call this after an evaluation error to
@@ -85,6 +159,54 @@ impl<'a> KernelEvaluator<'a> {
         &self.eval_trace
     }
 
+    /// Attach the current trace to `error` when error traces are enabled. Errors that already
+    /// carry a trace pass through unchanged, so the innermost frame list wins.
+    fn traced_error(&self, error: EvaluationError) -> EvaluationError {
+        if !self.error_traces || matches!(error, EvaluationError::WithTrace { .. }) {
+            return error;
+        }
+        let frames = self
+            .eval_trace
+            .iter()
+            .rev()
+            .map(|frame| {
+                self.program
+                    .items()
+                    .get(frame.item)
+                    .map_or_else(|| frame.item.to_string().into(), |item| item.name.clone())
+            })
+            .collect();
+        EvaluationError::WithTrace {
+            source: Box::new(error),
+            frames,
+        }
+    }
+
+    /// Shared storage for `text` when it has no interpolations, or `None` when it must be built
+    /// from evaluated parts.
+    fn interned_text_literal(&mut self, text: &crate::TextLiteral) -> Option<Arc<str>> {
+        let literal = match text.segments.as_slice() {
+            [] => Cow::Borrowed(""),
+            [crate::TextSegment::Fragment { raw, .. }] => Cow::Borrowed(raw.as_ref()),
+            segments => {
+                let mut literal = String::new();
+                for segment in segments {
+                    match segment {
+                        crate::TextSegment::Fragment { raw, .. } => literal.push_str(raw),
+                        crate::TextSegment::Interpolation { .. } => return None,
+                    }
+                }
+                Cow::Owned(literal)
+            }
+        };
+        if let Some(shared) = self.text_literals.get(literal.as_ref()) {
+            return Some(shared.clone());
+        }
+        let shared: Arc<str> = literal.into();
+        self.text_literals.insert(shared.clone());
+        Some(shared)
+    }
+
     pub fn evaluate_kernel(
         &mut self,
         kernel_id: KernelId,
@@ -151,6 +273,22 @@ impl<'a> KernelEvaluator<'a> {
         input_subject: Option<&RuntimeValue>,
         environment: &[RuntimeValue],
         globals: &BTreeMap<ItemId, RuntimeValue>,
+    ) -> Result<(RuntimeValue, LayoutId), EvaluationError> {
+        if self.kernel_depth == 0 {
+            self.allocated_bytes = 0;
+        }
+        self.kernel_depth += 1;
+        let result = self.evaluate_kernel_call(kernel_id, input_subject, environment, globals);
+        self.kernel_depth -= 1;
+        result
+    }
+
+    fn evaluate_kernel_call(
+        &mut self,
+        kernel_id: KernelId,
+        input_subject: Option<&RuntimeValue>,
+        environment: &[RuntimeValue],
+        globals: &BTreeMap<ItemId, RuntimeValue>,
     ) -> Result<(RuntimeValue, LayoutId), EvaluationError> {
         let started_at = self.profile.as_ref().map(|_| Instant::now());
         let kernel = self
@@ -158,6 +296,11 @@ impl<'a> KernelEvaluator<'a> {
             .kernels()
             .get(kernel_id)
             .ok_or(EvaluationError::UnknownKernel { kernel: kernel_id })?;
+        if let Some(fuel) = self.fuel.as_mut() {
+            *fuel = fuel
+                .checked_sub(1)
+                .ok_or(EvaluationError::FuelExhausted { kernel: kernel_id })?;
+        }
         // Check the single-entry call cache before doing any validation or evaluation.
         if let Some((cached_result, cached_layout)) =
             self.last_kernel_call.as_ref().and_then(|last| {
@@ -192,6 +335,80 @@ impl<'a> KernelEvaluator<'a> {
             }
             (None, None) => {}
         }
+        // A tail call replaces the running kernel and environment and loops here, so deep tail
+        // recursion, including mutual recursion, runs in constant Rust stack.
+        let mut current_kernel = kernel_id;
+        let mut current_subject = input_subject;
+        let trace_depth = self.eval_trace.len();
+        let mut current_environment = Cow::Borrowed(environment);
+        let result = loop {
+            self.check_kernel_environment(current_kernel, &current_environment)?;
+            let running = &self.program.kernels()[current_kernel];
+            let inline_subjects = vec![None; running.inline_subjects.len()];
+            match self.evaluate_expr_at(
+                current_kernel,
+                running.root,
+                current_subject,
+                &current_environment,
+                &inline_subjects,
+                globals,
+                true,
+            ) {
+                Ok(TailOutcome::Value(value)) => break Ok(value),
+                Ok(TailOutcome::TailCall {
+                    item,
+                    kernel: callee,
+                    arguments,
+                }) => {
+                    if let Some(fuel) = self.fuel.as_mut() {
+                        *fuel = fuel
+                            .checked_sub(1)
+                            .ok_or(EvaluationError::FuelExhausted { kernel: callee })?;
+                    }
+                    if self.error_traces {
+                        // Traced calls keep one frame per tail call; they live on the heap, so
+                        // the Rust stack stays flat either way.
+                        self.eval_trace.push(EvalFrame {
+                            item,
+                            kernel: callee,
+                        });
+                    }
+                    current_kernel = callee;
+                    current_subject = None;
+                    current_environment = Cow::Owned(arguments);
+                }
+                Err(error) => break Err(error),
+            }
+        };
+        self.record_kernel_profile(
+            kernel_id,
+            started_at.map_or(Duration::ZERO, |started| started.elapsed()),
+            false,
+        );
+        let mut result = result?;
+        self.charge_allocation(kernel_id, &result)?;
+        self.eval_trace.truncate(trace_depth);
+        if current_kernel != kernel_id {
+            // The skipped stage coercions of the caller's tail position.
+            result = coerce_runtime_value(self.program, result, kernel.result_layout)
+                .unwrap_or_else(|value| value);
+        }
+        self.last_kernel_call = Some(LastKernelCall {
+            kernel_id,
+            input_subject: input_subject.cloned(),
+            environment: environment.to_vec().into_boxed_slice(),
+            result: result.clone(),
+            result_layout: kernel.result_layout,
+        });
+        Ok((result, kernel.result_layout))
+    }
+
+    fn check_kernel_environment(
+        &self,
+        kernel_id: KernelId,
+        environment: &[RuntimeValue],
+    ) -> Result<(), EvaluationError> {
+        let kernel = &self.program.kernels()[kernel_id];
         if environment.len() != kernel.environment.len() {
             return Err(EvaluationError::KernelEnvironmentCountMismatch {
                 kernel: kernel_id,
@@ -214,29 +431,7 @@ impl<'a> KernelEvaluator<'a> {
                 });
             }
         }
-        let inline_subjects = vec![None; kernel.inline_subjects.len()];
-        let result = self.evaluate_expr(
-            kernel_id,
-            kernel.root,
-            input_subject,
-            environment,
-            &inline_subjects,
-            globals,
-        );
-        self.record_kernel_profile(
-            kernel_id,
-            started_at.map_or(Duration::ZERO, |started| started.elapsed()),
-            false,
-        );
-        let result = result?;
-        self.last_kernel_call = Some(LastKernelCall {
-            kernel_id,
-            input_subject: input_subject.cloned(),
-            environment: environment.to_vec().into_boxed_slice(),
-            result: result.clone(),
-            result_layout: kernel.result_layout,
-        });
-        Ok((result, kernel.result_layout))
+        Ok(())
     }
 
     pub fn evaluate_item(
@@ -294,7 +489,7 @@ impl<'a> KernelEvaluator<'a> {
                     started_at.map_or(Duration::ZERO, |started| started.elapsed()),
                     false,
                 );
-                return Err(e);
+                return Err(self.traced_error(e));
             }
         };
         let result = if matches!(item_decl.kind, crate::ItemKind::Signal(_)) {
@@ -339,6 +534,34 @@ impl<'a> KernelEvaluator<'a> {
         inline_subjects: &[Option<RuntimeValue>],
         globals: &BTreeMap<ItemId, RuntimeValue>,
     ) -> Result<RuntimeValue, EvaluationError> {
+        match self.evaluate_expr_at(
+            kernel_id,
+            root,
+            input_subject,
+            environment,
+            inline_subjects,
+            globals,
+            false,
+        )? {
+            TailOutcome::Value(value) => Ok(value),
+            TailOutcome::TailCall { .. } => {
+                unreachable!("calls are only deferred in tail position")
+            }
+        }
+    }
+
+    /// Evaluates `root`; when `tail` is set, `root` is the kernel's result, and a saturated call to
+    /// an item body is returned as [`TailOutcome::TailCall`] instead of run.
+    fn evaluate_expr_at(
+        &mut self,
+        kernel_id: KernelId,
+        root: KernelExprId,
+        input_subject: Option<&RuntimeValue>,
+        environment: &[RuntimeValue],
+        inline_subjects: &[Option<RuntimeValue>],
+        globals: &BTreeMap<ItemId, RuntimeValue>,
+        tail: bool,
+    ) -> Result<TailOutcome, EvaluationError> {
         enum Task {
             Visit(KernelExprId),
             BuildOptionSome,
@@ -359,6 +582,7 @@ impl<'a> KernelEvaluator<'a> {
                 len: usize,
             },
             BuildRecord {
+                layout: LayoutId,
                 labels: Vec<Box<str>>,
             },
             BuildProjection {
@@ -497,6 +721,9 @@ impl<'a> KernelEvaluator<'a> {
                                 })?;
                             values.push(value);
                         }
+                        KernelExprKind::Char(literal) => {
+                            values.push(RuntimeValue::Char(literal.value));
+                        }
                         KernelExprKind::Decimal(decimal) => {
                             let value = RuntimeDecimal::parse_literal(decimal.raw.as_ref())
                                 .map(RuntimeValue::Decimal)
@@ -524,6 +751,10 @@ impl<'a> KernelEvaluator<'a> {
                             });
                         }
                         KernelExprKind::Text(text) => {
+                            if let Some(literal) = self.interned_text_literal(text) {
+                                values.push(RuntimeValue::Text(literal));
+                                continue;
+                            }
                             tasks.push(Task::BuildText {
                                 expr: expr_id,
                                 fragments: text
@@ -576,6 +807,7 @@ impl<'a> KernelEvaluator<'a> {
                         }
                         KernelExprKind::Record(fields) => {
                             tasks.push(Task::BuildRecord {
+                                layout: expr.layout,
                                 labels: fields.iter().map(|field| field.label.clone()).collect(),
                             });
                             for field in fields.iter().rev() {
@@ -634,7 +866,7 @@ impl<'a> KernelEvaluator<'a> {
                                 KernelExprKind::Pipe(pipe) => pipe,
                                 _ => unreachable!(),
                             };
-                            values.push(self.evaluate_inline_pipe(
+                            match self.evaluate_inline_pipe(
                                 kernel_id,
                                 expr_id,
                                 pipe,
@@ -642,7 +874,11 @@ impl<'a> KernelEvaluator<'a> {
                                 environment,
                                 inline_subjects,
                                 globals,
-                            )?);
+                                tail && tasks.is_empty(),
+                            )? {
+                                TailOutcome::Value(value) => values.push(value),
+                                tail_call @ TailOutcome::TailCall { .. } => return Ok(tail_call),
+                            }
                         }
                     }
                 }
@@ -679,7 +915,7 @@ impl<'a> KernelEvaluator<'a> {
                             }
                         }
                     }
-                    values.push(RuntimeValue::Text(rendered.into_boxed_str()));
+                    values.push(RuntimeValue::Text(rendered.into()));
                 }
                 Task::BuildTuple { len } => {
                     let elements = drain_tail(&mut values, len);
@@ -703,16 +939,18 @@ impl<'a> KernelEvaluator<'a> {
                         .collect();
                     values.push(RuntimeValue::Map(RuntimeMap::from_entries(entries)));
                 }
-                Task::BuildRecord { labels } => {
+                Task::BuildRecord { layout, labels } => {
                     let len = labels.len();
                     let values_tail = drain_tail(&mut values, len);
-                    values.push(RuntimeValue::Record(
+                    values.push(RuntimeValue::Record(order_record_fields(
+                        self.program,
                         labels
                             .into_iter()
-                            .zip(values_tail.into_iter())
+                            .zip(values_tail)
                             .map(|(label, value)| RuntimeRecordField { label, value })
                             .collect(),
-                    ));
+                        layout,
+                    )));
                 }
                 Task::BuildProjection { expr, base, path } => {
                     let mut value = match base {
@@ -734,6 +972,9 @@ impl<'a> KernelEvaluator<'a> {
                 Task::BuildApply { expr, arguments } => {
                     let arguments = drain_tail(&mut values, arguments);
                     let callee = pop_value(&mut values);
+                    if tail && tasks.is_empty() && saturates_item_body(&callee, arguments.len()) {
+                        return Ok(self.item_tail_call(callee, arguments));
+                    }
                     let value = self.apply_callable(kernel_id, expr, callee, arguments, globals)?;
                     values.push(value);
                 }
@@ -750,7 +991,33 @@ impl<'a> KernelEvaluator<'a> {
                 }
             }
         }
-        Ok(pop_value(&mut values))
+        Ok(TailOutcome::Value(pop_value(&mut values)))
+    }
+
+    /// Defers a call already known to saturate an item body, binding `arguments` after the
+    /// callable's own bound arguments and coercing them as `apply_callable` would.
+    fn item_tail_call(&self, callee: RuntimeValue, arguments: Vec<RuntimeValue>) -> TailOutcome {
+        let RuntimeValue::Callable(RuntimeCallable::ItemBody {
+            item,
+            kernel,
+            parameters,
+            mut bound_arguments,
+        }) = strip_signal(callee)
+        else {
+            unreachable!("tail calls are only detected for item bodies");
+        };
+        for argument in arguments {
+            let expected = parameters[bound_arguments.len()];
+            bound_arguments.push(
+                coerce_runtime_value(self.program, argument, expected)
+                    .unwrap_or_else(|value| value),
+            );
+        }
+        TailOutcome::TailCall {
+            item,
+            kernel,
+            arguments: bound_arguments,
+        }
     }
 
     fn evaluate_inline_pipe(
@@ -762,18 +1029,25 @@ impl<'a> KernelEvaluator<'a> {
         environment: &[RuntimeValue],
         inline_subjects: &[Option<RuntimeValue>],
         globals: &BTreeMap<ItemId, RuntimeValue>,
-    ) -> Result<RuntimeValue, EvaluationError> {
+        tail: bool,
+    ) -> Result<TailOutcome, EvaluationError> {
         let kernel = &self.program.kernels()[kernel_id];
-        let mut current = self.evaluate_expr(
+        let mut current = match self.evaluate_expr_at(
             kernel_id,
             pipe.head,
             input_subject,
             environment,
             inline_subjects,
             globals,
-        )?;
+            tail && pipe.stages.is_empty(),
+        )? {
+            TailOutcome::Value(value) => value,
+            tail_call @ TailOutcome::TailCall { .. } => return Ok(tail_call),
+        };
         let mut pipe_subjects = inline_subjects.to_vec();
-        for stage in &pipe.stages {
+        for (index, stage) in pipe.stages.iter().enumerate() {
+            // Only the last stage's result is the pipe's result.
+            let stage_tail = tail && index + 1 == pipe.stages.len();
             let stage_found = current.clone();
             current = coerce_inline_pipe_value(self.program, current, stage.input_layout).ok_or(
                 EvaluationError::KernelResultLayoutMismatch {
@@ -790,14 +1064,18 @@ impl<'a> KernelEvaluator<'a> {
             let result = match &stage.kind {
                 InlinePipeStageKind::Transform { mode, expr } => match mode {
                     aivi_hir::PipeTransformMode::Apply | aivi_hir::PipeTransformMode::Replace => {
-                        self.evaluate_expr(
+                        match self.evaluate_expr_at(
                             kernel_id,
                             *expr,
                             input_subject,
                             environment,
                             &stage_subjects,
                             globals,
-                        )?
+                            stage_tail,
+                        )? {
+                            TailOutcome::Value(value) => value,
+                            tail_call @ TailOutcome::TailCall { .. } => return Ok(tail_call),
+                        }
                     }
                 },
                 InlinePipeStageKind::Tap { expr } => {
@@ -847,14 +1125,18 @@ impl<'a> KernelEvaluator<'a> {
                             &current,
                             &mut branch_subjects,
                         )? {
-                            matched = Some(self.evaluate_expr(
+                            match self.evaluate_expr_at(
                                 kernel_id,
                                 arm.body,
                                 input_subject,
                                 environment,
                                 &branch_subjects,
                                 globals,
-                            )?);
+                                stage_tail,
+                            )? {
+                                TailOutcome::Value(value) => matched = Some(value),
+                                tail_call @ TailOutcome::TailCall { .. } => return Ok(tail_call),
+                            }
                             break;
                         }
                     }
@@ -871,14 +1153,18 @@ impl<'a> KernelEvaluator<'a> {
                     if let (Some(slot), Some(payload)) = (branch.payload_subject, payload) {
                         branch_subjects[slot.index()] = Some(payload);
                     }
-                    self.evaluate_expr(
+                    match self.evaluate_expr_at(
                         kernel_id,
                         branch.body,
                         input_subject,
                         environment,
                         &branch_subjects,
                         globals,
-                    )?
+                        stage_tail,
+                    )? {
+                        TailOutcome::Value(value) => value,
+                        tail_call @ TailOutcome::TailCall { .. } => return Ok(tail_call),
+                    }
                 }
                 InlinePipeStageKind::FanOut { map_expr } => {
                     let elements = match current {
@@ -920,7 +1206,7 @@ impl<'a> KernelEvaluator<'a> {
                 pipe_subjects[slot.index()] = Some(current.clone());
             }
         }
-        Ok(current)
+        Ok(TailOutcome::Value(current))
     }
 
     fn select_truthy_falsy_branch<'b>(
@@ -979,6 +1265,22 @@ impl<'a> KernelEvaluator<'a> {
             InlinePipePatternKind::Text(raw) => {
                 Ok(matches!(value, RuntimeValue::Text(found) if found.as_ref() == raw.as_ref()))
             }
+            InlinePipePatternKind::TextPrefix { prefix, rest } => {
+                let RuntimeValue::Text(found) = value else {
+                    return Ok(false);
+                };
+                let Some(suffix) = found.strip_prefix(prefix.as_ref()) else {
+                    return Ok(false);
+                };
+                self.match_inline_pipe_pattern(
+                    kernel_id,
+                    expr_id,
+                    kernel,
+                    rest,
+                    &RuntimeValue::Text(suffix.into()),
+                    inline_subjects,
+                )
+            }
             InlinePipePatternKind::Tuple(elements) => {
                 let RuntimeValue::Tuple(values) = value else {
                     return Ok(false);
@@ -1180,7 +1482,19 @@ impl<'a> KernelEvaluator<'a> {
                 }
                 let mut remaining = bound_arguments.split_off(parameters.len());
                 remaining.extend(remaining_arguments);
-                let result = self.evaluate_kernel(kernel, None, &bound_arguments, globals)?;
+                let result = if self.error_traces {
+                    // Failed item evaluations leave their frames behind, so unwind by depth
+                    // rather than popping a single frame.
+                    let depth = self.eval_trace.len();
+                    self.eval_trace.push(EvalFrame { item, kernel });
+                    let result = self
+                        .evaluate_kernel(kernel, None, &bound_arguments, globals)
+                        .map_err(|error| self.traced_error(error));
+                    self.eval_trace.truncate(depth);
+                    result?
+                } else {
+                    self.evaluate_kernel(kernel, None, &bound_arguments, globals)?
+                };
                 if remaining.is_empty() {
                     Ok(result)
                 } else {
@@ -1740,6 +2054,22 @@ impl<'a> KernelEvaluator<'a> {
             {
                 ordering_rank(&left.variant_name).cmp(&ordering_rank(&right.variant_name))
             }
+            (BuiltinOrdSubject::Structural, left, right) => {
+                match self.structural_compare(&left, &right) {
+                    Some(ordering) => ordering,
+                    None => {
+                        return Err(EvaluationError::UnsupportedBuiltinClassMember {
+                            kernel: kernel_id,
+                            expr,
+                            intrinsic: BuiltinClassMemberIntrinsic::Compare {
+                                subject,
+                                ordering_item,
+                            },
+                            reason: "structural compare received values without a derivable ordering",
+                        });
+                    }
+                }
+            }
             _ => {
                 return Err(EvaluationError::UnsupportedBuiltinClassMember {
                     kernel: kernel_id,
@@ -1755,6 +2085,127 @@ impl<'a> KernelEvaluator<'a> {
         Ok(ordering_value(ordering_item, ordering))
     }
 
+    /// Orders two values for compiler-derived `Ord`: constructors rank by declaration order, then
+    /// constructor payloads, tuple elements, and record fields compare left to right.
+    fn structural_compare(
+        &self,
+        left: &RuntimeValue,
+        right: &RuntimeValue,
+    ) -> Option<std::cmp::Ordering> {
+        match (left, right) {
+            (RuntimeValue::Signal(left), right) | (right, RuntimeValue::Signal(left)) => {
+                self.structural_compare(left, right)
+            }
+            (RuntimeValue::Unit, RuntimeValue::Unit) => Some(std::cmp::Ordering::Equal),
+            (RuntimeValue::Int(left), RuntimeValue::Int(right)) => Some(left.cmp(right)),
+            (RuntimeValue::Float(left), RuntimeValue::Float(right)) => left.partial_cmp(right),
+            (RuntimeValue::Decimal(left), RuntimeValue::Decimal(right)) => Some(left.cmp(right)),
+            (RuntimeValue::BigInt(left), RuntimeValue::BigInt(right)) => Some(left.cmp(right)),
+            (RuntimeValue::Bool(left), RuntimeValue::Bool(right)) => Some(left.cmp(right)),
+            (RuntimeValue::Text(left), RuntimeValue::Text(right)) => {
+                Some(left.as_ref().cmp(right.as_ref()))
+            }
+            (RuntimeValue::Tuple(left), RuntimeValue::Tuple(right))
+                if left.len() == right.len() =>
+            {
+                self.structural_compare_all(left.iter().zip(right.iter()))
+            }
+            (RuntimeValue::Record(left), RuntimeValue::Record(right))
+                if left.len() == right.len() =>
+            {
+                let pairs = self
+                    .declared_record_labels(left)?
+                    .map(|label| {
+                        Some((record_field(left, label)?, record_field(right, label)?))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                self.structural_compare_all(pairs.into_iter())
+            }
+            (RuntimeValue::Sum(left), RuntimeValue::Sum(right))
+                if left.type_name == right.type_name =>
+            {
+                if left.type_name.as_ref() == "Ordering" {
+                    return Some(
+                        ordering_rank(&left.variant_name).cmp(&ordering_rank(&right.variant_name)),
+                    );
+                }
+                let rank = self
+                    .declared_variant_rank(left.item, &left.type_name, &left.variant_name)?
+                    .cmp(&self.declared_variant_rank(
+                        right.item,
+                        &right.type_name,
+                        &right.variant_name,
+                    )?);
+                if rank.is_ne() || left.fields.len() != right.fields.len() {
+                    return Some(rank);
+                }
+                self.structural_compare_all(left.fields.iter().zip(right.fields.iter()))
+            }
+            _ => None,
+        }
+    }
+
+    fn structural_compare_all<'v>(
+        &self,
+        pairs: impl Iterator<Item = (&'v RuntimeValue, &'v RuntimeValue)>,
+    ) -> Option<std::cmp::Ordering> {
+        for (left, right) in pairs {
+            let ordering = self.structural_compare(left, right)?;
+            if ordering.is_ne() {
+                return Some(ordering);
+            }
+        }
+        Some(std::cmp::Ordering::Equal)
+    }
+
+    /// Records compare field by field in the order their type declares, which is the order of the
+    /// first record layout with the same labels: backend lowering lays out declared types before
+    /// the record literals that use them, and literals written against a declared type are built
+    /// in its order.
+    fn declared_record_labels<'r>(
+        &'r self,
+        fields: &'r [RuntimeRecordField],
+    ) -> Option<impl Iterator<Item = &'r str>> {
+        self.program
+            .layouts()
+            .iter()
+            .find_map(|(_, layout)| match &layout.kind {
+                LayoutKind::Record(declared)
+                    if declared.len() == fields.len()
+                        && declared
+                            .iter()
+                            .all(|declared| record_field(fields, &declared.name).is_some()) =>
+                {
+                    Some(declared)
+                }
+                _ => None,
+            })
+            .map(|declared| declared.iter().map(|field| field.name.as_ref()))
+    }
+
+    /// Backend lowering keeps same-module sum layouts in declaration order.
+    fn declared_variant_rank(
+        &self,
+        item: HirItemId,
+        type_name: &str,
+        variant_name: &str,
+    ) -> Option<usize> {
+        self.program
+            .layouts()
+            .iter()
+            .find_map(|(_, layout)| match &layout.kind {
+                LayoutKind::Opaque {
+                    item: Some(layout_item),
+                    name,
+                    variants,
+                    ..
+                } if *layout_item == item && name.as_ref() == type_name => variants
+                    .iter()
+                    .position(|variant| variant.name.as_ref() == variant_name),
+                _ => None,
+            })
+    }
+
     fn append_builtin_carrier(
         &self,
         kernel_id: KernelId,
@@ -1767,7 +2218,7 @@ impl<'a> KernelEvaluator<'a> {
         match (carrier, strip_signal(left), strip_signal(right)) {
             (BuiltinAppendCarrier::Text, RuntimeValue::Text(left), RuntimeValue::Text(right)) => {
                 Ok(RuntimeValue::Text(
-                    format!("{}{}", left.as_ref(), right.as_ref()).into_boxed_str(),
+                    format!("{}{}", left.as_ref(), right.as_ref()).into(),
                 ))
             }
             (
@@ -2598,7 +3049,7 @@ impl<'a> KernelEvaluator<'a> {
         }
     }
 
-    fn apply_unary(
+    pub(crate) fn apply_unary(
         &self,
         kernel_id: KernelId,
         expr: KernelExprId,
@@ -2617,7 +3068,7 @@ impl<'a> KernelEvaluator<'a> {
         }
     }
 
-    fn apply_binary(
+    pub(crate) fn apply_binary(
         &self,
         kernel_id: KernelId,
         expr: KernelExprId,
@@ -3013,6 +3464,22 @@ fn runtime_i64_like_value(value: i64, preserved_suffix: Option<Box<str>>) -> Run
     }
 }
 
+/// Whether applying `arguments` more values to `callee` exactly saturates an item body.
+fn saturates_item_body(callee: &RuntimeValue, arguments: usize) -> bool {
+    let callee = match callee {
+        RuntimeValue::Signal(inner) => inner.as_ref(),
+        other => other,
+    };
+    matches!(
+        callee,
+        RuntimeValue::Callable(RuntimeCallable::ItemBody {
+            parameters,
+            bound_arguments,
+            ..
+        }) if bound_arguments.len() + arguments == parameters.len()
+    )
+}
+
 fn map_builtin(term: BuiltinTerm) -> RuntimeValue {
     match term {
         BuiltinTerm::True => RuntimeValue::Bool(true),
@@ -3111,6 +3578,10 @@ fn intrinsic_value_arity(value: IntrinsicValue) -> usize {
         IntrinsicValue::FsRename => 2,
         IntrinsicValue::FsCopy => 2,
         IntrinsicValue::FsDeleteDir => 1,
+        IntrinsicValue::FsOpen => 2,
+        IntrinsicValue::FsReadLine => 1,
+        IntrinsicValue::FsWrite => 2,
+        IntrinsicValue::FsClose => 1,
         IntrinsicValue::PathParent => 1,
         IntrinsicValue::PathFilename => 1,
         IntrinsicValue::PathStem => 1,
@@ -3126,12 +3597,37 @@ fn intrinsic_value_arity(value: IntrinsicValue) -> usize {
         IntrinsicValue::BytesToText => 1,
         IntrinsicValue::BytesRepeat => 2,
         IntrinsicValue::BytesEmpty => 0,
+        IntrinsicValue::BytesFromList => 1,
+        IntrinsicValue::BytesToList => 1,
+        IntrinsicValue::BytesFromBase64 => 1,
+        IntrinsicValue::BytesToBase64 => 1,
+        IntrinsicValue::BytesFromHex => 1,
+        IntrinsicValue::BytesToHex => 1,
+        IntrinsicValue::CryptoHmacSha256
+        | IntrinsicValue::CryptoHmacSha512
+        | IntrinsicValue::CryptoConstantTimeEquals
+        | IntrinsicValue::CryptoEd25519Sign => 2,
+        IntrinsicValue::CryptoEd25519Verify => 3,
+        IntrinsicValue::CharToInt
+        | IntrinsicValue::CharFromInt
+        | IntrinsicValue::CharIsDigit
+        | IntrinsicValue::CharIsAlpha
+        | IntrinsicValue::CharToUpper => 1,
+        IntrinsicValue::FnArity => 1,
+        IntrinsicValue::FnCanApply | IntrinsicValue::FnIsFullyApplied => 2,
         IntrinsicValue::JsonValidate => 1,
         IntrinsicValue::JsonGet => 2,
         IntrinsicValue::JsonAt => 2,
         IntrinsicValue::JsonKeys => 1,
         IntrinsicValue::JsonPretty => 1,
         IntrinsicValue::JsonMinify => 1,
+        IntrinsicValue::JsonParseInto => 2,
+        IntrinsicValue::JsonParseStreamInto => 2,
+        IntrinsicValue::JsonStringifyValue => 1,
+        IntrinsicValue::JsonEncode => 1,
+        IntrinsicValue::TomlParseInto => 2,
+        IntrinsicValue::TomlStringifyValue => 1,
+        IntrinsicValue::TomlEncodeValue => 1,
         IntrinsicValue::XdgDataHome => 0,
         IntrinsicValue::XdgConfigHome => 0,
         IntrinsicValue::XdgCacheHome => 0,
@@ -3144,6 +3640,9 @@ fn intrinsic_value_arity(value: IntrinsicValue) -> usize {
         | IntrinsicValue::TextByteLen
         | IntrinsicValue::TextToUpper
         | IntrinsicValue::TextToLower
+        | IntrinsicValue::TextCaseFold
+        | IntrinsicValue::TextReverse
+        | IntrinsicValue::TextGraphemeLength
         | IntrinsicValue::TextTrim
         | IntrinsicValue::TextTrimStart
         | IntrinsicValue::TextTrimEnd
@@ -3159,6 +3658,11 @@ fn intrinsic_value_arity(value: IntrinsicValue) -> usize {
         | IntrinsicValue::TextEndsWith
         | IntrinsicValue::TextSplit
         | IntrinsicValue::TextRepeat
+        | IntrinsicValue::TextWrap
+        | IntrinsicValue::TextTruncate
+        | IntrinsicValue::TextRenderTemplate
+        | IntrinsicValue::TextRenderLenient
+        | IntrinsicValue::TextRenderPositional
         | IntrinsicValue::I18nTranslatePlural => 2,
         IntrinsicValue::TextSlice
         | IntrinsicValue::TextReplace
@@ -3177,13 +3681,35 @@ fn intrinsic_value_arity(value: IntrinsicValue) -> usize {
         | IntrinsicValue::FloatTrunc
         | IntrinsicValue::FloatFrac => 1,
         IntrinsicValue::FloatAtan2 | IntrinsicValue::FloatPow | IntrinsicValue::FloatHypot => 2,
+        // Math intrinsics
+        IntrinsicValue::MathFftMagnitude | IntrinsicValue::MathFftSpectrum => 1,
+        IntrinsicValue::MatrixLuSolve => 2,
+        IntrinsicValue::MatrixLuDeterminant
+        | IntrinsicValue::MatrixLuInverse
+        | IntrinsicValue::MatrixJacobiSvd
+        | IntrinsicValue::MatrixJacobiEigenvalues => 1,
+        IntrinsicValue::GraphShortestPath => 3,
+        IntrinsicValue::GraphDistances => 2,
         // Time intrinsics
         IntrinsicValue::TimeNowMs
         | IntrinsicValue::TimeMonotonicMs
-        | IntrinsicValue::RandomFloat => 0,
+        | IntrinsicValue::RandomFloat
+        | IntrinsicValue::CryptoEd25519Keypair
+        | IntrinsicValue::ProcessArgs => 0,
+        IntrinsicValue::TimeSleep => 1,
         IntrinsicValue::TimeFormat | IntrinsicValue::TimeParse => 2,
+        // Clipboard intrinsics
+        IntrinsicValue::ClipboardRead => 0,
+        IntrinsicValue::ClipboardWrite => 1,
         // Env intrinsics
         IntrinsicValue::EnvGet | IntrinsicValue::EnvList => 1,
+        // Process intrinsics
+        IntrinsicValue::ProcessExit => 1,
+        IntrinsicValue::TestMatchesSnapshot => 2,
+        IntrinsicValue::TestAdvanceClock | IntrinsicValue::TestSetClock => 1,
+        IntrinsicValue::TaskAttempt => 1,
+        IntrinsicValue::TaskFinally => 2,
+        IntrinsicValue::TaskParallel => 1,
         // Log intrinsics
         IntrinsicValue::LogEmit => 2,
         IntrinsicValue::LogEmitContext => 3,
@@ -3198,7 +3724,9 @@ fn intrinsic_value_arity(value: IntrinsicValue) -> usize {
         IntrinsicValue::RegexIsMatch
         | IntrinsicValue::RegexFind
         | IntrinsicValue::RegexFindText
-        | IntrinsicValue::RegexFindAll => 2,
+        | IntrinsicValue::RegexFindAll
+        | IntrinsicValue::RegexCaptures
+        | IntrinsicValue::RegexCapturesAll => 2,
         IntrinsicValue::RegexReplace | IntrinsicValue::RegexReplaceAll => 3,
         // HTTP intrinsics
         IntrinsicValue::HttpGet
@@ -3206,8 +3734,17 @@ fn intrinsic_value_arity(value: IntrinsicValue) -> usize {
         | IntrinsicValue::HttpGetStatus
         | IntrinsicValue::HttpDelete
         | IntrinsicValue::HttpHead => 1,
-        IntrinsicValue::HttpPostJson => 2,
+        IntrinsicValue::HttpPostJson
+        | IntrinsicValue::HttpRequest
+        | IntrinsicValue::HttpDownload => 2,
         IntrinsicValue::HttpPost | IntrinsicValue::HttpPut => 3,
+        IntrinsicValue::WsConnect | IntrinsicValue::WsRecv | IntrinsicValue::WsClose => 1,
+        IntrinsicValue::WsSend | IntrinsicValue::WsRecvTimeout => 2,
+        IntrinsicValue::ProcessWait
+        | IntrinsicValue::ProcessKill
+        | IntrinsicValue::ProcessReadStdout => 1,
+        IntrinsicValue::ProcessWriteStdin => 2,
+        IntrinsicValue::ProcessSpawn => 3,
         // BigInt intrinsics
         IntrinsicValue::BigIntFromInt
         | IntrinsicValue::BigIntFromText
@@ -3225,6 +3762,13 @@ fn intrinsic_value_arity(value: IntrinsicValue) -> usize {
         | IntrinsicValue::BigIntEq
         | IntrinsicValue::BigIntGt
         | IntrinsicValue::BigIntLt => 2,
+        // Decimal intrinsics
+        IntrinsicValue::DecimalFromInt
+        | IntrinsicValue::DecimalFromText
+        | IntrinsicValue::DecimalToText => 1,
+        IntrinsicValue::DecimalAdd | IntrinsicValue::DecimalSub | IntrinsicValue::DecimalMul => 2,
+        IntrinsicValue::DecimalRoundWithMode => 3,
+        IntrinsicValue::DecimalDivWithMode => 4,
         IntrinsicValue::BitNot => 1,
         IntrinsicValue::BitAnd
         | IntrinsicValue::BitOr
@@ -26,6 +26,246 @@ fn xdg_search_dirs(env_var: &str, defaults: &[&str]) -> Vec<RuntimeValue> {
     }
 }
 
+/// Encode a `Json` sum value from `aivi.data.json`. Object keys come out sorted because
+/// `serde_json::Map` is ordered; a repeated key keeps its last value.
+fn runtime_value_to_json(value: &RuntimeValue) -> Option<serde_json::Value> {
+    let RuntimeValue::Sum(sum) = value else {
+        return None;
+    };
+    match (sum.variant_name.as_ref(), sum.fields.as_slice()) {
+        ("JsonNull", []) => Some(serde_json::Value::Null),
+        ("JsonBool", [RuntimeValue::Bool(flag)]) => Some(serde_json::Value::Bool(*flag)),
+        ("JsonNumber", [RuntimeValue::Float(number)]) => {
+            serde_json::Number::from_f64(number.to_f64()).map(serde_json::Value::Number)
+        }
+        ("JsonString", [RuntimeValue::Text(text)]) => {
+            Some(serde_json::Value::String(text.to_string()))
+        }
+        ("JsonArray", [RuntimeValue::List(elements)]) => elements
+            .iter()
+            .map(runtime_value_to_json)
+            .collect::<Option<Vec<_>>>()
+            .map(serde_json::Value::Array),
+        ("JsonObject", [RuntimeValue::Record(dict)]) => {
+            let entries = dict.iter().find_map(|field| match &field.value {
+                RuntimeValue::List(entries) if field.label.as_ref() == "entries" => Some(entries),
+                _ => None,
+            })?;
+            let mut object = serde_json::Map::new();
+            for entry in entries {
+                let RuntimeValue::Record(fields) = entry else {
+                    return None;
+                };
+                let key = fields.iter().find(|field| field.label.as_ref() == "key")?;
+                let value = fields
+                    .iter()
+                    .find(|field| field.label.as_ref() == "value")?;
+                let RuntimeValue::Text(key) = &key.value else {
+                    return None;
+                };
+                object.insert(key.to_string(), runtime_value_to_json(&value.value)?);
+            }
+            Some(serde_json::Value::Object(object))
+        }
+        _ => None,
+    }
+}
+
+/// Build a `Toml` sum value from `aivi.data.toml`. Tables become `TomlTable` dictionaries with
+/// sorted keys; TOML floats without an AIVI representation (`inf`, `nan`) are rejected.
+fn runtime_toml_value(
+    value: &toml::Value,
+    item: HirItemId,
+    type_name: &str,
+) -> Result<RuntimeValue, String> {
+    let (variant_name, field) = match value {
+        toml::Value::String(text) => ("TomlString", RuntimeValue::Text(text.as_str().into())),
+        toml::Value::Integer(number) => ("TomlInt", RuntimeValue::Int(*number)),
+        toml::Value::Float(number) => (
+            "TomlFloat",
+            RuntimeValue::Float(
+                RuntimeFloat::new(*number)
+                    .ok_or_else(|| format!("float `{number}` has no AIVI representation"))?,
+            ),
+        ),
+        toml::Value::Boolean(flag) => ("TomlBool", RuntimeValue::Bool(*flag)),
+        toml::Value::Datetime(datetime) => (
+            "TomlDatetime",
+            RuntimeValue::Text(datetime.to_string().into()),
+        ),
+        toml::Value::Array(elements) => (
+            "TomlArray",
+            RuntimeValue::List(
+                elements
+                    .iter()
+                    .map(|element| runtime_toml_value(element, item, type_name))
+                    .collect::<Result<_, _>>()?,
+            ),
+        ),
+        toml::Value::Table(table) => ("TomlTable", runtime_toml_table(table, item, type_name)?),
+    };
+    Ok(RuntimeValue::Sum(RuntimeSumValue {
+        item,
+        type_name: type_name.into(),
+        variant_name: variant_name.into(),
+        fields: vec![field],
+    }))
+}
+
+fn runtime_toml_table(
+    table: &toml::Table,
+    item: HirItemId,
+    type_name: &str,
+) -> Result<RuntimeValue, String> {
+    let entries = table
+        .iter()
+        .map(|(key, value)| {
+            Ok(RuntimeValue::Record(vec![
+                RuntimeRecordField {
+                    label: "key".into(),
+                    value: RuntimeValue::Text(key.as_str().into()),
+                },
+                RuntimeRecordField {
+                    label: "value".into(),
+                    value: runtime_toml_value(value, item, type_name)?,
+                },
+            ]))
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(RuntimeValue::Record(vec![RuntimeRecordField {
+        label: "entries".into(),
+        value: RuntimeValue::List(entries),
+    }]))
+}
+
+/// Parse a TOML document into a `TomlTable`, reporting syntax errors with their line and column.
+fn parse_toml_document(
+    text: &str,
+    item: HirItemId,
+    type_name: &str,
+) -> Result<RuntimeValue, String> {
+    let table = text.parse::<toml::Table>().map_err(|error| {
+        let start = error.span().map_or(0, |span| span.start).min(text.len());
+        let before = &text[..start];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .map_or(0, |prefix| prefix.chars().count())
+            + 1;
+        let message = error.message().trim_end().replace('\n', "; ");
+        format!("line {line}, column {column}: {message}")
+    })?;
+    let fields = vec![runtime_toml_table(&table, item, type_name)?];
+    Ok(RuntimeValue::Sum(RuntimeSumValue {
+        item,
+        type_name: type_name.into(),
+        variant_name: "TomlTable".into(),
+        fields,
+    }))
+}
+
+/// Encode a `Toml` sum value from `aivi.data.toml`; the inverse of [`runtime_toml_value`].
+/// The error names the part of the value that has no TOML form.
+fn runtime_value_to_toml(value: &RuntimeValue) -> Result<toml::Value, String> {
+    let unrepresentable = || format!("`{value}` is not a `Toml` value");
+    let RuntimeValue::Sum(sum) = value else {
+        return Err(unrepresentable());
+    };
+    match (sum.variant_name.as_ref(), sum.fields.as_slice()) {
+        ("TomlString", [RuntimeValue::Text(text)]) => Ok(toml::Value::String(text.to_string())),
+        ("TomlInt", [RuntimeValue::Int(number)]) => Ok(toml::Value::Integer(*number)),
+        ("TomlFloat", [RuntimeValue::Float(number)]) => Ok(toml::Value::Float(number.to_f64())),
+        ("TomlBool", [RuntimeValue::Bool(flag)]) => Ok(toml::Value::Boolean(*flag)),
+        ("TomlDatetime", [RuntimeValue::Text(text)]) => text
+            .parse()
+            .map(toml::Value::Datetime)
+            .map_err(|_| format!("`{text}` is not a TOML date-time")),
+        ("TomlArray", [RuntimeValue::List(elements)]) => elements
+            .iter()
+            .map(runtime_value_to_toml)
+            .collect::<Result<Vec<_>, _>>()
+            .map(toml::Value::Array),
+        ("TomlTable", [RuntimeValue::Record(dict)]) => {
+            let entries = dict
+                .iter()
+                .find_map(|field| match &field.value {
+                    RuntimeValue::List(entries) if field.label.as_ref() == "entries" => {
+                        Some(entries)
+                    }
+                    _ => None,
+                })
+                .ok_or_else(unrepresentable)?;
+            let mut table = toml::Table::new();
+            for entry in entries {
+                let RuntimeValue::Record(fields) = entry else {
+                    return Err(unrepresentable());
+                };
+                let field = |label: &str| {
+                    fields
+                        .iter()
+                        .find(|field| field.label.as_ref() == label)
+                        .ok_or_else(unrepresentable)
+                };
+                let RuntimeValue::Text(key) = &field("key")?.value else {
+                    return Err(unrepresentable());
+                };
+                table.insert(
+                    key.to_string(),
+                    runtime_value_to_toml(&field("value")?.value)?,
+                );
+            }
+            Ok(toml::Value::Table(table))
+        }
+        _ => Err(unrepresentable()),
+    }
+}
+
+/// Write a `Toml` value as a document. Only a `TomlTable` has a document form.
+fn encode_toml_document(value: &RuntimeValue) -> Result<String, String> {
+    match runtime_value_to_toml(value)? {
+        toml::Value::Table(table) => toml::to_string(&table).map_err(|error| error.to_string()),
+        _ => Err("only a `TomlTable` can be written as a TOML document".to_owned()),
+    }
+}
+
+/// Encode a plain data value for `aivi.data.json.encode`: records become objects, lists, sets,
+/// and tuples become arrays, `Unit` and `None` become `null`, and constructors without fields
+/// become their name. `Json` values keep the meaning they have for `stringifyValue`.
+fn runtime_data_to_json(value: &RuntimeValue) -> Option<serde_json::Value> {
+    let array = |elements: &[RuntimeValue]| {
+        elements
+            .iter()
+            .map(runtime_data_to_json)
+            .collect::<Option<Vec<_>>>()
+            .map(serde_json::Value::Array)
+    };
+    match value {
+        RuntimeValue::Unit | RuntimeValue::OptionNone => Some(serde_json::Value::Null),
+        RuntimeValue::Bool(flag) => Some(serde_json::Value::Bool(*flag)),
+        RuntimeValue::Int(number) => Some(serde_json::Value::from(*number)),
+        RuntimeValue::Float(number) => {
+            serde_json::Number::from_f64(number.to_f64()).map(serde_json::Value::Number)
+        }
+        RuntimeValue::Text(text) => Some(serde_json::Value::String(text.to_string())),
+        RuntimeValue::Tuple(elements)
+        | RuntimeValue::List(elements)
+        | RuntimeValue::Set(elements) => array(elements),
+        RuntimeValue::Record(fields) => fields
+            .iter()
+            .map(|field| Some((field.label.to_string(), runtime_data_to_json(&field.value)?)))
+            .collect::<Option<serde_json::Map<_, _>>>()
+            .map(serde_json::Value::Object),
+        RuntimeValue::OptionSome(inner) => runtime_data_to_json(inner),
+        RuntimeValue::Sum(sum) => runtime_value_to_json(value).or_else(|| {
+            sum.fields
+                .is_empty()
+                .then(|| serde_json::Value::String(sum.variant_name.to_string()))
+        }),
+        _ => None,
+    }
+}
+
 fn evaluate_intrinsic_value(
     kernel: KernelId,
     expr: KernelExprId,
@@ -271,6 +511,24 @@ fn evaluate_intrinsic_value(
                 path: expect_intrinsic_text(kernel, expr, value, 0, path)?,
             }))
         }
+        (IntrinsicValue::FsOpen, [path, mode]) => Ok(RuntimeValue::Task(RuntimeTaskPlan::FsOpen {
+            path: expect_intrinsic_text(kernel, expr, value, 0, path)?,
+            mode: expect_intrinsic_text(kernel, expr, value, 1, mode)?,
+        })),
+        (IntrinsicValue::FsReadLine, [handle]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::FsReadLine {
+                handle: expect_intrinsic_handle_id(kernel, expr, value, 0, handle)?,
+            }))
+        }
+        (IntrinsicValue::FsWrite, [handle, text]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::FsWrite {
+                handle: expect_intrinsic_handle_id(kernel, expr, value, 0, handle)?,
+                text: expect_intrinsic_text(kernel, expr, value, 1, text)?,
+            }))
+        }
+        (IntrinsicValue::FsClose, [handle]) => Ok(RuntimeValue::Task(RuntimeTaskPlan::FsClose {
+            handle: expect_intrinsic_handle_id(kernel, expr, value, 0, handle)?,
+        })),
         (IntrinsicValue::PathParent, [path]) => {
             let s = expect_intrinsic_text(kernel, expr, value, 0, path)?;
             let p = std::path::Path::new(&*s);
@@ -352,9 +610,8 @@ fn evaluate_intrinsic_value(
             let start = expect_intrinsic_i64(kernel, expr, value, 0, from)?;
             let end = expect_intrinsic_i64(kernel, expr, value, 1, to)?;
             let bytes = expect_intrinsic_bytes(kernel, expr, value, 2, b)?;
-            let start = (start as usize).min(bytes.len());
-            let end = (end as usize).min(bytes.len());
-            let end = end.max(start);
+            let start = (start.max(0) as usize).min(bytes.len());
+            let end = (end.max(0) as usize).min(bytes.len()).max(start);
             Ok(RuntimeValue::Bytes(bytes[start..end].into()))
         }
         (IntrinsicValue::BytesAppend, [a, b]) => {
@@ -382,6 +639,148 @@ fn evaluate_intrinsic_value(
             let n = (n.max(0)) as usize;
             Ok(RuntimeValue::Bytes(vec![byte; n].into()))
         }
+        (IntrinsicValue::BytesFromList, [list]) => {
+            let values = expect_intrinsic_list(kernel, expr, value, 0, list)?;
+            let mut bytes = Vec::with_capacity(values.len());
+            for entry in values.iter() {
+                let byte = expect_intrinsic_i64(kernel, expr, value, 0, entry)?;
+                let Ok(byte) = u8::try_from(byte) else {
+                    return Err(invalid_intrinsic_argument(
+                        kernel,
+                        expr,
+                        value,
+                        0,
+                        strip_signal(entry.clone()),
+                    ));
+                };
+                bytes.push(byte);
+            }
+            Ok(RuntimeValue::Bytes(bytes.into()))
+        }
+        (IntrinsicValue::BytesToList, [b]) => {
+            let bytes = expect_intrinsic_bytes(kernel, expr, value, 0, b)?;
+            Ok(RuntimeValue::List(
+                bytes
+                    .iter()
+                    .map(|&byte| RuntimeValue::Int(byte as i64))
+                    .collect(),
+            ))
+        }
+        (IntrinsicValue::BytesFromBase64, [t]) => {
+            let text = expect_intrinsic_text(kernel, expr, value, 0, t)?;
+            Ok(
+                base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &*text)
+                    .ok()
+                    .map(|bytes| {
+                        RuntimeValue::OptionSome(Box::new(RuntimeValue::Bytes(bytes.into())))
+                    })
+                    .unwrap_or(RuntimeValue::OptionNone),
+            )
+        }
+        (IntrinsicValue::BytesToBase64, [b]) => {
+            let bytes = expect_intrinsic_bytes(kernel, expr, value, 0, b)?;
+            Ok(RuntimeValue::Text(
+                base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes).into(),
+            ))
+        }
+        (IntrinsicValue::BytesFromHex, [t]) => {
+            let text = expect_intrinsic_text(kernel, expr, value, 0, t)?;
+            Ok(decode_hex(&text)
+                .map(|bytes| RuntimeValue::OptionSome(Box::new(RuntimeValue::Bytes(bytes.into()))))
+                .unwrap_or(RuntimeValue::OptionNone))
+        }
+        (IntrinsicValue::BytesToHex, [b]) => {
+            let bytes = expect_intrinsic_bytes(kernel, expr, value, 0, b)?;
+            let mut text = String::with_capacity(bytes.len() * 2);
+            for byte in bytes.iter() {
+                text.push_str(&format!("{byte:02x}"));
+            }
+            Ok(RuntimeValue::Text(text.into()))
+        }
+        (IntrinsicValue::CryptoHmacSha256, [key, message]) => {
+            let key = expect_intrinsic_bytes(kernel, expr, value, 0, key)?;
+            let message = expect_intrinsic_bytes(kernel, expr, value, 1, message)?;
+            Ok(RuntimeValue::Bytes(
+                hmac_digest::<hmac::Hmac<sha2::Sha256>>(&key, &message),
+            ))
+        }
+        (IntrinsicValue::CryptoHmacSha512, [key, message]) => {
+            let key = expect_intrinsic_bytes(kernel, expr, value, 0, key)?;
+            let message = expect_intrinsic_bytes(kernel, expr, value, 1, message)?;
+            Ok(RuntimeValue::Bytes(
+                hmac_digest::<hmac::Hmac<sha2::Sha512>>(&key, &message),
+            ))
+        }
+        (IntrinsicValue::CryptoConstantTimeEquals, [left, right]) => {
+            let left = expect_intrinsic_bytes(kernel, expr, value, 0, left)?;
+            let right = expect_intrinsic_bytes(kernel, expr, value, 1, right)?;
+            Ok(RuntimeValue::Bool(constant_time_equals(&left, &right)))
+        }
+        (IntrinsicValue::CryptoEd25519Keypair, []) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::CryptoEd25519Keypair))
+        }
+        (IntrinsicValue::CryptoEd25519Sign, [private_key, message]) => {
+            let seed = expect_intrinsic_bytes(kernel, expr, value, 0, private_key)?;
+            let message = expect_intrinsic_bytes(kernel, expr, value, 1, message)?;
+            let Ok(pair) = ring::signature::Ed25519KeyPair::from_seed_unchecked(&seed) else {
+                return Err(invalid_intrinsic_argument(
+                    kernel,
+                    expr,
+                    value,
+                    0,
+                    strip_signal(private_key.clone()),
+                ));
+            };
+            Ok(RuntimeValue::Bytes(pair.sign(&message).as_ref().into()))
+        }
+        (IntrinsicValue::CryptoEd25519Verify, [public_key, message, signature]) => {
+            let key = expect_intrinsic_bytes(kernel, expr, value, 0, public_key)?;
+            let message = expect_intrinsic_bytes(kernel, expr, value, 1, message)?;
+            let signature = expect_intrinsic_bytes(kernel, expr, value, 2, signature)?;
+            // A wrong-length signature is simply not valid, but a wrong-length key is a bug in
+            // the caller.
+            if key.len() != ED25519_PUBLIC_KEY_LEN {
+                return Err(invalid_intrinsic_argument(
+                    kernel,
+                    expr,
+                    value,
+                    0,
+                    strip_signal(public_key.clone()),
+                ));
+            }
+            let key = ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, &key);
+            Ok(RuntimeValue::Bool(key.verify(&message, &signature).is_ok()))
+        }
+        (IntrinsicValue::CharToInt, [c]) => {
+            let c = expect_intrinsic_char(kernel, expr, value, 0, c)?;
+            Ok(RuntimeValue::Int(i64::from(u32::from(c))))
+        }
+        (IntrinsicValue::CharFromInt, [code]) => {
+            let code = expect_intrinsic_i64(kernel, expr, value, 0, code)?;
+            // Surrogates and values past U+10FFFF are not scalar values.
+            Ok(u32::try_from(code)
+                .ok()
+                .and_then(char::from_u32)
+                .map(|c| RuntimeValue::OptionSome(Box::new(RuntimeValue::Char(c))))
+                .unwrap_or(RuntimeValue::OptionNone))
+        }
+        (IntrinsicValue::CharIsDigit, [c]) => {
+            let c = expect_intrinsic_char(kernel, expr, value, 0, c)?;
+            Ok(RuntimeValue::Bool(c.is_ascii_digit()))
+        }
+        (IntrinsicValue::CharIsAlpha, [c]) => {
+            let c = expect_intrinsic_char(kernel, expr, value, 0, c)?;
+            Ok(RuntimeValue::Bool(c.is_alphabetic()))
+        }
+        (IntrinsicValue::CharToUpper, [c]) => {
+            let c = expect_intrinsic_char(kernel, expr, value, 0, c)?;
+            // Characters that uppercase to several characters, such as `ß`, stay unchanged.
+            let mut upper = c.to_uppercase();
+            Ok(RuntimeValue::Char(match (upper.next(), upper.next()) {
+                (Some(single), None) => single,
+                _ => c,
+            }))
+        }
         (IntrinsicValue::JsonValidate, [json]) => {
             let text = expect_intrinsic_text(kernel, expr, value, 0, json)?;
             Ok(RuntimeValue::Task(RuntimeTaskPlan::JsonValidate {
@@ -420,6 +819,78 @@ fn evaluate_intrinsic_value(
                 json: text,
             }))
         }
+        (IntrinsicValue::JsonParseInto, [target, json]) => {
+            let (item, type_name) = expect_intrinsic_sum_type(kernel, expr, value, 0, target)?;
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::JsonParseInto {
+                json: expect_intrinsic_text(kernel, expr, value, 1, json)?,
+                item,
+                type_name,
+            }))
+        }
+        (IntrinsicValue::JsonParseStreamInto, [target, json]) => {
+            let (item, type_name) = expect_intrinsic_sum_type(kernel, expr, value, 0, target)?;
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::JsonParseStreamInto {
+                json: expect_intrinsic_text(kernel, expr, value, 1, json)?,
+                item,
+                type_name,
+            }))
+        }
+        (IntrinsicValue::FnArity, [function]) => {
+            let function = strip_signal(function.clone());
+            match runtime_value_arity(&function) {
+                Some(arity) => Ok(RuntimeValue::Int(arity as i64)),
+                None => Err(invalid_intrinsic_argument(kernel, expr, value, 0, function)),
+            }
+        }
+        (IntrinsicValue::FnCanApply | IntrinsicValue::FnIsFullyApplied, [function, count]) => {
+            let count = expect_intrinsic_i64(kernel, expr, value, 1, count)?;
+            let arity = runtime_value_arity(&strip_signal(function.clone()));
+            let saturating = value == IntrinsicValue::FnIsFullyApplied;
+            Ok(RuntimeValue::Bool(arity.is_some_and(|arity| {
+                usize::try_from(count)
+                    .is_ok_and(|count| count == arity || (!saturating && count < arity))
+            })))
+        }
+        (IntrinsicValue::JsonStringifyValue, [json]) => {
+            let json = strip_signal(json.clone());
+            match runtime_value_to_json(&json) {
+                Some(encoded) => Ok(RuntimeValue::Text(encoded.to_string().into())),
+                None => Err(invalid_intrinsic_argument(kernel, expr, value, 0, json)),
+            }
+        }
+        (IntrinsicValue::JsonEncode, [data]) => {
+            let data = strip_signal(data.clone());
+            match runtime_data_to_json(&data) {
+                Some(encoded) => Ok(RuntimeValue::Text(encoded.to_string().into())),
+                None => Err(invalid_intrinsic_argument(kernel, expr, value, 0, data)),
+            }
+        }
+        (IntrinsicValue::TomlParseInto, [target, text]) => {
+            let (item, type_name) = expect_intrinsic_sum_type(kernel, expr, value, 0, target)?;
+            let text = expect_intrinsic_text(kernel, expr, value, 1, text)?;
+            Ok(match parse_toml_document(&text, item, &type_name) {
+                Ok(document) => RuntimeValue::ResultOk(Box::new(document)),
+                Err(message) => {
+                    RuntimeValue::ResultErr(Box::new(RuntimeValue::Text(message.into())))
+                }
+            })
+        }
+        (IntrinsicValue::TomlStringifyValue, [document]) => {
+            let document = strip_signal(document.clone());
+            match encode_toml_document(&document) {
+                Ok(encoded) => Ok(RuntimeValue::Text(encoded.into())),
+                Err(_) => Err(invalid_intrinsic_argument(kernel, expr, value, 0, document)),
+            }
+        }
+        (IntrinsicValue::TomlEncodeValue, [document]) => {
+            let document = strip_signal(document.clone());
+            Ok(match encode_toml_document(&document) {
+                Ok(encoded) => RuntimeValue::ResultOk(Box::new(RuntimeValue::Text(encoded.into()))),
+                Err(message) => {
+                    RuntimeValue::ResultErr(Box::new(RuntimeValue::Text(message.into())))
+                }
+            })
+        }
         (IntrinsicValue::XdgDataHome, []) => {
             let path = xdg_dir("XDG_DATA_HOME", ".local/share");
             Ok(RuntimeValue::Text(path.into()))
@@ -503,6 +974,18 @@ fn evaluate_intrinsic_value(
             let s = expect_intrinsic_text(kernel, expr, value, 0, text)?;
             Ok(RuntimeValue::Text(s.to_lowercase().into()))
         }
+        (IntrinsicValue::TextCaseFold, [text]) => {
+            let s = expect_intrinsic_text(kernel, expr, value, 0, text)?;
+            Ok(RuntimeValue::Text(caseless::default_case_fold_str(&s).into()))
+        }
+        (IntrinsicValue::TextReverse, [text]) => {
+            let s = expect_intrinsic_text(kernel, expr, value, 0, text)?;
+            Ok(RuntimeValue::Text(s.graphemes(true).rev().collect::<String>().into()))
+        }
+        (IntrinsicValue::TextGraphemeLength, [text]) => {
+            let s = expect_intrinsic_text(kernel, expr, value, 0, text)?;
+            Ok(RuntimeValue::Int(s.graphemes(true).count() as i64))
+        }
         (IntrinsicValue::TextTrim, [text]) => {
             let s = expect_intrinsic_text(kernel, expr, value, 0, text)?;
             Ok(RuntimeValue::Text(s.trim().into()))
@@ -544,6 +1027,23 @@ fn evaluate_intrinsic_value(
             let text = expect_intrinsic_text(kernel, expr, value, 1, text)?;
             Ok(RuntimeValue::Text(text.repeat(count).into()))
         }
+        (IntrinsicValue::TextWrap, [width, text]) => {
+            let width = expect_intrinsic_i64(kernel, expr, value, 0, width)?;
+            let text = expect_intrinsic_text(kernel, expr, value, 1, text)?;
+            Ok(RuntimeValue::List(
+                wrap_text(&text, usize::try_from(width).unwrap_or(0).max(1))
+                    .into_iter()
+                    .map(|line| RuntimeValue::Text(line.into()))
+                    .collect(),
+            ))
+        }
+        (IntrinsicValue::TextTruncate, [width, text]) => {
+            let width = expect_intrinsic_i64(kernel, expr, value, 0, width)?;
+            let text = expect_intrinsic_text(kernel, expr, value, 1, text)?;
+            Ok(RuntimeValue::Text(
+                truncate_text(&text, usize::try_from(width).unwrap_or(0)).into(),
+            ))
+        }
         (IntrinsicValue::TextFromInt, [n]) => {
             let n = expect_intrinsic_i64(kernel, expr, value, 0, n)?;
             Ok(RuntimeValue::Text(n.to_string().into()))
@@ -593,14 +1093,54 @@ fn evaluate_intrinsic_value(
                     });
                 }
             };
-            let mut result = String::new();
-            for part in &parts {
-                if let RuntimeValue::Text(t) = strip_signal(part.clone()) {
-                    result.push_str(&t);
-                }
+            // Size the buffer up front so building text from many parts allocates once instead
+            // of regrowing as each part is appended.
+            let capacity = parts
+                .iter()
+                .filter_map(concat_part_text)
+                .map(str::len)
+                .sum();
+            let mut result = String::with_capacity(capacity);
+            for text in parts.iter().filter_map(concat_part_text) {
+                result.push_str(text);
             }
             Ok(RuntimeValue::Text(result.into()))
         }
+        (IntrinsicValue::TextRenderTemplate, [template, values]) => {
+            let template = expect_intrinsic_text(kernel, expr, value, 0, template)?;
+            let values = expect_intrinsic_text_map(kernel, expr, value, 1, values)?;
+            let lookup = |key: &str| {
+                values
+                    .iter()
+                    .find(|(name, _)| name.as_ref() == key)
+                    .map(|(_, text)| text.as_ref())
+            };
+            Ok(template_result(render_template(&template, lookup, true)))
+        }
+        (IntrinsicValue::TextRenderLenient, [template, values]) => {
+            let template = expect_intrinsic_text(kernel, expr, value, 0, template)?;
+            let values = expect_intrinsic_text_map(kernel, expr, value, 1, values)?;
+            let lookup = |key: &str| {
+                values
+                    .iter()
+                    .find(|(name, _)| name.as_ref() == key)
+                    .map(|(_, text)| text.as_ref())
+            };
+            let rendered = render_template(&template, lookup, false)
+                .expect("lenient template rendering should not fail");
+            Ok(RuntimeValue::Text(rendered.into()))
+        }
+        (IntrinsicValue::TextRenderPositional, [template, arguments]) => {
+            let template = expect_intrinsic_text(kernel, expr, value, 0, template)?;
+            let arguments = expect_intrinsic_text_list(kernel, expr, value, 1, arguments)?;
+            let lookup = |key: &str| {
+                key.parse::<usize>()
+                    .ok()
+                    .and_then(|index| arguments.get(index))
+                    .map(Box::as_ref)
+            };
+            Ok(template_result(render_template(&template, lookup, true)))
+        }
         // Float transcendental intrinsics — pure/synchronous
         (IntrinsicValue::FloatSin, [n]) => {
             let f = expect_intrinsic_float(kernel, expr, value, 0, n)?;
@@ -809,11 +1349,150 @@ fn evaluate_intrinsic_value(
                     reason: "frac result is not finite",
                 })
         }
+        // Matrix intrinsics — pure/synchronous LU decomposition
+        (IntrinsicValue::MatrixLuSolve, [matrix, rhs]) => {
+            let matrix = expect_intrinsic_square_matrix(kernel, expr, value, 0, matrix)?;
+            let rhs = expect_intrinsic_float_list(kernel, expr, value, 1, rhs)?;
+            if rhs.len() != matrix.len() {
+                return Err(EvaluationError::IntrinsicFailed {
+                    kernel,
+                    expr,
+                    value,
+                    reason: "right-hand side length does not match the matrix height",
+                });
+            }
+            match LuDecomposition::new(matrix) {
+                Some(lu) => runtime_finite_floats(kernel, expr, value, lu.solve(&rhs))
+                    .map(|solution| RuntimeValue::OptionSome(Box::new(solution))),
+                None => Ok(RuntimeValue::OptionNone),
+            }
+        }
+        (IntrinsicValue::MatrixLuDeterminant, [matrix]) => {
+            let matrix = expect_intrinsic_square_matrix(kernel, expr, value, 0, matrix)?;
+            let determinant = LuDecomposition::new(matrix).map_or(0.0, |lu| lu.determinant());
+            runtime_finite_float(kernel, expr, value, determinant, MATRIX_NOT_FINITE)
+        }
+        (IntrinsicValue::MatrixLuInverse, [matrix]) => {
+            let matrix = expect_intrinsic_square_matrix(kernel, expr, value, 0, matrix)?;
+            match LuDecomposition::new(matrix) {
+                Some(lu) => lu
+                    .inverse()
+                    .into_iter()
+                    .map(|row| runtime_finite_floats(kernel, expr, value, row))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|rows| RuntimeValue::OptionSome(Box::new(RuntimeValue::List(rows)))),
+                None => Ok(RuntimeValue::OptionNone),
+            }
+        }
+        // Matrix intrinsics — pure/synchronous Jacobi rotations
+        (IntrinsicValue::MatrixJacobiSvd, [matrix]) => {
+            let matrix = expect_intrinsic_rectangular_matrix(kernel, expr, value, 0, matrix)?;
+            let factors = SvdFactors::new(matrix);
+            let finite_rows = |rows: Vec<Vec<f64>>| {
+                rows.into_iter()
+                    .map(|row| runtime_finite_floats(kernel, expr, value, row))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(RuntimeValue::List)
+            };
+            Ok(RuntimeValue::Record(vec![
+                runtime_record_field("u", finite_rows(factors.u)?),
+                runtime_record_field(
+                    "singularValues",
+                    runtime_finite_floats(kernel, expr, value, factors.singular_values)?,
+                ),
+                runtime_record_field("vt", finite_rows(factors.vt)?),
+            ]))
+        }
+        (IntrinsicValue::MatrixJacobiEigenvalues, [matrix]) => {
+            let matrix = expect_intrinsic_square_matrix(kernel, expr, value, 0, matrix)?;
+            match symmetric_eigenvalues(matrix) {
+                Some(eigenvalues) => runtime_finite_floats(kernel, expr, value, eigenvalues)
+                    .map(|eigenvalues| RuntimeValue::OptionSome(Box::new(eigenvalues))),
+                None => Ok(RuntimeValue::OptionNone),
+            }
+        }
+        // Graph intrinsics — Dijkstra over non-negative edge weights
+        (IntrinsicValue::GraphShortestPath, [graph, start, target]) => {
+            let graph = expect_intrinsic_graph(kernel, expr, value, 0, graph)?;
+            let start = expect_intrinsic_i64(kernel, expr, value, 1, start)?;
+            let target = expect_intrinsic_i64(kernel, expr, value, 2, target)?;
+            let start = graph.index_of(kernel, expr, value, start)?;
+            let target = graph.index_of(kernel, expr, value, target)?;
+            let (distances, predecessors) = graph.dijkstra(start);
+            if distances[target].is_none() {
+                return Ok(RuntimeValue::OptionNone);
+            }
+            let mut path = vec![target];
+            let mut node = target;
+            while let Some(previous) = predecessors[node] {
+                path.push(previous);
+                node = previous;
+            }
+            path.reverse();
+            let path = path
+                .into_iter()
+                .map(|node| RuntimeValue::Int(graph.nodes[node]))
+                .collect();
+            Ok(RuntimeValue::OptionSome(Box::new(RuntimeValue::List(path))))
+        }
+        (IntrinsicValue::GraphDistances, [graph, start]) => {
+            let graph = expect_intrinsic_graph(kernel, expr, value, 0, graph)?;
+            let start = expect_intrinsic_i64(kernel, expr, value, 1, start)?;
+            let start = graph.index_of(kernel, expr, value, start)?;
+            let (distances, _) = graph.dijkstra(start);
+            let entries = distances
+                .into_iter()
+                .enumerate()
+                .filter_map(|(node, distance)| Some((node, distance?)))
+                .map(|(node, distance)| {
+                    runtime_finite_float(kernel, expr, value, distance, GRAPH_NOT_FINITE).map(
+                        |distance| RuntimeMapEntry {
+                            key: RuntimeValue::Int(graph.nodes[node]),
+                            value: distance,
+                        },
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(RuntimeValue::Map(RuntimeMap::from_entries(entries)))
+        }
+        // Math intrinsics — pure/synchronous
+        (IntrinsicValue::MathFftMagnitude, [samples]) => {
+            let samples = expect_intrinsic_float_list(kernel, expr, value, 0, samples)?;
+            real_fft_bins(&samples)
+                .into_iter()
+                .map(|bin| runtime_finite_float(kernel, expr, value, bin.norm(), FFT_NOT_FINITE))
+                .collect::<Result<Vec<_>, _>>()
+                .map(RuntimeValue::List)
+        }
+        (IntrinsicValue::MathFftSpectrum, [samples]) => {
+            let samples = expect_intrinsic_float_list(kernel, expr, value, 0, samples)?;
+            real_fft_bins(&samples)
+                .into_iter()
+                .enumerate()
+                .map(|(index, bin)| {
+                    Ok(RuntimeValue::Record(vec![
+                        runtime_record_field(
+                            "magnitude",
+                            runtime_finite_float(kernel, expr, value, bin.norm(), FFT_NOT_FINITE)?,
+                        ),
+                        runtime_record_field(
+                            "phase",
+                            runtime_finite_float(kernel, expr, value, bin.arg(), FFT_NOT_FINITE)?,
+                        ),
+                        runtime_record_field("frequencyBin", RuntimeValue::Int(index as i64)),
+                    ]))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(RuntimeValue::List)
+        }
         // Time intrinsics — Task-returning
         (IntrinsicValue::TimeNowMs, []) => Ok(RuntimeValue::Task(RuntimeTaskPlan::TimeNowMs)),
         (IntrinsicValue::TimeMonotonicMs, []) => {
             Ok(RuntimeValue::Task(RuntimeTaskPlan::TimeMonotonicMs))
         }
+        (IntrinsicValue::TimeSleep, [ms]) => Ok(RuntimeValue::Task(RuntimeTaskPlan::TimeSleep {
+            ms: expect_intrinsic_i64(kernel, expr, value, 0, ms)?,
+        })),
         (IntrinsicValue::TimeFormat, [ms, pattern]) => {
             Ok(RuntimeValue::Task(RuntimeTaskPlan::TimeFormat {
                 epoch_ms: expect_intrinsic_i64(kernel, expr, value, 0, ms)?,
@@ -826,6 +1505,15 @@ fn evaluate_intrinsic_value(
                 pattern: expect_intrinsic_text(kernel, expr, value, 1, pattern)?,
             }))
         }
+        // Clipboard intrinsics — Task-returning
+        (IntrinsicValue::ClipboardRead, []) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::ClipboardRead))
+        }
+        (IntrinsicValue::ClipboardWrite, [text]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::ClipboardWrite {
+                text: expect_intrinsic_text(kernel, expr, value, 0, text)?,
+            }))
+        }
         // Env intrinsics — Task-returning
         (IntrinsicValue::EnvGet, [name]) => Ok(RuntimeValue::Task(RuntimeTaskPlan::EnvGet {
             name: expect_intrinsic_text(kernel, expr, value, 0, name)?,
@@ -833,6 +1521,107 @@ fn evaluate_intrinsic_value(
         (IntrinsicValue::EnvList, [prefix]) => Ok(RuntimeValue::Task(RuntimeTaskPlan::EnvList {
             prefix: expect_intrinsic_text(kernel, expr, value, 0, prefix)?,
         })),
+        // Process intrinsics — Task-returning
+        (IntrinsicValue::ProcessArgs, []) => Ok(RuntimeValue::Task(RuntimeTaskPlan::ProcessArgs)),
+        (IntrinsicValue::ProcessExit, [code]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::ProcessExit {
+                code: expect_intrinsic_i64(kernel, expr, value, 0, code)?,
+            }))
+        }
+        // Test intrinsics — Task-returning
+        (IntrinsicValue::TestMatchesSnapshot, [name, content]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::MatchesSnapshot {
+                name: expect_intrinsic_text(kernel, expr, value, 0, name)?,
+                content: expect_intrinsic_text(kernel, expr, value, 1, content)?,
+            }))
+        }
+        (IntrinsicValue::TestAdvanceClock, [ms]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::AdvanceClock {
+                ms: expect_intrinsic_i64(kernel, expr, value, 0, ms)?,
+            }))
+        }
+        (IntrinsicValue::TestSetClock, [ms]) => Ok(RuntimeValue::Task(RuntimeTaskPlan::SetClock {
+            epoch_ms: expect_intrinsic_i64(kernel, expr, value, 0, ms)?,
+        })),
+        // Task combinators
+        (IntrinsicValue::TaskAttempt, [task]) => match strip_signal(task.clone()) {
+            // A pure task cannot fail, so its result is known without running anything.
+            RuntimeValue::Task(RuntimeTaskPlan::Pure { value }) => {
+                Ok(RuntimeValue::Task(RuntimeTaskPlan::Pure {
+                    value: Box::new(RuntimeValue::ResultOk(value)),
+                }))
+            }
+            RuntimeValue::Task(plan) => Ok(RuntimeValue::Task(RuntimeTaskPlan::Attempt {
+                inner: Box::new(plan),
+            })),
+            found => Err(EvaluationError::InvalidIntrinsicArgument {
+                kernel,
+                expr,
+                value,
+                index: 0,
+                found,
+            }),
+        },
+        (IntrinsicValue::TaskFinally, [task, cleanup]) => {
+            let inner = match strip_signal(task.clone()) {
+                RuntimeValue::Task(plan) => plan,
+                found => {
+                    return Err(EvaluationError::InvalidIntrinsicArgument {
+                        kernel,
+                        expr,
+                        value,
+                        index: 0,
+                        found,
+                    });
+                }
+            };
+            match strip_signal(cleanup.clone()) {
+                // Cleanup that does nothing leaves the main task unchanged.
+                RuntimeValue::Task(RuntimeTaskPlan::Pure { .. }) => Ok(RuntimeValue::Task(inner)),
+                RuntimeValue::Task(cleanup) => Ok(RuntimeValue::Task(RuntimeTaskPlan::Finally {
+                    inner: Box::new(inner),
+                    cleanup: Box::new(cleanup),
+                })),
+                found => Err(EvaluationError::InvalidIntrinsicArgument {
+                    kernel,
+                    expr,
+                    value,
+                    index: 1,
+                    found,
+                }),
+            }
+        }
+        (IntrinsicValue::TaskParallel, [tasks]) => {
+            let RuntimeValue::List(tasks) = strip_signal(tasks.clone()) else {
+                return Err(EvaluationError::InvalidIntrinsicArgument {
+                    kernel,
+                    expr,
+                    value,
+                    index: 0,
+                    found: strip_signal(tasks.clone()),
+                });
+            };
+            let tasks = tasks
+                .into_iter()
+                .map(|task| match strip_signal(task) {
+                    RuntimeValue::Task(plan) => Ok(plan),
+                    found => Err(EvaluationError::InvalidIntrinsicArgument {
+                        kernel,
+                        expr,
+                        value,
+                        index: 0,
+                        found,
+                    }),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            // Nothing to run concurrently: an empty list is already the result.
+            if tasks.is_empty() {
+                return Ok(RuntimeValue::Task(RuntimeTaskPlan::Pure {
+                    value: Box::new(RuntimeValue::List(Vec::new())),
+                }));
+            }
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::Parallel { tasks }))
+        }
         // Log intrinsics — Task-returning
         (IntrinsicValue::LogEmit, [level, message]) => {
             Ok(RuntimeValue::Task(RuntimeTaskPlan::LogEmit {
@@ -860,7 +1649,7 @@ fn evaluate_intrinsic_value(
                 match strip_signal(entry.clone()) {
                     RuntimeValue::Tuple(elements) if elements.len() == 2 => {
                         let k = match strip_signal(elements[0].clone()) {
-                            RuntimeValue::Text(t) => t,
+                            RuntimeValue::Text(t) => Box::from(&*t),
                             found => {
                                 return Err(EvaluationError::InvalidIntrinsicArgument {
                                     kernel,
@@ -872,7 +1661,7 @@ fn evaluate_intrinsic_value(
                             }
                         };
                         let v = match strip_signal(elements[1].clone()) {
-                            RuntimeValue::Text(t) => t,
+                            RuntimeValue::Text(t) => Box::from(&*t),
                             found => {
                                 return Err(EvaluationError::InvalidIntrinsicArgument {
                                     kernel,
@@ -907,17 +1696,15 @@ fn evaluate_intrinsic_value(
         // I18n intrinsics — pure/synchronous
         (IntrinsicValue::I18nTranslate, [text]) => {
             let s = expect_intrinsic_text(kernel, expr, value, 0, text)?;
-            Ok(RuntimeValue::Text(s))
+            Ok(RuntimeValue::Text(s.into()))
         }
         (IntrinsicValue::I18nTranslatePlural, [singular, plural, count]) => {
             let singular = expect_intrinsic_text(kernel, expr, value, 0, singular)?;
             let plural = expect_intrinsic_text(kernel, expr, value, 1, plural)?;
             let count = expect_intrinsic_i64(kernel, expr, value, 2, count)?;
-            Ok(RuntimeValue::Text(if count == 1 {
-                singular
-            } else {
-                plural
-            }))
+            Ok(RuntimeValue::Text(
+                if count == 1 { singular } else { plural }.into(),
+            ))
         }
         // Regex intrinsics — Task-returning
         (IntrinsicValue::RegexIsMatch, [pattern, text]) => {
@@ -944,6 +1731,18 @@ fn evaluate_intrinsic_value(
                 text: expect_intrinsic_text(kernel, expr, value, 1, text)?,
             }))
         }
+        (IntrinsicValue::RegexCaptures, [pattern, text]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::RegexCaptures {
+                pattern: expect_intrinsic_text(kernel, expr, value, 0, pattern)?,
+                text: expect_intrinsic_text(kernel, expr, value, 1, text)?,
+            }))
+        }
+        (IntrinsicValue::RegexCapturesAll, [pattern, text]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::RegexCapturesAll {
+                pattern: expect_intrinsic_text(kernel, expr, value, 0, pattern)?,
+                text: expect_intrinsic_text(kernel, expr, value, 1, text)?,
+            }))
+        }
         (IntrinsicValue::RegexReplace, [pattern, replacement, text]) => {
             Ok(RuntimeValue::Task(RuntimeTaskPlan::RegexReplace {
                 pattern: expect_intrinsic_text(kernel, expr, value, 0, pattern)?,
@@ -985,6 +1784,66 @@ fn evaluate_intrinsic_value(
                 body: expect_intrinsic_text(kernel, expr, value, 1, body)?,
             }))
         }
+        (IntrinsicValue::HttpRequest, [base, request]) => {
+            let base = expect_intrinsic_text(kernel, expr, value, 0, base)?;
+            expect_intrinsic_http_request(kernel, expr, value, 1, &base, request)
+                .map(RuntimeValue::Task)
+        }
+        (IntrinsicValue::HttpDownload, [url, path]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::HttpDownload {
+                url: expect_intrinsic_text(kernel, expr, value, 0, url)?,
+                path: expect_intrinsic_text(kernel, expr, value, 1, path)?,
+            }))
+        }
+        (IntrinsicValue::WsConnect, [url]) => Ok(RuntimeValue::Task(RuntimeTaskPlan::WsConnect {
+            url: expect_intrinsic_text(kernel, expr, value, 0, url)?,
+        })),
+        (IntrinsicValue::WsSend, [handle, text]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::WsSend {
+                handle: expect_intrinsic_handle_id(kernel, expr, value, 0, handle)?,
+                text: expect_intrinsic_text(kernel, expr, value, 1, text)?,
+            }))
+        }
+        (IntrinsicValue::WsRecv, [handle]) => Ok(RuntimeValue::Task(RuntimeTaskPlan::WsRecv {
+            handle: expect_intrinsic_handle_id(kernel, expr, value, 0, handle)?,
+        })),
+        (IntrinsicValue::WsRecvTimeout, [handle, timeout_ms]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::WsRecvTimeout {
+                handle: expect_intrinsic_handle_id(kernel, expr, value, 0, handle)?,
+                timeout_ms: expect_intrinsic_i64(kernel, expr, value, 1, timeout_ms)?,
+            }))
+        }
+        (IntrinsicValue::WsClose, [handle]) => Ok(RuntimeValue::Task(RuntimeTaskPlan::WsClose {
+            handle: expect_intrinsic_handle_id(kernel, expr, value, 0, handle)?,
+        })),
+        (IntrinsicValue::ProcessSpawn, [command, args, env]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::ProcessSpawn {
+                command: expect_intrinsic_text(kernel, expr, value, 0, command)?,
+                args: expect_intrinsic_text_list(kernel, expr, value, 1, args)?.into(),
+                env: expect_intrinsic_text_map(kernel, expr, value, 2, env)?,
+            }))
+        }
+        (IntrinsicValue::ProcessWait, [handle]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::ProcessWait {
+                handle: expect_intrinsic_handle_id(kernel, expr, value, 0, handle)?,
+            }))
+        }
+        (IntrinsicValue::ProcessKill, [handle]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::ProcessKill {
+                handle: expect_intrinsic_handle_id(kernel, expr, value, 0, handle)?,
+            }))
+        }
+        (IntrinsicValue::ProcessReadStdout, [handle]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::ProcessReadStdout {
+                handle: expect_intrinsic_handle_id(kernel, expr, value, 0, handle)?,
+            }))
+        }
+        (IntrinsicValue::ProcessWriteStdin, [handle, text]) => {
+            Ok(RuntimeValue::Task(RuntimeTaskPlan::ProcessWriteStdin {
+                handle: expect_intrinsic_handle_id(kernel, expr, value, 0, handle)?,
+                text: expect_intrinsic_text(kernel, expr, value, 1, text)?,
+            }))
+        }
         (IntrinsicValue::HttpPost, [url, content_type, body]) => {
             Ok(RuntimeValue::Task(RuntimeTaskPlan::HttpPost {
                 url: expect_intrinsic_text(kernel, expr, value, 0, url)?,
@@ -1077,7 +1936,7 @@ fn evaluate_intrinsic_value(
         }
         (IntrinsicValue::BigIntToText, [n]) => {
             let b = expect_intrinsic_bigint(kernel, expr, value, 0, n)?;
-            Ok(RuntimeValue::Text(b.to_decimal_str()))
+            Ok(RuntimeValue::Text(b.to_decimal_str().into()))
         }
         (IntrinsicValue::BigIntAdd, [a, b]) => {
             let a = expect_intrinsic_bigint(kernel, expr, value, 0, a)?;
@@ -1147,12 +2006,90 @@ fn evaluate_intrinsic_value(
             let b = expect_intrinsic_bigint(kernel, expr, value, 1, b)?;
             Ok(RuntimeValue::Bool(a < b))
         }
-        (IntrinsicValue::BitAnd, [a, b]) => {
-            let a = expect_intrinsic_i64(kernel, expr, value, 0, a)?;
-            let b = expect_intrinsic_i64(kernel, expr, value, 1, b)?;
-            Ok(RuntimeValue::Int(a & b))
+        // Decimal intrinsics — pure, no I/O
+        (IntrinsicValue::DecimalFromInt, [n]) => {
+            let n = expect_intrinsic_i64(kernel, expr, value, 0, n)?;
+            Ok(RuntimeValue::Decimal(RuntimeDecimal::from_i64(n)))
         }
-        (IntrinsicValue::BitOr, [a, b]) => {
+        (IntrinsicValue::DecimalFromText, [text]) => {
+            let s = expect_intrinsic_text(kernel, expr, value, 0, text)?;
+            match RuntimeDecimal::from_decimal_str(&s) {
+                Some(d) => Ok(RuntimeValue::OptionSome(Box::new(RuntimeValue::Decimal(d)))),
+                None => Ok(RuntimeValue::OptionNone),
+            }
+        }
+        (IntrinsicValue::DecimalToText, [n]) => {
+            let d = expect_intrinsic_decimal(kernel, expr, value, 0, n)?;
+            Ok(RuntimeValue::Text(d.to_decimal_str().into()))
+        }
+        (IntrinsicValue::DecimalAdd, [a, b]) => {
+            let a = expect_intrinsic_decimal(kernel, expr, value, 0, a)?;
+            let b = expect_intrinsic_decimal(kernel, expr, value, 1, b)?;
+            a.decimal_add(&b)
+                .map(RuntimeValue::Decimal)
+                .ok_or(EvaluationError::IntrinsicFailed {
+                    kernel,
+                    expr,
+                    value,
+                    reason: "decimal addition overflowed or lost precision",
+                })
+        }
+        (IntrinsicValue::DecimalSub, [a, b]) => {
+            let a = expect_intrinsic_decimal(kernel, expr, value, 0, a)?;
+            let b = expect_intrinsic_decimal(kernel, expr, value, 1, b)?;
+            a.decimal_sub(&b)
+                .map(RuntimeValue::Decimal)
+                .ok_or(EvaluationError::IntrinsicFailed {
+                    kernel,
+                    expr,
+                    value,
+                    reason: "decimal subtraction overflowed or lost precision",
+                })
+        }
+        (IntrinsicValue::DecimalMul, [a, b]) => {
+            let a = expect_intrinsic_decimal(kernel, expr, value, 0, a)?;
+            let b = expect_intrinsic_decimal(kernel, expr, value, 1, b)?;
+            a.decimal_mul(&b)
+                .map(RuntimeValue::Decimal)
+                .ok_or(EvaluationError::IntrinsicFailed {
+                    kernel,
+                    expr,
+                    value,
+                    reason: "decimal multiplication overflowed or lost precision",
+                })
+        }
+        (IntrinsicValue::DecimalDivWithMode, [mode, places, a, b]) => {
+            let (places, strategy) = expect_decimal_rounding(kernel, expr, value, mode, places)?;
+            let a = expect_intrinsic_decimal(kernel, expr, value, 2, a)?;
+            let b = expect_intrinsic_decimal(kernel, expr, value, 3, b)?;
+            if b.is_zero() {
+                return Err(EvaluationError::IntrinsicFailed {
+                    kernel,
+                    expr,
+                    value,
+                    reason: "decimal division by zero",
+                });
+            }
+            a.decimal_div(&b, places, strategy)
+                .map(RuntimeValue::Decimal)
+                .ok_or(EvaluationError::IntrinsicFailed {
+                    kernel,
+                    expr,
+                    value,
+                    reason: "decimal division overflowed",
+                })
+        }
+        (IntrinsicValue::DecimalRoundWithMode, [mode, places, n]) => {
+            let (places, strategy) = expect_decimal_rounding(kernel, expr, value, mode, places)?;
+            let d = expect_intrinsic_decimal(kernel, expr, value, 2, n)?;
+            Ok(RuntimeValue::Decimal(d.decimal_round(places, strategy)))
+        }
+        (IntrinsicValue::BitAnd, [a, b]) => {
+            let a = expect_intrinsic_i64(kernel, expr, value, 0, a)?;
+            let b = expect_intrinsic_i64(kernel, expr, value, 1, b)?;
+            Ok(RuntimeValue::Int(a & b))
+        }
+        (IntrinsicValue::BitOr, [a, b]) => {
             let a = expect_intrinsic_i64(kernel, expr, value, 0, a)?;
             let b = expect_intrinsic_i64(kernel, expr, value, 1, b)?;
             Ok(RuntimeValue::Int(a | b))
@@ -1610,7 +2547,26 @@ fn expect_intrinsic_text(
     argument: &RuntimeValue,
 ) -> Result<Box<str>, EvaluationError> {
     match strip_signal(argument.clone()) {
-        RuntimeValue::Text(found) => Ok(found),
+        RuntimeValue::Text(found) => Ok(Box::from(&*found)),
+        found => Err(EvaluationError::InvalidIntrinsicArgument {
+            kernel,
+            expr,
+            value,
+            index,
+            found: found.clone(),
+        }),
+    }
+}
+
+fn expect_intrinsic_char(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    index: usize,
+    argument: &RuntimeValue,
+) -> Result<char, EvaluationError> {
+    match strip_signal(argument.clone()) {
+        RuntimeValue::Char(found) => Ok(found),
         found => Err(EvaluationError::InvalidIntrinsicArgument {
             kernel,
             expr,
@@ -1621,6 +2577,40 @@ fn expect_intrinsic_text(
     }
 }
 
+/// Decode hex text in either case. Odd lengths and non-hex digits give `None`.
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    let digits = text.as_bytes();
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    digits
+        .chunks_exact(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16)?;
+            let low = (pair[1] as char).to_digit(16)?;
+            Some((high * 16 + low) as u8)
+        })
+        .collect()
+}
+
+fn hmac_digest<M: hmac::Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8]) -> Box<[u8]> {
+    let mut mac = <M as hmac::Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec().into_boxed_slice()
+}
+
+const ED25519_PUBLIC_KEY_LEN: usize = 32;
+
+/// Compare two byte strings in time that depends only on their lengths, never on where they
+/// first differ, so comparing a received MAC does not leak how much of it was right.
+fn constant_time_equals(left: &[u8], right: &[u8]) -> bool {
+    let mut difference = u8::from(left.len() != right.len());
+    for (index, byte) in left.iter().enumerate() {
+        difference |= byte ^ right.get(index).copied().unwrap_or(0);
+    }
+    std::hint::black_box(difference) == 0
+}
+
 fn expect_intrinsic_bytes(
     kernel: KernelId,
     expr: KernelExprId,
@@ -1659,6 +2649,54 @@ fn expect_intrinsic_float(
     }
 }
 
+fn expect_intrinsic_decimal(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    index: usize,
+    argument: &RuntimeValue,
+) -> Result<RuntimeDecimal, EvaluationError> {
+    match strip_signal(argument.clone()) {
+        RuntimeValue::Decimal(found) => Ok(found),
+        found => Err(EvaluationError::InvalidIntrinsicArgument {
+            kernel,
+            expr,
+            value,
+            index,
+            found,
+        }),
+    }
+}
+
+/// Decode the leading rounding-mode name and decimal-place count of the `aivi.decimal`
+/// rounding intrinsics.
+fn expect_decimal_rounding(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    mode: &RuntimeValue,
+    places: &RuntimeValue,
+) -> Result<(u32, rust_decimal::RoundingStrategy), EvaluationError> {
+    let mode = expect_intrinsic_text(kernel, expr, value, 0, mode)?;
+    let places = expect_intrinsic_i64(kernel, expr, value, 1, places)?;
+    let strategy = decimal_rounding_strategy(&mode).ok_or(EvaluationError::IntrinsicFailed {
+        kernel,
+        expr,
+        value,
+        reason: "unknown decimal rounding mode",
+    })?;
+    let places = u32::try_from(places)
+        .ok()
+        .filter(|places| *places <= RuntimeDecimal::MAX_SCALE)
+        .ok_or(EvaluationError::IntrinsicFailed {
+            kernel,
+            expr,
+            value,
+            reason: "decimal places must be between 0 and 28",
+        })?;
+    Ok((places, strategy))
+}
+
 fn expect_intrinsic_bigint(
     kernel: KernelId,
     expr: KernelExprId,
@@ -1678,6 +2716,64 @@ fn expect_intrinsic_bigint(
     }
 }
 
+/// Number of arguments a callable still expects before it runs, or `None` for
+/// non-callable values.
+fn runtime_value_arity(value: &RuntimeValue) -> Option<usize> {
+    let RuntimeValue::Callable(callable) = value else {
+        return None;
+    };
+    let (expected, bound) = match callable {
+        RuntimeCallable::ItemBody {
+            parameters,
+            bound_arguments,
+            ..
+        }
+        | RuntimeCallable::DomainMember {
+            parameters,
+            bound_arguments,
+            ..
+        } => (parameters.len(), bound_arguments.len()),
+        RuntimeCallable::BuiltinConstructor {
+            bound_arguments, ..
+        } => (1, bound_arguments.len()),
+        RuntimeCallable::SumConstructor {
+            handle,
+            bound_arguments,
+        } => (handle.field_count, bound_arguments.len()),
+        RuntimeCallable::BuiltinClassMember {
+            intrinsic,
+            bound_arguments,
+        } => (
+            builtin_class_member_arity(*intrinsic),
+            bound_arguments.len(),
+        ),
+        RuntimeCallable::IntrinsicValue {
+            value,
+            bound_arguments,
+        } => (intrinsic_value_arity(*value), bound_arguments.len()),
+    };
+    Some(expected.saturating_sub(bound))
+}
+
+/// Accept any constructor of a sum type and return that type's runtime identity.
+fn expect_intrinsic_sum_type(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    index: usize,
+    argument: &RuntimeValue,
+) -> Result<(HirItemId, Box<str>), EvaluationError> {
+    match strip_signal(argument.clone()) {
+        RuntimeValue::Sum(sum) => Ok((sum.item, sum.type_name)),
+        RuntimeValue::Callable(RuntimeCallable::SumConstructor { handle, .. }) => {
+            Ok((handle.item, handle.type_name))
+        }
+        found => Err(invalid_intrinsic_argument(
+            kernel, expr, value, index, found,
+        )),
+    }
+}
+
 fn invalid_intrinsic_argument(
     kernel: KernelId,
     expr: KernelExprId,
@@ -1727,7 +2823,7 @@ fn runtime_db_param(
 
 fn runtime_db_statement(sql: Box<str>, arguments: Vec<RuntimeValue>) -> RuntimeValue {
     RuntimeValue::Record(vec![
-        runtime_record_field("sql", RuntimeValue::Text(sql)),
+        runtime_record_field("sql", RuntimeValue::Text(sql.into())),
         runtime_record_field("arguments", RuntimeValue::List(arguments)),
     ])
 }
@@ -1739,6 +2835,104 @@ fn record_field<'a>(fields: &'a [RuntimeRecordField], label: &str) -> Option<&'a
         .map(|field| &field.value)
 }
 
+/// Text carried by one `aivi.text.concat` part, looking through a signal wrapper.
+/// Why strict template rendering stopped. Offsets count characters from the start of the
+/// template and point at the `{` that opens the offending placeholder.
+#[derive(Debug, PartialEq, Eq)]
+enum TemplateProblem {
+    MissingKey { key: String, offset: usize },
+    Unclosed { offset: usize },
+}
+
+/// Substitute `{key}` placeholders in `template` with the text `lookup` returns. `{{` and `}}`
+/// stand for literal braces, and a lone `}` is kept as written. When `strict` is false, unknown
+/// keys and an unclosed `{` are copied through unchanged, so rendering cannot fail.
+fn render_template<'a>(
+    template: &str,
+    lookup: impl Fn(&str) -> Option<&'a str>,
+    strict: bool,
+) -> Result<String, TemplateProblem> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut chars = template.char_indices().enumerate().peekable();
+    while let Some((offset, (start, character))) = chars.next() {
+        match character {
+            '{' if matches!(chars.peek(), Some((_, (_, '{')))) => {
+                chars.next();
+                rendered.push('{');
+            }
+            '}' if matches!(chars.peek(), Some((_, (_, '}')))) => {
+                chars.next();
+                rendered.push('}');
+            }
+            '{' => {
+                let rest = &template[start + 1..];
+                let Some(length) = rest
+                    .find(['{', '}'])
+                    .filter(|&end| rest[end..].starts_with('}'))
+                else {
+                    if strict {
+                        return Err(TemplateProblem::Unclosed { offset });
+                    }
+                    rendered.push('{');
+                    continue;
+                };
+                let key = &rest[..length];
+                match lookup(key) {
+                    Some(text) => rendered.push_str(text),
+                    None if strict => {
+                        return Err(TemplateProblem::MissingKey {
+                            key: key.to_owned(),
+                            offset,
+                        });
+                    }
+                    None => {
+                        rendered.push('{');
+                        rendered.push_str(key);
+                        rendered.push('}');
+                    }
+                }
+                // Skip the key and its closing brace.
+                for _ in 0..key.chars().count() + 1 {
+                    chars.next();
+                }
+            }
+            other => rendered.push(other),
+        }
+    }
+    Ok(rendered)
+}
+
+/// Encode a strict rendering outcome as `Result { missingKey, offset } Text`.
+fn template_result(outcome: Result<String, TemplateProblem>) -> RuntimeValue {
+    match outcome {
+        Ok(rendered) => RuntimeValue::ResultOk(Box::new(RuntimeValue::Text(rendered.into()))),
+        Err(problem) => {
+            let (missing_key, offset) = match problem {
+                TemplateProblem::MissingKey { key, offset } => (
+                    RuntimeValue::OptionSome(Box::new(RuntimeValue::Text(key.into()))),
+                    offset,
+                ),
+                TemplateProblem::Unclosed { offset } => (RuntimeValue::OptionNone, offset),
+            };
+            RuntimeValue::ResultErr(Box::new(RuntimeValue::Record(vec![
+                runtime_record_field("missingKey", missing_key),
+                runtime_record_field("offset", RuntimeValue::Int(offset as i64)),
+            ])))
+        }
+    }
+}
+
+fn concat_part_text(part: &RuntimeValue) -> Option<&str> {
+    match part {
+        RuntimeValue::Text(text) => Some(text),
+        RuntimeValue::Signal(inner) => match inner.as_ref() {
+            RuntimeValue::Text(text) => Some(text),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn expect_intrinsic_text_list(
     kernel: KernelId,
     expr: KernelExprId,
@@ -1755,7 +2949,31 @@ fn expect_intrinsic_text_list(
     values
         .iter()
         .map(|entry| match strip_signal(entry.clone()) {
-            RuntimeValue::Text(text) => Ok(text),
+            RuntimeValue::Text(text) => Ok(Box::from(&*text)),
+            found => Err(invalid_intrinsic_argument(
+                kernel, expr, value, index, found,
+            )),
+        })
+        .collect()
+}
+
+fn expect_intrinsic_float_list(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    index: usize,
+    argument: &RuntimeValue,
+) -> Result<Vec<f64>, EvaluationError> {
+    let found = strip_signal(argument.clone());
+    let RuntimeValue::List(values) = &found else {
+        return Err(invalid_intrinsic_argument(
+            kernel, expr, value, index, found,
+        ));
+    };
+    values
+        .iter()
+        .map(|entry| match strip_signal(entry.clone()) {
+            RuntimeValue::Float(float) => Ok(float.to_f64()),
             found => Err(invalid_intrinsic_argument(
                 kernel, expr, value, index, found,
             )),
@@ -1763,6 +2981,547 @@ fn expect_intrinsic_text_list(
         .collect()
 }
 
+/// Forward FFT of real samples. Only bins `0..=n/2` are returned: for real input the remaining
+/// bins are complex conjugates of these and carry no extra information. Any length is accepted,
+/// and an empty input yields no bins.
+fn real_fft_bins(samples: &[f64]) -> Vec<rustfft::num_complex::Complex<f64>> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+    let mut buffer = samples
+        .iter()
+        .map(|&sample| rustfft::num_complex::Complex::new(sample, 0.0))
+        .collect::<Vec<_>>();
+    rustfft::FftPlanner::new()
+        .plan_fft_forward(buffer.len())
+        .process(&mut buffer);
+    buffer.truncate(samples.len() / 2 + 1);
+    buffer
+}
+
+const FFT_NOT_FINITE: &str = "fft result is not finite";
+const MATRIX_NOT_FINITE: &str = "matrix result is not finite";
+const GRAPH_NOT_FINITE: &str = "graph distance is not finite";
+
+fn runtime_finite_float(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    result: f64,
+    reason: &'static str,
+) -> Result<RuntimeValue, EvaluationError> {
+    RuntimeFloat::new(result)
+        .map(RuntimeValue::Float)
+        .ok_or(EvaluationError::IntrinsicFailed {
+            kernel,
+            expr,
+            value,
+            reason,
+        })
+}
+
+fn runtime_finite_floats(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    results: Vec<f64>,
+) -> Result<RuntimeValue, EvaluationError> {
+    results
+        .into_iter()
+        .map(|result| runtime_finite_float(kernel, expr, value, result, MATRIX_NOT_FINITE))
+        .collect::<Result<Vec<_>, _>>()
+        .map(RuntimeValue::List)
+}
+
+/// Read a square row-major `List (List Float)` matrix. `aivi.matrix` validates shapes before
+/// calling the LU intrinsics, so a ragged or non-square argument is an internal failure.
+fn expect_intrinsic_square_matrix(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    index: usize,
+    argument: &RuntimeValue,
+) -> Result<Vec<Vec<f64>>, EvaluationError> {
+    let rows = expect_intrinsic_list(kernel, expr, value, index, argument)?
+        .iter()
+        .map(|row| expect_intrinsic_float_list(kernel, expr, value, index, row))
+        .collect::<Result<Vec<_>, _>>()?;
+    if rows.iter().any(|row| row.len() != rows.len()) {
+        return Err(EvaluationError::IntrinsicFailed {
+            kernel,
+            expr,
+            value,
+            reason: "matrix is not square",
+        });
+    }
+    Ok(rows)
+}
+
+/// Read a rectangular row-major `List (List Float)` matrix of any shape.
+fn expect_intrinsic_rectangular_matrix(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    index: usize,
+    argument: &RuntimeValue,
+) -> Result<Vec<Vec<f64>>, EvaluationError> {
+    let rows = expect_intrinsic_list(kernel, expr, value, index, argument)?
+        .iter()
+        .map(|row| expect_intrinsic_float_list(kernel, expr, value, index, row))
+        .collect::<Result<Vec<_>, _>>()?;
+    if rows.windows(2).any(|pair| pair[0].len() != pair[1].len()) {
+        return Err(EvaluationError::IntrinsicFailed {
+            kernel,
+            expr,
+            value,
+            reason: "matrix rows have different lengths",
+        });
+    }
+    Ok(rows)
+}
+
+/// Upper bound on Jacobi sweeps. Both iterations converge quadratically, so well-scaled input
+/// settles in well under ten sweeps; the bound only guards against pathological rounding.
+const JACOBI_MAX_SWEEPS: usize = 64;
+
+/// The rotation `(c, s)` that zeroes the off-diagonal entry of the symmetric 2×2 block
+/// `[[alpha, gamma], [gamma, beta]]` when applied as `p' = c·p - s·q`, `q' = s·p + c·q`.
+fn jacobi_rotation(alpha: f64, beta: f64, gamma: f64) -> (f64, f64) {
+    let zeta = (beta - alpha) / (2.0 * gamma);
+    let tangent = zeta.signum() / (zeta.abs() + zeta.hypot(1.0));
+    let cosine = 1.0 / tangent.hypot(1.0);
+    (cosine, cosine * tangent)
+}
+
+/// Thin singular value decomposition `A = U Σ Vᵀ` of an `m × n` matrix with `k = min(m, n)`:
+/// `u` is `m × k`, `vt` is `k × n`, and `singular_values` holds the `k` entries of `Σ` in
+/// descending order. Computed with one-sided (Hestenes) Jacobi rotations.
+struct SvdFactors {
+    u: Vec<Vec<f64>>,
+    singular_values: Vec<f64>,
+    vt: Vec<Vec<f64>>,
+}
+
+impl SvdFactors {
+    fn new(rows: Vec<Vec<f64>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if height >= width {
+            let (u, singular_values, v) = Self::tall(transpose_rows(&rows, width), height);
+            return Self {
+                u: transpose_rows(&u, height),
+                singular_values,
+                vt: v,
+            };
+        }
+        // `Aᵀ = U' Σ V'ᵀ` gives `A = V' Σ U'ᵀ`, so the factors of the tall transpose swap roles.
+        let (u, singular_values, v) = Self::tall(rows, width);
+        Self {
+            u: transpose_rows(&v, singular_values.len()),
+            singular_values,
+            vt: u,
+        }
+    }
+
+    /// Factor a tall matrix given as its `n` columns of length `m >= n`. Returns the columns of
+    /// `U`, the singular values, and the columns of `V`, all sorted by descending singular value.
+    fn tall(mut columns: Vec<Vec<f64>>, height: usize) -> (Vec<Vec<f64>>, Vec<f64>, Vec<Vec<f64>>) {
+        let width = columns.len();
+        let mut v = (0..width)
+            .map(|column| {
+                (0..width)
+                    .map(|row| if row == column { 1.0 } else { 0.0 })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for _ in 0..JACOBI_MAX_SWEEPS {
+            let mut rotated = false;
+            for p in 0..width {
+                for q in p + 1..width {
+                    let alpha = dot(&columns[p], &columns[p]);
+                    let beta = dot(&columns[q], &columns[q]);
+                    let gamma = dot(&columns[p], &columns[q]);
+                    if gamma == 0.0 || gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+                    let (cosine, sine) = jacobi_rotation(alpha, beta, gamma);
+                    rotate_columns(&mut columns, p, q, cosine, sine);
+                    rotate_columns(&mut v, p, q, cosine, sine);
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+        let mut singular_values = columns
+            .iter()
+            .map(|column| dot(column, column).sqrt())
+            .collect::<Vec<_>>();
+        let mut order = (0..width).collect::<Vec<_>>();
+        order.sort_by(|&left, &right| singular_values[right].total_cmp(&singular_values[left]));
+        let largest = order.first().map_or(0.0, |&index| singular_values[index]);
+        let tolerance = largest * height.max(width) as f64 * f64::EPSILON;
+        let mut u = Vec::with_capacity(width);
+        let mut rank_deficient = Vec::new();
+        for &index in &order {
+            let sigma = singular_values[index];
+            if sigma <= tolerance {
+                singular_values[index] = 0.0;
+                rank_deficient.push(u.len());
+                u.push(vec![0.0; height]);
+            } else {
+                u.push(columns[index].iter().map(|entry| entry / sigma).collect());
+            }
+        }
+        // Columns of `U` for zero singular values are arbitrary as long as `U` stays orthonormal:
+        // extend the basis with whichever unit vector has the largest residual.
+        for slot in rank_deficient {
+            let residual = |axis: usize| {
+                let mut candidate = vec![0.0; height];
+                candidate[axis] = 1.0;
+                for (other, basis) in u.iter().enumerate() {
+                    if other != slot {
+                        let projection = dot(&candidate, basis);
+                        for (entry, basis_entry) in candidate.iter_mut().zip(basis) {
+                            *entry -= projection * basis_entry;
+                        }
+                    }
+                }
+                candidate
+            };
+            let best = (0..height)
+                .map(residual)
+                .max_by(|left, right| dot(left, left).total_cmp(&dot(right, right)))
+                .expect("a rank-deficient tall matrix should have at least one row");
+            let norm = dot(&best, &best).sqrt();
+            u[slot] = best.into_iter().map(|entry| entry / norm).collect();
+        }
+        let singular_values = order.iter().map(|&index| singular_values[index]).collect();
+        let v = order.iter().map(|&index| v[index].clone()).collect();
+        (u, singular_values, v)
+    }
+}
+
+/// Eigenvalues of a symmetric matrix in descending order, computed with cyclic Jacobi rotations.
+/// Returns `None` when the matrix is not symmetric to working precision.
+fn symmetric_eigenvalues(mut rows: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+    let size = rows.len();
+    let scale = rows
+        .iter()
+        .flatten()
+        .fold(0.0_f64, |largest, entry| largest.max(entry.abs()));
+    let tolerance = scale * size as f64 * f64::EPSILON;
+    let symmetric = rows.iter().enumerate().all(|(y, row)| {
+        row.iter()
+            .enumerate()
+            .skip(y + 1)
+            .all(|(x, entry)| (entry - rows[x][y]).abs() <= tolerance)
+    });
+    if !symmetric {
+        return None;
+    }
+    for _ in 0..JACOBI_MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..size {
+            for q in p + 1..size {
+                let gamma = rows[p][q];
+                if gamma == 0.0
+                    || gamma.abs() <= f64::EPSILON * (rows[p][p] * rows[q][q]).abs().sqrt()
+                {
+                    continue;
+                }
+                rotated = true;
+                let (cosine, sine) = jacobi_rotation(rows[p][p], rows[q][q], gamma);
+                rotate_columns(&mut rows, p, q, cosine, sine);
+                for row in &mut rows {
+                    let (left, right) = (row[p], row[q]);
+                    row[p] = cosine * left - sine * right;
+                    row[q] = sine * left + cosine * right;
+                }
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+    let mut eigenvalues = (0..size)
+        .map(|index| rows[index][index])
+        .collect::<Vec<_>>();
+    eigenvalues.sort_by(|left, right| right.total_cmp(left));
+    Some(eigenvalues)
+}
+
+/// Replace vectors `p` and `q` by `c·p - s·q` and `s·p + c·q`.
+fn rotate_columns(vectors: &mut [Vec<f64>], p: usize, q: usize, cosine: f64, sine: f64) {
+    let (head, tail) = vectors.split_at_mut(q);
+    for (left, right) in head[p].iter_mut().zip(&mut tail[0]) {
+        let (old_left, old_right) = (*left, *right);
+        *left = cosine * old_left - sine * old_right;
+        *right = sine * old_left + cosine * old_right;
+    }
+}
+
+fn dot(left: &[f64], right: &[f64]) -> f64 {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| left * right)
+        .sum()
+}
+
+/// Transpose `rows`, which all have `width` entries. `width` is passed explicitly so a matrix
+/// with no rows still transposes to the right number of (empty) rows.
+fn transpose_rows(rows: &[Vec<f64>], width: usize) -> Vec<Vec<f64>> {
+    (0..width)
+        .map(|column| rows.iter().map(|row| row[column]).collect())
+        .collect()
+}
+
+/// LU factors of a square matrix computed with partial pivoting. `rows` stores `L` below the
+/// diagonal (its unit diagonal is implied) and `U` on and above it; `pivots[i]` is the original
+/// index of the row that ended up at position `i`.
+struct LuDecomposition {
+    rows: Vec<Vec<f64>>,
+    pivots: Vec<usize>,
+    swaps: usize,
+}
+
+impl LuDecomposition {
+    /// Factor `rows`, or `None` when the matrix is singular to working precision.
+    fn new(mut rows: Vec<Vec<f64>>) -> Option<Self> {
+        let size = rows.len();
+        let scale = rows
+            .iter()
+            .flatten()
+            .fold(0.0_f64, |largest, entry| largest.max(entry.abs()));
+        let tolerance = scale * size as f64 * f64::EPSILON;
+        let mut pivots = (0..size).collect::<Vec<_>>();
+        let mut swaps = 0;
+        for column in 0..size {
+            let pivot = (column..size)
+                .max_by(|&left, &right| {
+                    rows[left][column]
+                        .abs()
+                        .total_cmp(&rows[right][column].abs())
+                })
+                .expect("pivot search range should not be empty");
+            if rows[pivot][column].abs() <= tolerance {
+                return None;
+            }
+            if pivot != column {
+                rows.swap(pivot, column);
+                pivots.swap(pivot, column);
+                swaps += 1;
+            }
+            let (above, below) = rows.split_at_mut(column + 1);
+            let pivot_row = &above[column];
+            for row in below {
+                let factor = row[column] / pivot_row[column];
+                row[column] = factor;
+                for (entry, pivot_entry) in
+                    row[column + 1..].iter_mut().zip(&pivot_row[column + 1..])
+                {
+                    *entry -= factor * pivot_entry;
+                }
+            }
+        }
+        Some(Self {
+            rows,
+            pivots,
+            swaps,
+        })
+    }
+
+    fn determinant(&self) -> f64 {
+        let sign = if self.swaps.is_multiple_of(2) { 1.0 } else { -1.0 };
+        self.rows
+            .iter()
+            .enumerate()
+            .fold(sign, |product, (index, row)| product * row[index])
+    }
+
+    /// Solve `A x = rhs`; `rhs` must have one entry per row.
+    fn solve(&self, rhs: &[f64]) -> Vec<f64> {
+        let mut solution = self
+            .pivots
+            .iter()
+            .map(|&source| rhs[source])
+            .collect::<Vec<_>>();
+        for index in 0..solution.len() {
+            let lower = self.rows[index][..index]
+                .iter()
+                .zip(&solution[..index])
+                .map(|(factor, known)| factor * known)
+                .sum::<f64>();
+            solution[index] -= lower;
+        }
+        for index in (0..solution.len()).rev() {
+            let upper = self.rows[index][index + 1..]
+                .iter()
+                .zip(&solution[index + 1..])
+                .map(|(factor, known)| factor * known)
+                .sum::<f64>();
+            solution[index] = (solution[index] - upper) / self.rows[index][index];
+        }
+        solution
+    }
+
+    fn inverse(&self) -> Vec<Vec<f64>> {
+        let size = self.rows.len();
+        let columns = (0..size)
+            .map(|column| {
+                let unit = (0..size)
+                    .map(|row| if row == column { 1.0 } else { 0.0 })
+                    .collect::<Vec<_>>();
+                self.solve(&unit)
+            })
+            .collect::<Vec<_>>();
+        (0..size)
+            .map(|row| columns.iter().map(|column| column[row]).collect())
+            .collect()
+    }
+}
+
+/// Directed graph read from an `aivi.graph` `{ nodes, edges }` record. Node ids are renumbered to
+/// dense indices in the order they appear in `nodes`.
+struct WeightedGraph {
+    nodes: Vec<i64>,
+    indices: BTreeMap<i64, usize>,
+    adjacency: Vec<Vec<(usize, f64)>>,
+}
+
+impl WeightedGraph {
+    fn index_of(
+        &self,
+        kernel: KernelId,
+        expr: KernelExprId,
+        value: IntrinsicValue,
+        node: i64,
+    ) -> Result<usize, EvaluationError> {
+        self.indices
+            .get(&node)
+            .copied()
+            .ok_or(EvaluationError::IntrinsicFailed {
+                kernel,
+                expr,
+                value,
+                reason: "node is not in the graph",
+            })
+    }
+
+    /// Dijkstra from `start`. Returns the distance to and the predecessor of every node;
+    /// unreachable nodes have neither.
+    fn dijkstra(&self, start: usize) -> (Vec<Option<f64>>, Vec<Option<usize>>) {
+        let mut distances = vec![None; self.nodes.len()];
+        let mut predecessors = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::new();
+        distances[start] = Some(0.0);
+        queue.push(Reverse(DijkstraEntry {
+            distance: 0.0,
+            node: start,
+        }));
+        while let Some(Reverse(DijkstraEntry { distance, node })) = queue.pop() {
+            if distances[node].is_some_and(|best| distance > best) {
+                continue;
+            }
+            for &(next, weight) in &self.adjacency[node] {
+                let candidate = distance + weight;
+                if distances[next].is_none_or(|best| candidate < best) {
+                    distances[next] = Some(candidate);
+                    predecessors[next] = Some(node);
+                    queue.push(Reverse(DijkstraEntry {
+                        distance: candidate,
+                        node: next,
+                    }));
+                }
+            }
+        }
+        (distances, predecessors)
+    }
+}
+
+/// Priority-queue entry for [`WeightedGraph::dijkstra`], ordered by distance.
+#[derive(PartialEq)]
+struct DijkstraEntry {
+    distance: f64,
+    node: usize,
+}
+
+impl Eq for DijkstraEntry {}
+
+impl PartialOrd for DijkstraEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DijkstraEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.node.cmp(&other.node))
+    }
+}
+
+fn expect_intrinsic_graph(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    index: usize,
+    argument: &RuntimeValue,
+) -> Result<WeightedGraph, EvaluationError> {
+    let found = strip_signal(argument.clone());
+    let invalid = || invalid_intrinsic_argument(kernel, expr, value, index, found.clone());
+    let RuntimeValue::Record(fields) = &found else {
+        return Err(invalid());
+    };
+    let nodes = record_field(fields, "nodes")
+        .ok_or_else(invalid)
+        .and_then(|nodes| expect_intrinsic_list(kernel, expr, value, index, nodes))?
+        .iter()
+        .map(|node| expect_intrinsic_i64(kernel, expr, value, index, node))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut indices = BTreeMap::new();
+    for node in &nodes {
+        let next = indices.len();
+        indices.entry(*node).or_insert(next);
+    }
+    let mut graph = WeightedGraph {
+        adjacency: vec![Vec::new(); nodes.len()],
+        nodes,
+        indices,
+    };
+    let edges = record_field(fields, "edges")
+        .ok_or_else(invalid)
+        .and_then(|edges| expect_intrinsic_list(kernel, expr, value, index, edges))?;
+    for edge in edges {
+        let RuntimeValue::Record(edge) = strip_signal(edge.clone()) else {
+            return Err(invalid());
+        };
+        let endpoint = |label| {
+            record_field(&edge, label)
+                .ok_or_else(invalid)
+                .and_then(|node| expect_intrinsic_i64(kernel, expr, value, index, node))
+                .and_then(|node| graph.index_of(kernel, expr, value, node))
+        };
+        let from = endpoint("from")?;
+        let to = endpoint("to")?;
+        let weight = record_field(&edge, "weight")
+            .ok_or_else(invalid)
+            .and_then(|weight| expect_intrinsic_float(kernel, expr, value, index, weight))?;
+        if weight < 0.0 {
+            return Err(EvaluationError::IntrinsicFailed {
+                kernel,
+                expr,
+                value,
+                reason: "edge weights must not be negative",
+            });
+        }
+        graph.adjacency[from].push((to, weight));
+    }
+    Ok(graph)
+}
+
 fn expect_intrinsic_list(
     kernel: KernelId,
     expr: KernelExprId,
@@ -1813,12 +3572,104 @@ fn expect_intrinsic_text_map(
                     strip_signal(value_entry.clone()),
                 ));
             };
-            Ok((key, value_text))
+            Ok((Box::from(&*key), Box::from(&*value_text)))
         })
         .collect::<Result<Vec<_>, _>>()
         .map(Vec::into_boxed_slice)
 }
 
+/// Read an `http.request` record into a task plan, prefixing its `url` with the handle's `base`.
+fn expect_intrinsic_http_request(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    index: usize,
+    base: &str,
+    argument: &RuntimeValue,
+) -> Result<RuntimeTaskPlan, EvaluationError> {
+    let found = strip_signal(argument.clone());
+    let RuntimeValue::Record(fields) = &found else {
+        return Err(invalid_intrinsic_argument(
+            kernel, expr, value, index, found,
+        ));
+    };
+    let invalid = || invalid_intrinsic_argument(kernel, expr, value, index, found.clone());
+    let field = |label| {
+        record_field(fields, label)
+            .map(|field| strip_signal(field.clone()))
+            .ok_or_else(invalid)
+    };
+    let text = |label| match field(label)? {
+        RuntimeValue::Text(text) => Ok(Box::<str>::from(&*text)),
+        _ => Err(invalid()),
+    };
+    let optional = |label| match field(label)? {
+        RuntimeValue::OptionNone => Ok(None),
+        RuntimeValue::OptionSome(inner) => Ok(Some(strip_signal(*inner))),
+        _ => Err(invalid()),
+    };
+    let optional_int = |label| match optional(label)? {
+        None => Ok(None),
+        Some(RuntimeValue::Int(number)) => Ok(Some(number)),
+        Some(_) => Err(invalid()),
+    };
+    let RuntimeValue::List(header_entries) = field("headers")? else {
+        return Err(invalid());
+    };
+    let headers = header_entries
+        .into_iter()
+        .map(|entry| match strip_signal(entry) {
+            RuntimeValue::Tuple(pair) => match pair.as_slice() {
+                [RuntimeValue::Text(name), RuntimeValue::Text(header)] => {
+                    Ok((Box::from(&**name), Box::from(&**header)))
+                }
+                _ => Err(invalid()),
+            },
+            _ => Err(invalid()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let body = match optional("body")? {
+        None => None,
+        Some(RuntimeValue::Text(body)) => Some(Box::from(&*body)),
+        Some(_) => return Err(invalid()),
+    };
+    Ok(RuntimeTaskPlan::HttpRequest {
+        method: text("method")?,
+        url: format!("{base}{}", text("url")?).into(),
+        headers: headers.into(),
+        body,
+        timeout_ms: optional_int("timeoutMs")?,
+        max_redirects: optional_int("maxRedirects")?,
+    })
+}
+
+/// Read the `id` of a handle record produced by `aivi.fs.open`, `aivi.ws.connect`, or
+/// `aivi.process.spawn`.
+fn expect_intrinsic_handle_id(
+    kernel: KernelId,
+    expr: KernelExprId,
+    value: IntrinsicValue,
+    index: usize,
+    argument: &RuntimeValue,
+) -> Result<i64, EvaluationError> {
+    let found = strip_signal(argument.clone());
+    let RuntimeValue::Record(fields) = &found else {
+        return Err(invalid_intrinsic_argument(
+            kernel, expr, value, index, found,
+        ));
+    };
+    match record_field(fields, "id").map(|id| strip_signal(id.clone())) {
+        Some(RuntimeValue::Int(id)) => Ok(id),
+        _ => Err(invalid_intrinsic_argument(
+            kernel,
+            expr,
+            value,
+            index,
+            found.clone(),
+        )),
+    }
+}
+
 fn expect_intrinsic_db_connection(
     kernel: KernelId,
     expr: KernelExprId,
@@ -1842,7 +3693,9 @@ fn expect_intrinsic_db_connection(
         ));
     };
     match strip_signal(database.clone()) {
-        RuntimeValue::Text(database) => Ok(RuntimeDbConnection { database }),
+        RuntimeValue::Text(database) => Ok(RuntimeDbConnection {
+            database: Box::from(&*database),
+        }),
         found => Err(invalid_intrinsic_argument(
             kernel, expr, value, index, found,
         )),
@@ -1900,7 +3753,7 @@ fn expect_intrinsic_db_statement(
         ));
     };
     let sql = match strip_signal(sql.clone()) {
-        RuntimeValue::Text(sql) => sql,
+        RuntimeValue::Text(sql) => Box::from(&*sql),
         found => {
             return Err(invalid_intrinsic_argument(
                 kernel, expr, value, index, found,
@@ -2118,3 +3971,50 @@ fn shared_suffixed_integer_suffix(
         _ => Some(None),
     }
 }
+
+/// Greedily fill lines of at most `width` grapheme clusters, breaking at whitespace. Words wider
+/// than a line are split across lines. Line breaks in `text` are kept, and each input line is
+/// wrapped on its own.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut line_width = 0;
+        for word in paragraph.split_whitespace() {
+            let graphemes = word.graphemes(true).collect::<Vec<_>>();
+            if line_width > 0 && line_width + 1 + graphemes.len() <= width {
+                line.push(' ');
+                line.push_str(word);
+                line_width += 1 + graphemes.len();
+                continue;
+            }
+            if line_width > 0 {
+                lines.push(std::mem::take(&mut line));
+            }
+            let mut chunks = graphemes.chunks(width).peekable();
+            while let Some(chunk) = chunks.next() {
+                line = chunk.concat();
+                line_width = chunk.len();
+                if chunks.peek().is_some() {
+                    lines.push(std::mem::take(&mut line));
+                }
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Keep at most `width` grapheme clusters, ending in an ellipsis when anything was cut.
+fn truncate_text(text: &str, width: usize) -> String {
+    const ELLIPSIS: char = '\u{2026}';
+    if text.graphemes(true).nth(width).is_none() {
+        return text.to_owned();
+    }
+    let Some(kept) = width.checked_sub(1) else {
+        return String::new();
+    };
+    let mut truncated = text.graphemes(true).take(kept).collect::<String>();
+    truncated.push(ELLIPSIS);
+    truncated
+}
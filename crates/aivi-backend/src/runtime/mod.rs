@@ -1,11 +1,15 @@
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    borrow::Cow,
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, BinaryHeap},
     fmt,
     hash::Hash,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
 use indexmap::IndexMap;
+use unicode_segmentation::UnicodeSegmentation;
 
 use aivi_hir::{DomainMemberHandle, IntrinsicValue, ItemId as HirItemId, SumConstructorHandle};
 
@@ -17,7 +21,7 @@ use crate::{
     InlinePipePatternKind, InlinePipeStageKind, InlineSubjectId, ItemId, KernelExprId,
     KernelExprKind, KernelId, LayoutId, LayoutKind, PrimitiveType, Program, ProjectionBase,
     SubjectRef, UnaryOperator,
-    numeric::{RuntimeBigInt, RuntimeDecimal, RuntimeFloat},
+    numeric::{RuntimeBigInt, RuntimeDecimal, RuntimeFloat, decimal_rounding_strategy},
 };
 
 include!("values.rs");
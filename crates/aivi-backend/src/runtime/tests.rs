@@ -369,3 +369,196 @@ fn structural_equality_matches_bytes_maps_and_sets() {
             .expect("set equality should be order-independent")
     );
 }
+
+const ORDER_PROPERTY_CASES: u64 = 256;
+
+fn random_runtime_value(rng: &mut fastrand::Rng, depth: usize) -> RuntimeValue {
+    let choice = if depth == 0 {
+        rng.u8(0..2)
+    } else {
+        rng.u8(0..5)
+    };
+    match choice {
+        0 => RuntimeValue::Int(rng.i64(-8..8)),
+        1 => RuntimeValue::Text(format!("t{}", rng.u8(0..8)).into()),
+        2 => random_runtime_record(rng, depth - 1),
+        3 => {
+            let len = rng.usize(0..4);
+            // Distinct elements keep a set a set after shuffling.
+            RuntimeValue::Set((0..len as i64).map(RuntimeValue::Int).collect())
+        }
+        _ => random_runtime_map(rng, depth - 1),
+    }
+}
+
+fn random_runtime_record(rng: &mut fastrand::Rng, depth: usize) -> RuntimeValue {
+    let len = rng.usize(0..6);
+    RuntimeValue::Record(
+        (0..len)
+            .map(|index| RuntimeRecordField {
+                label: format!("field{index}").into(),
+                value: random_runtime_value(rng, depth),
+            })
+            .collect(),
+    )
+}
+
+fn random_runtime_map(rng: &mut fastrand::Rng, depth: usize) -> RuntimeValue {
+    let len = rng.usize(0..6);
+    RuntimeValue::Map(RuntimeMap::from_entries(
+        (0..len)
+            .map(|index| RuntimeMapEntry {
+                // Record keys exercise hashed lookups that must ignore field order.
+                key: if rng.bool() {
+                    RuntimeValue::Text(format!("key{index}").into())
+                } else {
+                    RuntimeValue::Record(vec![
+                        RuntimeRecordField {
+                            label: "id".into(),
+                            value: RuntimeValue::Int(index as i64),
+                        },
+                        RuntimeRecordField {
+                            label: "tag".into(),
+                            value: RuntimeValue::Text("key".into()),
+                        },
+                    ])
+                },
+                value: random_runtime_value(rng, depth),
+            })
+            .collect(),
+    ))
+}
+
+/// Rebuild `value` with every record, set, and map reordered, keeping its contents.
+fn reorder_runtime_value(rng: &mut fastrand::Rng, value: &RuntimeValue) -> RuntimeValue {
+    match value {
+        RuntimeValue::Record(fields) => {
+            let mut fields = fields
+                .iter()
+                .map(|field| RuntimeRecordField {
+                    label: field.label.clone(),
+                    value: reorder_runtime_value(rng, &field.value),
+                })
+                .collect::<Vec<_>>();
+            rng.shuffle(&mut fields);
+            RuntimeValue::Record(fields)
+        }
+        RuntimeValue::Set(elements) => {
+            let mut elements = elements
+                .iter()
+                .map(|element| reorder_runtime_value(rng, element))
+                .collect::<Vec<_>>();
+            rng.shuffle(&mut elements);
+            RuntimeValue::Set(elements)
+        }
+        RuntimeValue::Map(map) => {
+            let mut entries = map
+                .iter()
+                .map(|(key, value)| RuntimeMapEntry {
+                    key: reorder_runtime_value(rng, key),
+                    value: reorder_runtime_value(rng, value),
+                })
+                .collect::<Vec<_>>();
+            rng.shuffle(&mut entries);
+            RuntimeValue::Map(RuntimeMap::from_entries(entries))
+        }
+        other => other.clone(),
+    }
+}
+
+#[test]
+fn structural_equality_ignores_record_and_map_order_for_random_values() {
+    let kernel = KernelId::from_raw(0);
+    let expr = KernelExprId::from_raw(0);
+    let mut rng = fastrand::Rng::with_seed(0x5eed_1293);
+
+    for case in 0..ORDER_PROPERTY_CASES {
+        let original = if case % 2 == 0 {
+            random_runtime_record(&mut rng, 3)
+        } else {
+            random_runtime_map(&mut rng, 3)
+        };
+        let reordered = reorder_runtime_value(&mut rng, &original);
+        assert!(
+            structural_eq(kernel, expr, &original, &reordered)
+                .expect("records and maps should compare structurally"),
+            "case {case}: {original} should equal its reordered copy {reordered}"
+        );
+        assert!(
+            structural_eq(kernel, expr, &reordered, &original)
+                .expect("records and maps should compare structurally"),
+            "case {case}: equality should be symmetric for {original}"
+        );
+    }
+}
+
+#[test]
+fn structural_equality_still_sees_changed_record_fields_after_reordering() {
+    let kernel = KernelId::from_raw(0);
+    let expr = KernelExprId::from_raw(0);
+    let mut rng = fastrand::Rng::with_seed(0x5eed_0001);
+
+    for case in 0..ORDER_PROPERTY_CASES {
+        let RuntimeValue::Record(mut fields) = random_runtime_record(&mut rng, 2) else {
+            unreachable!("random_runtime_record always builds a record")
+        };
+        if fields.is_empty() {
+            continue;
+        }
+        let original = RuntimeValue::Record(fields.clone());
+        let changed = rng.usize(0..fields.len());
+        fields[changed].value = RuntimeValue::Text("changed".into());
+        let reordered = reorder_runtime_value(&mut rng, &RuntimeValue::Record(fields));
+        assert!(
+            !structural_eq(kernel, expr, &original, &reordered)
+                .expect("records should compare structurally"),
+            "case {case}: changing field {changed} of {original} should break equality"
+        );
+    }
+}
+
+#[test]
+fn runtime_map_hash_ignores_insertion_order() {
+    use std::hash::{BuildHasher, BuildHasherDefault};
+
+    let hasher = BuildHasherDefault::<std::collections::hash_map::DefaultHasher>::default();
+    let mut rng = fastrand::Rng::with_seed(0x5eed_0002);
+
+    for case in 0..ORDER_PROPERTY_CASES {
+        let len = rng.usize(0..8);
+        let mut entries = (0..len as i64)
+            .map(|index| RuntimeMapEntry {
+                key: RuntimeValue::Int(index),
+                value: RuntimeValue::Int(rng.i64(..)),
+            })
+            .collect::<Vec<_>>();
+        let original = RuntimeMap::from_entries(entries.clone());
+        rng.shuffle(&mut entries);
+        let reordered = RuntimeMap::from_entries(entries);
+        assert_eq!(original, reordered, "case {case}");
+        assert_eq!(
+            hasher.hash_one(&original),
+            hasher.hash_one(&reordered),
+            "case {case}: equal maps must hash equally"
+        );
+    }
+}
+
+#[test]
+fn runtime_value_identity_ignores_record_and_set_order() {
+    use std::hash::{BuildHasher, BuildHasherDefault};
+
+    let hasher = BuildHasherDefault::<std::collections::hash_map::DefaultHasher>::default();
+    let mut rng = fastrand::Rng::with_seed(0x5eed_0003);
+
+    for case in 0..ORDER_PROPERTY_CASES {
+        let original = random_runtime_value(&mut rng, 3);
+        let reordered = reorder_runtime_value(&mut rng, &original);
+        assert_eq!(original, reordered, "case {case}");
+        assert_eq!(
+            hasher.hash_one(&original),
+            hasher.hash_one(&reordered),
+            "case {case}: reordered values must hash equally so map keys stay O(1) lookups"
+        );
+    }
+}
@@ -52,14 +52,28 @@ impl RuntimeMap {
 }
 
 impl std::hash::Hash for RuntimeMap {
+    /// `IndexMap` equality ignores entry order, so the hash must too.
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        for (k, v) in &self.0 {
-            k.hash(state);
-            v.hash(state);
-        }
+        hash_unordered(self.0.iter(), state);
     }
 }
 
+/// Hash a collection whose equality ignores element order: each element is hashed on its own
+/// with a fixed-key hasher and the results are combined with a commutative sum.
+fn hash_unordered<T: Hash, H: std::hash::Hasher>(
+    elements: impl ExactSizeIterator<Item = T>,
+    state: &mut H,
+) {
+    use std::hash::{BuildHasher, BuildHasherDefault};
+    let hasher = BuildHasherDefault::<std::collections::hash_map::DefaultHasher>::default();
+    state.write_usize(elements.len());
+    state.write_u64(
+        elements
+            .map(|element| hasher.hash_one(element))
+            .fold(0u64, u64::wrapping_add),
+    );
+}
+
 impl<'a> IntoIterator for &'a RuntimeMap {
     type Item = (&'a RuntimeValue, &'a RuntimeValue);
     type IntoIter = indexmap::map::Iter<'a, RuntimeValue, RuntimeValue>;
@@ -271,6 +285,21 @@ pub enum RuntimeTaskPlan {
     FsDeleteDir {
         path: Box<str>,
     },
+    /// Open `path` in `mode` (`"r"`, `"w"`, or `"a"`) and produce a `{ id, path }` handle record.
+    FsOpen {
+        path: Box<str>,
+        mode: Box<str>,
+    },
+    FsReadLine {
+        handle: i64,
+    },
+    FsWrite {
+        handle: i64,
+        text: Box<str>,
+    },
+    FsClose {
+        handle: i64,
+    },
     JsonValidate {
         json: Box<str>,
     },
@@ -291,9 +320,26 @@ pub enum RuntimeTaskPlan {
     JsonMinify {
         json: Box<str>,
     },
+    /// Decode one JSON document into the sum type identified by `item`/`type_name`, using the
+    /// `JsonNull`/`JsonBool`/`JsonNumber`/`JsonString`/`JsonArray`/`JsonObject` constructors.
+    JsonParseInto {
+        json: Box<str>,
+        item: HirItemId,
+        type_name: Box<str>,
+    },
+    /// Like [`RuntimeTaskPlan::JsonParseInto`], but decodes newline-delimited JSON into a list.
+    JsonParseStreamInto {
+        json: Box<str>,
+        item: HirItemId,
+        type_name: Box<str>,
+    },
     // Time task plans
     TimeNowMs,
     TimeMonotonicMs,
+    /// Wait `ms` milliseconds; negative durations return at once.
+    TimeSleep {
+        ms: i64,
+    },
     TimeFormat {
         epoch_ms: i64,
         pattern: Box<str>,
@@ -302,6 +348,11 @@ pub enum RuntimeTaskPlan {
         text: Box<str>,
         pattern: Box<str>,
     },
+    // Clipboard task plans
+    ClipboardRead,
+    ClipboardWrite {
+        text: Box<str>,
+    },
     // Env task plans
     EnvGet {
         name: Box<str>,
@@ -309,6 +360,22 @@ pub enum RuntimeTaskPlan {
     EnvList {
         prefix: Box<str>,
     },
+    // Process task plans
+    ProcessArgs,
+    ProcessExit {
+        code: i64,
+    },
+    // Test task plans
+    MatchesSnapshot {
+        name: Box<str>,
+        content: Box<str>,
+    },
+    AdvanceClock {
+        ms: i64,
+    },
+    SetClock {
+        epoch_ms: i64,
+    },
     // Log task plans
     LogEmit {
         level: Box<str>,
@@ -321,6 +388,8 @@ pub enum RuntimeTaskPlan {
     },
     // Random float task plan
     RandomFloat,
+    /// Generate an Ed25519 key pair from an OS-random seed.
+    CryptoEd25519Keypair,
     // Regex task plans
     RegexIsMatch {
         pattern: Box<str>,
@@ -338,6 +407,14 @@ pub enum RuntimeTaskPlan {
         pattern: Box<str>,
         text: Box<str>,
     },
+    RegexCaptures {
+        pattern: Box<str>,
+        text: Box<str>,
+    },
+    RegexCapturesAll {
+        pattern: Box<str>,
+        text: Box<str>,
+    },
     RegexReplace {
         pattern: Box<str>,
         replacement: Box<str>,
@@ -378,6 +455,59 @@ pub enum RuntimeTaskPlan {
         url: Box<str>,
         body: Box<str>,
     },
+    /// Send an arbitrary request; error statuses still produce a `{ status, headers, body }`
+    /// record, and only transport failures such as timeouts fail the task.
+    HttpRequest {
+        method: Box<str>,
+        url: Box<str>,
+        headers: Box<[(Box<str>, Box<str>)]>,
+        body: Option<Box<str>>,
+        timeout_ms: Option<i64>,
+        max_redirects: Option<i64>,
+    },
+    /// Stream a response body straight into the file at `path`.
+    HttpDownload {
+        url: Box<str>,
+        path: Box<str>,
+    },
+    /// Connect to a `ws://` or `wss://` URL and produce a `{ id, url }` socket record.
+    WsConnect {
+        url: Box<str>,
+    },
+    WsSend {
+        handle: i64,
+        text: Box<str>,
+    },
+    WsRecv {
+        handle: i64,
+    },
+    /// Like `WsRecv`, but produce `None` when no message arrives within `timeout_ms`.
+    WsRecvTimeout {
+        handle: i64,
+        timeout_ms: i64,
+    },
+    WsClose {
+        handle: i64,
+    },
+    /// Start a child process with piped stdin/stdout and produce a `{ id, command }` record.
+    ProcessSpawn {
+        command: Box<str>,
+        args: Box<[Box<str>]>,
+        env: Box<[(Box<str>, Box<str>)]>,
+    },
+    ProcessWait {
+        handle: i64,
+    },
+    ProcessKill {
+        handle: i64,
+    },
+    ProcessReadStdout {
+        handle: i64,
+    },
+    ProcessWriteStdin {
+        handle: i64,
+        text: Box<str>,
+    },
     DbusCall {
         destination: Box<str>,
         path: Box<str>,
@@ -440,6 +570,22 @@ pub enum RuntimeTaskPlan {
     Join {
         outer: Box<RuntimeTaskPlan>,
     },
+    /// Execute `inner` and produce `Ok value` on success or `Err message` when it fails with a
+    /// catchable error. Fatal errors are not caught.
+    Attempt {
+        inner: Box<RuntimeTaskPlan>,
+    },
+    /// Execute `inner`, then always execute `cleanup`, even when `inner` failed. The result is
+    /// the result of `inner`; a cleanup failure is reported only when `inner` succeeded.
+    Finally {
+        inner: Box<RuntimeTaskPlan>,
+        cleanup: Box<RuntimeTaskPlan>,
+    },
+    /// Execute every task concurrently and produce their results as a list in task order. The
+    /// first task to fail cancels the others and its error becomes the error of the whole plan.
+    Parallel {
+        tasks: Vec<RuntimeTaskPlan>,
+    },
 }
 
 impl fmt::Display for RuntimeTaskPlan {
@@ -461,31 +607,52 @@ impl fmt::Display for RuntimeTaskPlan {
             Self::FsRename { from, to } => write!(f, "rename({from}, {to})"),
             Self::FsCopy { from, to } => write!(f, "copy({from}, {to})"),
             Self::FsDeleteDir { path } => write!(f, "deleteDir({path})"),
+            Self::FsOpen { path, mode } => write!(f, "open({path}, {mode})"),
+            Self::FsReadLine { handle } => write!(f, "readLine(#{handle})"),
+            Self::FsWrite { handle, .. } => write!(f, "write(#{handle})"),
+            Self::FsClose { handle } => write!(f, "close(#{handle})"),
             Self::JsonValidate { json } => write!(f, "json.validate({json})"),
             Self::JsonGet { json, key } => write!(f, "json.get({json}, {key})"),
             Self::JsonAt { json, index } => write!(f, "json.at({json}, {index})"),
             Self::JsonKeys { json } => write!(f, "json.keys({json})"),
             Self::JsonPretty { json } => write!(f, "json.pretty({json})"),
             Self::JsonMinify { json } => write!(f, "json.minify({json})"),
+            Self::JsonParseInto { json, .. } => write!(f, "json.parse({json})"),
+            Self::JsonParseStreamInto { json, .. } => write!(f, "json.parseStream({json})"),
             Self::TimeNowMs => f.write_str("time.nowMs"),
             Self::TimeMonotonicMs => f.write_str("time.monotonicMs"),
+            Self::TimeSleep { ms } => write!(f, "time.sleep({ms})"),
             Self::TimeFormat { epoch_ms, pattern } => {
                 write!(f, "time.format({epoch_ms}, {pattern})")
             }
             Self::TimeParse { text, pattern } => write!(f, "time.parse({text}, {pattern})"),
+            Self::ClipboardRead => f.write_str("clipboard.read"),
+            Self::ClipboardWrite { text } => write!(f, "clipboard.write({text})"),
             Self::EnvGet { name } => write!(f, "env.get({name})"),
             Self::EnvList { prefix } => write!(f, "env.list({prefix})"),
+            Self::ProcessArgs => f.write_str("process.args"),
+            Self::ProcessExit { code } => write!(f, "process.exit({code})"),
+            Self::MatchesSnapshot { name, .. } => write!(f, "test.matchesSnapshot({name})"),
+            Self::AdvanceClock { ms } => write!(f, "test.advanceClock({ms})"),
+            Self::SetClock { epoch_ms } => write!(f, "test.setClock({epoch_ms})"),
             Self::LogEmit { level, message } => write!(f, "log.emit({level}, {message})"),
             Self::LogEmitContext { level, message, .. } => {
                 write!(f, "log.emitContext({level}, {message})")
             }
             Self::RandomFloat => f.write_str("random.randomFloat"),
+            Self::CryptoEd25519Keypair => f.write_str("crypto.ed25519Keypair"),
             Self::RegexIsMatch { pattern, text } => write!(f, "regex.isMatch({pattern}, {text})"),
             Self::RegexFind { pattern, text } => write!(f, "regex.find({pattern}, {text})"),
             Self::RegexFindText { pattern, text } => {
                 write!(f, "regex.findText({pattern}, {text})")
             }
             Self::RegexFindAll { pattern, text } => write!(f, "regex.findAll({pattern}, {text})"),
+            Self::RegexCaptures { pattern, text } => {
+                write!(f, "regex.captures({pattern}, {text})")
+            }
+            Self::RegexCapturesAll { pattern, text } => {
+                write!(f, "regex.capturesAll({pattern}, {text})")
+            }
             Self::RegexReplace {
                 pattern,
                 replacement,
@@ -508,6 +675,20 @@ impl fmt::Display for RuntimeTaskPlan {
             Self::HttpDelete { url } => write!(f, "http.delete({url})"),
             Self::HttpHead { url } => write!(f, "http.head({url})"),
             Self::HttpPostJson { url, .. } => write!(f, "http.postJson({url})"),
+            Self::HttpRequest { method, url, .. } => write!(f, "http.request({method} {url})"),
+            Self::HttpDownload { url, path } => write!(f, "http.download({url} -> {path})"),
+            Self::WsConnect { url } => write!(f, "ws.connect({url})"),
+            Self::WsSend { handle, .. } => write!(f, "ws.send(#{handle})"),
+            Self::WsRecv { handle } => write!(f, "ws.recv(#{handle})"),
+            Self::WsRecvTimeout { handle, timeout_ms } => {
+                write!(f, "ws.recvTimeout(#{handle}, {timeout_ms})")
+            }
+            Self::WsClose { handle } => write!(f, "ws.close(#{handle})"),
+            Self::ProcessSpawn { command, .. } => write!(f, "process.spawn({command})"),
+            Self::ProcessWait { handle } => write!(f, "process.wait(#{handle})"),
+            Self::ProcessKill { handle } => write!(f, "process.kill(#{handle})"),
+            Self::ProcessReadStdout { handle } => write!(f, "process.readStdout(#{handle})"),
+            Self::ProcessWriteStdin { handle, .. } => write!(f, "process.writeStdin(#{handle})"),
             Self::DbusCall {
                 destination,
                 path,
@@ -535,6 +716,9 @@ impl fmt::Display for RuntimeTaskPlan {
             Self::Apply { .. } => f.write_str("task.apply(...)"),
             Self::Chain { .. } => f.write_str("task.chain(...)"),
             Self::Join { .. } => f.write_str("task.join(...)"),
+            Self::Attempt { inner } => write!(f, "task.attempt({inner})"),
+            Self::Finally { inner, cleanup } => write!(f, "task.finally({inner}, {cleanup})"),
+            Self::Parallel { tasks } => write!(f, "task.parallel({} tasks)", tasks.len()),
         }
     }
 }
@@ -571,7 +755,7 @@ pub enum RuntimeCallable {
     },
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum RuntimeValue {
     Unit,
     Bool(bool),
@@ -579,7 +763,8 @@ pub enum RuntimeValue {
     Float(RuntimeFloat),
     Decimal(RuntimeDecimal),
     BigInt(RuntimeBigInt),
-    Text(Box<str>),
+    Text(Arc<str>),
+    Char(char),
     Bytes(Box<[u8]>),
     Tuple(Vec<RuntimeValue>),
     List(Vec<RuntimeValue>),
@@ -603,6 +788,101 @@ pub enum RuntimeValue {
     Callable(RuntimeCallable),
 }
 
+/// Records compare by label and sets by membership, matching language `==`, so a record or set
+/// used as a map key is found by one hashed lookup whatever order its fields or elements were
+/// built in. Every other variant compares like a derived implementation would.
+impl PartialEq for RuntimeValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Record(left), Self::Record(right)) => {
+                left.len() == right.len()
+                    && left.iter().enumerate().all(|(index, left_field)| {
+                        let right_field = match right.get(index) {
+                            Some(field) if field.label == left_field.label => Some(field),
+                            _ => right.iter().find(|field| field.label == left_field.label),
+                        };
+                        right_field.is_some_and(|right_field| left_field.value == right_field.value)
+                    })
+            }
+            // Set elements are distinct, so equal lengths plus membership is set equality.
+            (Self::Set(left), Self::Set(right)) => {
+                left.len() == right.len() && left.iter().all(|element| right.contains(element))
+            }
+            (Self::Unit, Self::Unit) | (Self::OptionNone, Self::OptionNone) => true,
+            (Self::Bool(left), Self::Bool(right)) => left == right,
+            (Self::Int(left), Self::Int(right)) => left == right,
+            (Self::Float(left), Self::Float(right)) => left == right,
+            (Self::Decimal(left), Self::Decimal(right)) => left == right,
+            (Self::BigInt(left), Self::BigInt(right)) => left == right,
+            (Self::Text(left), Self::Text(right)) => left == right,
+            (Self::Char(left), Self::Char(right)) => left == right,
+            (Self::Bytes(left), Self::Bytes(right)) => left == right,
+            (Self::Tuple(left), Self::Tuple(right)) | (Self::List(left), Self::List(right)) => {
+                left == right
+            }
+            (Self::Map(left), Self::Map(right)) => left == right,
+            (Self::Sum(left), Self::Sum(right)) => left == right,
+            (Self::OptionSome(left), Self::OptionSome(right))
+            | (Self::ResultOk(left), Self::ResultOk(right))
+            | (Self::ResultErr(left), Self::ResultErr(right))
+            | (Self::ValidationValid(left), Self::ValidationValid(right))
+            | (Self::ValidationInvalid(left), Self::ValidationInvalid(right))
+            | (Self::Signal(left), Self::Signal(right)) => left == right,
+            (Self::Task(left), Self::Task(right)) => left == right,
+            (Self::DbTask(left), Self::DbTask(right)) => left == right,
+            (
+                Self::SuffixedInteger {
+                    raw: left_raw,
+                    suffix: left_suffix,
+                },
+                Self::SuffixedInteger {
+                    raw: right_raw,
+                    suffix: right_suffix,
+                },
+            ) => left_raw == right_raw && left_suffix == right_suffix,
+            (Self::Callable(left), Self::Callable(right)) => left == right,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for RuntimeValue {}
+
+impl Hash for RuntimeValue {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Record(fields) => hash_unordered(fields.iter(), state),
+            Self::Set(elements) => hash_unordered(elements.iter(), state),
+            Self::Unit | Self::OptionNone => {}
+            Self::Bool(value) => value.hash(state),
+            Self::Int(value) => value.hash(state),
+            Self::Float(value) => value.hash(state),
+            Self::Decimal(value) => value.hash(state),
+            Self::BigInt(value) => value.hash(state),
+            Self::Text(value) => value.hash(state),
+            Self::Char(value) => value.hash(state),
+            Self::Bytes(value) => value.hash(state),
+            Self::Tuple(elements) | Self::List(elements) => elements.hash(state),
+            Self::Map(map) => map.hash(state),
+            Self::Sum(sum) => sum.hash(state),
+            Self::OptionSome(inner)
+            | Self::ResultOk(inner)
+            | Self::ResultErr(inner)
+            | Self::ValidationValid(inner)
+            | Self::ValidationInvalid(inner)
+            | Self::Signal(inner) => inner.hash(state),
+            Self::Task(plan) => plan.hash(state),
+            Self::DbTask(plan) => plan.hash(state),
+            Self::SuffixedInteger { raw, suffix } => {
+                raw.hash(state);
+                suffix.hash(state);
+            }
+            Self::Callable(callable) => callable.hash(state),
+        }
+    }
+}
+
 /// Explicit snapshot used when runtime values cross GTK/worker/FFI boundaries.
 ///
 /// Future moving-collector work must not let those boundaries assume that
@@ -618,7 +898,9 @@ impl DetachedRuntimeValue {
     }
 
     pub fn from_runtime_copy(value: &RuntimeValue) -> Self {
-        Self(value.clone())
+        let mut copy = value.clone();
+        copy.unshare_text_storage();
+        Self(copy)
     }
 
     pub fn from_runtime_owned(value: RuntimeValue) -> Self {
@@ -692,7 +974,133 @@ impl RuntimeValue {
         }
     }
 
-    fn write_display_text(&self, target: &mut impl fmt::Write) -> fmt::Result {
+    /// Bytes of this value's outermost layer: its own slot, its text, bytes, or record labels, and
+    /// one slot per direct element. Nested values are not walked, so the cost stays constant per
+    /// element. The allocation budget charges this for each kernel result, which leaves nested
+    /// values uncharged unless they were the result of a kernel call of their own: the elements an
+    /// intrinsic builds inside its result, and the parts of constants and globals, are never
+    /// counted.
+    pub fn shallow_size(&self) -> usize {
+        let slot = std::mem::size_of::<RuntimeValue>();
+        let (payload, children) = match self {
+            Self::Text(text) => (text.len(), 0),
+            Self::Bytes(bytes) => (bytes.len(), 0),
+            Self::SuffixedInteger { raw, suffix } => (raw.len() + suffix.len(), 0),
+            Self::Tuple(elements) | Self::List(elements) | Self::Set(elements) => {
+                (0, elements.len())
+            }
+            Self::Map(map) => (0, map.len().saturating_mul(2)),
+            Self::Record(fields) => (
+                fields.iter().map(|field| field.label.len()).sum(),
+                fields.len(),
+            ),
+            Self::Sum(sum) => (0, sum.fields.len()),
+            Self::OptionSome(_)
+            | Self::ResultOk(_)
+            | Self::ResultErr(_)
+            | Self::ValidationValid(_)
+            | Self::ValidationInvalid(_)
+            | Self::Signal(_) => (0, 1),
+            Self::Callable(
+                RuntimeCallable::ItemBody {
+                    bound_arguments, ..
+                }
+                | RuntimeCallable::BuiltinConstructor {
+                    bound_arguments, ..
+                }
+                | RuntimeCallable::SumConstructor {
+                    bound_arguments, ..
+                }
+                | RuntimeCallable::DomainMember {
+                    bound_arguments, ..
+                }
+                | RuntimeCallable::BuiltinClassMember {
+                    bound_arguments, ..
+                }
+                | RuntimeCallable::IntrinsicValue {
+                    bound_arguments, ..
+                },
+            ) => (0, bound_arguments.len()),
+            Self::Unit
+            | Self::Bool(_)
+            | Self::Int(_)
+            | Self::Float(_)
+            | Self::Decimal(_)
+            | Self::BigInt(_)
+            | Self::Char(_)
+            | Self::OptionNone
+            | Self::Task(_)
+            | Self::DbTask(_) => (0, 0),
+        };
+        slot.saturating_add(payload)
+            .saturating_add(children.saturating_mul(slot))
+    }
+
+    /// Give every `Text` payload reachable through data constructors and bound callable
+    /// arguments its own allocation. Cloning a value shares `Text` storage (interned literals
+    /// in particular), so boundary snapshots call this to stop sharing addresses with live
+    /// values.
+    pub(crate) fn unshare_text_storage(&mut self) {
+        use indexmap::map::MutableKeys;
+
+        let mut stack = vec![self];
+        while let Some(value) = stack.pop() {
+            match value {
+                Self::Text(text) => *text = Arc::from(&**text),
+                Self::Tuple(elements) | Self::List(elements) | Self::Set(elements) => {
+                    stack.extend(elements.iter_mut());
+                }
+                Self::Map(map) => {
+                    for (key, value) in map.0.iter_mut2() {
+                        stack.push(key);
+                        stack.push(value);
+                    }
+                }
+                Self::Record(fields) => stack.extend(fields.iter_mut().map(|field| &mut field.value)),
+                Self::Sum(sum) => stack.extend(sum.fields.iter_mut()),
+                Self::OptionSome(inner)
+                | Self::ResultOk(inner)
+                | Self::ResultErr(inner)
+                | Self::ValidationValid(inner)
+                | Self::ValidationInvalid(inner)
+                | Self::Signal(inner) => stack.push(inner),
+                Self::Callable(
+                    RuntimeCallable::ItemBody {
+                        bound_arguments, ..
+                    }
+                    | RuntimeCallable::BuiltinConstructor {
+                        bound_arguments, ..
+                    }
+                    | RuntimeCallable::SumConstructor {
+                        bound_arguments, ..
+                    }
+                    | RuntimeCallable::DomainMember {
+                        bound_arguments, ..
+                    }
+                    | RuntimeCallable::BuiltinClassMember {
+                        bound_arguments, ..
+                    }
+                    | RuntimeCallable::IntrinsicValue {
+                        bound_arguments, ..
+                    },
+                ) => stack.extend(bound_arguments.iter_mut()),
+                Self::Unit
+                | Self::Bool(_)
+                | Self::Int(_)
+                | Self::Float(_)
+                | Self::Decimal(_)
+                | Self::BigInt(_)
+                | Self::Char(_)
+                | Self::Bytes(_)
+                | Self::OptionNone
+                | Self::Task(_)
+                | Self::DbTask(_)
+                | Self::SuffixedInteger { .. } => {}
+            }
+        }
+    }
+
+    pub(crate) fn write_display_text(&self, target: &mut impl fmt::Write) -> fmt::Result {
         let mut stack = vec![DisplayFrame::Value(self)];
         while let Some(frame) = stack.pop() {
             match frame {
@@ -705,6 +1113,7 @@ impl RuntimeValue {
                     Self::Decimal(value) => write!(target, "{value}")?,
                     Self::BigInt(value) => write!(target, "{value}")?,
                     Self::Text(value) => target.write_str(value)?,
+                    Self::Char(value) => target.write_char(*value)?,
                     Self::Bytes(value) => write!(target, "<bytes:{}>", value.len())?,
                     Self::Tuple(elements) => {
                         push_delimited_values(&mut stack, elements, "(", ")");
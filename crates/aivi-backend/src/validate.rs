@@ -3,6 +3,7 @@ use std::{collections::HashMap, fmt};
 use crate::{
     CallingConvention, DecodePlanId, DecodeStepId, EnvSlotId, InlineSubjectId, ItemId,
     KernelExprId, KernelId, LayoutId, PipelineId, Program, SourceId,
+    item_cycles::value_dependency_cycles,
     kernel::{
         InlinePipePattern, InlinePipePatternKind, InlinePipeRecordPatternField,
         InlinePipeStageKind, KernelExprKind, ParameterRole, ProjectionBase, SubjectRef,
@@ -1474,7 +1475,8 @@ fn validate_kernel(
             | KernelExprKind::Float(_)
             | KernelExprKind::Decimal(_)
             | KernelExprKind::BigInt(_)
-            | KernelExprKind::SuffixedInteger(_) => {}
+            | KernelExprKind::SuffixedInteger(_)
+            | KernelExprKind::Char(_) => {}
             KernelExprKind::Text(text) => {
                 for segment in &text.segments {
                     if let crate::TextSegment::Interpolation { expr, .. } = segment {
@@ -1777,6 +1779,7 @@ fn validate_inline_pipe_pattern(
             InlinePipePatternKind::Wildcard
             | InlinePipePatternKind::Integer(_)
             | InlinePipePatternKind::Text(_) => {}
+            InlinePipePatternKind::TextPrefix { rest, .. } => work.push(rest),
             InlinePipePatternKind::Binding { subject } => {
                 if kernel.inline_subjects.get(subject.index()).is_none() {
                     errors.push(ValidationError::KernelUnknownInlineSubject {
@@ -1840,10 +1843,11 @@ fn push_decode_steps(
 
 /// Validate that there are no circular dependencies between global items.
 ///
-/// A cycle in the item dependency graph (item A transitively depends on itself) means that
-/// runtime evaluation would loop forever. This function builds a dependency map from the
-/// `global_items` lists of all kernels owned by each item, then performs a DFS with
-/// white/gray/black coloring to detect back-edges.
+/// A cycle in the item dependency graph that passes through a value item (the value transitively
+/// depends on itself) means that runtime evaluation would loop forever. This function builds a
+/// dependency map from the `global_items` lists of all kernels owned by each item and reports each
+/// such cycle once. Function bodies only run once the function is applied, so cycles made of
+/// functions alone are recursion and stay valid.
 fn validate_no_item_dep_cycles(program: &Program, errors: &mut Vec<ValidationError>) {
     // Build item -> deps map: for each item, collect all items referenced in any kernel it owns.
     let mut deps: HashMap<ItemId, Vec<ItemId>> = HashMap::new();
@@ -1860,54 +1864,17 @@ fn validate_no_item_dep_cycles(program: &Program, errors: &mut Vec<ValidationErr
         }
     }
 
-    // DFS with white(0)/gray(1)/black(2) coloring to find cycles.
-    // color: 0 = unvisited, 1 = in stack (gray), 2 = done (black)
-    let mut color: HashMap<ItemId, u8> = HashMap::with_capacity(deps.len());
-    let mut path: Vec<ItemId> = Vec::new();
-
-    let all_items: Vec<ItemId> = deps.keys().copied().collect();
-    'outer: for start in all_items {
-        if *color.get(&start).unwrap_or(&0) == 0 {
-            // Iterative DFS using an explicit stack of (item, dep_index).
-            // We mark a node gray (1) when we first visit it, and black (2) when we finish it.
-            color.insert(start, 1);
-            path.push(start);
-            let mut stack: Vec<(ItemId, usize)> = vec![(start, 0)];
-            while let Some((node, idx)) = stack.last().copied() {
-                let neighbors: Vec<ItemId> = deps.get(&node).cloned().unwrap_or_default();
-                if idx < neighbors.len() {
-                    // Advance the index on the stack top before touching stack structure.
-                    stack.last_mut().unwrap().1 += 1;
-                    let neighbor = neighbors[idx];
-                    let neighbor_color = *color.get(&neighbor).unwrap_or(&0);
-                    if neighbor_color == 1 {
-                        // Back-edge found: extract cycle from path.
-                        let cycle_start = path.iter().position(|&n| n == neighbor).unwrap_or(0);
-                        let mut cycle = path[cycle_start..].to_vec();
-                        cycle.push(neighbor);
-                        let names = cycle
-                            .iter()
-                            .map(|item_id| program.item_name(*item_id).to_owned())
-                            .collect();
-                        errors.push(ValidationError::ItemCyclicDependency { cycle, names });
-                        // Mark all gray nodes on path as black to avoid duplicate reports.
-                        for &n in &path {
-                            color.insert(n, 2);
-                        }
-                        stack.clear();
-                        path.clear();
-                        continue 'outer;
-                    } else if neighbor_color == 0 {
-                        color.insert(neighbor, 1);
-                        path.push(neighbor);
-                        stack.push((neighbor, 0));
-                    }
-                } else {
-                    color.insert(node, 2);
-                    path.pop();
-                    stack.pop();
-                }
-            }
-        }
+    let cycles = value_dependency_cycles(&deps, |item_id| {
+        program
+            .items()
+            .get(item_id)
+            .is_some_and(|item| item.parameters.is_empty())
+    });
+    for cycle in cycles {
+        let names = cycle
+            .iter()
+            .map(|item_id| program.item_name(*item_id).to_owned())
+            .collect();
+        errors.push(ValidationError::ItemCyclicDependency { cycle, names });
     }
 }
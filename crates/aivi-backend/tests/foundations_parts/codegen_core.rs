@@ -870,3 +870,89 @@ value missingGreeting : Option Text = "hello"
     );
     assert!(!compiled.object().is_empty());
 }
+
+#[test]
+fn exported_entry_points_link_into_a_native_harness() {
+    let backend = lower_text(
+        "backend-exported-add.aivi",
+        "type Int -> Int -> Int\nfunc add = left right => left + right\n",
+    );
+    let exported = aivi_backend::compile_program_with_exports(
+        &backend,
+        &[aivi_backend::ExportSpec {
+            item: "add".into(),
+            symbol: "aivi_add".into(),
+            parameters: vec![aivi_backend::ExportType::Int, aivi_backend::ExportType::Int],
+            result: aivi_backend::ExportType::Int,
+        }],
+    )
+    .expect("scalar functions should export");
+    assert!(
+        exported
+            .header()
+            .contains("int64_t aivi_add(int64_t arg0, int64_t arg1);"),
+        "header should declare the wrapper, got:\n{}",
+        exported.header()
+    );
+
+    let unique = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock should be after unix epoch")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!(
+        "aivi-backend-exports-{}-{unique}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).expect("temporary directory should be creatable");
+    fs::write(dir.join("add.o"), exported.object()).expect("object should be writable");
+    fs::write(dir.join("aivi_exports.h"), exported.header()).expect("header should be writable");
+    fs::write(
+        dir.join("harness.c"),
+        "#include <stdio.h>\n#include \"aivi_exports.h\"\n\
+         int main(void) { printf(\"%lld\\n\", (long long)aivi_add(20, 22)); return 0; }\n",
+    )
+    .expect("harness should be writable");
+    let link = std::process::Command::new("cc")
+        .current_dir(&dir)
+        .args(["harness.c", "add.o", "-o", "harness"])
+        .output()
+        .expect("a C compiler should be available to link the harness");
+    assert!(
+        link.status.success(),
+        "harness should link against the exported object: {}",
+        String::from_utf8_lossy(&link.stderr)
+    );
+    let run = std::process::Command::new(dir.join("harness"))
+        .output()
+        .expect("linked harness should run");
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "42\n");
+}
+
+#[test]
+fn exports_reject_signatures_that_do_not_match_the_kernel() {
+    let backend = lower_text(
+        "backend-exported-mismatch.aivi",
+        "type Int -> Int -> Int\nfunc add = left right => left + right\n",
+    );
+    let errors = aivi_backend::compile_program_with_exports(
+        &backend,
+        &[aivi_backend::ExportSpec {
+            item: "add".into(),
+            symbol: "aivi_add".into(),
+            parameters: vec![
+                aivi_backend::ExportType::Int,
+                aivi_backend::ExportType::Float,
+            ],
+            result: aivi_backend::ExportType::Int,
+        }],
+    )
+    .expect_err("a Float parameter should not export an Int kernel");
+    assert!(
+        errors.errors().iter().any(|error| matches!(
+            error,
+            CodegenError::InvalidExport { symbol, .. } if symbol.as_ref() == "aivi_add"
+        )),
+        "expected an invalid-export error, got {errors:?}"
+    );
+}
@@ -596,6 +596,54 @@ value missing =
     );
 }
 
+#[test]
+fn evaluates_inline_case_pipe_with_text_literal_and_prefix_patterns() {
+    let backend = lower_text(
+        "backend-inline-case-text-prefix.aivi",
+        r#"
+fun command:Text = arg:Text=>    arg
+     ||> "quit" -> "exit"
+     ||> "--" ++ flag -> "flag:{flag}"
+     ||> "-" ++ "v" -> "verbose"
+     ||> other -> "arg:{other}"
+
+value quit =
+    command "quit"
+
+value longFlag =
+    command "--help"
+
+value emptyFlag =
+    command "--"
+
+value verbose =
+    command "-v"
+
+value plain =
+    command "quitter"
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+
+    for (name, expected) in [
+        ("quit", "exit"),
+        ("longFlag", "flag:help"),
+        ("emptyFlag", "flag:"),
+        ("verbose", "verbose"),
+        ("plain", "arg:quitter"),
+    ] {
+        assert_eq!(
+            evaluator
+                .evaluate_item(find_item(&backend, name), &globals)
+                .expect("inline case with text patterns should evaluate"),
+            RuntimeValue::Text(expected.into()),
+            "unexpected result for {name}"
+        );
+    }
+}
+
 #[test]
 fn evaluates_inline_truthy_falsy_item_bodies() {
     let backend = lower_text(
@@ -824,8 +872,8 @@ value tickSeen =
         .collect::<Vec<_>>();
     assert_eq!(
         variant_names,
-        vec!["Tick", "Turn"],
-        "opaque Event layouts should preserve every declared variant, not only the constructor arms used in one case expression"
+        vec!["Turn", "Tick"],
+        "opaque Event layouts should preserve every declared variant in declaration order, not only the constructor arms used in one case expression"
     );
 }
 
@@ -360,3 +360,138 @@ signal thresholdMet : Signal Bool = atLeast 0
     );
 }
 
+
+
+fn kernel_pretty(program: &aivi_backend::Program, item: &str) -> String {
+    let kernel = program.items()[find_item(program, item)]
+        .body
+        .expect("folded test items should carry a body kernel");
+    let pretty = program.pretty();
+    let header = format!("  kernel{kernel} ");
+    let start = pretty
+        .find(&header)
+        .unwrap_or_else(|| panic!("pretty output should list kernel{kernel}"));
+    let end = pretty[start + header.len()..]
+        .find("\n  kernel")
+        .map_or(pretty.len(), |offset| start + header.len() + offset + 1);
+    pretty[start..end].to_owned()
+}
+
+#[test]
+fn folds_constant_arithmetic_logic_and_interpolation_in_kernels() {
+    let backend = lower_text(
+        "backend-fold-constants.aivi",
+        r#"
+value total:Int = 2 + 3 * 4
+
+value half:Float = 1.5 * 2.0
+
+value flag:Bool = True and False or True
+
+value name:Text = "Ada"
+
+value label:Text = "total {2 + 3} for {name}"
+
+value xs:List Int = [1 + 1, 2]
+"#,
+    );
+    let folded = aivi_backend::fold_constants(&backend);
+    validate_program(&folded).expect("folded program should validate");
+
+    assert_eq!(
+        kernel_pretty(&backend, "total"),
+        "  kernel0 item-body item0 owner=item0 result=layout4
+    convention = runtime-kernel-v1() -> layout4 [by-value]
+    root = expr4
+    expr0: layout4 2
+    expr1: layout4 3
+    expr2: layout4 4
+    expr3: layout4 expr1 * expr2
+    expr4: layout4 expr0 + expr3
+"
+    );
+    assert_eq!(
+        kernel_pretty(&folded, "total"),
+        "  kernel0 item-body item0 owner=item0 result=layout4
+    convention = runtime-kernel-v1() -> layout4 [by-value]
+    root = expr4
+    expr0: layout4 2
+    expr1: layout4 3
+    expr2: layout4 4
+    expr3: layout4 12
+    expr4: layout4 14
+"
+    );
+    assert!(kernel_pretty(&backend, "half").contains("expr2: layout77 expr0 * expr1"));
+    assert!(kernel_pretty(&folded, "half").contains("expr2: layout77 3.0"));
+    assert!(kernel_pretty(&backend, "flag").contains("expr4: layout6 expr2 || expr3"));
+    assert!(kernel_pretty(&folded, "flag").contains("expr4: layout6 builtin True"));
+    assert!(kernel_pretty(&backend, "label").contains("expr4: layout21 text segments=4"));
+    assert!(kernel_pretty(&folded, "label").contains("expr4: layout21 text segments=2"));
+    assert!(kernel_pretty(&folded, "xs").contains("expr2: layout4 2"));
+
+    let label_kernel = &folded.kernels()[folded.items()[find_item(&folded, "label")]
+        .body
+        .expect("label should carry a body")];
+    let KernelExprKind::Text(label) = &label_kernel.exprs()[label_kernel.root].kind else {
+        panic!("label should stay a text literal");
+    };
+    assert!(matches!(
+        label.segments.first(),
+        Some(aivi_backend::TextSegment::Fragment { raw, .. }) if raw.as_ref() == "total 5 for "
+    ));
+
+    let globals = BTreeMap::new();
+    let mut before = KernelEvaluator::new(&backend);
+    let mut after = KernelEvaluator::new(&folded);
+    for name in ["total", "half", "flag", "label", "xs"] {
+        assert_eq!(
+            after
+                .evaluate_item(find_item(&folded, name), &globals)
+                .expect("folded item should evaluate"),
+            before
+                .evaluate_item(find_item(&backend, name), &globals)
+                .expect("unfolded item should evaluate"),
+            "folding should not change the value of `{name}`"
+        );
+    }
+}
+
+#[test]
+fn constant_folding_leaves_runtime_arithmetic_errors_to_the_interpreter() {
+    let backend = lower_text(
+        "backend-fold-overflow.aivi",
+        r#"
+value overflow:Int = 9223372036854775807 + 1
+
+value byZero:Int = 7 / 0
+
+value inside:Int = (1 + 1) * (9223372036854775807 + 1)
+"#,
+    );
+    let folded = aivi_backend::fold_constants(&backend);
+    validate_program(&folded).expect("folded program should validate");
+
+    // The interpreter uses checked i64 arithmetic, so overflow is an error rather than a wrap.
+    // Folding must keep that error at runtime instead of baking in a wrapped result.
+    assert_eq!(kernel_pretty(&folded, "overflow"), kernel_pretty(&backend, "overflow"));
+    assert_eq!(kernel_pretty(&folded, "byZero"), kernel_pretty(&backend, "byZero"));
+    let inside = kernel_pretty(&folded, "inside");
+    assert!(inside.contains("expr2: layout4 2"), "{inside}");
+    assert!(inside.contains("expr6: layout4 expr2 * expr5"), "{inside}");
+
+    let globals = BTreeMap::new();
+    let mut evaluator = KernelEvaluator::new(&folded);
+    for (name, reason) in [
+        ("overflow", "signed addition overflow"),
+        ("byZero", "division by zero"),
+        ("inside", "signed addition overflow"),
+    ] {
+        match evaluator.evaluate_item(find_item(&folded, name), &globals) {
+            Err(EvaluationError::InvalidBinaryArithmetic { reason: found, .. }) => {
+                assert_eq!(found, reason, "`{name}` should fail at runtime")
+            }
+            other => panic!("`{name}` should fail with `{reason}`, found {other:?}"),
+        }
+    }
+}
@@ -902,6 +902,2275 @@ value filteredMissing:Option Int =
     );
 }
 
+#[test]
+fn runtime_evaluates_derived_ord_for_adts_in_declaration_order() {
+    let backend = lower_text(
+        "backend-derived-ord.aivi",
+        r#"
+type Priority = Low | Medium | High
+
+type Ticket = Ticket Priority Int
+
+value urgent:Bool =
+    Low < High
+
+value ranked:Ordering =
+    compare (Ticket Medium 5) (Ticket Medium 2)
+
+value lowFirst:Ordering =
+    compare (Ticket Low 9) (Ticket High 0)
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "urgent"), &globals)
+            .expect("derived ordering operator should evaluate"),
+        RuntimeValue::Bool(true)
+    );
+    for (name, expected) in [("ranked", "Greater"), ("lowFirst", "Less")] {
+        match evaluator
+            .evaluate_item(find_item(&backend, name), &globals)
+            .expect("derived compare should evaluate")
+        {
+            RuntimeValue::Sum(value) => {
+                assert_eq!(value.type_name.as_ref(), "Ordering");
+                assert_eq!(value.variant_name.as_ref(), expected, "{name}");
+            }
+            other => panic!("expected Ordering result for {name}, found {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn runtime_compares_records_by_label_in_declared_field_order() {
+    let backend = lower_text(
+        "backend-record-field-order.aivi",
+        r#"
+type Point = {
+    y: Int,
+    x: Int
+}
+
+value first:Point = { y: 1, x: 2 }
+
+value reordered:Point = { x: 2, y: 1 }
+
+value later:Point = { x: 1, y: 2 }
+
+value same:Bool =
+    first == reordered
+
+value sameOrder:Ordering =
+    compare first reordered
+
+value declaredOrder:Ordering =
+    compare first later
+
+value reorderedFirst:Bool =
+    reordered < later
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    for (name, expected) in [("same", true), ("reorderedFirst", true)] {
+        assert_eq!(
+            evaluator
+                .evaluate_item(find_item(&backend, name), &globals)
+                .expect("record comparison should evaluate"),
+            RuntimeValue::Bool(expected),
+            "{name}"
+        );
+    }
+    for (name, expected) in [("sameOrder", "Equal"), ("declaredOrder", "Less")] {
+        match evaluator
+            .evaluate_item(find_item(&backend, name), &globals)
+            .expect("record compare should evaluate")
+        {
+            RuntimeValue::Sum(value) => {
+                assert_eq!(value.type_name.as_ref(), "Ordering");
+                assert_eq!(value.variant_name.as_ref(), expected, "{name}");
+            }
+            other => panic!("expected Ordering result for {name}, found {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn runtime_round_trips_bytes_through_hex() {
+    let backend = lower_text(
+        "backend-bytes-hex.aivi",
+        r#"
+use aivi.core.bytes (
+    fromHex,
+    toHex,
+    fromList
+)
+
+value encoded:Text =
+    toHex (fromList [0, 10, 171, 255])
+
+value decoded:(Option Bytes) =
+    fromHex "00aAbB"
+
+value oddLength:(Option Bytes) =
+    fromHex "abc"
+
+value notHex:(Option Bytes) =
+    fromHex "zz"
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "encoded"), &globals)
+            .expect("toHex should evaluate"),
+        RuntimeValue::Text("000aabff".into())
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "decoded"), &globals)
+            .expect("fromHex should evaluate"),
+        RuntimeValue::OptionSome(Box::new(RuntimeValue::Bytes(vec![0, 170, 187].into())))
+    );
+    for name in ["oddLength", "notHex"] {
+        assert_eq!(
+            evaluator
+                .evaluate_item(find_item(&backend, name), &globals)
+                .expect("invalid hex should evaluate to None"),
+            RuntimeValue::OptionNone
+        );
+    }
+}
+
+#[test]
+fn runtime_round_trips_bytes_through_lists_and_base64() {
+    let backend = lower_text(
+        "backend-bytes-base64.aivi",
+        r#"
+use aivi.core.bytes (
+    fromList,
+    toList,
+    fromBase64,
+    toBase64,
+    fromText,
+    slice
+)
+
+value encoded:Text =
+    toBase64 (fromText "hello")
+
+value decoded:(Option Bytes) =
+    fromBase64 "aGVsbG8="
+
+value malformed:(Option Bytes) =
+    fromBase64 "not base64!"
+
+value octets:List Int =
+    toList (fromList [0, 127, 255])
+
+value clamped:Bytes =
+    slice 3 99 (fromText "hello")
+
+value clampedStart:Bytes =
+    slice (0 - 2) 3 (fromText "hello")
+
+value reversed:Bytes =
+    slice 4 1 (fromText "hello")
+
+value pastEnd:Bytes =
+    slice 7 9 (fromText "hello")
+
+value outOfRange:Bytes =
+    fromList [1, 256]
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "encoded"), &globals)
+            .expect("toBase64 should evaluate"),
+        RuntimeValue::Text("aGVsbG8=".into())
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "decoded"), &globals)
+            .expect("fromBase64 should evaluate"),
+        RuntimeValue::OptionSome(Box::new(RuntimeValue::Bytes(b"hello".to_vec().into())))
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "malformed"), &globals)
+            .expect("invalid base64 should evaluate to None"),
+        RuntimeValue::OptionNone
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "octets"), &globals)
+            .expect("byte lists should round-trip"),
+        RuntimeValue::List(vec![
+            RuntimeValue::Int(0),
+            RuntimeValue::Int(127),
+            RuntimeValue::Int(255),
+        ])
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "clamped"), &globals)
+            .expect("slice should clamp out-of-range indices"),
+        RuntimeValue::Bytes(b"lo".to_vec().into())
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "clampedStart"), &globals)
+            .expect("slice should clamp a negative start to zero"),
+        RuntimeValue::Bytes(b"hel".to_vec().into())
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "reversed"), &globals)
+            .expect("slice should accept an end before the start"),
+        RuntimeValue::Bytes(Vec::new().into())
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "pastEnd"), &globals)
+            .expect("slice should accept a range past the end"),
+        RuntimeValue::Bytes(Vec::new().into())
+    );
+    assert!(
+        evaluator
+            .evaluate_item(find_item(&backend, "outOfRange"), &globals)
+            .is_err(),
+        "fromList should reject values outside 0-255"
+    );
+}
+
+#[test]
+fn runtime_matches_rfc_4231_hmac_vectors_and_compares_in_constant_time() {
+    let backend = lower_text(
+        "backend-crypto.aivi",
+        r#"
+use aivi.core.bytes (
+    fromText,
+    repeat
+)
+use aivi.crypto (
+    hmacSha256,
+    hmacSha512,
+    constantTimeEquals
+)
+
+value case1Sha256:Bytes =
+    hmacSha256 (repeat 11 20) (fromText "Hi There")
+
+value case1Sha512:Bytes =
+    hmacSha512 (repeat 11 20) (fromText "Hi There")
+
+value case2Sha256:Bytes =
+    hmacSha256 (fromText "Jefe") (fromText "what do ya want for nothing?")
+
+value case2Sha512:Bytes =
+    hmacSha512 (fromText "Jefe") (fromText "what do ya want for nothing?")
+
+value sameBytes:Bool =
+    constantTimeEquals (fromText "token") (fromText "token")
+
+value differentBytes:Bool =
+    constantTimeEquals (fromText "token") (fromText "tokem")
+
+value differentLengths:Bool =
+    constantTimeEquals (fromText "token") (fromText "tokens")
+"#,
+    );
+
+    fn hex(text: &str) -> RuntimeValue {
+        let bytes = (0..text.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&text[index..index + 2], 16).expect("valid hex"))
+            .collect::<Vec<_>>();
+        RuntimeValue::Bytes(bytes.into())
+    }
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    for (name, expected) in [
+        (
+            "case1Sha256",
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+        ),
+        (
+            "case1Sha512",
+            "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+             daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+        ),
+        (
+            "case2Sha256",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        ),
+        (
+            "case2Sha512",
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+        ),
+    ] {
+        assert_eq!(
+            evaluator
+                .evaluate_item(find_item(&backend, name), &globals)
+                .expect("HMAC should evaluate"),
+            hex(expected),
+            "{name}"
+        );
+    }
+    for (name, expected) in [
+        ("sameBytes", true),
+        ("differentBytes", false),
+        ("differentLengths", false),
+    ] {
+        assert_eq!(
+            evaluator
+                .evaluate_item(find_item(&backend, name), &globals)
+                .expect("constantTimeEquals should evaluate"),
+            RuntimeValue::Bool(expected),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn runtime_evaluates_char_literals_and_classifies_chars() {
+    let backend = lower_text(
+        "backend-char.aivi",
+        r#"
+use aivi.char (
+    toInt,
+    fromInt,
+    isDigit,
+    isAlpha,
+    toUpper
+)
+
+value smiley:Char =
+    '\u{1F600}'
+
+value smileyCode:Int =
+    toInt smiley
+
+value newline:Char =
+    '\n'
+
+value lambda:Option Char =
+    fromInt 955
+
+value surrogate:Option Char =
+    fromInt 55296
+
+value digitIsDigit:Bool =
+    isDigit '7'
+
+value letterIsDigit:Bool =
+    isDigit 'x'
+
+value accentIsAlpha:Bool =
+    isAlpha 'é'
+
+value spaceIsAlpha:Bool =
+    isAlpha ' '
+
+value upperQ:Char =
+    toUpper 'q'
+
+value upperEszett:Char =
+    toUpper 'ß'
+
+value sameChar:Bool =
+    'a' == 'a'
+
+value greeting:Text =
+    "smile {smiley}"
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    for (name, expected) in [
+        ("smiley", RuntimeValue::Char('\u{1F600}')),
+        ("smileyCode", RuntimeValue::Int(0x1F600)),
+        ("newline", RuntimeValue::Char('\n')),
+        (
+            "lambda",
+            RuntimeValue::OptionSome(Box::new(RuntimeValue::Char('λ'))),
+        ),
+        ("surrogate", RuntimeValue::OptionNone),
+        ("digitIsDigit", RuntimeValue::Bool(true)),
+        ("letterIsDigit", RuntimeValue::Bool(false)),
+        ("accentIsAlpha", RuntimeValue::Bool(true)),
+        ("spaceIsAlpha", RuntimeValue::Bool(false)),
+        ("upperQ", RuntimeValue::Char('Q')),
+        ("upperEszett", RuntimeValue::Char('ß')),
+        ("sameChar", RuntimeValue::Bool(true)),
+        ("greeting", RuntimeValue::Text("smile \u{1F600}".into())),
+    ] {
+        assert_eq!(
+            evaluator
+                .evaluate_item(find_item(&backend, name), &globals)
+                .unwrap_or_else(|error| panic!("{name} should evaluate: {error}")),
+            expected,
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn runtime_concatenates_many_text_parts_in_order() {
+    let backend = lower_text(
+        "backend-text-concat.aivi",
+        r#"
+use aivi.text (
+    concat,
+    repeat
+)
+
+value left:Text =
+    repeat 5000 "ab"
+
+value right:Text =
+    repeat 5000 "cd"
+
+value built:Text =
+    concat [left, "-", right, "", "!"]
+
+value interpolated:Text =
+    "{left}-{right}!"
+
+value empty:Text =
+    concat []
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let built = evaluator
+        .evaluate_item(find_item(&backend, "built"), &globals)
+        .expect("concat should evaluate");
+    let RuntimeValue::Text(text) = &built else {
+        panic!("expected concat to produce text, found {built:?}");
+    };
+    assert_eq!(text.len(), 20_002);
+    assert_eq!(
+        built,
+        evaluator
+            .evaluate_item(find_item(&backend, "interpolated"), &globals)
+            .expect("interpolation should evaluate")
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "empty"), &globals)
+            .expect("empty concat should evaluate"),
+        RuntimeValue::Text("".into())
+    );
+}
+
+#[test]
+fn runtime_renders_text_templates_strictly_and_leniently() {
+    let backend = lower_text(
+        "backend-text-template.aivi",
+        r#"
+use aivi.text (
+    renderTemplate,
+    renderLenient,
+    renderPositional
+)
+
+type TemplateProblem = {
+    missingKey: Option Text,
+    offset: Int
+}
+
+value named:Result TemplateProblem Text =
+    renderTemplate "Hi \{name\}, you have \{count\} new messages" (Map { "name": "Ada", "count": "3" })
+
+value escaped:Result TemplateProblem Text =
+    renderTemplate "\{\{name\}\} is \{name\} \}" (Map { "name": "Ada" })
+
+value missing:Result TemplateProblem Text =
+    renderTemplate "Hi \{name\}, from \{sender\}" (Map { "name": "Ada" })
+
+value unclosed:Result TemplateProblem Text =
+    renderTemplate "Hi \{name, \{name\}" (Map { "name": "Ada" })
+
+value lenient:Text =
+    renderLenient "\{greeting\}, \{name\}! \{oops" (Map { "name": "Ada" })
+
+value positional:Result TemplateProblem Text =
+    renderPositional "\{1\} \{0\} \{1\}" ["b", "a"]
+
+value positionalMissing:Result TemplateProblem Text =
+    renderPositional "\{0\} \{2\}" ["a", "b"]
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let mut evaluate = |name: &str| {
+        evaluator
+            .evaluate_item(find_item(&backend, name), &globals)
+            .expect("template intrinsics should evaluate")
+    };
+    let text = |value: &str| RuntimeValue::Text(value.into());
+    let rendered = |value: &str| RuntimeValue::ResultOk(Box::new(text(value)));
+    let problem = |missing_key: Option<&str>, offset: i64| {
+        RuntimeValue::ResultErr(Box::new(RuntimeValue::Record(vec![
+            RuntimeRecordField {
+                label: "missingKey".into(),
+                value: missing_key.map_or(RuntimeValue::OptionNone, |key| {
+                    RuntimeValue::OptionSome(Box::new(text(key)))
+                }),
+            },
+            RuntimeRecordField {
+                label: "offset".into(),
+                value: RuntimeValue::Int(offset),
+            },
+        ])))
+    };
+
+    assert_eq!(
+        evaluate("named"),
+        rendered("Hi Ada, you have 3 new messages")
+    );
+    assert_eq!(evaluate("escaped"), rendered("{name} is Ada }"));
+    assert_eq!(evaluate("missing"), problem(Some("sender"), 16));
+    assert_eq!(evaluate("unclosed"), problem(None, 3));
+    assert_eq!(evaluate("lenient"), text("{greeting}, Ada! {oops"));
+    assert_eq!(evaluate("positional"), rendered("a b a"));
+    assert_eq!(evaluate("positionalMissing"), problem(Some("2"), 4));
+}
+
+#[test]
+fn runtime_folds_case_and_reverses_text_by_grapheme_cluster() {
+    let backend = lower_text(
+        "backend-text-graphemes.aivi",
+        r#"
+use aivi.text (
+    caseFold,
+    reverse,
+    graphemeLength
+)
+
+value folded:Text =
+    caseFold "Straße ΣΑΣ"
+
+value combining:Text =
+    reverse "cafe\u{301}"
+
+value emoji:Text =
+    reverse "a\u{1F469}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{1F1EB}\u{1F1F7}\u{1F44D}\u{1F3FD}"
+
+value emptyReversed:Text =
+    reverse ""
+
+value combiningLength:Int =
+    graphemeLength "cafe\u{301}"
+
+value emojiLength:Int =
+    graphemeLength "a\u{1F469}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{1F1EB}\u{1F1F7}\u{1F44D}\u{1F3FD}"
+
+value emptyLength:Int =
+    graphemeLength ""
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let mut evaluate = |name: &str| {
+        evaluator
+            .evaluate_item(find_item(&backend, name), &globals)
+            .expect("grapheme intrinsics should evaluate")
+    };
+    let text = |value: &str| RuntimeValue::Text(value.into());
+
+    assert_eq!(evaluate("folded"), text("strasse σασ"));
+    assert_eq!(evaluate("combining"), text("e\u{301}fac"));
+    assert_eq!(
+        evaluate("emoji"),
+        text("\u{1F44D}\u{1F3FD}\u{1F1EB}\u{1F1F7}\u{1F469}\u{200D}\u{1F469}\u{200D}\u{1F467}a")
+    );
+    assert_eq!(evaluate("emptyReversed"), text(""));
+    assert_eq!(evaluate("combiningLength"), RuntimeValue::Int(4));
+    assert_eq!(evaluate("emojiLength"), RuntimeValue::Int(4));
+    assert_eq!(evaluate("emptyLength"), RuntimeValue::Int(0));
+}
+
+#[test]
+fn runtime_wraps_and_truncates_text_by_grapheme_width() {
+    let backend = lower_text(
+        "backend-text-wrap.aivi",
+        r#"
+use aivi.text (
+    wrap,
+    truncate
+)
+
+value paragraph:List Text =
+    wrap 16 "Width counts grapheme clusters, so cafe\u{301} is four wide.\n\nBlank lines stay."
+
+value longWord:List Text =
+    wrap 3 "a\u{1F469}\u{200D}\u{1F4BB}bcdef g"
+
+value cut:Text =
+    truncate 5 "cafe\u{301} cr\u{E8}me"
+
+value fits:Text =
+    truncate 4 "cafe\u{301}"
+
+value tight:Text =
+    truncate 1 "abc"
+
+value none:Text =
+    truncate 0 "abc"
+
+value negative:Text =
+    truncate (0 - 2) "abc"
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let mut evaluate = |name: &str| {
+        evaluator
+            .evaluate_item(find_item(&backend, name), &globals)
+            .expect("wrap and truncate should evaluate")
+    };
+    let text = |value: &str| RuntimeValue::Text(value.into());
+    let lines = |values: &[&str]| RuntimeValue::List(values.iter().map(|line| text(line)).collect());
+
+    assert_eq!(
+        evaluate("paragraph"),
+        lines(&[
+            "Width counts",
+            "grapheme",
+            "clusters, so",
+            "cafe\u{301} is four",
+            "wide.",
+            "",
+            "Blank lines",
+            "stay.",
+        ])
+    );
+    assert_eq!(
+        evaluate("longWord"),
+        lines(&["a\u{1F469}\u{200D}\u{1F4BB}b", "cde", "f g"])
+    );
+    assert_eq!(evaluate("cut"), text("cafe\u{301}\u{2026}"));
+    assert_eq!(evaluate("fits"), text("cafe\u{301}"));
+    assert_eq!(evaluate("tight"), text("\u{2026}"));
+    assert_eq!(evaluate("none"), text(""));
+    assert_eq!(evaluate("negative"), text(""));
+}
+
+#[test]
+fn runtime_stringifies_json_values_with_sorted_keys() {
+    let backend = lower_text(
+        "backend-json-stringify.aivi",
+        r#"
+use aivi.data.json (
+    parseInto,
+    stringifyValue
+)
+
+type Json =
+  | JsonNull
+  | JsonBool Bool
+  | JsonNumber Float
+  | JsonString Text
+  | JsonArray (List Json)
+  | JsonObject { entries: List { key: Text, value: Json } }
+
+value encoded:Text =
+    stringifyValue (
+        JsonObject {
+            entries: [
+                { key: "b", value: JsonArray [JsonNumber 1.5, JsonNull, JsonString "q\"uote"] },
+                { key: "a", value: JsonBool True }
+            ]
+        }
+    )
+
+value decodePlan:(Task Text Json) =
+    parseInto JsonNull "[1]"
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "encoded"), &globals)
+            .expect("stringifyValue should evaluate"),
+        RuntimeValue::Text(r#"{"a":true,"b":[1.5,null,"q\"uote"]}"#.into())
+    );
+    let RuntimeValue::Task(RuntimeTaskPlan::JsonParseInto {
+        json, type_name, ..
+    }) = evaluator
+        .evaluate_item(find_item(&backend, "decodePlan"), &globals)
+        .expect("parseInto should build a task plan")
+    else {
+        panic!("parseInto should build a JSON parse plan");
+    };
+    assert_eq!(json.as_ref(), "[1]");
+    assert_eq!(type_name.as_ref(), "Json");
+}
+
+#[test]
+fn runtime_encodes_plain_data_as_json() {
+    let backend = lower_text(
+        "backend-json-encode.aivi",
+        r#"
+use aivi.data.json (encode)
+
+type Role =
+  | Admin
+  | Guest
+
+type Shape =
+  | Circle Float
+
+type User = {
+    name: Text,
+    age: Int,
+    score: Float,
+    active: Bool,
+    role: Role,
+    nickname: Option Text,
+    tags: List Text,
+    origin: (Int, Int)
+}
+
+value ada:User = {
+    name: "Ada \"L\"",
+    age: 36,
+    score: 1.5,
+    active: True,
+    role: Admin,
+    nickname: None,
+    tags: ["math", "engines"],
+    origin: (0, -1)
+}
+
+value encoded:Text =
+    encode ada
+
+value roles:List (Option Role) =
+    [Some Guest, None]
+
+value nested:Text =
+    encode roles
+
+value withFields:Text =
+    encode (Circle 1.0)
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "encoded"), &globals)
+            .expect("encode should evaluate"),
+        RuntimeValue::Text(
+            concat!(
+                r#"{"active":true,"age":36,"name":"Ada \"L\"","nickname":null,"#,
+                r#""origin":[0,-1],"role":"Admin","score":1.5,"tags":["math","engines"]}"#,
+            )
+            .into()
+        )
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "nested"), &globals)
+            .expect("encode should evaluate"),
+        RuntimeValue::Text(r#"["Guest",null]"#.into())
+    );
+    assert!(
+        evaluator
+            .evaluate_item(find_item(&backend, "withFields"), &globals)
+            .is_err(),
+        "encode should reject constructors that carry fields"
+    );
+}
+
+#[test]
+fn runtime_reports_the_remaining_arity_of_callables() {
+    let backend = lower_text(
+        "backend-fn-arity.aivi",
+        r#"
+use aivi.core.bytes (repeat)
+use aivi.core.fn (arity)
+
+type Int -> Int -> Int -> Int
+func add3 = a b c =>
+    a + b + c
+
+type Int -> Int -> Int
+value add = (x y => x + y)
+
+value lambdaArity:Int =
+    arity add
+
+value builtinArity:Int =
+    arity repeat
+
+value partialArity:Int =
+    arity (add3 1)
+
+value constructorArity:Int =
+    arity Some
+
+value notAFunction:Int =
+    arity 42
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    for (name, expected) in [
+        ("lambdaArity", 2),
+        ("builtinArity", 2),
+        ("partialArity", 2),
+        ("constructorArity", 1),
+    ] {
+        assert_eq!(
+            evaluator
+                .evaluate_item(find_item(&backend, name), &globals)
+                .unwrap_or_else(|error| panic!("{name} should evaluate: {error:?}")),
+            RuntimeValue::Int(expected),
+            "{name}"
+        );
+    }
+    assert!(
+        evaluator
+            .evaluate_item(find_item(&backend, "notAFunction"), &globals)
+            .is_err(),
+        "arity should reject values that are not callable"
+    );
+}
+
+#[test]
+fn runtime_checks_whether_arguments_fit_a_callable() {
+    let backend = lower_text(
+        "backend-fn-can-apply.aivi",
+        r#"
+use aivi.core.fn (canApply, isFullyApplied)
+
+type Int -> Int
+func negate = n =>
+    0 - n
+
+type Int -> Int -> Int -> Int
+func add3 = a b c =>
+    a + b + c
+
+value unaryTakesOne:Bool =
+    canApply negate 1
+
+value unaryRejectsTwo:Bool =
+    canApply negate 2
+
+value ternaryTakesThree:Bool =
+    canApply add3 3
+
+value partialTakesTwo:Bool =
+    canApply (add3 1) 2
+
+value partialRejectsThree:Bool =
+    canApply (add3 1) 3
+
+value negativeCount:Bool =
+    canApply add3 (0 - 1)
+
+value notAFunction:Bool =
+    canApply 42 0
+
+value ternarySaturatedByThree:Bool =
+    isFullyApplied add3 3
+
+value ternaryNotSaturatedByTwo:Bool =
+    isFullyApplied add3 2
+
+value partialSaturatedByTwo:Bool =
+    isFullyApplied (add3 1) 2
+
+value constantNeverSaturated:Bool =
+    isFullyApplied 42 0
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    for (name, expected) in [
+        ("unaryTakesOne", true),
+        ("unaryRejectsTwo", false),
+        ("ternaryTakesThree", true),
+        ("partialTakesTwo", true),
+        ("partialRejectsThree", false),
+        ("negativeCount", false),
+        ("notAFunction", false),
+        ("ternarySaturatedByThree", true),
+        ("ternaryNotSaturatedByTwo", false),
+        ("partialSaturatedByTwo", true),
+        ("constantNeverSaturated", false),
+    ] {
+        assert_eq!(
+            evaluator
+                .evaluate_item(find_item(&backend, name), &globals)
+                .unwrap_or_else(|error| panic!("{name} should evaluate: {error:?}")),
+            RuntimeValue::Bool(expected),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn runtime_evaluates_exact_decimal_arithmetic() {
+    let backend = lower_text(
+        "backend-decimal-arithmetic.aivi",
+        r#"
+use aivi.decimal (
+    fromInt,
+    fromText,
+    toText,
+    add,
+    sub,
+    mul,
+    divWithMode,
+    roundWithMode
+)
+
+value tenthPlusFifth:Bool =
+    add 0.1d 0.2d == 0.3d
+
+value parsed:Option Decimal =
+    fromText " 19.99 "
+
+value unparsable:Option Decimal =
+    fromText "twelve"
+
+value product:Text =
+    toText (mul 1.10d 3.30d)
+
+value change:Text =
+    toText (sub (fromInt 10) 0.01d)
+
+value eighthHalfEven:Decimal =
+    divWithMode "halfEven" 2 (fromInt 1) (fromInt 8)
+
+value eighthHalfUp:Decimal =
+    divWithMode "halfUp" 2 (fromInt 1) (fromInt 8)
+
+value roundedDown:Decimal =
+    roundWithMode "floor" 1 2.99d
+
+value divideByZero:Decimal =
+    divWithMode "halfEven" 2 (fromInt 1) (fromInt 0)
+
+value unknownMode:Decimal =
+    roundWithMode "sideways" 2 1.005d
+
+value negativePlaces:Decimal =
+    roundWithMode "halfEven" (0 - 1) 1.005d
+
+value underflowingProduct:Decimal =
+    mul 0.0000000000000001d 0.0000000000000001d
+
+value overflowingSum:Decimal =
+    add 79228162514264337593543950335d 1d
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let item = |name: &str| find_item(&backend, name);
+    let decimal =
+        |raw: &str| RuntimeValue::Decimal(RuntimeDecimal::parse_literal(raw).expect("literal"));
+
+    assert_eq!(
+        evaluator
+            .evaluate_item(item("tenthPlusFifth"), &globals)
+            .expect("decimal addition should evaluate"),
+        RuntimeValue::Bool(true)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(item("parsed"), &globals)
+            .expect("fromText should evaluate"),
+        RuntimeValue::OptionSome(Box::new(decimal("19.99d")))
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(item("unparsable"), &globals)
+            .expect("fromText should evaluate"),
+        RuntimeValue::OptionNone
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(item("product"), &globals)
+            .expect("decimal multiplication should evaluate"),
+        RuntimeValue::Text("3.6300".into())
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(item("change"), &globals)
+            .expect("decimal subtraction should evaluate"),
+        RuntimeValue::Text("9.99".into())
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(item("eighthHalfEven"), &globals)
+            .expect("decimal division should evaluate"),
+        decimal("0.12d")
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(item("eighthHalfUp"), &globals)
+            .expect("decimal division should evaluate"),
+        decimal("0.13d")
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(item("roundedDown"), &globals)
+            .expect("decimal rounding should evaluate"),
+        decimal("2.9d")
+    );
+    for name in [
+        "divideByZero",
+        "unknownMode",
+        "negativePlaces",
+        "underflowingProduct",
+        "overflowingSum",
+    ] {
+        assert!(
+            matches!(
+                evaluator.evaluate_item(item(name), &globals),
+                Err(EvaluationError::IntrinsicFailed { .. })
+            ),
+            "{name} should fail with a runtime error"
+        );
+    }
+}
+
+#[test]
+fn runtime_wraps_attempted_tasks_in_result_plans() {
+    let backend = lower_text(
+        "backend-task-attempt.aivi",
+        r#"
+use aivi.task (attempt)
+use aivi.regex (isMatch)
+
+value ready:Task Text Int =
+    pure 3
+
+value readyAttempt:Task Text (Result Text Int) =
+    attempt ready
+
+value regexAttempt:Task Text (Result Text Bool) =
+    attempt (isMatch "(" "x")
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "readyAttempt"), &globals)
+            .expect("attempting a pure task should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::Pure {
+            value: Box::new(RuntimeValue::ResultOk(Box::new(RuntimeValue::Int(3)))),
+        })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "regexAttempt"), &globals)
+            .expect("attempting a regex task should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::Attempt {
+            inner: Box::new(RuntimeTaskPlan::RegexIsMatch {
+                pattern: "(".into(),
+                text: "x".into(),
+            }),
+        })
+    );
+}
+
+#[test]
+fn runtime_plans_cleanup_tasks_for_finally() {
+    let backend = lower_text(
+        "backend-task-finally.aivi",
+        r#"
+use aivi.task (finally)
+use aivi.stdio (stdoutWrite)
+
+value opened:Task Text Int =
+    pure 3
+
+value cleaned:Task Text Int =
+    finally opened (stdoutWrite "done")
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "cleaned"), &globals)
+            .expect("finally should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::Finally {
+            inner: Box::new(RuntimeTaskPlan::Pure {
+                value: Box::new(RuntimeValue::Int(3)),
+            }),
+            cleanup: Box::new(RuntimeTaskPlan::StdoutWrite {
+                text: "done".into(),
+            }),
+        })
+    );
+}
+
+#[test]
+fn runtime_plans_file_handle_tasks() {
+    let backend = lower_text(
+        "backend-fs-handles.aivi",
+        r#"
+use aivi.fs (
+    open
+    write
+    close
+)
+
+value opened:Task Text { id: Int, path: Text } =
+    open "notes.txt" "a"
+
+value handle:{ id: Int, path: Text } = { id: 4, path: "notes.txt" }
+
+value appended:Task Text Unit =
+    write handle "line"
+
+value closed:Task Text Unit =
+    close handle
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "opened"), &globals)
+            .expect("open should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::FsOpen {
+            path: "notes.txt".into(),
+            mode: "a".into(),
+        })
+    );
+    let handle = evaluator
+        .evaluate_item(find_item(&backend, "handle"), &globals)
+        .expect("handle record should evaluate");
+    let globals = BTreeMap::from([(find_item(&backend, "handle"), handle)]);
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "appended"), &globals)
+            .expect("write should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::FsWrite {
+            handle: 4,
+            text: "line".into(),
+        })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "closed"), &globals)
+            .expect("close should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::FsClose { handle: 4 })
+    );
+}
+
+#[test]
+fn runtime_plans_web_socket_tasks() {
+    let backend = lower_text(
+        "backend-ws-handles.aivi",
+        r#"
+use aivi.ws (
+    connect
+    send
+    recv
+    recvTimeout
+    close
+)
+
+value connected:Task Text { id: Int, url: Text } =
+    connect "ws://localhost:9000/chat"
+
+value socket:{ id: Int, url: Text } = { id: 2, url: "ws://localhost:9000/chat" }
+
+value sent:Task Text Unit =
+    send socket "hello"
+
+value received:Task Text (Option Text) =
+    recv socket
+
+value polled:Task Text (Option (Option Text)) =
+    recvTimeout socket 250
+
+value closed:Task Text Unit =
+    close socket
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "connected"), &globals)
+            .expect("connect should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::WsConnect {
+            url: "ws://localhost:9000/chat".into(),
+        })
+    );
+    let socket = evaluator
+        .evaluate_item(find_item(&backend, "socket"), &globals)
+        .expect("socket record should evaluate");
+    let globals = BTreeMap::from([(find_item(&backend, "socket"), socket)]);
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "sent"), &globals)
+            .expect("send should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::WsSend {
+            handle: 2,
+            text: "hello".into(),
+        })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "received"), &globals)
+            .expect("recv should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::WsRecv { handle: 2 })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "polled"), &globals)
+            .expect("recvTimeout should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::WsRecvTimeout {
+            handle: 2,
+            timeout_ms: 250,
+        })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "closed"), &globals)
+            .expect("close should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::WsClose { handle: 2 })
+    );
+}
+
+#[test]
+fn runtime_plans_process_tasks() {
+    let backend = lower_text(
+        "backend-process-handles.aivi",
+        r#"
+use aivi.process (
+    spawn
+    wait
+    kill
+    readStdout
+    writeStdin
+)
+
+value spawned:Task Text { id: Int, command: Text } =
+    spawn "echo" ["hello"] (Map { "LANG": "C" })
+
+value child:{ id: Int, command: Text } = { id: 3, command: "echo" }
+
+value waited:Task Text Int =
+    wait child
+
+value killed:Task Text Unit =
+    kill child
+
+value output:Task Text Text =
+    readStdout child
+
+value written:Task Text Unit =
+    writeStdin child "input"
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "spawned"), &globals)
+            .expect("spawn should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::ProcessSpawn {
+            command: "echo".into(),
+            args: vec!["hello".into()].into(),
+            env: vec![("LANG".into(), "C".into())].into(),
+        })
+    );
+    let child = evaluator
+        .evaluate_item(find_item(&backend, "child"), &globals)
+        .expect("process record should evaluate");
+    let globals = BTreeMap::from([(find_item(&backend, "child"), child)]);
+    let mut plan = |name: &str| match evaluator
+        .evaluate_item(find_item(&backend, name), &globals)
+        .expect("process task should evaluate")
+    {
+        RuntimeValue::Task(plan) => plan,
+        other => panic!("expected a task plan, found {other:?}"),
+    };
+    assert_eq!(plan("waited"), RuntimeTaskPlan::ProcessWait { handle: 3 });
+    assert_eq!(plan("killed"), RuntimeTaskPlan::ProcessKill { handle: 3 });
+    assert_eq!(
+        plan("output"),
+        RuntimeTaskPlan::ProcessReadStdout { handle: 3 }
+    );
+    assert_eq!(
+        plan("written"),
+        RuntimeTaskPlan::ProcessWriteStdin {
+            handle: 3,
+            text: "input".into(),
+        }
+    );
+}
+
+#[test]
+fn runtime_signs_and_verifies_ed25519_messages() {
+    let backend = lower_text(
+        "backend-crypto-ed25519.aivi",
+        r#"
+use aivi.core.bytes (
+    empty,
+    fromHex,
+    fromText,
+    toHex
+)
+use aivi.crypto (
+    ed25519Keypair,
+    ed25519Sign,
+    ed25519Verify
+)
+
+type Text -> Bytes
+func hex = text => fromHex text
+ ||> Some bytes -> bytes
+ ||> None       -> empty
+
+value privateKey:Bytes =
+    hex "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"
+
+value publicKey:Bytes =
+    hex "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+
+value emptySignature:Text =
+    toHex (ed25519Sign privateKey empty)
+
+value signature:Bytes =
+    ed25519Sign privateKey (fromText "transfer 10")
+
+value accepted:Bool =
+    ed25519Verify publicKey (fromText "transfer 10") signature
+
+value tampered:Bool =
+    ed25519Verify publicKey (fromText "transfer 99") signature
+
+value truncated:Bool =
+    ed25519Verify publicKey (fromText "transfer 10") (fromText "short")
+
+value shortKey:Bytes =
+    ed25519Sign (fromText "short") (fromText "transfer 10")
+
+value generated:Task Text { publicKey: Bytes, privateKey: Bytes } =
+    ed25519Keypair
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    // RFC 8032, section 7.1, test 1.
+    let expected = concat!(
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155",
+        "5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "emptySignature"), &globals)
+            .expect("signing should evaluate"),
+        RuntimeValue::Text(expected.into())
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "accepted"), &globals)
+            .expect("verification should evaluate"),
+        RuntimeValue::Bool(true)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "tampered"), &globals)
+            .expect("verification should evaluate"),
+        RuntimeValue::Bool(false)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "truncated"), &globals)
+            .expect("a short signature should simply fail to verify"),
+        RuntimeValue::Bool(false)
+    );
+    assert!(matches!(
+        evaluator.evaluate_item(find_item(&backend, "shortKey"), &globals),
+        Err(EvaluationError::InvalidIntrinsicArgument { index: 0, .. })
+    ));
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "generated"), &globals)
+            .expect("key generation should plan a task"),
+        RuntimeValue::Task(RuntimeTaskPlan::CryptoEd25519Keypair)
+    );
+}
+
+#[test]
+fn runtime_plans_http_request_and_download_tasks() {
+    let backend = lower_text(
+        "backend-http-request.aivi",
+        r#"
+type HttpSource = Unit
+
+@source http "http://localhost:8080"
+signal api : HttpSource
+
+value sent:Task Text { status: Int, headers: List (Text, Text), body: Text } =
+    api.request {
+        method: "PUT",
+        url: "/items/1",
+        headers: [("Accept", "text/plain")],
+        body: Some "payload",
+        timeoutMs: Some 1500,
+        maxRedirects: None
+    }
+
+value saved:Task Text Unit =
+    api.download "/archive.tar" "/tmp/archive.tar"
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "sent"), &globals)
+            .expect("request should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::HttpRequest {
+            method: "PUT".into(),
+            url: "http://localhost:8080/items/1".into(),
+            headers: vec![("Accept".into(), "text/plain".into())].into(),
+            body: Some("payload".into()),
+            timeout_ms: Some(1500),
+            max_redirects: None,
+        })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "saved"), &globals)
+            .expect("download should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::HttpDownload {
+            url: "http://localhost:8080/archive.tar".into(),
+            path: "/tmp/archive.tar".into(),
+        })
+    );
+}
+
+#[test]
+fn runtime_fuel_limits_kernel_calls() {
+    let backend = lower_text(
+        "backend-fuel.aivi",
+        r#"
+type Int -> Int -> Int
+func add = total item =>
+    total + item
+
+value total : Int =
+    reduce add 0 [1, 2, 3, 4, 5, 6]
+"#,
+    );
+    let globals = BTreeMap::new();
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    assert_eq!(evaluator.remaining_fuel(), None);
+    evaluator.set_fuel(1_000);
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "total"), &globals)
+            .expect("ample fuel should let the fold finish"),
+        RuntimeValue::Int(21)
+    );
+    let remaining = evaluator
+        .remaining_fuel()
+        .expect("fuel should stay limited");
+    assert!(
+        remaining <= 1_000 - 6,
+        "only {} fuel spent",
+        1_000 - remaining
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    evaluator.set_fuel(3);
+    let error = evaluator
+        .evaluate_item(find_item(&backend, "total"), &globals)
+        .expect_err("three kernel calls should not finish the fold");
+    assert!(
+        matches!(error, EvaluationError::FuelExhausted { .. }),
+        "unexpected error: {error}"
+    );
+    assert_eq!(evaluator.remaining_fuel(), Some(0));
+}
+
+#[test]
+fn runtime_error_traces_name_the_calls_that_failed() {
+    let backend = lower_text(
+        "backend-error-trace.aivi",
+        r#"
+type Int -> Int -> Int
+func divide = numerator denominator =>
+    numerator / denominator
+
+type Int -> Int
+func ratio = numerator =>
+    divide numerator 0
+
+value broken : Int =
+    ratio 10
+"#,
+    );
+    let globals = BTreeMap::new();
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let error = evaluator
+        .evaluate_item(find_item(&backend, "broken"), &globals)
+        .expect_err("division by zero should fail");
+    assert!(
+        matches!(error, EvaluationError::InvalidBinaryArithmetic { .. }),
+        "traces should stay off by default: {error}"
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    evaluator.set_error_traces(true);
+    let error = evaluator
+        .evaluate_item(find_item(&backend, "broken"), &globals)
+        .expect_err("division by zero should fail");
+    let EvaluationError::WithTrace { source, frames } = &error else {
+        panic!("expected a traced error, found {error}");
+    };
+    assert!(
+        matches!(**source, EvaluationError::InvalidBinaryArithmetic { .. }),
+        "unexpected source error: {source}"
+    );
+    assert_eq!(
+        frames.iter().map(AsRef::as_ref).collect::<Vec<&str>>(),
+        ["divide", "ratio", "broken"]
+    );
+    assert!(
+        error.to_string().ends_with("\n    in `divide`\n    in `ratio`\n    in `broken`"),
+        "unexpected rendering: {error}"
+    );
+}
+
+#[test]
+fn runtime_allocation_budget_bounds_estimated_allocation() {
+    let backend = lower_text(
+        "backend-allocation-budget.aivi",
+        r#"
+type List Text -> Int -> List Text
+func grow = lines count =>
+    append lines ["line {count} of a growing report"]
+
+value header : List Text =
+    ["report"]
+
+value report : List Text =
+    reduce grow header [1, 2, 3, 4, 5, 6, 7, 8]
+
+value footer : List Text =
+    ["end of report"]
+"#,
+    );
+    let globals = BTreeMap::new();
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    evaluator.set_allocation_budget(1 << 20);
+    let RuntimeValue::List(lines) = evaluator
+        .evaluate_item(find_item(&backend, "report"), &globals)
+        .expect("a generous budget should let the fold finish")
+    else {
+        panic!("report should evaluate to a list");
+    };
+    assert_eq!(lines.len(), 9);
+    // Every line is a flat text, so the list's own slots plus each line's text is all it holds.
+    let final_size = lines.iter().map(RuntimeValue::shallow_size).sum::<usize>()
+        + RuntimeValue::List(lines).shallow_size();
+    assert!(
+        evaluator.allocated_bytes() >= final_size,
+        "counted {} bytes for a {final_size}-byte result",
+        evaluator.allocated_bytes()
+    );
+    evaluator
+        .evaluate_item(find_item(&backend, "footer"), &globals)
+        .expect("the next evaluation starts with a fresh count");
+    assert!(
+        evaluator.allocated_bytes() < final_size,
+        "the count should restart for each evaluation, found {} bytes",
+        evaluator.allocated_bytes()
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    evaluator.set_allocation_budget(final_size);
+    let error = evaluator
+        .evaluate_item(find_item(&backend, "report"), &globals)
+        .expect_err("the intermediate lists of the fold spend the budget too");
+    assert!(
+        matches!(error, EvaluationError::AllocationBudgetExceeded { budget, .. } if budget == final_size),
+        "unexpected error: {error}"
+    );
+}
+
+#[test]
+fn runtime_allocation_budget_charges_only_the_outer_layer_of_intrinsic_results() {
+    let source = format!(
+        r#"
+use aivi.text (
+    split
+)
+
+value pieces : List Text =
+    split "," "{}"
+"#,
+        "abcdefgh,".repeat(200)
+    );
+    let backend = lower_text("backend-allocation-budget-intrinsic.aivi", &source);
+    let globals = BTreeMap::new();
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    evaluator.set_allocation_budget(usize::MAX);
+    let RuntimeValue::List(pieces) = evaluator
+        .evaluate_item(find_item(&backend, "pieces"), &globals)
+        .expect("split should evaluate")
+    else {
+        panic!("pieces should evaluate to a list");
+    };
+    // The texts `split` builds inside its result are never charged, only the list holding them.
+    let outer_size = RuntimeValue::List(pieces.clone()).shallow_size();
+    let nested_size = pieces.iter().map(RuntimeValue::shallow_size).sum::<usize>();
+    assert!(nested_size > 0);
+    assert_eq!(evaluator.allocated_bytes(), outer_size);
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    evaluator.set_allocation_budget(outer_size);
+    evaluator
+        .evaluate_item(find_item(&backend, "pieces"), &globals)
+        .expect("a budget for the outer list should cover the whole split");
+}
+
+#[test]
+fn runtime_value_shallow_size_skips_nested_values() {
+    let text = RuntimeValue::Text("twelve bytes".into());
+    let slot = std::mem::size_of::<RuntimeValue>();
+    assert_eq!(text.shallow_size(), slot + 12);
+    let nested = RuntimeValue::List(vec![text.clone(), text]);
+    assert_eq!(nested.shallow_size(), 3 * slot);
+    assert_eq!(
+        RuntimeValue::OptionSome(Box::new(nested)).shallow_size(),
+        2 * slot
+    );
+    assert_eq!(
+        RuntimeValue::Record(vec![RuntimeRecordField {
+            label: "name".into(),
+            value: RuntimeValue::Bytes(vec![0; 3].into_boxed_slice()),
+        }])
+        .shallow_size(),
+        2 * slot + 4
+    );
+}
+
+#[test]
+fn runtime_plans_time_sleep_tasks() {
+    let backend = lower_text(
+        "backend-time-sleep.aivi",
+        r#"
+use aivi.time (sleep)
+
+value pause:Task Text Unit =
+    sleep 250
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "pause"), &globals)
+            .expect("sleep should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::TimeSleep { ms: 250 })
+    );
+}
+
+#[test]
+fn runtime_plans_clipboard_tasks() {
+    let backend = lower_text(
+        "backend-clipboard.aivi",
+        r#"
+use aivi.clipboard (
+    read
+    write
+)
+
+value copy:Task Text Unit =
+    write "copied"
+
+value paste:Task Text Text =
+    read
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "copy"), &globals)
+            .expect("clipboard write should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::ClipboardWrite {
+            text: "copied".into(),
+        })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "paste"), &globals)
+            .expect("clipboard read should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::ClipboardRead)
+    );
+}
+
+#[test]
+fn runtime_lu_solves_inverts_and_detects_singular_matrices() {
+    let backend = lower_text(
+        "backend-matrix-lu.aivi",
+        r#"
+use aivi.matrix (
+    luSolve
+    luDeterminant
+    luInverse
+)
+
+value system:List (List Float) = [[2.0, 1.0], [4.0, 1.0]]
+
+value singular:List (List Float) = [[1.0, 2.0], [2.0, 4.0]]
+
+value solution:Option (List Float) =
+    luSolve system [4.0, 6.0]
+
+value determinant:Float =
+    luDeterminant system
+
+value inverse:Option (List (List Float)) =
+    luInverse system
+
+value singularSolution:Option (List Float) =
+    luSolve singular [1.0, 2.0]
+
+value singularDeterminant:Float =
+    luDeterminant singular
+
+value singularInverse:Option (List (List Float)) =
+    luInverse singular
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let mut evaluate = |name: &str| {
+        evaluator
+            .evaluate_item(find_item(&backend, name), &globals)
+            .expect("matrix intrinsics should evaluate")
+    };
+    let float = |value: f64| RuntimeValue::Float(RuntimeFloat::new(value).unwrap());
+    let floats = |values: &[f64]| RuntimeValue::List(values.iter().copied().map(float).collect());
+
+    assert_eq!(
+        evaluate("solution"),
+        RuntimeValue::OptionSome(Box::new(floats(&[1.0, 2.0])))
+    );
+    assert_eq!(evaluate("determinant"), float(-2.0));
+    assert_eq!(
+        evaluate("inverse"),
+        RuntimeValue::OptionSome(Box::new(RuntimeValue::List(vec![
+            floats(&[-0.5, 0.5]),
+            floats(&[2.0, -1.0]),
+        ])))
+    );
+    assert_eq!(evaluate("singularSolution"), RuntimeValue::OptionNone);
+    assert_eq!(evaluate("singularDeterminant"), float(0.0));
+    assert_eq!(evaluate("singularInverse"), RuntimeValue::OptionNone);
+}
+
+#[test]
+fn runtime_graph_finds_shortest_paths_and_distances() {
+    let backend = lower_text(
+        "backend-graph-dijkstra.aivi",
+        r#"
+use aivi.graph (
+    shortestPath
+    distances
+)
+
+value graph = {
+    nodes: [1, 2, 3, 4, 5],
+    edges: [
+        { from: 1, to: 2, weight: 1.0 },
+        { from: 2, to: 3, weight: 2.0 },
+        { from: 1, to: 3, weight: 4.0 },
+        { from: 3, to: 4, weight: 1.0 }
+    ]
+}
+
+value negative = {
+    nodes: [1, 2],
+    edges: [{ from: 1, to: 2, weight: 0.0 - 1.0 }]
+}
+
+value path:Option (List Int) =
+    shortestPath graph 1 4
+
+value unreachable:Option (List Int) =
+    shortestPath graph 1 5
+
+value fromStart:Map Int Float =
+    distances graph 1
+
+value unknownStart:Option (List Int) =
+    shortestPath graph 9 1
+
+value unknownDistances:Map Int Float =
+    distances graph 9
+
+value negativeWeight:Option (List Int) =
+    shortestPath negative 1 2
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let mut evaluate = |name: &str| {
+        evaluator
+            .evaluate_item(find_item(&backend, name), &globals)
+            .expect("graph intrinsics should evaluate")
+    };
+    let ints = |values: &[i64]| {
+        RuntimeValue::List(values.iter().copied().map(RuntimeValue::Int).collect())
+    };
+
+    assert_eq!(
+        evaluate("path"),
+        RuntimeValue::OptionSome(Box::new(ints(&[1, 2, 3, 4])))
+    );
+    assert_eq!(evaluate("unreachable"), RuntimeValue::OptionNone);
+
+    let RuntimeValue::Map(distances) = evaluate("fromStart") else {
+        panic!("distances should produce a map");
+    };
+    assert_eq!(
+        distances.len(),
+        4,
+        "the unreachable node should be left out"
+    );
+    for (node, expected) in [(1, 0.0), (2, 1.0), (3, 3.0), (4, 4.0)] {
+        assert_eq!(
+            distances.get(&RuntimeValue::Int(node)),
+            Some(&RuntimeValue::Float(RuntimeFloat::new(expected).unwrap())),
+            "distance to node {node}"
+        );
+    }
+
+    for name in ["unknownStart", "unknownDistances", "negativeWeight"] {
+        assert!(
+            matches!(
+                evaluator.evaluate_item(find_item(&backend, name), &globals),
+                Err(EvaluationError::IntrinsicFailed { .. })
+            ),
+            "{name} should fail with a runtime error"
+        );
+    }
+}
+
+#[test]
+fn runtime_jacobi_svd_reconstructs_tall_wide_and_rank_deficient_matrices() {
+    let backend = lower_text(
+        "backend-matrix-jacobi.aivi",
+        r#"
+use aivi.matrix (
+    jacobiSvd
+    jacobiEigenvalues
+)
+
+type SvdFactors = {
+    u: List (List Float),
+    singularValues: List Float,
+    vt: List (List Float)
+}
+
+value tall:SvdFactors =
+    jacobiSvd [[3.0, 2.0], [2.0, 3.0], [2.0, 0.0 - 2.0]]
+
+value wide:SvdFactors =
+    jacobiSvd [[3.0, 2.0, 2.0], [2.0, 3.0, 0.0 - 2.0]]
+
+value rankOne:SvdFactors =
+    jacobiSvd [[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]]
+
+value symmetric:Option (List Float) =
+    jacobiEigenvalues [[2.0, 1.0, 0.0], [1.0, 2.0, 1.0], [0.0, 1.0, 2.0]]
+
+value skewed:Option (List Float) =
+    jacobiEigenvalues [[1.0, 2.0], [0.0, 1.0]]
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let mut evaluate = |name: &str| {
+        evaluator
+            .evaluate_item(find_item(&backend, name), &globals)
+            .expect("jacobi intrinsics should evaluate")
+    };
+    let floats = |value: &RuntimeValue| -> Vec<f64> {
+        let RuntimeValue::List(values) = value else {
+            panic!("expected a list of floats, found {value:?}");
+        };
+        values
+            .iter()
+            .map(|value| match value {
+                RuntimeValue::Float(float) => float.to_f64(),
+                other => panic!("expected a float, found {other:?}"),
+            })
+            .collect()
+    };
+    let rows = |value: &RuntimeValue| -> Vec<Vec<f64>> {
+        let RuntimeValue::List(rows) = value else {
+            panic!("expected matrix rows, found {value:?}");
+        };
+        rows.iter().map(floats).collect()
+    };
+    let field = |record: &RuntimeValue, label: &str| -> RuntimeValue {
+        let RuntimeValue::Record(fields) = record else {
+            panic!("svd factors should be a record, found {record:?}");
+        };
+        fields
+            .iter()
+            .find(|field| field.label.as_ref() == label)
+            .map(|field| field.value.clone())
+            .unwrap_or_else(|| panic!("svd factors should have a `{label}` field"))
+    };
+    let assert_close = |actual: f64, expected: f64| {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, found {actual}"
+        );
+    };
+    let mut check = |name: &str, input: &[&[f64]], expected_sigma: &[f64]| {
+        let factors = evaluate(name);
+        let u = rows(&field(&factors, "u"));
+        let sigma = floats(&field(&factors, "singularValues"));
+        let vt = rows(&field(&factors, "vt"));
+        assert_eq!(sigma.len(), expected_sigma.len());
+        for (&actual, &expected) in sigma.iter().zip(expected_sigma) {
+            assert_close(actual, expected);
+        }
+        for (y, input_row) in input.iter().enumerate() {
+            for (x, &entry) in input_row.iter().enumerate() {
+                let rebuilt = (0..sigma.len())
+                    .map(|k| u[y][k] * sigma[k] * vt[k][x])
+                    .sum::<f64>();
+                assert_close(rebuilt, entry);
+            }
+        }
+        for left in 0..sigma.len() {
+            for right in 0..sigma.len() {
+                let identity = if left == right { 1.0 } else { 0.0 };
+                assert_close(
+                    (0..u.len()).map(|y| u[y][left] * u[y][right]).sum(),
+                    identity,
+                );
+                assert_close(
+                    vt[left].iter().zip(&vt[right]).map(|(a, b)| a * b).sum(),
+                    identity,
+                );
+            }
+        }
+    };
+
+    check(
+        "tall",
+        &[&[3.0, 2.0], &[2.0, 3.0], &[2.0, -2.0]],
+        &[5.0, 3.0],
+    );
+    check("wide", &[&[3.0, 2.0, 2.0], &[2.0, 3.0, -2.0]], &[5.0, 3.0]);
+    check(
+        "rankOne",
+        &[&[1.0, 2.0], &[2.0, 4.0], &[3.0, 6.0]],
+        &[70.0_f64.sqrt(), 0.0],
+    );
+
+    let RuntimeValue::OptionSome(eigenvalues) = evaluate("symmetric") else {
+        panic!("a symmetric matrix should have eigenvalues");
+    };
+    let eigenvalues = floats(&eigenvalues);
+    let root_two = 2.0_f64.sqrt();
+    for (&actual, expected) in eigenvalues
+        .iter()
+        .zip([2.0 + root_two, 2.0, 2.0 - root_two])
+    {
+        assert_close(actual, expected);
+    }
+    assert_eq!(evaluate("skewed"), RuntimeValue::OptionNone);
+}
+
+fn fft_float_values(value: RuntimeValue) -> Vec<f64> {
+    let RuntimeValue::List(values) = value else {
+        panic!("fft results should be lists, found {value:?}");
+    };
+    values
+        .into_iter()
+        .map(|value| match value {
+            RuntimeValue::Float(float) => float.to_f64(),
+            other => panic!("fft magnitudes should be floats, found {other:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn runtime_fft_finds_the_dominant_bin_of_a_sine_wave() {
+    // 3 cycles over 12 samples: not a power of two, so a mixed-radix plan is used.
+    let samples = (0..12)
+        .map(|index| {
+            let sample = (2.0 * std::f64::consts::PI * 3.0 * f64::from(index) / 12.0).sin();
+            if sample < 0.0 {
+                format!("(0.0 - {:.17})", -sample)
+            } else {
+                format!("{sample:.17}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let backend = lower_text(
+        "backend-math-fft.aivi",
+        &format!(
+            r#"
+use aivi.math (
+    fftMagnitude
+    fftSpectrum
+)
+
+value wave:List Float = [{samples}]
+
+value magnitudes:List Float =
+    fftMagnitude wave
+
+value spectrum:List {{ magnitude: Float, phase: Float, frequencyBin: Int }} =
+    fftSpectrum wave
+
+value nothing:List Float =
+    fftMagnitude []
+"#
+        ),
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let magnitudes = fft_float_values(
+        evaluator
+            .evaluate_item(find_item(&backend, "magnitudes"), &globals)
+            .expect("fft magnitudes should evaluate"),
+    );
+    assert_eq!(magnitudes.len(), 7, "real input keeps bins 0..=n/2");
+    for (bin, magnitude) in magnitudes.iter().enumerate() {
+        let expected = if bin == 3 { 6.0 } else { 0.0 };
+        assert!(
+            (magnitude - expected).abs() < 1e-9,
+            "bin {bin} should have magnitude {expected}, found {magnitude}"
+        );
+    }
+
+    let RuntimeValue::List(spectrum) = evaluator
+        .evaluate_item(find_item(&backend, "spectrum"), &globals)
+        .expect("fft spectrum should evaluate")
+    else {
+        panic!("fft spectrum should be a list");
+    };
+    assert_eq!(spectrum.len(), 7);
+    let RuntimeValue::Record(fields) = &spectrum[3] else {
+        panic!("fft spectrum entries should be records");
+    };
+    let labels = fields
+        .iter()
+        .map(|field| field.label.as_ref())
+        .collect::<Vec<_>>();
+    assert_eq!(labels, ["magnitude", "phase", "frequencyBin"]);
+    assert_eq!(fields[2].value, RuntimeValue::Int(3));
+    let RuntimeValue::Float(phase) = &fields[1].value else {
+        panic!("fft phase should be a float");
+    };
+    assert!(
+        (phase.to_f64() + std::f64::consts::FRAC_PI_2).abs() < 1e-9,
+        "a sine wave peaks with phase -pi/2, found {phase}"
+    );
+
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "nothing"), &globals)
+            .expect("fft of an empty list should evaluate"),
+        RuntimeValue::List(Vec::new())
+    );
+}
+
+#[test]
+fn runtime_plans_concurrent_tasks_for_parallel() {
+    let backend = lower_text(
+        "backend-task-parallel.aivi",
+        r#"
+use aivi.task (parallel)
+use aivi.fs (writeText)
+
+value writes:Task Text (List Unit) =
+    parallel [
+        writeText "a.txt" "a",
+        writeText "b.txt" "b"
+    ]
+
+value nothing:Task Text (List Int) =
+    parallel []
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "writes"), &globals)
+            .expect("parallel should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::Parallel {
+            tasks: vec![
+                RuntimeTaskPlan::FsWriteText {
+                    path: "a.txt".into(),
+                    text: "a".into(),
+                },
+                RuntimeTaskPlan::FsWriteText {
+                    path: "b.txt".into(),
+                    text: "b".into(),
+                },
+            ],
+        })
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "nothing"), &globals)
+            .expect("an empty parallel should evaluate"),
+        RuntimeValue::Task(RuntimeTaskPlan::Pure {
+            value: Box::new(RuntimeValue::List(Vec::new())),
+        })
+    );
+}
+
+#[test]
+fn runtime_prefers_explicit_ord_instances_over_derived_order() {
+    let backend = lower_text(
+        "backend-explicit-ord.aivi",
+        r#"
+type Priority = Low | High
+
+fun rank:Int = priority:Priority => priority
+ ||> Low  -> 0
+ ||> High -> 1
+
+instance Ord Priority = {
+    compare = left right => compare (rank right) (rank left)
+}
+
+value lowFirst:Bool =
+    Low < High
+
+value reversed:Ordering =
+    compare Low High
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "lowFirst"), &globals)
+            .expect("explicit ordering operator should evaluate"),
+        RuntimeValue::Bool(false),
+        "the explicit instance ranks High before Low"
+    );
+    match evaluator
+        .evaluate_item(find_item(&backend, "reversed"), &globals)
+        .expect("explicit compare should evaluate")
+    {
+        RuntimeValue::Sum(value) => assert_eq!(value.variant_name.as_ref(), "Greater"),
+        other => panic!("expected Ordering result, found {other:?}"),
+    }
+}
+
+#[test]
+fn runtime_orders_payloads_through_their_explicit_instances() {
+    let backend = lower_text(
+        "backend-explicit-ord-payload.aivi",
+        r#"
+type Batch = Batch Int
+
+fun batchSize:Int = batch:Batch => batch
+ ||> Batch size -> size
+
+instance Ord Batch = {
+    compare = left right => compare (batchSize right) (batchSize left)
+}
+
+type Queue = Queue Batch Int
+
+fun queueBatch:Batch = queue:Queue => queue
+ ||> Queue batch rank -> batch
+
+instance Ord Queue = {
+    compare = left right => compare (queueBatch left) (queueBatch right)
+}
+
+value queued:Ordering =
+    compare (Queue (Batch 1) 1) (Queue (Batch 2) 0)
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    match evaluator
+        .evaluate_item(find_item(&backend, "queued"), &globals)
+        .expect("payload compare should evaluate")
+    {
+        RuntimeValue::Sum(value) => assert_eq!(
+            value.variant_name.as_ref(),
+            "Greater",
+            "the reversed Batch instance should decide the Queue ordering"
+        ),
+        other => panic!("expected Ordering result, found {other:?}"),
+    }
+}
+
 #[test]
 fn runtime_evaluates_validation_apply_through_backend_runtime() {
     let backend = lower_text(
@@ -1188,3 +3457,360 @@ value totalImported : Int =
     assert_eq!(builtin_mapped_value, imported_mapped_value);
     assert_eq!(builtin_total, imported_total);
 }
+
+#[test]
+fn runtime_parses_and_stringifies_toml_documents() {
+    let backend = lower_text(
+        "backend-toml.aivi",
+        r#"
+use aivi.data.toml (
+    parseInto,
+    stringifyValue
+)
+
+type Toml =
+  | TomlString Text
+  | TomlInt Int
+  | TomlFloat Float
+  | TomlBool Bool
+  | TomlDatetime Text
+  | TomlArray (List Toml)
+  | TomlTable { entries: List { key: Text, value: Toml } }
+
+type (Result Text Toml) -> Text
+func render = result => result
+ ||> Ok document   -> stringifyValue document
+ ||> Err message   -> message
+
+value nested:Text =
+    render (parseInto (TomlBool False) "[server]\nport = 8080\nratio = 0.5\n[server.tls]\nenabled = true\n")
+
+value arrayOfTables:Text =
+    render (parseInto (TomlBool False) "[[plugin]]\nname = \"a\"\n\n[[plugin]]\nname = \"b\"\nwhen = 2024-01-02T03:04:05Z\n")
+
+value malformed:Text =
+    render (parseInto (TomlBool False) "title = \"ok\"\nname = \n")
+
+value encoded:Text =
+    stringifyValue (
+        TomlTable {
+            entries: [
+                { key: "b", value: TomlArray [TomlInt 1, TomlFloat 2.0] },
+                { key: "a", value: TomlTable { entries: [{ key: "on", value: TomlBool True }] } }
+            ]
+        }
+    )
+
+value notTable:Text =
+    stringifyValue (TomlInt 1)
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let mut text = |name: &str| {
+        evaluator
+            .evaluate_item(find_item(&backend, name), &globals)
+            .unwrap_or_else(|error| panic!("{name} should evaluate: {error:?}"))
+    };
+    assert_eq!(
+        text("nested"),
+        RuntimeValue::Text(
+            "[server]\nport = 8080\nratio = 0.5\n\n[server.tls]\nenabled = true\n".into()
+        ),
+        "integers and floats should stay distinct through a nested table"
+    );
+    assert_eq!(
+        text("arrayOfTables"),
+        RuntimeValue::Text(
+            "[[plugin]]\nname = \"a\"\n\n[[plugin]]\nname = \"b\"\nwhen = 2024-01-02T03:04:05Z\n"
+                .into()
+        )
+    );
+    assert_eq!(
+        text("malformed"),
+        RuntimeValue::Text("line 2, column 8: invalid string; expected `\"`, `'`".into())
+    );
+    assert_eq!(
+        text("encoded"),
+        RuntimeValue::Text("b = [1, 2.0]\n\n[a]\non = true\n".into())
+    );
+    assert!(
+        evaluator
+            .evaluate_item(find_item(&backend, "notTable"), &globals)
+            .is_err(),
+        "stringifyValue should only write tables"
+    );
+}
+
+#[test]
+fn runtime_encodes_toml_documents_into_results() {
+    let backend = lower_text(
+        "backend-toml-encode.aivi",
+        r#"
+use aivi.data.toml (
+    encodeValue
+)
+
+type Toml =
+  | TomlString Text
+  | TomlInt Int
+  | TomlFloat Float
+  | TomlBool Bool
+  | TomlDatetime Text
+  | TomlArray (List Toml)
+  | TomlTable { entries: List { key: Text, value: Toml } }
+
+value encoded : Result Text Text =
+    encodeValue (
+        TomlTable {
+            entries: [
+                { key: "name", value: TomlString "aivi" },
+                { key: "when", value: TomlDatetime "2024-01-02T03:04:05Z" }
+            ]
+        }
+    )
+
+value badDatetime : Result Text Text =
+    encodeValue (TomlTable { entries: [{ key: "when", value: TomlDatetime "tomorrow" }] })
+
+value notTable : Result Text Text =
+    encodeValue (TomlArray [TomlInt 1])
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let mut result = |name: &str| {
+        evaluator
+            .evaluate_item(find_item(&backend, name), &globals)
+            .unwrap_or_else(|error| panic!("{name} should evaluate: {error:?}"))
+    };
+    assert_eq!(
+        result("encoded"),
+        RuntimeValue::ResultOk(Box::new(RuntimeValue::Text(
+            "name = \"aivi\"\nwhen = 2024-01-02T03:04:05Z\n".into()
+        )))
+    );
+    assert_eq!(
+        result("badDatetime"),
+        RuntimeValue::ResultErr(Box::new(RuntimeValue::Text(
+            "`tomorrow` is not a TOML date-time".into()
+        )))
+    );
+    assert_eq!(
+        result("notTable"),
+        RuntimeValue::ResultErr(Box::new(RuntimeValue::Text(
+            "only a `TomlTable` can be written as a TOML document".into()
+        )))
+    );
+}
+
+#[test]
+fn runtime_shares_storage_between_identical_text_literals() {
+    let backend = lower_text(
+        "backend-text-literal-interning.aivi",
+        r#"
+value host:Text = "example"
+value first:Text = "example"
+value pair:List Text = ["example", "example"]
+value greeting:Text = "{host}"
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let mut text = |name: &str| match evaluator
+        .evaluate_item(find_item(&backend, name), &globals)
+        .unwrap_or_else(|error| panic!("{name} should evaluate: {error:?}"))
+    {
+        RuntimeValue::Text(text) => text,
+        other => panic!("{name} should evaluate to text, found {other:?}"),
+    };
+    let host = text("host");
+    let first = text("first");
+    let greeting = text("greeting");
+    assert!(
+        std::sync::Arc::ptr_eq(&host, &first),
+        "identical literals in different items should share one allocation"
+    );
+    assert_eq!(greeting, host);
+    assert!(
+        !std::sync::Arc::ptr_eq(&greeting, &host),
+        "interpolated text is built at runtime and must not be interned"
+    );
+
+    let RuntimeValue::List(pair) = evaluator
+        .evaluate_item(find_item(&backend, "pair"), &globals)
+        .expect("pair should evaluate")
+    else {
+        panic!("pair should evaluate to a list");
+    };
+    for element in &pair {
+        let RuntimeValue::Text(element) = element else {
+            panic!("pair elements should be text, found {element:?}");
+        };
+        assert!(std::sync::Arc::ptr_eq(element, &host));
+    }
+}
+
+#[test]
+fn runtime_applies_deep_and_transforming_record_patches() {
+    let backend = lower_text(
+        "backend-record-patch-paths.aivi",
+        r#"
+type Address = { city: Text, zip: Text }
+type Profile = { name: Text, visits: Int, address: Address }
+type User = { profile: Profile, active: Bool, onVisit: Int -> Int }
+
+type Int -> Int
+func bump = n => n + 1
+
+type Int -> Int
+func reset = n => 0
+
+value ada:User = {
+    profile: { name: "Ada", visits: 1, address: { city: "London", zip: "N1" } },
+    active: True,
+    onVisit: bump
+}
+
+value moved:User = ada <| {
+    profile.address.city: "Paris"
+    profile.visits: bump
+}
+
+value stored:User = ada <| {
+    onVisit: := reset
+}
+
+value storedVisit:Int = stored.onVisit 5
+
+value trimmed:{ profile: { name: Text, visits: Int }, active: Bool, onVisit: Int -> Int } = ada <| {
+    profile.address: -
+}
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let mut value = |name: &str| {
+        evaluator
+            .evaluate_item(find_item(&backend, name), &globals)
+            .unwrap_or_else(|error| panic!("{name} should evaluate: {error:?}"))
+    };
+    let field = |value: &RuntimeValue, label: &str| -> RuntimeValue {
+        let RuntimeValue::Record(fields) = value else {
+            panic!("expected a record, found {value:?}");
+        };
+        fields
+            .iter()
+            .find(|field| field.label.as_ref() == label)
+            .unwrap_or_else(|| panic!("record should have field {label}: {value:?}"))
+            .value
+            .clone()
+    };
+
+    let moved = value("moved");
+    let profile = field(&moved, "profile");
+    assert_eq!(field(&profile, "name"), RuntimeValue::Text("Ada".into()));
+    assert_eq!(
+        field(&profile, "visits"),
+        RuntimeValue::Int(2),
+        "an `A -> A` instruction should transform the selected field"
+    );
+    let address = field(&profile, "address");
+    assert_eq!(field(&address, "city"), RuntimeValue::Text("Paris".into()));
+    assert_eq!(field(&address, "zip"), RuntimeValue::Text("N1".into()));
+    assert_eq!(field(&moved, "active"), RuntimeValue::Bool(true));
+
+    assert_eq!(
+        value("storedVisit"),
+        RuntimeValue::Int(0),
+        "`:=` should store the function instead of applying it"
+    );
+
+    let trimmed = value("trimmed");
+    let RuntimeValue::Record(profile_fields) = field(&trimmed, "profile") else {
+        panic!("trimmed profile should stay a record");
+    };
+    assert_eq!(
+        profile_fields
+            .iter()
+            .map(|field| field.label.as_ref())
+            .collect::<Vec<_>>(),
+        ["name", "visits"]
+    );
+}
+
+#[test]
+fn runtime_runs_self_tail_recursive_folds_without_growing_the_stack() {
+    let backend = lower_text(
+        "runtime-self-tail-call.aivi",
+        r#"
+fun sumFrom:Int = total:Int items:(List Int)=>    items
+     ||> [] -> total
+     ||> [head, ...rest] -> sumFrom (total + head) rest
+
+fun sum:Int = items:(List Int)=>    sumFrom 0 items
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+    let item = find_item(&backend, "sum");
+    let kernel = backend.items()[item]
+        .body
+        .expect("sum should lower into a body kernel");
+    let callable = evaluator
+        .evaluate_item(item, &globals)
+        .expect("sum should evaluate to a callable");
+    let items = (1..=10_000).map(RuntimeValue::Int).collect();
+
+    assert_eq!(
+        evaluator
+            .apply_runtime_callable(kernel, callable, vec![RuntimeValue::List(items)], &globals)
+            .expect("a self tail call should reuse the current kernel frame"),
+        RuntimeValue::Int(50_005_000)
+    );
+}
+
+#[test]
+fn runtime_runs_mutual_and_long_tail_recursion_without_growing_the_stack() {
+    let backend = lower_text(
+        "runtime-tail-call.aivi",
+        r#"
+fun countUp:Int = count:Int remaining:Int=>    remaining
+     ||> 0 -> count
+     ||> _ -> countUp (count + 1) (remaining - 1)
+
+fun isEven:Bool = n:Int=>    n
+     ||> 0 -> True
+     ||> _ -> isOdd (n - 1)
+
+fun isOdd:Bool = n:Int=>    n
+     ||> 0 -> False
+     ||> _ -> isEven (n - 1)
+
+value counted:Int = countUp 0 1000000
+
+value evenCheck:Bool = isEven 100001
+"#,
+    );
+
+    let mut evaluator = KernelEvaluator::new(&backend);
+    let globals = BTreeMap::new();
+
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "counted"), &globals)
+            .expect("a million tail calls should run in constant stack"),
+        RuntimeValue::Int(1_000_000)
+    );
+    assert_eq!(
+        evaluator
+            .evaluate_item(find_item(&backend, "evenCheck"), &globals)
+            .expect("mutual tail calls should run in constant stack"),
+        RuntimeValue::Bool(false)
+    );
+}
@@ -280,3 +280,70 @@ fn lowering_rejects_global_item_cycles() {
     )));
 }
 
+#[test]
+fn lowering_rejects_value_cycles_through_functions() {
+    let mut sources = SourceDatabase::new();
+    let file_id = sources.add_file(
+        "backend-value-function-cycle.aivi",
+        r#"
+fun addLooped:Int = n:Int=>    looped + n
+
+value looped:Int = addLooped 1
+"#,
+    );
+    let parsed = parse_module(&sources[file_id]);
+    assert!(
+        !parsed.has_errors(),
+        "{:?}",
+        parsed.all_diagnostics().collect::<Vec<_>>()
+    );
+    let hir = aivi_hir::lower_module(&parsed.module);
+    assert!(!hir.has_errors(), "{:?}", hir.diagnostics());
+    let core = lower_core_module(hir.module()).expect("HIR should lower into typed core");
+    let lambda = lower_lambda_module(&core).expect("typed lambda lowering should succeed");
+    let errors = aivi_backend::lower_module_with_hir(&lambda, hir.module())
+        .expect_err("a value that reaches itself through a function should fail lowering");
+    assert!(
+        errors
+            .errors()
+            .iter()
+            .any(|error| matches!(error, LoweringError::GlobalItemCycle { .. })),
+        "expected a global item cycle, got {errors:?}"
+    );
+}
+
+#[test]
+fn validation_rejects_value_cycles_through_functions() {
+    let mut backend = lower_text(
+        "backend-validate-value-function-cycle.aivi",
+        r#"
+value base:Int = 1
+
+fun addBase:Int = n:Int=>    base + n
+
+value total:Int = addBase 1
+"#,
+    );
+    let add_base = find_item(&backend, "addBase");
+    let total = find_item(&backend, "total");
+    let kernel = backend.items()[add_base]
+        .body
+        .expect("addBase should lower into a body kernel");
+    backend
+        .kernels_mut()
+        .get_mut(kernel)
+        .expect("addBase kernel should exist")
+        .global_items
+        .push(total);
+
+    let errors = validate_program(&backend)
+        .expect_err("a value that reaches itself through a function should fail validation");
+    assert!(
+        errors.errors().iter().any(|error| matches!(
+            error,
+            ValidationError::ItemCyclicDependency { cycle, .. }
+                if cycle.first() == Some(&total) && cycle.last() == Some(&total)
+        )),
+        "expected a cycle through `total`, got {errors:?}"
+    );
+}
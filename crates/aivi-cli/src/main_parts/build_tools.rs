@@ -1,4 +1,8 @@
-fn compile_file(path: &Path, output: Option<&Path>) -> Result<ExitCode, String> {
+fn compile_file(
+    path: &Path,
+    output: Option<&Path>,
+    optimization: OptimizationLevel,
+) -> Result<ExitCode, String> {
     require_file_exists(path)?;
     let snapshot = WorkspaceHirSnapshot::load(path)?;
     let syntax_failed = workspace_syntax_failed(&snapshot, |sources, diagnostics| {
@@ -71,7 +75,7 @@ fn compile_file(path: &Path, output: Option<&Path>) -> Result<ExitCode, String>
     }
 
     let backend = match lower_backend_module(&lambda, hir_module) {
-        Ok(backend) => backend,
+        Ok(backend) => optimization.apply(backend),
         Err(errors) => {
             print_stage_errors(CompileStage::BackendLowering, errors.errors());
             print_pipeline_stop(CompileStage::BackendLowering);
@@ -1145,35 +1149,82 @@ fn format_file(path: &Path) -> Result<ExitCode, String> {
         return Ok(ExitCode::FAILURE);
     }
 
+    let options = manifest_format_options(path)?;
     let formatter = Formatter;
-    print!("{}", formatter.format(&parsed.module));
+    print!(
+        "{}",
+        formatter.format_with_options(&parsed.module, &options)
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Write Markdown API docs for the exported names of the module at `path`.
+fn document_file(path: &Path, output_path: Option<&Path>) -> Result<ExitCode, String> {
+    require_file_exists(path)?;
+    let snapshot = WorkspaceHirSnapshot::load(path)?;
+    let syntax_failed = workspace_syntax_failed(&snapshot, |sources, diagnostics| {
+        print_diagnostics(sources, diagnostics.iter())
+    });
+    if syntax_failed {
+        return Ok(ExitCode::FAILURE);
+    }
+    let (hir_lowering_failed, hir_validation_failed) = workspace_hir_failed(
+        &snapshot,
+        |sources, diagnostics| print_diagnostics(sources, diagnostics.iter()),
+        |sources, diagnostics| print_diagnostics(sources, diagnostics.iter()),
+    );
+    if hir_lowering_failed || hir_validation_failed {
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let docs = aivi_query::module_docs(&snapshot.frontend.db, snapshot.frontend.entry);
+    let markdown = aivi_query::render_markdown(&docs);
+    match output_path {
+        Some(output_path) => {
+            fs::write(output_path, &markdown).map_err(|error| {
+                format!(
+                    "failed to write output to `{}`: {error}",
+                    output_path.display()
+                )
+            })?;
+            eprintln!("wrote {}", output_path.display());
+        }
+        None => print!("{markdown}"),
+    }
     Ok(ExitCode::SUCCESS)
 }
 
+/// Formatter options from the `[format]` table of the manifest governing `path`.
+fn manifest_format_options(path: &Path) -> Result<FormatOptions, String> {
+    let manifest = parse_manifest(&discover_workspace_root(path))
+        .map_err(|message| format!("failed to parse aivi.toml: {message}"))?;
+    Ok(manifest.format.format_options())
+}
+
 fn format_stdin() -> Result<ExitCode, String> {
     let mut source = String::new();
     io::stdin()
         .read_to_string(&mut source)
         .map_err(|e| format!("failed to read stdin: {e}"))?;
+    print!("{}", format_stdin_text(&source));
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Format source piped in by an editor or script. Windows line endings are folded to `\n` first,
+/// so a stray `\r` never ends up inside comments or text fragments of the canonical output.
+fn format_stdin_text(source: &str) -> String {
     let mut sources = SourceDatabase::new();
-    let file_id = sources.add_file("<stdin>", source);
-    let file = &sources[file_id];
-    let parsed = parse_module(file);
+    let file_id = sources.add_file("<stdin>", source.replace("\r\n", "\n"));
+    let parsed = parse_module(&sources[file_id]);
     // Per plan/02: tolerate parse errors, emit formatted output regardless.
-    let formatter = Formatter;
-    print!("{}", formatter.format(&parsed.module));
-    Ok(ExitCode::SUCCESS)
+    Formatter.format(&parsed.module)
 }
 
 fn format_check(paths: &[PathBuf]) -> Result<ExitCode, String> {
     let mut any_changed = false;
     for path in paths {
-        let (sources, file_id) = load_source(path)?;
-        let file = &sources[file_id];
-        let parsed = parse_module(file);
-        let formatter = Formatter;
-        let formatted = formatter.format(&parsed.module);
-        if formatted != file.text() {
+        let (original, formatted) = format_source_at(path)?;
+        if formatted != original {
             println!("{}", path.display());
             any_changed = true;
         }
@@ -1185,6 +1236,59 @@ fn format_check(paths: &[PathBuf]) -> Result<ExitCode, String> {
     }
 }
 
+/// The on-disk text of `path` and its canonical formatting under the governing manifest.
+fn format_source_at(path: &Path) -> Result<(String, String), String> {
+    let (sources, file_id) = load_source(path)?;
+    let file = &sources[file_id];
+    let parsed = parse_module(file);
+    let options = manifest_format_options(path)?;
+    let formatted = Formatter.format_with_options(&parsed.module, &options);
+    Ok((file.text().to_owned(), formatted))
+}
+
+/// Assert that every `.aivi` file under `target` is byte-stable: already canonically formatted,
+/// and unchanged when its formatted output is formatted again.
+fn verify_frozen(target: &Path) -> Result<ExitCode, String> {
+    let files = if target.is_dir() {
+        collect_aivi_files(target)?
+    } else {
+        require_file_exists(target)?;
+        vec![target.to_path_buf()]
+    };
+    let mut drifted = 0_usize;
+    for path in &files {
+        let (original, formatted) = format_source_at(path)?;
+        if formatted != original {
+            println!("{}: not formatted", path.display());
+            drifted += 1;
+            continue;
+        }
+        let mut sources = SourceDatabase::new();
+        let file_id = sources.add_file(path.clone(), formatted.clone());
+        let reparsed = parse_module(&sources[file_id]);
+        let options = manifest_format_options(path)?;
+        if Formatter.format_with_options(&reparsed.module, &options) != formatted {
+            println!("{}: formatting is not stable", path.display());
+            drifted += 1;
+        }
+    }
+    if drifted == 0 {
+        println!(
+            "verified {} file(s) under `{}`",
+            files.len(),
+            target.display()
+        );
+        Ok(ExitCode::SUCCESS)
+    } else {
+        eprintln!(
+            "verify --frozen: {drifted} of {} file(s) under `{}` would change",
+            files.len(),
+            target.display()
+        );
+        Ok(ExitCode::FAILURE)
+    }
+}
+
 fn run_lsp(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, String> {
     if args.any(|a| a == "--help" || a == "-h") {
         return print_help(Some(std::ffi::OsStr::new("lsp")));
@@ -1235,10 +1339,12 @@ COMMANDS:
     build <path> -o <file> [opts]   Package a runnable source-free GTK executable
     run [path] [opts]               Launch a live GTK app
     execute <path> [-- args...]     Run a headless Task program
-    test <path>                     Run @test declarations in a workspace
+    test <path> [opts]              Run @test declarations in a workspace
     lex <path>                      Dump the lossless token stream
     fmt <path|--stdin|--check>      Format AIVI source code
+    verify --frozen [path]          Fail if formatting would change any file
     openapi-gen <spec> [-o file]    Generate AIVI types from an OpenAPI spec
+    doc [path] [-o file]            Generate Markdown API docs for a module
     lsp                             Start the language server (stdio)
     mcp [opts]                      Start the MCP introspection server (stdio)
     manual-snippets [opts]          Validate and format manual code blocks
@@ -1261,7 +1367,7 @@ fn format_subcommand_help(name: &str) -> Option<String> {
 aivi check — type-check a module through HIR
 
 USAGE:
-    aivi check [<path>]
+    aivi check [<path>] [--max-errors <n>]
 
 ARGS:
     <path>              Path to an .aivi source file, or a directory to check
@@ -1269,6 +1375,11 @@ ARGS:
                         aivi.toml are checked; if only one app (or a [run]
                         entry) is defined, that single entry is checked.
 
+OPTIONS:
+    --max-errors <n>
+            Stop type checking a module after it reports <n> errors and
+            note that the rest was skipped. 0, the default, means no limit.
+
 DESCRIPTION:
     Lexes, parses, lowers, and validates one or more modules through the full
     HIR pipeline. Reports any syntax errors, name resolution failures, or type
@@ -1281,7 +1392,7 @@ DESCRIPTION:
 aivi compile — compile a module to native object code
 
 USAGE:
-    aivi compile <path> [-o <object>]
+    aivi compile <path> [-o <object>] [--opt-level <0|1>]
 
 ARGS:
     <path>              Path to an .aivi source file
@@ -1290,6 +1401,10 @@ OPTIONS:
     -o, --output <object>
             Path for the output object file. When omitted, the object
             is written to a default location derived from the input path.
+    --opt-level <0|1>
+            Backend IR optimization level. 0 (the default) keeps kernels
+            as lowered; 1 folds constant arithmetic, boolean logic, and
+            text interpolation before validation and codegen.
 
 DESCRIPTION:
     Lowers the module through typed core, typed lambda IR, backend IR,
@@ -1381,7 +1496,7 @@ DESCRIPTION:
 aivi execute — run a headless Task program
 
 USAGE:
-    aivi execute <path> [-- args...]
+    aivi execute <path> [--backtrace] [--allocation-budget <bytes>] [-- args...]
 
 ARGS:
     <path>              Path to an .aivi source file containing a
@@ -1390,6 +1505,18 @@ ARGS:
     [-- args...]        Arguments passed to the program. Everything
                         after `--` is forwarded as program arguments.
 
+OPTIONS:
+    --backtrace
+            When evaluation fails, list the items and functions that were
+            being evaluated, innermost first.
+
+    --allocation-budget <bytes>
+            Stop an evaluation once the values it builds add up to more
+            than <bytes>, as estimated by the interpreter. Values that are
+            no longer used still count, so this bounds total allocation,
+            not peak memory. Each evaluation the program starts gets its
+            own budget.
+
 DESCRIPTION:
     Evaluates a top-level Task value without GTK or the widget runtime.
     Useful for command-line tools, scripts, and batch processing written
@@ -1401,16 +1528,35 @@ DESCRIPTION:
 aivi test — run @test declarations in a workspace
 
 USAGE:
-    aivi test <path>
+    aivi test <path> [--slow <ms>] [--slowest <n>] [--real-clock]
 
 ARGS:
     <path>              Path to an .aivi source file or workspace entry
 
+OPTIONS:
+    --slow <ms>
+            List every test whose body took at least <ms> milliseconds.
+
+    --slowest <n>
+            List the <n> slowest tests after the run.
+
+    --real-clock
+            Read the system clock. By default each test gets a virtual
+            clock starting at 2024-01-01T00:00:00Z, and `time.sleep`
+            advances it instead of waiting.
+
 DESCRIPTION:
     Discovers all `@test value ... : Task ...` declarations in the
     workspace and executes them. Each test runs in isolation. Reports
     pass/fail status for each test and exits with code 0 if all tests
     pass, 1 if any test fails.
+
+    Examples in doc comments run as doctests too: inside an `aivi`
+    code block, an expression line followed by `=> expected` passes
+    when the expression equals the expected value.
+
+    Test timings cover evaluating and running each test body only;
+    compiling the test artifact is not counted.
 "
         }
         "lex" => {
@@ -1436,6 +1582,7 @@ aivi fmt — format AIVI source code
 USAGE:
     aivi fmt <path>
     aivi fmt --stdin
+    aivi fmt -
     aivi fmt --check [path...]
 
 ARGS:
@@ -1443,8 +1590,9 @@ ARGS:
                         output is written to stdout.
 
 OPTIONS:
-    --stdin             Read source code from stdin and write the
-                        formatted output to stdout.
+    --stdin, -          Read source code from stdin and write the
+                        formatted output to stdout. CRLF line endings
+                        are written back as LF.
 
     --check [path...]   Check whether files are already formatted.
                         Exits with code 0 if all files are formatted,
@@ -1455,6 +1603,31 @@ DESCRIPTION:
     Canonically formats AIVI source code. The formatter preserves
     semantics while normalizing whitespace, indentation, and layout.
     Files with parse errors are left unchanged.
+"
+        }
+        "verify" => {
+            "\
+aivi verify — assert that source files are byte-stable
+
+USAGE:
+    aivi verify --frozen [path]
+
+ARGS:
+    [path]              An .aivi file or a directory searched
+                        recursively. Defaults to the workspace root
+                        containing the current directory.
+
+OPTIONS:
+    --frozen            Format every file in memory and fail if any
+                        result differs from the file on disk, or if
+                        formatting the result again changes it.
+
+DESCRIPTION:
+    A broader `aivi fmt --check` for reproducible-build pipelines.
+    Files are never modified. Each drifted file is printed with the
+    reason, and the command exits with code 1 if any file drifted.
+    Generated modules such as `aivi openapi-gen` output do not
+    record their inputs, so they are only checked for formatting.
 "
         }
         "openapi-gen" => {
@@ -1479,6 +1652,31 @@ DESCRIPTION:
 
     Example:
         aivi openapi-gen ./petstore.yaml -o types/petstore.aivi
+"
+        }
+        "doc" => {
+            "\
+aivi doc — generate Markdown API docs for a module
+
+USAGE:
+    aivi doc [path] [-o output.md]
+
+ARGS:
+    [path]              Path to an .aivi source file. When omitted, the
+                        [run] entry from aivi.toml is used.
+
+OPTIONS:
+    -o, --output <file>
+            Write the Markdown to a file. When omitted, output is
+            written to stdout.
+
+DESCRIPTION:
+    Checks the module, then lists every exported name with its
+    signature and the `/** ... **/` doc comment written directly above
+    its declaration. Values, functions, and signals show their declared
+    type, or the inferred type when unannotated; types, classes, and
+    domains show their declaration. A doc comment at the top of the
+    file, followed by a blank line, becomes the module summary.
 "
         }
         "lsp" => {
@@ -1503,7 +1701,7 @@ DESCRIPTION:
 aivi mcp — start the MCP introspection server
 
 USAGE:
-    aivi mcp [--path <path>] [--view <name>]
+    aivi mcp [--path <path>] [--view <name>] [--allow-tool <name>]... [--deny-tool <name>]...
 
 OPTIONS:
     --path <path>
@@ -1514,6 +1712,13 @@ OPTIONS:
             Dot-separated module path to the view entry point
             (e.g. \"app.main\"). When omitted, uses the default view.
 
+    --allow-tool <name>
+            Expose only the named tools. May be repeated.
+
+    --deny-tool <name>
+            Hide the named tool (e.g. \"diagnostics\"). May be repeated;
+            denials win over --allow-tool.
+
 DESCRIPTION:
     Starts a Model Context Protocol server over stdio for live app
     introspection. Provides tools to launch the app, inspect signals
@@ -68,8 +68,17 @@ fn collect_aivi_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
     Ok(files)
 }
 
+/// Command-line knobs for `aivi check`; each stays off unless requested.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct CheckOptions {
+    /// Print how long each phase took.
+    timings: bool,
+    /// Stop type checking a module once it has reported this many errors; 0 means no limit.
+    max_errors: usize,
+}
+
 /// Check every `.aivi` file found recursively under `dir`.
-fn check_directory(dir: &Path, timings: bool) -> Result<ExitCode, String> {
+fn check_directory(dir: &Path, options: CheckOptions) -> Result<ExitCode, String> {
     let files = collect_aivi_files(dir)?;
     if files.is_empty() {
         println!("no .aivi files found in `{}`", dir.display());
@@ -77,7 +86,7 @@ fn check_directory(dir: &Path, timings: bool) -> Result<ExitCode, String> {
     }
     let mut any_failed = false;
     for path in &files {
-        match check_file(path, timings)? {
+        match check_file(path, options)? {
             ExitCode::SUCCESS => {}
             _ => any_failed = true,
         }
@@ -93,12 +102,12 @@ fn check_directory(dir: &Path, timings: bool) -> Result<ExitCode, String> {
 fn check_all_apps(
     apps: &[aivi_query::AppConfig],
     workspace_root: &Path,
-    timings: bool,
+    options: CheckOptions,
 ) -> Result<ExitCode, String> {
     let mut any_failed = false;
     for app in apps {
         let entry_path = workspace_root.join(&app.entry);
-        match check_file(&entry_path, timings)? {
+        match check_file(&entry_path, options)? {
             ExitCode::SUCCESS => {}
             _ => any_failed = true,
         }
@@ -135,7 +144,7 @@ fn include_project_workspace_file(
     true
 }
 
-fn check_file(path: &Path, timings: bool) -> Result<ExitCode, String> {
+fn check_file(path: &Path, options: CheckOptions) -> Result<ExitCode, String> {
     let total_start = Instant::now();
     require_file_exists(path)?;
 
@@ -153,8 +162,9 @@ fn check_file(path: &Path, timings: bool) -> Result<ExitCode, String> {
     }
 
     let t0 = Instant::now();
-    let (lowering_failed, validation_failed) = workspace_hir_failed(
+    let (lowering_failed, validation_failed) = workspace_hir_failed_with_max_errors(
         &snapshot,
+        options.max_errors,
         |sources, diagnostics| print_diagnostics(sources, diagnostics.iter()),
         |sources, diagnostics| print_diagnostics(sources, diagnostics.iter()),
     );
@@ -212,7 +222,7 @@ fn check_file(path: &Path, timings: bool) -> Result<ExitCode, String> {
         }
     );
 
-    if timings {
+    if options.timings {
         let total = total_start.elapsed();
         eprintln!("timings for `aivi check` ({}):", path.display());
         eprintln!("  load + parse:  {:>8.2?}", load_duration);
@@ -533,27 +543,62 @@ struct TestTaskOutcome {
     detail: Option<String>,
 }
 
-fn execute_file(path: &Path, program_args: &[String]) -> Result<ExitCode, String> {
+/// Command-line knobs for `aivi test`; each stays off unless requested.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct TestReportOptions {
+    slow_threshold: Option<Duration>,
+    slowest: usize,
+    /// Read the real system clock instead of giving each test its own virtual clock.
+    real_clock: bool,
+}
+
+/// Command-line knobs for `aivi execute`; each stays off unless requested.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct ExecuteOptions {
+    /// Name the items and functions that led to an evaluation error.
+    backtrace: bool,
+    /// Estimated bytes one evaluation may allocate before it fails.
+    allocation_budget: Option<usize>,
+}
+
+/// Where each test's virtual clock starts: 2024-01-01T00:00:00Z.
+const TEST_VIRTUAL_CLOCK_START_MS: i64 = 1_704_067_200_000;
+
+/// Wall-clock time spent evaluating one test body, excluding artifact preparation.
+#[derive(Clone, Debug)]
+struct TestTiming {
+    location: String,
+    duration: Duration,
+}
+
+fn execute_file(
+    path: &Path,
+    program_args: &[String],
+    options: ExecuteOptions,
+) -> Result<ExitCode, String> {
     let context = current_execute_source_context(path, program_args)?;
     let mut stdout = io::stdout().lock();
     let mut stderr = io::stderr().lock();
-    execute_file_with_context(path, context, &mut stdout, &mut stderr)
+    execute_file_with_context(path, context, options, &mut stdout, &mut stderr)
 }
 
-fn test_file(path: &Path) -> Result<ExitCode, String> {
+fn test_file(path: &Path, options: TestReportOptions) -> Result<ExitCode, String> {
     let context = current_execute_source_context(path, &[])?;
     let mut stdout = io::stdout().lock();
     let mut stderr = io::stderr().lock();
-    test_file_with_context(path, context, &mut stdout, &mut stderr)
+    test_file_with_context(path, context, options, &mut stdout, &mut stderr)
 }
 
 fn test_file_with_context(
     path: &Path,
     context: SourceProviderContext,
+    options: TestReportOptions,
     stdout: &mut impl Write,
     stderr: &mut impl Write,
 ) -> Result<ExitCode, String> {
     require_file_exists(path)?;
+    // A test that calls `process.exit` fails on its own instead of ending the whole run.
+    let context = context.with_process_exit_intercepted();
     let snapshot = WorkspaceHirSnapshot::load(path)?;
     let syntax_failed = workspace_syntax_failed(&snapshot, |sources, diagnostics| {
         print_diagnostics(sources, diagnostics.iter())
@@ -577,45 +622,87 @@ fn test_file_with_context(
     let workspace_root = fs::canonicalize(&workspace_root_raw).unwrap_or(workspace_root_raw);
     let bundled_stdlib_root = discover_bundled_stdlib_root().ok();
 
-    let tests = discover_workspace_tests(&snapshot, &workspace_root, bundled_stdlib_root.as_deref());
-    if tests.is_empty() {
+    let mut tests =
+        discover_workspace_tests(&snapshot, &workspace_root, bundled_stdlib_root.as_deref());
+    let (doctests, invalid_doctests) =
+        discover_workspace_doctests(&snapshot, &workspace_root, bundled_stdlib_root.as_deref());
+    tests.extend(doctests);
+    if tests.is_empty() && invalid_doctests.is_empty() {
         write_output_line(stderr, "no `@test` values found in the loaded workspace")?;
         return Ok(ExitCode::FAILURE);
     }
 
     let mut passed = 0usize;
-    let mut failed = 0usize;
+    let mut failed = invalid_doctests.len();
+    let mut timings = Vec::with_capacity(tests.len());
+    for (location, message) in invalid_doctests {
+        write_output_line(stderr, &format!("fail {location}: {message}"))?;
+    }
 
     for test in tests {
         let hir = query_hir_module(&snapshot.frontend.db, test.file);
         let module = hir.module();
-        let artifact = match prepare_test_artifact_with_query_context(
-            module,
-            test.owner,
-            Some(snapshot.backend_query_context()),
-        ) {
-            Ok(artifact) => artifact,
-            Err(message) => {
-                failed += 1;
-                write_output_line(stderr, &format!("fail {}: {message}", test.location))?;
-                continue;
-            }
+        // A doctest lives in its own synthetic module, which must also root the backend query.
+        let query_context = match test.doctest_expected {
+            Some(_) => BackendQueryContext {
+                db: &snapshot.frontend.db,
+                entry: test.file,
+            },
+            None => snapshot.backend_query_context(),
         };
-        let value = match evaluate_task_owner_value(
+        let artifact =
+            match prepare_test_artifact_with_query_context(module, test.owner, Some(query_context))
+            {
+                Ok(artifact) => artifact,
+                Err(message) => {
+                    failed += 1;
+                    write_output_line(stderr, &format!("fail {}: {message}", test.location))?;
+                    continue;
+                }
+            };
+        // Log entries are kept per test and shown only when that test fails.
+        let test_context = context
+            .clone()
+            .with_snapshot_test_file(&canonicalize_check_path(
+                &cwd,
+                &test.file.path(&snapshot.frontend.db),
+            ))
+            .with_log_capture();
+        // Every test starts from the same instant, so time-dependent tests are reproducible.
+        let test_context = if options.real_clock {
+            test_context
+        } else {
+            test_context.with_virtual_clock(TEST_VIRTUAL_CLOCK_START_MS)
+        };
+        let started = Instant::now();
+        let outcome = run_task_owner_value(
             path,
             artifact,
-            context.clone(),
+            test_context.clone(),
+            ExecuteOptions::default(),
             "`aivi test`",
             &format!("test `{}`", test.name),
-        ) {
-            Ok(value) => value,
-            Err(message) => {
-                failed += 1;
-                write_output_line(stderr, &format!("fail {}: {message}", test.location))?;
-                continue;
-            }
-        };
-        match execute_test_task_value(value, &context, stdout, stderr) {
+            |value, applier, globals| match &test.doctest_expected {
+                Some(expected) => execute_doctest_task_value(
+                    value,
+                    expected,
+                    &test_context,
+                    stdout,
+                    stderr,
+                    applier,
+                    globals,
+                ),
+                None => {
+                    execute_test_task_value(value, &test_context, stdout, stderr, applier, globals)
+                }
+            },
+        );
+        timings.push(TestTiming {
+            location: test.location.clone(),
+            duration: started.elapsed(),
+        });
+        let log = test_context.take_captured_log();
+        match outcome {
             Ok(TestTaskOutcome {
                 passed: true,
                 detail,
@@ -639,14 +726,18 @@ fn test_file_with_context(
                     }
                     None => write_output_line(stderr, &format!("fail {}", test.location))?,
                 }
+                write_captured_log(stderr, &log)?;
             }
             Err(message) => {
                 failed += 1;
                 write_output_line(stderr, &format!("fail {}: {message}", test.location))?;
+                write_captured_log(stderr, &log)?;
             }
         }
     }
 
+    write_test_timing_report(stdout, &mut timings, options)?;
+
     let total = passed + failed;
     if failed == 0 {
         write_output_line(
@@ -663,6 +754,65 @@ fn test_file_with_context(
     }
 }
 
+/// Show the log entries a failing test wrote, indented under its `fail` line.
+fn write_captured_log(stderr: &mut impl Write, log: &str) -> Result<(), String> {
+    for line in log.lines() {
+        write_output_line(stderr, &format!("  log: {line}"))?;
+    }
+    Ok(())
+}
+
+fn write_test_timing_report(
+    stdout: &mut impl Write,
+    timings: &mut [TestTiming],
+    options: TestReportOptions,
+) -> Result<(), String> {
+    // Stable sort keeps the discovery order for equally fast tests.
+    timings.sort_by_key(|timing| std::cmp::Reverse(timing.duration));
+    if let Some(threshold) = options.slow_threshold {
+        let slow = timings
+            .iter()
+            .filter(|timing| timing.duration >= threshold)
+            .collect::<Vec<_>>();
+        if !slow.is_empty() {
+            write_output_line(
+                stdout,
+                &format!(
+                    "{} slow test{} (>= {}):",
+                    slow.len(),
+                    plural_suffix(slow.len()),
+                    format_duration_compact(threshold)
+                ),
+            )?;
+            for timing in slow {
+                write_test_timing_line(stdout, timing)?;
+            }
+        }
+    }
+    if options.slowest > 0 && !timings.is_empty() {
+        let shown = options.slowest.min(timings.len());
+        write_output_line(
+            stdout,
+            &format!("slowest {shown} test{}:", plural_suffix(shown)),
+        )?;
+        for timing in &timings[..shown] {
+            write_test_timing_line(stdout, timing)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_test_timing_line(stdout: &mut impl Write, timing: &TestTiming) -> Result<(), String> {
+    write_output_line(
+        stdout,
+        &format!(
+            "    {:>8}  {}",
+            format_duration_compact(timing.duration),
+            timing.location
+        ),
+    )
+}
+
 fn current_execute_source_context(
     entry_path: &Path,
     program_args: &[String],
@@ -706,6 +856,8 @@ struct DiscoveredWorkspaceTest {
     owner: HirItemId,
     name: Box<str>,
     location: String,
+    /// The expected value written after `=>`, for a doc comment example.
+    doctest_expected: Option<Box<str>>,
 }
 
 fn discover_workspace_tests(
@@ -738,6 +890,7 @@ fn discover_workspace_tests(
                     source_location(&snapshot.sources, value.header.span),
                     value.name.text()
                 ),
+                doctest_expected: None,
             });
         }
     }
@@ -749,9 +902,75 @@ fn discover_workspace_tests(
     tests
 }
 
+/// Turn the doc comment examples of every project file into tests, in source order. Examples that
+/// cannot become a test are returned as `(location, message)` failures instead.
+fn discover_workspace_doctests(
+    snapshot: &WorkspaceHirSnapshot,
+    workspace_root: &Path,
+    bundled_stdlib_root: Option<&Path>,
+) -> (Vec<DiscoveredWorkspaceTest>, Vec<(String, String)>) {
+    let db = &snapshot.frontend.db;
+    let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let mut tests = Vec::new();
+    let mut invalid = Vec::new();
+    for file in &snapshot.files {
+        let file_path = canonicalize_check_path(&cwd, &file.path(db));
+        if !include_project_workspace_file(workspace_root, bundled_stdlib_root, &file_path) {
+            continue;
+        }
+        let doctests = aivi_query::module_doctests(db, *file);
+        if doctests.is_empty() {
+            continue;
+        }
+        let source = file.source(db);
+        let program = aivi_query::doctest_module(db, *file, &doctests);
+        let hir = query_hir_module(db, program.file);
+        let module = hir.module();
+        for (doctest, test) in doctests.into_iter().zip(program.tests) {
+            let position = source.line_column(doctest.span.start());
+            let location = format!(
+                "{}:{}:{}::{} (doctest)",
+                source.path().display(),
+                position.line,
+                position.column,
+                doctest.owner
+            );
+            let name = match test {
+                Ok(name) => name,
+                Err(message) => {
+                    invalid.push((location, message));
+                    continue;
+                }
+            };
+            let owner = module
+                .items()
+                .iter()
+                .find_map(|(item_id, item)| match item {
+                    Item::Value(value) if value.name.text() == name => Some(item_id),
+                    _ => None,
+                });
+            match owner {
+                Some(owner) => tests.push(DiscoveredWorkspaceTest {
+                    file: program.file,
+                    owner,
+                    name: name.into(),
+                    location,
+                    doctest_expected: Some(doctest.expected.into()),
+                }),
+                None => invalid.push((
+                    location,
+                    format!("failed to lower example `{}`", doctest.expression),
+                )),
+            }
+        }
+    }
+    (tests, invalid)
+}
+
 fn execute_file_with_context(
     path: &Path,
     context: SourceProviderContext,
+    options: ExecuteOptions,
     stdout: &mut impl Write,
     stderr: &mut impl Write,
 ) -> Result<ExitCode, String> {
@@ -781,7 +1000,7 @@ fn execute_file_with_context(
             return Ok(ExitCode::FAILURE);
         }
     };
-    if let Err(message) = launch_execute(path, artifact, context, stdout, stderr) {
+    if let Err(message) = launch_execute(path, artifact, context, options, stdout, stderr) {
         write_output_line(stderr, &message)?;
         return Ok(ExitCode::FAILURE);
     }
@@ -990,21 +1209,40 @@ fn launch_execute(
     path: &Path,
     artifact: ExecuteArtifact,
     context: SourceProviderContext,
+    options: ExecuteOptions,
     stdout: &mut impl Write,
     stderr: &mut impl Write,
 ) -> Result<(), String> {
-    let value =
-        evaluate_task_owner_value(path, artifact, context.clone(), "`aivi execute`", "`main`")?;
-    execute_main_task_value(value, &context, stdout, stderr)
+    run_task_owner_value(
+        path,
+        artifact,
+        context.clone(),
+        options,
+        "`aivi execute`",
+        "`main`",
+        |value, applier, globals| {
+            execute_main_task_value(value, &context, stdout, stderr, applier, globals)
+        },
+    )
 }
 
-fn evaluate_task_owner_value(
+/// Evaluate the task owned by `artifact` and hand its value to `run` along with the engine that
+/// produced it, so deferred task compositions can call back into the same engine. With
+/// `backtrace`, evaluation errors name the items and functions that led to them; an
+/// `allocation_budget` bounds the estimated bytes each evaluation allocates.
+fn run_task_owner_value<T>(
     path: &Path,
     artifact: ExecuteArtifact,
     context: SourceProviderContext,
+    options: ExecuteOptions,
     command_name: &str,
     entry_name: &str,
-) -> Result<RuntimeValue, String> {
+    run: impl FnOnce(
+        RuntimeValue,
+        &mut dyn TaskFunctionApplier,
+        &BTreeMap<BackendItemId, RuntimeValue>,
+    ) -> Result<T, String>,
+) -> Result<T, String> {
     let ExecuteArtifact {
         task_owner,
         runtime_assembly,
@@ -1015,19 +1253,24 @@ fn evaluate_task_owner_value(
     if let Some(backend_item) = backend_item {
         let executable = BackendExecutableProgram::interpreted(backend.as_ref())
             .with_execution_options(aivi_backend::BackendExecutionOptions {
-                prefer_interpreter: cfg!(test),
+                prefer_interpreter: cfg!(test)
+                    || options.backtrace
+                    || options.allocation_budget.is_some(),
+                trace_errors: options.backtrace,
+                allocation_budget: options.allocation_budget,
                 ..Default::default()
             });
         let mut evaluator = executable.create_engine();
         let globals = BTreeMap::new();
-        return evaluator
+        let value = evaluator
             .evaluate_item(backend_item, &globals)
             .map_err(|error| {
                 format!(
                     "failed to evaluate {entry_name} for {command_name} in {}: {error}",
                     path.display()
                 )
-            });
+            })?;
+        return run(value, &mut *evaluator, &globals);
     }
     let Some(runtime_assembly) = runtime_assembly else {
         return Err(format!(
@@ -1053,17 +1296,18 @@ fn evaluate_task_owner_value(
         }
         rendered
     })?;
+    linked.set_error_traces(options.backtrace);
+    linked.set_allocation_budget(options.allocation_budget);
     let mut providers = SourceProviderManager::with_context(context);
     settle_execute_sources(&mut linked, &mut providers)?;
     linked
-        .evaluate_task_value_by_owner(task_owner)
-        .map(|value| value.into_runtime())
+        .with_task_value_by_owner(task_owner, run)
         .map_err(|error| {
             format!(
                 "failed to evaluate {entry_name} for {command_name} in {}: {error}",
                 path.display()
             )
-        })
+        })?
 }
 
 fn settle_execute_sources(
@@ -1130,14 +1374,18 @@ fn execute_main_task_value(
     context: &SourceProviderContext,
     stdout: &mut impl Write,
     stderr: &mut impl Write,
+    applier: &mut dyn TaskFunctionApplier,
+    globals: &BTreeMap<BackendItemId, RuntimeValue>,
 ) -> Result<(), String> {
     if !matches!(&value, RuntimeValue::Task(_) | RuntimeValue::DbTask(_)) {
         return Err(format!(
             "`aivi execute` expected `main` to evaluate to a task plan, found `{value}`"
         ));
     }
-    let result = execute_runtime_value_with_context(value, context, stdout, stderr)
-        .map_err(|error| error.to_string())?;
+    let result = execute_runtime_value_with_context_and_applier(
+        value, context, stdout, stderr, applier, globals,
+    )
+    .map_err(|error| error.to_string())?;
     if result != RuntimeValue::Unit {
         write_output_line(stdout, &result.to_string())?;
     }
@@ -1149,14 +1397,18 @@ fn execute_test_task_value(
     context: &SourceProviderContext,
     stdout: &mut impl Write,
     stderr: &mut impl Write,
+    applier: &mut dyn TaskFunctionApplier,
+    globals: &BTreeMap<BackendItemId, RuntimeValue>,
 ) -> Result<TestTaskOutcome, String> {
     if !matches!(&value, RuntimeValue::Task(_) | RuntimeValue::DbTask(_)) {
         return Err(format!(
             "`aivi test` expected each `@test` value to evaluate to a task plan, found `{value}`"
         ));
     }
-    let result = execute_runtime_value_with_context(value, context, stdout, stderr)
-        .map_err(|error| error.to_string())?;
+    let result = execute_runtime_value_with_context_and_applier(
+        value, context, stdout, stderr, applier, globals,
+    )
+    .map_err(|error| error.to_string())?;
     Ok(match result {
         RuntimeValue::Unit => TestTaskOutcome {
             passed: true,
@@ -1200,6 +1452,40 @@ fn execute_test_task_value(
     })
 }
 
+/// Run one doctest, whose task yields `Ok actual` when the example matched its expected value and
+/// `Err actual` when it did not.
+fn execute_doctest_task_value(
+    value: RuntimeValue,
+    expected: &str,
+    context: &SourceProviderContext,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+    applier: &mut dyn TaskFunctionApplier,
+    globals: &BTreeMap<BackendItemId, RuntimeValue>,
+) -> Result<TestTaskOutcome, String> {
+    let result = execute_runtime_value_with_context_and_applier(
+        value, context, stdout, stderr, applier, globals,
+    )
+    .map_err(|error| error.to_string())?;
+    Ok(match result {
+        RuntimeValue::ResultOk(_) => TestTaskOutcome {
+            passed: true,
+            detail: None,
+        },
+        RuntimeValue::ResultErr(actual) => TestTaskOutcome {
+            passed: false,
+            detail: Some(format!(
+                "example does not match its expected value:\n  - {expected}\n  + {actual}"
+            )),
+        },
+        other => {
+            return Err(format!(
+                "doctest task should produce a `Result`, found `{other}`"
+            ));
+        }
+    })
+}
+
 fn write_output_line(target: &mut impl Write, text: &str) -> Result<(), String> {
     writeln!(target, "{text}").map_err(|error| format!("failed to write CLI output: {error}"))
 }
@@ -89,12 +89,20 @@ fn run() -> Result<ExitCode, String> {
         return run_fmt(args);
     }
 
+    if first == "verify" {
+        return run_verify(args);
+    }
+
     if first == "openapi-gen" {
         return run_openapi_gen(args);
     }
 
+    if first == "doc" {
+        return run_doc(args);
+    }
+
     // Default: treat the first argument as a path and run `check`.
-    check_file(&PathBuf::from(first), false)
+    check_file(&PathBuf::from(first), CheckOptions::default())
 }
 
 enum PathOrHelp {
@@ -126,7 +134,7 @@ fn run_fmt(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, String>
         return print_help(Some(std::ffi::OsStr::new("fmt")));
     }
 
-    if next == "--stdin" {
+    if next == "--stdin" || next == "-" {
         return format_stdin();
     }
 
@@ -140,6 +148,40 @@ fn run_fmt(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, String>
     format_file(&PathBuf::from(next))
 }
 
+fn run_verify(args: impl Iterator<Item = OsString>) -> Result<ExitCode, String> {
+    let mut frozen = false;
+    let mut requested_path = None;
+
+    for argument in args {
+        if argument == "--help" || argument == "-h" {
+            return print_help(Some(std::ffi::OsStr::new("verify")));
+        }
+        if argument == "--frozen" {
+            frozen = true;
+            continue;
+        }
+        if requested_path.replace(PathBuf::from(&argument)).is_some() {
+            return Err("verify path was provided more than once".to_owned());
+        }
+    }
+    if !frozen {
+        return Err(
+            "expected `--frozen` for `verify`\nUsage: aivi verify --frozen [path]".to_owned(),
+        );
+    }
+
+    let target = match requested_path {
+        Some(path) => path,
+        None => {
+            let cwd = env::current_dir().map_err(|error| {
+                format!("failed to determine current directory for `aivi verify`: {error}")
+            })?;
+            discover_workspace_root_from_directory(&cwd)
+        }
+    };
+    verify_frozen(&target)
+}
+
 fn run_openapi_gen(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, String> {
     let Some(next) = args.next() else {
         return Err("expected a spec path argument after `openapi-gen`\n\
@@ -189,6 +231,31 @@ fn run_openapi_gen(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode,
     Ok(ExitCode::SUCCESS)
 }
 
+fn run_doc(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, String> {
+    let mut requested_path = None;
+    let mut output_path = None;
+
+    while let Some(argument) = args.next() {
+        if argument == "--help" || argument == "-h" {
+            return print_help(Some(std::ffi::OsStr::new("doc")));
+        }
+        if argument == "-o" || argument == "--output" {
+            let path = args
+                .next()
+                .map(PathBuf::from)
+                .ok_or_else(|| "expected a path after `-o` for `doc`".to_owned())?;
+            output_path = Some(path);
+            continue;
+        }
+        if requested_path.replace(PathBuf::from(&argument)).is_some() {
+            return Err("doc path was provided more than once".to_owned());
+        }
+    }
+
+    let path = resolve_command_entrypoint("doc", requested_path.as_deref())?;
+    document_file(&path, output_path.as_deref())
+}
+
 /// Resolve the entry file for a CLI command, using `aivi.toml` `[run] entry`
 /// as the fallback when no explicit path is provided on the command line.
 fn resolve_command_entrypoint(
@@ -205,14 +272,18 @@ fn resolve_command_entrypoint(
 
 fn run_check(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, String> {
     let mut requested_path = None;
-    let mut timings = false;
+    let mut options = CheckOptions::default();
 
     while let Some(argument) = args.next() {
         if argument == "--help" || argument == "-h" {
             return print_help(Some(std::ffi::OsStr::new("check")));
         }
         if argument == "--timings" {
-            timings = true;
+            options.timings = true;
+            continue;
+        }
+        if argument == "--max-errors" {
+            options.max_errors = parse_count_flag("check", "--max-errors", args.next())?;
             continue;
         }
         if argument == "--path" {
@@ -233,7 +304,7 @@ fn run_check(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, Strin
     // Directory: check every .aivi file found recursively inside it.
     if let Some(ref dir) = requested_path
         && dir.is_dir() {
-            return check_directory(dir, timings);
+            return check_directory(dir, options);
         }
 
     // No path given and the manifest declares multiple apps: check them all.
@@ -245,22 +316,39 @@ fn run_check(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, Strin
         let manifest = parse_manifest(&workspace_root)
             .map_err(|message| format!("failed to parse aivi.toml: {message}"))?;
         if manifest.apps.len() > 1 {
-            return check_all_apps(&manifest.apps, &workspace_root, timings);
+            return check_all_apps(&manifest.apps, &workspace_root, options);
         }
     }
 
     let path = resolve_command_entrypoint("check", requested_path.as_deref())?;
-    check_file(&path, timings)
+    check_file(&path, options)
 }
 
 fn run_compile(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, String> {
     let mut requested_path = None;
     let mut output = None;
+    let mut optimization = OptimizationLevel::None;
 
     while let Some(argument) = args.next() {
         if argument == "--help" || argument == "-h" {
             return print_help(Some(std::ffi::OsStr::new("compile")));
         }
+        if argument == "--opt-level" {
+            let value = args
+                .next()
+                .ok_or_else(|| "expected a level after `--opt-level` for `compile`".to_owned())?;
+            optimization = value
+                .to_str()
+                .and_then(|value| value.parse::<u8>().ok())
+                .and_then(OptimizationLevel::from_level)
+                .ok_or_else(|| {
+                    format!(
+                        "`--opt-level` expects 0 or 1, found `{}`",
+                        value.to_string_lossy()
+                    )
+                })?;
+            continue;
+        }
         if argument == "--path" {
             let path = args
                 .next()
@@ -287,7 +375,7 @@ fn run_compile(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, Str
     }
 
     let path = resolve_command_entrypoint("compile", requested_path.as_deref())?;
-    compile_file(&path, output.as_deref())
+    compile_file(&path, output.as_deref(), optimization)
 }
 
 fn run_build(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, String> {
@@ -569,6 +657,7 @@ fn run_execute(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, Str
     let mut requested_path = None;
     let mut program_args = Vec::new();
     let mut accepting_program_args = false;
+    let mut options = ExecuteOptions::default();
 
     while let Some(argument) = args.next() {
         if accepting_program_args {
@@ -578,6 +667,18 @@ fn run_execute(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, Str
         if argument == "--help" || argument == "-h" {
             return print_help(Some(std::ffi::OsStr::new("execute")));
         }
+        if argument == "--backtrace" {
+            options.backtrace = true;
+            continue;
+        }
+        if argument == "--allocation-budget" {
+            options.allocation_budget = Some(parse_count_flag(
+                "execute",
+                "--allocation-budget",
+                args.next(),
+            )?);
+            continue;
+        }
         if argument == "--path" {
             let path = args
                 .next()
@@ -603,16 +704,30 @@ fn run_execute(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, Str
     }
 
     let path = resolve_command_entrypoint("execute", requested_path.as_deref())?;
-    execute_file(&path, &program_args)
+    execute_file(&path, &program_args, options)
 }
 
 fn run_test(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, String> {
     let mut requested_path = None;
+    let mut options = TestReportOptions::default();
 
     while let Some(argument) = args.next() {
         if argument == "--help" || argument == "-h" {
             return print_help(Some(std::ffi::OsStr::new("test")));
         }
+        if argument == "--slow" {
+            let millis = parse_count_flag("test", "--slow", args.next())?;
+            options.slow_threshold = Some(Duration::from_millis(millis as u64));
+            continue;
+        }
+        if argument == "--slowest" {
+            options.slowest = parse_count_flag("test", "--slowest", args.next())?;
+            continue;
+        }
+        if argument == "--real-clock" {
+            options.real_clock = true;
+            continue;
+        }
         if argument == "--path" {
             let path = args
                 .next()
@@ -629,7 +744,20 @@ fn run_test(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, String
     }
 
     let path = resolve_command_entrypoint("test", requested_path.as_deref())?;
-    test_file(&path)
+    test_file(&path, options)
+}
+
+fn parse_count_flag(command: &str, flag: &str, value: Option<OsString>) -> Result<usize, String> {
+    let value = value.ok_or_else(|| format!("expected a number after `{flag}` for `{command}`"))?;
+    value
+        .to_str()
+        .and_then(|text| text.parse::<usize>().ok())
+        .ok_or_else(|| {
+            format!(
+                "expected a non-negative integer after `{flag}` for `{command}`, found `{}`",
+                value.to_string_lossy()
+            )
+        })
 }
 
 fn validate_module_path(path: &[&str]) -> Result<(), String> {
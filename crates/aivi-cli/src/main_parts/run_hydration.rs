@@ -2457,7 +2457,7 @@ fn evaluate_compiled_run_text<'a>(
             }
         }
     }
-    Ok(RuntimeValue::Text(rendered.into_boxed_str()))
+    Ok(RuntimeValue::Text(rendered.into()))
 }
 
 fn runtime_bool(value: RuntimeValue) -> Option<bool> {
@@ -2529,6 +2529,15 @@ fn match_pattern(
             strip_signal_runtime_value(value.clone()),
             RuntimeValue::Text(found) if expected.as_ref() == found.as_ref()
         )),
+        RunPatternKind::TextPrefix { prefix, rest } => {
+            let RuntimeValue::Text(found) = strip_signal_runtime_value(value.clone()) else {
+                return Ok(false);
+            };
+            let Some(suffix) = found.strip_prefix(prefix.as_ref()) else {
+                return Ok(false);
+            };
+            match_pattern(patterns, *rest, &RuntimeValue::Text(suffix.into()), bindings)
+        }
         RunPatternKind::Tuple(elements) => {
             let RuntimeValue::Tuple(found) = strip_signal_runtime_value(value.clone()) else {
                 return Ok(false);
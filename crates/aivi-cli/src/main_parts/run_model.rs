@@ -305,6 +305,10 @@ enum RunPatternKind {
     Text {
         value: Box<str>,
     },
+    TextPrefix {
+        prefix: Box<str>,
+        rest: PatternRef,
+    },
     Tuple(Box<[PatternRef]>),
     List {
         elements: Box<[PatternRef]>,
@@ -719,6 +719,24 @@ fn collect_run_pattern(
             })?
             .into(),
         },
+        PatternKind::TextPrefix { prefix, rest } => {
+            let rest = PatternRef {
+                origin_file: pattern_id.origin_file,
+                pattern: *rest,
+            };
+            collect_run_pattern(sources, module, workspace_hirs, rest, patterns, visited)?;
+            RunPatternKind::TextPrefix {
+                prefix: text_literal_static_text(prefix)
+                    .ok_or_else(|| {
+                        format!(
+                            "run artifact cannot serialize non-static text pattern at {}",
+                            source_location(sources, pattern.span)
+                        )
+                    })?
+                    .into(),
+                rest,
+            }
+        }
         PatternKind::Tuple(elements) => {
             let children = elements
                 .iter()
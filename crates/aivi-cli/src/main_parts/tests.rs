@@ -1,6 +1,7 @@
 use super::{
-    HydratedRunNode, ResolvedRunEventHandler, ResolvedRunEventPayload, RunFragmentExecutionUnit,
-    RunHydrationPreparationMode, RunHydrationStaticState, WorkspaceHirSnapshot, check_file,
+    CheckOptions, ExecuteOptions, HydratedRunNode, ResolvedRunEventHandler,
+    ResolvedRunEventPayload, RunFragmentExecutionUnit, RunHydrationPreparationMode,
+    RunHydrationStaticState, TestReportOptions, WorkspaceHirSnapshot, check_file,
     execute_file_with_context, plan_run_hydration, prepare_execute_artifact, prepare_run_artifact,
     prepare_run_artifact_with_metrics_and_progress, run_hydration_globals_ready,
     test_file_with_context,
@@ -829,8 +830,14 @@ fn execute_workspace(path: &Path, context: SourceProviderContext) -> (ExitCode,
     ensure_interpreted_main_parts_tests();
     let mut stdout = Vec::new();
     let mut stderr = Vec::new();
-    let code = execute_file_with_context(path, context, &mut stdout, &mut stderr)
-        .expect("execute should return an exit code");
+    let code = execute_file_with_context(
+        path,
+        context,
+        ExecuteOptions::default(),
+        &mut stdout,
+        &mut stderr,
+    )
+    .expect("execute should return an exit code");
     (
         code,
         String::from_utf8(stdout).expect("stdout should stay UTF-8 in tests"),
@@ -839,10 +846,18 @@ fn execute_workspace(path: &Path, context: SourceProviderContext) -> (ExitCode,
 }
 
 fn test_workspace(path: &Path, context: SourceProviderContext) -> (ExitCode, String, String) {
+    test_workspace_with_options(path, context, TestReportOptions::default())
+}
+
+fn test_workspace_with_options(
+    path: &Path,
+    context: SourceProviderContext,
+    options: TestReportOptions,
+) -> (ExitCode, String, String) {
     ensure_interpreted_main_parts_tests();
     let mut stdout = Vec::new();
     let mut stderr = Vec::new();
-    let code = test_file_with_context(path, context, &mut stdout, &mut stderr)
+    let code = test_file_with_context(path, context, options, &mut stdout, &mut stderr)
         .expect("test should return an exit code");
     (
         code,
@@ -909,13 +924,83 @@ value view =
 "#
 }
 
+#[test]
+fn workspace_validation_reports_fan_out_modules_in_sequential_order() {
+    let root = TempDir::new("fan-out-validation");
+    root.write("aivi.toml", "");
+    root.write("shared/base.aivi", "value seed : Int = 1\n\nexport seed\n");
+    let mut entry = String::new();
+    for index in 0..32 {
+        // Every fourth leaf is broken, so diagnostics come from modules spread across the list.
+        let body = if index % 4 == 0 {
+            "\"text\"".to_owned()
+        } else {
+            format!("seed + {index}")
+        };
+        root.write(
+            &format!("leaves/leaf{index}.aivi"),
+            &format!(
+                "use shared.base (\n    seed\n)\n\nvalue leaf{index} : Int = {body}\n\nexport leaf{index}\n"
+            ),
+        );
+        entry.push_str(&format!("use leaves.leaf{index} (\n    leaf{index}\n)\n"));
+    }
+    entry.push_str("\nvalue total : Int = leaf1 + leaf2\n");
+    let path = root.write("main.aivi", &entry);
+    let snapshot = WorkspaceHirSnapshot::load(&path).expect("fan-out workspace should load");
+
+    let mut sequential = Vec::new();
+    for file in &snapshot.files {
+        let hir = super::query_hir_module(&snapshot.frontend.db, *file);
+        sequential.extend(hir.hir_diagnostics().iter().cloned());
+        let lowering_failed = hir
+            .hir_diagnostics()
+            .iter()
+            .any(|diagnostic| diagnostic.severity == aivi_base::Severity::Error);
+        let mode = if lowering_failed {
+            ValidationMode::Structural
+        } else {
+            ValidationMode::RequireResolvedNames
+        };
+        sequential.extend(hir.module().validate(mode).diagnostics().iter().cloned());
+    }
+
+    let reported = std::cell::RefCell::new(Vec::new());
+    let report = |_: &SourceDatabase, diagnostics: &[aivi_base::Diagnostic]| {
+        reported.borrow_mut().extend(diagnostics.iter().cloned());
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == aivi_base::Severity::Error)
+    };
+    let (lowering_failed, validation_failed) =
+        super::workspace_hir_failed(&snapshot, report, report);
+
+    assert!(
+        !lowering_failed,
+        "the fan-out workspace should lower cleanly"
+    );
+    assert!(
+        validation_failed,
+        "the broken leaves should fail validation"
+    );
+    let reported = reported.into_inner();
+    assert_eq!(reported, sequential);
+    assert_eq!(
+        reported
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == aivi_base::Severity::Error)
+            .count(),
+        8
+    );
+}
+
 #[test]
 fn check_accepts_milestone_two_valid_fixtures() {
     for path in [
         "milestone-2/valid/source-decorator-signals/main.aivi",
         "milestone-2/valid/pipe-explicit-recurrence-wakeups/main.aivi",
     ] {
-        let result = check_file(&fixture(path), false).expect("check should run");
+        let result = check_file(&fixture(path), CheckOptions::default()).expect("check should run");
         assert_eq!(result, ExitCode::SUCCESS, "expected {path} to pass");
     }
 }
@@ -924,7 +1009,7 @@ fn check_accepts_milestone_two_valid_fixtures() {
 fn check_rejects_milestone_two_invalid_fixture() {
     let result = check_file(
         &fixture("milestone-2/invalid/unknown-decorator/main.aivi"),
-        false,
+        CheckOptions::default(),
     )
     .expect("check should run");
     assert_eq!(result, ExitCode::FAILURE);
@@ -2988,6 +3073,316 @@ value service_smoke : Task Text Bool =
     assert!(stdout.contains("test result: ok. 2 passed; 0 failed; 2 total"));
 }
 
+#[test]
+fn test_command_reports_process_exit_as_a_test_failure() {
+    let workspace = TempDir::new("workspace-test-exit");
+    let entry = workspace.write(
+        "main.aivi",
+        r#"
+use aivi.process (
+    ProcessSource
+)
+
+@source process
+signal runtime : ProcessSource
+
+@test
+value exits_early : Task Text Unit = runtime.exit 3
+
+@test
+value still_runs : Task Text Bool = pure True
+"#,
+    );
+
+    let (code, stdout, stderr) = test_workspace(
+        &entry,
+        SourceProviderContext::new(Vec::new(), workspace.path().to_path_buf(), BTreeMap::new()),
+    );
+
+    assert_eq!(code, ExitCode::FAILURE);
+    assert!(
+        stderr.contains("process.exit(3) was called"),
+        "exit should surface as a test failure, found {stderr:?}"
+    );
+    assert!(stdout.contains("still_runs"));
+    assert!(stdout.contains("1 passed; 1 failed; 2 total"), "{stdout}");
+}
+
+const EFFECT_ORDER_SOURCE: &str = r#"
+use aivi.stdio (
+    stdoutWrite
+)
+
+type Unit -> (Task Text Unit)
+func writeTwo = ignored =>
+    stdoutWrite "2\n"
+
+type Unit -> (Task Text Unit)
+func writeFour = ignored =>
+    stdoutWrite "4\n"
+
+type Unit -> Unit -> Unit
+func keepFirst = left right =>
+    left
+
+value main : Task Text Unit =
+    chain writeFour (apply (map keepFirst (chain writeTwo (stdoutWrite "1\n"))) (stdoutWrite "3\n"))
+
+@test
+value effectsRunInSourceOrder : Task Text Unit =
+    main
+"#;
+
+#[test]
+fn execute_runs_composed_task_effects_in_source_order() {
+    let workspace = TempDir::new("execute-effect-order");
+    let entry = workspace.write("main.aivi", EFFECT_ORDER_SOURCE);
+
+    let (code, stdout, stderr) = execute_workspace(
+        &entry,
+        SourceProviderContext::new(Vec::new(), workspace.path().to_path_buf(), BTreeMap::new()),
+    );
+
+    assert_eq!(code, ExitCode::SUCCESS, "stderr: {stderr}");
+    assert_eq!(stdout, "1\n2\n3\n4\n");
+}
+
+#[test]
+fn test_command_runs_composed_task_effects_in_source_order() {
+    let workspace = TempDir::new("workspace-test-effect-order");
+    let entry = workspace.write("main.aivi", EFFECT_ORDER_SOURCE);
+
+    let (code, stdout, stderr) = test_workspace(
+        &entry,
+        SourceProviderContext::new(Vec::new(), workspace.path().to_path_buf(), BTreeMap::new()),
+    );
+
+    assert_eq!(
+        code,
+        ExitCode::SUCCESS,
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(stdout.contains("1\n2\n3\n4\n"), "{stdout}");
+}
+
+const SNAPSHOT_TEST_SOURCE: &str = r#"
+use aivi.test (
+    matchesSnapshot
+)
+
+@test
+value greeting_matches : Task Text Bool = matchesSnapshot "greeting" "hello\nworld\n"
+"#;
+
+#[test]
+fn test_command_creates_missing_snapshots() {
+    let workspace = TempDir::new("workspace-test-snapshot-create");
+    let entry = workspace.write("main.aivi", SNAPSHOT_TEST_SOURCE);
+
+    let (code, stdout, stderr) = test_workspace(
+        &entry,
+        SourceProviderContext::new(Vec::new(), workspace.path().to_path_buf(), BTreeMap::new()),
+    );
+
+    assert_eq!(
+        code,
+        ExitCode::SUCCESS,
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    let snapshot = workspace.path().join("__snapshots__/main/greeting.snap");
+    assert_eq!(
+        fs::read_to_string(&snapshot).expect("snapshot should be created"),
+        "hello\nworld\n"
+    );
+}
+
+#[test]
+fn test_command_reports_snapshot_mismatches_with_a_diff() {
+    let workspace = TempDir::new("workspace-test-snapshot-mismatch");
+    let entry = workspace.write("main.aivi", SNAPSHOT_TEST_SOURCE);
+    workspace.write("__snapshots__/main/greeting.snap", "hello\nthere\n");
+
+    let (code, stdout, stderr) = test_workspace(
+        &entry,
+        SourceProviderContext::new(Vec::new(), workspace.path().to_path_buf(), BTreeMap::new()),
+    );
+
+    assert_eq!(code, ExitCode::FAILURE);
+    assert!(
+        stderr.contains("snapshot `greeting` does not match"),
+        "mismatch should surface as a test failure, found {stderr:?}"
+    );
+    assert!(stderr.contains("-    2 | there"), "{stderr}");
+    assert!(stderr.contains("+    2 | world"), "{stderr}");
+    assert!(stdout.contains("0 passed; 1 failed; 1 total"), "{stdout}");
+}
+
+#[test]
+fn test_command_rewrites_snapshots_in_update_mode() {
+    let workspace = TempDir::new("workspace-test-snapshot-update");
+    let entry = workspace.write("main.aivi", SNAPSHOT_TEST_SOURCE);
+    let snapshot = workspace.write("__snapshots__/main/greeting.snap", "stale\n");
+
+    let (code, stdout, stderr) = test_workspace(
+        &entry,
+        SourceProviderContext::new(
+            Vec::new(),
+            workspace.path().to_path_buf(),
+            BTreeMap::from([("AIVI_UPDATE_SNAPSHOTS".to_owned(), "1".to_owned())]),
+        ),
+    );
+
+    assert_eq!(
+        code,
+        ExitCode::SUCCESS,
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert_eq!(
+        fs::read_to_string(&snapshot).expect("snapshot should be readable"),
+        "hello\nworld\n"
+    );
+}
+
+const VIRTUAL_CLOCK_TEST_SOURCE: &str = r#"
+use aivi.time (
+    nowMs
+    sleep
+)
+use aivi.test (
+    setClock
+)
+
+type Int -> Int -> Bool
+func tenSecondsLater = before after =>
+    after - before == 10000
+
+type Int -> Task Text Bool
+func sleepTenSecondsFrom = before =>
+    chain (slept => map (tenSecondsLater before) nowMs) (sleep 10000)
+
+@test
+value sleep_advances_the_virtual_clock : Task Text Bool =
+    chain sleepTenSecondsFrom nowMs
+
+@test
+value set_clock_moves_now : Task Text Bool =
+    chain (reset => map (now => now == 0) nowMs) (setClock 0)
+"#;
+
+#[test]
+fn test_command_runs_sleeps_on_a_virtual_clock() {
+    let workspace = TempDir::new("workspace-test-virtual-clock");
+    let entry = workspace.write("main.aivi", VIRTUAL_CLOCK_TEST_SOURCE);
+
+    let started = Instant::now();
+    let (code, stdout, stderr) = test_workspace(
+        &entry,
+        SourceProviderContext::new(Vec::new(), workspace.path().to_path_buf(), BTreeMap::new()),
+    );
+
+    assert_eq!(
+        code,
+        ExitCode::SUCCESS,
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(
+        started.elapsed() < Duration::from_secs(10),
+        "a virtual sleep should not block, took {:?}",
+        started.elapsed()
+    );
+    assert!(stdout.contains("2 passed; 0 failed; 2 total"), "{stdout}");
+}
+
+#[test]
+fn test_command_rejects_clock_control_with_the_real_clock() {
+    let workspace = TempDir::new("workspace-test-real-clock");
+    let entry = workspace.write(
+        "main.aivi",
+        r#"
+use aivi.test (
+    setClock
+)
+
+@test
+value set_clock : Task Text Unit = setClock 0
+"#,
+    );
+
+    let (code, _, stderr) = test_workspace_with_options(
+        &entry,
+        SourceProviderContext::new(Vec::new(), workspace.path().to_path_buf(), BTreeMap::new()),
+        TestReportOptions {
+            real_clock: true,
+            ..TestReportOptions::default()
+        },
+    );
+
+    assert_eq!(code, ExitCode::FAILURE);
+    assert!(
+        stderr.contains("test.setClock(0) needs the virtual clock of `aivi test`"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_command_reports_slow_and_slowest_tests_when_requested() {
+    let workspace = TempDir::new("workspace-test-timings");
+    let entry = workspace.write(
+        "main.aivi",
+        r#"
+type Int -> Int -> Int
+func countUp = count remaining => remaining
+ ||> 0 -> count
+ ||> _ -> countUp (count + 1) (remaining - 1)
+
+@test
+value counts_up : Task Text Bool = pure (countUp 0 20000 == 20000)
+
+@test
+value trivially_ok : Task Text Bool = pure True
+"#,
+    );
+
+    let (code, stdout, stderr) = test_workspace_with_options(
+        &entry,
+        SourceProviderContext::new(Vec::new(), workspace.path().to_path_buf(), BTreeMap::new()),
+        TestReportOptions {
+            slow_threshold: Some(Duration::ZERO),
+            slowest: 1,
+            real_clock: false,
+        },
+    );
+
+    assert_eq!(code, ExitCode::SUCCESS);
+    assert!(
+        stderr.is_empty(),
+        "stderr should stay empty, found {stderr:?}"
+    );
+    assert!(stdout.contains("2 slow tests (>= "), "{stdout}");
+    let slowest = stdout
+        .lines()
+        .skip_while(|line| *line != "slowest 1 test:")
+        .nth(1)
+        .unwrap_or_else(|| panic!("slowest report should list one test, found {stdout:?}"));
+    assert!(slowest.ends_with("::counts_up"), "{stdout}");
+    // The report renders e.g. `12ms`; the counting loop must not round down to zero.
+    let elapsed = slowest
+        .split_whitespace()
+        .next()
+        .and_then(|duration| {
+            duration
+                .trim_end_matches(|ch: char| ch.is_alphabetic())
+                .parse::<f64>()
+                .ok()
+        })
+        .unwrap_or_else(|| panic!("slowest line should start with a duration: {slowest:?}"));
+    assert!(
+        elapsed > 0.0,
+        "counts_up should take measurable time: {slowest:?}"
+    );
+    assert!(stdout.contains("test result: ok. 2 passed; 0 failed; 2 total"));
+}
+
 #[test]
 fn production_entrypoint_selection_ignores_test_declarations() {
     let execute_workspace = TempDir::new("execute-test-entry");
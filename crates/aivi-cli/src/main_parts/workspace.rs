@@ -90,24 +90,57 @@ fn workspace_syntax_failed(
     failed
 }
 
+/// Lower every workspace file, then validate the lowered modules in parallel.
+///
+/// Validation, which includes type checking, only reads the module it checks, so modules are
+/// validated concurrently once the query database has lowered them. Diagnostics are still reported
+/// file by file in workspace order, each file's lowering diagnostics before its validation ones.
 fn workspace_hir_failed(
     snapshot: &WorkspaceHirSnapshot,
+    print_hir: impl FnMut(&SourceDatabase, &[Diagnostic]) -> bool,
+    print_validation: impl FnMut(&SourceDatabase, &[Diagnostic]) -> bool,
+) -> (bool, bool) {
+    workspace_hir_failed_with_max_errors(snapshot, 0, print_hir, print_validation)
+}
+
+/// Like [`workspace_hir_failed`], but type checking of each module stops after `max_errors`
+/// errors; 0 means no limit.
+fn workspace_hir_failed_with_max_errors(
+    snapshot: &WorkspaceHirSnapshot,
+    max_errors: usize,
     mut print_hir: impl FnMut(&SourceDatabase, &[Diagnostic]) -> bool,
     mut print_validation: impl FnMut(&SourceDatabase, &[Diagnostic]) -> bool,
 ) -> (bool, bool) {
+    let hirs = snapshot
+        .files
+        .iter()
+        .map(|file| query_hir_module(&snapshot.frontend.db, *file))
+        .collect::<Vec<_>>();
+    let validations = hirs
+        .par_iter()
+        .map(|hir| {
+            hir.module()
+                .validate_with_max_errors(workspace_validation_mode(hir), max_errors)
+        })
+        .collect::<Vec<_>>();
     let mut lowering_failed = false;
     let mut validation_failed = false;
-    for file in &snapshot.files {
-        let hir = query_hir_module(&snapshot.frontend.db, *file);
-        let file_lowering_failed = print_hir(&snapshot.sources, hir.hir_diagnostics());
-        lowering_failed |= file_lowering_failed;
-        let validation_mode = if file_lowering_failed {
-            ValidationMode::Structural
-        } else {
-            ValidationMode::RequireResolvedNames
-        };
-        let validation = hir.module().validate(validation_mode);
+    for (hir, validation) in hirs.iter().zip(&validations) {
+        lowering_failed |= print_hir(&snapshot.sources, hir.hir_diagnostics());
         validation_failed |= print_validation(&snapshot.sources, validation.diagnostics());
     }
     (lowering_failed, validation_failed)
 }
+
+/// A module that failed to lower only gets the structural checks, which tolerate unresolved names.
+fn workspace_validation_mode(hir: &HirModuleResult) -> ValidationMode {
+    if hir
+        .hir_diagnostics()
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+    {
+        ValidationMode::Structural
+    } else {
+        ValidationMode::RequireResolvedNames
+    }
+}
@@ -1,13 +1,13 @@
 use super::*;
 
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     ffi::OsString,
     io::{self, BufRead, BufReader, Write},
     path::{Path, PathBuf},
-    sync::mpsc as sync_mpsc,
+    sync::{Mutex, OnceLock, PoisonError, mpsc as sync_mpsc},
     thread,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
 use aivi_backend::{RuntimeFloat, RuntimeRecordField, RuntimeSumValue, RuntimeValue};
@@ -28,6 +28,7 @@ const HOST_POLL_INTERVAL: Duration = Duration::from_millis(10);
 const HYDRATION_SETTLE_TIMEOUT: Duration = Duration::from_secs(2);
 const HYDRATION_SETTLE_GRACE: Duration = Duration::from_millis(100);
 const CONTEXT_PUMP_ITERATION_BUDGET: usize = 256;
+const WORKSPACE_INDEX_URI: &str = "aivi://workspace/index";
 
 type HostTask = Box<dyn FnOnce(&mut McpHostState) + Send + 'static>;
 
@@ -40,6 +41,7 @@ enum JsonRpcTransport {
 pub(super) fn run_mcp(mut args: impl Iterator<Item = OsString>) -> Result<ExitCode, String> {
     let mut requested_path = None;
     let mut requested_view = None;
+    let mut policy = McpPolicy::default();
 
     while let Some(argument) = args.next() {
         if argument == "--help" || argument == "-h" {
@@ -70,8 +72,27 @@ pub(super) fn run_mcp(mut args: impl Iterator<Item = OsString>) -> Result<ExitCo
             continue;
         }
 
+        if argument == "--allow-tool" || argument == "--deny-tool" {
+            let name = args.next().ok_or_else(|| {
+                format!(
+                    "expected a tool name after `{}` for `mcp`",
+                    argument.to_string_lossy()
+                )
+            })?;
+            let name = name.to_string_lossy().into_owned();
+            if argument == "--allow-tool" {
+                policy
+                    .allowed_tools
+                    .get_or_insert_with(BTreeSet::new)
+                    .insert(name);
+            } else {
+                policy.denied_tools.insert(name);
+            }
+            continue;
+        }
+
         return Err(format!(
-            "unexpected argument `{}` for `mcp`; expected only `--path` and `--view`",
+            "unexpected argument `{}` for `mcp`; expected only `--path`, `--view`, `--allow-tool`, and `--deny-tool`",
             argument.to_string_lossy()
         ));
     }
@@ -88,6 +109,7 @@ pub(super) fn run_mcp(mut args: impl Iterator<Item = OsString>) -> Result<ExitCo
     let configured = ConfiguredTarget {
         entry_path,
         default_view: requested_view,
+        policy,
     };
 
     let (task_tx, task_rx) = sync_mpsc::channel::<HostTask>();
@@ -114,6 +136,25 @@ pub(super) fn run_mcp(mut args: impl Iterator<Item = OsString>) -> Result<ExitCo
 struct ConfiguredTarget {
     entry_path: Option<PathBuf>,
     default_view: Option<String>,
+    policy: McpPolicy,
+}
+
+/// Which tools the server exposes. Denials win over allowances; with no allow-list every
+/// tool not explicitly denied stays available.
+#[derive(Clone, Debug, Default)]
+struct McpPolicy {
+    allowed_tools: Option<BTreeSet<String>>,
+    denied_tools: BTreeSet<String>,
+}
+
+impl McpPolicy {
+    fn permits(&self, tool: &str) -> bool {
+        !self.denied_tools.contains(tool)
+            && self
+                .allowed_tools
+                .as_ref()
+                .is_none_or(|allowed| allowed.contains(tool))
+    }
 }
 
 #[derive(Clone)]
@@ -951,11 +992,22 @@ fn run_stdio_server(
     let stdout = io::stdout();
     let mut reader = BufReader::new(stdin.lock());
     let mut writer = stdout.lock();
-    let Some(transport) = detect_json_rpc_transport(&mut reader)? else {
+    serve_json_rpc_stream(&controller, &configured, &mut reader, &mut writer)?;
+    controller.shutdown();
+    Ok(())
+}
+
+fn serve_json_rpc_stream(
+    controller: &McpHostController,
+    configured: &ConfiguredTarget,
+    reader: &mut impl BufRead,
+    writer: &mut impl Write,
+) -> Result<(), String> {
+    let Some(transport) = detect_json_rpc_transport(reader)? else {
         return Ok(());
     };
 
-    while let Some(message) = read_json_rpc_message(&mut reader, transport)? {
+    while let Some(message) = read_json_rpc_message(reader, transport)? {
         let request: JsonRpcRequest = serde_json::from_value(message)
             .map_err(|error| format!("failed to decode MCP JSON-RPC request: {error}"))?;
         let Some(id) = request.id.clone() else {
@@ -964,7 +1016,7 @@ fn run_stdio_server(
             }
             continue;
         };
-        let response = match handle_json_rpc_request(&controller, &configured, request) {
+        let response = match handle_json_rpc_request(controller, configured, request) {
             Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
             Err(error) => json!({
                 "jsonrpc": "2.0",
@@ -976,10 +1028,8 @@ fn run_stdio_server(
                 }
             }),
         };
-        write_json_rpc_message(&mut writer, &response, transport)?;
+        write_json_rpc_message(writer, &response, transport)?;
     }
-
-    controller.shutdown();
     Ok(())
 }
 
@@ -996,7 +1046,7 @@ fn handle_json_rpc_request(
                 .and_then(|params| params.get("protocolVersion"))
                 .and_then(JsonValue::as_str)
                 .unwrap_or(MCP_PROTOCOL_VERSION),
-            "capabilities": { "tools": {} },
+            "capabilities": { "tools": {}, "resources": {} },
             "serverInfo": {
                 "name": "aivi",
                 "version": env!("CARGO_PKG_VERSION"),
@@ -1008,12 +1058,27 @@ fn handle_json_rpc_request(
             }
         })),
         "ping" => Ok(json!({})),
-        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/list" => Ok(json!({
+            "tools": tool_definitions()
+                .into_iter()
+                .filter(|tool| {
+                    tool["name"]
+                        .as_str()
+                        .is_some_and(|name| configured.policy.permits(name))
+                })
+                .collect::<Vec<_>>()
+        })),
         "tools/call" => handle_tool_call(
             controller,
             configured,
             request.params.unwrap_or(JsonValue::Null),
         ),
+        "resources/list" => Ok(json!({ "resources": resource_definitions() })),
+        "resources/read" => handle_resource_read(
+            controller,
+            configured,
+            request.params.unwrap_or(JsonValue::Null),
+        ),
         method => Err(JsonRpcError::method_not_found(method)),
     }
 }
@@ -1025,6 +1090,12 @@ fn handle_tool_call(
 ) -> Result<JsonValue, JsonRpcError> {
     let request: ToolCallRequest = serde_json::from_value(params)
         .map_err(|error| JsonRpcError::invalid_params(error.to_string()))?;
+    if !configured.policy.permits(&request.name) {
+        return Err(JsonRpcError::tool_failure(format!(
+            "tool `{}` is disabled by the server policy",
+            request.name
+        )));
+    }
     let arguments = request
         .arguments
         .unwrap_or(JsonValue::Object(Default::default()));
@@ -1244,25 +1315,7 @@ fn handle_tool_call(
             };
             let snapshot =
                 WorkspaceHirSnapshot::load(&entry_path).map_err(JsonRpcError::tool_failure)?;
-            let mut diagnostics: Vec<JsonValue> = Vec::new();
-            for file in &snapshot.files {
-                let hir = query_hir_module(&snapshot.frontend.db, *file);
-                for diag in hir.diagnostics() {
-                    diagnostics.push(serialize_diagnostic(diag, &snapshot.sources));
-                }
-                let file_lowering_failed = hir
-                    .hir_diagnostics()
-                    .iter()
-                    .any(|d| d.severity == Severity::Error);
-                let validation_mode = if file_lowering_failed {
-                    ValidationMode::Structural
-                } else {
-                    ValidationMode::RequireResolvedNames
-                };
-                for diag in hir.module().validate(validation_mode).diagnostics() {
-                    diagnostics.push(serialize_diagnostic(diag, &snapshot.sources));
-                }
-            }
+            let diagnostics = workspace_diagnostics(&snapshot);
             let error_count = diagnostics
                 .iter()
                 .filter(|d| d.get("severity").and_then(|s| s.as_str()) == Some("error"))
@@ -1399,11 +1452,140 @@ fn handle_tool_call(
                 }),
             )
         }
+        "format" => {
+            let args: FormatArgs = serde_json::from_value(arguments)
+                .map_err(|error| JsonRpcError::invalid_params(error.to_string()))?;
+            let configured_target = effective_configured_target(controller, configured);
+            let root = project_root(&configured_target);
+            let (label, text, options_path) = match (args.path, args.content) {
+                (Some(path), None) => {
+                    let resolved = root.join(&path);
+                    let text = std::fs::read_to_string(&resolved).map_err(|error| {
+                        JsonRpcError::tool_failure(format!(
+                            "failed to read `{}`: {error}",
+                            resolved.display()
+                        ))
+                    })?;
+                    (path, text, resolved)
+                }
+                (None, Some(content)) => {
+                    // Inline content takes the settings of the configured workspace.
+                    let options_path = configured_target
+                        .entry_path
+                        .clone()
+                        .unwrap_or_else(|| root.join("main.aivi"));
+                    ("<content>".to_owned(), content, options_path)
+                }
+                _ => {
+                    return Err(JsonRpcError::invalid_params(
+                        "`format` expects exactly one of `path` or `content`",
+                    ));
+                }
+            };
+            let mut options =
+                manifest_format_options(&options_path).map_err(JsonRpcError::tool_failure)?;
+            if let Some(overrides) = args.options {
+                if let Some(max_width) = overrides.max_width {
+                    options.max_width = max_width;
+                }
+                if let Some(reflow_comments) = overrides.reflow_comments {
+                    options.reflow_comments = reflow_comments;
+                }
+            }
+            let mut sources = SourceDatabase::new();
+            let file_id = sources.add_file(label.clone(), text);
+            let file = &sources[file_id];
+            let parsed = parse_module(file);
+            if parsed.has_errors() {
+                let diagnostics: Vec<JsonValue> = parsed
+                    .all_diagnostics()
+                    .map(|diag| serialize_diagnostic(diag, &sources))
+                    .collect();
+                return tool_error(
+                    format!("`{label}` has syntax errors and was not formatted"),
+                    json!({ "diagnostics": diagnostics }),
+                );
+            }
+            let formatted = Formatter.format_with_options(&parsed.module, &options);
+            let changed = formatted != file.text();
+            tool_success(
+                if changed {
+                    format!("Formatted `{label}`")
+                } else {
+                    format!("`{label}` is already formatted")
+                },
+                json!({
+                    "formatted": formatted,
+                    "changed": changed,
+                }),
+            )
+        }
+        "diagnostics" => {
+            let args: DiagnosticsArgs = serde_json::from_value(arguments)
+                .map_err(|error| JsonRpcError::invalid_params(error.to_string()))?;
+            let configured_target = effective_configured_target(controller, configured);
+            let entry_path = project_root(&configured_target).join(&args.target);
+            let snapshot =
+                WorkspaceHirSnapshot::load(&entry_path).map_err(JsonRpcError::tool_failure)?;
+            let diagnostics = workspace_diagnostics(&snapshot);
+            let error_count = diagnostics
+                .iter()
+                .filter(|d| d.get("severity").and_then(|s| s.as_str()) == Some("error"))
+                .count();
+            tool_success(
+                format!(
+                    "Found {} diagnostic(s), {} error(s) for `{}`",
+                    diagnostics.len(),
+                    error_count,
+                    args.target
+                ),
+                json!({ "diagnostics": diagnostics }),
+            )
+        }
         other => return Err(JsonRpcError::method_not_found(other)),
     }?;
     Ok(result)
 }
 
+fn handle_resource_read(
+    controller: &McpHostController,
+    configured: &ConfiguredTarget,
+    params: JsonValue,
+) -> Result<JsonValue, JsonRpcError> {
+    let args: ReadResourceArgs = serde_json::from_value(params)
+        .map_err(|error| JsonRpcError::invalid_params(error.to_string()))?;
+    let index = match args.uri.as_str() {
+        WORKSPACE_INDEX_URI => {
+            let configured_target = effective_configured_target(controller, configured);
+            let root = aivi_query::discover_workspace_root_from_directory(&project_root(
+                &configured_target,
+            ));
+            cached_workspace_index(&root)
+        }
+        other => {
+            return Err(JsonRpcError::invalid_params(format!(
+                "unknown resource `{other}`"
+            )));
+        }
+    };
+    Ok(json!({
+        "contents": [{
+            "uri": args.uri,
+            "mimeType": "application/json",
+            "text": index.to_string(),
+        }]
+    }))
+}
+
+fn resource_definitions() -> Vec<JsonValue> {
+    vec![json!({
+        "uri": WORKSPACE_INDEX_URI,
+        "name": "workspace_index",
+        "description": "Every module in the configured workspace with its exported values, types, classes, and domains, and the file it is defined in. Modules that fail to parse are listed with an `error` field.",
+        "mimeType": "application/json",
+    })]
+}
+
 fn tool_definitions() -> Vec<JsonValue> {
     vec![
         json!({
@@ -1650,6 +1832,51 @@ fn tool_definitions() -> Vec<JsonValue> {
                 "additionalProperties": false
             }
         }),
+        json!({
+            "name": "format",
+            "description": "Format AIVI source text. Pass either `path` (relative to the project root) or inline `content`; the file is not rewritten. Formatter settings come from the `[format]` table of aivi.toml, overridden by `options`.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Relative path to the .aivi source file to format."
+                    },
+                    "content": {
+                        "type": "string",
+                        "description": "Source text to format instead of reading a file."
+                    },
+                    "options": {
+                        "type": "object",
+                        "properties": {
+                            "max_width": { "type": "integer", "minimum": 1 },
+                            "reflow_comments": { "type": "boolean" }
+                        },
+                        "additionalProperties": false
+                    }
+                },
+                "oneOf": [
+                    { "required": ["path"] },
+                    { "required": ["content"] }
+                ],
+                "additionalProperties": false
+            }
+        }),
+        json!({
+            "name": "diagnostics",
+            "description": "Check the workspace reachable from `target` and return its diagnostics with severity, message, and source span. Reads the filesystem.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "target": {
+                        "type": "string",
+                        "description": "Entry .aivi file path (relative to project root)."
+                    }
+                },
+                "required": ["target"],
+                "additionalProperties": false
+            }
+        }),
     ]
 }
 
@@ -2011,7 +2238,7 @@ fn runtime_value_from_json(value: &JsonValue) -> Result<RuntimeValue, String> {
                 Ok(RuntimeValue::Float(runtime))
             }
         }
-        JsonValue::String(value) => Ok(RuntimeValue::Text(value.clone().into_boxed_str())),
+        JsonValue::String(value) => Ok(RuntimeValue::Text(value.clone().into())),
         JsonValue::Array(values) => values
             .iter()
             .map(runtime_value_from_json)
@@ -2845,6 +3072,164 @@ struct GetTypeAtArgs {
     character: u32,
 }
 
+#[derive(Deserialize)]
+struct FormatArgs {
+    path: Option<String>,
+    content: Option<String>,
+    options: Option<FormatOverrides>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FormatOverrides {
+    max_width: Option<usize>,
+    reflow_comments: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct DiagnosticsArgs {
+    target: String,
+}
+
+#[derive(Deserialize)]
+struct ReadResourceArgs {
+    uri: String,
+}
+
+/// A built workspace index together with the modification times of the files it was built from.
+struct CachedWorkspaceIndex {
+    fingerprint: BTreeMap<PathBuf, Option<SystemTime>>,
+    index: JsonValue,
+}
+
+fn workspace_index_cache() -> &'static Mutex<BTreeMap<PathBuf, CachedWorkspaceIndex>> {
+    static CACHE: OnceLock<Mutex<BTreeMap<PathBuf, CachedWorkspaceIndex>>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// Return the module/export index for `root`, rebuilding it only when an `.aivi` file was
+/// added, removed, or modified since the last read.
+fn cached_workspace_index(root: &Path) -> JsonValue {
+    let fingerprint = workspace_file_fingerprint(root);
+    let mut cache = workspace_index_cache()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(cached) = cache.get(root)
+        && cached.fingerprint == fingerprint
+    {
+        return cached.index.clone();
+    }
+    let index = build_workspace_index(root, fingerprint.keys());
+    cache.insert(
+        root.to_path_buf(),
+        CachedWorkspaceIndex {
+            fingerprint,
+            index: index.clone(),
+        },
+    );
+    index
+}
+
+/// Modification time of every `.aivi` file under `root`, skipping hidden and `target`
+/// directories like workspace discovery does.
+fn workspace_file_fingerprint(root: &Path) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    fn walk(dir: &Path, files: &mut BTreeMap<PathBuf, Option<SystemTime>>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                let skip = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with('.') || name == "target");
+                if !skip {
+                    walk(&path, files);
+                }
+            } else if path.extension().and_then(|extension| extension.to_str()) == Some("aivi") {
+                let modified = entry.metadata().and_then(|meta| meta.modified()).ok();
+                files.insert(path, modified);
+            }
+        }
+    }
+
+    let mut files = BTreeMap::new();
+    walk(root, &mut files);
+    files
+}
+
+fn build_workspace_index<'a>(root: &Path, paths: impl Iterator<Item = &'a PathBuf>) -> JsonValue {
+    let db = RootDatabase::new();
+    let mut modules = Vec::new();
+    for path in paths {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let module = relative
+            .with_extension("")
+            .iter()
+            .map(|segment| segment.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join(".");
+        let file_label = relative.display().to_string();
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) => {
+                modules.push(json!({
+                    "module": module,
+                    "file": file_label,
+                    "error": format!("failed to read `{file_label}`: {error}"),
+                }));
+                continue;
+            }
+        };
+        let file = QuerySourceFile::new(&db, path.clone(), text);
+        let parsed = aivi_query::parsed_file(&db, file);
+        if let Some(error) = parsed
+            .diagnostics()
+            .iter()
+            .find(|diagnostic| diagnostic.severity == Severity::Error)
+        {
+            modules.push(json!({
+                "module": module,
+                "file": file_label,
+                "error": error.message,
+            }));
+            continue;
+        }
+        let mut values = Vec::new();
+        let mut types = Vec::new();
+        let mut classes = Vec::new();
+        let mut domains = Vec::new();
+        for exported in aivi_query::exported_names(&db, file).iter() {
+            let bucket = match exported.kind {
+                aivi_hir::ExportedNameKind::Value
+                | aivi_hir::ExportedNameKind::Function
+                | aivi_hir::ExportedNameKind::Signal => &mut values,
+                aivi_hir::ExportedNameKind::Type => &mut types,
+                aivi_hir::ExportedNameKind::Class => &mut classes,
+                aivi_hir::ExportedNameKind::Domain => &mut domains,
+                aivi_hir::ExportedNameKind::SourceProvider
+                | aivi_hir::ExportedNameKind::Instance => continue,
+            };
+            bucket.push(exported.name.clone());
+        }
+        modules.push(json!({
+            "module": module,
+            "file": file_label,
+            "exports": {
+                "values": values,
+                "types": types,
+                "classes": classes,
+                "domains": domains,
+            },
+        }));
+    }
+    json!({
+        "root": root.display().to_string(),
+        "modules": modules,
+    })
+}
+
 fn project_root(configured: &ConfiguredTarget) -> PathBuf {
     configured
         .entry_path
@@ -2854,30 +3239,59 @@ fn project_root(configured: &ConfiguredTarget) -> PathBuf {
         .unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
 }
 
+fn workspace_diagnostics(snapshot: &WorkspaceHirSnapshot) -> Vec<JsonValue> {
+    let mut diagnostics = Vec::new();
+    for file in &snapshot.files {
+        let hir = query_hir_module(&snapshot.frontend.db, *file);
+        for diag in hir.diagnostics() {
+            diagnostics.push(serialize_diagnostic(diag, &snapshot.sources));
+        }
+        let file_lowering_failed = hir
+            .hir_diagnostics()
+            .iter()
+            .any(|d| d.severity == Severity::Error);
+        let validation_mode = if file_lowering_failed {
+            ValidationMode::Structural
+        } else {
+            ValidationMode::RequireResolvedNames
+        };
+        for diag in hir.module().validate(validation_mode).diagnostics() {
+            diagnostics.push(serialize_diagnostic(diag, &snapshot.sources));
+        }
+    }
+    diagnostics
+}
+
 fn serialize_diagnostic(diag: &aivi_base::Diagnostic, sources: &SourceDatabase) -> JsonValue {
     let primary = diag
         .labels
         .iter()
         .find(|l| l.style == aivi_base::LabelStyle::Primary)
         .or_else(|| diag.labels.first());
-    let (file_path, line, column) = if let Some(label) = primary {
+    let (file_path, line, column, span) = if let Some(label) = primary {
         if let Some(file) = sources.file(label.span.file()) {
             let lc = file.line_column(label.span.span().start());
+            let range = file.span_to_lsp_range(label.span.span());
             (
                 file.path().display().to_string(),
                 lc.line.saturating_sub(1) as u64,
                 lc.column.saturating_sub(1) as u64,
+                Some(json!({
+                    "start": { "line": range.start.line, "char": range.start.character },
+                    "end": { "line": range.end.line, "char": range.end.character },
+                })),
             )
         } else {
-            (String::new(), 0u64, 0u64)
+            (String::new(), 0u64, 0u64, None)
         }
     } else {
-        (String::new(), 0u64, 0u64)
+        (String::new(), 0u64, 0u64, None)
     };
     json!({
         "file": file_path,
         "line": line,
         "column": column,
+        "span": span,
         "severity": diag.severity.as_str(),
         "message": diag.message,
         "code": diag.code.map(|c| c.to_string()),
@@ -2920,10 +3334,11 @@ mod tests {
     use super::{
         CaptureWidgetScreenshotArgs, ConfiguredTarget, EmitGtkEventArgs, FindWidgetsArgs,
         JsonRpcError, JsonRpcRequest, JsonRpcTransport, LaunchSourceArgs, MCP_PROTOCOL_VERSION,
-        McpHostController, McpHostState, WidgetSnapshot, detect_json_rpc_transport,
+        McpHostController, McpHostState, McpPolicy, WidgetSnapshot, detect_json_rpc_transport,
         handle_json_rpc_request, load_cached_source_run_artifact, parse_prefixed_u32,
         parse_prefixed_u64, prepare_launch_request, read_json_rpc_message,
-        resolve_initial_entry_path, runtime_value_from_json, write_json_rpc_message,
+        resolve_initial_entry_path, runtime_value_from_json, serve_json_rpc_stream,
+        write_json_rpc_message,
     };
     use aivi_backend::RuntimeValue;
     use serde_json::{Value as JsonValue, json};
@@ -3023,6 +3438,7 @@ mod tests {
         let configured = ConfiguredTarget {
             entry_path: Some(PathBuf::from("fixtures/snake/main.aivi")),
             default_view: None,
+            policy: McpPolicy::default(),
         };
         let initialize = handle_json_rpc_request(
             &controller,
@@ -3087,6 +3503,8 @@ mod tests {
                 "list_diagnostics",
                 "read_source_file",
                 "get_type_at",
+                "format",
+                "diagnostics",
             ]
         );
         let launch_schema = &tools["tools"][0]["inputSchema"]["properties"];
@@ -3103,6 +3521,7 @@ mod tests {
         let configured = ConfiguredTarget {
             entry_path: Some(PathBuf::from("fixtures/snake/main.aivi")),
             default_view: None,
+            policy: McpPolicy::default(),
         };
         let initialize = handle_json_rpc_request(
             &controller,
@@ -3137,6 +3556,7 @@ mod tests {
         let configured = ConfiguredTarget {
             entry_path: None,
             default_view: None,
+            policy: McpPolicy::default(),
         };
         let error = handle_json_rpc_request(
             &controller,
@@ -3293,6 +3713,7 @@ value main =
         let configured = ConfiguredTarget {
             entry_path: Some(repo_path("demos/snake.aivi")),
             default_view: None,
+            policy: McpPolicy::default(),
         };
         let result = handle_json_rpc_request(
             &controller,
@@ -3324,6 +3745,7 @@ value main =
         let configured = ConfiguredTarget {
             entry_path: Some(snake_path.clone()),
             default_view: None,
+            policy: McpPolicy::default(),
         };
         let result = handle_json_rpc_request(
             &controller,
@@ -3360,6 +3782,7 @@ value main =
         let configured = ConfiguredTarget {
             entry_path: Some(snake_path.clone()),
             default_view: None,
+            policy: McpPolicy::default(),
         };
         let result = handle_json_rpc_request(
             &controller,
@@ -3391,6 +3814,7 @@ value main =
         let configured = ConfiguredTarget {
             entry_path: Some(snake_path.clone()),
             default_view: None,
+            policy: McpPolicy::default(),
         };
         let result = handle_json_rpc_request(
             &controller,
@@ -3413,6 +3837,256 @@ value main =
         );
     }
 
+    #[test]
+    fn format_tool_formats_inline_content_over_stdio_framing() {
+        let temp = tempfile::tempdir().expect("tempdir should create");
+        let configured = ConfiguredTarget {
+            entry_path: Some(temp.path().join("main.aivi")),
+            default_view: None,
+            policy: McpPolicy::default(),
+        };
+        let responses = exchange_over_stdio(
+            &configured,
+            &[json!({
+                "jsonrpc": "2.0",
+                "id": 20,
+                "method": "tools/call",
+                "params": {
+                    "name": "format",
+                    "arguments": { "content": "value total=left+middle*right/scale%modulo\n" }
+                }
+            })],
+        );
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0]["id"], json!(20));
+        let result = &responses[0]["result"];
+        assert_eq!(result["isError"], json!(false));
+        assert_eq!(
+            result["structuredContent"]["formatted"],
+            json!("value total = left + middle * right / scale % modulo\n")
+        );
+        assert_eq!(result["structuredContent"]["changed"], json!(true));
+    }
+
+    #[test]
+    fn format_tool_rejects_ambiguous_input() {
+        let temp = tempfile::tempdir().expect("tempdir should create");
+        let configured = ConfiguredTarget {
+            entry_path: Some(temp.path().join("main.aivi")),
+            default_view: None,
+            policy: McpPolicy::default(),
+        };
+        let responses = exchange_over_stdio(
+            &configured,
+            &[json!({
+                "jsonrpc": "2.0",
+                "id": 21,
+                "method": "tools/call",
+                "params": {
+                    "name": "format",
+                    "arguments": { "path": "main.aivi", "content": "value x = 1\n" }
+                }
+            })],
+        );
+        assert_eq!(responses[0]["error"]["code"], json!(-32602));
+    }
+
+    #[test]
+    fn diagnostics_tool_reports_severity_message_and_span_over_stdio_framing() {
+        let temp = tempfile::tempdir().expect("tempdir should create");
+        let entry = temp.path().join("main.aivi");
+        std::fs::write(&entry, "value broken = missingName\n").expect("entry should write");
+        let configured = ConfiguredTarget {
+            entry_path: Some(entry),
+            default_view: None,
+            policy: McpPolicy::default(),
+        };
+        let responses = exchange_over_stdio(
+            &configured,
+            &[json!({
+                "jsonrpc": "2.0",
+                "id": 22,
+                "method": "tools/call",
+                "params": {
+                    "name": "diagnostics",
+                    "arguments": { "target": "main.aivi" }
+                }
+            })],
+        );
+        let result = &responses[0]["result"];
+        assert_eq!(result["isError"], json!(false));
+        let diagnostics = result["structuredContent"]["diagnostics"]
+            .as_array()
+            .expect("diagnostics should return an array");
+        let error = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic["severity"] == json!("error"))
+            .expect("an unresolved name should produce an error diagnostic");
+        assert!(
+            error["message"]
+                .as_str()
+                .is_some_and(|message| !message.is_empty())
+        );
+        assert_eq!(error["span"]["start"]["line"], json!(0));
+        assert!(error["span"]["end"]["char"].as_u64().is_some());
+    }
+
+    #[test]
+    fn policy_can_deny_the_diagnostics_tool() {
+        let temp = tempfile::tempdir().expect("tempdir should create");
+        let configured = ConfiguredTarget {
+            entry_path: Some(temp.path().join("main.aivi")),
+            default_view: None,
+            policy: McpPolicy {
+                allowed_tools: None,
+                denied_tools: ["diagnostics".to_owned()].into_iter().collect(),
+            },
+        };
+        let responses = exchange_over_stdio(
+            &configured,
+            &[
+                json!({ "jsonrpc": "2.0", "id": 23, "method": "tools/list", "params": {} }),
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 24,
+                    "method": "tools/call",
+                    "params": {
+                        "name": "diagnostics",
+                        "arguments": { "target": "main.aivi" }
+                    }
+                }),
+            ],
+        );
+        let tool_names: Vec<&str> = responses[0]["result"]["tools"]
+            .as_array()
+            .expect("tools/list should return an array")
+            .iter()
+            .filter_map(|tool| tool["name"].as_str())
+            .collect();
+        assert!(tool_names.contains(&"format"));
+        assert!(!tool_names.contains(&"diagnostics"));
+        assert!(
+            responses[1]["error"]["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("disabled"))
+        );
+    }
+
+    #[test]
+    fn workspace_index_resource_lists_module_exports() {
+        let temp = tempfile::tempdir().expect("tempdir should create");
+        std::fs::write(
+            temp.path().join("main.aivi"),
+            "use util (double)\n\ntype Color =\n  | Red\n  | Green\n\nvalue answer : Int = double 21\n\nexport (Color, answer)\n",
+        )
+        .expect("main module should write");
+        std::fs::write(
+            temp.path().join("util.aivi"),
+            "type Int -> Int\nfunc double = n =>\n    n * 2\n\nexport (double)\n",
+        )
+        .expect("util module should write");
+        std::fs::write(temp.path().join("broken.aivi"), "value = \n")
+            .expect("broken module should write");
+        let configured = ConfiguredTarget {
+            entry_path: Some(temp.path().join("main.aivi")),
+            default_view: None,
+            policy: McpPolicy::default(),
+        };
+        let read_index = json!({
+            "jsonrpc": "2.0",
+            "id": 26,
+            "method": "resources/read",
+            "params": { "uri": "aivi://workspace/index" }
+        });
+        let responses = exchange_over_stdio(
+            &configured,
+            &[
+                json!({ "jsonrpc": "2.0", "id": 25, "method": "resources/list", "params": {} }),
+                read_index.clone(),
+            ],
+        );
+        assert_eq!(
+            responses[0]["result"]["resources"][0]["uri"],
+            json!("aivi://workspace/index")
+        );
+        let index_of = |response: &JsonValue| -> JsonValue {
+            let text = response["result"]["contents"][0]["text"]
+                .as_str()
+                .expect("index should be returned as JSON text");
+            serde_json::from_str(text).expect("index text should be JSON")
+        };
+        let module = |index: &JsonValue, name: &str| -> JsonValue {
+            index["modules"]
+                .as_array()
+                .expect("index should list modules")
+                .iter()
+                .find(|module| module["module"] == json!(name))
+                .cloned()
+                .unwrap_or_else(|| panic!("index should contain module `{name}`"))
+        };
+        let index = index_of(&responses[1]);
+        let main = module(&index, "main");
+        assert_eq!(main["file"], json!("main.aivi"));
+        assert_eq!(main["exports"]["values"], json!(["answer"]));
+        assert_eq!(main["exports"]["types"], json!(["Color"]));
+        assert_eq!(
+            module(&index, "util")["exports"]["values"],
+            json!(["double"])
+        );
+        assert!(module(&index, "broken")["error"].as_str().is_some());
+
+        let util_path = temp.path().join("util.aivi");
+        std::fs::write(
+            &util_path,
+            "type Int -> Int\nfunc triple = n =>\n    n * 3\n\nexport (triple)\n",
+        )
+        .expect("util module should rewrite");
+        std::fs::File::options()
+            .write(true)
+            .open(&util_path)
+            .and_then(|file| {
+                file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+            })
+            .expect("util module mtime should update");
+        let responses = exchange_over_stdio(&configured, &[read_index]);
+        assert_eq!(
+            module(&index_of(&responses[0]), "util")["exports"]["values"],
+            json!(["triple"])
+        );
+    }
+
+    /// Frames `requests` with `Content-Length` headers, serves them, and decodes the replies.
+    fn exchange_over_stdio(
+        configured: &ConfiguredTarget,
+        requests: &[JsonValue],
+    ) -> Vec<JsonValue> {
+        let (task_tx, task_rx) = sync_mpsc::channel();
+        drop(task_rx);
+        let controller = McpHostController { task_tx };
+        let mut input = Vec::new();
+        for request in requests {
+            write_json_rpc_message(&mut input, request, JsonRpcTransport::ContentLength)
+                .expect("request should encode");
+        }
+        let mut output = Vec::new();
+        serve_json_rpc_stream(
+            &controller,
+            configured,
+            &mut BufReader::new(input.as_slice()),
+            &mut output,
+        )
+        .expect("server should answer framed requests");
+        let mut reader = BufReader::new(output.as_slice());
+        let mut responses = Vec::new();
+        while let Some(response) =
+            read_json_rpc_message(&mut reader, JsonRpcTransport::ContentLength)
+                .expect("response should decode")
+        {
+            responses.push(response);
+        }
+        responses
+    }
+
     #[gtk::test]
     fn emit_gtk_event_waits_for_reversi_hydration() {
         let _guard = crate::gtk_test_lock().lock().expect("gtk test lock");
@@ -3425,6 +4099,7 @@ value main =
             configured: ConfiguredTarget {
                 entry_path: Some(path.clone()),
                 default_view: Some("main".to_owned()),
+                policy: McpPolicy::default(),
             },
             session: None,
             widget_ids: Default::default(),
@@ -3513,6 +4188,7 @@ value main =
             configured: ConfiguredTarget {
                 entry_path: Some(path.clone()),
                 default_view: Some("main".to_owned()),
+                policy: McpPolicy::default(),
             },
             session: None,
             widget_ids: Default::default(),
@@ -3587,6 +4263,7 @@ value main =
             configured: ConfiguredTarget {
                 entry_path: Some(path.clone()),
                 default_view: Some("main".to_owned()),
+                policy: McpPolicy::default(),
             },
             session: None,
             widget_ids: Default::default(),
@@ -3623,6 +4300,7 @@ value main =
             configured: ConfiguredTarget {
                 entry_path: Some(path.clone()),
                 default_view: Some("main".to_owned()),
+                policy: McpPolicy::default(),
             },
             session: None,
             widget_ids: Default::default(),
@@ -3692,6 +4370,7 @@ value main =
             configured: ConfiguredTarget {
                 entry_path: Some(path.clone()),
                 default_view: None,
+                policy: McpPolicy::default(),
             },
             session: None,
             widget_ids: Default::default(),
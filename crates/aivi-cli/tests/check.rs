@@ -936,6 +936,7 @@ fn check_accepts_stdlib_validation_files() {
     for relative in [
         "aivi/nonEmpty.aivi",
         "aivi/order.aivi",
+        "aivi/laws.aivi",
         "aivi/prelude.aivi",
         "aivi/validation.aivi",
         "aivi/http.aivi",
@@ -943,6 +944,7 @@ fn check_accepts_stdlib_validation_files() {
         "aivi/log.aivi",
         "aivi/list.aivi",
         "aivi/matrix.aivi",
+        "aivi/graph.aivi",
         "aivi/option.aivi",
         "aivi/result.aivi",
         "aivi/bool.aivi",
@@ -957,10 +959,15 @@ fn check_accepts_stdlib_validation_files() {
         "aivi/core/bytes.aivi",
         "aivi/core/set.aivi",
         "aivi/data/json.aivi",
+        "aivi/data/toml.aivi",
         "aivi/desktop/xdg.aivi",
         "aivi/app/lifecycle.aivi",
         "aivi/fs.aivi",
         "aivi/path.aivi",
+        "aivi/ws.aivi",
+        "aivi/process.aivi",
+        "aivi/crypto.aivi",
+        "aivi/char.aivi",
         "tests/foundation-validation/main.aivi",
         "tests/boundary-validation/main.aivi",
         "tests/extended-stdlib-validation/main.aivi",
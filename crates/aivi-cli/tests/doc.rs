@@ -0,0 +1,73 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    fn new(prefix: &str) -> Self {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after unix epoch")
+            .as_nanos();
+        let path = env::temp_dir().join(format!("aivi-{prefix}-{}-{unique}", std::process::id()));
+        fs::create_dir_all(&path).expect("temporary directory should be creatable");
+        Self { path }
+    }
+
+    fn write(&self, relative: &str, text: &str) -> PathBuf {
+        let path = self.path.join(relative);
+        fs::write(&path, text).expect("temporary file should be writable");
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[test]
+fn doc_prints_exported_functions_with_signatures_and_comments() {
+    let workspace = TempDir::new("doc-markdown");
+    workspace.write("aivi.toml", "");
+    let path = workspace.write(
+        "geometry.aivi",
+        concat!(
+            "/** Plane geometry helpers. **/\n",
+            "\n",
+            "/** Scale a length by two. **/\n",
+            "type Int -> Int\n",
+            "func double = n => n * 2\n",
+            "\n",
+            "export (double)\n",
+        ),
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aivi"))
+        .arg("doc")
+        .arg(&path)
+        .output()
+        .expect("doc command should run");
+
+    assert!(
+        output.status.success(),
+        "expected doc to succeed, stderr was: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("# geometry\n\nPlane geometry helpers.\n"),
+        "expected the module title and summary, got stdout: {stdout}"
+    );
+    assert!(
+        stdout.contains("```aivi\ndouble : Int -> Int\n```\n\nScale a length by two.\n"),
+        "expected the function signature and comment, got stdout: {stdout}"
+    );
+}
@@ -1,10 +1,14 @@
 use std::{
     env, fs,
+    io::Write,
     path::{Path, PathBuf},
-    process::Command,
+    process::{Command, Stdio},
     time::{SystemTime, UNIX_EPOCH},
 };
 
+use aivi_base::SourceDatabase;
+use aivi_syntax::{Formatter, parse_module};
+
 struct TempFile {
     path: PathBuf,
 }
@@ -257,6 +261,52 @@ fn fmt_fails_on_syntax_errors() {
     );
 }
 
+/// Run `aivi fmt <mode>` with `source` piped to stdin and return its stdout.
+fn format_piped(mode: &str, source: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aivi"))
+        .arg("fmt")
+        .arg(mode)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("fmt command should start");
+    child
+        .stdin
+        .take()
+        .expect("stdin should be piped")
+        .write_all(source.as_bytes())
+        .expect("source should be written to stdin");
+    let output = child.wait_with_output().expect("fmt command should finish");
+    assert!(
+        output.status.success(),
+        "fmt {mode} should succeed, stderr was: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("stdout should be utf-8")
+}
+
+fn format_text(source: &str) -> String {
+    let mut sources = SourceDatabase::new();
+    let file_id = sources.add_file("test.aivi", source.to_owned());
+    Formatter.format(&parse_module(&sources[file_id]).module)
+}
+
+#[test]
+fn fmt_formats_piped_source_like_format_text() {
+    let source = concat!(
+        "// totals\n",
+        "signal left = 20\n",
+        "signal right = 22\n",
+        "signal total:Signal Int=left+right",
+    );
+    let expected = format_text(source);
+
+    assert_eq!(format_piped("-", source), expected);
+    assert_eq!(format_piped("--stdin", source), expected);
+    assert_eq!(format_piped("-", &source.replace('\n', "\r\n")), expected);
+}
+
 #[test]
 fn fmt_normalizes_grouped_exports() {
     let input = TempFile::new(
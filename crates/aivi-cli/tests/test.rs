@@ -124,6 +124,94 @@ fn test_command_reports_when_workspace_has_no_tests() {
     );
 }
 
+#[test]
+fn test_command_runs_doc_comment_examples() {
+    let dir = TempDir::new("test-doctests");
+    let path = dir.write(
+        "main.aivi",
+        concat!(
+            "/**\n",
+            " * Scale a length by two.\n",
+            " *\n",
+            " * ```aivi\n",
+            " * double 21\n",
+            " * => 42\n",
+            " * double 2\n",
+            " * => 5\n",
+            " * ```\n",
+            " **/\n",
+            "type Int -> Int\n",
+            "func double = n => n * 2\n",
+        ),
+    );
+    let output = Command::new(env!("CARGO_BIN_EXE_aivi"))
+        .arg("test")
+        .arg(&path)
+        .output()
+        .expect("test command should run");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !output.status.success(),
+        "expected the wrong example to fail `aivi test`, stdout was: {stdout}"
+    );
+    assert!(
+        stdout.contains("ok   ") && stdout.contains(":5:4::double (doctest)"),
+        "expected the matching example to pass, stdout was: {stdout}"
+    );
+    assert!(
+        stderr.contains(
+            ":7:4::double (doctest): example does not match its expected value:\n  - 5\n  + 4"
+        ),
+        "expected the wrong example to fail with a diff, stderr was: {stderr}"
+    );
+    assert!(
+        stderr.contains("test result: FAILED. 1 passed; 1 failed; 2 total"),
+        "expected a failing summary, stderr was: {stderr}"
+    );
+}
+
+#[test]
+fn test_command_shows_the_log_of_failing_tests_only() {
+    let dir = TempDir::new("test-captured-log");
+    let path = dir.write(
+        "main.aivi",
+        concat!(
+            "use aivi.log (\n    LogSource\n    levelInfo\n)\n",
+            "use aivi.task (\n    finally\n)\n\n",
+            "@source log\n",
+            "signal logger : LogSource\n\n",
+            "value loud : Task Text Unit = logger.emit levelInfo \"checking \\\"quotes\\\"\"\n\n",
+            "value quiet : Task Text Unit = logger.emit levelInfo \"all good\"\n\n",
+            "@test\n",
+            "value logs_then_fails : Task Text Bool = finally (pure False) loud\n\n",
+            "@test\n",
+            "value logs_then_passes : Task Text Bool = finally (pure True) quiet\n",
+        ),
+    );
+    let output = Command::new(env!("CARGO_BIN_EXE_aivi"))
+        .arg("test")
+        .arg(&path)
+        .output()
+        .expect("test command should run");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !output.status.success(),
+        "expected the failing test to fail `aivi test`, stdout was: {stdout}"
+    );
+    assert!(
+        stderr.contains("logs_then_fails: returned false\n  log: [INFO] checking \"quotes\"\n"),
+        "expected the failing test's log under its fail line, stderr was: {stderr}"
+    );
+    assert!(
+        !stdout.contains("all good") && !stderr.contains("all good"),
+        "expected the passing test's log to be dropped, stdout was: {stdout}, stderr was: {stderr}"
+    );
+}
+
 #[test]
 fn test_command_accepts_contains_membership_forms() {
     let dir = TempDir::new("test-contains-predicate-forms");
@@ -168,3 +256,141 @@ fn test_command_accepts_contains_membership_forms() {
         "expected success summary for contains membership forms, stdout was: {stdout}"
     );
 }
+
+#[test]
+fn test_command_retries_failing_tasks_with_backoff() {
+    let dir = TempDir::new("test-http-retry");
+    let path = dir.write(
+        "main.aivi",
+        concat!(
+            "use aivi.http (\n",
+            "    RetryPolicy\n",
+            "    withRetry\n",
+            ")\n",
+            "use aivi.fs (\n",
+            "    exists\n",
+            "    writeText\n",
+            "    deleteFile\n",
+            ")\n",
+            "\n",
+            "value quickRetries : RetryPolicy = { maxAttempts: 3, baseDelayMs: 1, maxDelayMs: 5 }\n",
+            "\n",
+            "value singleAttempt : RetryPolicy = { maxAttempts: 1, baseDelayMs: 1, maxDelayMs: 5 }\n",
+            "\n",
+            "type Unit -> Task Text Bool\n",
+            "func deleted = done =>\n",
+            "    pure True\n",
+            "\n",
+            "value alwaysFailing : Task Text Bool = chain deleted (deleteFile \"never-created.txt\")\n",
+            "\n",
+            "type Unit -> Task Text Bool\n",
+            "func failAfterMarking = written =>\n",
+            "    alwaysFailing\n",
+            "\n",
+            "type Text -> Bool -> Task Text Bool\n",
+            "func recoverOnSecondRun = marker marked => marked\n",
+            " T|> pure True\n",
+            " F|> chain failAfterMarking (writeText marker \"seen\")\n",
+            "\n",
+            "type Text -> Task Text Bool\n",
+            "func failsOnce = marker =>\n",
+            "    chain (recoverOnSecondRun marker) (exists marker)\n",
+            "\n",
+            "type Result Text Bool -> Bool\n",
+            "func recovered = outcome => outcome\n",
+            " ||> Ok found -> found\n",
+            " ||> Err _    -> False\n",
+            "\n",
+            "type Result Text Bool -> Bool\n",
+            "func gaveUp = outcome => outcome\n",
+            " ||> Ok _  -> False\n",
+            " ||> Err _ -> True\n",
+            "\n",
+            "@test\n",
+            "value retry_recovers_from_a_transient_failure : Task Text Bool = map recovered (withRetry quickRetries (failsOnce \"first.marker\"))\n",
+            "\n",
+            "@test\n",
+            "value retry_stops_after_max_attempts : Task Text Bool = map gaveUp (withRetry singleAttempt (failsOnce \"second.marker\"))\n",
+            "\n",
+            "@test\n",
+            "value retry_reports_the_last_failure : Task Text Bool = map gaveUp (withRetry quickRetries alwaysFailing)\n",
+        ),
+    );
+    let output = Command::new(env!("CARGO_BIN_EXE_aivi"))
+        .arg("test")
+        .arg(&path)
+        .current_dir(&dir.path)
+        .output()
+        .expect("test command should run");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "expected retried tasks to pass `aivi test`, stdout was: {stdout}, stderr was: {stderr}"
+    );
+    assert!(
+        stdout.contains("test result: ok. 3 passed; 0 failed; 3 total"),
+        "expected success summary for retried tasks, stdout was: {stdout}"
+    );
+}
+
+#[test]
+fn test_command_runs_timer_tasks_a_fixed_number_of_times() {
+    let dir = TempDir::new("test-timer-tasks");
+    let path = dir.write(
+        "main.aivi",
+        concat!(
+            "use aivi.timer (\n",
+            "    after\n",
+            "    every\n",
+            ")\n",
+            "use aivi.fs (\n",
+            "    exists\n",
+            "    writeText\n",
+            ")\n",
+            "\n",
+            "type Int -> Task Text Unit\n",
+            "func markTick = n =>\n",
+            "    writeText \"tick-{n}.marker\" \"tick\"\n",
+            "\n",
+            "type Bool -> Bool -> Bool\n",
+            "func exactlyThree = third fourth => third and not fourth\n",
+            "\n",
+            "type Bool -> Task Text Bool\n",
+            "func checkFourth = third =>\n",
+            "    map (exactlyThree third) (exists \"tick-4.marker\")\n",
+            "\n",
+            "type Unit -> Task Text Bool\n",
+            "func checkTicks = done =>\n",
+            "    chain checkFourth (exists \"tick-3.marker\")\n",
+            "\n",
+            "type Unit -> Task Text Bool\n",
+            "func checkDelayed = done =>\n",
+            "    exists \"delayed.marker\"\n",
+            "\n",
+            "@test\n",
+            "value every_fires_three_ticks_then_stops : Task Text Bool = chain checkTicks (every 5 3 markTick)\n",
+            "\n",
+            "@test\n",
+            "value after_runs_the_task_once_the_delay_passes : Task Text Bool = chain checkDelayed (after 5 (writeText \"delayed.marker\" \"done\"))\n",
+        ),
+    );
+    let output = Command::new(env!("CARGO_BIN_EXE_aivi"))
+        .arg("test")
+        .arg(&path)
+        .current_dir(&dir.path)
+        .output()
+        .expect("test command should run");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "expected timer tasks to pass `aivi test`, stdout was: {stdout}, stderr was: {stderr}"
+    );
+    assert!(
+        stdout.contains("test result: ok. 2 passed; 0 failed; 2 total"),
+        "expected success summary for timer tasks, stdout was: {stdout}"
+    );
+}
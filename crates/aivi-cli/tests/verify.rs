@@ -0,0 +1,87 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    fn new(prefix: &str) -> Self {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after unix epoch")
+            .as_nanos();
+        let path = env::temp_dir().join(format!("aivi-{prefix}-{}-{unique}", std::process::id()));
+        fs::create_dir_all(&path).expect("temporary directory should be creatable");
+        Self { path }
+    }
+
+    fn write(&self, relative: &str, text: &str) -> PathBuf {
+        let path = self.path.join(relative);
+        fs::write(&path, text).expect("temporary file should be writable");
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[test]
+fn verify_frozen_fails_when_a_file_is_not_formatted() {
+    let workspace = TempDir::new("verify-frozen-drift");
+    workspace.write("aivi.toml", "");
+    workspace.write("tidy.aivi", "value answer = 42\n");
+    let messy = workspace.write("messy.aivi", "value   total:Int=1+2\n");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aivi"))
+        .arg("verify")
+        .arg("--frozen")
+        .arg(&workspace.path)
+        .output()
+        .expect("verify command should run");
+
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "expected verify to report drift, stderr was: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        format!("{}: not formatted\n", messy.display()),
+        "expected only the unformatted file to be reported"
+    );
+    assert_eq!(
+        fs::read_to_string(&messy).expect("unformatted file should still be readable"),
+        "value   total:Int=1+2\n",
+        "verify must not rewrite files"
+    );
+}
+
+#[test]
+fn verify_frozen_accepts_formatted_files() {
+    let workspace = TempDir::new("verify-frozen-clean");
+    workspace.write("aivi.toml", "");
+    let path = workspace.write("tidy.aivi", "value answer : Int = 42\n");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aivi"))
+        .arg("verify")
+        .arg("--frozen")
+        .arg(&path)
+        .output()
+        .expect("verify command should run");
+
+    assert!(
+        output.status.success(),
+        "expected verify to pass, stdout was: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
@@ -1,8 +1,9 @@
 use aivi_base::SourceSpan;
 use aivi_hir::{
-    BigIntLiteral, BinaryOperator, BindingId as HirBindingId, BuiltinTerm, DecimalLiteral,
-    DomainMemberHandle, FloatLiteral, IntegerLiteral, IntrinsicValue, ItemId as HirItemId,
-    PipeTransformMode, SuffixedIntegerLiteral, SumConstructorHandle, UnaryOperator,
+    BigIntLiteral, BinaryOperator, BindingId as HirBindingId, BuiltinTerm, CharLiteral,
+    DecimalLiteral, DomainMemberHandle, FloatLiteral, IntegerLiteral, IntrinsicValue,
+    ItemId as HirItemId, PipeTransformMode, SuffixedIntegerLiteral, SumConstructorHandle,
+    UnaryOperator,
 };
 
 use crate::{ids::ExprId, ty::Type};
@@ -28,6 +29,7 @@ pub enum ExprKind {
     BigInt(BigIntLiteral),
     SuffixedInteger(SuffixedIntegerLiteral),
     Text(TextLiteral),
+    Char(CharLiteral),
     Tuple(Vec<ExprId>),
     List(Vec<ExprId>),
     Map(Vec<MapEntry>),
@@ -173,6 +175,9 @@ pub enum BuiltinOrdSubject {
     Bool,
     Text,
     Ordering,
+    /// Compiler-derived ordering for tuples, records, and user ADTs whose fields are all `Ord`:
+    /// constructors compare by declaration order, then payloads compare left to right.
+    Structural,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -222,6 +227,11 @@ pub enum PatternKind {
     Binding(PatternBinding),
     Integer(IntegerLiteral),
     Text(Box<str>),
+    /// Text starting with `prefix`; the remaining suffix is matched against `rest`.
+    TextPrefix {
+        prefix: Box<str>,
+        rest: Box<Pattern>,
+    },
     Tuple(Vec<Pattern>),
     List {
         elements: Vec<Pattern>,
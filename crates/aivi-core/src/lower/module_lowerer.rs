@@ -2011,6 +2011,10 @@ impl<'a> ModuleLowerer<'a> {
             }),
             aivi_hir::PatternKind::Integer(literal) => PatternKind::Integer(literal),
             aivi_hir::PatternKind::Text(text) => PatternKind::Text(lower_text_pattern(&text)),
+            aivi_hir::PatternKind::TextPrefix { prefix, rest } => PatternKind::TextPrefix {
+                prefix: lower_text_pattern(&prefix),
+                rest: Box::new(self.lower_pattern(rest, subject)),
+            },
             aivi_hir::PatternKind::Tuple(elements) => {
                 let subject_elements = match subject {
                     Some(aivi_hir::GateType::Tuple(elements)) => Some(elements.as_slice()),
@@ -2393,14 +2397,23 @@ impl<'a> ModuleLowerer<'a> {
                     self.ordering_item_from_gate_type(expr_ty).ok_or_else(|| {
                         unsupported("runtime lowering could not recover the Ordering result type")
                     })?;
-                crate::builtin_compare_intrinsic(
-                    self.builtin_executable_carrier_from_type_binding(&dispatch.subject)
-                        .ok_or_else(|| unsupported(
-                            "runtime lowering only supports compare for Int, Float, Decimal, BigInt, Bool, Text, and Ordering",
-                        ))?,
-                    ordering_item,
-                )
-                .map_err(unsupported)?
+                if is_structural_ord_subject(&dispatch.subject) {
+                    // The typechecker only resolves these subjects to builtin `Ord` evidence
+                    // through its compiler-derived structural fallback.
+                    BuiltinClassMemberIntrinsic::Compare {
+                        subject: crate::BuiltinOrdSubject::Structural,
+                        ordering_item,
+                    }
+                } else {
+                    crate::builtin_compare_intrinsic(
+                        self.builtin_executable_carrier_from_type_binding(&dispatch.subject)
+                            .ok_or_else(|| unsupported(
+                                "runtime lowering only supports compare for Int, Float, Decimal, BigInt, Bool, Text, and Ordering",
+                            ))?,
+                        ordering_item,
+                    )
+                    .map_err(unsupported)?
+                }
             }
             _ => {
                 return Err(unsupported(
@@ -3415,6 +3428,17 @@ impl<'a> ModuleLowerer<'a> {
                                 },
                             )?);
                         }
+                        GateRuntimeExprKind::Char(literal) => {
+                            values.push(self.alloc_expr(
+                                owner,
+                                expr.span,
+                                Expr {
+                                    span: expr.span,
+                                    ty,
+                                    kind: ExprKind::Char(literal.clone()),
+                                },
+                            )?);
+                        }
                         GateRuntimeExprKind::BigInt(bigint) => {
                             values.push(self.alloc_expr(
                                 owner,
@@ -3999,6 +4023,14 @@ fn join_spans(left: SourceSpan, right: SourceSpan) -> SourceSpan {
         .expect("typed-core lowering only joins spans from the same source file")
 }
 
+fn is_structural_ord_subject(binding: &TypeBinding) -> bool {
+    match binding {
+        TypeBinding::Type(aivi_hir::GateType::OpaqueItem { name, .. }) => name != "Ordering",
+        TypeBinding::Type(aivi_hir::GateType::Tuple(_) | aivi_hir::GateType::Record(_)) => true,
+        _ => false,
+    }
+}
+
 fn drain_tail<T>(values: &mut Vec<T>, len: usize) -> Vec<T> {
     let split = values
         .len()
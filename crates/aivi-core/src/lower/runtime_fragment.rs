@@ -670,6 +670,7 @@ fn referenced_hir_dependencies(root: &GateRuntimeExpr) -> HirDependencies {
             | GateRuntimeExprKind::Decimal(_)
             | GateRuntimeExprKind::BigInt(_)
             | GateRuntimeExprKind::SuffixedInteger(_)
+            | GateRuntimeExprKind::Char(_)
             | GateRuntimeExprKind::Reference(GateRuntimeReference::Local(_))
             | GateRuntimeExprKind::Reference(GateRuntimeReference::Builtin(_))
             | GateRuntimeExprKind::Reference(GateRuntimeReference::IntrinsicValue(_))
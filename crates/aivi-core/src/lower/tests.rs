@@ -177,6 +177,7 @@ fn builtin_type(module: &mut aivi_hir::Module, builtin: BuiltinType) -> aivi_hir
         BuiltinType::Text => "Text",
         BuiltinType::Unit => "Unit",
         BuiltinType::Bytes => "Bytes",
+        BuiltinType::Char => "Char",
         BuiltinType::List => "List",
         BuiltinType::Map => "Map",
         BuiltinType::Set => "Set",
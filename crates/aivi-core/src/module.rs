@@ -768,6 +768,7 @@ fn format_expr(module: &Module, expr_id: ExprId, f: &mut fmt::Formatter<'_>) ->
         crate::expr::ExprKind::Float(value) => write!(f, "{}", value.raw),
         crate::expr::ExprKind::Decimal(value) => write!(f, "{}", value.raw),
         crate::expr::ExprKind::BigInt(value) => write!(f, "{}", value.raw),
+        crate::expr::ExprKind::Char(value) => write!(f, "{:?}", value.value),
         crate::expr::ExprKind::SuffixedInteger(value) => {
             write!(f, "{}{}", value.raw, value.suffix.text())
         }
@@ -926,6 +927,10 @@ fn format_pattern(pattern: &Pattern, f: &mut fmt::Formatter<'_>) -> fmt::Result
         PatternKind::Binding(PatternBinding { name, .. }) => f.write_str(name),
         PatternKind::Integer(value) => write!(f, "{}", value.raw),
         PatternKind::Text(raw) => write!(f, "\"{raw}\""),
+        PatternKind::TextPrefix { prefix, rest } => {
+            write!(f, "\"{prefix}\" ++ ")?;
+            format_pattern(rest, f)
+        }
         PatternKind::Tuple(elements) => {
             f.write_str("(")?;
             for (index, element) in elements.iter().enumerate() {
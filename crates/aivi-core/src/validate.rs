@@ -580,6 +580,7 @@ pub fn validate_module(module: &Module) -> Result<(), ValidationErrors> {
             | ExprKind::Decimal(_)
             | ExprKind::BigInt(_)
             | ExprKind::SuffixedInteger(_)
+            | ExprKind::Char(_)
             | ExprKind::Reference(Reference::Local(_))
             | ExprKind::Reference(Reference::Builtin(_))
             | ExprKind::Reference(Reference::BuiltinClassMember(_))
@@ -1037,6 +1038,7 @@ fn validate_pattern(
         | PatternKind::Binding(_)
         | PatternKind::Integer(_)
         | PatternKind::Text(_) => {}
+        PatternKind::TextPrefix { rest, .. } => validate_pattern(rest, _module, _work, _errors),
         PatternKind::Tuple(elements) => {
             for element in elements {
                 validate_pattern(element, _module, _work, _errors);
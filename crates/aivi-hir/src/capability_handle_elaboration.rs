@@ -994,6 +994,20 @@ fn lower_builtin_value_member(
         BuiltinCapabilityFamily::Fs => {
             lower_fs_value_member(module, handle, invocation, diagnostics)
         }
+        BuiltinCapabilityFamily::Http if invocation.member == "request" => {
+            // The request record carries its own URL, so the base URL travels separately and
+            // the runtime prefixes it.
+            let base = scoped_http_url_argument(module, handle, None, invocation.span, diagnostics)
+                .unwrap_or_else(|| synthesize_text_literal(module, "", invocation.span));
+            let mut arguments = vec![base];
+            arguments.extend(invocation.arguments.iter().copied());
+            Some(build_intrinsic_call(
+                module,
+                IntrinsicValue::HttpRequest,
+                invocation.span,
+                arguments,
+            ))
+        }
         BuiltinCapabilityFamily::Http => {
             let intrinsic = match invocation.member.as_str() {
                 "get" => IntrinsicValue::HttpGet,
@@ -1004,6 +1018,7 @@ fn lower_builtin_value_member(
                 "delete" => IntrinsicValue::HttpDelete,
                 "head" => IntrinsicValue::HttpHead,
                 "postJson" => IntrinsicValue::HttpPostJson,
+                "download" => IntrinsicValue::HttpDownload,
                 _ => return None,
             };
             let arguments = combine_http_value_arguments(
@@ -1095,6 +1110,19 @@ fn lower_builtin_value_member(
                 inherited_arguments(handle, &invocation.arguments),
             ))
         }
+        BuiltinCapabilityFamily::Process => {
+            let intrinsic = match invocation.member.as_str() {
+                "args" => IntrinsicValue::ProcessArgs,
+                "exit" => IntrinsicValue::ProcessExit,
+                _ => return None,
+            };
+            Some(build_intrinsic_call(
+                module,
+                intrinsic,
+                invocation.span,
+                inherited_arguments(handle, &invocation.arguments),
+            ))
+        }
         BuiltinCapabilityFamily::Log => {
             let intrinsic = match invocation.member.as_str() {
                 "emit" => IntrinsicValue::LogEmit,
@@ -1174,8 +1202,7 @@ fn lower_builtin_value_member(
                 arguments,
             ))
         }
-        BuiltinCapabilityFamily::Process
-        | BuiltinCapabilityFamily::Imap
+        BuiltinCapabilityFamily::Imap
         | BuiltinCapabilityFamily::Time
         | BuiltinCapabilityFamily::Tray => None,
         BuiltinCapabilityFamily::Smtp => None,
@@ -1696,12 +1723,9 @@ fn scoped_http_url_argument(
     match (handle.arguments.as_slice(), member_argument) {
         ([], Some(argument)) => Some(argument),
         ([], None) => None,
-        ([base], Some(argument)) => Some(build_intrinsic_call(
-            module,
-            IntrinsicValue::TextConcat,
-            span,
-            vec![*base, argument],
-        )),
+        ([base], Some(argument)) => {
+            Some(concat_text_arguments(module, span, vec![*base, argument]))
+        }
         ([base], None) => Some(*base),
         ([first, ..], Some(argument)) => {
             diagnostics.push(
@@ -1714,12 +1738,7 @@ fn scoped_http_url_argument(
                     "collapse the base URL to one expression before declaring the handle",
                 )),
             );
-            Some(build_intrinsic_call(
-                module,
-                IntrinsicValue::TextConcat,
-                span,
-                vec![*first, argument],
-            ))
+            Some(concat_text_arguments(module, span, vec![*first, argument]))
         }
         ([first, ..], None) => {
             diagnostics.push(
@@ -1737,6 +1756,17 @@ fn scoped_http_url_argument(
     }
 }
 
+/// `aivi.text.concat` takes a list, so the pieces are wrapped in a list literal first.
+fn concat_text_arguments(module: &mut Module, span: SourceSpan, parts: Vec<ExprId>) -> ExprId {
+    let list = module
+        .alloc_expr(Expr {
+            span,
+            kind: ExprKind::List(parts),
+        })
+        .expect("capability lowering should fit inside the expression arena");
+    build_intrinsic_call(module, IntrinsicValue::TextConcat, span, vec![list])
+}
+
 fn build_intrinsic_call(
     module: &mut Module,
     intrinsic: IntrinsicValue,
@@ -1912,7 +1942,16 @@ fn supports_builtin_value_member(family: BuiltinCapabilityFamily, member: &str)
         ),
         BuiltinCapabilityFamily::Http => matches!(
             member,
-            "get" | "getBytes" | "getStatus" | "post" | "put" | "delete" | "head" | "postJson"
+            "get"
+                | "getBytes"
+                | "getStatus"
+                | "post"
+                | "put"
+                | "delete"
+                | "head"
+                | "postJson"
+                | "request"
+                | "download"
         ),
         BuiltinCapabilityFamily::Db => matches!(member, "query" | "commit" | "exec"),
         BuiltinCapabilityFamily::Auth => matches!(member, "pkce" | "refresh"),
@@ -1941,8 +1980,8 @@ fn supports_builtin_value_member(family: BuiltinCapabilityFamily, member: &str)
                 | "configDirs"
         ),
         BuiltinCapabilityFamily::Dbus => matches!(member, "call"),
-        BuiltinCapabilityFamily::Process
-        | BuiltinCapabilityFamily::Imap
+        BuiltinCapabilityFamily::Process => matches!(member, "args" | "exit"),
+        BuiltinCapabilityFamily::Imap
         | BuiltinCapabilityFamily::Time
         | BuiltinCapabilityFamily::Tray => false,
         BuiltinCapabilityFamily::Smtp => matches!(member, "send"),
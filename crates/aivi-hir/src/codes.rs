@@ -32,16 +32,20 @@ pub const MISSING_INSTANCE_REQUIREMENT: DiagnosticCode =
     DiagnosticCode::new("hir", "missing-instance-requirement");
 pub const NON_EXHAUSTIVE_CASE_PATTERN: DiagnosticCode =
     DiagnosticCode::new("hir", "non-exhaustive-case-pattern");
+pub const PARTIAL_BUILTIN_USE: DiagnosticCode = DiagnosticCode::new("hir", "partial-builtin-use");
 pub const REACTIVE_UPDATE_SELF_REFERENCE: DiagnosticCode =
     DiagnosticCode::new("hir", "reactive-update-self-reference");
 pub const RECORD_ROW_RENAME_COLLISION: DiagnosticCode =
     DiagnosticCode::new("hir", "record-row-rename-collision");
 pub const RECORD_ROW_TRANSFORM_SOURCE: DiagnosticCode =
     DiagnosticCode::new("hir", "record-row-transform-source");
+pub const REDUNDANT_IMPORT: DiagnosticCode = DiagnosticCode::new("hir", "redundant-import");
 pub const RESULT_BLOCK_BINDING_NOT_RESULT: DiagnosticCode =
     DiagnosticCode::new("hir", "result-block-binding-not-result");
 pub const RESULT_BLOCK_ERROR_MISMATCH: DiagnosticCode =
     DiagnosticCode::new("hir", "result-block-error-mismatch");
+pub const SHADOWED_PRELUDE_NAME: DiagnosticCode =
+    DiagnosticCode::new("hir", "shadowed-prelude-name");
 pub const SOURCE_OPTION_TYPE_MISMATCH: DiagnosticCode =
     DiagnosticCode::new("hir", "source-option-type-mismatch");
 pub const SOURCE_OPTION_UNBOUND_CONTRACT_PARAMETER: DiagnosticCode =
@@ -55,4 +59,7 @@ pub const UNKNOWN_PROJECTION_FIELD: DiagnosticCode =
     DiagnosticCode::new("hir", "unknown-projection-field");
 pub const UNKNOWN_RECORD_ROW_FIELD: DiagnosticCode =
     DiagnosticCode::new("hir", "unknown-record-row-field");
+pub const UNREACHABLE_CASE_PATTERN: DiagnosticCode =
+    DiagnosticCode::new("hir", "unreachable-case-pattern");
 pub const UNRESOLVED_NAME: DiagnosticCode = DiagnosticCode::new("hir", "unresolved-name");
+pub const UNUSED_BINDING: DiagnosticCode = DiagnosticCode::new("hir", "unused-binding");
@@ -702,7 +702,8 @@ impl<'a> DecodeTypeLowerer<'a> {
                         | BuiltinType::Bool
                         | BuiltinType::Text
                         | BuiltinType::Unit
-                        | BuiltinType::Bytes => {
+                        | BuiltinType::Bytes
+                        | BuiltinType::Char => {
                             unreachable!("scalar builtins should have matched above")
                         }
                     },
@@ -1186,7 +1187,8 @@ impl<'a> DecodeTypeLowerer<'a> {
                 | BuiltinType::Bool
                 | BuiltinType::Text
                 | BuiltinType::Unit
-                | BuiltinType::Bytes => unreachable!("scalar builtins should match above"),
+                | BuiltinType::Bytes
+                | BuiltinType::Char => unreachable!("scalar builtins should match above"),
             },
         }
     }
@@ -1268,6 +1270,7 @@ fn builtin_scalar(builtin: BuiltinType) -> Option<PrimitiveType> {
         BuiltinType::Text => Some(PrimitiveType::Text),
         BuiltinType::Unit => Some(PrimitiveType::Unit),
         BuiltinType::Bytes => Some(PrimitiveType::Bytes),
+        BuiltinType::Char => Some(PrimitiveType::Char),
         BuiltinType::List
         | BuiltinType::Map
         | BuiltinType::Set
@@ -1289,6 +1292,7 @@ fn builtin_type_name(builtin: BuiltinType) -> &'static str {
         BuiltinType::Text => "Text",
         BuiltinType::Unit => "Unit",
         BuiltinType::Bytes => "Bytes",
+        BuiltinType::Char => "Char",
         BuiltinType::List => "List",
         BuiltinType::Map => "Map",
         BuiltinType::Set => "Set",
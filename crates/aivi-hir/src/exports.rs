@@ -1273,7 +1273,8 @@ fn primitive_import_value_type(reference: &TypeReference) -> Option<ImportValueT
         | crate::BuiltinType::Bool
         | crate::BuiltinType::Text
         | crate::BuiltinType::Unit
-        | crate::BuiltinType::Bytes => Some(ImportValueType::Primitive(*builtin)),
+        | crate::BuiltinType::Bytes
+        | crate::BuiltinType::Char => Some(ImportValueType::Primitive(*builtin)),
         crate::BuiltinType::List
         | crate::BuiltinType::Map
         | crate::BuiltinType::Set
@@ -1716,7 +1717,8 @@ fn primitive_import_value_type_from_builtin(
         | crate::BuiltinType::Bool
         | crate::BuiltinType::Text
         | crate::BuiltinType::Unit
-        | crate::BuiltinType::Bytes => Some(ImportValueType::Primitive(builtin)),
+        | crate::BuiltinType::Bytes
+        | crate::BuiltinType::Char => Some(ImportValueType::Primitive(builtin)),
         _ => None,
     }
 }
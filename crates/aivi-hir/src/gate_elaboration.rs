@@ -4,11 +4,12 @@ use aivi_base::SourceSpan;
 use aivi_typing::{GatePlanner, GateResultKind};
 
 use crate::{
-    BigIntLiteral, BinaryOperator, BindingId, BuiltinTerm, ClassMemberResolution, ClusterId,
-    DecimalLiteral, DomainMemberHandle, ExprId, ExprKind, FloatLiteral, ImportBindingMetadata,
-    ImportId, IntegerLiteral, IntrinsicValue, Item, ItemId, Module, Name, NamePath, PatternId,
-    PipeExpr, PipeStageKind, PipeTransformMode, ProjectionBase, SuffixedIntegerLiteral,
-    TermReference, TermResolution, TextFragment, TextSegment, UnaryOperator,
+    BigIntLiteral, BinaryOperator, BindingId, BuiltinTerm, CharLiteral, ClassMemberResolution,
+    ClusterId, DecimalLiteral, DomainMemberHandle, ExprId, ExprKind, FloatLiteral,
+    ImportBindingMetadata, ImportId, IntegerLiteral, IntrinsicValue, Item, ItemId, Module, Name,
+    NamePath, PatternId, PipeExpr, PipeStageKind, PipeTransformMode, ProjectionBase,
+    SuffixedIntegerLiteral, TermReference, TermResolution, TextFragment, TextSegment,
+    UnaryOperator,
     domain_operator_elaboration::select_domain_binary_operator,
     general_expr_elaboration::{
         EqualityEvidenceCatalog, build_equality_runtime_expr, build_ordering_runtime_expr,
@@ -216,6 +217,7 @@ pub enum GateRuntimeExprKind {
     BigInt(BigIntLiteral),
     SuffixedInteger(SuffixedIntegerLiteral),
     Text(GateRuntimeTextLiteral),
+    Char(CharLiteral),
     Tuple(Vec<GateRuntimeExpr>),
     List(Vec<GateRuntimeExpr>),
     Map(Vec<GateRuntimeMapEntry>),
@@ -1254,6 +1256,13 @@ fn lower_gate_runtime_expr_with_purity(
                             kind: GateRuntimeExprKind::Decimal(literal),
                         });
                     }
+                    ExprKind::Char(literal) => {
+                        results.push(GateRuntimeExpr {
+                            span: expr.span,
+                            ty,
+                            kind: GateRuntimeExprKind::Char(literal),
+                        });
+                    }
                     ExprKind::BigInt(literal) => {
                         results.push(GateRuntimeExpr {
                             span: expr.span,
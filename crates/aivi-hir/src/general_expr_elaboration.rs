@@ -2317,6 +2317,7 @@ impl<'a> GeneralExprElaborator<'a> {
             ExprKind::Text(text) => {
                 GateRuntimeExprKind::Text(self.lower_text_literal(&text, env, ambient)?)
             }
+            ExprKind::Char(literal) => GateRuntimeExprKind::Char(literal),
             ExprKind::Regex(regex) => {
                 let pattern = regex
                     .raw
@@ -2650,10 +2651,10 @@ impl<'a> GeneralExprElaborator<'a> {
 
     /// Lower `target <| { f1: v1, f2: v2, ... }` for record patches.
     ///
-    /// Supports `PatchInstructionKind::Replace` and `PatchInstructionKind::Remove`
-    /// with single or multi-segment `Named` selectors on closed record types.
-    /// Multi-segment selectors (e.g., `address.street: "Main St"`) recursively
-    /// construct nested record updates.
+    /// Supports `Replace`, `Store`, and `Remove` instructions with `Named` selectors of any depth
+    /// on closed record types. A `Replace` whose value has type `A -> A` for the selected field
+    /// type `A` transforms the current field value instead of replacing it. Only the records on a
+    /// selector's path are rebuilt; every other field is projected from the target.
     fn lower_patch_apply_expr(
         &mut self,
         expr_id: ExprId,
@@ -2665,18 +2666,14 @@ impl<'a> GeneralExprElaborator<'a> {
     ) -> Result<GateRuntimeExpr, Vec<GeneralExprBlocker>> {
         let span = self.module.exprs()[expr_id].span;
 
-        // Validate: all patch entries must use Named selectors with Replace or Remove.
+        // Validate: all patch entries must use Named selectors.
         for entry in &patch.entries {
             let all_named = entry
                 .selector
                 .segments
                 .iter()
                 .all(|s| matches!(s, PatchSelectorSegment::Named { .. }));
-            let is_replace_or_remove = matches!(
-                entry.instruction.kind,
-                PatchInstructionKind::Replace(_) | PatchInstructionKind::Remove
-            );
-            if !all_named || entry.selector.segments.is_empty() || !is_replace_or_remove {
+            if !all_named || entry.selector.segments.is_empty() {
                 return Err(vec![GeneralExprBlocker::UnsupportedRuntimeExpr {
                     span,
                     kind: GateRuntimeUnsupportedKind::PatchExpr,
@@ -2692,238 +2689,216 @@ impl<'a> GeneralExprElaborator<'a> {
         else {
             return Err(vec![GeneralExprBlocker::UnknownExprType { span }]);
         };
-        // Extract the record fields — either from a local Record or from an imported TypeConstructor.
-        let record_fields: Vec<GateRecordField> = match &target_gate_ty {
-            GateType::Record(fields) => fields.clone(),
+        let Some(record_fields) = self.patch_record_fields(&target_gate_ty) else {
+            return Err(vec![GeneralExprBlocker::UnsupportedRuntimeExpr {
+                span,
+                kind: GateRuntimeUnsupportedKind::PatchExpr,
+            }]);
+        };
+
+        // Lower the target expression once so we can project from it.
+        let lowered_target = self.lower_expr(target, env, ambient, Some(&target_gate_ty))?;
+        let entries = patch
+            .entries
+            .iter()
+            .map(|entry| (entry.selector.segments.as_slice(), &entry.instruction.kind))
+            .collect::<Vec<_>>();
+        let patched = self.lower_record_patch(
+            span,
+            &lowered_target,
+            &record_fields,
+            &entries,
+            env,
+            ambient,
+        )?;
+
+        Ok(match expected {
+            Some(expected) => GateRuntimeExpr {
+                ty: expected.clone(),
+                ..patched
+            },
+            None if patched.ty.same_shape(&GateType::Record(record_fields)) => GateRuntimeExpr {
+                ty: target_gate_ty,
+                ..patched
+            },
+            None => patched,
+        })
+    }
+
+    /// Record fields a patch can select through, looking through imported record types.
+    fn patch_record_fields(&mut self, ty: &GateType) -> Option<Vec<GateRecordField>> {
+        match ty {
+            GateType::Record(fields) => Some(fields.clone()),
             GateType::OpaqueImport { import, .. } => {
                 match self.module.imports().get(*import).map(|b| &b.metadata) {
                     Some(crate::ImportBindingMetadata::TypeConstructor {
                         fields: Some(import_fields),
                         ..
-                    }) => import_fields
-                        .iter()
-                        .map(|f| GateRecordField {
-                            name: f.name.to_string(),
-                            ty: self.typing.lower_import_value_type(&f.ty),
-                        })
-                        .collect(),
-                    _ => {
-                        return Err(vec![GeneralExprBlocker::UnsupportedRuntimeExpr {
-                            span,
-                            kind: GateRuntimeUnsupportedKind::PatchExpr,
-                        }]);
-                    }
+                    }) => Some(
+                        import_fields
+                            .iter()
+                            .map(|f| GateRecordField {
+                                name: f.name.to_string(),
+                                ty: self.typing.lower_import_value_type(&f.ty),
+                            })
+                            .collect(),
+                    ),
+                    _ => None,
                 }
             }
-            _ => {
-                return Err(vec![GeneralExprBlocker::UnsupportedRuntimeExpr {
-                    span,
-                    kind: GateRuntimeUnsupportedKind::PatchExpr,
-                }]);
-            }
-        };
-
-        // Build maps for patch entries, separating single-segment and nested.
-        let mut replace_map: HashMap<String, ExprId> = HashMap::new();
-        let mut remove_set: HashSet<String> = HashSet::new();
-        // Nested patches: top-level field → list of (remaining segments, instruction kind)
-        let mut nested_patches: HashMap<
-            String,
-            Vec<(Vec<PatchSelectorSegment>, PatchInstructionKind)>,
-        > = HashMap::new();
+            _ => None,
+        }
+    }
 
-        for entry in &patch.entries {
-            let segments = &entry.selector.segments;
-            if segments.len() == 1 {
-                if let PatchSelectorSegment::Named { name, .. } = &segments[0] {
-                    match &entry.instruction.kind {
-                        PatchInstructionKind::Replace(value_id) => {
-                            replace_map.insert(name.text().to_owned(), *value_id);
-                        }
-                        PatchInstructionKind::Remove => {
-                            remove_set.insert(name.text().to_owned());
-                        }
-                        _ => {}
-                    }
-                }
-            } else if segments.len() > 1
-                && let PatchSelectorSegment::Named { name, .. } = &segments[0]
-            {
-                nested_patches
-                    .entry(name.text().to_owned())
+    /// Rebuild the record `base` with the patch `entries`, whose selectors are relative to
+    /// `base`. Entries sharing a first segment are applied recursively to that field, so the
+    /// result type drops exactly the fields removed at each depth.
+    fn lower_record_patch(
+        &mut self,
+        span: SourceSpan,
+        base: &GateRuntimeExpr,
+        record_fields: &[GateRecordField],
+        entries: &[(&[PatchSelectorSegment], &PatchInstructionKind)],
+        env: &GateExprEnv,
+        ambient: Option<&GateType>,
+    ) -> Result<GateRuntimeExpr, Vec<GeneralExprBlocker>> {
+        let segment_name = |segment: &PatchSelectorSegment| match segment {
+            PatchSelectorSegment::Named { name, .. } => name.text().to_owned(),
+            _ => unreachable!("patch selectors are validated as named before lowering"),
+        };
+        // Direct instructions by field name (the last one wins), and the nested entries for each
+        // field in source order.
+        let mut instructions: HashMap<String, &PatchInstructionKind> = HashMap::new();
+        let mut nested: HashMap<String, Vec<(&[PatchSelectorSegment], &PatchInstructionKind)>> =
+            HashMap::new();
+        for &(segments, kind) in entries {
+            let (first, rest) = segments
+                .split_first()
+                .expect("patch selectors are validated as non-empty before lowering");
+            if rest.is_empty() {
+                instructions.insert(segment_name(first), kind);
+            } else {
+                nested
+                    .entry(segment_name(first))
                     .or_default()
-                    .push((segments[1..].to_vec(), entry.instruction.kind.clone()));
+                    .push((rest, kind));
             }
         }
 
-        // Lower the target expression once so we can project from it.
-        let lowered_target = self.lower_expr(target, env, ambient, Some(&target_gate_ty))?;
-
         let mut result_fields = Vec::with_capacity(record_fields.len());
+        let mut result_types = Vec::with_capacity(record_fields.len());
         let mut errors: Vec<GeneralExprBlocker> = Vec::new();
 
-        for field in &record_fields {
+        for field in record_fields {
             let field_name = field.name.as_str();
             let field_ty = &field.ty;
-
-            if remove_set.contains(field_name) {
-                continue;
-            }
-
             let label =
                 Name::new(field_name.to_owned(), span).expect("record field names are non-empty");
+            let path = NamePath::from_vec(vec![label.clone()])
+                .expect("single-segment projection paths are always valid");
+            let projection_base = match &base.kind {
+                GateRuntimeExprKind::AmbientSubject => GateRuntimeProjectionBase::AmbientSubject,
+                _ => GateRuntimeProjectionBase::Expr(Box::new(base.clone())),
+            };
+            let projected = GateRuntimeExpr {
+                span,
+                ty: field_ty.clone(),
+                kind: GateRuntimeExprKind::Projection {
+                    base: projection_base,
+                    path,
+                },
+            };
 
-            if let Some(replacement_id) = replace_map.get(field_name) {
-                match self.lower_expr(*replacement_id, env, ambient, Some(field_ty)) {
-                    Ok(value) => {
-                        result_fields.push(GateRuntimeRecordField { label, value });
-                    }
-                    Err(mut errs) => errors.append(&mut errs),
+            let value = match instructions.get(field_name) {
+                Some(PatchInstructionKind::Remove) => continue,
+                Some(PatchInstructionKind::Replace(value_id)) => {
+                    self.lower_patch_replacement(*value_id, projected, env, ambient)
                 }
-            } else if let Some(nested) = nested_patches.get(field_name) {
-                // Nested patch: project the field, then apply nested updates.
-                let path = NamePath::from_vec(vec![label.clone()])
-                    .expect("single-segment projection paths are always valid");
-                let base = match &lowered_target.kind {
-                    GateRuntimeExprKind::AmbientSubject => {
-                        GateRuntimeProjectionBase::AmbientSubject
-                    }
-                    _ => GateRuntimeProjectionBase::Expr(Box::new(lowered_target.clone())),
-                };
-                let projected = GateRuntimeExpr {
-                    span,
-                    ty: field_ty.clone(),
-                    kind: GateRuntimeExprKind::Projection { base, path },
-                };
-
-                if let GateType::Record(nested_record_fields) = field_ty {
-                    let mut nested_replace: HashMap<String, ExprId> = HashMap::new();
-                    let mut nested_remove: HashSet<String> = HashSet::new();
-                    for (segs, kind) in nested {
-                        if segs.len() == 1
-                            && let PatchSelectorSegment::Named { name, .. } = &segs[0]
-                        {
-                            match kind {
-                                PatchInstructionKind::Replace(value_id) => {
-                                    nested_replace.insert(name.text().to_owned(), *value_id);
-                                }
-                                PatchInstructionKind::Remove => {
-                                    nested_remove.insert(name.text().to_owned());
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-
-                    let mut nested_result_fields = Vec::with_capacity(nested_record_fields.len());
-                    for nf in nested_record_fields {
-                        let nf_name = nf.name.as_str();
-                        if nested_remove.contains(nf_name) {
-                            continue;
-                        }
-                        let nf_label = Name::new(nf_name.to_owned(), span)
-                            .expect("record field names are non-empty");
-                        if let Some(rep_id) = nested_replace.get(nf_name) {
-                            match self.lower_expr(*rep_id, env, ambient, Some(&nf.ty)) {
-                                Ok(value) => {
-                                    nested_result_fields.push(GateRuntimeRecordField {
-                                        label: nf_label,
-                                        value,
-                                    });
-                                }
-                                Err(mut errs) => errors.append(&mut errs),
-                            }
-                        } else {
-                            let nf_path = NamePath::from_vec(vec![nf_label.clone()])
-                                .expect("single-segment projection paths are always valid");
-                            let nf_projected = GateRuntimeExpr {
-                                span,
-                                ty: nf.ty.clone(),
-                                kind: GateRuntimeExprKind::Projection {
-                                    base: GateRuntimeProjectionBase::Expr(Box::new(
-                                        projected.clone(),
-                                    )),
-                                    path: nf_path,
-                                },
-                            };
-                            nested_result_fields.push(GateRuntimeRecordField {
-                                label: nf_label,
-                                value: nf_projected,
-                            });
-                        }
-                    }
-
-                    let nested_ty = if nested_remove.is_empty() {
-                        field_ty.clone()
-                    } else {
-                        let remaining: Vec<GateRecordField> = nested_record_fields
-                            .iter()
-                            .filter(|f| !nested_remove.contains(f.name.as_str()))
-                            .cloned()
-                            .collect();
-                        GateType::Record(remaining)
-                    };
-                    result_fields.push(GateRuntimeRecordField {
-                        label,
-                        value: GateRuntimeExpr {
+                Some(PatchInstructionKind::Store(value_id)) => {
+                    self.lower_expr(*value_id, env, ambient, Some(field_ty))
+                }
+                None => match nested.get(field_name) {
+                    Some(nested_entries) => match self.patch_record_fields(field_ty) {
+                        Some(nested_fields) => self.lower_record_patch(
+                            span,
+                            &projected,
+                            &nested_fields,
+                            nested_entries,
+                            env,
+                            ambient,
+                        ),
+                        None => Err(vec![GeneralExprBlocker::UnsupportedRuntimeExpr {
                             span,
-                            ty: nested_ty,
-                            kind: GateRuntimeExprKind::Record(nested_result_fields),
+                            kind: GateRuntimeUnsupportedKind::PatchExpr,
+                        }]),
+                    },
+                    None => Ok(projected),
+                },
+            };
+            match value {
+                Ok(value) => {
+                    result_types.push(GateRecordField {
+                        name: field.name.clone(),
+                        ty: if value.ty.same_shape(field_ty) {
+                            field_ty.clone()
+                        } else {
+                            value.ty.clone()
                         },
                     });
-                } else {
-                    result_fields.push(GateRuntimeRecordField {
-                        label,
-                        value: projected,
-                    });
+                    result_fields.push(GateRuntimeRecordField { label, value });
                 }
-            } else {
-                // Unpatched field: project from the lowered target.
-                let path = NamePath::from_vec(vec![label.clone()])
-                    .expect("single-segment projection paths are always valid");
-                let base = match &lowered_target.kind {
-                    GateRuntimeExprKind::AmbientSubject => {
-                        GateRuntimeProjectionBase::AmbientSubject
-                    }
-                    _ => GateRuntimeProjectionBase::Expr(Box::new(lowered_target.clone())),
-                };
-                let projected = GateRuntimeExpr {
-                    span,
-                    ty: field_ty.clone(),
-                    kind: GateRuntimeExprKind::Projection { base, path },
-                };
-                result_fields.push(GateRuntimeRecordField {
-                    label,
-                    value: projected,
-                });
+                Err(mut errs) => errors.append(&mut errs),
             }
         }
 
         if !errors.is_empty() {
             return Err(errors);
         }
-
-        let result_ty = if let Some(exp) = expected {
-            exp.clone()
-        } else if remove_set.is_empty() {
-            target_gate_ty.clone()
-        } else {
-            // Compute the result type with removed fields omitted.
-            let remaining_fields: Vec<GateRecordField> = record_fields
-                .iter()
-                .filter(|f| !remove_set.contains(f.name.as_str()))
-                .cloned()
-                .collect();
-            GateType::Record(remaining_fields)
-        };
-
         Ok(GateRuntimeExpr {
             span,
-            ty: result_ty,
+            ty: GateType::Record(result_types),
             kind: GateRuntimeExprKind::Record(result_fields),
         })
     }
 
+    /// Lower a plain patch instruction for the field read by `current`. A value of type `A -> A`
+    /// for the field type `A` is applied to the current field value; anything else replaces it.
+    fn lower_patch_replacement(
+        &mut self,
+        value_id: ExprId,
+        current: GateRuntimeExpr,
+        env: &GateExprEnv,
+        ambient: Option<&GateType>,
+    ) -> Result<GateRuntimeExpr, Vec<GeneralExprBlocker>> {
+        let field_ty = current.ty.clone();
+        let value_ty = self.typing.infer_expr(value_id, env, ambient);
+        let is_transform = value_ty
+            .actual_gate_type()
+            .or(value_ty.ty)
+            .is_some_and(|ty| match ty {
+                GateType::Arrow { parameter, result } => {
+                    parameter.same_shape(&field_ty) && result.same_shape(&field_ty)
+                }
+                _ => false,
+            });
+        if !is_transform {
+            return self.lower_expr(value_id, env, ambient, Some(&field_ty));
+        }
+        let transform_ty = GateType::Arrow {
+            parameter: Box::new(field_ty.clone()),
+            result: Box::new(field_ty.clone()),
+        };
+        let callee = self.lower_expr(value_id, env, ambient, Some(&transform_ty))?;
+        Ok(GateRuntimeExpr {
+            span: current.span,
+            ty: field_ty,
+            kind: GateRuntimeExprKind::Apply {
+                callee: Box::new(callee),
+                arguments: vec![current],
+            },
+        })
+    }
+
     fn lower_apply_expr(
         &mut self,
         _expr_id: ExprId,
@@ -4511,6 +4486,9 @@ impl<'a> GeneralExprElaborator<'a> {
                 crate::PatternKind::Binding(binding) => {
                     env.locals.insert(binding.binding, subject_ty);
                 }
+                crate::PatternKind::TextPrefix { rest, .. } => {
+                    work.push((rest, subject_ty));
+                }
                 crate::PatternKind::Tuple(elements) => {
                     let GateType::Tuple(subject_elements) = &subject_ty else {
                         continue;
@@ -5143,6 +5121,7 @@ mod tests {
             BuiltinType::Text => "Text",
             BuiltinType::Unit => "Unit",
             BuiltinType::Bytes => "Bytes",
+            BuiltinType::Char => "Char",
             BuiltinType::List => "List",
             BuiltinType::Map => "Map",
             BuiltinType::Set => "Set",
@@ -10,7 +10,9 @@ use crate::{
         PatternId, TypeId, TypeParameterId,
     },
     sequence::{AtLeastTwo, NonEmpty, SequenceError},
-    validate::{ValidationMode, ValidationReport, validate_module},
+    validate::{
+        ValidationMode, ValidationReport, validate_module, validate_module_with_max_errors,
+    },
 };
 
 /// One source-stable surface name preserved into HIR for diagnostics.
@@ -313,6 +315,11 @@ pub enum IntrinsicValue {
     FsRename,
     FsCopy,
     FsDeleteDir,
+    // FS handles (async tasks over a runtime-owned file table)
+    FsOpen,
+    FsReadLine,
+    FsWrite,
+    FsClose,
     // Path operations (pure/synchronous)
     PathParent,
     PathFilename,
@@ -330,6 +337,29 @@ pub enum IntrinsicValue {
     BytesToText,
     BytesRepeat,
     BytesEmpty,
+    BytesFromList,
+    BytesToList,
+    BytesFromBase64,
+    BytesToBase64,
+    BytesFromHex,
+    BytesToHex,
+    // Message authentication over `Bytes` (pure)
+    CryptoHmacSha256,
+    CryptoHmacSha512,
+    CryptoConstantTimeEquals,
+    CryptoEd25519Keypair,
+    CryptoEd25519Sign,
+    CryptoEd25519Verify,
+    // Unicode scalar values (pure)
+    CharToInt,
+    CharFromInt,
+    CharIsDigit,
+    CharIsAlpha,
+    CharToUpper,
+    // Function reflection
+    FnArity,
+    FnCanApply,
+    FnIsFullyApplied,
     // JSON operations (async tasks via serde_json in CLI)
     JsonValidate,
     JsonGet,
@@ -337,6 +367,14 @@ pub enum IntrinsicValue {
     JsonKeys,
     JsonPretty,
     JsonMinify,
+    JsonParseInto,
+    JsonParseStreamInto,
+    JsonStringifyValue,
+    JsonEncode,
+    // TOML documents (pure via the `toml` crate)
+    TomlParseInto,
+    TomlStringifyValue,
+    TomlEncodeValue,
     // XDG base directory intrinsics (pure/synchronous — read env vars with fallbacks)
     XdgDataHome,
     XdgConfigHome,
@@ -367,6 +405,14 @@ pub enum IntrinsicValue {
     TextFromBool,
     TextParseBool,
     TextConcat,
+    TextRenderTemplate,
+    TextRenderLenient,
+    TextRenderPositional,
+    TextCaseFold,
+    TextReverse,
+    TextGraphemeLength,
+    TextWrap,
+    TextTruncate,
     // Float transcendental intrinsics (pure/synchronous)
     FloatSin,
     FloatCos,
@@ -383,14 +429,39 @@ pub enum IntrinsicValue {
     FloatHypot,
     FloatTrunc,
     FloatFrac,
+    // Math intrinsics (pure/synchronous)
+    MathFftMagnitude,
+    MathFftSpectrum,
+    MatrixLuSolve,
+    MatrixLuDeterminant,
+    MatrixLuInverse,
+    MatrixJacobiSvd,
+    MatrixJacobiEigenvalues,
+    GraphShortestPath,
+    GraphDistances,
     // Time intrinsics (Task-returning)
     TimeNowMs,
     TimeMonotonicMs,
+    TimeSleep,
     TimeFormat,
     TimeParse,
+    // Clipboard intrinsics (Task-returning)
+    ClipboardRead,
+    ClipboardWrite,
     // Env intrinsics (Task-returning)
     EnvGet,
     EnvList,
+    // Process intrinsics (Task-returning)
+    ProcessArgs,
+    ProcessExit,
+    // Test intrinsics (Task-returning)
+    TestMatchesSnapshot,
+    TestAdvanceClock,
+    TestSetClock,
+    // Task combinators
+    TaskAttempt,
+    TaskFinally,
+    TaskParallel,
     // Log intrinsics (Task-returning)
     LogEmit,
     LogEmitContext,
@@ -417,6 +488,8 @@ pub enum IntrinsicValue {
     RegexFindAll,
     RegexReplace,
     RegexReplaceAll,
+    RegexCaptures,
+    RegexCapturesAll,
     // HTTP intrinsics (Task-returning, runs on worker thread via ureq)
     HttpGet,
     HttpGetBytes,
@@ -426,6 +499,20 @@ pub enum IntrinsicValue {
     HttpDelete,
     HttpHead,
     HttpPostJson,
+    HttpRequest,
+    HttpDownload,
+    // WebSocket client (async tasks over a runtime-owned socket table)
+    WsConnect,
+    WsSend,
+    WsRecv,
+    WsRecvTimeout,
+    WsClose,
+    // Child processes (async tasks over a runtime-owned process table)
+    ProcessSpawn,
+    ProcessWait,
+    ProcessKill,
+    ProcessReadStdout,
+    ProcessWriteStdin,
     // BigInt intrinsics (pure/synchronous)
     BigIntFromInt,
     BigIntFromText,
@@ -443,6 +530,15 @@ pub enum IntrinsicValue {
     BigIntEq,
     BigIntGt,
     BigIntLt,
+    // Decimal intrinsics (pure/synchronous)
+    DecimalFromInt,
+    DecimalFromText,
+    DecimalToText,
+    DecimalAdd,
+    DecimalSub,
+    DecimalMul,
+    DecimalDivWithMode,
+    DecimalRoundWithMode,
     // Bitwise intrinsics (pure/synchronous)
     BitAnd,
     BitOr,
@@ -533,6 +629,10 @@ intrinsic_unit_variants!(
     FsRename,
     FsCopy,
     FsDeleteDir,
+    FsOpen,
+    FsReadLine,
+    FsWrite,
+    FsClose,
     PathParent,
     PathFilename,
     PathStem,
@@ -548,12 +648,39 @@ intrinsic_unit_variants!(
     BytesToText,
     BytesRepeat,
     BytesEmpty,
+    BytesFromList,
+    BytesToList,
+    BytesFromBase64,
+    BytesToBase64,
+    BytesFromHex,
+    BytesToHex,
+    CryptoHmacSha256,
+    CryptoHmacSha512,
+    CryptoConstantTimeEquals,
+    CryptoEd25519Keypair,
+    CryptoEd25519Sign,
+    CryptoEd25519Verify,
+    CharToInt,
+    CharFromInt,
+    CharIsDigit,
+    CharIsAlpha,
+    CharToUpper,
+    FnArity,
+    FnCanApply,
+    FnIsFullyApplied,
     JsonValidate,
     JsonGet,
     JsonAt,
     JsonKeys,
     JsonPretty,
     JsonMinify,
+    JsonParseInto,
+    JsonParseStreamInto,
+    JsonStringifyValue,
+    JsonEncode,
+    TomlParseInto,
+    TomlStringifyValue,
+    TomlEncodeValue,
     XdgDataHome,
     XdgConfigHome,
     XdgCacheHome,
@@ -582,6 +709,14 @@ intrinsic_unit_variants!(
     TextFromBool,
     TextParseBool,
     TextConcat,
+    TextRenderTemplate,
+    TextRenderLenient,
+    TextRenderPositional,
+    TextCaseFold,
+    TextReverse,
+    TextGraphemeLength,
+    TextWrap,
+    TextTruncate,
     FloatSin,
     FloatCos,
     FloatTan,
@@ -597,12 +732,32 @@ intrinsic_unit_variants!(
     FloatHypot,
     FloatTrunc,
     FloatFrac,
+    MathFftMagnitude,
+    MathFftSpectrum,
+    MatrixLuSolve,
+    MatrixLuDeterminant,
+    MatrixLuInverse,
+    MatrixJacobiSvd,
+    MatrixJacobiEigenvalues,
+    GraphShortestPath,
+    GraphDistances,
     TimeNowMs,
     TimeMonotonicMs,
+    TimeSleep,
     TimeFormat,
     TimeParse,
+    ClipboardRead,
+    ClipboardWrite,
     EnvGet,
     EnvList,
+    ProcessArgs,
+    ProcessExit,
+    TestMatchesSnapshot,
+    TestAdvanceClock,
+    TestSetClock,
+    TaskAttempt,
+    TaskFinally,
+    TaskParallel,
     LogEmit,
     LogEmitContext,
     RandomFloat,
@@ -622,6 +777,8 @@ intrinsic_unit_variants!(
     RegexFindAll,
     RegexReplace,
     RegexReplaceAll,
+    RegexCaptures,
+    RegexCapturesAll,
     HttpGet,
     HttpGetBytes,
     HttpGetStatus,
@@ -630,6 +787,18 @@ intrinsic_unit_variants!(
     HttpDelete,
     HttpHead,
     HttpPostJson,
+    HttpRequest,
+    HttpDownload,
+    WsConnect,
+    WsSend,
+    WsRecv,
+    WsRecvTimeout,
+    WsClose,
+    ProcessSpawn,
+    ProcessWait,
+    ProcessKill,
+    ProcessReadStdout,
+    ProcessWriteStdin,
     BigIntFromInt,
     BigIntFromText,
     BigIntToInt,
@@ -646,6 +815,14 @@ intrinsic_unit_variants!(
     BigIntEq,
     BigIntGt,
     BigIntLt,
+    DecimalFromInt,
+    DecimalFromText,
+    DecimalToText,
+    DecimalAdd,
+    DecimalSub,
+    DecimalMul,
+    DecimalDivWithMode,
+    DecimalRoundWithMode,
     BitAnd,
     BitOr,
     BitXor,
@@ -720,6 +897,10 @@ impl fmt::Display for IntrinsicValue {
             Self::FsRename => f.write_str("aivi.fs.rename"),
             Self::FsCopy => f.write_str("aivi.fs.copy"),
             Self::FsDeleteDir => f.write_str("aivi.fs.deleteDir"),
+            Self::FsOpen => f.write_str("aivi.fs.open"),
+            Self::FsReadLine => f.write_str("aivi.fs.readLine"),
+            Self::FsWrite => f.write_str("aivi.fs.write"),
+            Self::FsClose => f.write_str("aivi.fs.close"),
             Self::PathParent => f.write_str("aivi.path.parent"),
             Self::PathFilename => f.write_str("aivi.path.filename"),
             Self::PathStem => f.write_str("aivi.path.stem"),
@@ -735,12 +916,39 @@ impl fmt::Display for IntrinsicValue {
             Self::BytesToText => f.write_str("aivi.core.bytes.toText"),
             Self::BytesRepeat => f.write_str("aivi.core.bytes.repeat"),
             Self::BytesEmpty => f.write_str("aivi.core.bytes.empty"),
+            Self::BytesFromList => f.write_str("aivi.core.bytes.fromList"),
+            Self::BytesToList => f.write_str("aivi.core.bytes.toList"),
+            Self::BytesFromBase64 => f.write_str("aivi.core.bytes.fromBase64"),
+            Self::BytesToBase64 => f.write_str("aivi.core.bytes.toBase64"),
+            Self::BytesFromHex => f.write_str("aivi.core.bytes.fromHex"),
+            Self::BytesToHex => f.write_str("aivi.core.bytes.toHex"),
+            Self::CryptoHmacSha256 => f.write_str("aivi.crypto.hmacSha256"),
+            Self::CryptoHmacSha512 => f.write_str("aivi.crypto.hmacSha512"),
+            Self::CryptoConstantTimeEquals => f.write_str("aivi.crypto.constantTimeEquals"),
+            Self::CryptoEd25519Keypair => f.write_str("aivi.crypto.ed25519Keypair"),
+            Self::CryptoEd25519Sign => f.write_str("aivi.crypto.ed25519Sign"),
+            Self::CryptoEd25519Verify => f.write_str("aivi.crypto.ed25519Verify"),
+            Self::CharToInt => f.write_str("aivi.char.toInt"),
+            Self::CharFromInt => f.write_str("aivi.char.fromInt"),
+            Self::CharIsDigit => f.write_str("aivi.char.isDigit"),
+            Self::CharIsAlpha => f.write_str("aivi.char.isAlpha"),
+            Self::CharToUpper => f.write_str("aivi.char.toUpper"),
+            Self::FnArity => f.write_str("aivi.core.fn.arity"),
+            Self::FnCanApply => f.write_str("aivi.core.fn.canApply"),
+            Self::FnIsFullyApplied => f.write_str("aivi.core.fn.isFullyApplied"),
             Self::JsonValidate => f.write_str("aivi.data.json.validate"),
             Self::JsonGet => f.write_str("aivi.data.json.get"),
             Self::JsonAt => f.write_str("aivi.data.json.at"),
             Self::JsonKeys => f.write_str("aivi.data.json.keys"),
             Self::JsonPretty => f.write_str("aivi.data.json.pretty"),
             Self::JsonMinify => f.write_str("aivi.data.json.minify"),
+            Self::JsonParseInto => f.write_str("aivi.data.json.parseInto"),
+            Self::JsonParseStreamInto => f.write_str("aivi.data.json.parseStreamInto"),
+            Self::JsonStringifyValue => f.write_str("aivi.data.json.stringifyValue"),
+            Self::JsonEncode => f.write_str("aivi.data.json.encode"),
+            Self::TomlParseInto => f.write_str("aivi.data.toml.parseInto"),
+            Self::TomlStringifyValue => f.write_str("aivi.data.toml.stringifyValue"),
+            Self::TomlEncodeValue => f.write_str("aivi.data.toml.encodeValue"),
             Self::XdgDataHome => f.write_str("aivi.desktop.xdg.dataHome"),
             Self::XdgConfigHome => f.write_str("aivi.desktop.xdg.configHome"),
             Self::XdgCacheHome => f.write_str("aivi.desktop.xdg.cacheHome"),
@@ -769,6 +977,14 @@ impl fmt::Display for IntrinsicValue {
             Self::TextFromBool => f.write_str("aivi.text.fromBool"),
             Self::TextParseBool => f.write_str("aivi.text.parseBool"),
             Self::TextConcat => f.write_str("aivi.text.concat"),
+            Self::TextRenderTemplate => f.write_str("aivi.text.renderTemplate"),
+            Self::TextRenderLenient => f.write_str("aivi.text.renderLenient"),
+            Self::TextRenderPositional => f.write_str("aivi.text.renderPositional"),
+            Self::TextCaseFold => f.write_str("aivi.text.caseFold"),
+            Self::TextReverse => f.write_str("aivi.text.reverse"),
+            Self::TextGraphemeLength => f.write_str("aivi.text.graphemeLength"),
+            Self::TextWrap => f.write_str("aivi.text.wrap"),
+            Self::TextTruncate => f.write_str("aivi.text.truncate"),
             Self::FloatSin => f.write_str("aivi.core.float.sin"),
             Self::FloatCos => f.write_str("aivi.core.float.cos"),
             Self::FloatTan => f.write_str("aivi.core.float.tan"),
@@ -784,12 +1000,32 @@ impl fmt::Display for IntrinsicValue {
             Self::FloatHypot => f.write_str("aivi.core.float.hypot"),
             Self::FloatTrunc => f.write_str("aivi.core.float.trunc"),
             Self::FloatFrac => f.write_str("aivi.core.float.frac"),
+            Self::MathFftMagnitude => f.write_str("aivi.math.fftMagnitude"),
+            Self::MathFftSpectrum => f.write_str("aivi.math.fftSpectrum"),
+            Self::MatrixLuSolve => f.write_str("aivi.matrix.luSolve"),
+            Self::MatrixLuDeterminant => f.write_str("aivi.matrix.luDeterminant"),
+            Self::MatrixLuInverse => f.write_str("aivi.matrix.luInverse"),
+            Self::MatrixJacobiSvd => f.write_str("aivi.matrix.jacobiSvd"),
+            Self::MatrixJacobiEigenvalues => f.write_str("aivi.matrix.jacobiEigenvalues"),
+            Self::GraphShortestPath => f.write_str("aivi.graph.shortestPath"),
+            Self::GraphDistances => f.write_str("aivi.graph.distances"),
             Self::TimeNowMs => f.write_str("aivi.time.nowMs"),
             Self::TimeMonotonicMs => f.write_str("aivi.time.monotonicMs"),
+            Self::TimeSleep => f.write_str("aivi.time.sleep"),
             Self::TimeFormat => f.write_str("aivi.time.format"),
             Self::TimeParse => f.write_str("aivi.time.parse"),
+            Self::ClipboardRead => f.write_str("aivi.clipboard.read"),
+            Self::ClipboardWrite => f.write_str("aivi.clipboard.write"),
             Self::EnvGet => f.write_str("aivi.env.get"),
             Self::EnvList => f.write_str("aivi.env.list"),
+            Self::ProcessArgs => f.write_str("aivi.process.args"),
+            Self::ProcessExit => f.write_str("aivi.process.exit"),
+            Self::TestMatchesSnapshot => f.write_str("aivi.test.matchesSnapshot"),
+            Self::TestAdvanceClock => f.write_str("aivi.test.advanceClock"),
+            Self::TestSetClock => f.write_str("aivi.test.setClock"),
+            Self::TaskAttempt => f.write_str("aivi.task.attempt"),
+            Self::TaskFinally => f.write_str("aivi.task.finally"),
+            Self::TaskParallel => f.write_str("aivi.task.parallel"),
             Self::LogEmit => f.write_str("aivi.log.emit"),
             Self::LogEmitContext => f.write_str("aivi.log.emitContext"),
             Self::RandomFloat => f.write_str("aivi.random.randomFloat"),
@@ -809,6 +1045,8 @@ impl fmt::Display for IntrinsicValue {
             Self::RegexFindAll => f.write_str("aivi.regex.findAll"),
             Self::RegexReplace => f.write_str("aivi.regex.replace"),
             Self::RegexReplaceAll => f.write_str("aivi.regex.replaceAll"),
+            Self::RegexCaptures => f.write_str("aivi.regex.captures"),
+            Self::RegexCapturesAll => f.write_str("aivi.regex.capturesAll"),
             Self::HttpGet => f.write_str("aivi.http.get"),
             Self::HttpGetBytes => f.write_str("aivi.http.getBytes"),
             Self::HttpGetStatus => f.write_str("aivi.http.getStatus"),
@@ -817,6 +1055,18 @@ impl fmt::Display for IntrinsicValue {
             Self::HttpDelete => f.write_str("aivi.http.delete"),
             Self::HttpHead => f.write_str("aivi.http.head"),
             Self::HttpPostJson => f.write_str("aivi.http.postJson"),
+            Self::HttpRequest => f.write_str("aivi.http.request"),
+            Self::HttpDownload => f.write_str("aivi.http.download"),
+            Self::WsConnect => f.write_str("aivi.ws.connect"),
+            Self::WsSend => f.write_str("aivi.ws.send"),
+            Self::WsRecv => f.write_str("aivi.ws.recv"),
+            Self::WsRecvTimeout => f.write_str("aivi.ws.recvTimeout"),
+            Self::WsClose => f.write_str("aivi.ws.close"),
+            Self::ProcessSpawn => f.write_str("aivi.process.spawn"),
+            Self::ProcessWait => f.write_str("aivi.process.wait"),
+            Self::ProcessKill => f.write_str("aivi.process.kill"),
+            Self::ProcessReadStdout => f.write_str("aivi.process.readStdout"),
+            Self::ProcessWriteStdin => f.write_str("aivi.process.writeStdin"),
             Self::BigIntFromInt => f.write_str("aivi.bigint.fromInt"),
             Self::BigIntFromText => f.write_str("aivi.bigint.fromText"),
             Self::BigIntToInt => f.write_str("aivi.bigint.toInt"),
@@ -833,6 +1083,14 @@ impl fmt::Display for IntrinsicValue {
             Self::BigIntEq => f.write_str("aivi.bigint.eq"),
             Self::BigIntGt => f.write_str("aivi.bigint.gt"),
             Self::BigIntLt => f.write_str("aivi.bigint.lt"),
+            Self::DecimalFromInt => f.write_str("aivi.decimal.fromInt"),
+            Self::DecimalFromText => f.write_str("aivi.decimal.fromText"),
+            Self::DecimalToText => f.write_str("aivi.decimal.toText"),
+            Self::DecimalAdd => f.write_str("aivi.decimal.add"),
+            Self::DecimalSub => f.write_str("aivi.decimal.sub"),
+            Self::DecimalMul => f.write_str("aivi.decimal.mul"),
+            Self::DecimalDivWithMode => f.write_str("aivi.decimal.divWithMode"),
+            Self::DecimalRoundWithMode => f.write_str("aivi.decimal.roundWithMode"),
             Self::BitAnd => f.write_str("aivi.bits.and"),
             Self::BitOr => f.write_str("aivi.bits.or"),
             Self::BitXor => f.write_str("aivi.bits.xor"),
@@ -859,6 +1117,7 @@ pub enum BuiltinType {
     BigInt,
     Bool,
     Text,
+    Char,
     Unit,
     Bytes,
     List,
@@ -1596,6 +1855,12 @@ pub struct FloatLiteral {
     pub raw: Box<str>,
 }
 
+/// One char literal, decoded by the parser.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CharLiteral {
+    pub value: char,
+}
+
 /// One decimal literal preserved in raw form.
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct DecimalLiteral {
@@ -1695,6 +1960,7 @@ pub enum ExprKind {
     BigInt(BigIntLiteral),
     SuffixedInteger(SuffixedIntegerLiteral),
     Text(TextLiteral),
+    Char(CharLiteral),
     Regex(RegexLiteral),
     Tuple(AtLeastTwo<ExprId>),
     List(Vec<ExprId>),
@@ -3482,6 +3748,11 @@ pub enum PatternKind {
     Binding(BindingPattern),
     Integer(IntegerLiteral),
     Text(TextLiteral),
+    /// Text that starts with `prefix`; the remaining text is matched against `rest`.
+    TextPrefix {
+        prefix: TextLiteral,
+        rest: PatternId,
+    },
     Tuple(AtLeastTwo<PatternId>),
     List {
         elements: Vec<PatternId>,
@@ -3947,6 +4218,15 @@ impl Module {
     pub fn validate(&self, mode: ValidationMode) -> ValidationReport {
         validate_module(self, mode)
     }
+
+    /// Validate this resolved module, stopping type checking after `max_errors` errors.
+    pub fn validate_with_max_errors(
+        &self,
+        mode: ValidationMode,
+        max_errors: usize,
+    ) -> ValidationReport {
+        validate_module_with_max_errors(self, mode, max_errors)
+    }
 }
 
 impl Module<Unresolved> {
@@ -17,6 +17,7 @@ mod general_expr_elaboration;
 mod hir;
 mod ids;
 mod lower;
+mod print_expr;
 mod recurrence_elaboration;
 pub mod resolver;
 mod resource_signal_elaboration;
@@ -24,6 +25,7 @@ mod sequence;
 mod signal_metadata_elaboration;
 mod source_contract_resolution;
 mod source_lifecycle_elaboration;
+mod strict_checks;
 pub mod symbols;
 mod temporal_elaboration;
 mod truthy_falsy_elaboration;
@@ -77,9 +79,9 @@ pub use general_expr_elaboration::{
 };
 pub use hir::{
     ApplicativeCluster, ApplicativeSpine, ApplicativeSpineHead, BigIntLiteral, BinaryOperator,
-    Binding, BindingKind, BindingPattern, BuiltinTerm, BuiltinType, CaseControl, ClassItem,
-    ClassMember, ClassMemberResolution, ClusterFinalizer, ClusterPresentation, ControlNode,
-    ControlNodeKind, CustomCapabilityCommandSpec, CustomSourceArgumentSchema,
+    Binding, BindingKind, BindingPattern, BuiltinTerm, BuiltinType, CaseControl, CharLiteral,
+    ClassItem, ClassMember, ClassMemberResolution, ClusterFinalizer, ClusterPresentation,
+    ControlNode, ControlNodeKind, CustomCapabilityCommandSpec, CustomSourceArgumentSchema,
     CustomSourceCapabilityMember, CustomSourceContractMetadata, CustomSourceOptionSchema,
     CustomSourceRecurrenceWakeup, DebugDecorator, DecimalLiteral, Decorator, DecoratorCall,
     DecoratorPayload, DeprecatedDecorator, DeprecationNotice, DomainItem, DomainMember,
@@ -112,6 +114,7 @@ pub use ids::{
 };
 pub use lower::lower_module_with_resolver;
 pub use lower::{LoweringResult, lower_module, lower_structure, resolve_imports};
+pub use print_expr::print_expr;
 pub use recurrence_elaboration::{
     BlockedRecurrenceNode, RecurrenceElaborationBlocker, RecurrenceElaborationReport,
     RecurrenceGuardPlan, RecurrenceNodeElaboration, RecurrenceNodeOutcome, RecurrenceNodePlan,
@@ -137,6 +140,7 @@ pub use source_lifecycle_elaboration::{
     SourceOptionSignalBinding, SourceOptionValueBinding, SourceReplacementPolicy,
     SourceStaleWorkPolicy, SourceTeardownPolicy, elaborate_source_lifecycles,
 };
+pub use strict_checks::{collect_prelude_shadowing_diagnostics, collect_strict_diagnostics};
 pub use symbols::{LspSymbol, LspSymbolKind, extract_symbols};
 pub use temporal_elaboration::{
     BlockedTemporalStage, BurstStagePlan, DelayStagePlan, DiffStageMode, DiffStagePlan,
@@ -149,9 +153,10 @@ pub use truthy_falsy_elaboration::{
     TruthyFalsyStageOutcome, TruthyFalsyStagePlan, elaborate_truthy_falsy,
 };
 pub use typecheck::{
-    ClassMemberImplementation, ConstraintClass, ResolvedClassMemberDispatch, TypeCheckReport,
-    TypeConstraint, apply_defaults, elaborate_default_record_fields, signal_payload_type,
-    typecheck_module,
+    ClassInstanceCoverage, ClassMemberImplementation, ConstraintClass, CoverageName,
+    InstanceCoverageReport, ResolvedClassMemberDispatch, TypeCheckReport, TypeConstraint,
+    apply_defaults, elaborate_default_record_fields, instance_coverage, signal_payload_type,
+    typecheck_module, typecheck_module_with_max_errors,
 };
 pub use typecheck_context::{
     OpaqueTypeVariant, domain_carrier_type, opaque_type_carrier_type, opaque_type_variants,
@@ -162,5 +167,5 @@ pub use typed_declarations::{
 pub use validate::{
     GateRecordField, GateType, TypeBinding, TypeConstructorBinding, TypeConstructorHead,
     ValidationMode, ValidationReport, case_pattern_field_types, validate_bindings, validate_module,
-    validate_structure, validate_types,
+    validate_module_with_max_errors, validate_structure, validate_types,
 };
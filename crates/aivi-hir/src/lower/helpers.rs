@@ -319,6 +319,7 @@ fn builtin_type(name: &str) -> Option<BuiltinType> {
         "Text" => Some(BuiltinType::Text),
         "Unit" => Some(BuiltinType::Unit),
         "Bytes" => Some(BuiltinType::Bytes),
+        "Char" => Some(BuiltinType::Char),
         "List" => Some(BuiltinType::List),
         "Map" => Some(BuiltinType::Map),
         "Set" => Some(BuiltinType::Set),
@@ -342,6 +343,7 @@ fn is_known_module(module: &str) -> bool {
             | "aivi.db"
             | "aivi.text"
             | "aivi.time"
+            | "aivi.clipboard"
             | "aivi.env"
             | "aivi.i18n"
             | "aivi.log"
@@ -355,6 +357,7 @@ fn is_known_module(module: &str) -> bool {
             | "aivi.option"
             | "aivi.list"
             | "aivi.pair"
+            | "aivi.test"
     )
 }
 
@@ -508,6 +511,91 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
                 ),
             ),
         )),
+        ("aivi.test", "matchesSnapshot") => Some(intrinsic_import_value(
+            IntrinsicValue::TestMatchesSnapshot,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        primitive_import_type(BuiltinType::Bool),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.test", "advanceClock") => Some(intrinsic_import_value(
+            IntrinsicValue::TestAdvanceClock,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Int),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Unit),
+                ),
+            ),
+        )),
+        ("aivi.test", "setClock") => Some(intrinsic_import_value(
+            IntrinsicValue::TestSetClock,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Int),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Unit),
+                ),
+            ),
+        )),
+        // Runs a task and reports its failure as `Err` instead of failing the caller; only fatal
+        // runtime errors (such as an intercepted `process.exit`) still propagate.
+        ("aivi.task", "attempt") => Some(intrinsic_import_value(
+            IntrinsicValue::TaskAttempt,
+            arrow_import_type(
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    type_variable_import_type(0, "A"),
+                ),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    result_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        type_variable_import_type(0, "A"),
+                    ),
+                ),
+            ),
+        )),
+        // Runs the cleanup task after the main task whether it succeeded or failed.
+        ("aivi.task", "finally") => Some(intrinsic_import_value(
+            IntrinsicValue::TaskFinally,
+            arrow_import_type(
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    type_variable_import_type(0, "A"),
+                ),
+                arrow_import_type(
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        primitive_import_type(BuiltinType::Unit),
+                    ),
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        type_variable_import_type(0, "A"),
+                    ),
+                ),
+            ),
+        )),
+        // Runs every task concurrently and collects the results in list order.
+        ("aivi.task", "parallel") => Some(intrinsic_import_value(
+            IntrinsicValue::TaskParallel,
+            arrow_import_type(
+                list_import_type(task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    type_variable_import_type(0, "A"),
+                )),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    list_import_type(type_variable_import_type(0, "A")),
+                ),
+            ),
+        )),
         ("aivi.fs", "exists") => Some(intrinsic_import_value(
             IntrinsicValue::FsExists,
             arrow_import_type(
@@ -518,6 +606,178 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
                 ),
             ),
         )),
+        // File handles: the open file stays in the running program's handle table, and the
+        // `{ id, path }` record only names it.
+        ("aivi.fs", "open") => Some(intrinsic_import_value(
+            IntrinsicValue::FsOpen,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        file_handle_import_type(),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.fs", "readLine") => Some(intrinsic_import_value(
+            IntrinsicValue::FsReadLine,
+            arrow_import_type(
+                file_handle_import_type(),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    option_import_type(primitive_import_type(BuiltinType::Text)),
+                ),
+            ),
+        )),
+        ("aivi.fs", "write") => Some(intrinsic_import_value(
+            IntrinsicValue::FsWrite,
+            arrow_import_type(
+                file_handle_import_type(),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        primitive_import_type(BuiltinType::Unit),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.fs", "close") => Some(intrinsic_import_value(
+            IntrinsicValue::FsClose,
+            arrow_import_type(
+                file_handle_import_type(),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Unit),
+                ),
+            ),
+        )),
+        // WebSockets follow the file handle shape: the socket stays in the running program's
+        // table, and the `{ id, url }` record only names it.
+        ("aivi.ws", "connect") => Some(intrinsic_import_value(
+            IntrinsicValue::WsConnect,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    web_socket_import_type(),
+                ),
+            ),
+        )),
+        ("aivi.ws", "send") => Some(intrinsic_import_value(
+            IntrinsicValue::WsSend,
+            arrow_import_type(
+                web_socket_import_type(),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        primitive_import_type(BuiltinType::Unit),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.ws", "recv") => Some(intrinsic_import_value(
+            IntrinsicValue::WsRecv,
+            arrow_import_type(
+                web_socket_import_type(),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    option_import_type(primitive_import_type(BuiltinType::Text)),
+                ),
+            ),
+        )),
+        ("aivi.ws", "recvTimeout") => Some(intrinsic_import_value(
+            IntrinsicValue::WsRecvTimeout,
+            arrow_import_type(
+                web_socket_import_type(),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Int),
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        option_import_type(option_import_type(primitive_import_type(
+                            BuiltinType::Text,
+                        ))),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.ws", "close") => Some(intrinsic_import_value(
+            IntrinsicValue::WsClose,
+            arrow_import_type(
+                web_socket_import_type(),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Unit),
+                ),
+            ),
+        )),
+        // Child processes use the same handle shape: the running process stays in the program's
+        // table, and the `{ id, command }` record only names it.
+        ("aivi.process", "spawn") => Some(intrinsic_import_value(
+            IntrinsicValue::ProcessSpawn,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    list_import_type(primitive_import_type(BuiltinType::Text)),
+                    arrow_import_type(
+                        map_import_type(
+                            primitive_import_type(BuiltinType::Text),
+                            primitive_import_type(BuiltinType::Text),
+                        ),
+                        task_import_type(
+                            primitive_import_type(BuiltinType::Text),
+                            process_handle_import_type(),
+                        ),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.process", "wait") => Some(intrinsic_import_value(
+            IntrinsicValue::ProcessWait,
+            arrow_import_type(
+                process_handle_import_type(),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Int),
+                ),
+            ),
+        )),
+        ("aivi.process", "kill") => Some(intrinsic_import_value(
+            IntrinsicValue::ProcessKill,
+            arrow_import_type(
+                process_handle_import_type(),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Unit),
+                ),
+            ),
+        )),
+        ("aivi.process", "readStdout") => Some(intrinsic_import_value(
+            IntrinsicValue::ProcessReadStdout,
+            arrow_import_type(
+                process_handle_import_type(),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Text),
+                ),
+            ),
+        )),
+        ("aivi.process", "writeStdin") => Some(intrinsic_import_value(
+            IntrinsicValue::ProcessWriteStdin,
+            arrow_import_type(
+                process_handle_import_type(),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        primitive_import_type(BuiltinType::Unit),
+                    ),
+                ),
+            ),
+        )),
         ("aivi.random", "RandomError") => Some(ImportBindingMetadata::TypeConstructor {
             type_item: None,
             constructors: None,
@@ -770,6 +1030,176 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
             IntrinsicValue::BytesEmpty,
             primitive_import_type(BuiltinType::Bytes),
         )),
+        ("aivi.core.bytes", "fromList") => Some(intrinsic_import_value(
+            IntrinsicValue::BytesFromList,
+            arrow_import_type(
+                list_import_type(primitive_import_type(BuiltinType::Int)),
+                primitive_import_type(BuiltinType::Bytes),
+            ),
+        )),
+        ("aivi.core.bytes", "toList") => Some(intrinsic_import_value(
+            IntrinsicValue::BytesToList,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Bytes),
+                list_import_type(primitive_import_type(BuiltinType::Int)),
+            ),
+        )),
+        ("aivi.core.bytes", "fromBase64") => Some(intrinsic_import_value(
+            IntrinsicValue::BytesFromBase64,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                option_import_type(primitive_import_type(BuiltinType::Bytes)),
+            ),
+        )),
+        ("aivi.core.bytes", "toBase64") => Some(intrinsic_import_value(
+            IntrinsicValue::BytesToBase64,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Bytes),
+                primitive_import_type(BuiltinType::Text),
+            ),
+        )),
+        ("aivi.core.bytes", "fromHex") => Some(intrinsic_import_value(
+            IntrinsicValue::BytesFromHex,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                option_import_type(primitive_import_type(BuiltinType::Bytes)),
+            ),
+        )),
+        ("aivi.core.bytes", "toHex") => Some(intrinsic_import_value(
+            IntrinsicValue::BytesToHex,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Bytes),
+                primitive_import_type(BuiltinType::Text),
+            ),
+        )),
+        // HMAC takes the key first, then the message.
+        ("aivi.crypto", "hmacSha256") => Some(intrinsic_import_value(
+            IntrinsicValue::CryptoHmacSha256,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Bytes),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Bytes),
+                    primitive_import_type(BuiltinType::Bytes),
+                ),
+            ),
+        )),
+        ("aivi.crypto", "hmacSha512") => Some(intrinsic_import_value(
+            IntrinsicValue::CryptoHmacSha512,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Bytes),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Bytes),
+                    primitive_import_type(BuiltinType::Bytes),
+                ),
+            ),
+        )),
+        ("aivi.crypto", "constantTimeEquals") => Some(intrinsic_import_value(
+            IntrinsicValue::CryptoConstantTimeEquals,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Bytes),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Bytes),
+                    primitive_import_type(BuiltinType::Bool),
+                ),
+            ),
+        )),
+        // Keys are the 32-byte public key and the 32-byte private seed.
+        ("aivi.crypto", "ed25519Keypair") => Some(intrinsic_import_value(
+            IntrinsicValue::CryptoEd25519Keypair,
+            task_import_type(
+                primitive_import_type(BuiltinType::Text),
+                record_import_type(vec![
+                    record_import_field("publicKey", primitive_import_type(BuiltinType::Bytes)),
+                    record_import_field("privateKey", primitive_import_type(BuiltinType::Bytes)),
+                ]),
+            ),
+        )),
+        ("aivi.crypto", "ed25519Sign") => Some(intrinsic_import_value(
+            IntrinsicValue::CryptoEd25519Sign,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Bytes),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Bytes),
+                    primitive_import_type(BuiltinType::Bytes),
+                ),
+            ),
+        )),
+        ("aivi.crypto", "ed25519Verify") => Some(intrinsic_import_value(
+            IntrinsicValue::CryptoEd25519Verify,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Bytes),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Bytes),
+                    arrow_import_type(
+                        primitive_import_type(BuiltinType::Bytes),
+                        primitive_import_type(BuiltinType::Bool),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.char", "toInt") => Some(intrinsic_import_value(
+            IntrinsicValue::CharToInt,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Char),
+                primitive_import_type(BuiltinType::Int),
+            ),
+        )),
+        ("aivi.char", "fromInt") => Some(intrinsic_import_value(
+            IntrinsicValue::CharFromInt,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Int),
+                option_import_type(primitive_import_type(BuiltinType::Char)),
+            ),
+        )),
+        ("aivi.char", "isDigit") => Some(intrinsic_import_value(
+            IntrinsicValue::CharIsDigit,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Char),
+                primitive_import_type(BuiltinType::Bool),
+            ),
+        )),
+        ("aivi.char", "isAlpha") => Some(intrinsic_import_value(
+            IntrinsicValue::CharIsAlpha,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Char),
+                primitive_import_type(BuiltinType::Bool),
+            ),
+        )),
+        ("aivi.char", "toUpper") => Some(intrinsic_import_value(
+            IntrinsicValue::CharToUpper,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Char),
+                primitive_import_type(BuiltinType::Char),
+            ),
+        )),
+        // Reflection over callable values; anything else fails at runtime.
+        ("aivi.core.fn", "arity") => Some(intrinsic_import_value(
+            IntrinsicValue::FnArity,
+            arrow_import_type(
+                type_variable_import_type(0, "A"),
+                primitive_import_type(BuiltinType::Int),
+            ),
+        )),
+        ("aivi.core.fn", "canApply") => Some(intrinsic_import_value(
+            IntrinsicValue::FnCanApply,
+            arrow_import_type(
+                type_variable_import_type(0, "A"),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Int),
+                    primitive_import_type(BuiltinType::Bool),
+                ),
+            ),
+        )),
+        ("aivi.core.fn", "isFullyApplied") => Some(intrinsic_import_value(
+            IntrinsicValue::FnIsFullyApplied,
+            arrow_import_type(
+                type_variable_import_type(0, "A"),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Int),
+                    primitive_import_type(BuiltinType::Bool),
+                ),
+            ),
+        )),
         // JSON intrinsics — async tasks, executed via serde_json in CLI
         ("aivi.data.json", "validate") => Some(intrinsic_import_value(
             IntrinsicValue::JsonValidate,
@@ -837,6 +1267,84 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
                 ),
             ),
         )),
+        // Structural decoding into the module's `Json` sum type: the first argument is any `Json`
+        // constructor, which tells the executor which runtime type identity to build values with.
+        ("aivi.data.json", "parseInto") => Some(intrinsic_import_value(
+            IntrinsicValue::JsonParseInto,
+            arrow_import_type(
+                named_import_type("Json"),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        named_import_type("Json"),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.data.json", "parseStreamInto") => Some(intrinsic_import_value(
+            IntrinsicValue::JsonParseStreamInto,
+            arrow_import_type(
+                named_import_type("Json"),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        list_import_type(named_import_type("Json")),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.data.json", "stringifyValue") => Some(intrinsic_import_value(
+            IntrinsicValue::JsonStringifyValue,
+            arrow_import_type(
+                named_import_type("Json"),
+                primitive_import_type(BuiltinType::Text),
+            ),
+        )),
+        // Encodes any plain data value; functions, tasks, and constructors with fields fail at
+        // runtime.
+        ("aivi.data.json", "encode") => Some(intrinsic_import_value(
+            IntrinsicValue::JsonEncode,
+            arrow_import_type(
+                type_variable_import_type(0, "A"),
+                primitive_import_type(BuiltinType::Text),
+            ),
+        )),
+        // Like `aivi.data.json.parseInto`, the witness constructor names the target sum type.
+        // TOML parsing is pure, so the result is a plain `Result` rather than a task.
+        ("aivi.data.toml", "parseInto") => Some(intrinsic_import_value(
+            IntrinsicValue::TomlParseInto,
+            arrow_import_type(
+                named_import_type("Toml"),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    result_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        named_import_type("Toml"),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.data.toml", "stringifyValue") => Some(intrinsic_import_value(
+            IntrinsicValue::TomlStringifyValue,
+            arrow_import_type(
+                named_import_type("Toml"),
+                primitive_import_type(BuiltinType::Text),
+            ),
+        )),
+        // Like `stringifyValue`, but a value with no TOML form is an `Err` instead of a runtime
+        // error.
+        ("aivi.data.toml", "encodeValue") => Some(intrinsic_import_value(
+            IntrinsicValue::TomlEncodeValue,
+            arrow_import_type(
+                type_variable_import_type(0, "A"),
+                result_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Text),
+                ),
+            ),
+        )),
         // XDG base directory intrinsics — synchronous, no I/O cost beyond env-var reads
         ("aivi.desktop.xdg", "dataHome") => Some(intrinsic_import_value(
             IntrinsicValue::XdgDataHome,
@@ -914,40 +1422,81 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
                 ),
             ),
         )),
-        ("aivi.text", "startsWith") => Some(intrinsic_import_value(
-            IntrinsicValue::TextStartsWith,
+        ("aivi.text", "startsWith") => Some(intrinsic_import_value(
+            IntrinsicValue::TextStartsWith,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Bool),
+                ),
+            ),
+        )),
+        ("aivi.text", "endsWith") => Some(intrinsic_import_value(
+            IntrinsicValue::TextEndsWith,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Bool),
+                ),
+            ),
+        )),
+        ("aivi.text", "toUpper") => Some(intrinsic_import_value(
+            IntrinsicValue::TextToUpper,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                primitive_import_type(BuiltinType::Text),
+            ),
+        )),
+        ("aivi.text", "toLower") => Some(intrinsic_import_value(
+            IntrinsicValue::TextToLower,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                primitive_import_type(BuiltinType::Text),
+            ),
+        )),
+        ("aivi.text", "caseFold") => Some(intrinsic_import_value(
+            IntrinsicValue::TextCaseFold,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                primitive_import_type(BuiltinType::Text),
+            ),
+        )),
+        ("aivi.text", "reverse") => Some(intrinsic_import_value(
+            IntrinsicValue::TextReverse,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                primitive_import_type(BuiltinType::Text),
+            ),
+        )),
+        ("aivi.text", "graphemeLength") => Some(intrinsic_import_value(
+            IntrinsicValue::TextGraphemeLength,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                primitive_import_type(BuiltinType::Int),
+            ),
+        )),
+        ("aivi.text", "wrap") => Some(intrinsic_import_value(
+            IntrinsicValue::TextWrap,
             arrow_import_type(
-                primitive_import_type(BuiltinType::Text),
+                primitive_import_type(BuiltinType::Int),
                 arrow_import_type(
                     primitive_import_type(BuiltinType::Text),
-                    primitive_import_type(BuiltinType::Bool),
+                    list_import_type(primitive_import_type(BuiltinType::Text)),
                 ),
             ),
         )),
-        ("aivi.text", "endsWith") => Some(intrinsic_import_value(
-            IntrinsicValue::TextEndsWith,
+        ("aivi.text", "truncate") => Some(intrinsic_import_value(
+            IntrinsicValue::TextTruncate,
             arrow_import_type(
-                primitive_import_type(BuiltinType::Text),
+                primitive_import_type(BuiltinType::Int),
                 arrow_import_type(
                     primitive_import_type(BuiltinType::Text),
-                    primitive_import_type(BuiltinType::Bool),
+                    primitive_import_type(BuiltinType::Text),
                 ),
             ),
         )),
-        ("aivi.text", "toUpper") => Some(intrinsic_import_value(
-            IntrinsicValue::TextToUpper,
-            arrow_import_type(
-                primitive_import_type(BuiltinType::Text),
-                primitive_import_type(BuiltinType::Text),
-            ),
-        )),
-        ("aivi.text", "toLower") => Some(intrinsic_import_value(
-            IntrinsicValue::TextToLower,
-            arrow_import_type(
-                primitive_import_type(BuiltinType::Text),
-                primitive_import_type(BuiltinType::Text),
-            ),
-        )),
         ("aivi.text", "trim") => Some(intrinsic_import_value(
             IntrinsicValue::TextTrim,
             arrow_import_type(
@@ -1050,6 +1599,50 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
                 primitive_import_type(BuiltinType::Text),
             ),
         )),
+        // Templates substitute `{key}` placeholders; strict rendering reports the first problem
+        // as a `{ missingKey, offset }` record that `aivi.text` turns into a `TemplateError`.
+        ("aivi.text", "renderTemplate") => Some(intrinsic_import_value(
+            IntrinsicValue::TextRenderTemplate,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    map_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        primitive_import_type(BuiltinType::Text),
+                    ),
+                    result_import_type(
+                        template_problem_import_type(),
+                        primitive_import_type(BuiltinType::Text),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.text", "renderLenient") => Some(intrinsic_import_value(
+            IntrinsicValue::TextRenderLenient,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    map_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        primitive_import_type(BuiltinType::Text),
+                    ),
+                    primitive_import_type(BuiltinType::Text),
+                ),
+            ),
+        )),
+        ("aivi.text", "renderPositional") => Some(intrinsic_import_value(
+            IntrinsicValue::TextRenderPositional,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    list_import_type(primitive_import_type(BuiltinType::Text)),
+                    result_import_type(
+                        template_problem_import_type(),
+                        primitive_import_type(BuiltinType::Text),
+                    ),
+                ),
+            ),
+        )),
         // Float transcendental intrinsics
         ("aivi.core.float", "sin") => Some(intrinsic_import_value(
             IntrinsicValue::FloatSin,
@@ -1165,6 +1758,89 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
                 primitive_import_type(BuiltinType::Float),
             ),
         )),
+        // Math intrinsics
+        ("aivi.math", "fftMagnitude") => Some(intrinsic_import_value(
+            IntrinsicValue::MathFftMagnitude,
+            arrow_import_type(
+                list_import_type(primitive_import_type(BuiltinType::Float)),
+                list_import_type(primitive_import_type(BuiltinType::Float)),
+            ),
+        )),
+        ("aivi.math", "fftSpectrum") => Some(intrinsic_import_value(
+            IntrinsicValue::MathFftSpectrum,
+            arrow_import_type(
+                list_import_type(primitive_import_type(BuiltinType::Float)),
+                list_import_type(fft_bin_import_type()),
+            ),
+        )),
+        // Matrix intrinsics: LU decomposition over square row-major `Float` rows. The
+        // `aivi.matrix` module checks shapes before calling them.
+        ("aivi.matrix", "luSolve") => Some(intrinsic_import_value(
+            IntrinsicValue::MatrixLuSolve,
+            arrow_import_type(
+                float_rows_import_type(),
+                arrow_import_type(
+                    list_import_type(primitive_import_type(BuiltinType::Float)),
+                    option_import_type(list_import_type(primitive_import_type(BuiltinType::Float))),
+                ),
+            ),
+        )),
+        ("aivi.matrix", "luDeterminant") => Some(intrinsic_import_value(
+            IntrinsicValue::MatrixLuDeterminant,
+            arrow_import_type(
+                float_rows_import_type(),
+                primitive_import_type(BuiltinType::Float),
+            ),
+        )),
+        ("aivi.matrix", "luInverse") => Some(intrinsic_import_value(
+            IntrinsicValue::MatrixLuInverse,
+            arrow_import_type(
+                float_rows_import_type(),
+                option_import_type(float_rows_import_type()),
+            ),
+        )),
+        // Matrix intrinsics: Jacobi rotations over row-major `Float` rows. The SVD takes any
+        // rectangular shape; the eigenvalue solver answers `None` for non-symmetric input.
+        ("aivi.matrix", "jacobiSvd") => Some(intrinsic_import_value(
+            IntrinsicValue::MatrixJacobiSvd,
+            arrow_import_type(float_rows_import_type(), svd_factors_import_type()),
+        )),
+        ("aivi.matrix", "jacobiEigenvalues") => Some(intrinsic_import_value(
+            IntrinsicValue::MatrixJacobiEigenvalues,
+            arrow_import_type(
+                float_rows_import_type(),
+                option_import_type(list_import_type(primitive_import_type(BuiltinType::Float))),
+            ),
+        )),
+        // Graph intrinsics: Dijkstra over a directed `{ nodes, edges }` record.
+        ("aivi.graph", "shortestPath") => Some(intrinsic_import_value(
+            IntrinsicValue::GraphShortestPath,
+            arrow_import_type(
+                graph_import_type(),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Int),
+                    arrow_import_type(
+                        primitive_import_type(BuiltinType::Int),
+                        option_import_type(list_import_type(primitive_import_type(
+                            BuiltinType::Int,
+                        ))),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.graph", "distances") => Some(intrinsic_import_value(
+            IntrinsicValue::GraphDistances,
+            arrow_import_type(
+                graph_import_type(),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Int),
+                    map_import_type(
+                        primitive_import_type(BuiltinType::Int),
+                        primitive_import_type(BuiltinType::Float),
+                    ),
+                ),
+            ),
+        )),
         // Time intrinsics
         ("aivi.time", "nowMs") => Some(intrinsic_import_value(
             IntrinsicValue::TimeNowMs,
@@ -1180,6 +1856,16 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
                 primitive_import_type(BuiltinType::Int),
             ),
         )),
+        ("aivi.time", "sleep") => Some(intrinsic_import_value(
+            IntrinsicValue::TimeSleep,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Int),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Unit),
+                ),
+            ),
+        )),
         ("aivi.time", "format") => Some(intrinsic_import_value(
             IntrinsicValue::TimeFormat,
             arrow_import_type(
@@ -1206,6 +1892,24 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
                 ),
             ),
         )),
+        // Clipboard intrinsics
+        ("aivi.clipboard", "read") => Some(intrinsic_import_value(
+            IntrinsicValue::ClipboardRead,
+            task_import_type(
+                primitive_import_type(BuiltinType::Text),
+                primitive_import_type(BuiltinType::Text),
+            ),
+        )),
+        ("aivi.clipboard", "write") => Some(intrinsic_import_value(
+            IntrinsicValue::ClipboardWrite,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                task_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    primitive_import_type(BuiltinType::Unit),
+                ),
+            ),
+        )),
         // Regex intrinsics
         ("aivi.regex", "isMatch") => Some(intrinsic_import_value(
             IntrinsicValue::RegexIsMatch,
@@ -1259,6 +1963,32 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
                 ),
             ),
         )),
+        ("aivi.regex", "captures") => Some(intrinsic_import_value(
+            IntrinsicValue::RegexCaptures,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        option_import_type(captures_import_type()),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.regex", "capturesAll") => Some(intrinsic_import_value(
+            IntrinsicValue::RegexCapturesAll,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Text),
+                    task_import_type(
+                        primitive_import_type(BuiltinType::Text),
+                        list_import_type(captures_import_type()),
+                    ),
+                ),
+            ),
+        )),
         ("aivi.regex", "replace") => Some(intrinsic_import_value(
             IntrinsicValue::RegexReplace,
             arrow_import_type(
@@ -1455,6 +2185,87 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
                 ),
             ),
         )),
+        // Decimal intrinsics (pure/synchronous)
+        ("aivi.decimal", "fromInt") => Some(intrinsic_import_value(
+            IntrinsicValue::DecimalFromInt,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Int),
+                primitive_import_type(BuiltinType::Decimal),
+            ),
+        )),
+        ("aivi.decimal", "fromText") => Some(intrinsic_import_value(
+            IntrinsicValue::DecimalFromText,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                option_import_type(primitive_import_type(BuiltinType::Decimal)),
+            ),
+        )),
+        ("aivi.decimal", "toText") => Some(intrinsic_import_value(
+            IntrinsicValue::DecimalToText,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Decimal),
+                primitive_import_type(BuiltinType::Text),
+            ),
+        )),
+        ("aivi.decimal", "add") => Some(intrinsic_import_value(
+            IntrinsicValue::DecimalAdd,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Decimal),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Decimal),
+                    primitive_import_type(BuiltinType::Decimal),
+                ),
+            ),
+        )),
+        ("aivi.decimal", "sub") => Some(intrinsic_import_value(
+            IntrinsicValue::DecimalSub,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Decimal),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Decimal),
+                    primitive_import_type(BuiltinType::Decimal),
+                ),
+            ),
+        )),
+        ("aivi.decimal", "mul") => Some(intrinsic_import_value(
+            IntrinsicValue::DecimalMul,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Decimal),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Decimal),
+                    primitive_import_type(BuiltinType::Decimal),
+                ),
+            ),
+        )),
+        ("aivi.decimal", "divWithMode") => Some(intrinsic_import_value(
+            IntrinsicValue::DecimalDivWithMode,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Int),
+                    arrow_import_type(
+                        primitive_import_type(BuiltinType::Decimal),
+                        arrow_import_type(
+                            primitive_import_type(BuiltinType::Decimal),
+                            primitive_import_type(BuiltinType::Decimal),
+                        ),
+                    ),
+                ),
+            ),
+        )),
+        ("aivi.decimal", "roundWithMode") => Some(intrinsic_import_value(
+            IntrinsicValue::DecimalRoundWithMode,
+            arrow_import_type(
+                primitive_import_type(BuiltinType::Text),
+                arrow_import_type(
+                    primitive_import_type(BuiltinType::Int),
+                    arrow_import_type(
+                        primitive_import_type(BuiltinType::Decimal),
+                        primitive_import_type(BuiltinType::Decimal),
+                    ),
+                ),
+            ),
+        )),
         // Bitwise intrinsics
         ("aivi.bits", "and") => Some(intrinsic_import_value(
             IntrinsicValue::BitAnd,
@@ -1708,6 +2519,37 @@ fn known_import_metadata(module: &str, member: &str) -> Option<ImportBindingMeta
     }
 }
 
+/// Intrinsics from the catalog above that fail at runtime on some well-typed arguments, with how
+/// to avoid the failure. Intrinsics that only fail on non-finite results are not listed.
+pub(crate) fn partial_intrinsic_alternative(value: IntrinsicValue) -> Option<&'static str> {
+    match value {
+        IntrinsicValue::FloatSqrt => Some("check the argument with `isNegative` before calling it"),
+        IntrinsicValue::FloatLog | IntrinsicValue::FloatLog2 | IntrinsicValue::FloatLog10 => {
+            Some("check the argument with `isPositive` before calling it")
+        }
+        IntrinsicValue::FloatAsin | IntrinsicValue::FloatAcos => {
+            Some("check the argument is `between` -1.0 and 1.0 before calling it")
+        }
+        IntrinsicValue::DecimalDivWithMode => {
+            Some("compare the divisor with `zero` before calling it")
+        }
+        IntrinsicValue::MatrixLuSolve => {
+            Some("use `aivi.matrix.solve`, which returns a `Result` to match on")
+        }
+        IntrinsicValue::MatrixLuDeterminant => {
+            Some("use `aivi.matrix.determinant`, which returns a `Result` to match on")
+        }
+        IntrinsicValue::MatrixLuInverse => {
+            Some("use `aivi.matrix.inverse`, which returns a `Result` to match on")
+        }
+        IntrinsicValue::MatrixJacobiSvd => Some("use `aivi.matrix.svd`, which validates the shape"),
+        IntrinsicValue::MatrixJacobiEigenvalues => {
+            Some("use `aivi.matrix.eigenvalues`, which returns a `Result` to match on")
+        }
+        _ => None,
+    }
+}
+
 fn intrinsic_import_value(value: IntrinsicValue, ty: ImportValueType) -> ImportBindingMetadata {
     ImportBindingMetadata::IntrinsicValue { value, ty }
 }
@@ -1745,10 +2587,48 @@ fn option_import_type(element: ImportValueType) -> ImportValueType {
     ImportValueType::Option(Box::new(element))
 }
 
+fn result_import_type(error: ImportValueType, value: ImportValueType) -> ImportValueType {
+    ImportValueType::Result {
+        error: Box::new(error),
+        value: Box::new(value),
+    }
+}
+
 fn list_import_type(element: ImportValueType) -> ImportValueType {
     ImportValueType::List(Box::new(element))
 }
 
+fn map_import_type(key: ImportValueType, value: ImportValueType) -> ImportValueType {
+    ImportValueType::Map {
+        key: Box::new(key),
+        value: Box::new(value),
+    }
+}
+
+/// Capture groups keyed by group name and by index (`"0"` is the whole match).
+fn captures_import_type() -> ImportValueType {
+    map_import_type(
+        primitive_import_type(BuiltinType::Text),
+        primitive_import_type(BuiltinType::Text),
+    )
+}
+
+/// A sum type named by the importing module, either imported or declared locally.
+fn named_import_type(type_name: &str) -> ImportValueType {
+    ImportValueType::Named {
+        type_name: type_name.to_owned(),
+        arguments: Vec::new(),
+        definition: None,
+    }
+}
+
+fn type_variable_import_type(index: usize, name: &str) -> ImportValueType {
+    ImportValueType::TypeVariable {
+        index,
+        name: name.to_owned(),
+    }
+}
+
 fn db_param_import_type() -> ImportValueType {
     record_import_type(vec![
         record_import_field("kind", primitive_import_type(BuiltinType::Text)),
@@ -1783,6 +2663,77 @@ fn db_param_import_type() -> ImportValueType {
     ])
 }
 
+fn file_handle_import_type() -> ImportValueType {
+    record_import_type(vec![
+        record_import_field("id", primitive_import_type(BuiltinType::Int)),
+        record_import_field("path", primitive_import_type(BuiltinType::Text)),
+    ])
+}
+
+fn web_socket_import_type() -> ImportValueType {
+    record_import_type(vec![
+        record_import_field("id", primitive_import_type(BuiltinType::Int)),
+        record_import_field("url", primitive_import_type(BuiltinType::Text)),
+    ])
+}
+
+fn process_handle_import_type() -> ImportValueType {
+    record_import_type(vec![
+        record_import_field("id", primitive_import_type(BuiltinType::Int)),
+        record_import_field("command", primitive_import_type(BuiltinType::Text)),
+    ])
+}
+
+fn template_problem_import_type() -> ImportValueType {
+    record_import_type(vec![
+        record_import_field(
+            "missingKey",
+            option_import_type(primitive_import_type(BuiltinType::Text)),
+        ),
+        record_import_field("offset", primitive_import_type(BuiltinType::Int)),
+    ])
+}
+
+fn float_rows_import_type() -> ImportValueType {
+    list_import_type(list_import_type(primitive_import_type(BuiltinType::Float)))
+}
+
+fn svd_factors_import_type() -> ImportValueType {
+    record_import_type(vec![
+        record_import_field("u", float_rows_import_type()),
+        record_import_field(
+            "singularValues",
+            list_import_type(primitive_import_type(BuiltinType::Float)),
+        ),
+        record_import_field("vt", float_rows_import_type()),
+    ])
+}
+
+fn graph_import_type() -> ImportValueType {
+    record_import_type(vec![
+        record_import_field(
+            "nodes",
+            list_import_type(primitive_import_type(BuiltinType::Int)),
+        ),
+        record_import_field(
+            "edges",
+            list_import_type(record_import_type(vec![
+                record_import_field("from", primitive_import_type(BuiltinType::Int)),
+                record_import_field("to", primitive_import_type(BuiltinType::Int)),
+                record_import_field("weight", primitive_import_type(BuiltinType::Float)),
+            ])),
+        ),
+    ])
+}
+
+fn fft_bin_import_type() -> ImportValueType {
+    record_import_type(vec![
+        record_import_field("magnitude", primitive_import_type(BuiltinType::Float)),
+        record_import_field("phase", primitive_import_type(BuiltinType::Float)),
+        record_import_field("frequencyBin", primitive_import_type(BuiltinType::Int)),
+    ])
+}
+
 fn db_statement_import_type() -> ImportValueType {
     record_import_type(vec![
         record_import_field("sql", primitive_import_type(BuiltinType::Text)),
@@ -1799,6 +2750,7 @@ fn surface_exprs_equal(left: &syn::Expr, right: &syn::Expr) -> bool {
         (syn::ExprKind::Float(left), syn::ExprKind::Float(right)) => left.raw == right.raw,
         (syn::ExprKind::Decimal(left), syn::ExprKind::Decimal(right)) => left.raw == right.raw,
         (syn::ExprKind::BigInt(left), syn::ExprKind::BigInt(right)) => left.raw == right.raw,
+        (syn::ExprKind::Char(left), syn::ExprKind::Char(right)) => left.value == right.value,
         (syn::ExprKind::SuffixedInteger(left), syn::ExprKind::SuffixedInteger(right)) => {
             left.literal.raw == right.literal.raw && left.suffix.text == right.suffix.text
         }
@@ -1518,8 +1518,10 @@ impl<'a> Lowerer<'a> {
             // a real circular import dependency.
             let is_hoist_induced_cycle = cycle.modules().iter().any(|m| m.contains('/'));
             if !(is_hoist_induced_cycle || is_direct_self_import && all_intrinsics) {
-                self.diagnostics.push(
-                    Diagnostic::error(format!(
+                let message = if is_direct_self_import {
+                    format!("import cycle detected: module `{module_name}` imports itself")
+                } else {
+                    format!(
                         "import cycle detected: {}",
                         cycle
                             .modules()
@@ -1527,13 +1529,21 @@ impl<'a> Lowerer<'a> {
                             .map(|module| module.as_ref())
                             .collect::<Vec<_>>()
                             .join(" -> ")
-                    ))
+                    )
+                };
+                let mut diagnostic = Diagnostic::error(message)
                     .with_code(code("import-cycle"))
                     .with_primary_label(
                         item.base.span,
                         "this `use` item closes a cycle in the workspace import graph",
-                    ),
-                );
+                    );
+                for (site, importer) in cycle.use_sites().iter().zip(cycle.modules()) {
+                    diagnostic = diagnostic.with_secondary_label(
+                        *site,
+                        format!("`{importer}` continues the cycle here"),
+                    );
+                }
+                self.diagnostics.push(diagnostic);
             }
         }
         let mut import_specs = item
@@ -2257,6 +2267,12 @@ impl<'a> Lowerer<'a> {
                     raw: float.raw.clone().into_boxed_str(),
                 }),
             }),
+            syn::ExprKind::Char(literal) => self.alloc_expr(Expr {
+                span: expr.span,
+                kind: ExprKind::Char(CharLiteral {
+                    value: literal.value,
+                }),
+            }),
             syn::ExprKind::Decimal(decimal) => self.alloc_expr(Expr {
                 span: expr.span,
                 kind: ExprKind::Decimal(DecimalLiteral {
@@ -3456,6 +3472,19 @@ impl<'a> Lowerer<'a> {
                 }
                 PatternKind::Text(self.lower_text_literal(text))
             }
+            syn::PatternKind::TextPrefix { prefix, rest } => {
+                if prefix.has_interpolation() {
+                    self.emit_error(
+                        prefix.span,
+                        "pattern text literals cannot contain interpolation",
+                        code("interpolated-pattern-text"),
+                    );
+                }
+                PatternKind::TextPrefix {
+                    prefix: self.lower_text_literal(prefix),
+                    rest: self.lower_pattern(rest),
+                }
+            }
             syn::PatternKind::Group(_) => unreachable!("group patterns are handled above"),
             syn::PatternKind::Tuple(elements) => {
                 let elements = elements
@@ -4940,7 +4969,23 @@ impl<'a> Lowerer<'a> {
                     );
                     continue;
                 }
-                ImportBindingResolution::Cycle => continue,
+                ImportBindingResolution::Cycle => {
+                    // The `import-cycle` diagnostic already covers this binding. Keep its name in
+                    // scope, without metadata, so uses of it do not cascade into unknown names.
+                    insert_site(
+                        &mut namespaces.term_imports,
+                        import.local_name.text(),
+                        *import_id,
+                        import.span,
+                    );
+                    insert_site(
+                        &mut namespaces.type_imports,
+                        import.local_name.text(),
+                        *import_id,
+                        import.span,
+                    );
+                    continue;
+                }
             }
 
             match import.metadata.clone() {
@@ -5451,6 +5496,7 @@ impl<'a> Lowerer<'a> {
             ExprKind::Name(_)
             | ExprKind::Integer(_)
             | ExprKind::Float(_)
+            | ExprKind::Char(_)
             | ExprKind::Decimal(_)
             | ExprKind::BigInt(_)
             | ExprKind::SuffixedInteger(_)
@@ -5769,6 +5815,11 @@ impl<'a> Lowerer<'a> {
                 self.hoist_text_literal(&mut text, owner);
                 PatternKind::Text(text)
             }
+            PatternKind::TextPrefix { mut prefix, rest } => {
+                self.hoist_text_literal(&mut prefix, owner);
+                self.hoist_pattern(rest, owner);
+                PatternKind::TextPrefix { prefix, rest }
+            }
             PatternKind::Tuple(elements) => {
                 for element in elements.iter() {
                     self.hoist_pattern(*element, owner);
@@ -6071,6 +6122,7 @@ impl<'a> Lowerer<'a> {
             }
             ExprKind::Integer(_)
             | ExprKind::Float(_)
+            | ExprKind::Char(_)
             | ExprKind::Decimal(_)
             | ExprKind::BigInt(_)
             | ExprKind::SuffixedInteger(_)
@@ -6370,6 +6422,14 @@ impl<'a> Lowerer<'a> {
                     }
                 }
             }
+            PatternKind::TextPrefix { prefix, rest } => {
+                for segment in prefix.segments {
+                    if let TextSegment::Interpolation(interpolation) = segment {
+                        self.collect_captures_expr(interpolation.expr, scope, seen, ordered);
+                    }
+                }
+                self.collect_captures_pattern(rest, scope, seen, ordered);
+            }
             PatternKind::Tuple(elements) => {
                 for element in elements.iter() {
                     self.collect_captures_pattern(*element, scope, seen, ordered);
@@ -6404,6 +6464,7 @@ impl<'a> Lowerer<'a> {
         let pattern = self.module.patterns()[pattern_id].clone();
         match pattern.kind {
             PatternKind::Binding(binding) => vec![binding.binding],
+            PatternKind::TextPrefix { rest, .. } => self.pattern_bindings(rest),
             PatternKind::Tuple(elements) => elements
                 .iter()
                 .flat_map(|element| self.pattern_bindings(*element))
@@ -6450,6 +6511,7 @@ impl<'a> Lowerer<'a> {
             }
             ExprKind::Integer(_)
             | ExprKind::Float(_)
+            | ExprKind::Char(_)
             | ExprKind::Decimal(_)
             | ExprKind::BigInt(_)
             | ExprKind::SuffixedInteger(_)
@@ -6714,6 +6776,7 @@ impl<'a> Lowerer<'a> {
             ExprKind::Name(_)
             | ExprKind::Integer(_)
             | ExprKind::Float(_)
+            | ExprKind::Char(_)
             | ExprKind::Decimal(_)
             | ExprKind::BigInt(_)
             | ExprKind::SuffixedInteger(_)
@@ -7392,6 +7455,7 @@ impl<'a> Lowerer<'a> {
                     ExprKind::Name(_)
                     | ExprKind::Integer(_)
                     | ExprKind::Float(_)
+                    | ExprKind::Char(_)
                     | ExprKind::Decimal(_)
                     | ExprKind::BigInt(_)
                     | ExprKind::SuffixedInteger(_)
@@ -8061,6 +8125,7 @@ impl<'a> Lowerer<'a> {
             }
             ExprKind::Integer(_)
             | ExprKind::Float(_)
+            | ExprKind::Char(_)
             | ExprKind::Decimal(_)
             | ExprKind::BigInt(_)
             | ExprKind::AmbientSubject
@@ -8520,6 +8585,14 @@ impl<'a> Lowerer<'a> {
                     kind: PatternKind::Text(text),
                 }
             }
+            PatternKind::TextPrefix { prefix, rest } => {
+                self.resolve_text_literal(&prefix, namespaces, env);
+                bindings.extend(self.resolve_pattern(rest, namespaces, env));
+                Pattern {
+                    span: pattern.span,
+                    kind: PatternKind::TextPrefix { prefix, rest },
+                }
+            }
             PatternKind::Binding(binding) => {
                 bindings.push(binding.binding);
                 Pattern {
@@ -6,12 +6,12 @@ use aivi_typing::Kind;
 
 use crate::{
     ApplicativeCluster, ApplicativeSpineHead, AtLeastTwo, BigIntLiteral, BinaryOperator, Binding,
-    BindingId, BindingKind, BindingPattern, BuiltinTerm, BuiltinType, CaseControl, ClassItem,
-    ClassMember, ClusterFinalizer, ClusterPresentation, ControlNode, ControlNodeId, DebugDecorator,
-    DecimalLiteral, Decorator, DecoratorCall, DecoratorId, DecoratorPayload, DeprecatedDecorator,
-    DomainItem, DomainMember, DomainMemberKind, DomainMemberResolution, EachControl, EmptyControl,
-    ExportItem, ExportResolution, Expr, ExprId, ExprKind, FloatLiteral, FragmentControl,
-    FunctionItem, FunctionParameter, HoistItem, HoistKindFilter, ImportBinding,
+    BindingId, BindingKind, BindingPattern, BuiltinTerm, BuiltinType, CaseControl, CharLiteral,
+    ClassItem, ClassMember, ClusterFinalizer, ClusterPresentation, ControlNode, ControlNodeId,
+    DebugDecorator, DecimalLiteral, Decorator, DecoratorCall, DecoratorId, DecoratorPayload,
+    DeprecatedDecorator, DomainItem, DomainMember, DomainMemberKind, DomainMemberResolution,
+    EachControl, EmptyControl, ExportItem, ExportResolution, Expr, ExprId, ExprKind, FloatLiteral,
+    FragmentControl, FunctionItem, FunctionParameter, HoistItem, HoistKindFilter, ImportBinding,
     ImportBindingMetadata, ImportBindingResolution, ImportBundleKind, ImportId,
     ImportModuleResolution, ImportRecordField, ImportValueType, ImportedDomainLiteralSuffix,
     InstanceItem, InstanceMember, IntegerLiteral, IntrinsicValue, Item, ItemHeader, ItemId,
@@ -2464,6 +2464,59 @@ value cleanup = files.delete "cache.txt"
     );
 }
 
+#[test]
+fn lowers_process_capability_task_members_into_intrinsics() {
+    let lowered = lower_text(
+        "process_capability_tasks.aivi",
+        r#"
+type ProcessSource = Unit
+
+@source process
+signal runtime : ProcessSource
+
+signal cliArgs : Signal (List Text) = runtime.args
+value argv = runtime.args
+value quit = runtime.exit 2
+"#,
+    );
+    assert!(
+        !lowered.has_errors(),
+        "process task members should lower without diagnostics: {:?}",
+        lowered.diagnostics()
+    );
+
+    let cli_args = find_signal(lowered.module(), "cliArgs");
+    assert_eq!(
+        cli_args
+            .source_metadata
+            .as_ref()
+            .expect("signal-context `runtime.args` should stay a source")
+            .provider,
+        SourceProviderRef::Builtin(aivi_typing::BuiltinSourceProvider::ProcessArgs)
+    );
+
+    let argv = find_value(lowered.module(), "argv");
+    let ExprKind::Name(reference) = &lowered.module().exprs()[argv.body].kind else {
+        panic!("expected value-context `runtime.args` to lower into an intrinsic reference");
+    };
+    assert_eq!(
+        reference.resolution,
+        ResolutionState::Resolved(TermResolution::IntrinsicValue(IntrinsicValue::ProcessArgs))
+    );
+
+    let quit = find_value(lowered.module(), "quit");
+    let ExprKind::Apply { callee, .. } = &lowered.module().exprs()[quit.body].kind else {
+        panic!("expected `runtime.exit 2` to lower into an intrinsic application");
+    };
+    let ExprKind::Name(reference) = &lowered.module().exprs()[*callee].kind else {
+        panic!("expected exit callee to be a resolved intrinsic");
+    };
+    assert_eq!(
+        reference.resolution,
+        ResolutionState::Resolved(TermResolution::IntrinsicValue(IntrinsicValue::ProcessExit))
+    );
+}
+
 #[test]
 fn lowers_dbus_call_capability_handle_values() {
     let lowered = lower_text(
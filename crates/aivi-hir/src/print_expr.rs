@@ -0,0 +1,282 @@
+//! Compact one-line rendering of HIR expressions for diagnostics.
+//!
+//! The output follows the inline layout of `aivi_syntax::Formatter` (same operator precedence and
+//! parenthesisation), so a diagnostic can say "in the expression `f x y`" in surface syntax.
+//! Constructs that have no short inline form (pipes, patches, clusters, markup) are elided with
+//! `…`, and long renderings are cut off at [`MAX_WIDTH`] characters.
+
+use std::fmt::Write as _;
+
+use aivi_syntax::{
+    self as syn,
+    format::{
+        EXPR_APPLY_PREC, EXPR_LAMBDA_PREC, EXPR_PIPE_PREC, EXPR_PREFIX_PREC, EXPR_PROJECTION_PREC,
+        binary_operator_precedence, binary_operator_text,
+    },
+};
+
+use crate::{
+    BinaryOperator, ExprId, ExprKind, Module, ProjectionBase, RecordFieldSurface, TextLiteral,
+    TextSegment, UnaryOperator,
+};
+
+/// Longest rendering, in characters, before the tail is replaced with `…`.
+const MAX_WIDTH: usize = 60;
+
+/// Render `expr` as compact surface syntax, e.g. `add 1 (length items)`.
+pub fn print_expr(module: &Module, expr: ExprId) -> String {
+    let mut printer = ExprPrinter {
+        module,
+        rendered: String::new(),
+        remaining: MAX_WIDTH,
+        truncated: false,
+    };
+    printer.inline(expr, 0);
+    printer.rendered
+}
+
+/// Writes into `rendered` until `remaining` characters are used up. The first character past the
+/// budget replaces the last one written with `…`, and from then on nothing more is rendered, so a
+/// huge expression is only walked as far as it is shown.
+struct ExprPrinter<'a> {
+    module: &'a Module,
+    rendered: String,
+    remaining: usize,
+    truncated: bool,
+}
+
+impl ExprPrinter<'_> {
+    fn push(&mut self, text: &str) {
+        if self.truncated {
+            return;
+        }
+        for ch in text.chars() {
+            if self.remaining == 0 {
+                self.rendered.pop();
+                self.rendered.push('…');
+                self.truncated = true;
+                return;
+            }
+            self.remaining -= 1;
+            self.rendered.push(ch);
+        }
+    }
+
+    fn inline(&mut self, expr: ExprId, parent_prec: u8) {
+        if self.truncated {
+            return;
+        }
+        let module = self.module;
+        match &module.exprs()[expr].kind {
+            ExprKind::Name(reference) => self.push(&reference.path.to_string()),
+            ExprKind::Integer(integer) => self.push(&integer.raw),
+            ExprKind::Float(float) => self.push(&float.raw),
+            ExprKind::Decimal(decimal) => self.push(&decimal.raw),
+            ExprKind::BigInt(bigint) => self.push(&bigint.raw),
+            ExprKind::SuffixedInteger(literal) => {
+                self.push(&literal.raw);
+                self.push(literal.suffix.text());
+            }
+            ExprKind::Text(text) => self.text(text),
+            ExprKind::Char(literal) => {
+                self.push(&format!("'{}'", literal.value.escape_default()));
+            }
+            ExprKind::Regex(regex) => self.push(&regex.raw),
+            ExprKind::Tuple(elements) => {
+                self.push("(");
+                self.list(elements.iter());
+                self.push(")");
+            }
+            ExprKind::List(elements) => {
+                self.push("[");
+                self.list(elements.iter());
+                self.push("]");
+            }
+            ExprKind::Set(elements) if elements.is_empty() => self.push("Set []"),
+            ExprKind::Set(elements) => {
+                self.push("Set [");
+                self.list(elements.iter());
+                self.push("]");
+            }
+            ExprKind::Map(map) if map.entries.is_empty() => self.push("Map {}"),
+            ExprKind::Map(map) => {
+                self.push("Map { ");
+                for (index, entry) in map.entries.iter().enumerate() {
+                    if index > 0 {
+                        self.push(", ");
+                    }
+                    self.inline(entry.key, 0);
+                    self.push(": ");
+                    self.inline(entry.value, 0);
+                }
+                self.push(" }");
+            }
+            ExprKind::Record(record) => {
+                let mut fields = record
+                    .fields
+                    .iter()
+                    .filter(|field| field.surface != RecordFieldSurface::Defaulted)
+                    .peekable();
+                if fields.peek().is_none() {
+                    self.push("{}");
+                    return;
+                }
+                self.push("{ ");
+                for (index, field) in fields.enumerate() {
+                    if index > 0 {
+                        self.push(", ");
+                    }
+                    self.push(field.label.text());
+                    if field.surface == RecordFieldSurface::Explicit {
+                        self.push(": ");
+                        self.inline(field.value, 0);
+                    }
+                }
+                self.push(" }");
+            }
+            ExprKind::Lambda(lambda) => self.wrapped(EXPR_LAMBDA_PREC, parent_prec, |printer| {
+                for parameter in lambda.parameters.iter() {
+                    printer.push(module.bindings()[parameter.binding].name.text());
+                    printer.push(" ");
+                }
+                if !lambda.parameters.is_empty() {
+                    printer.push("=> ");
+                }
+                printer.inline(lambda.body, EXPR_LAMBDA_PREC);
+            }),
+            ExprKind::AmbientSubject => self.push("."),
+            ExprKind::Projection {
+                base: ProjectionBase::Ambient,
+                path,
+            } => self.push(&format!(".{path}")),
+            ExprKind::Projection {
+                base: ProjectionBase::Expr(base),
+                path,
+            } => self.wrapped(EXPR_PROJECTION_PREC, parent_prec, |printer| {
+                printer.inline(*base, EXPR_PROJECTION_PREC);
+                printer.push(&format!(".{path}"));
+            }),
+            ExprKind::Apply { callee, arguments } => {
+                self.wrapped(EXPR_APPLY_PREC, parent_prec, |printer| {
+                    printer.inline(*callee, EXPR_APPLY_PREC);
+                    for argument in arguments.iter() {
+                        printer.push(" ");
+                        printer.inline(*argument, EXPR_APPLY_PREC + 1);
+                    }
+                })
+            }
+            ExprKind::Unary {
+                operator: UnaryOperator::Not,
+                expr,
+            } => self.wrapped(EXPR_PREFIX_PREC, parent_prec, |printer| {
+                printer.push("not ");
+                printer.inline(*expr, EXPR_PREFIX_PREC);
+            }),
+            ExprKind::Binary {
+                left,
+                operator,
+                right,
+            } => {
+                let operator = surface_operator(*operator);
+                let precedence = binary_operator_precedence(operator);
+                self.wrapped(precedence, parent_prec, |printer| {
+                    printer.inline(*left, precedence);
+                    printer.push(&format!(" {} ", binary_operator_text(operator)));
+                    printer.inline(*right, precedence + 1);
+                })
+            }
+            ExprKind::PatchApply { target, .. } => {
+                self.wrapped(EXPR_PIPE_PREC, parent_prec, |printer| {
+                    printer.inline(*target, EXPR_PIPE_PREC + 1);
+                    printer.push(" <| { … }");
+                })
+            }
+            ExprKind::PatchLiteral(_) => self.push("patch { … }"),
+            ExprKind::Pipe(pipe) => self.wrapped(EXPR_PIPE_PREC, parent_prec, |printer| {
+                printer.inline(pipe.head, EXPR_PIPE_PREC + 1);
+                printer.push(" |> …");
+            }),
+            ExprKind::Cluster(_) => self.push("…"),
+            ExprKind::Markup(_) => self.push("<…>"),
+        }
+    }
+
+    fn list<'e>(&mut self, elements: impl Iterator<Item = &'e ExprId>) {
+        for (index, element) in elements.enumerate() {
+            if index > 0 {
+                self.push(", ");
+            }
+            self.inline(*element, 0);
+        }
+    }
+
+    fn text(&mut self, text: &TextLiteral) {
+        self.push("\"");
+        for segment in &text.segments {
+            match segment {
+                TextSegment::Text(fragment) => {
+                    let mut escaped = String::new();
+                    escape_text_fragment(&mut escaped, &fragment.raw);
+                    self.push(&escaped);
+                }
+                TextSegment::Interpolation(interpolation) => {
+                    self.push("{");
+                    self.inline(interpolation.expr, 0);
+                    self.push("}");
+                }
+            }
+        }
+        self.push("\"");
+    }
+
+    /// Print with `print`, in parentheses when it binds looser than its parent.
+    fn wrapped(&mut self, current_prec: u8, parent_prec: u8, print: impl FnOnce(&mut Self)) {
+        let parenthesize = current_prec < parent_prec;
+        if parenthesize {
+            self.push("(");
+        }
+        print(self);
+        if parenthesize {
+            self.push(")");
+        }
+    }
+}
+
+fn escape_text_fragment(escaped: &mut String, raw: &str) {
+    for ch in raw.chars() {
+        match ch {
+            '\n' => escaped.push_str(r"\n"),
+            '\t' => escaped.push_str(r"\t"),
+            '\r' => escaped.push_str(r"\r"),
+            '\\' => escaped.push_str(r"\\"),
+            '"' => escaped.push_str("\\\""),
+            '{' => escaped.push_str(r"\{"),
+            '}' => escaped.push_str(r"\}"),
+            ch if ch.is_control() => {
+                write!(escaped, r"\u{{{:X}}}", ch as u32)
+                    .expect("writing to a String should not fail");
+            }
+            other => escaped.push(other),
+        }
+    }
+}
+
+/// The surface operator a HIR operator was lowered from, so spelling and precedence come from
+/// the formatter's own table.
+fn surface_operator(operator: BinaryOperator) -> syn::BinaryOperator {
+    match operator {
+        BinaryOperator::Add => syn::BinaryOperator::Add,
+        BinaryOperator::Subtract => syn::BinaryOperator::Subtract,
+        BinaryOperator::Multiply => syn::BinaryOperator::Multiply,
+        BinaryOperator::Divide => syn::BinaryOperator::Divide,
+        BinaryOperator::Modulo => syn::BinaryOperator::Modulo,
+        BinaryOperator::GreaterThan => syn::BinaryOperator::GreaterThan,
+        BinaryOperator::LessThan => syn::BinaryOperator::LessThan,
+        BinaryOperator::GreaterThanOrEqual => syn::BinaryOperator::GreaterThanOrEqual,
+        BinaryOperator::LessThanOrEqual => syn::BinaryOperator::LessThanOrEqual,
+        BinaryOperator::Equals => syn::BinaryOperator::Equals,
+        BinaryOperator::NotEquals => syn::BinaryOperator::NotEquals,
+        BinaryOperator::And => syn::BinaryOperator::And,
+        BinaryOperator::Or => syn::BinaryOperator::Or,
+    }
+}
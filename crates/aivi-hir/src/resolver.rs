@@ -1,3 +1,5 @@
+use aivi_base::SourceSpan;
+
 use crate::exports::ExportedNames;
 use crate::hir::HoistKindFilter;
 
@@ -5,6 +7,7 @@ use crate::hir::HoistKindFilter;
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ImportCycle {
     modules: Box<[Box<str>]>,
+    use_sites: Box<[SourceSpan]>,
 }
 
 impl ImportCycle {
@@ -15,12 +18,25 @@ impl ImportCycle {
                 .map(|module| module.into_boxed_str())
                 .collect::<Vec<_>>()
                 .into_boxed_slice(),
+            use_sites: Box::default(),
         }
     }
 
+    /// Attach the spans of the `use` items in the other cycle members, in cycle order.
+    pub fn with_use_sites(mut self, use_sites: Vec<SourceSpan>) -> Self {
+        self.use_sites = use_sites.into_boxed_slice();
+        self
+    }
+
     pub fn modules(&self) -> &[Box<str>] {
         &self.modules
     }
+
+    /// `use` items that form the cycle's other edges. The item that closes the cycle is reported
+    /// by the module being lowered and is not included.
+    pub fn use_sites(&self) -> &[SourceSpan] {
+        &self.use_sites
+    }
 }
 
 /// Resolution result for one imported module path.
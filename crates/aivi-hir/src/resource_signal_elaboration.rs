@@ -855,6 +855,7 @@ fn builtin_type_name(builtin: BuiltinType) -> &'static str {
         BuiltinType::Text => "Text",
         BuiltinType::Unit => "Unit",
         BuiltinType::Bytes => "Bytes",
+        BuiltinType::Char => "Char",
         BuiltinType::List => "List",
         BuiltinType::Map => "Map",
         BuiltinType::Set => "Set",
@@ -326,6 +326,7 @@ fn collect_signal_deps_internal(
                     },
                     ExprKind::Integer(_)
                     | ExprKind::Float(_)
+                    | ExprKind::Char(_)
                     | ExprKind::Decimal(_)
                     | ExprKind::BigInt(_)
                     | ExprKind::SuffixedInteger(_)
@@ -450,6 +451,14 @@ fn collect_signal_deps_internal(
                             }
                         }
                     }
+                    PatternKind::TextPrefix { prefix, rest } => {
+                        for segment in &prefix.segments {
+                            if let TextSegment::Interpolation(interpolation) = segment {
+                                work.push(DependencyWork::Expr(interpolation.expr));
+                            }
+                        }
+                        work.push(DependencyWork::Pattern(*rest));
+                    }
                     PatternKind::Tuple(elements) => {
                         work.extend(elements.iter().copied().map(DependencyWork::Pattern));
                     }
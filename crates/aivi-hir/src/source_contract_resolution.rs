@@ -211,6 +211,7 @@ fn builtin_primitive(primitive: PrimitiveType) -> BuiltinType {
         PrimitiveType::Text => BuiltinType::Text,
         PrimitiveType::Unit => BuiltinType::Unit,
         PrimitiveType::Bytes => BuiltinType::Bytes,
+        PrimitiveType::Char => BuiltinType::Char,
     }
 }
 
@@ -309,6 +310,7 @@ mod tests {
             BuiltinType::Text => "Text",
             BuiltinType::Unit => "Unit",
             BuiltinType::Bytes => "Bytes",
+            BuiltinType::Char => "Char",
             BuiltinType::List => "List",
             BuiltinType::Map => "Map",
             BuiltinType::Set => "Set",
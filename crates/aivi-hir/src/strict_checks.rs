@@ -0,0 +1,640 @@
+//! Opt-in lints for editors running in strict mode.
+//!
+//! None of these checks affect whether a module compiles, so they are kept out of validation and
+//! only run when a caller asks for them (the LSP does so when `aivi.strictMode` is enabled).
+//! Warnings are only reported for root items and `use` declarations: ambient prelude items share
+//! the module's arenas and file id, and must never be reported against the user's file.
+
+use std::collections::HashSet;
+
+use aivi_base::Diagnostic;
+
+use crate::{
+    BindingId, BindingKind, ExprKind, ImportBindingMetadata, ImportBindingResolution, Item,
+    MarkupNodeKind, Module, NamePath, PatternId, PatternKind, PipeSemanticStage, PipeStageKind,
+    ResolutionState, TermReference, TermResolution, TextLiteral, TextSegment, TypeKind, codes,
+    lower::partial_intrinsic_alternative, type_analysis::walk_expr_tree,
+};
+
+/// Collect strict-mode warnings: local bindings that are never read, imports that are never
+/// used, case arms that an earlier arm already covers, and direct uses of builtins that can fail
+/// at runtime.
+pub fn collect_strict_diagnostics(module: &Module) -> Vec<Diagnostic> {
+    let mut walk = StrictWalk::default();
+    for item_id in module.root_items().iter().copied() {
+        walk.visit_item(module, &module.items()[item_id]);
+    }
+
+    let mut diagnostics = walk.unused_binding_diagnostics(module);
+    diagnostics.extend(walk.unreachable_case_diagnostics);
+    diagnostics.extend(walk.partial_use_diagnostics);
+    diagnostics.extend(redundant_import_diagnostics(module));
+    diagnostics.sort_by_key(|diagnostic| {
+        diagnostic
+            .labels
+            .first()
+            .map(|label| label.span.span().start())
+    });
+    diagnostics
+}
+
+/// Collect strict-mode warnings for top-level declarations and local bindings that reuse a name
+/// from `prelude_names`, the terms `aivi.prelude` makes available.
+///
+/// Shadowing is legal, so this is kept apart from [`collect_strict_diagnostics`]: the caller has
+/// to supply the prelude's names, and the warning is about readability rather than dead code.
+pub fn collect_prelude_shadowing_diagnostics(
+    module: &Module,
+    prelude_names: &HashSet<String>,
+) -> Vec<Diagnostic> {
+    let mut walk = StrictWalk::default();
+    let mut names = Vec::new();
+    for item_id in module.root_items().iter().copied() {
+        let item = &module.items()[item_id];
+        match item {
+            Item::Value(item) => names.push(&item.name),
+            Item::Function(item) => names.push(&item.name),
+            Item::Signal(item) => names.push(&item.name),
+            _ => {}
+        }
+        walk.visit_item(module, item);
+    }
+    let mut reported = HashSet::new();
+    names.extend(
+        walk.declared
+            .iter()
+            .copied()
+            .filter(|binding| reported.insert(*binding))
+            .map(|binding| &module.bindings()[binding].name),
+    );
+
+    let mut diagnostics = names
+        .into_iter()
+        .filter(|name| prelude_names.contains(name.text()))
+        .map(|name| {
+            let name_text = name.text();
+            Diagnostic::warning(format!("`{name_text}` shadows the prelude's `{name_text}`"))
+                .with_code(codes::SHADOWED_PRELUDE_NAME)
+                .with_primary_label(name.span(), "declared here")
+                .with_help(format!(
+                    "rename it if you meant a new name; the prelude's `{name_text}` is hidden in its scope"
+                ))
+        })
+        .collect::<Vec<_>>();
+    diagnostics.sort_by_key(|diagnostic| {
+        diagnostic
+            .labels
+            .first()
+            .map(|label| label.span.span().start())
+    });
+    diagnostics
+}
+
+#[derive(Default)]
+struct StrictWalk {
+    /// Parameters and pattern bindings introduced inside root items, in discovery order.
+    declared: Vec<BindingId>,
+    read: HashSet<BindingId>,
+    unreachable_case_diagnostics: Vec<Diagnostic>,
+    partial_use_diagnostics: Vec<Diagnostic>,
+}
+
+impl StrictWalk {
+    fn visit_item(&mut self, module: &Module, item: &Item) {
+        match item {
+            Item::Value(item) => self.visit_expr(module, item.body),
+            Item::Function(item) => {
+                self.declared
+                    .extend(item.parameters.iter().map(|parameter| parameter.binding));
+                self.visit_expr(module, item.body);
+            }
+            Item::Signal(item) => {
+                if let Some(body) = item.body {
+                    self.visit_expr(module, body);
+                }
+                for update in &item.reactive_updates {
+                    self.visit_expr(module, update.guard);
+                    self.visit_expr(module, update.body);
+                }
+            }
+            Item::Instance(item) => {
+                for member in &item.members {
+                    self.declared
+                        .extend(member.parameters.iter().map(|parameter| parameter.binding));
+                    self.visit_expr(module, member.body);
+                }
+            }
+            Item::Domain(item) => {
+                for member in &item.members {
+                    let Some(body) = member.body else {
+                        continue;
+                    };
+                    self.declared
+                        .extend(member.parameters.iter().map(|parameter| parameter.binding));
+                    self.visit_expr(module, body);
+                }
+            }
+            Item::Type(_)
+            | Item::Class(_)
+            | Item::SourceProviderContract(_)
+            | Item::Use(_)
+            | Item::Export(_)
+            | Item::Hoist(_) => {}
+        }
+    }
+
+    fn visit_expr(&mut self, module: &Module, root: crate::ExprId) {
+        walk_expr_tree(module, root, |_, expr, _| match &expr.kind {
+            ExprKind::Name(reference) => {
+                if let ResolutionState::Resolved(TermResolution::Local(binding)) =
+                    reference.resolution
+                {
+                    self.read.insert(binding);
+                }
+                self.check_partial_use(module, reference);
+            }
+            ExprKind::Lambda(lambda) => {
+                self.declared
+                    .extend(lambda.parameters.iter().map(|parameter| parameter.binding));
+            }
+            ExprKind::Pipe(pipe) => {
+                for stage in pipe.semantic_stages() {
+                    let PipeSemanticStage::CaseRun(run) = stage else {
+                        continue;
+                    };
+                    let patterns = run
+                        .stages()
+                        .filter_map(|stage| match &stage.kind {
+                            PipeStageKind::Case { pattern, .. } => Some(*pattern),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>();
+                    for pattern in &patterns {
+                        collect_pattern_bindings(module, *pattern, &mut self.declared);
+                    }
+                    self.check_case_run(module, &patterns);
+                }
+            }
+            _ => {}
+        });
+    }
+
+    fn check_case_run(&mut self, module: &Module, patterns: &[PatternId]) {
+        for (index, pattern) in patterns.iter().copied().enumerate() {
+            if patterns[..index]
+                .iter()
+                .any(|earlier| pattern_subsumes(module, *earlier, pattern))
+            {
+                self.unreachable_case_diagnostics.push(
+                    Diagnostic::warning("this case can never match")
+                        .with_code(codes::UNREACHABLE_CASE_PATTERN)
+                        .with_primary_label(
+                            module.patterns()[pattern].span,
+                            "an earlier case already matches every value this one does",
+                        )
+                        .with_help("remove this case or move it above the case that covers it"),
+                );
+            }
+        }
+    }
+
+    fn check_partial_use(&mut self, module: &Module, reference: &TermReference) {
+        let value = match &reference.resolution {
+            ResolutionState::Resolved(TermResolution::IntrinsicValue(value)) => *value,
+            ResolutionState::Resolved(TermResolution::Import(import)) => {
+                match module.imports()[*import].metadata {
+                    ImportBindingMetadata::IntrinsicValue { value, .. } => value,
+                    _ => return,
+                }
+            }
+            _ => return,
+        };
+        let Some(alternative) = partial_intrinsic_alternative(value) else {
+            return;
+        };
+        let name = reference.path.to_string();
+        self.partial_use_diagnostics.push(
+            Diagnostic::warning(format!("`{name}` can fail at runtime"))
+                .with_code(codes::PARTIAL_BUILTIN_USE)
+                .with_primary_label(reference.path.span(), "partial builtin used here")
+                .with_help(alternative),
+        );
+    }
+
+    fn unused_binding_diagnostics(&self, module: &Module) -> Vec<Diagnostic> {
+        let mut reported = HashSet::new();
+        self.declared
+            .iter()
+            .copied()
+            .filter(|binding| !self.read.contains(binding) && reported.insert(*binding))
+            .filter_map(|binding| {
+                let binding = &module.bindings()[binding];
+                let name = binding.name.text();
+                let reportable = matches!(
+                    binding.kind,
+                    BindingKind::FunctionParameter | BindingKind::Pattern
+                ) && !name.starts_with('_')
+                    && !name.contains('#');
+                reportable.then(|| {
+                    Diagnostic::warning(format!("`{name}` is never used"))
+                        .with_code(codes::UNUSED_BINDING)
+                        .with_primary_label(binding.name.span(), "bound here")
+                        .with_help(format!(
+                            "remove the binding or rename it to `_{name}` to mark it as intentionally unused"
+                        ))
+                })
+            })
+            .collect()
+    }
+}
+
+fn collect_pattern_bindings(module: &Module, pattern: PatternId, bindings: &mut Vec<BindingId>) {
+    let mut work = vec![pattern];
+    while let Some(pattern) = work.pop() {
+        match &module.patterns()[pattern].kind {
+            PatternKind::Binding(binding) => bindings.push(binding.binding),
+            PatternKind::Tuple(elements) => work.extend(elements.iter().copied()),
+            PatternKind::TextPrefix { rest, .. } => work.push(*rest),
+            PatternKind::List { elements, rest } => {
+                work.extend(elements.iter().copied());
+                work.extend(rest.iter().copied());
+            }
+            PatternKind::Record(fields) => work.extend(fields.iter().map(|field| field.pattern)),
+            PatternKind::Constructor { arguments, .. } => work.extend(arguments.iter().copied()),
+            PatternKind::Wildcard
+            | PatternKind::Integer(_)
+            | PatternKind::Text(_)
+            | PatternKind::UnresolvedName(_) => {}
+        }
+    }
+}
+
+/// Whether every value matched by `specific` is already matched by `general`.
+///
+/// The check is purely structural, so it never reports an arm that could still match; it only
+/// misses some subsumed arms, such as ones covered by a combination of earlier constructors.
+fn pattern_subsumes(module: &Module, general: PatternId, specific: PatternId) -> bool {
+    let patterns = module.patterns();
+    match (&patterns[general].kind, &patterns[specific].kind) {
+        (PatternKind::Wildcard | PatternKind::Binding(_), _) => true,
+        (PatternKind::Integer(general), PatternKind::Integer(specific)) => general == specific,
+        (PatternKind::Text(general), PatternKind::Text(specific)) => general == specific,
+        (
+            PatternKind::TextPrefix { prefix, rest },
+            PatternKind::Text(specific)
+            | PatternKind::TextPrefix {
+                prefix: specific, ..
+            },
+        ) => {
+            matches!(
+                patterns[*rest].kind,
+                PatternKind::Wildcard | PatternKind::Binding(_)
+            ) && static_text(prefix)
+                .zip(static_text(specific))
+                .is_some_and(|(prefix, specific)| specific.starts_with(&prefix))
+        }
+        (PatternKind::Tuple(general), PatternKind::Tuple(specific)) => {
+            general.len() == specific.len()
+                && general
+                    .iter()
+                    .zip(specific.iter())
+                    .all(|(general, specific)| pattern_subsumes(module, *general, *specific))
+        }
+        (
+            PatternKind::Constructor {
+                callee: general_callee,
+                arguments: general_arguments,
+            },
+            PatternKind::Constructor {
+                callee: specific_callee,
+                arguments: specific_arguments,
+            },
+        ) => {
+            matches!(general_callee.resolution, ResolutionState::Resolved(_))
+                && general_callee.resolution == specific_callee.resolution
+                && general_arguments.len() == specific_arguments.len()
+                && general_arguments
+                    .iter()
+                    .zip(specific_arguments)
+                    .all(|(general, specific)| pattern_subsumes(module, *general, *specific))
+        }
+        (PatternKind::Record(general), PatternKind::Record(specific)) => {
+            general.iter().all(|general| {
+                specific.iter().any(|specific| {
+                    specific.label.text() == general.label.text()
+                        && pattern_subsumes(module, general.pattern, specific.pattern)
+                })
+            })
+        }
+        (
+            PatternKind::List {
+                elements: general_elements,
+                rest: general_rest,
+            },
+            PatternKind::List {
+                elements: specific_elements,
+                rest: specific_rest,
+            },
+        ) => {
+            let prefix_matches = general_elements
+                .iter()
+                .zip(specific_elements)
+                .all(|(general, specific)| pattern_subsumes(module, *general, *specific));
+            match general_rest {
+                None => {
+                    specific_rest.is_none()
+                        && general_elements.len() == specific_elements.len()
+                        && prefix_matches
+                }
+                Some(rest) => {
+                    specific_elements.len() >= general_elements.len()
+                        && prefix_matches
+                        && matches!(
+                            patterns[*rest].kind,
+                            PatternKind::Wildcard | PatternKind::Binding(_)
+                        )
+                }
+            }
+        }
+        _ => false,
+    }
+}
+
+/// The decoded contents of a text literal without interpolation holes.
+fn static_text(text: &TextLiteral) -> Option<String> {
+    text.segments
+        .iter()
+        .map(|segment| match segment {
+            TextSegment::Text(fragment) => Some(&*fragment.raw),
+            TextSegment::Interpolation(_) => None,
+        })
+        .collect()
+}
+
+/// Report `use` entries whose local name is never referenced.
+///
+/// Builtin, intrinsic, and ambient imports resolve straight to their targets, so usage is matched
+/// by spelling rather than by `ImportId`. Names are gathered from every arena rather than from
+/// root items alone; ambient items cannot refer to the user's imports, so this can only make the
+/// check more forgiving.
+fn redundant_import_diagnostics(module: &Module) -> Vec<Diagnostic> {
+    let referenced = referenced_names(module);
+    let mut diagnostics = Vec::new();
+    for item_id in module.root_items().iter().copied() {
+        let Item::Use(item) = &module.items()[item_id] else {
+            continue;
+        };
+        for import_id in item.imports.iter().copied() {
+            let import = &module.imports()[import_id];
+            let local_name = import.local_name.text();
+            if import.resolution != ImportBindingResolution::Resolved
+                || local_name.contains('#')
+                || referenced.contains(local_name)
+            {
+                continue;
+            }
+            diagnostics.push(
+                Diagnostic::warning(format!("`{local_name}` is imported but never used"))
+                    .with_code(codes::REDUNDANT_IMPORT)
+                    .with_primary_label(import.local_name.span(), "imported here")
+                    .with_help(format!("remove `{local_name}` from this `use` declaration")),
+            );
+        }
+    }
+    diagnostics
+}
+
+fn referenced_names(module: &Module) -> HashSet<&str> {
+    let mut names = HashSet::new();
+    for (_, expr) in module.exprs().iter() {
+        match &expr.kind {
+            ExprKind::Name(reference) => add_term_reference(&mut names, reference),
+            ExprKind::SuffixedInteger(literal) => {
+                names.insert(literal.suffix.text());
+            }
+            _ => {}
+        }
+    }
+    for (_, pattern) in module.patterns().iter() {
+        if let PatternKind::Constructor { callee, .. } | PatternKind::UnresolvedName(callee) =
+            &pattern.kind
+        {
+            add_term_reference(&mut names, callee);
+        }
+    }
+    for (_, ty) in module.types().iter() {
+        if let TypeKind::Name(reference) = &ty.kind {
+            add_path(&mut names, &reference.path);
+        }
+    }
+    for (_, node) in module.markup_nodes().iter() {
+        if let MarkupNodeKind::Element(element) = &node.kind {
+            add_path(&mut names, &element.name);
+        }
+    }
+    for (_, item) in module.items().iter() {
+        match item {
+            Item::Instance(instance) => add_path(&mut names, &instance.class.path),
+            Item::Export(export) => add_path(&mut names, &export.target),
+            _ => {}
+        }
+    }
+    names
+}
+
+fn add_term_reference<'a>(names: &mut HashSet<&'a str>, reference: &'a TermReference) {
+    // A local binding with the same spelling shadows the import rather than using it.
+    if !matches!(
+        reference.resolution,
+        ResolutionState::Resolved(TermResolution::Local(_))
+    ) {
+        add_path(names, &reference.path);
+    }
+}
+
+fn add_path<'a>(names: &mut HashSet<&'a str>, path: &'a NamePath) {
+    names.extend(path.segments().iter().map(|segment| segment.text()));
+}
+
+#[cfg(test)]
+mod tests {
+    use aivi_base::{DiagnosticCode, Severity};
+
+    use super::*;
+    use crate::test_support::{lower_text, lower_text_with_stdlib};
+
+    fn strict_messages(path: &str, text: &str, code: DiagnosticCode) -> Vec<String> {
+        let lowered = lower_text(path, text);
+        assert!(
+            !lowered.has_errors(),
+            "strict-mode fixture should lower cleanly: {:?}",
+            lowered.diagnostics()
+        );
+        collect_strict_diagnostics(lowered.module())
+            .into_iter()
+            .inspect(|diagnostic| assert_eq!(diagnostic.severity, Severity::Warning))
+            .filter(|diagnostic| diagnostic.code == Some(code))
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn reports_unused_parameters_and_pattern_bindings() {
+        let messages = strict_messages(
+            "strict_unused.aivi",
+            r#"type Event = Turn Int | Tick
+
+type Int -> Int -> Int
+func first = left right => left
+
+type Int -> Int -> Int
+func ignoring = left _right => left
+
+type Event -> Int
+func steps = input => input
+ ||> Turn amount -> 1
+ ||> Tick -> 0
+
+value apply = (count => 1) 2
+"#,
+            codes::UNUSED_BINDING,
+        );
+        assert_eq!(
+            messages,
+            vec![
+                "`right` is never used".to_owned(),
+                "`amount` is never used".to_owned(),
+                "`count` is never used".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_imports_that_are_never_referenced() {
+        let messages = strict_messages(
+            "strict_imports.aivi",
+            r#"use aivi.matrix (
+    luSolve
+    luDeterminant
+)
+
+value det = luDeterminant [[1.0]]
+"#,
+            codes::REDUNDANT_IMPORT,
+        );
+        assert_eq!(
+            messages,
+            vec!["`luSolve` is imported but never used".to_owned()]
+        );
+    }
+
+    #[test]
+    fn reports_case_arms_covered_by_earlier_arms() {
+        let messages = strict_messages(
+            "strict_unreachable.aivi",
+            r#"type Event = Turn Int | Tick
+
+type Event -> Int
+func afterWildcard = input => input
+ ||> _ -> 0
+ ||> Tick -> 1
+
+type Event -> Int
+func repeatedLiteral = input => input
+ ||> Turn 1 -> 1
+ ||> Turn _ -> 2
+ ||> Turn 1 -> 3
+ ||> Tick -> 0
+"#,
+            codes::UNREACHABLE_CASE_PATTERN,
+        );
+        assert_eq!(messages.len(), 2, "unexpected warnings: {messages:?}");
+    }
+
+    #[test]
+    fn reports_declarations_that_shadow_prelude_names() {
+        let lowered = lower_text(
+            "strict_shadowing.aivi",
+            r#"type Int -> Int
+func map = value => value + 1
+
+type Int -> Int
+func bump = length => length + 1
+
+value total = bump (map 1)
+"#,
+        );
+        assert!(!lowered.has_errors(), "{:?}", lowered.diagnostics());
+        let prelude = ["map", "filter", "length"]
+            .into_iter()
+            .map(str::to_owned)
+            .collect();
+        let messages = collect_prelude_shadowing_diagnostics(lowered.module(), &prelude)
+            .into_iter()
+            .inspect(|diagnostic| {
+                assert_eq!(diagnostic.severity, Severity::Warning);
+                assert_eq!(diagnostic.code, Some(codes::SHADOWED_PRELUDE_NAME));
+            })
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "`map` shadows the prelude's `map`".to_owned(),
+                "`length` shadows the prelude's `length`".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_direct_uses_of_partial_builtins() {
+        let lowered = lower_text_with_stdlib(
+            "strict_partial.aivi",
+            r#"use aivi.matrix (
+    Matrix
+    MatrixError
+    luSolve
+    solve
+)
+
+value raw = luSolve [[2.0]] [4.0]
+
+type Matrix Float -> Result MatrixError (List Float)
+func checked = matrix => solve matrix [4.0]
+"#,
+        );
+        assert!(!lowered.has_errors(), "{:?}", lowered.diagnostics());
+        let diagnostics = collect_strict_diagnostics(lowered.module())
+            .into_iter()
+            .filter(|diagnostic| diagnostic.code == Some(codes::PARTIAL_BUILTIN_USE))
+            .collect::<Vec<_>>();
+        assert_eq!(diagnostics.len(), 1, "unexpected warnings: {diagnostics:?}");
+        assert_eq!(diagnostics[0].message, "`luSolve` can fail at runtime");
+        assert!(
+            diagnostics[0]
+                .help
+                .iter()
+                .any(|help| help.contains("`aivi.matrix.solve`")),
+            "{:?}",
+            diagnostics[0].help
+        );
+    }
+
+    #[test]
+    fn clean_modules_have_no_strict_warnings() {
+        let lowered = lower_text(
+            "strict_clean.aivi",
+            r#"type Event = Turn Int | Tick
+
+type Event -> Int
+func steps = input => input
+ ||> Turn amount -> amount
+ ||> Tick -> 0
+
+value total = steps (Turn 3)
+"#,
+        );
+        assert!(!lowered.has_errors(), "{:?}", lowered.diagnostics());
+        assert!(collect_strict_diagnostics(lowered.module()).is_empty());
+    }
+}
@@ -85,6 +85,7 @@ pub(crate) fn walk_expr_tree(
                     ExprKind::Name(_)
                     | ExprKind::Integer(_)
                     | ExprKind::Float(_)
+                    | ExprKind::Char(_)
                     | ExprKind::Decimal(_)
                     | ExprKind::BigInt(_)
                     | ExprKind::SuffixedInteger(_)
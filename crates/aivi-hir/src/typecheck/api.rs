@@ -122,6 +122,7 @@ impl TypeConstraint {
 pub struct TypeCheckReport {
     diagnostics: Vec<Diagnostic>,
     elisions: Vec<DefaultRecordElision>,
+    aborted: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -144,10 +145,15 @@ pub struct ResolvedClassMemberDispatch {
 }
 
 impl TypeCheckReport {
-    fn new(diagnostics: Vec<Diagnostic>, elisions: Vec<DefaultRecordElision>) -> Self {
+    fn new(
+        diagnostics: Vec<Diagnostic>,
+        elisions: Vec<DefaultRecordElision>,
+        aborted: bool,
+    ) -> Self {
         Self {
             diagnostics,
             elisions,
+            aborted,
         }
     }
 
@@ -162,12 +168,37 @@ impl TypeCheckReport {
     pub fn is_ok(&self) -> bool {
         self.diagnostics.is_empty()
     }
+
+    /// Whether checking stopped early because the error budget was spent.
+    pub fn was_aborted(&self) -> bool {
+        self.aborted
+    }
 }
 
 pub fn typecheck_module(module: &Module) -> TypeCheckReport {
+    run_type_checker(TypeChecker::new(module))
+}
+
+/// Like [`typecheck_module`], but stops once `max_errors` errors have been reported.
+///
+/// Items after the one that spent the budget are not checked. The report keeps the first
+/// `max_errors` errors and ends with a note saying that checking was aborted, so a huge broken
+/// file costs about as much as the part of it that was reported. A budget first spent by the last
+/// item aborts nothing, and that report is the same as an unbounded one. A budget of 0 means no
+/// limit.
+pub fn typecheck_module_with_max_errors(module: &Module, max_errors: usize) -> TypeCheckReport {
     let mut checker = TypeChecker::new(module);
+    checker.max_errors = (max_errors > 0).then_some(max_errors);
+    run_type_checker(checker)
+}
+
+fn run_type_checker(mut checker: TypeChecker<'_>) -> TypeCheckReport {
     checker.run();
-    TypeCheckReport::new(checker.diagnostics, checker.default_record_elisions)
+    TypeCheckReport::new(
+        checker.diagnostics,
+        checker.default_record_elisions,
+        checker.aborted,
+    )
 }
 
 /// Applies the default-record-field elisions computed by [`typecheck_module`] to `module`,
@@ -180,6 +211,179 @@ pub fn elaborate_default_record_fields(module: &Module) -> Module {
     apply_defaults(module, &typecheck_module(module))
 }
 
+/// A class or type as instance coverage tracks it: the module that declares it and its name
+/// there. Builtin and ambient prelude classes and types have no module.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CoverageName {
+    pub module: Option<Box<str>>,
+    pub name: Box<str>,
+}
+
+impl CoverageName {
+    pub fn declared(module: &str, name: &str) -> Self {
+        Self {
+            module: Some(module.into()),
+            name: name.into(),
+        }
+    }
+
+    pub fn builtin(name: &str) -> Self {
+        Self {
+            module: None,
+            name: name.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for CoverageName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.module {
+            Some(module) => write!(f, "{module}.{}", self.name),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+/// Which declared types carry an instance of one class across a set of modules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClassInstanceCoverage {
+    pub class: CoverageName,
+    pub covered_types: Vec<CoverageName>,
+}
+
+/// Read-only report of class instance coverage collected from resolved HIR modules.
+///
+/// Classes and types are keyed by what their names resolve to rather than by how they are
+/// spelled, so a local class and an imported class that share a name stay apart, while an
+/// instance of an imported class counts towards the class its module declares. Types are taken
+/// from the head constructor of each instance argument, so `instance Eq (Box A)` covers `Box`.
+/// Only user-declared (root) items contribute; ambient prelude items are skipped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InstanceCoverageReport {
+    classes: Vec<ClassInstanceCoverage>,
+    declared_types: Vec<CoverageName>,
+}
+
+impl InstanceCoverageReport {
+    pub fn classes(&self) -> &[ClassInstanceCoverage] {
+        &self.classes
+    }
+
+    pub fn declared_types(&self) -> &[CoverageName] {
+        &self.declared_types
+    }
+
+    pub fn class(&self, class: &CoverageName) -> Option<&ClassInstanceCoverage> {
+        self.classes
+            .iter()
+            .find(|coverage| &coverage.class == class)
+    }
+
+    pub fn has_instance(&self, class: &CoverageName, ty: &CoverageName) -> bool {
+        self.class(class)
+            .is_some_and(|coverage| coverage.covered_types.contains(ty))
+    }
+
+    /// Declared types that have no instance of `class` in the scanned modules.
+    pub fn uncovered_types(&self, class: &CoverageName) -> Vec<&CoverageName> {
+        self.declared_types
+            .iter()
+            .filter(|ty| !self.has_instance(class, ty))
+            .collect()
+    }
+}
+
+/// Collects which declared types have instances of which classes across `modules`, each given
+/// with the dotted path other modules import it by.
+pub fn instance_coverage<'a>(
+    modules: impl IntoIterator<Item = (&'a str, &'a Module)>,
+) -> InstanceCoverageReport {
+    let mut classes = BTreeMap::<CoverageName, BTreeSet<CoverageName>>::new();
+    let mut declared_types = BTreeSet::<CoverageName>::new();
+    for (module_name, module) in modules {
+        for item_id in module.root_items().iter().copied() {
+            match &module.items()[item_id] {
+                Item::Type(item) => {
+                    declared_types.insert(CoverageName::declared(module_name, item.name.text()));
+                }
+                Item::Class(item) => {
+                    classes
+                        .entry(CoverageName::declared(module_name, item.name.text()))
+                        .or_default();
+                }
+                Item::Instance(item) => {
+                    let Some(class) = coverage_name(module_name, module, &item.class) else {
+                        continue;
+                    };
+                    let covered = classes.entry(class).or_default();
+                    if let Some(head) =
+                        instance_head_name(module_name, module, *item.arguments.first())
+                    {
+                        covered.insert(head);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    InstanceCoverageReport {
+        classes: classes
+            .into_iter()
+            .map(|(class, covered_types)| ClassInstanceCoverage {
+                class,
+                covered_types: covered_types.into_iter().collect(),
+            })
+            .collect(),
+        declared_types: declared_types.into_iter().collect(),
+    }
+}
+
+fn instance_head_name(module_name: &str, module: &Module, ty: TypeId) -> Option<CoverageName> {
+    let mut current = ty;
+    loop {
+        match &module.types().get(current)?.kind {
+            TypeKind::Name(reference) => return coverage_name(module_name, module, reference),
+            TypeKind::Apply { callee, .. } => current = *callee,
+            TypeKind::Tuple(_)
+            | TypeKind::Record(_)
+            | TypeKind::RecordTransform { .. }
+            | TypeKind::Arrow { .. } => return None,
+        }
+    }
+}
+
+/// What a class or type reference in `module` resolves to. An import names the class or type in
+/// the module it comes from, so it matches that module's own declaration.
+fn coverage_name(
+    module_name: &str,
+    module: &Module,
+    reference: &TypeReference,
+) -> Option<CoverageName> {
+    let surface_name = reference.path.segments().last().text();
+    match reference.resolution {
+        ResolutionState::Resolved(TypeResolution::Item(item))
+            if module.ambient_items().contains(&item) =>
+        {
+            Some(CoverageName::builtin(surface_name))
+        }
+        ResolutionState::Resolved(TypeResolution::Item(_)) => {
+            Some(CoverageName::declared(module_name, surface_name))
+        }
+        ResolutionState::Resolved(TypeResolution::Import(import)) => {
+            let binding = &module.imports()[import];
+            Some(CoverageName {
+                module: binding.source_module.clone(),
+                name: binding.imported_name.text().into(),
+            })
+        }
+        ResolutionState::Resolved(TypeResolution::Builtin(_)) => {
+            Some(CoverageName::builtin(surface_name))
+        }
+        ResolutionState::Resolved(TypeResolution::TypeParameter(_))
+        | ResolutionState::Unresolved => None,
+    }
+}
+
 pub(crate) fn expression_matches(
     module: &Module,
     expr_id: ExprId,
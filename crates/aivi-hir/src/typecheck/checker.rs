@@ -10,6 +10,13 @@ struct TypeChecker<'a> {
     /// any in-scope class evidence through `with` / `require`.
     eq_constrained_parameters: HashSet<TypeParameterId>,
     in_scope_class_constraints: Vec<ClassConstraintBinding>,
+    /// Stop checking further items once this many errors have been reported.
+    max_errors: Option<usize>,
+    /// Errors among `diagnostics[..counted_diagnostics]`, kept up to date between items so the
+    /// budget check does not rescan every diagnostic.
+    error_count: usize,
+    counted_diagnostics: usize,
+    aborted: bool,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -68,6 +75,10 @@ impl<'a> TypeChecker<'a> {
             pending_eq_constraints: Vec::new(),
             eq_constrained_parameters: HashSet::new(),
             in_scope_class_constraints: Vec::new(),
+            max_errors: None,
+            error_count: 0,
+            counted_diagnostics: 0,
+            aborted: false,
         }
     }
 
@@ -84,6 +95,10 @@ impl<'a> TypeChecker<'a> {
             pending_eq_constraints: Vec::new(),
             eq_constrained_parameters: HashSet::new(),
             in_scope_class_constraints: Vec::new(),
+            max_errors: None,
+            error_count: 0,
+            counted_diagnostics: 0,
+            aborted: false,
         }
     }
 
@@ -94,7 +109,26 @@ impl<'a> TypeChecker<'a> {
             .iter()
             .map(|(item_id, item)| (item_id, item.clone()))
             .collect::<Vec<_>>();
-        for (item_id, item) in items {
+        // Ambient prelude items are checked after the module's own items. Only the module's own
+        // items decide whether spending the budget leaves anything worth reporting unchecked.
+        let ambient_items = self
+            .module
+            .ambient_items()
+            .iter()
+            .copied()
+            .collect::<HashSet<_>>();
+        let last_module_item = items.iter().rposition(|(item_id, item)| {
+            !ambient_items.contains(item_id)
+                && matches!(
+                    item,
+                    Item::Value(_)
+                        | Item::Function(_)
+                        | Item::Signal(_)
+                        | Item::Instance(_)
+                        | Item::Domain(_)
+                )
+        });
+        for (index, (item_id, item)) in items.into_iter().enumerate() {
             match item {
                 Item::Value(item) => self.check_value_item(&item),
                 Item::Function(item) => self.check_function_item(item_id, &item),
@@ -108,10 +142,48 @@ impl<'a> TypeChecker<'a> {
                 | Item::Export(_)
                 | Item::Hoist(_) => {}
             }
+            if let Some(max_errors) = self.max_errors
+                && last_module_item.is_some_and(|last| index < last)
+                && self.error_budget_spent()
+            {
+                self.abort_for_error_budget(max_errors);
+                return;
+            }
         }
         self.solve_pending_eq_constraints();
     }
 
+    fn error_budget_spent(&mut self) -> bool {
+        let Some(max_errors) = self.max_errors else {
+            return false;
+        };
+        self.error_count += self.diagnostics[self.counted_diagnostics..]
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .count();
+        self.counted_diagnostics = self.diagnostics.len();
+        self.error_count >= max_errors
+    }
+
+    /// Drop the errors past the budget and note that the remaining items were not checked.
+    fn abort_for_error_budget(&mut self, max_errors: usize) {
+        let mut errors = 0;
+        self.diagnostics.retain(|diagnostic| {
+            if diagnostic.severity != Severity::Error {
+                return true;
+            }
+            errors += 1;
+            errors <= max_errors
+        });
+        self.diagnostics.push(
+            Diagnostic::note(format!(
+                "type checking aborted after {max_errors} errors; later items were not checked"
+            ))
+            .with_code(code("type-checking-aborted")),
+        );
+        self.aborted = true;
+    }
+
     fn collect_default_imports(module: &Module) -> (bool, Vec<ImportedDefaultValue>) {
         let mut option_default_in_scope = false;
         let mut imported_default_values = Vec::new();
@@ -668,7 +740,7 @@ impl<'a> TypeChecker<'a> {
         match (expected, info.ty.as_ref()) {
             (Some(expected), Some(actual)) if actual.same_shape(expected) => true,
             (Some(expected), Some(actual)) => {
-                self.emit_type_mismatch(self.module.exprs()[expr_id].span, expected, actual);
+                self.emit_expr_type_mismatch(expr_id, expected, actual);
                 false
             }
             (Some(_), None) => false,
@@ -709,7 +781,7 @@ impl<'a> TypeChecker<'a> {
         match (expected, info.ty.as_ref()) {
             (Some(expected), Some(actual)) if actual.same_shape(expected) => true,
             (Some(expected), Some(actual)) => {
-                self.emit_type_mismatch(self.module.exprs()[expr_id].span, expected, actual);
+                self.emit_expr_type_mismatch(expr_id, expected, actual);
                 false
             }
             (Some(_), None) => false,
@@ -1109,7 +1181,7 @@ impl<'a> TypeChecker<'a> {
     ) -> bool {
         match expected {
             Some(expected) if !actual.same_shape(expected) => {
-                self.emit_type_mismatch(self.module.exprs()[expr_id].span, expected, actual);
+                self.emit_expr_type_mismatch(expr_id, expected, actual);
                 false
             }
             _ => true,
@@ -1610,6 +1682,7 @@ impl<'a> TypeChecker<'a> {
             ExprKind::AmbientSubject => None,
             ExprKind::Integer(_)
             | ExprKind::Float(_)
+            | ExprKind::Char(_)
             | ExprKind::Decimal(_)
             | ExprKind::BigInt(_)
             | ExprKind::Text(_)
@@ -2233,11 +2306,7 @@ impl<'a> TypeChecker<'a> {
                             .collect();
                         let resolved_result = result_ty.substitute_type_parameters(&bindings);
                         if !resolved_result.same_shape(expected) {
-                            self.emit_type_mismatch(
-                                self.module.exprs()[expr_id].span,
-                                expected,
-                                &resolved_result,
-                            );
+                            self.emit_expr_type_mismatch(expr_id, expected, &resolved_result);
                             return Some(false);
                         }
                         resolved
@@ -2247,19 +2316,11 @@ impl<'a> TypeChecker<'a> {
                         // directly (the check_expr below will handle type param matching).
                         parameter_types
                     } else {
-                        self.emit_type_mismatch(
-                            self.module.exprs()[expr_id].span,
-                            expected,
-                            &result_ty,
-                        );
+                        self.emit_expr_type_mismatch(expr_id, expected, &result_ty);
                         return Some(false);
                     }
                 } else {
-                    self.emit_type_mismatch(
-                        self.module.exprs()[expr_id].span,
-                        expected,
-                        &result_ty,
-                    );
+                    self.emit_expr_type_mismatch(expr_id, expected, &result_ty);
                     return Some(false);
                 }
             }
@@ -2484,11 +2545,7 @@ impl<'a> TypeChecker<'a> {
         let ok = self.check_expr(expr_id, env, Some(expected), value_stack);
         if !ok && self.diagnostics.len() == checkpoint {
             let actual = self.inferred_expr_shape(expr_id, env);
-            self.emit_type_mismatch_or_unresolved(
-                self.module.exprs()[expr_id].span,
-                expected,
-                actual.as_ref(),
-            );
+            self.emit_type_mismatch_or_unresolved(expr_id, expected, actual.as_ref());
         }
         ok
     }
@@ -3703,6 +3760,106 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
+    /// `Ord` for a derived payload. The runtime compares derived payloads structurally without
+    /// consulting instance evidence, so a payload with its own instance, a domain, or an imported
+    /// type cannot be ordered this way and the outer type needs an explicit instance instead.
+    fn require_derived_ord_payload(
+        &mut self,
+        ty: &GateType,
+        item_stack: &mut Vec<ItemId>,
+    ) -> Result<(), String> {
+        if let Some(class_item_id) = self.class_item_id_by_name("Ord")
+            && (self
+                .resolve_same_module_instance(class_item_id, ty)?
+                .is_some()
+                || self
+                    .has_imported_instance_binding(class_item_id, &TypeBinding::Type(ty.clone())))
+        {
+            return Err(format!(
+                "payload `{ty}` has its own `Ord` instance, which a derived ordering cannot use; \
+                 write an explicit `Ord` instance for the enclosing type"
+            ));
+        }
+        self.require_compiler_derived_ord(ty, item_stack)
+    }
+
+    /// Structural `Ord` fallback for same-module ADTs without an explicit instance: constructors
+    /// order by declaration, then payloads compare left to right, so every payload must be `Ord`.
+    fn require_compiler_derived_ord(
+        &mut self,
+        ty: &GateType,
+        item_stack: &mut Vec<ItemId>,
+    ) -> Result<(), String> {
+        match ty {
+            GateType::Primitive(
+                BuiltinType::Unit
+                | BuiltinType::Int
+                | BuiltinType::Float
+                | BuiltinType::Decimal
+                | BuiltinType::BigInt
+                | BuiltinType::Bool
+                | BuiltinType::Text,
+            ) => Ok(()),
+            // The runtime would compare the parameter's values structurally and ignore the `Ord`
+            // evidence passed for it.
+            GateType::TypeParameter { name, .. } => Err(format!(
+                "open type parameter `{name}` cannot be ordered by a compiler-derived `Ord` \
+                 instance; write an explicit `Ord` instance for the enclosing type"
+            )),
+            GateType::Tuple(elements) => {
+                for element in elements {
+                    self.require_derived_ord_payload(element, item_stack)?;
+                }
+                Ok(())
+            }
+            GateType::Record(fields) => {
+                for field in fields {
+                    self.require_derived_ord_payload(&field.ty, item_stack)?;
+                }
+                Ok(())
+            }
+            GateType::OpaqueItem { name, .. } if name == "Ordering" => Ok(()),
+            GateType::OpaqueItem {
+                item, arguments, ..
+            } => {
+                if item_stack.contains(item) {
+                    return Ok(());
+                }
+                let (parameters, body) = match &self.module.items()[*item] {
+                    Item::Type(item_ty) => (item_ty.parameters.clone(), item_ty.body.clone()),
+                    _ => return Err(format!("`{ty}` does not refer to a type declaration")),
+                };
+                let substitutions = parameters
+                    .iter()
+                    .copied()
+                    .zip(arguments.iter().cloned())
+                    .collect::<HashMap<TypeParameterId, GateType>>();
+                let field_types = match body {
+                    TypeItemBody::Alias(alias) => vec![alias],
+                    TypeItemBody::Sum(variants) => variants
+                        .iter()
+                        .flat_map(|variant| variant.fields.iter().map(|field| field.ty))
+                        .collect(),
+                };
+                item_stack.push(*item);
+                let result = field_types.into_iter().try_for_each(|field| {
+                    let Some(lowered) = self.typing.lower_hir_type(field, &substitutions) else {
+                        return Err(format!(
+                            "constructor payloads for `{ty}` could not be lowered for Ord checking"
+                        ));
+                    };
+                    self.require_derived_ord_payload(&lowered, item_stack)
+                });
+                let popped = item_stack.pop();
+                debug_assert_eq!(popped, Some(*item));
+                result
+            }
+            _ => Err(format!(
+                "`{ty}` does not have a compiler-derived `Ord` instance"
+            )),
+        }
+    }
+
     fn class_item_id_by_name(&self, class_name: &str) -> Option<ItemId> {
         self.module
             .items()
@@ -3820,6 +3977,11 @@ impl<'a> TypeChecker<'a> {
         {
             return Some(ClassMemberImplementation::Builtin);
         }
+        if class_name == "Ord"
+            && matches!(subject, TypeBinding::Type(ty) if self.require_compiler_derived_ord(ty, &mut Vec::new()).is_ok())
+        {
+            return Some(ClassMemberImplementation::Builtin);
+        }
         None
     }
 
@@ -3932,6 +4094,15 @@ impl<'a> TypeChecker<'a> {
         if self.has_imported_instance_binding(binding.class_item, &binding.subject) {
             return Ok(());
         }
+        // Explicit instances win; the derived ordering is only a fallback.
+        if class_name == "Ord"
+            && let TypeBinding::Type(ty) = &binding.subject
+            && self
+                .require_compiler_derived_ord(ty, &mut Vec::new())
+                .is_ok()
+        {
+            return Ok(());
+        }
         Err(format!(
             "no compiler-provided, imported, or same-module `{class_name}` instance matches `{}`",
             self.type_binding_label(&binding.subject)
@@ -4217,57 +4388,58 @@ impl<'a> TypeChecker<'a> {
     }
 
     fn emit_type_mismatch(&mut self, span: SourceSpan, expected: &GateType, actual: &GateType) {
-        let mut diag = Diagnostic::error(format!("expected `{expected}` but found `{actual}`"))
-            .with_code(code("type-mismatch"))
-            .with_primary_label(
-                span,
-                format!("found `{actual}` here, expected `{expected}`"),
-            );
-
-        // Suggest conversions for common primitive mismatches.
-        if let (GateType::Primitive(e), GateType::Primitive(a)) = (expected, actual) {
-            use crate::hir::BuiltinType;
-            match (e, a) {
-                (BuiltinType::Text, BuiltinType::Int | BuiltinType::Float) => {
-                    diag = diag.with_help("use `toString` to convert a number to text");
-                }
-                (BuiltinType::Int, BuiltinType::Float) => {
-                    diag =
-                        diag.with_help("use `round`, `floor`, or `ceil` to convert Float to Int");
-                }
-                (BuiltinType::Float, BuiltinType::Int) => {
-                    diag = diag.with_help("use `toFloat` to convert Int to Float");
-                }
-                (BuiltinType::Text, BuiltinType::Bool) => {
-                    diag = diag.with_help("use `toString` to convert Bool to text");
-                }
-                _ => {}
-            }
-        }
+        let diag = type_mismatch_diagnostic(span, expected, actual);
+        self.diagnostics.push(diag);
+    }
 
+    /// Like [`Self::emit_type_mismatch`] at `expr_id`, with a note that spells out the offending
+    /// expression when it is more than a single name or literal.
+    fn emit_expr_type_mismatch(&mut self, expr_id: ExprId, expected: &GateType, actual: &GateType) {
+        let diag = type_mismatch_diagnostic(self.module.exprs()[expr_id].span, expected, actual);
+        let diag = self.with_expression_note(diag, expr_id);
         self.diagnostics.push(diag);
     }
 
+    fn with_expression_note(&self, diag: Diagnostic, expr_id: ExprId) -> Diagnostic {
+        match &self.module.exprs()[expr_id].kind {
+            ExprKind::Name(_)
+            | ExprKind::Integer(_)
+            | ExprKind::Float(_)
+            | ExprKind::Decimal(_)
+            | ExprKind::BigInt(_)
+            | ExprKind::SuffixedInteger(_)
+            | ExprKind::Char(_)
+            | ExprKind::Regex(_)
+            | ExprKind::AmbientSubject => diag,
+            ExprKind::Text(text) if !text.has_interpolation() => diag,
+            _ => diag.with_note(format!(
+                "in the expression `{}`",
+                print_expr(self.module, expr_id)
+            )),
+        }
+    }
+
     fn emit_type_mismatch_or_unresolved(
         &mut self,
-        span: SourceSpan,
+        expr_id: ExprId,
         expected: &GateType,
         actual: Option<&GateType>,
     ) {
-        match actual {
-            Some(actual) => self.emit_type_mismatch(span, expected, actual),
-            None => self.diagnostics.push(
-                Diagnostic::error(format!(
-                    "expected `{expected}` but found {}",
-                    describe_inferred_type(None)
-                ))
-                .with_code(code("type-mismatch"))
-                .with_primary_label(
-                    span,
-                    format!("expected `{expected}` but the type could not be inferred"),
-                ),
+        let span = self.module.exprs()[expr_id].span;
+        let diag = match actual {
+            Some(actual) => type_mismatch_diagnostic(span, expected, actual),
+            None => Diagnostic::error(format!(
+                "expected `{expected}` but found {}",
+                describe_inferred_type(None)
+            ))
+            .with_code(code("type-mismatch"))
+            .with_primary_label(
+                span,
+                format!("expected `{expected}` but the type could not be inferred"),
             ),
-        }
+        };
+        let diag = self.with_expression_note(diag, expr_id);
+        self.diagnostics.push(diag);
     }
 
     fn emit_invalid_patch_selector(
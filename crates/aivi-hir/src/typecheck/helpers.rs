@@ -40,6 +40,40 @@ fn describe_inferred_type(ty: Option<&GateType>) -> String {
         .unwrap_or_else(|| "an unresolved expression".to_owned())
 }
 
+fn type_mismatch_diagnostic(
+    span: SourceSpan,
+    expected: &GateType,
+    actual: &GateType,
+) -> Diagnostic {
+    let mut diag = Diagnostic::error(format!("expected `{expected}` but found `{actual}`"))
+        .with_code(code("type-mismatch"))
+        .with_primary_label(
+            span,
+            format!("found `{actual}` here, expected `{expected}`"),
+        );
+
+    // Suggest conversions for common primitive mismatches.
+    if let (GateType::Primitive(e), GateType::Primitive(a)) = (expected, actual) {
+        match (e, a) {
+            (BuiltinType::Text, BuiltinType::Int | BuiltinType::Float) => {
+                diag = diag.with_help("use `toString` to convert a number to text");
+            }
+            (BuiltinType::Int, BuiltinType::Float) => {
+                diag = diag.with_help("use `round`, `floor`, or `ceil` to convert Float to Int");
+            }
+            (BuiltinType::Float, BuiltinType::Int) => {
+                diag = diag.with_help("use `toFloat` to convert Int to Float");
+            }
+            (BuiltinType::Text, BuiltinType::Bool) => {
+                diag = diag.with_help("use `toString` to convert Bool to text");
+            }
+            _ => {}
+        }
+    }
+
+    diag
+}
+
 fn patch_map_entry_type(key: &GateType, value: &GateType) -> GateType {
     GateType::Record(vec![
         GateRecordField {
@@ -1,6 +1,6 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
-use aivi_base::{Diagnostic, DiagnosticCode, SourceSpan};
+use aivi_base::{Diagnostic, DiagnosticCode, Severity, SourceSpan};
 
 use crate::{
     domain_operator_elaboration::select_domain_binary_operator,
@@ -10,10 +10,11 @@ use crate::{
         FunctionItem, ImportBindingMetadata, ImportBundleKind, InstanceItem, InstanceMember, Item,
         MapExpr, Module, Name, NamePath, PatternKind, PipeExpr, PipeStageKind, ProjectionBase,
         ReactiveUpdateBodyMode, RecordExpr, RecordExprField, RecordFieldSurface, ResolutionState,
-        SignalItem, TermReference, TermResolution, TypeItemBody, TypeResolution, UnaryOperator,
-        ValueItem,
+        SignalItem, TermReference, TermResolution, TypeItemBody, TypeKind, TypeReference,
+        TypeResolution, UnaryOperator, ValueItem,
     },
     ids::{BindingId, ExprId, ImportId, ItemId, PatternId, TypeId, TypeParameterId},
+    print_expr::print_expr,
     typecheck_context::LiteralSuffixSelection,
     validate::{
         ClassConstraintBinding, ClassMemberCallMatch, DomainMemberSelection, GateExprEnv,
@@ -1,7 +1,10 @@
 use aivi_base::{FileId, SourceDatabase, SourceSpan};
 use aivi_syntax::parse_module;
 
-use crate::{BuiltinType, Item, PipeTransformMode, RecordFieldSurface, lower_module};
+use crate::{
+    BuiltinType, ImportModuleResolution, ImportResolver, Item, PipeTransformMode,
+    RecordFieldSurface, ValidationMode, exports, lower_module, lower_module_with_resolver,
+};
 
 use super::*;
 
@@ -84,6 +87,7 @@ fn builtin_type(module: &mut Module, builtin: BuiltinType) -> crate::TypeId {
         BuiltinType::Text => "Text",
         BuiltinType::Unit => "Unit",
         BuiltinType::Bytes => "Bytes",
+        BuiltinType::Char => "Char",
         BuiltinType::List => "List",
         BuiltinType::Map => "Map",
         BuiltinType::Set => "Set",
@@ -242,6 +246,88 @@ fn typecheck_elaborates_option_default_record_elision_into_explicit_fields() {
     }
 }
 
+#[test]
+fn typecheck_stops_at_the_error_budget_and_reports_the_truncation() {
+    let text = (0..50)
+        .map(|index| format!("value broken{index} : Int = \"text\"\n"))
+        .collect::<String>();
+    let module = lowered_module_text("many-errors.aivi", &text);
+    let errors = |report: &TypeCheckReport| {
+        report
+            .diagnostics()
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == aivi_base::Severity::Error)
+            .count()
+    };
+
+    let unbounded = typecheck_module(&module);
+    assert_eq!(errors(&unbounded), 50);
+    assert!(!unbounded.was_aborted());
+
+    let bounded = typecheck_module_with_max_errors(&module, 10);
+    assert_eq!(errors(&bounded), 10);
+    assert!(bounded.was_aborted());
+    let note = bounded
+        .diagnostics()
+        .last()
+        .expect("an aborted check should end with a note");
+    assert_eq!(note.severity, aivi_base::Severity::Note);
+    assert_eq!(
+        note.message,
+        "type checking aborted after 10 errors; later items were not checked"
+    );
+
+    let exact = typecheck_module_with_max_errors(&module, 50);
+    assert_eq!(
+        exact.diagnostics(),
+        unbounded.diagnostics(),
+        "a budget spent by the last item leaves nothing unchecked"
+    );
+    assert!(!exact.was_aborted());
+
+    let roomy = typecheck_module_with_max_errors(&module, 100);
+    assert_eq!(roomy.diagnostics(), unbounded.diagnostics());
+    assert!(!roomy.was_aborted());
+
+    let unlimited = typecheck_module_with_max_errors(&module, 0);
+    assert_eq!(unlimited.diagnostics(), unbounded.diagnostics());
+    assert!(!unlimited.was_aborted());
+
+    let validation = module.validate_with_max_errors(ValidationMode::RequireResolvedNames, 10);
+    assert_eq!(
+        validation
+            .diagnostics()
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == aivi_base::Severity::Error)
+            .count(),
+        10
+    );
+    assert!(validation.diagnostics().contains(note));
+}
+
+#[test]
+fn typecheck_solves_deferred_constraints_when_the_last_item_spends_the_budget() {
+    let module = lowered_module_text(
+        "budget-on-last-item.aivi",
+        "value left = Map { \"id\": 1 }\n\
+         value right = Map { \"id\": 1 }\n\
+         value same:Bool = left == right\n\
+         value broken : Int = \"text\"\n",
+    );
+    let unbounded = typecheck_module(&module);
+    let bounded = typecheck_module_with_max_errors(&module, 1);
+    assert!(!bounded.was_aborted());
+    assert_eq!(bounded.diagnostics(), unbounded.diagnostics());
+    assert!(
+        bounded
+            .diagnostics()
+            .iter()
+            .any(|diagnostic| diagnostic.code == Some(crate::codes::MISSING_EQ_INSTANCE)),
+        "deferred Eq constraints should still be solved, got diagnostics: {:?}",
+        bounded.diagnostics()
+    );
+}
+
 #[test]
 fn typecheck_reports_missing_eq_for_map_equality() {
     let report = typecheck_text(
@@ -380,6 +466,101 @@ fn typecheck_reports_missing_instance_requirement_for_class_requirements() {
     );
 }
 
+#[test]
+fn instance_coverage_separates_covered_and_uncovered_types() {
+    let module = lowered_module_text(
+        "instance-coverage.aivi",
+        "class Show A = {\n\
+             \x20\x20\x20\x20show : A -> Text\n\
+             }\n\
+             type Shown = Shown Text\n\
+             type Hidden = Hidden Int\n\
+             instance Show Shown = {\n\
+             \x20\x20\x20\x20show value = \"shown\"\n\
+             }\n",
+    );
+    let coverage = instance_coverage([("app", &module)]);
+    let show = CoverageName::declared("app", "Show");
+    let shown = CoverageName::declared("app", "Shown");
+    let hidden = CoverageName::declared("app", "Hidden");
+
+    assert!(coverage.has_instance(&show, &shown));
+    assert!(!coverage.has_instance(&show, &hidden));
+    assert_eq!(coverage.uncovered_types(&show), vec![&hidden]);
+    assert_eq!(
+        coverage
+            .class(&show)
+            .map(|class| class.covered_types.as_slice()),
+        Some(&[shown][..])
+    );
+}
+
+#[test]
+fn instance_coverage_keeps_same_named_classes_of_different_modules_apart() {
+    struct SharedResolver;
+
+    impl ImportResolver for SharedResolver {
+        fn resolve(&self, path: &[&str]) -> ImportModuleResolution {
+            if path != ["shared"] {
+                return ImportModuleResolution::Missing;
+            }
+            ImportModuleResolution::Resolved(exports(&shared_module()))
+        }
+    }
+
+    fn shared_module() -> Module {
+        lowered_module_text(
+            "shared.aivi",
+            "class Show A = {\n\
+                 \x20\x20\x20\x20show : A -> Text\n\
+                 }\n\
+                 export (Show)\n",
+        )
+    }
+
+    let shared = shared_module();
+    let mut sources = SourceDatabase::new();
+    let file_id = sources.add_file(
+        "client.aivi",
+        "use shared (\n\
+             \x20\x20\x20\x20Show\n\
+             )\n\
+             type Client = Client Int\n\
+             instance Show Client = {\n\
+             \x20\x20\x20\x20show value = \"client\"\n\
+             }\n",
+    );
+    let parsed = parse_module(&sources[file_id]);
+    let lowered = lower_module_with_resolver(&parsed.module, Some(&SharedResolver));
+    assert!(
+        !lowered.has_errors(),
+        "client module should lower cleanly: {:?}",
+        lowered.diagnostics()
+    );
+    let client = lowered.module().clone();
+    let app = lowered_module_text(
+        "app.aivi",
+        "class Show A = {\n\
+             \x20\x20\x20\x20show : A -> Text\n\
+             }\n\
+             type Local = Local Int\n\
+             instance Show Local = {\n\
+             \x20\x20\x20\x20show value = \"local\"\n\
+             }\n",
+    );
+    let coverage = instance_coverage([("shared", &shared), ("client", &client), ("app", &app)]);
+    let shared_show = CoverageName::declared("shared", "Show");
+    let app_show = CoverageName::declared("app", "Show");
+    let client_type = CoverageName::declared("client", "Client");
+    let local_type = CoverageName::declared("app", "Local");
+
+    assert_eq!(coverage.classes().len(), 2);
+    assert!(coverage.has_instance(&shared_show, &client_type));
+    assert!(!coverage.has_instance(&shared_show, &local_type));
+    assert!(coverage.has_instance(&app_show, &local_type));
+    assert_eq!(coverage.uncovered_types(&app_show), vec![&client_type]);
+}
+
 #[test]
 fn typecheck_reports_instance_member_operator_operand_mismatch() {
     let report = typecheck_text(
@@ -575,6 +756,100 @@ fn typecheck_accepts_ord_comparison_for_nominal_domains() {
     );
 }
 
+#[test]
+fn typecheck_derives_ord_for_adts_without_explicit_instances() {
+    let report = typecheck_text(
+        "derived-ord-adt.aivi",
+        "type Priority = Low | Medium | High\n\
+             type Ticket = Ticket Priority Int\n\
+             value urgent:Bool = Low < High\n\
+             value ranked:Ordering = compare (Ticket Medium 2) (Ticket Medium 5)\n",
+    );
+    assert!(
+        report.is_ok(),
+        "expected derived Ord to satisfy ADT comparisons, got diagnostics: {:?}",
+        report.diagnostics()
+    );
+}
+
+#[test]
+fn typecheck_does_not_derive_ord_through_unordered_payloads() {
+    let report = typecheck_text(
+        "derived-ord-unordered-payload.aivi",
+        "type Batch = Batch (List Int)\n\
+             value broken:Bool = Batch [1] < Batch [2]\n",
+    );
+    assert!(
+        !report.is_ok(),
+        "expected a List payload to block derived Ord, got no diagnostics"
+    );
+}
+
+#[test]
+fn typecheck_prefers_explicit_ord_instances_over_derivation() {
+    let report = typecheck_text(
+        "explicit-ord-instance.aivi",
+        r#"type Batch = Batch (List Int)
+fun batchSize:Int = batch:Batch => batch
+ ||> Batch items -> 0
+instance Ord Batch = {
+    compare = left right => compare (batchSize left) (batchSize right)
+}
+value smaller:Bool = Batch [1] < Batch [2, 3]
+"#,
+    );
+    assert!(
+        report.is_ok(),
+        "expected an explicit Ord instance to satisfy direct comparisons, got diagnostics: {:?}",
+        report.diagnostics()
+    );
+}
+
+#[test]
+fn typecheck_does_not_derive_ord_through_payloads_with_explicit_instances() {
+    let report = typecheck_text(
+        "derived-ord-explicit-payload.aivi",
+        r#"type Batch = Batch (List Int)
+fun batchSize:Int = batch:Batch => batch
+ ||> Batch items -> 0
+instance Ord Batch = {
+    compare = left right => compare (batchSize left) (batchSize right)
+}
+type Queue = Queue Batch Int
+value queued:Ordering = compare (Queue (Batch [1]) 1) (Queue (Batch [2]) 0)
+"#,
+    );
+    assert!(
+        report
+            .diagnostics()
+            .iter()
+            .any(|diagnostic| diagnostic.message.contains("Queue")),
+        "a derived ordering cannot call the payload's own instance, so `Queue` needs an explicit \
+         one; got diagnostics: {:?}",
+        report.diagnostics()
+    );
+}
+
+#[test]
+fn typecheck_does_not_derive_ord_through_domain_payloads() {
+    let report = typecheck_text(
+        "derived-ord-domain-payload.aivi",
+        "domain Calendar over Int = {\n\
+             \x20\x20\x20\x20suffix day : Int = value => Calendar value\n\
+             }\n\
+             type Delay = Delay Calendar\n\
+             value broken:Ordering = compare (Delay 1day) (Delay 2day)\n",
+    );
+    assert!(
+        report
+            .diagnostics()
+            .iter()
+            .any(|diagnostic| diagnostic.message.contains("Delay")),
+        "expected a domain payload to block derived Ord, got diagnostics: {:?}",
+        report.diagnostics()
+    );
+}
+
 #[test]
 fn typecheck_accepts_ordering_operator_sections() {
     let report = typecheck_text(
@@ -618,6 +893,64 @@ fn typecheck_reports_value_annotation_mismatch() {
     );
 }
 
+#[test]
+fn typecheck_type_mismatch_notes_render_the_offending_expression() {
+    let report = typecheck_text(
+        "value-mismatch-expression.aivi",
+        "fun add:Int = left:Int right:Int => left + right\n\
+         value answer:Text = add 1 (add 2 3)\n",
+    );
+    let mismatch = report
+        .diagnostics()
+        .iter()
+        .find(|diagnostic| diagnostic.code == Some(crate::codes::TYPE_MISMATCH))
+        .unwrap_or_else(|| {
+            panic!(
+                "expected type mismatch diagnostic, got diagnostics: {:?}",
+                report.diagnostics()
+            )
+        });
+    assert!(
+        mismatch
+            .notes
+            .iter()
+            .any(|note| note == "in the expression `add 1 (add 2 3)`"),
+        "expected the mismatch to render its expression, got notes: {:?}",
+        mismatch.notes
+    );
+}
+
+#[test]
+fn typecheck_type_mismatch_notes_cut_long_expressions_off() {
+    let arguments = (1..=40)
+        .map(|argument| format!("(add {argument} {argument})"))
+        .collect::<Vec<_>>()
+        .join(" + ");
+    let report = typecheck_text(
+        "value-mismatch-long-expression.aivi",
+        &format!(
+            "fun add:Int = left:Int right:Int => left + right\n\
+             value answer:Text = {arguments}\n"
+        ),
+    );
+    let note = report
+        .diagnostics()
+        .iter()
+        .filter(|diagnostic| diagnostic.code == Some(crate::codes::TYPE_MISMATCH))
+        .flat_map(|diagnostic| diagnostic.notes.iter())
+        .find_map(|note| note.strip_prefix("in the expression `"))
+        .unwrap_or_else(|| {
+            panic!(
+                "expected a mismatch note with the expression, got diagnostics: {:?}",
+                report.diagnostics()
+            )
+        });
+    assert_eq!(
+        note,
+        "add 1 1 + add 2 2 + add 3 3 + add 4 4 + add 5 5 + add 6 6 +…`"
+    );
+}
+
 #[test]
 fn typecheck_accepts_signal_merge_arms_against_signal_payloads() {
     let report = typecheck_text(
@@ -2075,3 +2408,45 @@ fn typecheck_infers_signal_without_double_wrapping() {
         report.diagnostics()
     );
 }
+
+#[test]
+fn typecheck_restricts_structural_json_intrinsics_to_the_json_type() {
+    const JSON_TYPE: &str = "use aivi.data.json (parseInto, stringifyValue)\n\
+         type Json =\n\
+           | JsonNull\n\
+           | JsonBool Bool\n\
+           | JsonNumber Float\n\
+           | JsonString Text\n\
+           | JsonArray (List Json)\n\
+           | JsonObject { entries: List { key: Text, value: Json } }\n";
+
+    let report = typecheck_text(
+        "json-structural-ok.aivi",
+        &format!(
+            "{JSON_TYPE}value decoded:(Task Text Json) = parseInto JsonNull \"[1]\"\n\
+             value encoded:Text = stringifyValue (JsonBool True)\n"
+        ),
+    );
+    assert!(
+        report.is_ok(),
+        "Json values should flow through parseInto and stringifyValue: {:?}",
+        report.diagnostics()
+    );
+
+    for (path, body) in [
+        (
+            "json-structural-bad-witness.aivi",
+            "value decoded:(Task Text (Option Int)) = parseInto (Some 0) \"1\"\n",
+        ),
+        (
+            "json-structural-bad-value.aivi",
+            "value encoded:Text = stringifyValue 42\n",
+        ),
+    ] {
+        let report = typecheck_text(path, &format!("{JSON_TYPE}{body}"));
+        assert!(
+            !report.is_ok(),
+            "{path}: a non-Json argument should be rejected"
+        );
+    }
+}
@@ -340,6 +340,7 @@ impl<'a> GateTypeContext<'a> {
             }
             PatternKind::Integer(_)
             | PatternKind::Text(_)
+            | PatternKind::TextPrefix { .. }
             | PatternKind::Tuple(_)
             | PatternKind::List { .. }
             | PatternKind::Record(_) => CasePatternCoverage::None,
@@ -365,6 +366,9 @@ impl<'a> GateTypeContext<'a> {
                 PatternKind::Binding(binding) => {
                     env.locals.insert(binding.binding, subject_ty);
                 }
+                PatternKind::TextPrefix { rest, .. } => {
+                    work.push((rest, GateType::Primitive(BuiltinType::Text)));
+                }
                 PatternKind::Tuple(elements) => {
                     let GateType::Tuple(subject_elements) = &subject_ty else {
                         continue;
@@ -619,7 +623,8 @@ impl<'a> GateTypeContext<'a> {
                     | BuiltinType::Bool
                     | BuiltinType::Text
                     | BuiltinType::Unit
-                    | BuiltinType::Bytes),
+                    | BuiltinType::Bytes
+                    | BuiltinType::Char),
                 )) => Some(GateType::Primitive(*builtin)),
                 ResolutionState::Resolved(TypeResolution::Item(item_id)) => {
                     self.lower_type_item(*item_id, &[], item_stack, true)
@@ -1189,6 +1194,12 @@ impl<'a> GateTypeContext<'a> {
                     .map(|(id, _)| id);
                 if let Some(import) = import_id {
                     self.import_type_for_domain_or_opaque(import, type_name.clone(), lowered_args)
+                } else if let Some(item) = self.local_sum_type_item(type_name, lowered_args.len()) {
+                    GateType::OpaqueItem {
+                        item,
+                        name: type_name.clone(),
+                        arguments: lowered_args,
+                    }
                 } else {
                     // Fallback: create an opaque import with a sentinel; the type checker
                     // will treat this as an unknown opaque type.
@@ -1203,7 +1214,30 @@ impl<'a> GateTypeContext<'a> {
         }
     }
 
+    /// A sum type declared in this module, for import signatures that name a type the importing
+    /// module defines itself (e.g. `Json` inside `aivi.data.json`).
+    fn local_sum_type_item(&self, name: &str, arity: usize) -> Option<ItemId> {
+        self.module.root_items().iter().copied().find(|&id| {
+            matches!(
+                &self.module.items()[id],
+                Item::Type(item)
+                    if item.name.text() == name
+                        && item.parameters.len() == arity
+                        && matches!(item.body, crate::hir::TypeItemBody::Sum(_))
+            )
+        })
+    }
+
     pub(crate) fn intrinsic_value_type(&self, value: IntrinsicValue) -> GateType {
+        // Data-format intrinsics convert to and from the module's own `Json`/`Toml` sum type.
+        let data_type = |name: &str| {
+            self.lower_import_value_type(&ImportValueType::Named {
+                type_name: name.to_owned(),
+                arguments: Vec::new(),
+                definition: None,
+            })
+        };
+
         fn primitive(builtin: BuiltinType) -> GateType {
             GateType::Primitive(builtin)
         }
@@ -1265,6 +1299,82 @@ impl<'a> GateTypeContext<'a> {
             )
         }
 
+        fn file_handle_type() -> GateType {
+            record(vec![
+                ("id", primitive(BuiltinType::Int)),
+                ("path", primitive(BuiltinType::Text)),
+            ])
+        }
+
+        fn web_socket_type() -> GateType {
+            record(vec![
+                ("id", primitive(BuiltinType::Int)),
+                ("url", primitive(BuiltinType::Text)),
+            ])
+        }
+
+        fn text_pairs() -> GateType {
+            list(GateType::Tuple(vec![
+                primitive(BuiltinType::Text),
+                primitive(BuiltinType::Text),
+            ]))
+        }
+
+        fn http_request_type() -> GateType {
+            record(vec![
+                ("method", primitive(BuiltinType::Text)),
+                ("url", primitive(BuiltinType::Text)),
+                ("headers", text_pairs()),
+                ("body", option(primitive(BuiltinType::Text))),
+                ("timeoutMs", option(primitive(BuiltinType::Int))),
+                ("maxRedirects", option(primitive(BuiltinType::Int))),
+            ])
+        }
+
+        fn http_reply_type() -> GateType {
+            record(vec![
+                ("status", primitive(BuiltinType::Int)),
+                ("headers", text_pairs()),
+                ("body", primitive(BuiltinType::Text)),
+            ])
+        }
+
+        fn process_handle_type() -> GateType {
+            record(vec![
+                ("id", primitive(BuiltinType::Int)),
+                ("command", primitive(BuiltinType::Text)),
+            ])
+        }
+
+        fn graph_type() -> GateType {
+            record(vec![
+                ("nodes", list(primitive(BuiltinType::Int))),
+                (
+                    "edges",
+                    list(record(vec![
+                        ("from", primitive(BuiltinType::Int)),
+                        ("to", primitive(BuiltinType::Int)),
+                        ("weight", primitive(BuiltinType::Float)),
+                    ])),
+                ),
+            ])
+        }
+
+        fn template_problem_type() -> GateType {
+            record(vec![
+                ("missingKey", option(primitive(BuiltinType::Text))),
+                ("offset", primitive(BuiltinType::Int)),
+            ])
+        }
+
+        fn fft_bin_type() -> GateType {
+            record(vec![
+                ("magnitude", primitive(BuiltinType::Float)),
+                ("phase", primitive(BuiltinType::Float)),
+                ("frequencyBin", primitive(BuiltinType::Int)),
+            ])
+        }
+
         fn db_connection_type() -> GateType {
             record(vec![("database", primitive(BuiltinType::Text))])
         }
@@ -1456,6 +1566,31 @@ impl<'a> GateTypeContext<'a> {
                 primitive(BuiltinType::Text),
                 task(primitive(BuiltinType::Text), primitive(BuiltinType::Unit)),
             ),
+            IntrinsicValue::FsOpen => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    task(primitive(BuiltinType::Text), file_handle_type()),
+                ),
+            ),
+            IntrinsicValue::FsReadLine => arrow(
+                file_handle_type(),
+                task(
+                    primitive(BuiltinType::Text),
+                    option(primitive(BuiltinType::Text)),
+                ),
+            ),
+            IntrinsicValue::FsWrite => arrow(
+                file_handle_type(),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    task(primitive(BuiltinType::Text), primitive(BuiltinType::Unit)),
+                ),
+            ),
+            IntrinsicValue::FsClose => arrow(
+                file_handle_type(),
+                task(primitive(BuiltinType::Text), primitive(BuiltinType::Unit)),
+            ),
             IntrinsicValue::PathParent => arrow(
                 primitive(BuiltinType::Text),
                 GateType::Option(Box::new(primitive(BuiltinType::Text))),
@@ -1515,6 +1650,67 @@ impl<'a> GateTypeContext<'a> {
                 arrow(primitive(BuiltinType::Int), primitive(BuiltinType::Bytes)),
             ),
             IntrinsicValue::BytesEmpty => primitive(BuiltinType::Bytes),
+            IntrinsicValue::BytesFromList => arrow(
+                GateType::List(Box::new(primitive(BuiltinType::Int))),
+                primitive(BuiltinType::Bytes),
+            ),
+            IntrinsicValue::BytesToList => arrow(
+                primitive(BuiltinType::Bytes),
+                GateType::List(Box::new(primitive(BuiltinType::Int))),
+            ),
+            IntrinsicValue::BytesFromBase64 | IntrinsicValue::BytesFromHex => arrow(
+                primitive(BuiltinType::Text),
+                GateType::Option(Box::new(primitive(BuiltinType::Bytes))),
+            ),
+            IntrinsicValue::BytesToBase64 | IntrinsicValue::BytesToHex => {
+                arrow(primitive(BuiltinType::Bytes), primitive(BuiltinType::Text))
+            }
+            IntrinsicValue::CryptoHmacSha256 | IntrinsicValue::CryptoHmacSha512 => arrow(
+                primitive(BuiltinType::Bytes),
+                arrow(primitive(BuiltinType::Bytes), primitive(BuiltinType::Bytes)),
+            ),
+            IntrinsicValue::CryptoConstantTimeEquals => arrow(
+                primitive(BuiltinType::Bytes),
+                arrow(primitive(BuiltinType::Bytes), primitive(BuiltinType::Bool)),
+            ),
+            IntrinsicValue::CryptoEd25519Keypair => task(
+                primitive(BuiltinType::Text),
+                record(vec![
+                    ("publicKey", primitive(BuiltinType::Bytes)),
+                    ("privateKey", primitive(BuiltinType::Bytes)),
+                ]),
+            ),
+            IntrinsicValue::CryptoEd25519Sign => arrow(
+                primitive(BuiltinType::Bytes),
+                arrow(primitive(BuiltinType::Bytes), primitive(BuiltinType::Bytes)),
+            ),
+            IntrinsicValue::CryptoEd25519Verify => arrow(
+                primitive(BuiltinType::Bytes),
+                arrow(
+                    primitive(BuiltinType::Bytes),
+                    arrow(primitive(BuiltinType::Bytes), primitive(BuiltinType::Bool)),
+                ),
+            ),
+            IntrinsicValue::CharToInt => {
+                arrow(primitive(BuiltinType::Char), primitive(BuiltinType::Int))
+            }
+            IntrinsicValue::CharFromInt => arrow(
+                primitive(BuiltinType::Int),
+                option(primitive(BuiltinType::Char)),
+            ),
+            IntrinsicValue::CharIsDigit | IntrinsicValue::CharIsAlpha => {
+                arrow(primitive(BuiltinType::Char), primitive(BuiltinType::Bool))
+            }
+            IntrinsicValue::CharToUpper => {
+                arrow(primitive(BuiltinType::Char), primitive(BuiltinType::Char))
+            }
+            IntrinsicValue::FnArity => {
+                arrow(synthetic_type_parameter(0), primitive(BuiltinType::Int))
+            }
+            IntrinsicValue::FnCanApply | IntrinsicValue::FnIsFullyApplied => arrow(
+                synthetic_type_parameter(0),
+                arrow(primitive(BuiltinType::Int), primitive(BuiltinType::Bool)),
+            ),
             IntrinsicValue::JsonValidate => arrow(
                 primitive(BuiltinType::Text),
                 task(primitive(BuiltinType::Text), primitive(BuiltinType::Bool)),
@@ -1554,6 +1750,46 @@ impl<'a> GateTypeContext<'a> {
                 primitive(BuiltinType::Text),
                 task(primitive(BuiltinType::Text), primitive(BuiltinType::Text)),
             ),
+            IntrinsicValue::JsonParseInto => arrow(
+                data_type("Json"),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    task(primitive(BuiltinType::Text), data_type("Json")),
+                ),
+            ),
+            IntrinsicValue::JsonParseStreamInto => arrow(
+                data_type("Json"),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    task(primitive(BuiltinType::Text), list(data_type("Json"))),
+                ),
+            ),
+            IntrinsicValue::JsonStringifyValue => {
+                arrow(data_type("Json"), primitive(BuiltinType::Text))
+            }
+            IntrinsicValue::JsonEncode => {
+                arrow(synthetic_type_parameter(0), primitive(BuiltinType::Text))
+            }
+            IntrinsicValue::TomlParseInto => arrow(
+                data_type("Toml"),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    GateType::Result {
+                        error: Box::new(primitive(BuiltinType::Text)),
+                        value: Box::new(data_type("Toml")),
+                    },
+                ),
+            ),
+            IntrinsicValue::TomlStringifyValue => {
+                arrow(data_type("Toml"), primitive(BuiltinType::Text))
+            }
+            IntrinsicValue::TomlEncodeValue => arrow(
+                synthetic_type_parameter(0),
+                GateType::Result {
+                    error: Box::new(primitive(BuiltinType::Text)),
+                    value: Box::new(primitive(BuiltinType::Text)),
+                },
+            ),
             IntrinsicValue::XdgDataHome => primitive(BuiltinType::Text),
             IntrinsicValue::XdgConfigHome => primitive(BuiltinType::Text),
             IntrinsicValue::XdgCacheHome => primitive(BuiltinType::Text),
@@ -1564,7 +1800,9 @@ impl<'a> GateTypeContext<'a> {
             IntrinsicValue::XdgDataDirs => GateType::List(Box::new(primitive(BuiltinType::Text))),
             IntrinsicValue::XdgConfigDirs => GateType::List(Box::new(primitive(BuiltinType::Text))),
             // Text intrinsics
-            IntrinsicValue::TextLength | IntrinsicValue::TextByteLen => {
+            IntrinsicValue::TextLength
+            | IntrinsicValue::TextByteLen
+            | IntrinsicValue::TextGraphemeLength => {
                 arrow(primitive(BuiltinType::Text), primitive(BuiltinType::Int))
             }
             IntrinsicValue::TextSlice => arrow(
@@ -1589,6 +1827,8 @@ impl<'a> GateTypeContext<'a> {
             ),
             IntrinsicValue::TextToUpper
             | IntrinsicValue::TextToLower
+            | IntrinsicValue::TextCaseFold
+            | IntrinsicValue::TextReverse
             | IntrinsicValue::TextTrim
             | IntrinsicValue::TextTrimStart
             | IntrinsicValue::TextTrimEnd => {
@@ -1608,10 +1848,17 @@ impl<'a> GateTypeContext<'a> {
                     list(primitive(BuiltinType::Text)),
                 ),
             ),
-            IntrinsicValue::TextRepeat => arrow(
+            IntrinsicValue::TextRepeat | IntrinsicValue::TextTruncate => arrow(
                 primitive(BuiltinType::Int),
                 arrow(primitive(BuiltinType::Text), primitive(BuiltinType::Text)),
             ),
+            IntrinsicValue::TextWrap => arrow(
+                primitive(BuiltinType::Int),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    list(primitive(BuiltinType::Text)),
+                ),
+            ),
             IntrinsicValue::TextFromInt => {
                 arrow(primitive(BuiltinType::Int), primitive(BuiltinType::Text))
             }
@@ -1630,6 +1877,33 @@ impl<'a> GateTypeContext<'a> {
                 list(primitive(BuiltinType::Text)),
                 primitive(BuiltinType::Text),
             ),
+            IntrinsicValue::TextRenderTemplate => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    map(primitive(BuiltinType::Text), primitive(BuiltinType::Text)),
+                    GateType::Result {
+                        error: Box::new(template_problem_type()),
+                        value: Box::new(primitive(BuiltinType::Text)),
+                    },
+                ),
+            ),
+            IntrinsicValue::TextRenderLenient => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    map(primitive(BuiltinType::Text), primitive(BuiltinType::Text)),
+                    primitive(BuiltinType::Text),
+                ),
+            ),
+            IntrinsicValue::TextRenderPositional => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    list(primitive(BuiltinType::Text)),
+                    GateType::Result {
+                        error: Box::new(template_problem_type()),
+                        value: Box::new(primitive(BuiltinType::Text)),
+                    },
+                ),
+            ),
             // Float transcendental intrinsics
             IntrinsicValue::FloatSin
             | IntrinsicValue::FloatCos
@@ -1661,10 +1935,69 @@ impl<'a> GateTypeContext<'a> {
                     option(primitive(BuiltinType::Float)),
                 ),
             ),
+            // Math intrinsics
+            IntrinsicValue::MathFftMagnitude => arrow(
+                list(primitive(BuiltinType::Float)),
+                list(primitive(BuiltinType::Float)),
+            ),
+            IntrinsicValue::MathFftSpectrum => arrow(
+                list(primitive(BuiltinType::Float)),
+                list(fft_bin_type()),
+            ),
+            // Matrix intrinsics (row-major `List (List Float)`; `None` for singular input)
+            IntrinsicValue::MatrixLuSolve => arrow(
+                list(list(primitive(BuiltinType::Float))),
+                arrow(
+                    list(primitive(BuiltinType::Float)),
+                    option(list(primitive(BuiltinType::Float))),
+                ),
+            ),
+            IntrinsicValue::MatrixLuDeterminant => arrow(
+                list(list(primitive(BuiltinType::Float))),
+                primitive(BuiltinType::Float),
+            ),
+            IntrinsicValue::MatrixLuInverse => arrow(
+                list(list(primitive(BuiltinType::Float))),
+                option(list(list(primitive(BuiltinType::Float)))),
+            ),
+            IntrinsicValue::MatrixJacobiSvd => arrow(
+                list(list(primitive(BuiltinType::Float))),
+                record(vec![
+                    ("u", list(list(primitive(BuiltinType::Float)))),
+                    ("singularValues", list(primitive(BuiltinType::Float))),
+                    ("vt", list(list(primitive(BuiltinType::Float)))),
+                ]),
+            ),
+            IntrinsicValue::MatrixJacobiEigenvalues => arrow(
+                list(list(primitive(BuiltinType::Float))),
+                option(list(primitive(BuiltinType::Float))),
+            ),
+            // Graph intrinsics (directed `{ nodes, edges }` records with non-negative weights)
+            IntrinsicValue::GraphShortestPath => arrow(
+                graph_type(),
+                arrow(
+                    primitive(BuiltinType::Int),
+                    arrow(
+                        primitive(BuiltinType::Int),
+                        option(list(primitive(BuiltinType::Int))),
+                    ),
+                ),
+            ),
+            IntrinsicValue::GraphDistances => arrow(
+                graph_type(),
+                arrow(
+                    primitive(BuiltinType::Int),
+                    map(primitive(BuiltinType::Int), primitive(BuiltinType::Float)),
+                ),
+            ),
             // Time intrinsics
             IntrinsicValue::TimeNowMs | IntrinsicValue::TimeMonotonicMs => {
                 task(primitive(BuiltinType::Text), primitive(BuiltinType::Int))
             }
+            IntrinsicValue::TimeSleep => arrow(
+                primitive(BuiltinType::Int),
+                task(primitive(BuiltinType::Text), GateType::Primitive(BuiltinType::Unit)),
+            ),
             IntrinsicValue::TimeFormat => arrow(
                 primitive(BuiltinType::Int),
                 arrow(
@@ -1679,6 +2012,17 @@ impl<'a> GateTypeContext<'a> {
                     task(primitive(BuiltinType::Text), primitive(BuiltinType::Int)),
                 ),
             ),
+            // Clipboard intrinsics
+            IntrinsicValue::ClipboardRead => {
+                task(primitive(BuiltinType::Text), primitive(BuiltinType::Text))
+            }
+            IntrinsicValue::ClipboardWrite => arrow(
+                primitive(BuiltinType::Text),
+                task(
+                    primitive(BuiltinType::Text),
+                    GateType::Primitive(BuiltinType::Unit),
+                ),
+            ),
             // Env intrinsics
             IntrinsicValue::EnvGet => arrow(
                 primitive(BuiltinType::Text),
@@ -1697,6 +2041,55 @@ impl<'a> GateTypeContext<'a> {
                     ])),
                 ),
             ),
+            // Process intrinsics
+            IntrinsicValue::ProcessArgs => task(
+                primitive(BuiltinType::Text),
+                list(primitive(BuiltinType::Text)),
+            ),
+            IntrinsicValue::ProcessExit => arrow(
+                primitive(BuiltinType::Int),
+                task(primitive(BuiltinType::Text), primitive(BuiltinType::Unit)),
+            ),
+            // Test intrinsics
+            IntrinsicValue::TestMatchesSnapshot => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    task(primitive(BuiltinType::Text), primitive(BuiltinType::Bool)),
+                ),
+            ),
+            IntrinsicValue::TestAdvanceClock | IntrinsicValue::TestSetClock => arrow(
+                primitive(BuiltinType::Int),
+                task(primitive(BuiltinType::Text), GateType::Primitive(BuiltinType::Unit)),
+            ),
+            // Task combinators
+            IntrinsicValue::TaskAttempt => arrow(
+                task(primitive(BuiltinType::Text), synthetic_type_parameter(0)),
+                task(
+                    primitive(BuiltinType::Text),
+                    GateType::Result {
+                        error: Box::new(primitive(BuiltinType::Text)),
+                        value: Box::new(synthetic_type_parameter(0)),
+                    },
+                ),
+            ),
+            IntrinsicValue::TaskFinally => arrow(
+                task(primitive(BuiltinType::Text), synthetic_type_parameter(0)),
+                arrow(
+                    task(primitive(BuiltinType::Text), primitive(BuiltinType::Unit)),
+                    task(primitive(BuiltinType::Text), synthetic_type_parameter(0)),
+                ),
+            ),
+            IntrinsicValue::TaskParallel => arrow(
+                list(task(
+                    primitive(BuiltinType::Text),
+                    synthetic_type_parameter(0),
+                )),
+                task(
+                    primitive(BuiltinType::Text),
+                    list(synthetic_type_parameter(0)),
+                ),
+            ),
             // Log intrinsics
             IntrinsicValue::LogEmit => arrow(
                 primitive(BuiltinType::Text),
@@ -1868,6 +2261,32 @@ impl<'a> GateTypeContext<'a> {
                     ),
                 ),
             ),
+            IntrinsicValue::RegexCaptures => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    task(
+                        primitive(BuiltinType::Text),
+                        option(map(
+                            primitive(BuiltinType::Text),
+                            primitive(BuiltinType::Text),
+                        )),
+                    ),
+                ),
+            ),
+            IntrinsicValue::RegexCapturesAll => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    task(
+                        primitive(BuiltinType::Text),
+                        list(map(
+                            primitive(BuiltinType::Text),
+                            primitive(BuiltinType::Text),
+                        )),
+                    ),
+                ),
+            ),
             IntrinsicValue::RegexReplace | IntrinsicValue::RegexReplaceAll => arrow(
                 primitive(BuiltinType::Text),
                 arrow(
@@ -1907,16 +2326,88 @@ impl<'a> GateTypeContext<'a> {
                     task(primitive(BuiltinType::Text), primitive(BuiltinType::Text)),
                 ),
             ),
-            IntrinsicValue::HttpHead => arrow(
+            IntrinsicValue::HttpRequest => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    http_request_type(),
+                    task(primitive(BuiltinType::Text), http_reply_type()),
+                ),
+            ),
+            IntrinsicValue::HttpDownload => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    task(primitive(BuiltinType::Text), primitive(BuiltinType::Unit)),
+                ),
+            ),
+            IntrinsicValue::WsConnect => arrow(
                 primitive(BuiltinType::Text),
+                task(primitive(BuiltinType::Text), web_socket_type()),
+            ),
+            IntrinsicValue::WsSend => arrow(
+                web_socket_type(),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    task(primitive(BuiltinType::Text), primitive(BuiltinType::Unit)),
+                ),
+            ),
+            IntrinsicValue::WsRecv => arrow(
+                web_socket_type(),
                 task(
                     primitive(BuiltinType::Text),
-                    list(GateType::Tuple(vec![
-                        primitive(BuiltinType::Text),
+                    option(primitive(BuiltinType::Text)),
+                ),
+            ),
+            IntrinsicValue::WsRecvTimeout => arrow(
+                web_socket_type(),
+                arrow(
+                    primitive(BuiltinType::Int),
+                    task(
                         primitive(BuiltinType::Text),
-                    ])),
+                        option(option(primitive(BuiltinType::Text))),
+                    ),
+                ),
+            ),
+            IntrinsicValue::WsClose => arrow(
+                web_socket_type(),
+                task(primitive(BuiltinType::Text), primitive(BuiltinType::Unit)),
+            ),
+            IntrinsicValue::ProcessSpawn => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    list(primitive(BuiltinType::Text)),
+                    arrow(
+                        map(
+                            primitive(BuiltinType::Text),
+                            primitive(BuiltinType::Text),
+                        ),
+                        task(primitive(BuiltinType::Text), process_handle_type()),
+                    ),
                 ),
             ),
+            IntrinsicValue::ProcessWait => arrow(
+                process_handle_type(),
+                task(primitive(BuiltinType::Text), primitive(BuiltinType::Int)),
+            ),
+            IntrinsicValue::ProcessKill => arrow(
+                process_handle_type(),
+                task(primitive(BuiltinType::Text), primitive(BuiltinType::Unit)),
+            ),
+            IntrinsicValue::ProcessReadStdout => arrow(
+                process_handle_type(),
+                task(primitive(BuiltinType::Text), primitive(BuiltinType::Text)),
+            ),
+            IntrinsicValue::ProcessWriteStdin => arrow(
+                process_handle_type(),
+                arrow(
+                    primitive(BuiltinType::Text),
+                    task(primitive(BuiltinType::Text), primitive(BuiltinType::Unit)),
+                ),
+            ),
+            IntrinsicValue::HttpHead => arrow(
+                primitive(BuiltinType::Text),
+                task(primitive(BuiltinType::Text), text_pairs()),
+            ),
             IntrinsicValue::BigIntFromInt => {
                 arrow(primitive(BuiltinType::Int), primitive(BuiltinType::BigInt))
             }
@@ -1965,6 +2456,49 @@ impl<'a> GateTypeContext<'a> {
                     arrow(primitive(BuiltinType::BigInt), primitive(BuiltinType::Bool)),
                 )
             }
+            IntrinsicValue::DecimalFromInt => {
+                arrow(primitive(BuiltinType::Int), primitive(BuiltinType::Decimal))
+            }
+            IntrinsicValue::DecimalFromText => arrow(
+                primitive(BuiltinType::Text),
+                option(primitive(BuiltinType::Decimal)),
+            ),
+            IntrinsicValue::DecimalToText => arrow(
+                primitive(BuiltinType::Decimal),
+                primitive(BuiltinType::Text),
+            ),
+            IntrinsicValue::DecimalAdd
+            | IntrinsicValue::DecimalSub
+            | IntrinsicValue::DecimalMul => arrow(
+                primitive(BuiltinType::Decimal),
+                arrow(
+                    primitive(BuiltinType::Decimal),
+                    primitive(BuiltinType::Decimal),
+                ),
+            ),
+            IntrinsicValue::DecimalDivWithMode => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    primitive(BuiltinType::Int),
+                    arrow(
+                        primitive(BuiltinType::Decimal),
+                        arrow(
+                            primitive(BuiltinType::Decimal),
+                            primitive(BuiltinType::Decimal),
+                        ),
+                    ),
+                ),
+            ),
+            IntrinsicValue::DecimalRoundWithMode => arrow(
+                primitive(BuiltinType::Text),
+                arrow(
+                    primitive(BuiltinType::Int),
+                    arrow(
+                        primitive(BuiltinType::Decimal),
+                        primitive(BuiltinType::Decimal),
+                    ),
+                ),
+            ),
             IntrinsicValue::BitAnd
             | IntrinsicValue::BitOr
             | IntrinsicValue::BitXor
@@ -3198,7 +3732,8 @@ impl<'a> GateTypeContext<'a> {
                 | BuiltinType::Bool
                 | BuiltinType::Text
                 | BuiltinType::Unit
-                | BuiltinType::Bytes),
+                | BuiltinType::Bytes
+                | BuiltinType::Char),
             )) => Some(GateType::Primitive(*builtin)),
             ResolutionState::Resolved(TypeResolution::Builtin(_)) => None,
             ResolutionState::Resolved(TypeResolution::TypeParameter(parameter)) => {
@@ -3294,7 +3829,8 @@ impl<'a> GateTypeContext<'a> {
                 | BuiltinType::Bool
                 | BuiltinType::Text
                 | BuiltinType::Unit
-                | BuiltinType::Bytes,
+                | BuiltinType::Bytes
+                | BuiltinType::Char,
             ))
             | ResolutionState::Unresolved => None,
         }
@@ -3480,7 +4016,8 @@ impl<'a> GateTypeContext<'a> {
                 | BuiltinType::Bool
                 | BuiltinType::Text
                 | BuiltinType::Unit
-                | BuiltinType::Bytes),
+                | BuiltinType::Bytes
+                | BuiltinType::Char),
             )) => Some(GateType::Primitive(*builtin)),
             ResolutionState::Resolved(TypeResolution::Item(item_id)) => {
                 self.lower_type_item(*item_id, &[], item_stack, false)
@@ -3858,7 +4395,8 @@ impl<'a> GateTypeContext<'a> {
             | BuiltinType::Bool
             | BuiltinType::Text
             | BuiltinType::Unit
-            | BuiltinType::Bytes => Some(GateType::Primitive(builtin)),
+            | BuiltinType::Bytes
+            | BuiltinType::Char => Some(GateType::Primitive(builtin)),
             BuiltinType::List => Some(GateType::List(Box::new(arguments.first()?.clone()))),
             BuiltinType::Map => Some(GateType::Map {
                 key: Box::new(arguments.first()?.clone()),
@@ -3903,6 +4441,10 @@ impl<'a> GateTypeContext<'a> {
                 ty: Some(GateType::Primitive(BuiltinType::Decimal)),
                 ..GateExprInfo::default()
             },
+            ExprKind::Char(_) => GateExprInfo {
+                ty: Some(GateType::Primitive(BuiltinType::Char)),
+                ..GateExprInfo::default()
+            },
             ExprKind::BigInt(_) => GateExprInfo {
                 ty: Some(GateType::Primitive(BuiltinType::BigInt)),
                 ..GateExprInfo::default()
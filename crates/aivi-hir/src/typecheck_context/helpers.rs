@@ -7,7 +7,8 @@ pub(crate) fn builtin_type_arity(builtin: BuiltinType) -> usize {
         | BuiltinType::Bool
         | BuiltinType::Text
         | BuiltinType::Unit
-        | BuiltinType::Bytes => 0,
+        | BuiltinType::Bytes
+        | BuiltinType::Char => 0,
         BuiltinType::List | BuiltinType::Set | BuiltinType::Option | BuiltinType::Signal => 1,
         BuiltinType::Map | BuiltinType::Result | BuiltinType::Validation | BuiltinType::Task => 2,
     }
@@ -93,7 +93,8 @@ impl SourceOptionExpectedType {
                 | BuiltinType::Bool
                 | BuiltinType::Text
                 | BuiltinType::Unit
-                | BuiltinType::Bytes),
+                | BuiltinType::Bytes
+                | BuiltinType::Char),
             ) => Some(Self::Primitive(*builtin)),
             ResolvedSourceContractType::Builtin(_) => None,
             ResolvedSourceContractType::ContractParameter(parameter) => {
@@ -147,7 +148,8 @@ impl SourceOptionExpectedType {
                     | BuiltinType::Bool
                     | BuiltinType::Text
                     | BuiltinType::Unit
-                    | BuiltinType::Bytes),
+                    | BuiltinType::Bytes
+                    | BuiltinType::Char),
                 )) => Some(Self::Primitive(*builtin)),
                 ResolutionState::Resolved(TypeResolution::TypeParameter(parameter)) => {
                     substitutions.get(parameter).cloned()
@@ -21,6 +21,8 @@ pub struct TypedDeclarationInfo {
     pub name_span: SourceSpan,
     pub declared_type: Option<String>,
     pub inferred_type: Option<String>,
+    /// The inferred type before rendering, without any class constraints.
+    pub inferred_gate_type: Option<GateType>,
     pub annotation_matches_inferred: Option<bool>,
     pub has_explicit_constraints: bool,
 }
@@ -78,6 +80,7 @@ fn value_info(
         declared_type: declared.as_ref().map(ToString::to_string),
         inferred_type: inferred.as_ref().map(ToString::to_string),
         annotation_matches_inferred: type_match(declared.as_ref(), inferred.as_ref()),
+        inferred_gate_type: inferred,
         has_explicit_constraints: false,
     }
 }
@@ -102,6 +105,7 @@ fn function_info(
             declared.as_ref().map(|signature| &signature.comparable),
             inferred.as_ref().map(|signature| &signature.comparable),
         ),
+        inferred_gate_type: inferred.map(|signature| signature.comparable),
         has_explicit_constraints: !item.context.is_empty(),
     }
 }
@@ -125,6 +129,7 @@ fn signal_info(
         declared_type: declared.as_ref().map(ToString::to_string),
         inferred_type: inferred.as_ref().map(ToString::to_string),
         annotation_matches_inferred: type_match(declared.as_ref(), inferred.as_ref()),
+        inferred_gate_type: inferred,
         has_explicit_constraints: false,
     }
 }
@@ -74,6 +74,14 @@ pub fn validate_bindings(module: &Module, mode: ValidationMode) -> ValidationRep
 /// Validates the type system: kinds, instances, source contracts, expression
 /// types, constructor arity, and pipe semantics.
 pub fn validate_types(module: &Module, mode: ValidationMode) -> ValidationReport {
+    validate_types_with_max_errors(module, mode, 0)
+}
+
+fn validate_types_with_max_errors(
+    module: &Module,
+    mode: ValidationMode,
+    max_errors: usize,
+) -> ValidationReport {
     let mut v = Validator {
         module,
         mode,
@@ -84,16 +92,26 @@ pub fn validate_types(module: &Module, mode: ValidationMode) -> ValidationReport
     v.validate_type_kinds();
     v.validate_instance_items();
     v.validate_source_contract_types();
-    v.validate_expression_types();
+    v.validate_expression_types(max_errors);
     v.validate_constructor_arity();
     v.validate_pipe_semantics();
     ValidationReport::new(v.diagnostics)
 }
 
 pub fn validate_module(module: &Module, mode: ValidationMode) -> ValidationReport {
+    validate_module_with_max_errors(module, mode, 0)
+}
+
+/// Like [`validate_module`], but type checking stops once `max_errors` errors have been reported,
+/// as in [`crate::typecheck_module_with_max_errors`]. A budget of 0 means no limit.
+pub fn validate_module_with_max_errors(
+    module: &Module,
+    mode: ValidationMode,
+    max_errors: usize,
+) -> ValidationReport {
     let mut report = validate_structure(module, mode);
     report.extend(validate_bindings(module, mode));
-    report.extend(validate_types(module, mode));
+    report.extend(validate_types_with_max_errors(module, mode, max_errors));
     let mut v = Validator {
         module,
         mode,
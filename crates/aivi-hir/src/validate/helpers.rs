@@ -138,7 +138,8 @@ fn builtin_kind(builtin: BuiltinType) -> Kind {
         | BuiltinType::Bool
         | BuiltinType::Text
         | BuiltinType::Unit
-        | BuiltinType::Bytes => Kind::Type,
+        | BuiltinType::Bytes
+        | BuiltinType::Char => Kind::Type,
         BuiltinType::List | BuiltinType::Set | BuiltinType::Option | BuiltinType::Signal => {
             Kind::constructor(1)
         }
@@ -158,6 +159,7 @@ pub(crate) fn builtin_type_name(builtin: BuiltinType) -> &'static str {
         BuiltinType::Text => "Text",
         BuiltinType::Unit => "Unit",
         BuiltinType::Bytes => "Bytes",
+        BuiltinType::Char => "Char",
         BuiltinType::List => "List",
         BuiltinType::Map => "Map",
         BuiltinType::Set => "Set",
@@ -31,7 +31,7 @@ use crate::{
     },
     signal_metadata_elaboration::expr_signal_dependencies,
     source_contract_resolution::{SourceContractResolutionErrorKind, SourceContractTypeResolver},
-    typecheck::typecheck_module,
+    typecheck::typecheck_module_with_max_errors,
 };
 
 pub use crate::type_analysis::GateRecordField;
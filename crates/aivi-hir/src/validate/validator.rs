@@ -313,6 +313,15 @@ impl Validator<'_> {
             match &pattern.kind {
                 PatternKind::Wildcard | PatternKind::Integer(_) => {}
                 PatternKind::Text(text) => self.check_text_literal(pattern.span, text),
+                PatternKind::TextPrefix { prefix, rest } => {
+                    self.check_text_literal(pattern.span, prefix);
+                    self.require_pattern(
+                        pattern.span,
+                        "pattern",
+                        "text prefix rest pattern",
+                        *rest,
+                    );
+                }
                 PatternKind::Binding(binding) => {
                     self.check_name(&binding.name);
                     self.require_binding(pattern.span, "pattern", "binding", binding.binding);
@@ -378,6 +387,7 @@ impl Validator<'_> {
                 ExprKind::Name(reference) => self.check_term_reference(reference),
                 ExprKind::Integer(_)
                 | ExprKind::Float(_)
+                | ExprKind::Char(_)
                 | ExprKind::Decimal(_)
                 | ExprKind::BigInt(_) => {}
                 ExprKind::Regex(regex) => self.check_regex_literal(expr.span, regex),
@@ -2308,12 +2318,12 @@ impl Validator<'_> {
         }
     }
 
-    fn validate_expression_types(&mut self) {
+    fn validate_expression_types(&mut self, max_errors: usize) {
         if self.mode != ValidationMode::RequireResolvedNames {
             return;
         }
         self.diagnostics
-            .extend(typecheck_module(self.module).into_diagnostics());
+            .extend(typecheck_module_with_max_errors(self.module, max_errors).into_diagnostics());
     }
 
     /// Checks that every constructor call site (in both patterns and expressions) supplies
@@ -2531,6 +2541,7 @@ impl Validator<'_> {
         match &self.module.exprs()[expr_id].kind {
             ExprKind::Integer(_)
             | ExprKind::Float(_)
+            | ExprKind::Char(_)
             | ExprKind::Decimal(_)
             | ExprKind::BigInt(_)
             | ExprKind::Text(_)
@@ -3420,6 +3431,7 @@ impl Validator<'_> {
         match &self.module.exprs()[expr_id].kind {
             ExprKind::Integer(_)
             | ExprKind::Float(_)
+            | ExprKind::Char(_)
             | ExprKind::Decimal(_)
             | ExprKind::BigInt(_)
             | ExprKind::Text(_)
@@ -3808,7 +3820,8 @@ impl Validator<'_> {
                 | BuiltinType::Bool
                 | BuiltinType::Text
                 | BuiltinType::Unit
-                | BuiltinType::Bytes),
+                | BuiltinType::Bytes
+                | BuiltinType::Char),
             )) => Some(SourceOptionActualType::Primitive(*builtin)),
             ResolutionState::Resolved(TypeResolution::TypeParameter(parameter)) => Some(
                 substitutions
@@ -4068,7 +4081,8 @@ impl Validator<'_> {
                 | BuiltinType::Bool
                 | BuiltinType::Text
                 | BuiltinType::Unit
-                | BuiltinType::Bytes),
+                | BuiltinType::Bytes
+                | BuiltinType::Char),
             )) => Some(match actual {
                 SourceOptionActualType::Hole => true,
                 SourceOptionActualType::Primitive(actual_builtin) => actual_builtin == builtin,
@@ -4930,6 +4944,7 @@ impl Validator<'_> {
                         ExprKind::Name(_)
                         | ExprKind::Integer(_)
                         | ExprKind::Float(_)
+                        | ExprKind::Char(_)
                         | ExprKind::Decimal(_)
                         | ExprKind::BigInt(_)
                         | ExprKind::SuffixedInteger(_)
@@ -47,6 +47,7 @@ pub(crate) fn capture_free_bindings(
             | ExprKind::Decimal(_)
             | ExprKind::BigInt(_)
             | ExprKind::SuffixedInteger(_)
+            | ExprKind::Char(_)
             // NOTE: If this closure's body contains a reference to the item that owns it
             // (i.e., the item calls itself), that reference appears here as a captured
             // variable rather than a self-reference. This means truly self-recursive
@@ -194,6 +195,7 @@ fn extend_scope_with_pattern(scope: &mut BTreeSet<BindingId>, pattern: &core::Pa
             PatternKind::Binding(binding) => {
                 scope.insert(binding.binding);
             }
+            PatternKind::TextPrefix { rest, .. } => work.push(rest),
             PatternKind::Tuple(elements) => {
                 for element in elements.iter().rev() {
                     work.push(element);
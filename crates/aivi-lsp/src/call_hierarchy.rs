@@ -0,0 +1,200 @@
+use std::{collections::HashSet, sync::Arc};
+
+use aivi_base::{LspPosition, SourceSpan};
+use aivi_hir::ItemKind;
+use aivi_query::{RootDatabase, SourceFile};
+use tower_lsp::lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+    CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams, Range,
+    SymbolKind, Url,
+};
+
+use crate::{
+    navigation::{CallableDeclaration, NavigationAnalysis},
+    state::ServerState,
+};
+
+/// Resolve the symbol under the cursor to the functions, values, or signals it
+/// names, ready to be expanded with incoming/outgoing call requests.
+pub async fn prepare_call_hierarchy(
+    params: CallHierarchyPrepareParams,
+    state: Arc<ServerState>,
+) -> Option<Vec<CallHierarchyItem>> {
+    let uri = &params.text_document_position_params.text_document.uri;
+    let lsp_pos = params.text_document_position_params.position;
+
+    let file = *state.files.get(uri)?;
+    let navigation = NavigationAnalysis::load(&state.db, file);
+    let items = navigation
+        .callable_declarations_at_lsp_position(
+            &state.db,
+            LspPosition {
+                line: lsp_pos.line,
+                character: lsp_pos.character,
+            },
+        )
+        .into_iter()
+        .filter_map(|declaration| call_hierarchy_item(&state.db, &declaration))
+        .collect::<Vec<_>>();
+
+    if items.is_empty() { None } else { Some(items) }
+}
+
+/// Find every caller of the given item across the workspace.
+///
+/// Callers are searched in every module under the workspace roots of the
+/// callee and the open documents, whether or not the client opened them.
+/// Each caller appears once, with one `from_range` per reference inside its
+/// body.
+pub async fn incoming_calls(
+    params: CallHierarchyIncomingCallsParams,
+    state: Arc<ServerState>,
+) -> Option<Vec<CallHierarchyIncomingCall>> {
+    let callee = declaration_for_item(&state, &params.item)?;
+
+    let mut grouped: Vec<(CallableDeclaration, Vec<SourceSpan>)> = Vec::new();
+    for file in caller_search_files(&state, callee.target.file()) {
+        let navigation = NavigationAnalysis::load(&state.db, file);
+        for (caller, span) in navigation.incoming_call_sites(&state.db, &[callee.target]) {
+            push_grouped(&mut grouped, caller, span);
+        }
+    }
+
+    let calls = grouped
+        .into_iter()
+        .filter_map(|(caller, spans)| {
+            Some(CallHierarchyIncomingCall {
+                from_ranges: ranges_in_file(&state.db, caller.target.file(), &spans),
+                from: call_hierarchy_item(&state.db, &caller)?,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if calls.is_empty() { None } else { Some(calls) }
+}
+
+/// Find every callable referenced from the body of the given item, including
+/// callables declared in other modules.
+pub async fn outgoing_calls(
+    params: CallHierarchyOutgoingCallsParams,
+    state: Arc<ServerState>,
+) -> Option<Vec<CallHierarchyOutgoingCall>> {
+    let caller = declaration_for_item(&state, &params.item)?;
+    let navigation = NavigationAnalysis::load(&state.db, caller.target.file());
+
+    let mut grouped: Vec<(CallableDeclaration, Vec<SourceSpan>)> = Vec::new();
+    for (callee, span) in navigation.outgoing_call_sites(&state.db, &caller) {
+        push_grouped(&mut grouped, callee, span);
+    }
+
+    // `from_ranges` are relative to the caller, not the callee.
+    let calls = grouped
+        .into_iter()
+        .filter_map(|(callee, spans)| {
+            Some(CallHierarchyOutgoingCall {
+                from_ranges: ranges_in_file(&state.db, caller.target.file(), &spans),
+                to: call_hierarchy_item(&state.db, &callee)?,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if calls.is_empty() { None } else { Some(calls) }
+}
+
+fn declaration_for_item(
+    state: &ServerState,
+    item: &CallHierarchyItem,
+) -> Option<CallableDeclaration> {
+    // Callees may live in imported modules the client never opened.
+    let file = match state.files.get(&item.uri) {
+        Some(file) => *file,
+        None => state.db.file_at_path(&item.uri.to_file_path().ok()?)?,
+    };
+    let start = item.selection_range.start;
+    NavigationAnalysis::load(&state.db, file).callable_declaration_named_at(LspPosition {
+        line: start.line,
+        character: start.character,
+    })
+}
+
+/// The open documents plus every module of the workspaces they and `callee_file` belong to.
+fn caller_search_files(state: &ServerState, callee_file: SourceFile) -> Vec<SourceFile> {
+    let mut roots = HashSet::new();
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    let tracked = state
+        .files
+        .iter()
+        .map(|entry| *entry.value())
+        .collect::<Vec<_>>();
+    for file in tracked.into_iter().chain([callee_file]) {
+        if seen.insert(file) {
+            files.push(file);
+        }
+        if !roots.insert(aivi_query::discover_workspace_root(&file.path(&state.db))) {
+            continue;
+        }
+        for module in aivi_query::workspace_files(&state.db, file) {
+            if seen.insert(module) {
+                files.push(module);
+            }
+        }
+    }
+    files
+}
+
+fn push_grouped(
+    grouped: &mut Vec<(CallableDeclaration, Vec<SourceSpan>)>,
+    declaration: CallableDeclaration,
+    span: SourceSpan,
+) {
+    match grouped
+        .iter_mut()
+        .find(|(existing, _)| *existing == declaration)
+    {
+        Some((_, spans)) => {
+            if !spans.contains(&span) {
+                spans.push(span);
+            }
+        }
+        None => grouped.push((declaration, vec![span])),
+    }
+}
+
+fn call_hierarchy_item(
+    db: &RootDatabase,
+    declaration: &CallableDeclaration,
+) -> Option<CallHierarchyItem> {
+    let file = declaration.target.file();
+    let uri = Url::from_file_path(file.path(db)).ok()?;
+    Some(CallHierarchyItem {
+        name: declaration.name.to_string(),
+        kind: symbol_kind(declaration.kind),
+        tags: None,
+        detail: None,
+        uri,
+        range: range_in_file(db, file, declaration.span),
+        selection_range: range_in_file(db, file, declaration.target.span),
+        data: None,
+    })
+}
+
+fn ranges_in_file(db: &RootDatabase, file: SourceFile, spans: &[SourceSpan]) -> Vec<Range> {
+    spans
+        .iter()
+        .map(|span| range_in_file(db, file, *span))
+        .collect()
+}
+
+fn range_in_file(db: &RootDatabase, file: SourceFile, span: SourceSpan) -> Range {
+    let source = file.source(db);
+    crate::diagnostics::lsp_range(source.span_to_lsp_range(span.span()))
+}
+
+fn symbol_kind(kind: ItemKind) -> SymbolKind {
+    match kind {
+        ItemKind::Function => SymbolKind::FUNCTION,
+        ItemKind::Signal => SymbolKind::EVENT,
+        _ => SymbolKind::VARIABLE,
+    }
+}
@@ -9,7 +9,8 @@ use crate::{analysis::FileAnalysis, state::ServerState};
 
 /// Produce code actions for the requested range.
 ///
-/// Currently emits a "Remove unused symbol" quickfix for every
+/// Emits the type-annotation quickfixes, an "Add type signature" refactor for
+/// unannotated functions, and a "Remove unused symbol" quickfix for every
 /// `aivi/unused-symbol` diagnostic that overlaps the request range.
 pub fn code_actions(
     params: CodeActionParams,
@@ -32,6 +33,14 @@ pub fn code_actions(
         analysis.source.as_ref(),
         request_range,
     ));
+    actions.extend(
+        crate::type_annotations::build_add_type_signature_code_actions(
+            uri,
+            analysis.typed_declarations.as_ref(),
+            analysis.source.as_ref(),
+            request_range,
+        ),
+    );
 
     for diag in &unused_diags {
         if diag.code != Some(NumberOrString::String("aivi/unused-symbol".to_owned())) {
@@ -43,7 +43,7 @@ pub fn collect_code_lenses(
     lenses
 }
 
-fn item_has_test_decorator(module: &Module, item_id: ItemId) -> bool {
+pub(crate) fn item_has_test_decorator(module: &Module, item_id: ItemId) -> bool {
     let item = &module.items()[item_id];
     let decorator_ids = item.decorators();
     decorator_ids.iter().any(|&dec_id| {
@@ -54,7 +54,10 @@ fn item_has_test_decorator(module: &Module, item_id: ItemId) -> bool {
     })
 }
 
-fn item_name_and_span(module: &Module, item_id: ItemId) -> Option<(&str, aivi_base::SourceSpan)> {
+pub(crate) fn item_name_and_span(
+    module: &Module,
+    item_id: ItemId,
+) -> Option<(&str, aivi_base::SourceSpan)> {
     match &module.items()[item_id] {
         Item::Value(item) => Some((item.name.text(), item.name.span())),
         Item::Function(item) => Some((item.name.text(), item.name.span())),
@@ -1,7 +1,10 @@
+use std::collections::HashSet;
+
 use aivi_base::{Diagnostic, LabelStyle, LspRange, Severity};
+use aivi_syntax::{Token, TokenKind};
 use tower_lsp::lsp_types::{
-    self as lsp, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString,
-    Position, Range, Url,
+    self as lsp, DiagnosticRelatedInformation, DiagnosticSeverity, DiagnosticTag, Location,
+    NumberOrString, Position, Range, Url,
 };
 
 /// Convert an aivi_base::LspRange to a tower-lsp Range.
@@ -19,17 +22,24 @@ pub fn lsp_range(r: LspRange) -> Range {
 }
 
 /// Collect all diagnostics for a file and convert to LSP format.
+///
+/// Type checking stops after `max_errors` errors; 0 means no limit. `strict_mode` adds the warnings from [`aivi_hir::collect_strict_diagnostics`] and
+/// [`aivi_hir::collect_prelude_shadowing_diagnostics`].
 pub fn collect_lsp_diagnostics(
     db: &aivi_query::RootDatabase,
     file: aivi_query::SourceFile,
     uri: &Url,
+    strict_mode: bool,
+    max_errors: usize,
 ) -> Vec<lsp::Diagnostic> {
     let analysis = crate::analysis::FileAnalysis::load(db, file);
     let hir = aivi_query::hir_module(db, file);
+    let validation = aivi_query::validation_diagnostics(db, file, max_errors);
 
     let mut diagnostics: Vec<lsp::Diagnostic> = analysis
         .diagnostics
         .iter()
+        .chain(validation.iter())
         .map(|diagnostic| convert_diagnostic(diagnostic, analysis.source.as_ref(), db, uri))
         .collect();
 
@@ -45,15 +55,172 @@ pub fn collect_lsp_diagnostics(
     let has_errors = hir
         .diagnostics()
         .iter()
+        .chain(validation.iter())
         .any(|d| d.severity == aivi_base::Severity::Error);
     if !has_errors {
         diagnostics.extend(crate::unused::collect_unused_diagnostics(
             hir.module(),
             analysis.source.as_ref(),
         ));
+        if strict_mode {
+            diagnostics.extend(
+                aivi_hir::collect_strict_diagnostics(hir.module())
+                    .iter()
+                    .map(|diagnostic| {
+                        let mut converted =
+                            convert_diagnostic(diagnostic, analysis.source.as_ref(), db, uri);
+                        // A partial builtin is live code that needs attention, not dead code.
+                        if diagnostic.code != Some(aivi_hir::codes::PARTIAL_BUILTIN_USE) {
+                            converted.tags = Some(vec![DiagnosticTag::UNNECESSARY]);
+                        }
+                        converted
+                    }),
+            );
+            let prelude_names = aivi_query::prelude_term_names(db, file);
+            diagnostics.extend(
+                aivi_hir::collect_prelude_shadowing_diagnostics(hir.module(), &prelude_names)
+                    .iter()
+                    .map(|diagnostic| {
+                        convert_diagnostic(diagnostic, analysis.source.as_ref(), db, uri)
+                    }),
+            );
+        }
+    }
+
+    let parsed = aivi_query::parsed_file(db, file);
+    let suppressions =
+        parse_suppression_comments(analysis.source.as_ref(), parsed.parsed().lexed.tokens());
+    apply_suppressions(diagnostics, &suppressions)
+}
+
+/// One `// @suppress(code, ...)` comment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SuppressionComment {
+    /// Zero-based line whose diagnostics are silenced: the comment's own line for a trailing
+    /// comment, otherwise the line below it.
+    pub line: u32,
+    /// Range of the comment itself, used to report suppressions that silence nothing.
+    pub range: Range,
+    pub codes: HashSet<String>,
+}
+
+/// Find every `// @suppress(code, ...)` comment in a token stream.
+pub fn parse_suppression_comments(
+    source: &aivi_base::SourceFile,
+    tokens: &[Token],
+) -> Vec<SuppressionComment> {
+    let mut suppressions = Vec::new();
+    let mut last_code_line = None;
+    for token in tokens {
+        let range = lsp_range(source.span_to_lsp_range(token.span()));
+        if token.kind() != TokenKind::LineComment {
+            if !token.kind().is_trivia() {
+                last_code_line = Some(range.end.line);
+            }
+            continue;
+        }
+        let Some(codes) = suppressed_codes(token.text(source)) else {
+            continue;
+        };
+        let trailing = last_code_line == Some(range.start.line);
+        suppressions.push(SuppressionComment {
+            line: if trailing {
+                range.start.line
+            } else {
+                range.start.line + 1
+            },
+            range,
+            codes,
+        });
     }
+    suppressions
+}
+
+fn suppressed_codes(comment: &str) -> Option<HashSet<String>> {
+    let body = comment.strip_prefix("//")?.trim();
+    let arguments = body.strip_prefix("@suppress(")?.strip_suffix(')')?;
+    let codes = arguments
+        .split(',')
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+        .map(str::to_owned)
+        .collect::<HashSet<_>>();
+    (!codes.is_empty()).then_some(codes)
+}
+
+/// Drop warnings and hints silenced by a suppression comment and report suppressions that
+/// matched nothing. Errors are never suppressed.
+///
+/// A suppression code matches a diagnostic code exactly or by its final segment, so both
+/// `aivi/unused-symbol` and `unused-symbol` silence the unused-symbol hint.
+pub fn apply_suppressions(
+    diagnostics: Vec<lsp::Diagnostic>,
+    suppressions: &[SuppressionComment],
+) -> Vec<lsp::Diagnostic> {
+    if suppressions.is_empty() {
+        return diagnostics;
+    }
+    let mut used = vec![HashSet::new(); suppressions.len()];
+    let mut kept = diagnostics
+        .into_iter()
+        .filter(|diagnostic| {
+            if diagnostic.severity == Some(DiagnosticSeverity::ERROR) {
+                return true;
+            }
+            let Some(NumberOrString::String(code)) = &diagnostic.code else {
+                return true;
+            };
+            let mut suppressed = false;
+            for (index, suppression) in suppressions.iter().enumerate() {
+                if suppression.line != diagnostic.range.start.line {
+                    continue;
+                }
+                for candidate in &suppression.codes {
+                    if suppression_matches(candidate, code) {
+                        used[index].insert(candidate.clone());
+                        suppressed = true;
+                    }
+                }
+            }
+            !suppressed
+        })
+        .collect::<Vec<_>>();
+
+    for (suppression, used) in suppressions.iter().zip(used) {
+        let mut unused = suppression
+            .codes
+            .iter()
+            .filter(|code| !used.contains(*code))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        if unused.is_empty() {
+            continue;
+        }
+        unused.sort_unstable();
+        kept.push(lsp::Diagnostic {
+            range: suppression.range,
+            severity: Some(DiagnosticSeverity::WARNING),
+            code: Some(NumberOrString::String("aivi/unused-suppression".to_owned())),
+            code_description: None,
+            source: Some("aivi".to_owned()),
+            message: format!(
+                "`@suppress({})` does not suppress any diagnostic",
+                unused.join(", ")
+            ),
+            related_information: None,
+            tags: None,
+            data: None,
+        });
+    }
+    kept
+}
 
-    diagnostics
+fn suppression_matches(suppression: &str, code: &str) -> bool {
+    code == suppression
+        || code
+            .rsplit(['/', ':'])
+            .next()
+            .is_some_and(|name| name == suppression)
 }
 
 fn convert_diagnostic(
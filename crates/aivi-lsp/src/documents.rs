@@ -11,8 +11,10 @@ pub fn open_document(state: &ServerState, uri: &Url, text: String) {
     state.files.insert(uri.clone(), file);
 }
 
-/// Update an existing document's text.
+/// Update an existing document's text. Recorded test outcomes describe the old text and are
+/// dropped.
 pub fn change_document(state: &ServerState, uri: &Url, text: String) {
+    state.test_results.remove(uri);
     if let Some(file) = state.files.get(uri) {
         file.set_text(&state.db, text);
     } else {
@@ -22,6 +24,7 @@ pub fn change_document(state: &ServerState, uri: &Url, text: String) {
 
 /// Remove a document from tracking and from the database.
 pub fn close_document(state: &ServerState, uri: &Url) {
+    state.test_results.remove(uri);
     if let Some((_, file)) = state.files.remove(uri) {
         state.db.remove_file(file);
     }
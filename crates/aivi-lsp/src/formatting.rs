@@ -1,13 +1,14 @@
-use tower_lsp::lsp_types::TextEdit;
+use tower_lsp::lsp_types::{Range, TextEdit};
 
-/// Format a document and return LSP text edits.
+/// Format a document with its workspace's `[format]` options and return LSP text edits.
 pub fn format_document(
     db: &aivi_query::RootDatabase,
     file: aivi_query::SourceFile,
 ) -> Option<Vec<TextEdit>> {
     let parsed = aivi_query::parsed_file(db, file);
     let source = parsed.source_arc();
-    let formatted = aivi_query::format_file(db, file)?;
+    let options = aivi_query::load_format_options(db, file);
+    let formatted = aivi_query::format_file_with_options(db, file, &options)?;
 
     if formatted == source.text() {
         return Some(Vec::new());
@@ -19,6 +20,43 @@ pub fn format_document(
     }])
 }
 
+/// Format the top-level items touched by `range` and return LSP text edits.
+///
+/// The edit covers whole items even when `range` starts or ends inside one.
+pub fn format_range(
+    db: &aivi_query::RootDatabase,
+    file: aivi_query::SourceFile,
+    range: Range,
+) -> Option<Vec<TextEdit>> {
+    let parsed = aivi_query::parsed_file(db, file);
+    let source = parsed.source_arc();
+    // An exclusive end at column 0 does not select anything on that line.
+    let end_line = if range.end.character == 0 && range.end.line > range.start.line {
+        range.end.line - 1
+    } else {
+        range.end.line
+    };
+    let options = aivi_query::load_format_options(db, file);
+    let Some(formatted) = aivi_query::format_file_range(
+        db,
+        file,
+        range.start.line as usize,
+        end_line as usize,
+        &options,
+    ) else {
+        return parsed.diagnostics().is_empty().then(Vec::new);
+    };
+
+    if formatted.text == source.slice(formatted.span) {
+        return Some(Vec::new());
+    }
+
+    Some(vec![TextEdit {
+        range: crate::diagnostics::lsp_range(source.span_to_lsp_range(formatted.span)),
+        new_text: formatted.text,
+    }])
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -3,6 +3,7 @@
 //! AIVI Language Server Protocol server.
 
 pub mod analysis;
+pub mod call_hierarchy;
 pub mod code_actions;
 pub mod code_lens;
 pub mod completion;
@@ -20,7 +21,9 @@ pub mod semantic_tokens;
 pub mod server;
 pub mod state;
 pub mod symbols;
+pub mod test_diagnostics;
 pub mod type_annotations;
+pub mod type_definition;
 pub mod unused;
 
 /// Collect unused-symbol warnings as native [`aivi_base::Diagnostic`] items.
@@ -40,7 +43,12 @@ pub async fn run() -> anyhow::Result<()> {
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let (service, socket) = tower_lsp::LspService::new(server::Backend::new);
+    let (service, socket) = tower_lsp::LspService::build(server::Backend::new)
+        .custom_method(
+            test_diagnostics::TEST_RESULTS_METHOD,
+            server::Backend::test_results,
+        )
+        .finish();
 
     tower_lsp::Server::new(stdin, stdout, socket)
         .serve(service)
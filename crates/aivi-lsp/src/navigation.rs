@@ -2,11 +2,11 @@ use std::sync::Arc;
 
 use aivi_base::{ByteIndex, LspPosition, SourceSpan, Span};
 use aivi_hir::{
-    BinaryOperator, BindingId, BuiltinTerm, BuiltinType, ClassMemberResolution, DomainMemberKind,
-    DomainMemberResolution, ExportResolution, ExprKind, ImportBinding, ImportBindingMetadata,
-    ImportBindingResolution, ImportId, Item, ItemId, LiteralSuffixResolution, Module, NamePath,
-    PatternKind, ResolutionState, TermResolution, TypeItemBody, TypeKind, TypeParameterId,
-    TypeResolution,
+    BinaryOperator, BindingId, BuiltinTerm, BuiltinType, ClassMemberResolution, DecoratorPayload,
+    DomainMemberKind, DomainMemberResolution, ExportResolution, ExprKind, FunctionParameter,
+    GateType, ImportBinding, ImportBindingMetadata, ImportBindingResolution, ImportId, Item,
+    ItemId, ItemKind, LiteralSuffixResolution, Module, NamePath, PatternKind, ResolutionState,
+    TermResolution, TypeId, TypeItemBody, TypeKind, TypeParameterId, TypeResolution,
 };
 use aivi_query::{HirModuleResult, RootDatabase, SourceFile};
 use tower_lsp::lsp_types::{GotoDefinitionResponse, Location, Url};
@@ -54,6 +54,31 @@ impl NavigationTarget {
     }
 }
 
+/// A top-level function, value, or signal that can take part in a call hierarchy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CallableDeclaration {
+    pub(crate) target: NavigationTarget,
+    pub(crate) name: Box<str>,
+    pub(crate) kind: ItemKind,
+    pub(crate) span: SourceSpan,
+    pub(crate) deprecated: bool,
+}
+
+/// A name that declares or refers to a [`CallableDeclaration`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CallableNameSite {
+    pub(crate) span: SourceSpan,
+    pub(crate) declaration: CallableDeclaration,
+    /// `true` at the declaration's own name, `false` at a use site.
+    pub(crate) is_definition: bool,
+}
+
+impl CallableDeclaration {
+    fn encloses(&self, span: SourceSpan) -> bool {
+        self.span.file() == span.file() && self.span.span().contains(span.span().start())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum NavigationLookup {
     NoSite,
@@ -126,6 +151,36 @@ impl NavigationAnalysis {
         NavigationLookup::from_targets(self.implementation_targets_for_site(db, &site))
     }
 
+    /// Resolve the symbol under the cursor, then jump to the declaration of its
+    /// type rather than to the symbol itself. Type references resolve to the
+    /// type they name.
+    pub fn type_definition_targets_at_lsp_position(
+        &self,
+        db: &RootDatabase,
+        position: LspPosition,
+    ) -> NavigationLookup {
+        let Some(cursor) = self.source.lsp_position_to_offset(position) else {
+            return NavigationLookup::NoSite;
+        };
+        let Some(site) = self.semantic_site_at_offset(cursor) else {
+            return NavigationLookup::NoSite;
+        };
+        let mut targets = Vec::new();
+        for declaration in self.definition_targets_for_site(db, &site) {
+            let declared_in = if declaration.file == self.file {
+                None
+            } else {
+                Some(Self::load(db, declaration.file))
+            };
+            let analysis = declared_in.as_ref().unwrap_or(self);
+            push_targets(
+                &mut targets,
+                analysis.type_definition_targets_for_declaration(db, declaration.span),
+            );
+        }
+        NavigationLookup::from_targets(targets)
+    }
+
     /// Return all `Location`s in this file that refer to any of the given
     /// definition `targets`.  This powers find-all-references and rename.
     pub fn all_reference_locations_for_targets(
@@ -146,6 +201,170 @@ impl NavigationAnalysis {
         locations
     }
 
+    /// Resolve the symbol under the cursor to the callable declarations it names.
+    pub(crate) fn callable_declarations_at_lsp_position(
+        &self,
+        db: &RootDatabase,
+        position: LspPosition,
+    ) -> Vec<CallableDeclaration> {
+        let NavigationLookup::Targets(targets) =
+            self.preferred_definition_targets_at_lsp_position(db, position)
+        else {
+            return Vec::new();
+        };
+        let mut declarations = Vec::new();
+        for target in targets {
+            if let Some(declaration) = callable_declaration_for_target(db, target)
+                && !declarations.contains(&declaration)
+            {
+                declarations.push(declaration);
+            }
+        }
+        declarations
+    }
+
+    /// Find the callable declaration whose name contains `position`.  Call
+    /// hierarchy items round-trip through their selection range, so this is
+    /// how incoming/outgoing requests recover the declaration they started from.
+    pub(crate) fn callable_declaration_named_at(
+        &self,
+        position: LspPosition,
+    ) -> Option<CallableDeclaration> {
+        let cursor = self.source.lsp_position_to_offset(position)?;
+        self.module()
+            .root_items()
+            .iter()
+            .copied()
+            .filter_map(|item| self.callable_declaration(item))
+            .find(|declaration| {
+                let name = declaration.target.span.span();
+                name.contains(cursor) || name.end() == cursor
+            })
+    }
+
+    /// Return every term reference in this module that resolves to one of
+    /// `targets`, paired with the declaration whose body contains it.
+    pub(crate) fn incoming_call_sites(
+        &self,
+        db: &RootDatabase,
+        targets: &[NavigationTarget],
+    ) -> Vec<(CallableDeclaration, SourceSpan)> {
+        let mut calls = Vec::new();
+        for (span, site) in self.collect_all_sites() {
+            if !matches!(site, NavigationSite::TermReference { .. }) {
+                continue;
+            }
+            let site_targets = self.definition_targets_for_site(db, &site);
+            if !site_targets.iter().any(|target| targets.contains(target)) {
+                continue;
+            }
+            if let Some(caller) = self.enclosing_callable_declaration(span) {
+                calls.push((caller, span));
+            }
+        }
+        calls
+    }
+
+    /// Return every callable referenced from the body of `caller`, paired with
+    /// the span of the reference.  Functions passed to higher-order helpers
+    /// count as calls, matching how pipelines and `map f xs` read in source.
+    pub(crate) fn outgoing_call_sites(
+        &self,
+        db: &RootDatabase,
+        caller: &CallableDeclaration,
+    ) -> Vec<(CallableDeclaration, SourceSpan)> {
+        let mut calls = Vec::new();
+        for (span, site) in self.collect_all_sites() {
+            if !matches!(site, NavigationSite::TermReference { .. }) || !caller.encloses(span) {
+                continue;
+            }
+            for target in self.definition_targets_for_site(db, &site) {
+                if let Some(callee) = callable_declaration_for_target(db, target) {
+                    calls.push((callee, span));
+                }
+            }
+        }
+        calls
+    }
+
+    /// Return every declaration name and term reference in this module that
+    /// names a top-level function, value, or signal, including ones declared in
+    /// other modules.
+    pub(crate) fn callable_name_sites(&self, db: &RootDatabase) -> Vec<CallableNameSite> {
+        let mut names = Vec::new();
+        for (span, site) in self.collect_all_sites() {
+            match site {
+                NavigationSite::ItemDecl { item } => {
+                    if let Some(declaration) = self.callable_declaration(item)
+                        && declaration.target.span == span
+                    {
+                        names.push(CallableNameSite {
+                            span,
+                            declaration,
+                            is_definition: true,
+                        });
+                    }
+                }
+                NavigationSite::TermReference { .. } => {
+                    if let Some(declaration) = self
+                        .definition_targets_for_site(db, &site)
+                        .into_iter()
+                        .find_map(|target| callable_declaration_for_target(db, target))
+                    {
+                        names.push(CallableNameSite {
+                            span,
+                            declaration,
+                            is_definition: false,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        names
+    }
+
+    fn enclosing_callable_declaration(&self, span: SourceSpan) -> Option<CallableDeclaration> {
+        self.module()
+            .root_items()
+            .iter()
+            .copied()
+            .filter_map(|item| self.callable_declaration(item))
+            .find(|declaration| declaration.encloses(span))
+    }
+
+    fn callable_declaration(&self, item_id: ItemId) -> Option<CallableDeclaration> {
+        let item = self.module().items().get(item_id)?;
+        let name = match item {
+            Item::Value(item) => &item.name,
+            Item::Function(item) => &item.name,
+            Item::Signal(item) => &item.name,
+            Item::Type(_)
+            | Item::Class(_)
+            | Item::Domain(_)
+            | Item::SourceProviderContract(_)
+            | Item::Instance(_)
+            | Item::Use(_)
+            | Item::Export(_)
+            | Item::Hoist(_) => return None,
+        };
+        let deprecated = item.decorators().iter().any(|decorator| {
+            self.module()
+                .decorators()
+                .get(*decorator)
+                .is_some_and(|decorator| {
+                    matches!(decorator.payload, DecoratorPayload::Deprecated(_))
+                })
+        });
+        Some(CallableDeclaration {
+            target: NavigationTarget::new(self.file, name.span()),
+            name: name.text().into(),
+            kind: item.kind(),
+            span: item.span(),
+            deprecated,
+        })
+    }
+
     /// Collect every navigable (span, site) pair in this module without
     /// cursor filtering.  Mirrors the traversal in `semantic_site_at_offset`
     /// but accumulates all entries instead of selecting the tightest one.
@@ -312,6 +531,7 @@ impl NavigationAnalysis {
                 | PatternKind::Binding(_)
                 | PatternKind::Integer(_)
                 | PatternKind::Text(_)
+                | PatternKind::TextPrefix { .. }
                 | PatternKind::Tuple(_)
                 | PatternKind::List { .. }
                 | PatternKind::Record(_) => {}
@@ -356,6 +576,7 @@ impl NavigationAnalysis {
                 | ExprKind::Decimal(_)
                 | ExprKind::BigInt(_)
                 | ExprKind::Text(_)
+                | ExprKind::Char(_)
                 | ExprKind::Regex(_)
                 | ExprKind::Tuple(_)
                 | ExprKind::List(_)
@@ -581,6 +802,7 @@ impl NavigationAnalysis {
                 | PatternKind::Binding(_)
                 | PatternKind::Integer(_)
                 | PatternKind::Text(_)
+                | PatternKind::TextPrefix { .. }
                 | PatternKind::Tuple(_)
                 | PatternKind::List { .. }
                 | PatternKind::Record(_) => {}
@@ -627,6 +849,7 @@ impl NavigationAnalysis {
                 | ExprKind::Decimal(_)
                 | ExprKind::BigInt(_)
                 | ExprKind::Text(_)
+                | ExprKind::Char(_)
                 | ExprKind::Regex(_)
                 | ExprKind::Tuple(_)
                 | ExprKind::List(_)
@@ -1020,6 +1243,169 @@ impl NavigationAnalysis {
         }
     }
 
+    fn type_definition_targets_for_declaration(
+        &self,
+        db: &RootDatabase,
+        span: SourceSpan,
+    ) -> Vec<NavigationTarget> {
+        let module = self.module();
+        for item_id in module.root_items().iter().copied() {
+            let annotation = match &module.items()[item_id] {
+                Item::Type(item) => {
+                    let names_type = item.name.span() == span
+                        || matches!(&item.body, TypeItemBody::Sum(variants)
+                            if variants.iter().any(|variant| variant.name.span() == span));
+                    if names_type {
+                        return self.item_targets(item_id, None);
+                    }
+                    continue;
+                }
+                Item::Class(item) if item.name.span() == span => {
+                    return self.item_targets(item_id, None);
+                }
+                Item::Domain(item) if item.name.span() == span => {
+                    return self.item_targets(item_id, None);
+                }
+                Item::Value(item) if item.name.span() == span => item.annotation,
+                // Function annotations only cover the result type.
+                Item::Function(item) if item.name.span() == span => item.annotation,
+                Item::Signal(item) if item.name.span() == span => item.annotation,
+                Item::Function(item) => match self.parameter_annotation(&item.parameters, span) {
+                    Some(annotation) => Some(annotation),
+                    None => continue,
+                },
+                Item::Class(_)
+                | Item::Domain(_)
+                | Item::Value(_)
+                | Item::Signal(_)
+                | Item::SourceProviderContract(_)
+                | Item::Instance(_)
+                | Item::Use(_)
+                | Item::Export(_)
+                | Item::Hoist(_) => continue,
+            };
+            return match annotation {
+                Some(annotation) => self.type_definition_targets_for_type(db, annotation),
+                None => self.inferred_type_definition_targets(db, span),
+            };
+        }
+
+        for (_, expr) in module.exprs().iter() {
+            if let ExprKind::Lambda(lambda) = &expr.kind
+                && let Some(annotation) = self.parameter_annotation(&lambda.parameters, span)
+            {
+                return self.type_definition_targets_for_type(db, annotation);
+            }
+        }
+        Vec::new()
+    }
+
+    fn parameter_annotation(
+        &self,
+        parameters: &[FunctionParameter],
+        span: SourceSpan,
+    ) -> Option<TypeId> {
+        parameters.iter().find_map(|parameter| {
+            let binding = self.module().bindings().get(parameter.binding)?;
+            (binding.name.span() == span)
+                .then_some(parameter.annotation)
+                .flatten()
+        })
+    }
+
+    /// Follow a type annotation to the first user-declared constructor it
+    /// mentions: the head of an application, then its arguments, and the result
+    /// of a function type. Builtin types such as `List` or `Signal` are skipped
+    /// so `Signal (Option User)` still reaches `User`.
+    fn type_definition_targets_for_type(
+        &self,
+        db: &RootDatabase,
+        ty: TypeId,
+    ) -> Vec<NavigationTarget> {
+        let Some(node) = self.module().types().get(ty) else {
+            return Vec::new();
+        };
+        match &node.kind {
+            TypeKind::Name(reference) => match &reference.resolution {
+                ResolutionState::Unresolved
+                | ResolutionState::Resolved(TypeResolution::Builtin(_)) => Vec::new(),
+                resolution => self.definition_targets_for_type_reference(
+                    db,
+                    reference.path.segments().last().text(),
+                    resolution,
+                ),
+            },
+            TypeKind::Apply { callee, arguments } => std::iter::once(*callee)
+                .chain(arguments.iter().copied())
+                .map(|ty| self.type_definition_targets_for_type(db, ty))
+                .find(|targets| !targets.is_empty())
+                .unwrap_or_default(),
+            TypeKind::Tuple(elements) => elements
+                .iter()
+                .map(|ty| self.type_definition_targets_for_type(db, *ty))
+                .find(|targets| !targets.is_empty())
+                .unwrap_or_default(),
+            TypeKind::Arrow { result, .. } => self.type_definition_targets_for_type(db, *result),
+            TypeKind::RecordTransform { source, .. } => {
+                self.type_definition_targets_for_type(db, *source)
+            }
+            TypeKind::Record(_) => Vec::new(),
+        }
+    }
+
+    /// Unannotated declarations fall back to their inferred type.
+    fn inferred_type_definition_targets(
+        &self,
+        db: &RootDatabase,
+        span: SourceSpan,
+    ) -> Vec<NavigationTarget> {
+        aivi_hir::collect_typed_declarations(self.module())
+            .into_iter()
+            .find(|declaration| declaration.name_span == span)
+            .and_then(|declaration| declaration.inferred_gate_type)
+            .map(|inferred| self.type_definition_targets_for_gate_type(db, &inferred))
+            .unwrap_or_default()
+    }
+
+    /// The inferred-type counterpart of [`Self::type_definition_targets_for_type`], walking the
+    /// same positions in the same order.
+    fn type_definition_targets_for_gate_type(
+        &self,
+        db: &RootDatabase,
+        ty: &GateType,
+    ) -> Vec<NavigationTarget> {
+        let first = |types: &mut dyn Iterator<Item = &GateType>| {
+            types
+                .map(|ty| self.type_definition_targets_for_gate_type(db, ty))
+                .find(|targets| !targets.is_empty())
+                .unwrap_or_default()
+        };
+        match ty {
+            GateType::Primitive(_) | GateType::Record(_) => Vec::new(),
+            GateType::TypeParameter { parameter, .. } => self.type_parameter_targets(*parameter),
+            GateType::Domain { item, name, .. } | GateType::OpaqueItem { item, name, .. } => {
+                self.item_targets(*item, Some(name))
+            }
+            GateType::OpaqueImport { import, .. } => {
+                self.import_definition_targets_for_import_id(db, *import)
+            }
+            GateType::Tuple(elements) => first(&mut elements.iter()),
+            GateType::Arrow { result, .. } => {
+                self.type_definition_targets_for_gate_type(db, result)
+            }
+            GateType::List(element)
+            | GateType::Set(element)
+            | GateType::Option(element)
+            | GateType::Signal(element) => self.type_definition_targets_for_gate_type(db, element),
+            GateType::Map { key, value } => first(&mut [key, value].into_iter().map(Box::as_ref)),
+            GateType::Result { error, value }
+            | GateType::Validation { error, value }
+            | GateType::Task { error, value } => {
+                first(&mut [error, value].into_iter().map(Box::as_ref))
+            }
+        }
+    }
+
     fn binding_targets(&self, binding: BindingId) -> Vec<NavigationTarget> {
         self.module()
             .bindings()
@@ -1635,6 +2021,20 @@ pub fn goto_response(
     }
 }
 
+fn callable_declaration_for_target(
+    db: &RootDatabase,
+    target: NavigationTarget,
+) -> Option<CallableDeclaration> {
+    let analysis = NavigationAnalysis::load(db, target.file);
+    analysis
+        .module()
+        .root_items()
+        .iter()
+        .copied()
+        .filter_map(|item| analysis.callable_declaration(item))
+        .find(|declaration| declaration.target == target)
+}
+
 fn location_for_target(db: &RootDatabase, target: NavigationTarget) -> Option<Location> {
     let uri = Url::from_file_path(target.file.path(db)).ok()?;
     let source = target.file.source(db);
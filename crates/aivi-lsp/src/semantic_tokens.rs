@@ -1,11 +1,18 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
+use aivi_base::Span;
+use aivi_hir::{ItemKind, TypedDeclarationKind};
 use aivi_syntax::{TokenKind, lex_module};
 use tower_lsp::lsp_types::{
-    SemanticToken, SemanticTokenType, SemanticTokens, SemanticTokensParams, SemanticTokensResult,
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensParams,
+    SemanticTokensResult,
 };
 
-use crate::state::ServerState;
+use crate::{
+    analysis::FileAnalysis,
+    navigation::{CallableDeclaration, NavigationAnalysis},
+    state::ServerState,
+};
 
 /// Ordered list of token type names used in the legend.  The index in this
 /// array is the `token_type` field emitted for each `SemanticToken`.
@@ -20,11 +27,27 @@ pub const TOKEN_TYPES: &[SemanticTokenType] = &[
     SemanticTokenType::COMMENT,
 ];
 
+/// Ordered list of token modifier names used in the legend.  Bit `i` of the
+/// `token_modifiers_bitset` field selects the modifier at index `i`.
+pub const TOKEN_MODIFIERS: &[SemanticTokenModifier] = &[
+    SemanticTokenModifier::READONLY,
+    SemanticTokenModifier::DEPRECATED,
+    SemanticTokenModifier::DEFINITION,
+    SemanticTokenModifier::new("reference"),
+];
+
+const IDX_FUNCTION: u32 = 1;
+const IDX_VARIABLE: u32 = 2;
 const IDX_KEYWORD: u32 = 3;
 const IDX_STRING: u32 = 4;
 const IDX_NUMBER: u32 = 5;
 const IDX_COMMENT: u32 = 7;
 
+const MOD_READONLY: u32 = 1 << 0;
+const MOD_DEPRECATED: u32 = 1 << 1;
+const MOD_DEFINITION: u32 = 1 << 2;
+const MOD_REFERENCE: u32 = 1 << 3;
+
 fn token_type_index(kind: TokenKind) -> Option<u32> {
     match kind {
         // Keywords
@@ -48,7 +71,9 @@ fn token_type_index(kind: TokenKind) -> Option<u32> {
         TokenKind::Identifier => None,
 
         // Literals
-        TokenKind::StringLiteral | TokenKind::RegexLiteral => Some(IDX_STRING),
+        TokenKind::StringLiteral | TokenKind::CharLiteral | TokenKind::RegexLiteral => {
+            Some(IDX_STRING)
+        }
         TokenKind::Integer | TokenKind::Float | TokenKind::Decimal | TokenKind::BigInt => {
             Some(IDX_NUMBER)
         }
@@ -122,8 +147,9 @@ pub async fn semantic_tokens_full(
 ) -> Option<SemanticTokensResult> {
     let uri = &params.text_document.uri;
     let file = *state.files.get(uri)?;
-    let analysis = crate::analysis::FileAnalysis::load(&state.db, file);
+    let analysis = FileAnalysis::load(&state.db, file);
     let source = analysis.source.as_ref();
+    let callable_names = callable_name_tokens(&state.db, file, &analysis);
 
     let lexed = lex_module(source);
     let mut result: Vec<SemanticToken> = Vec::new();
@@ -131,8 +157,11 @@ pub async fn semantic_tokens_full(
     let mut prev_char: u32 = 0;
 
     for (index, token) in lexed.tokens().iter().copied().enumerate() {
-        let Some(type_index) = soft_or_hard_token_type_index(token, lexed.tokens(), index, source)
-        else {
+        let classified = callable_names.get(&token.span()).copied().or_else(|| {
+            soft_or_hard_token_type_index(token, lexed.tokens(), index, source)
+                .map(|type_index| (type_index, 0))
+        });
+        let Some((type_index, modifiers)) = classified else {
             continue;
         };
 
@@ -162,7 +191,7 @@ pub async fn semantic_tokens_full(
             delta_start,
             length: token_len,
             token_type: type_index,
-            token_modifiers_bitset: 0,
+            token_modifiers_bitset: modifiers,
         });
 
         prev_line = token_line;
@@ -175,6 +204,72 @@ pub async fn semantic_tokens_full(
     }))
 }
 
+/// Token type and modifier bits for every name that declares or refers to a
+/// top-level function, value, or signal, keyed by the name's span.
+fn callable_name_tokens(
+    db: &aivi_query::RootDatabase,
+    file: aivi_query::SourceFile,
+    analysis: &FileAnalysis,
+) -> HashMap<Span, (u32, u32)> {
+    let navigation = NavigationAnalysis::load(db, file);
+    // Declarations imported from other modules need their own file's types.
+    let mut imported_analyses: HashMap<aivi_query::SourceFile, FileAnalysis> = HashMap::new();
+    let mut tokens = HashMap::new();
+    for site in navigation.callable_name_sites(db) {
+        let declaration = &site.declaration;
+        let declaring_file = declaration.target.file();
+        let declaring_analysis = if declaring_file == file {
+            analysis
+        } else {
+            imported_analyses
+                .entry(declaring_file)
+                .or_insert_with(|| FileAnalysis::load(db, declaring_file))
+        };
+
+        let type_index = match declaration.kind {
+            ItemKind::Function => IDX_FUNCTION,
+            _ => IDX_VARIABLE,
+        };
+        let mut modifiers = if site.is_definition {
+            MOD_DEFINITION
+        } else {
+            MOD_REFERENCE
+        };
+        if declaration.deprecated {
+            modifiers |= MOD_DEPRECATED;
+        }
+        if declaration_is_pure(declaration, declaring_analysis) {
+            modifiers |= MOD_READONLY;
+        }
+        tokens.insert(site.span.span(), (type_index, modifiers));
+    }
+    tokens
+}
+
+/// Functions and values are pure when their type never mentions `Task` or
+/// `Signal`.  Signals are reactive by definition and never pure.
+fn declaration_is_pure(declaration: &CallableDeclaration, analysis: &FileAnalysis) -> bool {
+    let Some(typed) = analysis.typed_declaration_for_name_span(declaration.target.span) else {
+        return false;
+    };
+    if !matches!(
+        typed.kind,
+        TypedDeclarationKind::Function | TypedDeclarationKind::Value
+    ) {
+        return false;
+    }
+    typed
+        .declared_type
+        .as_deref()
+        .or(typed.inferred_type.as_deref())
+        .is_some_and(|ty| !type_mentions_effects(ty))
+}
+
+fn type_mentions_effects(ty: &str) -> bool {
+    ty.split(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .any(|name| matches!(name, "Task" | "Signal"))
+}
+
 fn soft_or_hard_token_type_index(
     token: aivi_syntax::Token,
     tokens: &[aivi_syntax::Token],
@@ -189,7 +284,9 @@ fn soft_or_hard_token_type_index(
         TokenKind::StringLiteral if string_literal_has_interpolation(token.text(source)) => None,
         // Let TextMate grammar handle identifier coloring — it uses specific scopes
         // (e.g. variable.parameter.labeled, variable.other.field) that carry more
-        // precise color intent than a blanket `variable` semantic token.
+        // precise color intent than a blanket `variable` semantic token.  Names of
+        // top-level functions, values, and signals are classified earlier by
+        // `callable_name_tokens` so they can carry modifiers.
         TokenKind::Identifier => None,
         kind => token_type_index(kind),
     }
@@ -1,27 +1,34 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
 use tower_lsp::{
     Client, LanguageServer,
     jsonrpc::Result,
-    lsp_types::request::{GotoImplementationParams, GotoImplementationResponse},
+    lsp_types::request::{
+        GotoImplementationParams, GotoImplementationResponse, GotoTypeDefinitionParams,
+        GotoTypeDefinitionResponse,
+    },
     lsp_types::{
-        CodeActionOptions, CodeActionParams, CodeActionProviderCapability, CodeLens,
-        CodeLensOptions, CodeLensParams, CompletionOptions, CompletionParams, CompletionResponse,
+        CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+        CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams,
+        CallHierarchyServerCapability, CodeActionOptions, CodeActionParams,
+        CodeActionProviderCapability, CodeLens, CodeLensOptions, CodeLensParams, CompletionOptions,
+        CompletionParams, CompletionResponse, ConfigurationItem, DidChangeConfigurationParams,
         DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-        DocumentFormattingParams, DocumentSymbolParams, DocumentSymbolResponse,
-        GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams, HoverProviderCapability,
-        ImplementationProviderCapability, InitializeParams, InitializeResult, InitializedParams,
-        InlayHint, InlayHintParams, Location, MessageType, OneOf, PrepareRenameResponse,
-        ReferenceParams, RenameOptions, RenameParams, SemanticTokensFullOptions,
-        SemanticTokensLegend, SemanticTokensOptions, SemanticTokensParams, SemanticTokensResult,
-        SemanticTokensServerCapabilities, ServerCapabilities, SymbolInformation, SymbolKind,
-        TextDocumentPositionParams, TextDocumentSyncCapability, TextDocumentSyncKind,
-        TextDocumentSyncOptions, TextEdit, WorkDoneProgressOptions, WorkspaceEdit,
+        DocumentFormattingParams, DocumentRangeFormattingParams, DocumentSymbolParams,
+        DocumentSymbolResponse, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams,
+        HoverProviderCapability, ImplementationProviderCapability, InitializeParams,
+        InitializeResult, InitializedParams, InlayHint, InlayHintParams, Location, MessageType,
+        OneOf, PrepareRenameResponse, ReferenceParams, RenameOptions, RenameParams,
+        SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
+        SemanticTokensParams, SemanticTokensResult, SemanticTokensServerCapabilities,
+        ServerCapabilities, SymbolInformation, SymbolKind, TextDocumentPositionParams,
+        TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions, TextEdit,
+        TypeDefinitionProviderCapability, WorkDoneProgressOptions, WorkspaceEdit,
         WorkspaceSymbolParams,
     },
 };
 
-use crate::state::{ServerConfig, ServerState};
+use crate::state::{INDEXING_PROGRESS_DELAY, ProgressReporter, ServerConfig, ServerState};
 
 pub struct Backend {
     pub client: Client,
@@ -36,21 +43,102 @@ impl Backend {
         }
     }
 
-    async fn publish_diagnostics_for_uri(&self, uri: tower_lsp::lsp_types::Url) {
+    /// Publish diagnostics for a newly opened document. Analysing it loads every workspace module
+    /// it imports, so when that takes longer than [`INDEXING_PROGRESS_DELAY`] the client is shown
+    /// an indexing progress item until the diagnostics are ready.
+    async fn index_and_publish_diagnostics(&self, uri: tower_lsp::lsp_types::Url) {
         let maybe_file = self.state.files.get(&uri).map(|file| *file);
         let Some(file) = maybe_file else {
             tracing::error!(
-                "publish_diagnostics_for_uri: URI {} is not tracked; diagnostics will not be published",
+                "index_and_publish_diagnostics: URI {} is not tracked; diagnostics will not be published",
                 uri
             );
             return;
         };
 
-        let lsp_diags = crate::diagnostics::collect_lsp_diagnostics(&self.state.db, file, &uri);
-        self.client
-            .publish_diagnostics(uri.clone(), lsp_diags, None)
-            .await;
-        tracing::debug!("Published diagnostics for {}", uri);
+        let state = Arc::clone(&self.state);
+        let analysed_uri = uri.clone();
+        let mut analysis = tokio::task::spawn_blocking(move || {
+            let config = state.config();
+            let mut diagnostics = crate::diagnostics::collect_lsp_diagnostics(
+                &state.db,
+                file,
+                &analysed_uri,
+                config.strict_mode,
+                config.max_errors,
+            );
+            diagnostics.extend(crate::test_diagnostics::collect_test_diagnostics(
+                &state,
+                file,
+                &analysed_uri,
+            ));
+            diagnostics
+        });
+        let mut progress = ProgressReporter::indexing(self.client.clone());
+        let analysed = match tokio::time::timeout(INDEXING_PROGRESS_DELAY, &mut analysis).await {
+            Ok(analysed) => analysed,
+            Err(_) => {
+                if self.state.work_done_progress_supported() {
+                    progress
+                        .begin("Indexing workspace", Some(uri.path().to_owned()))
+                        .await;
+                }
+                analysis.await
+            }
+        };
+        progress.end(None).await;
+
+        match analysed {
+            Ok(lsp_diags) => {
+                self.client
+                    .publish_diagnostics(uri.clone(), lsp_diags, None)
+                    .await;
+                tracing::debug!("Published diagnostics for {}", uri);
+            }
+            Err(error) => tracing::error!("indexing {} failed: {}", uri, error),
+        }
+    }
+
+    /// Ask the client for the `aivi` settings section, if it answers `workspace/configuration`.
+    async fn pull_workspace_settings(&self) {
+        if !self.state.workspace_configuration_supported() {
+            return;
+        }
+        let items = vec![ConfigurationItem {
+            scope_uri: None,
+            section: Some("aivi".to_owned()),
+        }];
+        match self.client.configuration(items).await {
+            Ok(settings) => {
+                if let Some(settings) = settings.first() {
+                    self.state
+                        .set_config(self.state.config().with_workspace_settings(settings));
+                }
+            }
+            Err(error) => tracing::debug!("workspace/configuration request failed: {error}"),
+        }
+    }
+
+    /// Handle [`crate::test_diagnostics::TEST_RESULTS_METHOD`]: record the outcomes and, when the
+    /// document is open, republish its diagnostics with one pass/fail marker per `@test`.
+    pub async fn test_results(&self, params: crate::test_diagnostics::TestResultsParams) {
+        let uri = params.uri.clone();
+        crate::test_diagnostics::record_test_results(&self.state, params);
+        if self.state.files.contains_key(&uri) {
+            self.index_and_publish_diagnostics(uri).await;
+        }
+    }
+
+    async fn republish_open_diagnostics(&self) {
+        let uris = self
+            .state
+            .files
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect::<Vec<_>>();
+        for uri in uris {
+            self.index_and_publish_diagnostics(uri).await;
+        }
     }
 }
 
@@ -59,6 +147,22 @@ impl LanguageServer for Backend {
     async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
         let config = ServerConfig::from_initialization_options(params.initialization_options);
         self.state.set_config(config);
+        self.state.set_work_done_progress_supported(
+            params
+                .capabilities
+                .window
+                .as_ref()
+                .and_then(|window| window.work_done_progress)
+                .unwrap_or(false),
+        );
+        self.state.set_workspace_configuration_supported(
+            params
+                .capabilities
+                .workspace
+                .as_ref()
+                .and_then(|workspace| workspace.configuration)
+                .unwrap_or(false),
+        );
 
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
@@ -71,6 +175,7 @@ impl LanguageServer for Backend {
                 )),
                 document_symbol_provider: Some(OneOf::Left(true)),
                 document_formatting_provider: Some(OneOf::Left(true)),
+                document_range_formatting_provider: Some(OneOf::Left(true)),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 completion_provider: Some(CompletionOptions {
                     resolve_provider: Some(false),
@@ -78,8 +183,10 @@ impl LanguageServer for Backend {
                     ..Default::default()
                 }),
                 definition_provider: Some(OneOf::Left(true)),
+                type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
                 implementation_provider: Some(ImplementationProviderCapability::Simple(true)),
                 references_provider: Some(OneOf::Left(true)),
+                call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
                 rename_provider: Some(OneOf::Right(RenameOptions {
                     prepare_provider: Some(true),
                     work_done_progress_options: WorkDoneProgressOptions::default(),
@@ -98,7 +205,7 @@ impl LanguageServer for Backend {
                             work_done_progress_options: WorkDoneProgressOptions::default(),
                             legend: SemanticTokensLegend {
                                 token_types: crate::semantic_tokens::TOKEN_TYPES.to_vec(),
-                                token_modifiers: Vec::new(),
+                                token_modifiers: crate::semantic_tokens::TOKEN_MODIFIERS.to_vec(),
                             },
                             range: None,
                             full: Some(SemanticTokensFullOptions::Bool(true)),
@@ -112,6 +219,7 @@ impl LanguageServer for Backend {
     }
 
     async fn initialized(&self, _params: InitializedParams) {
+        self.pull_workspace_settings().await;
         self.client
             .log_message(MessageType::INFO, "aivi language server initialized")
             .await;
@@ -121,11 +229,22 @@ impl LanguageServer for Backend {
         Ok(())
     }
 
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        let previous = self.state.config();
+        self.state
+            .set_config(previous.with_workspace_settings(&params.settings));
+        // Pull-model clients send an empty payload and expect the server to ask for the section.
+        self.pull_workspace_settings().await;
+        if self.state.config().strict_mode != previous.strict_mode {
+            self.republish_open_diagnostics().await;
+        }
+    }
+
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
         let uri = params.text_document.uri;
         let text = params.text_document.text;
         crate::documents::open_document(&self.state, &uri, text);
-        self.publish_diagnostics_for_uri(uri).await;
+        self.index_and_publish_diagnostics(uri).await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
@@ -153,8 +272,14 @@ impl LanguageServer for Backend {
                 );
                 return;
             };
-            let lsp_diags =
-                crate::diagnostics::collect_lsp_diagnostics(&state_clone.db, file, &uri_clone);
+            let config = state_clone.config();
+            let lsp_diags = crate::diagnostics::collect_lsp_diagnostics(
+                &state_clone.db,
+                file,
+                &uri_clone,
+                config.strict_mode,
+                config.max_errors,
+            );
             client_clone
                 .publish_diagnostics(uri_clone.clone(), lsp_diags, None)
                 .await;
@@ -199,6 +324,23 @@ impl LanguageServer for Backend {
         Ok(crate::formatting::format_document(&self.state.db, file))
     }
 
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let uri = &params.text_document.uri;
+        let maybe_file = self.state.files.get(uri).map(|file| *file);
+        let Some(file) = maybe_file else {
+            return Ok(None);
+        };
+
+        Ok(crate::formatting::format_range(
+            &self.state.db,
+            file,
+            params.range,
+        ))
+    }
+
     async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
         Ok(crate::hover::hover(params, Arc::clone(&self.state)).await)
     }
@@ -214,6 +356,13 @@ impl LanguageServer for Backend {
         Ok(crate::definition::definition(params, Arc::clone(&self.state)).await)
     }
 
+    async fn goto_type_definition(
+        &self,
+        params: GotoTypeDefinitionParams,
+    ) -> Result<Option<GotoTypeDefinitionResponse>> {
+        Ok(crate::type_definition::type_definition(params, Arc::clone(&self.state)).await)
+    }
+
     async fn goto_implementation(
         &self,
         params: GotoImplementationParams,
@@ -225,6 +374,27 @@ impl LanguageServer for Backend {
         Ok(crate::references::references(params, Arc::clone(&self.state)).await)
     }
 
+    async fn prepare_call_hierarchy(
+        &self,
+        params: CallHierarchyPrepareParams,
+    ) -> Result<Option<Vec<CallHierarchyItem>>> {
+        Ok(crate::call_hierarchy::prepare_call_hierarchy(params, Arc::clone(&self.state)).await)
+    }
+
+    async fn incoming_calls(
+        &self,
+        params: CallHierarchyIncomingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyIncomingCall>>> {
+        Ok(crate::call_hierarchy::incoming_calls(params, Arc::clone(&self.state)).await)
+    }
+
+    async fn outgoing_calls(
+        &self,
+        params: CallHierarchyOutgoingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyOutgoingCall>>> {
+        Ok(crate::call_hierarchy::outgoing_calls(params, Arc::clone(&self.state)).await)
+    }
+
     async fn prepare_rename(
         &self,
         params: TextDocumentPositionParams,
@@ -260,8 +430,31 @@ impl LanguageServer for Backend {
         let query = params.query.to_ascii_lowercase();
         let mut results: Vec<SymbolInformation> = Vec::new();
 
-        for entry in self.state.files.iter() {
-            let (uri, file) = (entry.key().clone(), *entry.value());
+        // Collect the files up front so no map guard is held across progress notifications.
+        let files: Vec<_> = self
+            .state
+            .files
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect();
+        let total = files.len();
+        let started = Instant::now();
+        let mut progress = ProgressReporter::indexing(self.client.clone());
+
+        for (index, (uri, file)) in files.into_iter().enumerate() {
+            if !progress.is_active()
+                && self.state.work_done_progress_supported()
+                && started.elapsed() >= INDEXING_PROGRESS_DELAY
+            {
+                progress.begin("Indexing workspace symbols", None).await;
+            }
+            progress
+                .report(
+                    Some(format!("{index} of {total} files")),
+                    Some((index * 100 / total) as u32),
+                )
+                .await;
+
             let analysis = crate::analysis::FileAnalysis::load(&self.state.db, file);
             let source = analysis.source.as_ref();
 
@@ -295,6 +488,7 @@ impl LanguageServer for Backend {
                 stack.extend(sym.children.iter());
             }
         }
+        progress.end(None).await;
 
         Ok(Some(results))
     }
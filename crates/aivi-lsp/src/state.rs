@@ -1,10 +1,32 @@
-use std::sync::RwLock;
+use std::{
+    sync::{
+        RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
 
 use aivi_query::{RootDatabase, SourceFile};
 use dashmap::DashMap;
 use serde::Deserialize;
 use tokio::task::JoinHandle;
-use tower_lsp::lsp_types::Url;
+use tower_lsp::{
+    Client,
+    lsp_types::{
+        NumberOrString, ProgressParams, ProgressParamsValue, Url, WorkDoneProgress,
+        WorkDoneProgressBegin, WorkDoneProgressCreateParams, WorkDoneProgressEnd,
+        WorkDoneProgressReport, notification::Progress, request::WorkDoneProgressCreate,
+    },
+};
+
+use crate::test_diagnostics::TestResult;
+
+/// Progress token used for every workspace indexing run. Reusing one token means repeated
+/// indexing updates the same progress item in the client instead of starting new spinners.
+pub const INDEXING_PROGRESS_TOKEN: &str = "aivi/indexing";
+
+/// Indexing that finishes faster than this is not shown to the user.
+pub const INDEXING_PROGRESS_DELAY: Duration = Duration::from_millis(300);
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct ServerConfig {
@@ -12,6 +34,10 @@ pub struct ServerConfig {
     pub inlay_hints_enabled: bool,
     pub inlay_hints_max_length: usize,
     pub code_lens_enabled: bool,
+    /// Extra warnings for unused bindings, redundant imports, and unreachable case arms.
+    pub strict_mode: bool,
+    /// Type checking of a document stops after this many errors; 0 means no limit.
+    pub max_errors: usize,
 }
 
 impl ServerConfig {
@@ -34,6 +60,23 @@ impl ServerConfig {
             code_lens_enabled: options
                 .code_lens_enabled
                 .unwrap_or(defaults.code_lens_enabled),
+            strict_mode: options.strict_mode.unwrap_or(defaults.strict_mode),
+            max_errors: options.max_errors.unwrap_or(defaults.max_errors),
+        }
+    }
+
+    /// Apply the `aivi` section of the client's workspace settings.
+    ///
+    /// Accepts either the section itself (a `workspace/configuration` reply) or the whole settings
+    /// object (a `workspace/didChangeConfiguration` push). Settings that are absent keep their
+    /// current value.
+    pub fn with_workspace_settings(self, settings: &serde_json::Value) -> Self {
+        let section = settings.get("aivi").unwrap_or(settings);
+        let settings =
+            serde_json::from_value::<WorkspaceSettings>(section.clone()).unwrap_or_default();
+        Self {
+            strict_mode: settings.strict_mode.unwrap_or(self.strict_mode),
+            ..self
         }
     }
 }
@@ -45,6 +88,8 @@ impl Default for ServerConfig {
             inlay_hints_enabled: true,
             inlay_hints_max_length: 30,
             code_lens_enabled: true,
+            strict_mode: false,
+            max_errors: 100,
         }
     }
 }
@@ -56,6 +101,14 @@ struct InitializationOptions {
     inlay_hints_enabled: Option<bool>,
     inlay_hints_max_length: Option<usize>,
     code_lens_enabled: Option<bool>,
+    strict_mode: Option<bool>,
+    max_errors: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkspaceSettings {
+    strict_mode: Option<bool>,
 }
 
 /// Shared state for the language server.
@@ -64,7 +117,13 @@ pub struct ServerState {
     pub files: DashMap<Url, SourceFile>,
     /// Pending debounced diagnostics tasks, keyed by document URI.
     pub pending_diagnostics: DashMap<Url, JoinHandle<()>>,
+    /// Latest `aivi test` outcomes reported by the client, keyed by document URI.
+    pub test_results: DashMap<Url, Vec<TestResult>>,
     config: RwLock<ServerConfig>,
+    /// Whether the client accepts server-initiated `$/progress` notifications.
+    work_done_progress: AtomicBool,
+    /// Whether the client answers `workspace/configuration` requests.
+    workspace_configuration: AtomicBool,
 }
 
 impl ServerState {
@@ -73,7 +132,10 @@ impl ServerState {
             db: RootDatabase::new(),
             files: DashMap::new(),
             pending_diagnostics: DashMap::new(),
+            test_results: DashMap::new(),
             config: RwLock::new(ServerConfig::default()),
+            work_done_progress: AtomicBool::new(false),
+            workspace_configuration: AtomicBool::new(false),
         }
     }
 
@@ -90,6 +152,23 @@ impl ServerState {
             .write()
             .expect("server config lock should not be poisoned") = config;
     }
+
+    pub fn work_done_progress_supported(&self) -> bool {
+        self.work_done_progress.load(Ordering::Relaxed)
+    }
+
+    pub fn set_work_done_progress_supported(&self, supported: bool) {
+        self.work_done_progress.store(supported, Ordering::Relaxed);
+    }
+
+    pub fn workspace_configuration_supported(&self) -> bool {
+        self.workspace_configuration.load(Ordering::Relaxed)
+    }
+
+    pub fn set_workspace_configuration_supported(&self, supported: bool) {
+        self.workspace_configuration
+            .store(supported, Ordering::Relaxed);
+    }
 }
 
 impl Default for ServerState {
@@ -98,9 +177,97 @@ impl Default for ServerState {
     }
 }
 
+/// Sends `$/progress` begin/report/end notifications for one long-running operation.
+///
+/// Nothing is sent until [`ProgressReporter::begin`] succeeds, so `report` and `end` are safe to
+/// call unconditionally.
+pub struct ProgressReporter {
+    client: Client,
+    token: NumberOrString,
+    active: bool,
+}
+
+impl ProgressReporter {
+    /// A reporter for workspace indexing, using [`INDEXING_PROGRESS_TOKEN`].
+    pub fn indexing(client: Client) -> Self {
+        Self {
+            client,
+            token: NumberOrString::String(INDEXING_PROGRESS_TOKEN.to_owned()),
+            active: false,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Ask the client to create the progress item and show it with `title`.
+    pub async fn begin(&mut self, title: &str, message: Option<String>) {
+        if self.active {
+            return;
+        }
+        let created = self
+            .client
+            .send_request::<WorkDoneProgressCreate>(WorkDoneProgressCreateParams {
+                token: self.token.clone(),
+            })
+            .await;
+        if let Err(error) = created {
+            tracing::debug!("client refused progress token {:?}: {error}", self.token);
+            return;
+        }
+        self.active = true;
+        self.send(WorkDoneProgress::Begin(WorkDoneProgressBegin {
+            title: title.to_owned(),
+            cancellable: Some(false),
+            message,
+            percentage: Some(0),
+        }))
+        .await;
+    }
+
+    pub async fn report(&self, message: Option<String>, percentage: Option<u32>) {
+        if !self.active {
+            return;
+        }
+        self.send(WorkDoneProgress::Report(WorkDoneProgressReport {
+            cancellable: Some(false),
+            message,
+            percentage,
+        }))
+        .await;
+    }
+
+    pub async fn end(&mut self, message: Option<String>) {
+        if !self.active {
+            return;
+        }
+        self.active = false;
+        self.send(WorkDoneProgress::End(WorkDoneProgressEnd { message }))
+            .await;
+    }
+
+    async fn send(&self, progress: WorkDoneProgress) {
+        self.client
+            .send_notification::<Progress>(progress_params(&self.token, progress))
+            .await;
+    }
+}
+
+fn progress_params(token: &NumberOrString, progress: WorkDoneProgress) -> ProgressParams {
+    ProgressParams {
+        token: token.clone(),
+        value: ProgressParamsValue::WorkDone(progress),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ServerConfig;
+    use tower_lsp::lsp_types::{
+        NumberOrString, ProgressParamsValue, WorkDoneProgress, WorkDoneProgressEnd,
+    };
+
+    use super::{INDEXING_PROGRESS_TOKEN, ServerConfig, ServerState, progress_params};
 
     #[test]
     fn initialization_options_override_defaults() {
@@ -108,12 +275,59 @@ mod tests {
             "diagnosticsDebounceMs": 75,
             "inlayHintsEnabled": false,
             "inlayHintsMaxLength": 12,
-            "codeLensEnabled": false
+            "codeLensEnabled": false,
+            "strictMode": true,
+            "maxErrors": 25
         })));
 
         assert_eq!(config.diagnostics_debounce_ms, 75);
         assert!(!config.inlay_hints_enabled);
         assert_eq!(config.inlay_hints_max_length, 12);
         assert!(!config.code_lens_enabled);
+        assert!(config.strict_mode);
+        assert_eq!(config.max_errors, 25);
+    }
+
+    #[test]
+    fn workspace_settings_toggle_strict_mode() {
+        let config = ServerConfig::default();
+        assert!(!config.strict_mode);
+
+        let config = config.with_workspace_settings(&serde_json::json!({ "strictMode": true }));
+        assert!(config.strict_mode);
+
+        let config =
+            config.with_workspace_settings(&serde_json::json!({ "aivi": { "strictMode": false } }));
+        assert!(!config.strict_mode);
+
+        let unrelated = config.with_workspace_settings(&serde_json::json!({ "editor": {} }));
+        assert_eq!(unrelated, config);
+    }
+
+    #[test]
+    fn indexing_progress_uses_a_stable_token() {
+        let token = NumberOrString::String(INDEXING_PROGRESS_TOKEN.to_owned());
+        let params = progress_params(
+            &token,
+            WorkDoneProgress::End(WorkDoneProgressEnd { message: None }),
+        );
+
+        assert_eq!(
+            params.token,
+            NumberOrString::String("aivi/indexing".to_owned())
+        );
+        assert!(matches!(
+            params.value,
+            ProgressParamsValue::WorkDone(WorkDoneProgress::End(_))
+        ));
+    }
+
+    #[test]
+    fn work_done_progress_is_off_until_the_client_supports_it() {
+        let state = ServerState::new();
+        assert!(!state.work_done_progress_supported());
+
+        state.set_work_done_progress_supported(true);
+        assert!(state.work_done_progress_supported());
     }
 }
@@ -0,0 +1,100 @@
+//! Inline pass/fail markers for `@test` values.
+//!
+//! The server does not run tests itself: the client runs `aivi test` and reports each outcome
+//! through the custom [`TEST_RESULTS_METHOD`] notification. The latest report per document is
+//! kept in [`ServerState::test_results`] and published next to the ordinary diagnostics until
+//! the document is edited, since the outcomes describe the text that ran.
+
+use aivi_hir::Module;
+use serde::{Deserialize, Serialize};
+use tower_lsp::lsp_types::{self as lsp, DiagnosticSeverity, NumberOrString, Url};
+
+use crate::{
+    code_lens::{item_has_test_decorator, item_name_and_span},
+    diagnostics::lsp_range,
+    state::ServerState,
+};
+
+/// Client-to-server notification carrying [`TestResultsParams`].
+pub const TEST_RESULTS_METHOD: &str = "aivi/testResults";
+
+/// Value of the `source` field on every test outcome diagnostic.
+pub const TEST_DIAGNOSTIC_SOURCE: &str = "aivi-test";
+
+/// Outcome of one `@test` value, matched to its declaration by name.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestResult {
+    pub name: String,
+    pub passed: bool,
+    /// Failure detail, or the optional note a passing test printed.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestResultsParams {
+    pub uri: Url,
+    pub results: Vec<TestResult>,
+}
+
+/// Replace the recorded outcomes for one document.
+pub fn record_test_results(state: &ServerState, params: TestResultsParams) {
+    state.test_results.insert(params.uri, params.results);
+}
+
+/// Diagnostics for the recorded outcomes of `uri`, or nothing when no run was reported.
+pub fn collect_test_diagnostics(
+    state: &ServerState,
+    file: aivi_query::SourceFile,
+    uri: &Url,
+) -> Vec<lsp::Diagnostic> {
+    let Some(results) = state.test_results.get(uri) else {
+        return Vec::new();
+    };
+    let hir = aivi_query::hir_module(&state.db, file);
+    test_result_diagnostics(hir.module(), hir.source(), &results)
+}
+
+/// One diagnostic per `@test` value that has a result: a hint when it passed and an error when
+/// it failed. Results naming no `@test` value in the module are ignored.
+pub fn test_result_diagnostics(
+    module: &Module,
+    source: &aivi_base::SourceFile,
+    results: &[TestResult],
+) -> Vec<lsp::Diagnostic> {
+    module
+        .root_items()
+        .iter()
+        .filter(|&&item_id| item_has_test_decorator(module, item_id))
+        .filter_map(|&item_id| item_name_and_span(module, item_id))
+        .filter_map(|(name, span)| {
+            let result = results.iter().find(|result| result.name == name)?;
+            Some(test_result_diagnostic(
+                lsp_range(source.span_to_lsp_range(span.span())),
+                result,
+            ))
+        })
+        .collect()
+}
+
+fn test_result_diagnostic(range: lsp::Range, result: &TestResult) -> lsp::Diagnostic {
+    let (severity, code, status) = if result.passed {
+        (DiagnosticSeverity::HINT, "test-passed", "passed")
+    } else {
+        (DiagnosticSeverity::ERROR, "test-failed", "failed")
+    };
+    let message = match &result.message {
+        Some(detail) => format!("test `{}` {status}: {detail}", result.name),
+        None => format!("test `{}` {status}", result.name),
+    };
+    lsp::Diagnostic {
+        range,
+        severity: Some(severity),
+        code: Some(NumberOrString::String(code.to_owned())),
+        source: Some(TEST_DIAGNOSTIC_SOURCE.to_owned()),
+        message,
+        ..Default::default()
+    }
+}
@@ -186,6 +186,62 @@ pub fn build_type_annotation_code_actions(
     actions
 }
 
+/// Offer "Add type signature" for unannotated functions whose signature could be inferred.
+///
+/// The inferred type is inserted as a standalone `type` line above the declaration. Type
+/// parameters keep their names, since free type variables in a signature are already quantified
+/// over the whole declaration. Values and signals are skipped because an annotation that repeats
+/// their inferred type would immediately be reported as unnecessary.
+pub fn build_add_type_signature_code_actions(
+    uri: &Url,
+    summaries: &[TypedDeclarationSummary],
+    source: &aivi_base::SourceFile,
+    request_range: Range,
+) -> Vec<CodeActionOrCommand> {
+    summaries
+        .iter()
+        .filter(|summary| {
+            summary.annotation.is_none() && !summary.annotation_is_independently_inferable
+        })
+        .filter(|summary| {
+            ranges_overlap(range_for_span(source, summary.header_span), request_range)
+        })
+        .filter_map(|summary| {
+            let inferred = summary.inferred_type.as_deref()?;
+            let edit = signature_insertion_edit(source, summary.header_span, inferred);
+            let mut changes = std::collections::HashMap::new();
+            changes.insert(uri.clone(), vec![edit]);
+            Some(CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Add type signature".to_owned(),
+                kind: Some(CodeActionKind::REFACTOR_REWRITE),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(changes),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }))
+        })
+        .collect()
+}
+
+fn signature_insertion_edit(
+    source: &aivi_base::SourceFile,
+    header_span: SourceSpan,
+    signature: &str,
+) -> TextEdit {
+    let text = source.text();
+    let line_start = line_start_offset(text, header_span.span().start().as_usize());
+    let indent_len = text[line_start..]
+        .find(|ch: char| ch != ' ' && ch != '\t')
+        .unwrap_or(0);
+    let indent = &text[line_start..line_start + indent_len];
+    let start = range_for_span(source, source.source_span(line_start..line_start)).start;
+    TextEdit {
+        range: Range { start, end: start },
+        new_text: format!("{indent}type {signature}\n"),
+    }
+}
+
 pub fn range_for_span(source: &aivi_base::SourceFile, span: SourceSpan) -> Range {
     crate::diagnostics::lsp_range(source.span_to_lsp_range(span.span()))
 }
@@ -436,8 +492,8 @@ mod tests {
     use super::{
         MISMATCHED_TYPE_ANNOTATION_CODE, MISSING_TYPE_ANNOTATION_CODE,
         TypeAnnotationDiagnosticKind, TypeAnnotationStyle, UNNECESSARY_TYPE_ANNOTATION_CODE,
-        build_type_annotation_code_actions, collect_type_annotation_diagnostics,
-        collect_typed_declaration_summaries, diagnostic_kind,
+        build_add_type_signature_code_actions, build_type_annotation_code_actions,
+        collect_type_annotation_diagnostics, collect_typed_declaration_summaries, diagnostic_kind,
     };
 
     fn parse(
@@ -633,4 +689,63 @@ mod tests {
         assert_eq!(edit.range.start.character, 12);
         assert_eq!(edit.range.end.character, 18);
     }
+
+    fn whole_file_range() -> Range {
+        Range {
+            start: tower_lsp::lsp_types::Position {
+                line: 0,
+                character: 0,
+            },
+            end: tower_lsp::lsp_types::Position {
+                line: 8,
+                character: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn add_type_signature_inserts_the_inferred_signature_above_functions() {
+        let (source, _, _, summaries) = parse(
+            "func keepLeft = left right => left\n\
+             value chooser:(Int -> Int) = keepLeft 1\n",
+        );
+        let actions =
+            build_add_type_signature_code_actions(&uri(), &summaries, &source, whole_file_range());
+
+        assert_eq!(
+            actions.len(),
+            1,
+            "only the function should get a signature action"
+        );
+        let CodeActionOrCommand::CodeAction(action) = &actions[0] else {
+            panic!("expected a code action");
+        };
+        assert_eq!(action.title, "Add type signature");
+        let edit = action
+            .edit
+            .clone()
+            .and_then(|edit| edit.changes)
+            .and_then(|mut changes| changes.remove(&uri()))
+            .and_then(|mut edits| edits.pop())
+            .expect("expected insertion edit");
+
+        assert_eq!(edit.new_text, "type Int -> Int -> Int\n");
+        assert_eq!(edit.range.start, edit.range.end);
+        assert_eq!(edit.range.start.line, 0);
+        assert_eq!(edit.range.start.character, 0);
+    }
+
+    #[test]
+    fn add_type_signature_skips_annotated_and_uninferred_functions() {
+        let (source, _, _, summaries) = parse(
+            "type Int -> Int\n\
+             func inc = value => value + 1\n\
+             func id = x => x\n",
+        );
+
+        assert!(
+            build_add_type_signature_code_actions(&uri(), &summaries, &source, whole_file_range())
+                .is_empty()
+        );
+    }
 }
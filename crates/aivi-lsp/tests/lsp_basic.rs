@@ -4,8 +4,12 @@ use aivi_lsp::{
     diagnostics::collect_lsp_diagnostics,
     documents::{change_document, close_document, open_document},
     state::ServerState,
+    test_diagnostics::{
+        TEST_DIAGNOSTIC_SOURCE, TestResult, TestResultsParams, collect_test_diagnostics,
+        record_test_results,
+    },
 };
-use tower_lsp::lsp_types::{DiagnosticSeverity, Url};
+use tower_lsp::lsp_types::{DiagnosticSeverity, NumberOrString, Url};
 
 fn test_uri(name: &str) -> Url {
     Url::from_file_path(PathBuf::from("/test-documents").join(name))
@@ -43,7 +47,7 @@ fn valid_document_has_no_error_diagnostics() {
     open_document(&state, &uri, "value answer = 42\n".to_owned());
     let file = *state.files.get(&uri).expect("file should be open");
 
-    let diagnostics = collect_lsp_diagnostics(&state.db, file, &uri);
+    let diagnostics = collect_lsp_diagnostics(&state.db, file, &uri, false, 0);
     let errors: Vec<_> = diagnostics
         .iter()
         .filter(|d| d.severity == Some(DiagnosticSeverity::ERROR))
@@ -63,9 +67,211 @@ fn invalid_document_has_error_diagnostics() {
     open_document(&state, &uri, "val = 42\n".to_owned());
     let file = *state.files.get(&uri).expect("file should be open");
 
-    let diagnostics = collect_lsp_diagnostics(&state.db, file, &uri);
+    let diagnostics = collect_lsp_diagnostics(&state.db, file, &uri, false, 0);
     assert!(
         !diagnostics.is_empty(),
         "an invalid document should produce at least one diagnostic"
     );
 }
+
+#[test]
+fn type_errors_stop_at_the_error_budget() {
+    let state = ServerState::new();
+    let uri = test_uri("many-errors.aivi");
+    let text = (0..20)
+        .map(|index| format!("value broken{index} : Int = \"text\"\n"))
+        .collect::<String>();
+    open_document(&state, &uri, text);
+    let file = *state.files.get(&uri).expect("file should be open");
+    let errors = |max_errors| {
+        collect_lsp_diagnostics(&state.db, file, &uri, false, max_errors)
+            .into_iter()
+            .filter(|d| d.severity == Some(DiagnosticSeverity::ERROR))
+            .count()
+    };
+
+    assert_eq!(errors(0), 20, "type errors should be published");
+    assert_eq!(errors(5), 5);
+    let bounded = collect_lsp_diagnostics(&state.db, file, &uri, false, 5);
+    assert!(
+        bounded
+            .iter()
+            .any(|d| d.message
+                == "type checking aborted after 5 errors; later items were not checked"),
+        "the truncation should be reported; got: {bounded:#?}"
+    );
+}
+
+#[test]
+fn strict_mode_reports_unused_bindings() {
+    let state = ServerState::new();
+    let uri = test_uri("strict.aivi");
+    open_document(
+        &state,
+        &uri,
+        "type Int -> Int -> Int\nfunc first = left right => left\n\nvalue answer = first 1 2\n"
+            .to_owned(),
+    );
+    let file = *state.files.get(&uri).expect("file should be open");
+    let unused_binding = NumberOrString::String("hir::unused-binding".to_owned());
+
+    let relaxed = collect_lsp_diagnostics(&state.db, file, &uri, false, 0);
+    assert!(
+        relaxed
+            .iter()
+            .all(|diagnostic| diagnostic.code.as_ref() != Some(&unused_binding)),
+        "strict warnings should be off by default; got: {relaxed:#?}"
+    );
+
+    let strict = collect_lsp_diagnostics(&state.db, file, &uri, true, 0);
+    let warning = strict
+        .iter()
+        .find(|diagnostic| diagnostic.code.as_ref() == Some(&unused_binding))
+        .expect("strict mode should report the unused parameter");
+    assert_eq!(warning.severity, Some(DiagnosticSeverity::WARNING));
+    assert_eq!(warning.message, "`right` is never used");
+    assert_eq!(warning.range.start.line, 1);
+}
+
+#[test]
+fn strict_mode_reports_shadowed_prelude_names() {
+    let state = ServerState::new();
+    let uri = test_uri("shadowing.aivi");
+    open_document(
+        &state,
+        &uri,
+        "type Int -> Int\nfunc map = value => value + 1\n\nvalue answer = map 1\n".to_owned(),
+    );
+    let file = *state.files.get(&uri).expect("file should be open");
+    let shadowed = NumberOrString::String("hir::shadowed-prelude-name".to_owned());
+
+    let relaxed = collect_lsp_diagnostics(&state.db, file, &uri, false, 0);
+    assert!(
+        relaxed
+            .iter()
+            .all(|diagnostic| diagnostic.code.as_ref() != Some(&shadowed)),
+        "shadowing warnings should be off by default; got: {relaxed:#?}"
+    );
+
+    let strict = collect_lsp_diagnostics(&state.db, file, &uri, true, 0);
+    let warnings = strict
+        .iter()
+        .filter(|diagnostic| diagnostic.code.as_ref() == Some(&shadowed))
+        .collect::<Vec<_>>();
+    assert_eq!(warnings.len(), 1, "unexpected warnings: {strict:#?}");
+    assert_eq!(warnings[0].message, "`map` shadows the prelude's `map`");
+    assert_eq!(warnings[0].range.start.line, 1);
+    assert_eq!(warnings[0].tags, None);
+}
+
+#[test]
+fn reported_test_results_become_inline_diagnostics() {
+    let state = ServerState::new();
+    let uri = test_uri("tests.aivi");
+    open_document(
+        &state,
+        &uri,
+        "@test\nvalue adds : Task Text Bool = pure (1 + 1 == 2)\n\n@test\nvalue breaks : Task Text Bool = pure False\n"
+            .to_owned(),
+    );
+    let file = *state.files.get(&uri).expect("file should be open");
+    assert!(collect_test_diagnostics(&state, file, &uri).is_empty());
+
+    record_test_results(
+        &state,
+        TestResultsParams {
+            uri: uri.clone(),
+            results: vec![
+                TestResult {
+                    name: "adds".to_owned(),
+                    passed: true,
+                    message: None,
+                },
+                TestResult {
+                    name: "breaks".to_owned(),
+                    passed: false,
+                    message: Some("returned False".to_owned()),
+                },
+                TestResult {
+                    name: "missing".to_owned(),
+                    passed: false,
+                    message: None,
+                },
+            ],
+        },
+    );
+    let outcomes = collect_test_diagnostics(&state, file, &uri)
+        .into_iter()
+        .map(|diagnostic| {
+            assert_eq!(diagnostic.source.as_deref(), Some(TEST_DIAGNOSTIC_SOURCE));
+            (
+                diagnostic.range.start.line,
+                diagnostic.severity,
+                diagnostic.message,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        outcomes,
+        vec![
+            (
+                1,
+                Some(DiagnosticSeverity::HINT),
+                "test `adds` passed".to_owned()
+            ),
+            (
+                4,
+                Some(DiagnosticSeverity::ERROR),
+                "test `breaks` failed: returned False".to_owned()
+            ),
+        ]
+    );
+
+    change_document(&state, &uri, "value answer = 42\n".to_owned());
+    assert!(
+        collect_test_diagnostics(&state, file, &uri).is_empty(),
+        "editing the document should drop stale test outcomes"
+    );
+}
+
+fn diagnostic_codes(source: &str) -> Vec<(u32, String)> {
+    let state = ServerState::new();
+    let uri = test_uri("suppression.aivi");
+    open_document(&state, &uri, source.to_owned());
+    let file = *state.files.get(&uri).expect("file should be open");
+
+    collect_lsp_diagnostics(&state.db, file, &uri, false, 0)
+        .into_iter()
+        .map(|diagnostic| {
+            let code = match diagnostic.code {
+                Some(NumberOrString::String(code)) => code,
+                other => panic!("expected a string diagnostic code, got {other:?}"),
+            };
+            (diagnostic.range.start.line, code)
+        })
+        .collect()
+}
+
+#[test]
+fn suppression_comments_silence_matching_diagnostics() {
+    let codes = diagnostic_codes(concat!(
+        "value first = 1 // @suppress(unused-symbol)\n",
+        "\n",
+        "// @suppress(aivi/unused-symbol)\n",
+        "value second = 2\n",
+        "\n",
+        "value third = 3\n",
+    ));
+
+    assert_eq!(codes, vec![(5, "aivi/unused-symbol".to_owned())]);
+}
+
+#[test]
+fn suppression_comments_that_match_nothing_are_reported() {
+    let codes = diagnostic_codes(concat!(
+        "// @suppress(unused-symbol, type-mismatch)\n",
+        "value first = 1\n",
+    ));
+
+    assert_eq!(codes, vec![(0, "aivi/unused-suppression".to_owned())]);
+}
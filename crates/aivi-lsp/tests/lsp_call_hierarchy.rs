@@ -0,0 +1,239 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use aivi_lsp::{
+    call_hierarchy::{incoming_calls, outgoing_calls, prepare_call_hierarchy},
+    documents::open_document,
+    state::ServerState,
+};
+use tower_lsp::lsp_types::{
+    CallHierarchyIncomingCallsParams, CallHierarchyItem, CallHierarchyOutgoingCallsParams,
+    CallHierarchyPrepareParams, PartialResultParams, Position, SymbolKind, TextDocumentIdentifier,
+    TextDocumentPositionParams, Url, WorkDoneProgressParams,
+};
+
+fn inline_uri(name: &str) -> Url {
+    Url::from_file_path(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join(name),
+    )
+    .expect("test file path should convert to a file URL")
+}
+
+fn fixture_path(relative: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .join(relative)
+}
+
+fn fixture_uri(relative: &str) -> Url {
+    Url::from_file_path(fixture_path(relative)).expect("fixture path should convert to a file URL")
+}
+
+fn fixture_text(relative: &str) -> String {
+    fs::read_to_string(fixture_path(relative)).expect("fixture text should be readable")
+}
+
+fn position_at_byte(text: &str, byte_index: usize) -> Position {
+    let prefix = &text[..byte_index];
+    let line = prefix.bytes().filter(|b| *b == b'\n').count() as u32;
+    let line_start = prefix.rfind('\n').map_or(0, |i| i + 1);
+    Position {
+        line,
+        character: text[line_start..byte_index].encode_utf16().count() as u32,
+    }
+}
+
+fn position_of_nth(text: &str, needle: &str, occurrence: usize) -> Position {
+    let mut start = 0usize;
+    let mut seen = 0usize;
+    loop {
+        let relative = text[start..]
+            .find(needle)
+            .unwrap_or_else(|| panic!("could not find occurrence #{occurrence} of `{needle}`"));
+        let byte_index = start + relative;
+        if seen == occurrence {
+            return position_at_byte(text, byte_index);
+        }
+        seen += 1;
+        start = byte_index + needle.len();
+    }
+}
+
+fn prepare_params(uri: Url, position: Position) -> CallHierarchyPrepareParams {
+    CallHierarchyPrepareParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri },
+            position,
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    }
+}
+
+fn incoming_params(item: CallHierarchyItem) -> CallHierarchyIncomingCallsParams {
+    CallHierarchyIncomingCallsParams {
+        item,
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    }
+}
+
+fn outgoing_params(item: CallHierarchyItem) -> CallHierarchyOutgoingCallsParams {
+    CallHierarchyOutgoingCallsParams {
+        item,
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    }
+}
+
+async fn prepare_single(
+    state: &Arc<ServerState>,
+    uri: Url,
+    position: Position,
+) -> CallHierarchyItem {
+    let mut items = prepare_call_hierarchy(prepare_params(uri, position), Arc::clone(state))
+        .await
+        .expect("call hierarchy should prepare an item for a function reference");
+    assert_eq!(
+        items.len(),
+        1,
+        "expected one prepared item, found {items:?}"
+    );
+    items.remove(0)
+}
+
+const LOCAL_CALLS: &str = "type Int -> Int\nfunc double = x =>\n    x + x\n\ntype Int -> Int\nfunc quadruple = x =>\n    double (double x)\n\nvalue answer : Int = quadruple 2\n";
+
+#[tokio::test]
+async fn call_hierarchy_prepares_function_declaration_from_use_site() {
+    let state = Arc::new(ServerState::new());
+    let uri = inline_uri("call-hierarchy-prepare.aivi");
+    open_document(&state, &uri, LOCAL_CALLS.to_owned());
+
+    let item = prepare_single(
+        &state,
+        uri.clone(),
+        position_of_nth(LOCAL_CALLS, "double", 1),
+    )
+    .await;
+
+    assert_eq!(item.name, "double");
+    assert_eq!(item.kind, SymbolKind::FUNCTION);
+    assert_eq!(item.uri, uri);
+    assert_eq!(
+        item.selection_range.start,
+        position_of_nth(LOCAL_CALLS, "double", 0)
+    );
+}
+
+#[tokio::test]
+async fn call_hierarchy_groups_incoming_calls_by_caller() {
+    let state = Arc::new(ServerState::new());
+    let uri = inline_uri("call-hierarchy-incoming.aivi");
+    open_document(&state, &uri, LOCAL_CALLS.to_owned());
+
+    let item = prepare_single(&state, uri, position_of_nth(LOCAL_CALLS, "double", 0)).await;
+    let calls = incoming_calls(incoming_params(item), Arc::clone(&state))
+        .await
+        .expect("`double` should have incoming calls");
+
+    assert_eq!(calls.len(), 1, "expected one caller, found {calls:?}");
+    assert_eq!(calls[0].from.name, "quadruple");
+    let starts = calls[0]
+        .from_ranges
+        .iter()
+        .map(|range| range.start)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        starts,
+        vec![
+            position_of_nth(LOCAL_CALLS, "double", 1),
+            position_of_nth(LOCAL_CALLS, "double", 2),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn call_hierarchy_lists_outgoing_calls_from_body() {
+    let state = Arc::new(ServerState::new());
+    let uri = inline_uri("call-hierarchy-outgoing.aivi");
+    open_document(&state, &uri, LOCAL_CALLS.to_owned());
+
+    let item = prepare_single(&state, uri, position_of_nth(LOCAL_CALLS, "quadruple", 0)).await;
+    let calls = outgoing_calls(outgoing_params(item), Arc::clone(&state))
+        .await
+        .expect("`quadruple` should have outgoing calls");
+
+    assert_eq!(calls.len(), 1, "expected one callee, found {calls:?}");
+    assert_eq!(calls[0].to.name, "double");
+    assert_eq!(calls[0].from_ranges.len(), 2);
+}
+
+#[tokio::test]
+async fn call_hierarchy_crosses_module_boundaries() {
+    let main_relative = "fixtures/frontend/milestone-2/valid/workspace-typeclass-prelude/main.aivi";
+    let target_relative =
+        "fixtures/frontend/milestone-2/valid/workspace-typeclass-prelude/shared/logic.aivi";
+    let state = Arc::new(ServerState::new());
+    let uri = fixture_uri(main_relative);
+    let text = fixture_text(main_relative);
+    open_document(&state, &uri, text.clone());
+
+    let item = prepare_single(&state, uri.clone(), position_of_nth(&text, "liftOne", 1)).await;
+    assert_eq!(item.uri, fixture_uri(target_relative));
+
+    let incoming = incoming_calls(incoming_params(item.clone()), Arc::clone(&state))
+        .await
+        .expect("`liftOne` should be called from the importing module");
+    assert_eq!(incoming.len(), 1, "expected one caller, found {incoming:?}");
+    assert_eq!(incoming[0].from.name, "lifted");
+    assert_eq!(incoming[0].from.uri, uri);
+
+    let outgoing = outgoing_calls(outgoing_params(item), Arc::clone(&state))
+        .await
+        .expect("`liftOne` should call helpers from its own module");
+    assert!(
+        outgoing.iter().any(|call| call.to.name == "increment"),
+        "expected `increment` among outgoing calls, found {outgoing:?}"
+    );
+}
+
+#[tokio::test]
+async fn call_hierarchy_finds_callers_in_unopened_workspace_modules() {
+    let main_relative = "fixtures/frontend/milestone-2/valid/workspace-typeclass-prelude/main.aivi";
+    let target_relative =
+        "fixtures/frontend/milestone-2/valid/workspace-typeclass-prelude/shared/logic.aivi";
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock should be after unix epoch")
+        .as_nanos();
+    let root = env::temp_dir().join(format!("aivi-lsp-call-hierarchy-{unique}"));
+    fs::create_dir_all(root.join("shared")).expect("workspace directory should be creatable");
+    fs::write(root.join("aivi.toml"), "").expect("manifest should be writable");
+    fs::write(root.join("main.aivi"), fixture_text(main_relative))
+        .expect("caller module should be writable");
+    let target_text = fixture_text(target_relative);
+    let target_path = root.join("shared").join("logic.aivi");
+    fs::write(&target_path, &target_text).expect("callee module should be writable");
+
+    // Only the callee is open; its caller is found by walking the workspace.
+    let state = Arc::new(ServerState::new());
+    let uri = Url::from_file_path(&target_path).expect("test URI should be valid");
+    open_document(&state, &uri, target_text.clone());
+    let item = prepare_single(&state, uri, position_of_nth(&target_text, "liftOne", 0)).await;
+    let incoming = incoming_calls(incoming_params(item), Arc::clone(&state)).await;
+    let _ = fs::remove_dir_all(&root);
+
+    let incoming = incoming.expect("`liftOne` should be called from the unopened main module");
+    assert_eq!(incoming.len(), 1, "expected one caller, found {incoming:?}");
+    assert_eq!(incoming[0].from.name, "lifted");
+    assert_eq!(
+        incoming[0].from.uri.to_file_path().ok(),
+        Some(root.join("main.aivi"))
+    );
+}
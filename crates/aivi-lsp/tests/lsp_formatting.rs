@@ -1,11 +1,15 @@
-use std::path::PathBuf;
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use aivi_lsp::{
     documents::{change_document, open_document},
-    formatting::format_document,
+    formatting::{format_document, format_range},
     state::ServerState,
 };
-use tower_lsp::lsp_types::Url;
+use tower_lsp::lsp_types::{Position, Range, Url};
 
 fn test_uri(name: &str) -> Url {
     Url::from_file_path(PathBuf::from("/test-documents").join(name))
@@ -56,3 +60,71 @@ fn formatting_is_idempotent() {
         "formatting an already-formatted document should produce no edits"
     );
 }
+
+#[test]
+fn range_formatting_only_rewrites_items_in_range() {
+    let source = "value first=1\n\n\nvalue second=[1,2]\nvalue  third =  3\n";
+    let (state, uri) = open_inline("format-range.aivi", source);
+    let file = *state.files.get(&uri).expect("file should be open");
+
+    let range = Range::new(Position::new(3, 8), Position::new(3, 10));
+    let edits = format_range(&state.db, file, range).expect("range formatting should succeed");
+    assert_eq!(edits.len(), 1);
+    assert_eq!(
+        edits[0].range,
+        Range::new(Position::new(3, 0), Position::new(3, 18))
+    );
+    assert_eq!(edits[0].new_text, "value second = [1, 2]");
+}
+
+#[test]
+fn range_formatting_with_parse_errors_returns_none() {
+    let (state, uri) = open_inline("format-range-broken.aivi", "value = = =\n");
+    let file = *state.files.get(&uri).expect("file should be open");
+
+    let range = Range::new(Position::new(0, 0), Position::new(0, 5));
+    assert!(format_range(&state.db, file, range).is_none());
+}
+
+#[test]
+fn formatting_round_trips_with_workspace_format_options() {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock should be after unix epoch")
+        .as_nanos();
+    let root = env::temp_dir().join(format!("aivi-lsp-format-options-{unique}"));
+    fs::create_dir_all(&root).expect("workspace directory should be creatable");
+    fs::write(
+        root.join("aivi.toml"),
+        "[format]\nreflow_comments = true\nmax_width = 30\n",
+    )
+    .expect("manifest should be writable");
+
+    let state = ServerState::new();
+    let uri = Url::from_file_path(root.join("main.aivi")).expect("test URI should be valid");
+    let source = "//Counts the widgets that are currently visible on screen.\nvalue count=3\n";
+    open_document(&state, &uri, source.to_owned());
+    let file = *state.files.get(&uri).expect("file should be open");
+
+    let edits = format_document(&state.db, file).expect("formatting should succeed");
+    assert_eq!(edits.len(), 1);
+    assert_eq!(
+        edits[0].new_text,
+        concat!(
+            "// Counts the widgets that are\n",
+            "// currently visible on\n",
+            "// screen.\n",
+            "value count = 3\n",
+        )
+    );
+
+    change_document(&state, &uri, edits[0].new_text.clone());
+    let second_edits = format_document(&state.db, file).expect("second format should succeed");
+    let range = Range::new(Position::new(0, 0), Position::new(3, 0));
+    let range_edits =
+        format_range(&state.db, file, range).expect("range formatting should succeed");
+    let _ = fs::remove_dir_all(&root);
+
+    assert!(second_edits.is_empty());
+    assert!(range_edits.is_empty());
+}
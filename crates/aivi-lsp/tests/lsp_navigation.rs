@@ -2,9 +2,11 @@ use std::{fs, path::PathBuf, sync::Arc};
 
 use aivi_lsp::{
     definition::definition, documents::open_document, implementation::implementation,
-    state::ServerState,
+    state::ServerState, type_definition::type_definition,
+};
+use tower_lsp::lsp_types::request::{
+    GotoImplementationParams, GotoImplementationResponse, GotoTypeDefinitionParams,
 };
-use tower_lsp::lsp_types::request::{GotoImplementationParams, GotoImplementationResponse};
 use tower_lsp::lsp_types::{
     GotoDefinitionParams, GotoDefinitionResponse, Location, Position, TextDocumentIdentifier,
     TextDocumentPositionParams, Url,
@@ -55,6 +57,17 @@ fn implementation_params(uri: Url, position: Position) -> GotoImplementationPara
     }
 }
 
+fn type_definition_params(uri: Url, position: Position) -> GotoTypeDefinitionParams {
+    GotoTypeDefinitionParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri },
+            position,
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    }
+}
+
 fn response_locations(response: GotoDefinitionResponse) -> Vec<Location> {
     match response {
         GotoDefinitionResponse::Scalar(location) => vec![location],
@@ -310,3 +323,121 @@ async fn definition_resolves_from_fanout_signal_use_site() {
         position_of_nth(&text, "gameOver", 0)
     );
 }
+
+#[tokio::test]
+async fn type_definition_resolves_annotated_value_use_site() {
+    let text = "type Status =\n  | Idle\n  | Busy\n\nvalue current : Status = Idle\n\nvalue again = current\n";
+    let (state, uri, text) = open_inline_document("type-definition-nav.aivi", text);
+
+    let response = type_definition(
+        type_definition_params(uri.clone(), position_of_nth(&text, "current", 1)),
+        state,
+    )
+    .await
+    .expect("type definition should resolve for an annotated value use site");
+
+    let locations = response_locations(response);
+    assert_eq!(locations.len(), 1);
+    assert_eq!(locations[0].uri, uri);
+    assert_eq!(
+        locations[0].range.start,
+        position_of_nth(&text, "Status", 0)
+    );
+}
+
+#[tokio::test]
+async fn type_definition_skips_builtin_wrappers() {
+    let text = "type Status =\n  | Idle\n  | Busy\n\nvalue pending : Option Status = Some Idle\n";
+    let (state, uri, text) = open_inline_document("type-definition-wrapped-nav.aivi", text);
+
+    let response = type_definition(
+        type_definition_params(uri.clone(), position_of_nth(&text, "pending", 0)),
+        state,
+    )
+    .await
+    .expect("type definition should look through builtin type constructors");
+
+    let locations = response_locations(response);
+    assert_eq!(locations.len(), 1);
+    assert_eq!(
+        locations[0].range.start,
+        position_of_nth(&text, "Status", 0)
+    );
+}
+
+#[tokio::test]
+async fn type_definition_uses_inferred_type_without_annotation() {
+    let text = "type Status =\n  | Idle\n  | Busy\n\nvalue current = Busy\n";
+    let (state, uri, text) = open_inline_document("type-definition-inferred-nav.aivi", text);
+
+    let response = type_definition(
+        type_definition_params(uri.clone(), position_of_nth(&text, "current", 0)),
+        state,
+    )
+    .await
+    .expect("type definition should resolve through the inferred type");
+
+    let locations = response_locations(response);
+    assert_eq!(locations.len(), 1);
+    assert_eq!(
+        locations[0].range.start,
+        position_of_nth(&text, "Status", 0)
+    );
+}
+
+#[tokio::test]
+async fn type_definition_walks_nested_inferred_types() {
+    let text = "type Status =\n  | Idle\n  | Busy\n\nvalue history = [(1, Busy), (2, Idle)]\n";
+    let (state, uri, text) = open_inline_document("type-definition-inferred-nested-nav.aivi", text);
+
+    let response = type_definition(
+        type_definition_params(uri.clone(), position_of_nth(&text, "history", 0)),
+        state,
+    )
+    .await
+    .expect("type definition should look inside inferred lists and tuples");
+
+    let locations = response_locations(response);
+    assert_eq!(locations.len(), 1);
+    assert_eq!(
+        locations[0].range.start,
+        position_of_nth(&text, "Status", 0)
+    );
+}
+
+#[tokio::test]
+async fn type_definition_resolves_cross_file_imported_type() {
+    let main_relative = "fixtures/frontend/milestone-2/valid/workspace-type-imports/main.aivi";
+    let target_relative =
+        "fixtures/frontend/milestone-2/valid/workspace-type-imports/shared/types.aivi";
+    let (state, uri, text) = open_fixture_document(main_relative);
+
+    let response = type_definition(
+        type_definition_params(uri, position_of_nth(&text, "hello", 0)),
+        state,
+    )
+    .await
+    .expect("type definition should resolve to an imported type declaration");
+
+    let locations = response_locations(response);
+    assert_eq!(locations.len(), 1);
+    assert_eq!(locations[0].uri, fixture_uri(target_relative));
+    assert_eq!(
+        locations[0].range.start,
+        position_of_nth(&fixture_text(target_relative), "Envelope", 0)
+    );
+}
+
+#[tokio::test]
+async fn type_definition_has_no_targets_for_builtin_types() {
+    let text = "value count : Int = 1\n";
+    let (state, uri, text) = open_inline_document("type-definition-builtin-nav.aivi", text);
+
+    let response = type_definition(
+        type_definition_params(uri, position_of_nth(&text, "count", 0)),
+        state,
+    )
+    .await;
+
+    assert!(response.is_none());
+}
@@ -0,0 +1,181 @@
+use std::{path::PathBuf, sync::Arc};
+
+use aivi_lsp::{
+    documents::open_document,
+    semantic_tokens::{TOKEN_MODIFIERS, semantic_tokens_full},
+    state::ServerState,
+};
+use tower_lsp::lsp_types::{
+    PartialResultParams, SemanticTokenModifier, SemanticTokensParams, SemanticTokensResult,
+    TextDocumentIdentifier, Url, WorkDoneProgressParams,
+};
+
+const SOURCE: &str = concat!(
+    "@deprecated \"use next\"\n",
+    "value old = 1\n",
+    "\n",
+    "type Int -> Int\n",
+    "func double = n => n * 2\n",
+    "\n",
+    "signal counter = 0\n",
+    "\n",
+    "value total = double old\n",
+);
+
+/// One decoded semantic token: `(line, start, length, type, modifiers)`.
+type DecodedToken = (u32, u32, u32, u32, u32);
+
+async fn decoded_tokens(source: &str) -> Vec<DecodedToken> {
+    let state = Arc::new(ServerState::new());
+    let uri = Url::from_file_path(PathBuf::from("/test-documents/semantic-modifiers.aivi"))
+        .expect("test URI should be valid");
+    open_document(&state, &uri, source.to_owned());
+
+    let params = SemanticTokensParams {
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+        text_document: TextDocumentIdentifier { uri },
+    };
+    let Some(SemanticTokensResult::Tokens(tokens)) = semantic_tokens_full(params, state).await
+    else {
+        panic!("expected a full semantic token result");
+    };
+
+    let mut line = 0;
+    let mut start = 0;
+    tokens
+        .data
+        .iter()
+        .map(|token| {
+            if token.delta_line == 0 {
+                start += token.delta_start;
+            } else {
+                line += token.delta_line;
+                start = token.delta_start;
+            }
+            (
+                line,
+                start,
+                token.length,
+                token.token_type,
+                token.token_modifiers_bitset,
+            )
+        })
+        .collect()
+}
+
+fn modifier_bits(names: &[SemanticTokenModifier]) -> u32 {
+    names
+        .iter()
+        .map(|name| {
+            let index = TOKEN_MODIFIERS
+                .iter()
+                .position(|modifier| modifier == name)
+                .expect("modifier should be in the legend");
+            1 << index
+        })
+        .fold(0, |bits, bit| bits | bit)
+}
+
+fn token_at(tokens: &[DecodedToken], line: u32, start: u32) -> DecodedToken {
+    *tokens
+        .iter()
+        .find(|token| token.0 == line && token.1 == start)
+        .unwrap_or_else(|| panic!("expected a semantic token at {line}:{start}"))
+}
+
+#[test]
+fn legend_declares_readonly_deprecated_definition_and_reference() {
+    assert_eq!(
+        TOKEN_MODIFIERS,
+        &[
+            SemanticTokenModifier::READONLY,
+            SemanticTokenModifier::DEPRECATED,
+            SemanticTokenModifier::DEFINITION,
+            SemanticTokenModifier::new("reference"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn declaration_names_carry_definition_and_purity_modifiers() {
+    let tokens = decoded_tokens(SOURCE).await;
+    let reference = SemanticTokenModifier::new("reference");
+
+    // `old` is deprecated and pure.
+    assert_eq!(
+        token_at(&tokens, 1, 6),
+        (
+            1,
+            6,
+            3,
+            2,
+            modifier_bits(&[
+                SemanticTokenModifier::DEFINITION,
+                SemanticTokenModifier::DEPRECATED,
+                SemanticTokenModifier::READONLY,
+            ])
+        )
+    );
+    // `double` is a pure function.
+    assert_eq!(
+        token_at(&tokens, 4, 5),
+        (
+            4,
+            5,
+            6,
+            1,
+            modifier_bits(&[
+                SemanticTokenModifier::DEFINITION,
+                SemanticTokenModifier::READONLY,
+            ])
+        )
+    );
+    // Signals are never readonly.
+    assert_eq!(
+        token_at(&tokens, 6, 7),
+        (
+            6,
+            7,
+            7,
+            2,
+            modifier_bits(&[SemanticTokenModifier::DEFINITION])
+        )
+    );
+    // Use sites mirror their declaration's modifiers, marked as references.
+    assert_eq!(
+        token_at(&tokens, 8, 14),
+        (
+            8,
+            14,
+            6,
+            1,
+            modifier_bits(&[reference.clone(), SemanticTokenModifier::READONLY])
+        )
+    );
+    assert_eq!(
+        token_at(&tokens, 8, 21),
+        (
+            8,
+            21,
+            3,
+            2,
+            modifier_bits(&[
+                reference,
+                SemanticTokenModifier::DEPRECATED,
+                SemanticTokenModifier::READONLY,
+            ])
+        )
+    );
+}
+
+#[tokio::test]
+async fn parameters_are_left_to_textmate() {
+    let tokens = decoded_tokens(SOURCE).await;
+    // `n` in `func double = n => n * 2`.
+    assert!(
+        !tokens
+            .iter()
+            .any(|token| token.0 == 4 && (token.1 == 14 || token.1 == 19))
+    );
+}
@@ -0,0 +1,241 @@
+//! API documentation for one module, built from its export list.
+//!
+//! Each exported declaration is paired with its signature and with the `/** ... **/` comment
+//! written directly above it (or above its decorators). A doc comment at the very top of the file
+//! that is followed by a blank line documents the module itself.
+
+use std::fmt::Write as _;
+
+use aivi_base::ByteIndex;
+use aivi_hir::{ExportedNameKind, Item, ItemId, Module, collect_typed_declarations};
+use aivi_syntax::{Token, TokenKind};
+
+use crate::{RootDatabase, SourceFile, hir_module, parsed_file, workspace::Workspace};
+
+/// Documentation for every exported name of one module, in export-list order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleDocs {
+    pub module_name: String,
+    pub summary: Option<String>,
+    pub entries: Vec<DocEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocEntry {
+    pub name: String,
+    pub kind: ExportedNameKind,
+    /// `name : Type` for values, functions, and signals, or the declaration source for types,
+    /// classes, and domains. `None` for names the module re-exports from elsewhere.
+    pub signature: Option<String>,
+    pub doc: Option<String>,
+}
+
+/// Collect the documentation of `file`'s exported names.
+pub fn module_docs(db: &RootDatabase, file: SourceFile) -> ModuleDocs {
+    let hir = hir_module(db, file);
+    let parsed = parsed_file(db, file);
+    let module = hir.module();
+    let source = hir.source();
+    let tokens = parsed.parsed().lexed.tokens();
+    let typed = collect_typed_declarations(module);
+
+    let entries = hir
+        .exported_names()
+        .iter()
+        .map(|exported| {
+            let item = declaration_item(module, &exported.name, exported.kind);
+            let signature = item.and_then(|item_id| match exported.kind {
+                ExportedNameKind::Value | ExportedNameKind::Function | ExportedNameKind::Signal => {
+                    typed
+                        .iter()
+                        .find(|declaration| declaration.item_id == item_id)
+                        .and_then(|declaration| {
+                            declaration
+                                .declared_type
+                                .as_ref()
+                                .or(declaration.inferred_type.as_ref())
+                        })
+                        .map(|ty| format!("{} : {ty}", exported.name))
+                }
+                ExportedNameKind::Type | ExportedNameKind::Class | ExportedNameKind::Domain => {
+                    Some(
+                        source
+                            .slice(module.items()[item_id].span().span())
+                            .trim()
+                            .to_owned(),
+                    )
+                }
+                ExportedNameKind::SourceProvider | ExportedNameKind::Instance => None,
+            });
+            let doc = item.and_then(|item_id| {
+                attached_doc_comment(tokens, item_start(module, item_id))
+                    .map(|token| doc_comment_text(token.text(source)))
+            });
+            DocEntry {
+                name: exported.name.clone(),
+                kind: exported.kind,
+                signature,
+                doc,
+            }
+        })
+        .collect();
+
+    let module_name = Workspace::discover(db, file)
+        .module_name_for_file(db, file)
+        .unwrap_or_else(|| {
+            file.path(db)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+
+    ModuleDocs {
+        module_name,
+        summary: module_doc_comment(tokens).map(|token| doc_comment_text(token.text(source))),
+        entries,
+    }
+}
+
+/// Render `docs` as one Markdown page: a title, the module summary, and one section per export.
+pub fn render_markdown(docs: &ModuleDocs) -> String {
+    let mut out = format!("# {}\n", docs.module_name);
+    if let Some(summary) = &docs.summary {
+        let _ = write!(out, "\n{summary}\n");
+    }
+    for entry in &docs.entries {
+        let _ = write!(
+            out,
+            "\n## `{}`\n\n_{}_\n",
+            entry.name,
+            kind_label(entry.kind)
+        );
+        if let Some(signature) = &entry.signature {
+            let _ = write!(out, "\n```aivi\n{signature}\n```\n");
+        }
+        if let Some(doc) = &entry.doc {
+            let _ = write!(out, "\n{doc}\n");
+        }
+    }
+    out
+}
+
+fn kind_label(kind: ExportedNameKind) -> &'static str {
+    match kind {
+        ExportedNameKind::Type => "type",
+        ExportedNameKind::Value => "value",
+        ExportedNameKind::Function => "function",
+        ExportedNameKind::Signal => "signal",
+        ExportedNameKind::Class => "class",
+        ExportedNameKind::Domain => "domain",
+        ExportedNameKind::SourceProvider => "source provider",
+        ExportedNameKind::Instance => "instance",
+    }
+}
+
+fn declaration_item(module: &Module, name: &str, kind: ExportedNameKind) -> Option<ItemId> {
+    module
+        .root_items()
+        .iter()
+        .copied()
+        .find(|&item_id| declaration_name(module, item_id) == Some((name, kind)))
+}
+
+/// The name and export kind of a root declaration that can carry a doc comment.
+pub(crate) fn declaration_name(
+    module: &Module,
+    item_id: ItemId,
+) -> Option<(&str, ExportedNameKind)> {
+    match &module.items()[item_id] {
+        Item::Type(item) => Some((item.name.text(), ExportedNameKind::Type)),
+        Item::Value(item) => Some((item.name.text(), ExportedNameKind::Value)),
+        Item::Function(item) => Some((item.name.text(), ExportedNameKind::Function)),
+        Item::Signal(item) => Some((item.name.text(), ExportedNameKind::Signal)),
+        Item::Class(item) => Some((item.name.text(), ExportedNameKind::Class)),
+        Item::Domain(item) => Some((item.name.text(), ExportedNameKind::Domain)),
+        _ => None,
+    }
+}
+
+/// Where an item's leading text begins, counting its decorators and a standalone `type` line.
+pub(crate) fn item_start(module: &Module, item_id: ItemId) -> ByteIndex {
+    let item = &module.items()[item_id];
+    // A standalone function signature is split over the parameter and result annotations.
+    let annotations = match item {
+        Item::Value(item) => item.annotation.into_iter().collect(),
+        Item::Function(item) => item
+            .parameters
+            .iter()
+            .filter_map(|parameter| parameter.annotation)
+            .chain(item.annotation)
+            .collect(),
+        Item::Signal(item) => item.annotation.into_iter().collect(),
+        _ => Vec::new(),
+    };
+    item.decorators()
+        .iter()
+        .map(|&decorator| module.decorators()[decorator].span.span().start())
+        .chain(
+            annotations
+                .into_iter()
+                .map(|annotation| module.types()[annotation].span.span().start()),
+        )
+        .chain(std::iter::once(item.span().span().start()))
+        .min()
+        .expect("an item span is always present")
+}
+
+/// The doc comment ending directly above `start`, with no blank line in between.
+pub(crate) fn attached_doc_comment(tokens: &[Token], start: ByteIndex) -> Option<&Token> {
+    let mut newlines = 0;
+    for token in tokens
+        .iter()
+        .rev()
+        .skip_while(|token| token.span().start() >= start)
+    {
+        match token.kind() {
+            TokenKind::Whitespace => {}
+            // The keyword of a standalone annotation that `start` points into.
+            TokenKind::TypeKw if newlines == 0 => {}
+            TokenKind::Newline if newlines == 0 => newlines += 1,
+            TokenKind::DocComment => return Some(token),
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// A doc comment opening the file and separated from what follows by a blank line.
+pub(crate) fn module_doc_comment(tokens: &[Token]) -> Option<&Token> {
+    let mut significant = tokens
+        .iter()
+        .filter(|token| !matches!(token.kind(), TokenKind::Whitespace | TokenKind::Newline));
+    let first = significant.next()?;
+    if first.kind() != TokenKind::DocComment {
+        return None;
+    }
+    let blank_line_follows = tokens
+        .iter()
+        .skip_while(|token| token.span() != first.span())
+        .skip(1)
+        .take_while(|token| matches!(token.kind(), TokenKind::Whitespace | TokenKind::Newline))
+        .filter(|token| token.kind() == TokenKind::Newline)
+        .count()
+        >= 2;
+    blank_line_follows.then_some(first)
+}
+
+/// Strip the `/**`/`**/` delimiters and any leading `*` gutter from each line.
+fn doc_comment_text(raw: &str) -> String {
+    let inner = raw.strip_prefix("/**").unwrap_or(raw);
+    let inner = inner.strip_suffix("**/").unwrap_or(inner);
+    inner
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            line.strip_prefix('*').map(str::trim_start).unwrap_or(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_owned()
+}
@@ -0,0 +1,214 @@
+//! Doctests: examples in doc comments that state their expected value.
+//!
+//! Inside a ```` ```aivi ```` fence of a `/** ... **/` comment, an expression line directly
+//! followed by a `=> expected` line is one doctest. Fence lines that are not part of such a pair
+//! stay ordinary example text.
+//!
+//! A doctest runs as a synthetic `@test` value appended to a copy of the module that documents it,
+//! so an example can use every name the module itself can see. The copy is built in two passes:
+//! the first infers the type of each example expression, and the second annotates one
+//! `Task Text (Result T T)` test per example. The test yields `Ok actual` when the example matched
+//! and `Err actual` when it did not.
+
+use std::path::PathBuf;
+
+use aivi_base::{ByteIndex, Span};
+use aivi_hir::collect_typed_declarations;
+use aivi_syntax::Token;
+
+use crate::{
+    RootDatabase, SourceFile,
+    docs::{attached_doc_comment, declaration_name, item_start, module_doc_comment},
+    hir_module, parsed_file,
+    workspace::Workspace,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Doctest {
+    /// The documented declaration, or the module name for the module doc comment.
+    pub owner: String,
+    pub expression: String,
+    pub expected: String,
+    /// Where `expression` sits in the module source.
+    pub span: Span,
+}
+
+/// The synthetic module that runs the doctests of one source file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DoctestModule {
+    pub file: SourceFile,
+    /// For each doctest, the name of the `@test` value running it, or why no test could be built.
+    pub tests: Vec<Result<String, String>>,
+}
+
+const COMPARE_HELPER: &str = "aiviDoctestCompare";
+
+/// Collect the doctests of every doc comment in `file`, in source order.
+pub fn module_doctests(db: &RootDatabase, file: SourceFile) -> Vec<Doctest> {
+    let hir = hir_module(db, file);
+    let parsed = parsed_file(db, file);
+    let module = hir.module();
+    let source = hir.source();
+    let tokens = parsed.parsed().lexed.tokens();
+
+    let module_name = Workspace::discover(db, file)
+        .module_name_for_file(db, file)
+        .unwrap_or_else(|| {
+            file.path(db)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+    let mut comments: Vec<(String, Token)> = module_doc_comment(tokens)
+        .map(|token| (module_name, *token))
+        .into_iter()
+        .collect();
+    for &item_id in module.root_items() {
+        let Some((name, _)) = declaration_name(module, item_id) else {
+            continue;
+        };
+        if let Some(token) = attached_doc_comment(tokens, item_start(module, item_id))
+            && comments.iter().all(|(_, seen)| seen.span() != token.span())
+        {
+            comments.push((name.to_owned(), *token));
+        }
+    }
+    comments.sort_by_key(|(_, token)| token.span().start());
+
+    let mut doctests = Vec::new();
+    for (owner, token) in comments {
+        comment_doctests(
+            &owner,
+            token.text(source),
+            token.span().start(),
+            &mut doctests,
+        );
+    }
+    doctests
+}
+
+/// Build the synthetic module for `doctests` next to `file`.
+pub fn doctest_module(db: &RootDatabase, file: SourceFile, doctests: &[Doctest]) -> DoctestModule {
+    let original = file.text(db);
+    let path = doctest_path(&file.path(db));
+
+    let mut probe = base_text(&original);
+    for (index, doctest) in doctests.iter().enumerate() {
+        probe.push_str(&format!(
+            "\nvalue {} = {}\n",
+            actual_name(index),
+            doctest.expression
+        ));
+    }
+    let probe_file = SourceFile::new(db, path.clone(), probe);
+    let typed = collect_typed_declarations(hir_module(db, probe_file).module());
+
+    let mut text = base_text(&original);
+    text.push_str(&format!(
+        "\ntype Eq A => A -> A -> (Result A A)\nfunc {COMPARE_HELPER} = actual expected => actual == expected\n T|> Ok actual\n F|> Err actual\n"
+    ));
+    let tests = doctests
+        .iter()
+        .enumerate()
+        .map(|(index, doctest)| {
+            let actual = actual_name(index);
+            let ty = typed
+                .iter()
+                .find(|declaration| declaration.name == actual)
+                .and_then(|declaration| declaration.inferred_type.as_ref())
+                .ok_or_else(|| {
+                    format!("could not infer the type of example `{}`", doctest.expression)
+                })?;
+            let name = test_name(index);
+            text.push_str(&format!(
+                "\n@test\nvalue {name} : Task Text (Result ({ty}) ({ty})) = pure ({COMPARE_HELPER} ({}) ({}))\n",
+                doctest.expression, doctest.expected
+            ));
+            Ok(name)
+        })
+        .collect();
+
+    DoctestModule {
+        file: SourceFile::new(db, path, text),
+        tests,
+    }
+}
+
+fn comment_doctests(owner: &str, raw: &str, start: ByteIndex, out: &mut Vec<Doctest>) {
+    let mut in_fence = false;
+    let mut pending: Option<(&str, Span)> = None;
+    let mut line_start = start.as_u32();
+    for line in raw.split_inclusive('\n') {
+        let (text, offset) = comment_line_content(line);
+        let text_start = line_start + offset as u32;
+        line_start += line.len() as u32;
+        if !in_fence {
+            in_fence = text == "```aivi";
+            continue;
+        }
+        if text.starts_with("```") {
+            in_fence = false;
+            pending = None;
+        } else if let Some(expected) = text.strip_prefix("=>") {
+            if let Some((expression, span)) = pending.take() {
+                out.push(Doctest {
+                    owner: owner.to_owned(),
+                    expression: expression.to_owned(),
+                    expected: expected.trim().to_owned(),
+                    span,
+                });
+            }
+        } else {
+            pending = (!text.is_empty()).then(|| {
+                let end = text_start + text.len() as u32;
+                (
+                    text,
+                    Span::new(ByteIndex::new(text_start), ByteIndex::new(end)),
+                )
+            });
+        }
+    }
+}
+
+/// One comment line without its `/**`/`**/` delimiters and `*` gutter, and the byte offset of the
+/// returned text within the line.
+fn comment_line_content(line: &str) -> (&str, usize) {
+    let mut text = line.trim_end();
+    text = text.strip_suffix("**/").unwrap_or(text);
+    let mut rest = text.trim_start();
+    rest = rest.strip_prefix("/**").unwrap_or(rest);
+    if !line.trim_start().starts_with("/**") {
+        rest = rest.strip_prefix('*').unwrap_or(rest);
+    }
+    let rest = rest.trim();
+    if rest.is_empty() {
+        return ("", 0);
+    }
+    let offset = rest.as_ptr() as usize - line.as_ptr() as usize;
+    (rest, offset)
+}
+
+fn base_text(original: &str) -> String {
+    let mut text = original.to_owned();
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
+
+/// The synthetic module sits beside `path` so that workspace imports resolve as they do there.
+fn doctest_path(path: &std::path::Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{stem}_doctests.aivi"))
+}
+
+fn actual_name(index: usize) -> String {
+    format!("aiviDoctestActual{}", index + 1)
+}
+
+fn test_name(index: usize) -> String {
+    format!("aiviDoctest{}", index + 1)
+}
@@ -10,6 +10,8 @@
 //! fingerprints that later JIT/cache layers can key on.
 
 mod db;
+mod docs;
+mod doctests;
 mod entry;
 mod inputs;
 mod manifest;
@@ -17,20 +19,25 @@ mod queries;
 mod workspace;
 
 pub use db::{QueryCacheStats, RootDatabase};
+pub use docs::{DocEntry, ModuleDocs, module_docs, render_markdown};
+pub use doctests::{Doctest, DoctestModule, doctest_module, module_doctests};
 pub use entry::{
     EntrypointOrigin, EntrypointResolutionError, ResolvedEntrypoint, resolve_v1_entrypoint,
 };
 pub use inputs::SourceFile;
 pub use manifest::{
-    AiviManifest, AppConfig, RunConfig, RunLaunchConfig, WorkspaceConfig, parse_manifest,
+    AiviManifest, AppConfig, FormatConfig, RunConfig, RunLaunchConfig, WorkspaceConfig,
+    parse_manifest,
 };
 pub use queries::{
     BackendUnitError, HirModuleResult, ParsedFileResult, RuntimeFragmentBackendUnit,
     RuntimeFragmentFingerprint, StableFingerprint, WholeProgramBackendUnit,
     WholeProgramFingerprint, WorkspaceHirModule, all_diagnostics, exported_names, format_file,
-    hir_module, parsed_file, reachable_workspace_hir_modules, resolve_module_file,
+    format_file_range, format_file_with_options, hir_module, load_format_options, parsed_file,
+    prelude_term_names, reachable_workspace_hir_modules, resolve_module_file,
     runtime_fragment_backend_fingerprint, runtime_fragment_backend_unit, symbol_index,
-    whole_program_backend_fingerprint, whole_program_backend_fingerprint_with_items,
-    whole_program_backend_unit, whole_program_backend_unit_with_items,
+    validation_diagnostics, whole_program_backend_fingerprint,
+    whole_program_backend_fingerprint_with_items, whole_program_backend_unit,
+    whole_program_backend_unit_with_items, workspace_files,
 };
 pub use workspace::{discover_workspace_root, discover_workspace_root_from_directory};
@@ -1,5 +1,6 @@
 use std::{fs, path::Path};
 
+use aivi_syntax::FormatOptions;
 use serde::Deserialize;
 
 /// Parsed representation of an `aivi.toml` workspace manifest.
@@ -12,6 +13,8 @@ pub struct AiviManifest {
     pub workspace: WorkspaceConfig,
     #[serde(default)]
     pub run: RunConfig,
+    #[serde(default)]
+    pub format: FormatConfig,
     /// Entries from `[[app]]` arrays, each declaring a named application.
     #[serde(rename = "app", default)]
     pub apps: Vec<AppConfig>,
@@ -41,6 +44,25 @@ pub struct RunConfig {
     pub launch: Vec<RunLaunchConfig>,
 }
 
+/// Formatter settings from the `[format]` table, shared by `aivi fmt` and the language server.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct FormatConfig {
+    /// Rewrap comment blocks attached to top-level definitions.
+    pub reflow_comments: Option<bool>,
+    /// Column limit used when reflowing comments.
+    pub max_width: Option<usize>,
+}
+
+impl FormatConfig {
+    pub fn format_options(&self) -> FormatOptions {
+        let defaults = FormatOptions::default();
+        FormatOptions {
+            max_width: self.max_width.unwrap_or(defaults.max_width),
+            reflow_comments: self.reflow_comments.unwrap_or(defaults.reflow_comments),
+        }
+    }
+}
+
 /// One entry from a `[[run.launch]]` array, declaring an additional AIVI run target.
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
 pub struct RunLaunchConfig {
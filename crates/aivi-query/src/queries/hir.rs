@@ -4,14 +4,15 @@ use std::{
     sync::Arc,
 };
 
-use aivi_base::Diagnostic;
+use aivi_base::{Diagnostic, Severity, SourceSpan};
 use aivi_hir::{
-    ExportedNames, HoistKindFilter, ImportCycle, ImportModuleResolution, ImportResolver,
-    LoweringResult, LspSymbol, exports, extract_symbols, lower_module_with_resolver,
+    ExportedNameKind, ExportedNames, HoistKindFilter, ImportCycle, ImportModuleResolution,
+    ImportResolver, Item, LoweringResult, LspSymbol, ValidationMode, exports, extract_symbols,
+    lower_module_with_resolver,
 };
-use aivi_syntax::Formatter;
+use aivi_syntax::{FormatOptions, Formatter, RangeFormatResult};
 
-use crate::{RootDatabase, SourceFile, queries::parsed_file, workspace::Workspace};
+use crate::{RootDatabase, SourceFile, parse_manifest, queries::parsed_file, workspace::Workspace};
 
 // Track which modules are currently being compiled for hoist resolution.
 // This prevents re-entrant compilation of the same module during the
@@ -126,12 +127,18 @@ impl<'a> WorkspaceImportResolver<'a> {
 
     fn cycle(&self, file: SourceFile, requested_module: &[&str]) -> Option<ImportCycle> {
         let cycle_start = self.stack.iter().position(|entry| entry.file == file)?;
-        let mut modules = self.stack[cycle_start..]
+        let members = &self.stack[cycle_start..];
+        let mut modules = members
             .iter()
             .map(|entry| entry.module_name.clone())
             .collect::<Vec<_>>();
         modules.push(requested_module.join("."));
-        Some(ImportCycle::new(modules))
+        // Every member but the one being lowered reached the next member through a `use` item.
+        let use_sites = members
+            .windows(2)
+            .filter_map(|edge| use_item_span(self.db, edge[0].file, &edge[1].module_name))
+            .collect();
+        Some(ImportCycle::new(modules).with_use_sites(use_sites))
     }
 }
 
@@ -320,6 +327,33 @@ pub fn all_diagnostics(db: &RootDatabase, file: SourceFile) -> Arc<[Diagnostic]>
     hir_module(db, file).diagnostics_arc()
 }
 
+/// Validate the HIR module, type checking included, and return its diagnostics.
+///
+/// Type checking stops once `max_errors` errors have been reported and ends with a note saying
+/// so, as in [`aivi_hir::typecheck_module_with_max_errors`]; 0 means no limit. A module whose
+/// lowering reported errors only gets the structural checks, which tolerate unresolved names.
+pub fn validation_diagnostics(
+    db: &RootDatabase,
+    file: SourceFile,
+    max_errors: usize,
+) -> Arc<[Diagnostic]> {
+    let hir = hir_module(db, file);
+    let mode = if hir
+        .hir_diagnostics()
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+    {
+        ValidationMode::Structural
+    } else {
+        ValidationMode::RequireResolvedNames
+    };
+    Arc::from(
+        hir.module()
+            .validate_with_max_errors(mode, max_errors)
+            .into_diagnostics(),
+    )
+}
+
 /// Extract LSP symbols from the HIR module.
 pub fn symbol_index(db: &RootDatabase, file: SourceFile) -> Arc<[LspSymbol]> {
     hir_module(db, file).symbols_arc()
@@ -342,18 +376,91 @@ pub fn resolve_module_file(
     Workspace::discover(db, file).resolve_module_file(db, module)
 }
 
+/// Every `.aivi` file under the workspace root of `file`, including files on disk that no query
+/// has loaded yet. Bundled stdlib modules are not included.
+pub fn workspace_files(db: &RootDatabase, file: SourceFile) -> Vec<SourceFile> {
+    Workspace::discover(db, file).all_project_files(db)
+}
+
+/// Term names `file` would see from `aivi.prelude`: its exported values and functions plus the
+/// members of the classes it exports. Empty when the prelude cannot be resolved.
+pub fn prelude_term_names(db: &RootDatabase, file: SourceFile) -> HashSet<String> {
+    let Some(prelude) = resolve_module_file(db, file, &["aivi", "prelude"]) else {
+        return HashSet::new();
+    };
+    let hir = hir_module(db, prelude);
+    let module = hir.module();
+    let mut names = HashSet::new();
+    for exported in &hir.exported_names().names {
+        match exported.kind {
+            ExportedNameKind::Value | ExportedNameKind::Function | ExportedNameKind::Signal => {
+                names.insert(exported.name.clone());
+            }
+            ExportedNameKind::Class => {
+                let class = module.items().iter().find_map(|(_, item)| match item {
+                    Item::Class(class) if class.name.text() == exported.name => Some(class),
+                    _ => None,
+                });
+                names.extend(
+                    class
+                        .into_iter()
+                        .flat_map(|class| &class.members)
+                        .map(|member| member.name.text().to_owned()),
+                );
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
 /// Format the source file using the memoised CST.
 ///
 /// Returns `None` when the file has parse errors so the editor does not
 /// receive a format edit that might corrupt the file (e.g. by substituting
 /// unparseable regions with placeholder text).
 pub fn format_file(db: &RootDatabase, file: SourceFile) -> Option<String> {
+    format_file_with_options(db, file, &FormatOptions::default())
+}
+
+/// Like [`format_file`], with explicit formatter options.
+pub fn format_file_with_options(
+    db: &RootDatabase,
+    file: SourceFile,
+    options: &FormatOptions,
+) -> Option<String> {
     let parsed = parsed_file(db, file);
     if !parsed.diagnostics().is_empty() {
         return None;
     }
     let formatter = Formatter;
-    Some(formatter.format(parsed.cst()))
+    Some(formatter.format_with_options(parsed.cst(), options))
+}
+
+/// Formatter options from the `[format]` table of the file's workspace `aivi.toml`.
+///
+/// A missing or malformed manifest yields the defaults, so formatting never fails on config.
+pub fn load_format_options(db: &RootDatabase, file: SourceFile) -> FormatOptions {
+    parse_manifest(Workspace::discover(db, file).root())
+        .map(|manifest| manifest.format.format_options())
+        .unwrap_or_default()
+}
+
+/// Format the top-level items touching the zero-based lines `start_line..=end_line`.
+///
+/// Like [`format_file`], returns `None` when the file has parse errors.
+pub fn format_file_range(
+    db: &RootDatabase,
+    file: SourceFile,
+    start_line: usize,
+    end_line: usize,
+    options: &FormatOptions,
+) -> Option<RangeFormatResult> {
+    let parsed = parsed_file(db, file);
+    if !parsed.diagnostics().is_empty() {
+        return None;
+    }
+    Formatter.format_range(parsed.source(), parsed.cst(), start_line, end_line, options)
 }
 
 /// Collect all `hoist` declarations from every `.aivi` file in the workspace.
@@ -424,6 +531,22 @@ fn collect_workspace_hoist_items(
     db.store_workspace_hoist_cache_entry(workspace.root().to_path_buf(), Arc::from(result))
 }
 
+/// Span of the first `use` item in `file` that imports `module_name`.
+fn use_item_span(db: &RootDatabase, file: SourceFile, module_name: &str) -> Option<SourceSpan> {
+    let parsed = parsed_file(db, file);
+    parsed.cst().items.iter().find_map(|item| match item {
+        aivi_syntax::cst::Item::Use(item)
+            if item
+                .path
+                .as_ref()
+                .is_some_and(|path| path.as_dotted() == module_name) =>
+        {
+            Some(item.base.span)
+        }
+        _ => None,
+    })
+}
+
 /// Walk a module's CST and collect `hoist` declarations as `RawHoistItem`s.
 ///
 /// `declaring_module` is the dotted module path of the file being scanned
@@ -11,7 +11,8 @@ pub use backend::{
     whole_program_backend_unit_with_items,
 };
 pub use hir::{
-    HirModuleResult, all_diagnostics, exported_names, format_file, hir_module, resolve_module_file,
-    symbol_index,
+    HirModuleResult, all_diagnostics, exported_names, format_file, format_file_range,
+    format_file_with_options, hir_module, load_format_options, prelude_term_names,
+    resolve_module_file, symbol_index, validation_diagnostics, workspace_files,
 };
 pub use source::{ParsedFileResult, parsed_file};
@@ -4,10 +4,11 @@ use aivi_backend::cache::compute_program_fingerprint;
 use aivi_core::{IncludedItems, RuntimeFragmentSpec};
 use aivi_hir::GeneralExprOutcome;
 use aivi_query::{
-    RootDatabase, SourceFile, hir_module, reachable_workspace_hir_modules,
-    runtime_fragment_backend_fingerprint, runtime_fragment_backend_unit,
-    whole_program_backend_fingerprint, whole_program_backend_fingerprint_with_items,
-    whole_program_backend_unit, whole_program_backend_unit_with_items,
+    RootDatabase, SourceFile, doctest_module, hir_module, module_doctests,
+    reachable_workspace_hir_modules, runtime_fragment_backend_fingerprint,
+    runtime_fragment_backend_unit, whole_program_backend_fingerprint,
+    whole_program_backend_fingerprint_with_items, whole_program_backend_unit,
+    whole_program_backend_unit_with_items,
 };
 
 fn first_general_expr_fragment(module: &aivi_hir::Module) -> RuntimeFragmentSpec {
@@ -276,3 +277,79 @@ fn runtime_fragment_backend_fingerprint_matches_backend_content_fingerprint() {
         compute_program_fingerprint(unit.backend())
     );
 }
+
+#[test]
+fn doctest_module_runs_each_example_as_a_comparing_test() {
+    let db = RootDatabase::new();
+    let file = SourceFile::new(
+        &db,
+        PathBuf::from("geometry.aivi"),
+        concat!(
+            "/**\n",
+            " * Scale a length by two.\n",
+            " *\n",
+            " * ```aivi\n",
+            " * double 21\n",
+            " * => 42\n",
+            " * [double 1, double 2]\n",
+            " * => [2, 5]\n",
+            " * ```\n",
+            " **/\n",
+            "type Int -> Int\n",
+            "func double = n => n * 2\n",
+        )
+        .to_owned(),
+    );
+
+    let doctests = module_doctests(&db, file);
+    assert_eq!(
+        doctests
+            .iter()
+            .map(|doctest| (
+                doctest.owner.as_str(),
+                doctest.expression.as_str(),
+                doctest.expected.as_str()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("double", "double 21", "42"),
+            ("double", "[double 1, double 2]", "[2, 5]"),
+        ]
+    );
+    assert_eq!(
+        file.source(&db).slice(doctests[1].span),
+        "[double 1, double 2]"
+    );
+
+    let program = doctest_module(&db, file, &doctests);
+    let tests = program
+        .tests
+        .iter()
+        .map(|test| test.clone().expect("every example should become a test"))
+        .collect::<Vec<_>>();
+    assert_eq!(tests, vec!["aiviDoctest1", "aiviDoctest2"]);
+
+    let unit = whole_program_backend_unit(&db, program.file)
+        .expect("the doctest module should lower to a backend unit");
+    let backend = unit.backend();
+    let mut evaluator = aivi_backend::KernelEvaluator::new(backend);
+    let outcomes = tests
+        .iter()
+        .map(|name| {
+            let item = backend
+                .items()
+                .iter()
+                .find(|(_, item)| item.name.as_ref() == name.as_str())
+                .map(|(item_id, _)| item_id)
+                .expect("each doctest should lower to a backend item");
+            evaluator
+                .evaluate_item(item, &std::collections::BTreeMap::new())
+                .expect("each doctest should evaluate")
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        outcomes,
+        vec!["<task pure(Ok 42)>", "<task pure(Err [2, 4])>"]
+    );
+}
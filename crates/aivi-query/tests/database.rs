@@ -7,7 +7,7 @@ use std::{
 
 use aivi_query::{
     RootDatabase, SourceFile, all_diagnostics, exported_names, format_file, hir_module,
-    parsed_file, symbol_index,
+    module_docs, parsed_file, render_markdown, symbol_index,
 };
 
 fn fixture_path(relative: &str) -> PathBuf {
@@ -316,6 +316,9 @@ fn hir_queries_matrix_module_exports_public_api() {
         "NegativeWidth",
         "NegativeHeight",
         "RaggedRows",
+        "Singular",
+        "NotSymmetric",
+        "Svd",
         "init",
         "fromRows",
         "width",
@@ -324,6 +327,13 @@ fn hir_queries_matrix_module_exports_public_api() {
         "row",
         "at",
         "replaceAt",
+        "solve",
+        "determinant",
+        "inverse",
+        "transpose",
+        "multiply",
+        "svd",
+        "eigenvalues",
     ] {
         assert!(
             exported.find(name).is_some(),
@@ -584,6 +594,152 @@ fn hir_queries_prefer_workspace_modules_over_bundled_stdlib_fallback() {
     );
 }
 
+/// Lower every module in `files` and collect the `import-cycle` diagnostics they report, along
+/// with all other HIR diagnostics.
+fn import_cycle_diagnostics(
+    workspace: &TempDir,
+    files: &[(&str, &str)],
+) -> (Vec<aivi_base::Diagnostic>, Vec<aivi_base::Diagnostic>) {
+    workspace.write("aivi.toml", "");
+    let db = RootDatabase::new();
+    let modules = files
+        .iter()
+        .map(|(relative, text)| {
+            SourceFile::new(&db, workspace.write(relative, text), (*text).to_owned())
+        })
+        .collect::<Vec<_>>();
+    modules
+        .into_iter()
+        .flat_map(|module| hir_module(&db, module).hir_diagnostics().to_vec())
+        .partition(|diagnostic| {
+            diagnostic
+                .code
+                .is_some_and(|code| code.to_string() == "hir::import-cycle")
+        })
+}
+
+#[test]
+fn hir_queries_report_a_two_module_import_cycle_once() {
+    let workspace = TempDir::new("import-cycle-two");
+    let (cycles, others) = import_cycle_diagnostics(
+        &workspace,
+        &[
+            (
+                "app/a.aivi",
+                "use app.b (\n    y\n)\n\nvalue x : Int = y\n\nvalue z : Int = 3\n\nexport (x, z)\n",
+            ),
+            (
+                "app/b.aivi",
+                "use app.a (\n    z\n)\n\nvalue y : Int = z\n\nexport y\n",
+            ),
+        ],
+    );
+
+    assert_eq!(cycles.len(), 1, "expected one cycle diagnostic: {cycles:?}");
+    assert_eq!(
+        cycles[0].message,
+        "import cycle detected: app.a -> app.b -> app.a"
+    );
+    assert_eq!(
+        cycles[0].labels.len(),
+        2,
+        "both `use` items of the cycle should be labelled: {:?}",
+        cycles[0].labels
+    );
+    assert!(
+        others.is_empty(),
+        "names imported across the cycle should not cascade into more errors: {others:?}"
+    );
+}
+
+#[test]
+fn hir_queries_report_a_three_module_import_cycle_once() {
+    let workspace = TempDir::new("import-cycle-three");
+    let (cycles, others) = import_cycle_diagnostics(
+        &workspace,
+        &[
+            (
+                "app/a.aivi",
+                "use app.b (\n    y\n)\n\nvalue x : Int = y\n\nexport x\n",
+            ),
+            (
+                "app/b.aivi",
+                "use app.c (\n    w\n)\n\nvalue y : Int = w\n\nexport y\n",
+            ),
+            (
+                "app/c.aivi",
+                "use app.a (\n    x\n)\n\nvalue w : Int = 1\n\nvalue v : Int = x\n\nexport w\n",
+            ),
+        ],
+    );
+
+    assert_eq!(cycles.len(), 1, "expected one cycle diagnostic: {cycles:?}");
+    assert_eq!(
+        cycles[0].message,
+        "import cycle detected: app.a -> app.b -> app.c -> app.a"
+    );
+    assert_eq!(
+        cycles[0].labels.len(),
+        3,
+        "every `use` item of the cycle should be labelled: {:?}",
+        cycles[0].labels
+    );
+    assert!(
+        others.is_empty(),
+        "names imported across the cycle should not cascade into more errors: {others:?}"
+    );
+}
+
+#[test]
+fn hir_queries_report_self_imports_as_cycles() {
+    let workspace = TempDir::new("import-cycle-self");
+    let (cycles, _) = import_cycle_diagnostics(
+        &workspace,
+        &[(
+            "app/a.aivi",
+            "use app.a (\n    x\n)\n\nvalue x : Int = 1\n\nexport x\n",
+        )],
+    );
+
+    assert_eq!(cycles.len(), 1, "expected one cycle diagnostic: {cycles:?}");
+    assert_eq!(
+        cycles[0].message,
+        "import cycle detected: module `app.a` imports itself"
+    );
+}
+
+#[test]
+fn derived_ord_does_not_reach_through_imported_sum_payloads() {
+    let workspace = TempDir::new("derived-ord-imported-payload");
+    workspace.write("aivi.toml", "");
+    workspace.write(
+        "app/level.aivi",
+        "type Level = Low | High\n\nexport (Level, Low, High)\n",
+    );
+    let text = "use app.level (\n    Level\n    Low\n    High\n)\n\ntype Alarm = Alarm Level Int\n\nvalue louder : Ordering = compare (Alarm Low 1) (Alarm High 0)\n";
+    let db = RootDatabase::new();
+    let main = SourceFile::new(&db, workspace.write("app/main.aivi", text), text.to_owned());
+    let hir = hir_module(&db, main);
+    assert!(
+        hir.hir_diagnostics().is_empty(),
+        "the imported sum should resolve: {:?}",
+        hir.hir_diagnostics()
+    );
+
+    let report = hir
+        .module()
+        .validate(aivi_hir::ValidationMode::RequireResolvedNames);
+    assert!(
+        report
+            .diagnostics()
+            .iter()
+            .any(|diagnostic| diagnostic.message.contains("Alarm")),
+        "the runtime cannot rank another module's constructors, so deriving `Ord` for `Alarm` \
+         should be rejected: {:?}",
+        report.diagnostics()
+    );
+}
+
 #[test]
 fn changing_an_imported_file_invalidates_transitive_hir_dependents() {
     let workspace = TempDir::new("workspace-invalidation");
@@ -635,3 +791,44 @@ fn changing_an_imported_file_invalidates_transitive_hir_dependents() {
         second.hir_diagnostics()
     );
 }
+
+#[test]
+fn module_docs_list_exported_declarations_with_signatures_and_comments() {
+    let workspace = TempDir::new("module-docs");
+    workspace.write("aivi.toml", "");
+    let path = workspace.write(
+        "geometry.aivi",
+        "/** Plane geometry helpers. **/\n\n/**\n * Scale a length by two.\n **/\ntype Int -> Int\nfunc double = n => n * 2\n\n/** The unit length. **/\nvalue unit : Int = 1\n\nvalue hidden = 3\n\nexport (double, unit)\n",
+    );
+
+    let db = RootDatabase::new();
+    let file = SourceFile::new(
+        &db,
+        path.clone(),
+        fs::read_to_string(&path).expect("docs fixture should exist"),
+    );
+    let docs = module_docs(&db, file);
+    assert_eq!(docs.module_name, "geometry");
+    assert_eq!(docs.summary.as_deref(), Some("Plane geometry helpers."));
+    assert_eq!(
+        docs.entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["double", "unit"],
+        "only exported names should be documented"
+    );
+
+    let markdown = render_markdown(&docs);
+    assert!(
+        markdown.contains(
+            "## `double`\n\n_function_\n\n```aivi\ndouble : Int -> Int\n```\n\nScale a length by two.\n"
+        ),
+        "exported function should appear with its signature and comment:\n{markdown}"
+    );
+    assert!(
+        markdown.contains("```aivi\nunit : Int\n```\n\nThe unit length.\n"),
+        "exported value should appear with its signature and comment:\n{markdown}"
+    );
+    assert!(!markdown.contains("hidden"));
+}
@@ -179,11 +179,33 @@ impl TaskRuntimeSpec {
 #[derive(Clone, Debug, Default)]
 pub struct CancellationObserver {
     state: Arc<AtomicBool>,
+    /// The enclosing work's observer when this one watches nested work.
+    parent: Option<Arc<CancellationObserver>>,
 }
 
 impl CancellationObserver {
     pub fn is_cancelled(&self) -> bool {
         self.state.load(Ordering::Acquire)
+            || self
+                .parent
+                .as_deref()
+                .is_some_and(CancellationObserver::is_cancelled)
+    }
+
+    /// Observe nested work that stops when `state` is set or when `parent` is cancelled.
+    pub(crate) fn nested(parent: Option<&Self>, state: Arc<AtomicBool>) -> Self {
+        Self {
+            state,
+            parent: parent.cloned().map(Arc::new),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn from_flag(state: Arc<AtomicBool>) -> Self {
+        Self {
+            state,
+            parent: None,
+        }
     }
 }
 
@@ -200,6 +222,7 @@ impl CancellationHandle {
     fn observer(&self) -> CancellationObserver {
         CancellationObserver {
             state: self.state.clone(),
+            parent: None,
         }
     }
 
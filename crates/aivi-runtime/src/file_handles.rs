@@ -0,0 +1,134 @@
+//! Open files behind `aivi.fs.open`.
+//!
+//! A handle is a plain `{ id, path }` record, so it can flow through ordinary runtime values. The
+//! open file itself lives in a [`FileHandleTable`] owned by the run's `SourceProviderContext`.
+//! Ids are drawn at random, so a record built by hand does not name another open file.
+//!
+//! Runtime values carry no destructor, so dropping the last copy of a handle does not close its
+//! file. `close` flushes and releases one entry; any handle the program never closes stays open
+//! until the last clone of the context drops the table. The table holds at most
+//! [`MAX_OPEN_FILES`] entries, so a program that forgets to close handles in a loop gets a task
+//! error instead of exhausting the process's file descriptors.
+
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
+};
+
+/// Files one run may hold open through `aivi.fs.open` at the same time.
+pub(crate) const MAX_OPEN_FILES: usize = 256;
+
+#[derive(Default)]
+pub(crate) struct FileHandleTable {
+    open: BTreeMap<i64, OpenFile>,
+}
+
+enum OpenFile {
+    Read {
+        path: Box<str>,
+        reader: BufReader<File>,
+    },
+    Write {
+        path: Box<str>,
+        writer: BufWriter<File>,
+    },
+}
+
+impl FileHandleTable {
+    /// Open `path` for reading (`"r"`), truncating writes (`"w"`), or appending (`"a"`).
+    pub(crate) fn open(&mut self, path: &str, mode: &str) -> Result<i64, String> {
+        let mut options = OpenOptions::new();
+        match mode {
+            "r" => options.read(true),
+            "w" => options.write(true).create(true).truncate(true),
+            "a" => options.append(true).create(true),
+            other => {
+                return Err(format!(
+                    "fs.open mode must be \"r\", \"w\", or \"a\", found {other:?}"
+                ));
+            }
+        };
+        if self.open.len() >= MAX_OPEN_FILES {
+            return Err(format!(
+                "fs.open: {MAX_OPEN_FILES} files are already open; close one before opening {path}"
+            ));
+        }
+        let file = options
+            .open(path)
+            .map_err(|error| format!("failed to open {path}: {error}"))?;
+        let path = Box::<str>::from(path);
+        let entry = if mode == "r" {
+            OpenFile::Read {
+                path,
+                reader: BufReader::new(file),
+            }
+        } else {
+            OpenFile::Write {
+                path,
+                writer: BufWriter::new(file),
+            }
+        };
+        let id = loop {
+            let id = fastrand::i64(0..);
+            if !self.open.contains_key(&id) {
+                break id;
+            }
+        };
+        self.open.insert(id, entry);
+        Ok(id)
+    }
+
+    /// Read the next line without its `\n` or `\r\n` terminator, or `None` at end of file.
+    pub(crate) fn read_line(&mut self, id: i64) -> Result<Option<String>, String> {
+        match self.open.get_mut(&id) {
+            Some(OpenFile::Read { path, reader }) => {
+                let mut line = String::new();
+                let read = reader
+                    .read_line(&mut line)
+                    .map_err(|error| format!("failed to read {path}: {error}"))?;
+                if read == 0 {
+                    return Ok(None);
+                }
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
+                }
+                Ok(Some(line))
+            }
+            Some(OpenFile::Write { path, .. }) => {
+                Err(format!("fs.readLine: {path} was not opened for reading"))
+            }
+            None => Err(closed_handle_error("fs.readLine", id)),
+        }
+    }
+
+    pub(crate) fn write(&mut self, id: i64, text: &str) -> Result<(), String> {
+        match self.open.get_mut(&id) {
+            Some(OpenFile::Write { path, writer }) => writer
+                .write_all(text.as_bytes())
+                .map_err(|error| format!("failed to write {path}: {error}")),
+            Some(OpenFile::Read { path, .. }) => {
+                Err(format!("fs.write: {path} was not opened for writing"))
+            }
+            None => Err(closed_handle_error("fs.write", id)),
+        }
+    }
+
+    /// Flush and release one handle. Closing a handle twice is an error.
+    pub(crate) fn close(&mut self, id: i64) -> Result<(), String> {
+        match self.open.remove(&id) {
+            Some(OpenFile::Write { path, mut writer }) => writer
+                .flush()
+                .map_err(|error| format!("failed to flush {path}: {error}")),
+            Some(OpenFile::Read { .. }) => Ok(()),
+            None => Err(closed_handle_error("fs.close", id)),
+        }
+    }
+}
+
+fn closed_handle_error(operation: &str, id: i64) -> String {
+    format!("{operation}: file handle #{id} is not open")
+}
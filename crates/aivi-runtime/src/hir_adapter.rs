@@ -2514,6 +2514,7 @@ fn collect_direct_signal_dependencies(
             | hir::ExprKind::Decimal(_)
             | hir::ExprKind::BigInt(_)
             | hir::ExprKind::SuffixedInteger(_)
+            | hir::ExprKind::Char(_)
             | hir::ExprKind::AmbientSubject
             | hir::ExprKind::Regex(_)
             | hir::ExprKind::Cluster(_)
@@ -3,17 +3,23 @@
 //! Runtime and scheduler foundations for the AIVI execution engine.
 
 pub mod effects;
+mod file_handles;
 pub mod glib_adapter;
 pub mod graph;
 pub mod hir_adapter;
+mod log_output;
+mod process_handles;
 pub mod providers;
 pub mod reactive_program;
 pub mod runtime_errors;
 pub mod scheduler;
+mod snapshots;
 mod source_decode;
 pub mod source_map;
 pub mod startup;
 pub mod task_executor;
+pub mod task_scheduler;
+mod ws_handles;
 
 pub use effects::{
     CancellationObserver, PublicationPortError, RuntimeSourceProvider, SourceActiveWhenEvaluator,
@@ -86,5 +92,6 @@ pub use startup::{
 pub use task_executor::{
     CustomCapabilityCommandExecutor, RuntimeTaskExecutionError, execute_runtime_db_task_plan,
     execute_runtime_task_plan, execute_runtime_task_plan_with_context, execute_runtime_value,
-    execute_runtime_value_with_context,
+    execute_runtime_value_with_context, execute_runtime_value_with_context_and_applier,
 };
+pub use task_scheduler::run_concurrent;
@@ -0,0 +1,151 @@
+//! Filtering and formatting for entries written through `@source log` handles.
+//!
+//! `AIVI_LOG` names the lowest level that is written (`debug`, `info`, `warn`, `error`, or
+//! `fatal`); when it is unset or unrecognized every entry is written. `AIVI_LOG_FORMAT=json`
+//! replaces the `[LEVEL] message {key=value}` text lines with one JSON object per line.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) const LOG_LEVEL_ENV: &str = "AIVI_LOG";
+pub(crate) const LOG_FORMAT_ENV: &str = "AIVI_LOG_FORMAT";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LogFormat {
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    pub(crate) fn from_env_value(value: Option<&str>) -> Self {
+        match value {
+            Some(value) if value.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// Whether an entry at `level` passes the `AIVI_LOG` value `filter`. Levels the filter does not
+/// know, on either side, never suppress an entry.
+pub(crate) fn level_enabled(filter: Option<&str>, level: &str) -> bool {
+    match (filter.and_then(level_rank), level_rank(level)) {
+        (Some(minimum), Some(rank)) => rank >= minimum,
+        _ => true,
+    }
+}
+
+fn level_rank(level: &str) -> Option<u8> {
+    let rank = match level.trim().to_ascii_uppercase().as_str() {
+        "DEBUG" => 0,
+        "INFO" => 1,
+        "WARN" | "WARNING" => 2,
+        "ERROR" => 3,
+        "FATAL" => 4,
+        _ => return None,
+    };
+    Some(rank)
+}
+
+/// Render one entry as a single line without the trailing newline.
+pub(crate) fn format_log_line(
+    format: LogFormat,
+    level: &str,
+    message: &str,
+    fields: &[(Box<str>, Box<str>)],
+) -> String {
+    match format {
+        LogFormat::Text if fields.is_empty() => format!("[{level}] {message}"),
+        LogFormat::Text => {
+            let fields = fields
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>();
+            format!("[{level}] {message} {{{}}}", fields.join(", "))
+        }
+        LogFormat::Json => {
+            let timestamp_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis());
+            json_log_line(timestamp_ms, level, message, fields)
+        }
+    }
+}
+
+fn json_log_line(
+    timestamp_ms: u128,
+    level: &str,
+    message: &str,
+    fields: &[(Box<str>, Box<str>)],
+) -> String {
+    let fields = fields
+        .iter()
+        .map(|(key, value)| {
+            (
+                key.to_string(),
+                serde_json::Value::String(value.to_string()),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    format!(
+        "{{\"timestamp\":{timestamp_ms},\"level\":{},\"message\":{},\"fields\":{}}}",
+        serde_json::Value::from(level),
+        serde_json::Value::from(message),
+        serde_json::Value::Object(fields),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_keeps_entries_at_or_above_the_minimum_level() {
+        assert!(level_enabled(None, "DEBUG"));
+        assert!(!level_enabled(Some("warn"), "INFO"));
+        assert!(level_enabled(Some("warn"), "WARN"));
+        assert!(level_enabled(Some("WARN"), "FATAL"));
+        assert!(level_enabled(Some("verbose"), "DEBUG"));
+        assert!(level_enabled(Some("error"), "AUDIT"));
+    }
+
+    #[test]
+    fn text_lines_list_fields_after_the_message() {
+        assert_eq!(
+            format_log_line(LogFormat::Text, "INFO", "Started", &[]),
+            "[INFO] Started"
+        );
+        assert_eq!(
+            format_log_line(
+                LogFormat::Text,
+                "WARN",
+                "Slow query",
+                &[("mailbox".into(), "primary".into())],
+            ),
+            "[WARN] Slow query {mailbox=primary}"
+        );
+    }
+
+    #[test]
+    fn json_lines_escape_quotes_and_newlines() {
+        let line = json_log_line(
+            1_700_000_000_000,
+            "ERROR",
+            "bad \"input\"\nsecond line",
+            &[("path".into(), "C:\\tmp".into())],
+        );
+        assert_eq!(
+            line,
+            r#"{"timestamp":1700000000000,"level":"ERROR","message":"bad \"input\"\nsecond line","fields":{"path":"C:\\tmp"}}"#
+        );
+        assert!(!line.contains('\n'));
+        let parsed: serde_json::Value =
+            serde_json::from_str(&line).expect("a JSON log line should parse");
+        assert_eq!(parsed["message"], "bad \"input\"\nsecond line");
+    }
+
+    #[test]
+    fn json_format_is_selected_case_insensitively() {
+        assert_eq!(LogFormat::from_env_value(Some("JSON")), LogFormat::Json);
+        assert_eq!(LogFormat::from_env_value(Some("text")), LogFormat::Text);
+        assert_eq!(LogFormat::from_env_value(None), LogFormat::Text);
+    }
+}
@@ -0,0 +1,233 @@
+//! Child processes behind `aivi.process.spawn`.
+//!
+//! Like file handles and web sockets, a process handle is a plain `{ id, command }` record and the
+//! child itself lives in a [`ProcessTable`] owned by the run's `SourceProviderContext`. Ids are
+//! drawn at random and the table holds at most [`MAX_RUNNING_PROCESSES`] entries, as for files.
+//!
+//! The table lock is only held to look a child up, store it, or remove it. A child's stdin, stdout,
+//! and process each have a lock of their own, and `wait` polls the child instead of blocking on
+//! it, so a task waiting for one child neither blocks other children nor a `kill` of the same one,
+//! and it notices when its task is cancelled. `wait` and `kill` reap the child and release its
+//! entry; any child the program never reaps is killed when the table is dropped.
+
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, ExitStatus, Stdio},
+    sync::{Arc, Mutex, MutexGuard, mpsc},
+    thread,
+    time::Duration,
+};
+
+use crate::CancellationObserver;
+
+/// Children one run may have running through `aivi.process.spawn` at the same time.
+pub(crate) const MAX_RUNNING_PROCESSES: usize = 256;
+
+/// How often a waiting task checks whether its child exited or its task was cancelled.
+const WAIT_POLL: Duration = Duration::from_millis(10);
+
+pub(crate) type SharedProcess = Arc<RunningProcess>;
+
+#[derive(Default)]
+pub(crate) struct ProcessTable {
+    running: BTreeMap<i64, SharedProcess>,
+}
+
+pub(crate) struct RunningProcess {
+    command: Box<str>,
+    child: Mutex<Child>,
+    stdin: Mutex<Option<ChildStdin>>,
+    stdout: Mutex<Option<ChildStdout>>,
+}
+
+impl ProcessTable {
+    /// Start `command` with piped stdin and stdout. Stderr stays attached to the program's own
+    /// stderr so a chatty child cannot block on a pipe nobody reads.
+    pub(crate) fn spawn(
+        &mut self,
+        command: &str,
+        args: &[Box<str>],
+        env: &[(Box<str>, Box<str>)],
+    ) -> Result<i64, String> {
+        if self.running.len() >= MAX_RUNNING_PROCESSES {
+            return Err(format!(
+                "process.spawn: {MAX_RUNNING_PROCESSES} processes are already running; wait for or kill one before spawning {command}"
+            ));
+        }
+        let mut child = Command::new(command)
+            .args(args.iter().map(AsRef::as_ref))
+            .envs(
+                env.iter()
+                    .map(|(key, value)| (key.as_ref(), value.as_ref())),
+            )
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|error| format!("failed to spawn {command}: {error}"))?;
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take();
+        let id = loop {
+            let id = fastrand::i64(0..);
+            if !self.running.contains_key(&id) {
+                break id;
+            }
+        };
+        self.running.insert(
+            id,
+            Arc::new(RunningProcess {
+                command: command.into(),
+                child: Mutex::new(child),
+                stdin: Mutex::new(stdin),
+                stdout: Mutex::new(stdout),
+            }),
+        );
+        Ok(id)
+    }
+
+    pub(crate) fn get(&self, operation: &str, id: i64) -> Result<SharedProcess, String> {
+        self.running
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| unknown_process_error(operation, id))
+    }
+
+    /// Release one handle. Removing a handle twice is an error.
+    pub(crate) fn remove(&mut self, operation: &str, id: i64) -> Result<SharedProcess, String> {
+        self.running
+            .remove(&id)
+            .ok_or_else(|| unknown_process_error(operation, id))
+    }
+
+    /// Release the handle of a child that `wait` has reaped, unless a `kill` released it first.
+    pub(crate) fn release(&mut self, id: i64, process: &SharedProcess) {
+        if self
+            .running
+            .get(&id)
+            .is_some_and(|running| Arc::ptr_eq(running, process))
+        {
+            self.running.remove(&id);
+        }
+    }
+}
+
+impl Drop for ProcessTable {
+    fn drop(&mut self) {
+        for process in self.running.values() {
+            let mut child = lock(&process.child);
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl RunningProcess {
+    pub(crate) fn write_stdin(&self, text: &str) -> Result<(), String> {
+        let command = &self.command;
+        let mut stdin = lock(&self.stdin);
+        let stdin = stdin
+            .as_mut()
+            .ok_or_else(|| format!("process.writeStdin: stdin of {command} is already closed"))?;
+        stdin
+            .write_all(text.as_bytes())
+            .and_then(|()| stdin.flush())
+            .map_err(|error| format!("failed to write to {command}: {error}"))
+    }
+
+    /// Close the child's stdin and read its stdout to the end. Invalid UTF-8 is replaced. Reading
+    /// stdout a second time returns empty text.
+    ///
+    /// A cancellable read runs on a helper thread, which is abandoned on cancellation together
+    /// with the rest of the output; it finishes once the child exits or is killed.
+    pub(crate) fn read_stdout(
+        &self,
+        cancellation: Option<&CancellationObserver>,
+    ) -> Result<String, String> {
+        // A child that reads its input to the end would otherwise never finish its output.
+        drop(lock(&self.stdin).take());
+        let Some(mut stdout) = lock(&self.stdout).take() else {
+            return Ok(String::new());
+        };
+        let command = &self.command;
+        let read = |stdout: &mut ChildStdout| {
+            let mut bytes = Vec::new();
+            stdout.read_to_end(&mut bytes).map(|_| bytes)
+        };
+        let bytes = match cancellation {
+            None => read(&mut stdout),
+            Some(cancellation) => {
+                let (sender, receiver) = mpsc::sync_channel(1);
+                thread::spawn(move || {
+                    let _ = sender.send(read(&mut stdout));
+                });
+                loop {
+                    if cancellation.is_cancelled() {
+                        return Err("process.readStdout was cancelled".to_owned());
+                    }
+                    match receiver.recv_timeout(WAIT_POLL) {
+                        Ok(bytes) => break bytes,
+                        Err(mpsc::RecvTimeoutError::Timeout) => {}
+                        Err(mpsc::RecvTimeoutError::Disconnected) => {
+                            return Err(format!("failed to read from {command}: reader stopped"));
+                        }
+                    }
+                }
+            }
+        }
+        .map_err(|error| format!("failed to read from {command}: {error}"))?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Close the child's stdin and wait for it to exit. A child killed meanwhile reports the
+    /// signal that killed it.
+    pub(crate) fn wait(&self, cancellation: Option<&CancellationObserver>) -> Result<i64, String> {
+        drop(lock(&self.stdin).take());
+        loop {
+            if cancellation.is_some_and(CancellationObserver::is_cancelled) {
+                return Err("process.wait was cancelled".to_owned());
+            }
+            let status = lock(&self.child)
+                .try_wait()
+                .map_err(|error| format!("failed to wait for {}: {error}", self.command))?;
+            if let Some(status) = status {
+                return Ok(exit_code(status));
+            }
+            thread::sleep(WAIT_POLL);
+        }
+    }
+
+    /// Kill the child and reap it. Killing a child that already exited is not an error.
+    pub(crate) fn kill(&self) -> Result<(), String> {
+        let mut child = lock(&self.child);
+        child
+            .kill()
+            .and_then(|()| child.wait().map(drop))
+            .map_err(|error| format!("failed to kill {}: {error}", self.command))
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .expect("process handle mutex should not be poisoned")
+}
+
+/// The exit code, or `128 + signal` for a child ended by a signal, as shells report it.
+fn exit_code(status: ExitStatus) -> i64 {
+    if let Some(code) = status.code() {
+        return i64::from(code);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + i64::from(signal);
+        }
+    }
+    -1
+}
+
+fn unknown_process_error(operation: &str, id: i64) -> String {
+    format!("{operation}: process #{id} is not running")
+}
@@ -104,6 +104,45 @@ pub struct SourceProviderContext {
     stdin_text: Arc<OnceLock<Result<Box<str>, Box<str>>>>,
     custom_capability_command_executor: Option<Arc<dyn CustomCapabilityCommandExecutor>>,
     decode_diagnostic_reporter: Arc<std::sync::Mutex<Option<Arc<DecodeDiagnosticReporter>>>>,
+    intercept_process_exit: bool,
+    snapshot_dir: Option<Arc<PathBuf>>,
+    file_handles: Arc<Mutex<crate::file_handles::FileHandleTable>>,
+    web_sockets: Arc<Mutex<crate::ws_handles::WebSocketTable>>,
+    processes: Arc<Mutex<crate::process_handles::ProcessTable>>,
+    log_capture: Option<Arc<Mutex<String>>>,
+    cancellation: Option<crate::CancellationObserver>,
+    virtual_clock: Option<Arc<Mutex<VirtualClock>>>,
+}
+
+/// Controllable time source behind [`SourceProviderContext::with_virtual_clock`].
+///
+/// `now_ms` is the wall-clock reading and may be moved anywhere by `test.setClock`.
+/// `elapsed_ms` only grows, so monotonic readings stay ordered after the wall clock is reset.
+#[derive(Debug)]
+pub(crate) struct VirtualClock {
+    now_ms: i64,
+    elapsed_ms: i64,
+}
+
+impl VirtualClock {
+    pub(crate) fn now_ms(&self) -> i64 {
+        self.now_ms
+    }
+
+    pub(crate) fn monotonic_ms(&self) -> i64 {
+        self.elapsed_ms
+    }
+
+    /// Move both readings forward. Negative amounts are ignored, like negative sleeps.
+    pub(crate) fn advance(&mut self, ms: i64) {
+        let ms = ms.max(0);
+        self.now_ms = self.now_ms.saturating_add(ms);
+        self.elapsed_ms = self.elapsed_ms.saturating_add(ms);
+    }
+
+    pub(crate) fn set(&mut self, epoch_ms: i64) {
+        self.now_ms = epoch_ms;
+    }
 }
 
 type DecodeDiagnosticReporter = dyn Fn(
@@ -148,6 +187,14 @@ impl SourceProviderContext {
             stdin_text: Arc::new(OnceLock::new()),
             custom_capability_command_executor: None,
             decode_diagnostic_reporter: Arc::new(std::sync::Mutex::new(None)),
+            intercept_process_exit: false,
+            snapshot_dir: None,
+            file_handles: Arc::new(Mutex::new(crate::file_handles::FileHandleTable::default())),
+            web_sockets: Arc::new(Mutex::new(crate::ws_handles::WebSocketTable::default())),
+            processes: Arc::new(Mutex::new(crate::process_handles::ProcessTable::default())),
+            log_capture: None,
+            cancellation: None,
+            virtual_clock: None,
         }
     }
 
@@ -179,6 +226,153 @@ impl SourceProviderContext {
         self
     }
 
+    /// Turn `process.exit` into an ordinary task failure instead of terminating the host
+    /// process. Test runners use this so one test cannot end the whole suite.
+    pub fn with_process_exit_intercepted(mut self) -> Self {
+        self.intercept_process_exit = true;
+        self
+    }
+
+    pub(crate) fn intercepts_process_exit(&self) -> bool {
+        self.intercept_process_exit
+    }
+
+    /// Resolve `aivi.test.matchesSnapshot` names under `__snapshots__/<module>/` beside
+    /// `test_file`.
+    pub fn with_snapshot_test_file(mut self, test_file: &Path) -> Self {
+        self.snapshot_dir = Some(Arc::new(crate::snapshots::snapshot_dir_for_test_file(
+            test_file,
+        )));
+        self
+    }
+
+    pub(crate) fn snapshot_dir(&self) -> Option<&Path> {
+        self.snapshot_dir.as_deref().map(PathBuf::as_path)
+    }
+
+    /// Keep log entries in memory instead of writing them to stderr, until
+    /// [`Self::take_captured_log`] collects them. Test runners use this to show the log of a
+    /// failing test only.
+    pub fn with_log_capture(mut self) -> Self {
+        self.log_capture = Some(Arc::new(Mutex::new(String::new())));
+        self
+    }
+
+    /// Let long-running tasks such as `time.sleep` stop early once `observer` reports that the
+    /// task driving them was cancelled.
+    pub fn with_cancellation(mut self, observer: crate::CancellationObserver) -> Self {
+        self.cancellation = Some(observer);
+        self
+    }
+
+    /// Read `time.nowMs` and `time.monotonicMs` from a clock that starts at `start_epoch_ms`
+    /// and moves only when told to. `time.sleep` advances it instead of blocking, and
+    /// `aivi.test.advanceClock` / `aivi.test.setClock` become available. Every clone of the
+    /// context shares the same clock.
+    pub fn with_virtual_clock(mut self, start_epoch_ms: i64) -> Self {
+        self.virtual_clock = Some(Arc::new(Mutex::new(VirtualClock {
+            now_ms: start_epoch_ms,
+            elapsed_ms: 0,
+        })));
+        self
+    }
+
+    pub(crate) fn virtual_clock(&self) -> Option<std::sync::MutexGuard<'_, VirtualClock>> {
+        self.virtual_clock.as_ref().map(|clock| {
+            clock
+                .lock()
+                .expect("virtual clock mutex should not be poisoned")
+        })
+    }
+
+    /// A context for nested work that is cancelled with this one or when `state` is set.
+    pub(crate) fn with_nested_cancellation(&self, state: Arc<AtomicBool>) -> Self {
+        let observer = crate::CancellationObserver::nested(self.cancellation.as_ref(), state);
+        self.clone().with_cancellation(observer)
+    }
+
+    pub(crate) fn cancellation(&self) -> Option<&crate::CancellationObserver> {
+        self.cancellation.as_ref()
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(crate::CancellationObserver::is_cancelled)
+    }
+
+    /// Drain the lines captured since the last call, one entry per line.
+    pub fn take_captured_log(&self) -> String {
+        self.log_capture
+            .as_ref()
+            .map(|capture| {
+                std::mem::take(
+                    &mut *capture
+                        .lock()
+                        .expect("log capture mutex should not be poisoned"),
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// Write one `@source log` entry, honoring `AIVI_LOG` and `AIVI_LOG_FORMAT`.
+    pub(crate) fn emit_log(&self, level: &str, message: &str, fields: &[(Box<str>, Box<str>)]) {
+        let filter = self.env.get(crate::log_output::LOG_LEVEL_ENV);
+        if !crate::log_output::level_enabled(filter.map(String::as_str), level) {
+            return;
+        }
+        let format = crate::log_output::LogFormat::from_env_value(
+            self.env
+                .get(crate::log_output::LOG_FORMAT_ENV)
+                .map(String::as_str),
+        );
+        let line = crate::log_output::format_log_line(format, level, message, fields);
+        match &self.log_capture {
+            Some(capture) => {
+                let mut captured = capture
+                    .lock()
+                    .expect("log capture mutex should not be poisoned");
+                captured.push_str(&line);
+                captured.push('\n');
+            }
+            None => eprintln!("{line}"),
+        }
+    }
+
+    pub(crate) fn updates_snapshots(&self) -> bool {
+        self.env
+            .get(crate::snapshots::UPDATE_SNAPSHOTS_ENV)
+            .is_some_and(|value| value == "1")
+    }
+
+    /// Files opened by `aivi.fs.open` during this run, shared by every clone of the context.
+    pub(crate) fn file_handles(
+        &self,
+    ) -> std::sync::MutexGuard<'_, crate::file_handles::FileHandleTable> {
+        self.file_handles
+            .lock()
+            .expect("file handle table mutex should not be poisoned")
+    }
+
+    /// Sockets opened by `aivi.ws.connect` during this run, shared by every clone of the context.
+    pub(crate) fn web_sockets(
+        &self,
+    ) -> std::sync::MutexGuard<'_, crate::ws_handles::WebSocketTable> {
+        self.web_sockets
+            .lock()
+            .expect("web socket table mutex should not be poisoned")
+    }
+
+    /// Children started by `aivi.process.spawn` during this run, shared by every clone of the
+    /// context.
+    pub(crate) fn processes(
+        &self,
+    ) -> std::sync::MutexGuard<'_, crate::process_handles::ProcessTable> {
+        self.processes
+            .lock()
+            .expect("process table mutex should not be poisoned")
+    }
+
     pub fn with_custom_capability_command_executor(
         mut self,
         executor: Arc<dyn CustomCapabilityCommandExecutor>,
@@ -219,33 +413,49 @@ impl SourceProviderContext {
         }
     }
 
-    fn args_runtime_value(&self) -> RuntimeValue {
+    pub(crate) fn args_runtime_value(&self) -> RuntimeValue {
         RuntimeValue::List(
             self.args
                 .iter()
                 .cloned()
-                .map(|arg| RuntimeValue::Text(arg.into_boxed_str()))
+                .map(|arg| RuntimeValue::Text(arg.into()))
                 .collect(),
         )
     }
 
     fn cwd_runtime_value(&self) -> RuntimeValue {
-        RuntimeValue::Text(self.cwd.to_string_lossy().into_owned().into_boxed_str())
+        RuntimeValue::Text(self.cwd.to_string_lossy().into_owned().into())
     }
 
     fn app_dir_runtime_value(&self) -> RuntimeValue {
-        RuntimeValue::Text(self.app_dir.to_string_lossy().into_owned().into_boxed_str())
+        RuntimeValue::Text(self.app_dir.to_string_lossy().into_owned().into())
     }
 
-    fn env_runtime_value(&self, key: &str) -> RuntimeValue {
+    pub(crate) fn env_runtime_value(&self, key: &str) -> RuntimeValue {
         match self.env.get(key) {
-            Some(value) => RuntimeValue::OptionSome(Box::new(RuntimeValue::Text(
-                value.clone().into_boxed_str(),
-            ))),
+            Some(value) => {
+                RuntimeValue::OptionSome(Box::new(RuntimeValue::Text(value.as_str().into())))
+            }
             None => RuntimeValue::OptionNone,
         }
     }
 
+    /// Every `(name, value)` pair whose name starts with `prefix`, in name order.
+    pub(crate) fn env_entries_runtime_value(&self, prefix: &str) -> RuntimeValue {
+        RuntimeValue::List(
+            self.env
+                .iter()
+                .filter(|(name, _)| name.starts_with(prefix))
+                .map(|(name, value)| {
+                    RuntimeValue::Tuple(vec![
+                        RuntimeValue::Text(name.as_str().into()),
+                        RuntimeValue::Text(value.as_str().into()),
+                    ])
+                })
+                .collect(),
+        )
+    }
+
     fn stdin_text(&self) -> Result<Box<str>, Box<str>> {
         if let Some(value) = &self.stdin_override {
             return value.clone();
@@ -1193,7 +1193,7 @@ fn parse_goa_mail_auth(
                     value: strip_signal(value).clone(),
                 });
             };
-            Ok(ImapAuthConfig::Password(value.clone()))
+            Ok(ImapAuthConfig::Password(Box::from(value.as_ref())))
         }
         "GoaMailOAuthToken" => {
             let Some(RuntimeValue::Record(fields)) = sum.fields.first() else {
@@ -1285,7 +1285,7 @@ fn record_text_field(
             value: strip_signal(value).clone(),
         });
     };
-    Ok(value.clone())
+    Ok(Box::from(value.as_ref()))
 }
 
 fn record_bool_field(
@@ -572,7 +572,7 @@ impl SourceProviderManager {
                     instance,
                     BuiltinSourceProvider::StdioRead,
                     &port,
-                    RuntimeValue::Text(stdin),
+                    RuntimeValue::Text(stdin.into()),
                 )?;
                 ActiveProviderState::Passive {
                     provider: config.provider.clone(),
@@ -594,7 +594,7 @@ impl SourceProviderManager {
                     instance,
                     BuiltinSourceProvider::PathHome,
                     &port,
-                    RuntimeValue::Text(path),
+                    RuntimeValue::Text(path.into()),
                 )?;
                 ActiveProviderState::Passive {
                     provider: config.provider.clone(),
@@ -621,7 +621,7 @@ impl SourceProviderManager {
                     instance,
                     BuiltinSourceProvider::PathConfigHome,
                     &port,
-                    RuntimeValue::Text(path),
+                    RuntimeValue::Text(path.into()),
                 )?;
                 ActiveProviderState::Passive {
                     provider: config.provider.clone(),
@@ -643,7 +643,7 @@ impl SourceProviderManager {
                     instance,
                     BuiltinSourceProvider::PathDataHome,
                     &port,
-                    RuntimeValue::Text(path),
+                    RuntimeValue::Text(path.into()),
                 )?;
                 ActiveProviderState::Passive {
                     provider: config.provider.clone(),
@@ -665,7 +665,7 @@ impl SourceProviderManager {
                     instance,
                     BuiltinSourceProvider::PathCacheHome,
                     &port,
-                    RuntimeValue::Text(path),
+                    RuntimeValue::Text(path.into()),
                 )?;
                 ActiveProviderState::Passive {
                     provider: config.provider.clone(),
@@ -680,7 +680,7 @@ impl SourceProviderManager {
                     instance,
                     BuiltinSourceProvider::PathTempDir,
                     &port,
-                    RuntimeValue::Text(self.context.temp_dir_text()),
+                    RuntimeValue::Text(self.context.temp_dir_text().into()),
                 )?;
                 ActiveProviderState::Passive {
                     provider: config.provider.clone(),
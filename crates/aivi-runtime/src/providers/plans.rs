@@ -982,7 +982,7 @@ fn extract_auth_header(
                 }
                 "ApiKey" => {
                     if let Some(RuntimeValue::Text(key)) = sum.fields.first() {
-                        return Ok(Some(("X-API-Key".into(), key.clone())));
+                        return Ok(Some(("X-API-Key".into(), Box::from(key.as_ref()))));
                     }
                 }
                 "ApiKeyQuery" => {
@@ -1856,7 +1856,7 @@ impl DbusBus {
                 provider,
                 option_name: option_name.into(),
                 expected: "\"session\" or \"system\"".into(),
-                value: RuntimeValue::Text(value),
+                value: RuntimeValue::Text(value.into()),
             }),
         }
     }
@@ -3145,7 +3145,7 @@ fn parse_portal_open_file_argument(
         }
     }
     let title = match runtime_record_field(fields, "title") {
-        Some(RuntimeValue::Text(text)) => text.clone(),
+        Some(RuntimeValue::Text(text)) => Box::from(text.as_ref()),
         Some(other) => {
             return Err(SourceProviderExecutionError::InvalidArgument {
                 instance,
@@ -3158,7 +3158,7 @@ fn parse_portal_open_file_argument(
         None => "Open File".into(),
     };
     let accept_label = match runtime_record_field(fields, "acceptLabel") {
-        Some(RuntimeValue::Text(text)) => Some(text.clone()),
+        Some(RuntimeValue::Text(text)) => Some(Box::from(text.as_ref())),
         Some(other) => {
             return Err(SourceProviderExecutionError::InvalidArgument {
                 instance,
@@ -3210,7 +3210,7 @@ fn parse_portal_open_file_argument(
         None => false,
     };
     let current_folder = match runtime_record_field(fields, "currentFolder") {
-        Some(RuntimeValue::Text(text)) => Some(text.clone()),
+        Some(RuntimeValue::Text(text)) => Some(Box::from(text.as_ref())),
         Some(other) => {
             return Err(SourceProviderExecutionError::InvalidArgument {
                 instance,
@@ -3286,7 +3286,7 @@ fn parse_portal_file_filter(
     let patterns = patterns
         .iter()
         .map(|pattern| match strip_signal(pattern) {
-            RuntimeValue::Text(text) => Ok(text.clone()),
+            RuntimeValue::Text(text) => Ok(Box::from(text.as_ref())),
             other => Err(SourceProviderExecutionError::InvalidArgument {
                 instance,
                 provider,
@@ -3298,7 +3298,7 @@ fn parse_portal_file_filter(
         .collect::<Result<Vec<_>, _>>()?
         .into_boxed_slice();
     Ok(PortalFileFilterSpec {
-        name: name.clone(),
+        name: Box::from(name.as_ref()),
         patterns,
     })
 }
@@ -1724,13 +1724,38 @@ fn spawn_fs_watch_worker(
     stop: Arc<AtomicBool>,
 ) -> thread::JoinHandle<()> {
     thread::spawn(move || {
+        // Block on file system notifications where the platform watcher can be set up, and poll
+        // otherwise. Either way a wake-up only triggers the snapshot comparison below, so the
+        // published events mean the same thing on every platform.
+        let notifications = fs_watch_notifications(&plan);
+        let wait_for_change = || {
+            let Some((_watcher, receiver)) = &notifications else {
+                thread::sleep(Duration::from_millis(40));
+                return true;
+            };
+            match receiver.recv_timeout(Duration::from_millis(40)) {
+                Ok(_) => {
+                    // One write usually arrives as several notifications.
+                    while receiver.try_recv().is_ok() {}
+                    true
+                }
+                Err(RecvTimeoutError::Timeout) => false,
+                Err(RecvTimeoutError::Disconnected) => {
+                    thread::sleep(Duration::from_millis(40));
+                    true
+                }
+            }
+        };
         if plan.recursive {
             let mut previous = dir_signatures(&plan.path);
             while !stop.load(Ordering::Acquire) && !port.is_cancelled() {
-                thread::sleep(Duration::from_millis(40));
+                let changed = wait_for_change();
                 if stop.load(Ordering::Acquire) || port.is_cancelled() {
                     break;
                 }
+                if !changed {
+                    continue;
+                }
                 let current = dir_signatures(&plan.path);
                 // Detect created/changed/deleted entries by comparing the two snapshots.
                 for (path, sig) in &current {
@@ -1759,10 +1784,13 @@ fn spawn_fs_watch_worker(
         } else {
             let mut previous = file_signature(&plan.path);
             while !stop.load(Ordering::Acquire) && !port.is_cancelled() {
-                thread::sleep(Duration::from_millis(40));
+                let changed = wait_for_change();
                 if stop.load(Ordering::Acquire) || port.is_cancelled() {
                     break;
                 }
+                if !changed {
+                    continue;
+                }
                 let current = file_signature(&plan.path);
                 let event = match (previous.exists, current.exists) {
                     (false, true) => Some("Created"),
@@ -1782,6 +1810,36 @@ fn spawn_fs_watch_worker(
     })
 }
 
+type FsWatchNotifications = (
+    notify::RecommendedWatcher,
+    mpsc::Receiver<notify::Result<notify::Event>>,
+);
+
+/// Platform notifications for the watched path, or `None` when the watcher cannot be set up, for
+/// example because the watched directory does not exist yet.
+fn fs_watch_notifications(plan: &FsWatchPlan) -> Option<FsWatchNotifications> {
+    use notify::Watcher as _;
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).ok()?;
+    if plan.recursive {
+        watcher
+            .watch(&plan.path, notify::RecursiveMode::Recursive)
+            .ok()?;
+    } else {
+        // A single file is watched through its directory so that creating it is noticed too.
+        let directory = plan
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        watcher
+            .watch(directory, notify::RecursiveMode::NonRecursive)
+            .ok()?;
+    }
+    Some((watcher, receiver))
+}
+
 fn emit_fs_event(
     event: &str,
     plan: &FsWatchPlan,
@@ -2406,7 +2464,7 @@ fn parse_text_argument(
     value: &DetachedRuntimeValue,
 ) -> Result<Box<str>, SourceProviderExecutionError> {
     match strip_detached_signal(value) {
-        RuntimeValue::Text(value) => Ok(value.clone()),
+        RuntimeValue::Text(value) => Ok(Box::from(value.as_ref())),
         other => Err(SourceProviderExecutionError::InvalidArgument {
             instance,
             provider,
@@ -2489,7 +2547,7 @@ fn parse_text_option(
     value: &DetachedRuntimeValue,
 ) -> Result<Box<str>, SourceProviderExecutionError> {
     match strip_detached_signal(value) {
-        RuntimeValue::Text(value) => Ok(value.clone()),
+        RuntimeValue::Text(value) => Ok(Box::from(value.as_ref())),
         other => Err(SourceProviderExecutionError::InvalidOption {
             instance,
             provider,
@@ -2549,7 +2607,7 @@ fn parse_text_list(
     values
         .iter()
         .map(|value| match strip_signal(value) {
-            RuntimeValue::Text(value) => Ok(value.clone()),
+            RuntimeValue::Text(value) => Ok(Box::from(value.as_ref())),
             other => Err(SourceProviderExecutionError::InvalidArgument {
                 instance,
                 provider,
@@ -2597,7 +2655,7 @@ fn parse_text_map(
                     value: strip_signal(v).clone(),
                 });
             };
-            Ok((key.clone(), value.clone()))
+            Ok((Box::from(key.as_ref()), Box::from(value.as_ref())))
         })
         .collect()
 }
@@ -2824,7 +2882,7 @@ fn parse_stream_mode(
 fn variant_name_value(value: &RuntimeValue) -> Option<Box<str>> {
     match value {
         RuntimeValue::Sum(value) if value.fields.is_empty() => Some(value.variant_name.clone()),
-        RuntimeValue::Text(value) => Some(value.clone()),
+        RuntimeValue::Text(value) => Some(Box::from(value.as_ref())),
         RuntimeValue::Callable(RuntimeCallable::SumConstructor {
             handle,
             bound_arguments,
@@ -2842,7 +2900,7 @@ fn encode_runtime_body(
 ) -> Result<Box<str>, SourceProviderExecutionError> {
     let value = strip_detached_signal(value);
     match value {
-        RuntimeValue::Text(value) => Ok(value.clone()),
+        RuntimeValue::Text(value) => Ok(Box::from(value.as_ref())),
         _ => encode_runtime_json(value)
             .map_err(
                 |detail| SourceProviderExecutionError::UnsupportedProviderShape {
@@ -608,6 +608,56 @@ signal fileEvents : Signal FsWatchEvent
     let _ = fs::remove_file(path);
 }
 
+#[test]
+fn fs_watch_detects_modified_files() {
+    let path = temp_path("fs-watch-modify");
+    fs::write(&path, "hello").expect("watched file should write");
+    let lowered = lower_text(
+        "runtime-provider-fs-watch-modify.aivi",
+        &format!(
+            r#"
+type FsWatchEvent =
+  | Created
+  | Changed
+  | Deleted
+
+@source fs.watch "{}"
+signal fileEvents : Signal FsWatchEvent
+"#,
+            path.display()
+        ),
+    );
+    let assembly =
+        assemble_hir_runtime(lowered.hir.module()).expect("runtime assembly should build");
+    let mut linked = link_backend_runtime(
+        assembly,
+        &lowered.core,
+        std::sync::Arc::new(lowered.backend.clone()),
+    )
+    .expect("startup link should succeed");
+    let actions = linked
+        .tick_with_source_lifecycle()
+        .expect("linked runtime tick should succeed");
+    let mut providers = SourceProviderManager::new();
+    providers
+        .apply_actions(actions.source_actions())
+        .expect("fs.watch source should execute");
+    thread::sleep(Duration::from_millis(100));
+    fs::write(&path, "hello, watcher").expect("watched file should rewrite");
+    let signal = linked
+        .assembly()
+        .signal(item_id(lowered.hir.module(), "fileEvents"))
+        .expect("fileEvents signal binding should exist")
+        .signal();
+    let value = spin_until(&mut linked, signal, Duration::from_secs(1))
+        .expect("fs.watch should publish a change event");
+    assert!(
+        matches!(&value, RuntimeValue::Sum(sum) if sum.variant_name.as_ref() == "Changed"),
+        "expected a Changed event, got {value:?}"
+    );
+    let _ = fs::remove_file(path);
+}
+
 #[test]
 fn socket_connect_reads_text_lines() {
     let listener = TcpListener::bind("127.0.0.1:0").expect("listener should bind");
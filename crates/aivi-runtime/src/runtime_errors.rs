@@ -98,12 +98,15 @@ fn eval_error_kernel(error: &EvaluationError) -> Option<KernelId> {
         | EvaluationError::InvalidFloatLiteral { kernel, .. }
         | EvaluationError::InvalidDecimalLiteral { kernel, .. }
         | EvaluationError::InvalidBigIntLiteral { kernel, .. }
-        | EvaluationError::UnsupportedStructuralEquality { kernel, .. } => Some(*kernel),
+        | EvaluationError::UnsupportedStructuralEquality { kernel, .. }
+        | EvaluationError::FuelExhausted { kernel }
+        | EvaluationError::AllocationBudgetExceeded { kernel, .. } => Some(*kernel),
         EvaluationError::UnknownItem { .. }
         | EvaluationError::MissingItemBody { .. }
         | EvaluationError::MissingItemValue { .. }
         | EvaluationError::RecursiveItemEvaluation { .. }
         | EvaluationError::UnsupportedNativeOnlyRuntimeOperation { .. } => None,
+        EvaluationError::WithTrace { source, .. } => eval_error_kernel(source),
     }
 }
 
@@ -126,6 +129,9 @@ fn push_eval_error_layout_notes(
                 backend.layouts()[*expected]
             ));
         }
+        EvaluationError::WithTrace { source, .. } => {
+            diag = push_eval_error_layout_notes(diag, backend, source);
+        }
         _ => {}
     }
     diag
@@ -0,0 +1,110 @@
+//! Golden-file snapshots behind `aivi.test.matchesSnapshot`.
+//!
+//! Snapshots live at `__snapshots__/<module>/<name>.snap` next to the test file. A missing
+//! snapshot is written on first use; an existing one must match byte for byte unless
+//! `AIVI_UPDATE_SNAPSHOTS=1` asks the runner to rewrite it.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable that turns snapshot comparisons into rewrites.
+pub(crate) const UPDATE_SNAPSHOTS_ENV: &str = "AIVI_UPDATE_SNAPSHOTS";
+
+/// Snapshot directory for the tests declared in `test_file`.
+pub(crate) fn snapshot_dir_for_test_file(test_file: &Path) -> PathBuf {
+    let module = test_file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    test_file
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("__snapshots__")
+        .join(sanitize_snapshot_segment(&module))
+}
+
+/// Keep one user-supplied name inside its parent directory: separators and other unusual
+/// characters become `_`, and dot-only names cannot walk upwards.
+fn sanitize_snapshot_segment(segment: &str) -> String {
+    let sanitized = segment
+        .trim()
+        .chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if sanitized.chars().all(|ch| ch == '.') {
+        "_".repeat(sanitized.len().max(1))
+    } else {
+        sanitized
+    }
+}
+
+/// Compare `content` against the stored snapshot, creating or rewriting it when allowed.
+pub(crate) fn match_snapshot(
+    directory: &Path,
+    name: &str,
+    content: &str,
+    update: bool,
+) -> Result<(), String> {
+    let path = directory.join(format!("{}.snap", sanitize_snapshot_segment(name)));
+    if !update && path.exists() {
+        let expected = fs::read_to_string(&path)
+            .map_err(|error| format!("failed to read snapshot {}: {error}", path.display()))?;
+        if expected == content {
+            return Ok(());
+        }
+        return Err(describe_snapshot_mismatch(name, &path, &expected, content));
+    }
+    fs::create_dir_all(directory).map_err(|error| {
+        format!(
+            "failed to create snapshot directory {}: {error}",
+            directory.display()
+        )
+    })?;
+    fs::write(&path, content)
+        .map_err(|error| format!("failed to write snapshot {}: {error}", path.display()))
+}
+
+fn describe_snapshot_mismatch(name: &str, path: &Path, expected: &str, actual: &str) -> String {
+    let expected_lines = expected.lines().collect::<Vec<_>>();
+    let actual_lines = actual.lines().collect::<Vec<_>>();
+    // Content that differs only in a trailing newline still needs a line to point at.
+    let index = expected_lines
+        .iter()
+        .zip(actual_lines.iter())
+        .position(|(expected, actual)| expected != actual)
+        .unwrap_or_else(|| expected_lines.len().min(actual_lines.len()));
+    let line = index + 1;
+    let mut message = format!(
+        "snapshot `{name}` does not match {} at line {line}:",
+        path.display()
+    );
+    if let Some(previous) = index.checked_sub(1).and_then(|i| expected_lines.get(i)) {
+        message.push_str(&format!("\n    {:>4} | {previous}", line - 1));
+    }
+    message.push_str(&format!(
+        "\n  - {line:>4} | {}",
+        expected_lines
+            .get(index)
+            .copied()
+            .unwrap_or("<end of snapshot>")
+    ));
+    message.push_str(&format!(
+        "\n  + {line:>4} | {}",
+        actual_lines
+            .get(index)
+            .copied()
+            .unwrap_or("<end of output>")
+    ));
+    message.push_str(&format!(
+        "\nrerun with {UPDATE_SNAPSHOTS_ENV}=1 to accept the new output"
+    ));
+    message
+}
@@ -173,6 +173,7 @@ pub fn validate_supported_program(
                 | aivi_typing::PrimitiveType::Decimal
                 | aivi_typing::PrimitiveType::BigInt
                 | aivi_typing::PrimitiveType::Bytes
+                | aivi_typing::PrimitiveType::Char
                 | aivi_typing::PrimitiveType::Text => {}
             },
             DecodeProgramStep::Domain { surface, .. } => {
@@ -522,6 +523,7 @@ fn runtime_to_json(value: &RuntimeValue) -> Result<JsonValue, Box<str>> {
         RuntimeValue::Decimal(value) => Ok(JsonValue::String(value.to_string())),
         RuntimeValue::BigInt(value) => Ok(JsonValue::String(value.to_string())),
         RuntimeValue::Text(value) => Ok(JsonValue::String(value.as_ref().to_owned())),
+        RuntimeValue::Char(value) => Ok(JsonValue::String(value.to_string())),
         RuntimeValue::Tuple(values) | RuntimeValue::List(values) | RuntimeValue::Set(values) => {
             values
                 .iter()
@@ -693,9 +695,19 @@ fn decode_step(
                 other => Err(wrap(other, type_mismatch("float", other))),
             },
             aivi_typing::PrimitiveType::Text => match value {
-                ExternalSourceValue::Text(value) => Ok(RuntimeValue::Text(value.clone())),
+                ExternalSourceValue::Text(value) => Ok(RuntimeValue::Text(value.as_ref().into())),
                 other => Err(wrap(other, type_mismatch("text", other))),
             },
+            aivi_typing::PrimitiveType::Char => match value {
+                ExternalSourceValue::Text(text) => {
+                    let mut chars = text.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(value), None) => Ok(RuntimeValue::Char(value)),
+                        _ => Err(wrap(value, type_mismatch("single-character text", value))),
+                    }
+                }
+                other => Err(wrap(other, type_mismatch("single-character text", other))),
+            },
             aivi_typing::PrimitiveType::Decimal => decode_literal_scalar(
                 value,
                 "Decimal",
@@ -174,6 +174,8 @@ fn link_backend_runtime_with_tables_from_parts(
         temporal_triggers_bootstrapped: false,
         db_commit_invalidation_sink: None,
         execution_context: SourceProviderContext::current(),
+        error_traces: false,
+        allocation_budget: None,
     };
     linked_runtime.prime_db_changed_routes();
     linked_runtime
@@ -206,4 +208,6 @@ pub struct BackendLinkedRuntime {
     temporal_triggers_bootstrapped: bool,
     db_commit_invalidation_sink: Option<DbCommitInvalidationSink>,
     execution_context: SourceProviderContext,
+    error_traces: bool,
+    allocation_budget: Option<usize>,
 }
@@ -47,6 +47,18 @@ impl BackendLinkedRuntime {
         self.execution_context = context;
     }
 
+    /// Make [`Self::with_task_value_by_owner`] report evaluation errors with the chain of items
+    /// and function calls that led to them.
+    pub fn set_error_traces(&mut self, enabled: bool) {
+        self.error_traces = enabled;
+    }
+
+    /// Bound the estimated bytes each evaluation in [`Self::with_task_value_by_owner`] may
+    /// allocate; `None` removes the bound.
+    pub fn set_allocation_budget(&mut self, bytes: Option<usize>) {
+        self.allocation_budget = bytes;
+    }
+
     pub fn signal_graph(&self) -> &crate::SignalGraph {
         self.runtime.graph()
     }
@@ -171,6 +183,23 @@ impl BackendLinkedRuntime {
         &self,
         owner: hir::ItemId,
     ) -> Result<DetachedRuntimeValue, BackendRuntimeError> {
+        self.with_task_value_by_owner(owner, |value, _, _| {
+            DetachedRuntimeValue::from_runtime_owned(value)
+        })
+    }
+
+    /// Evaluate one task body and hand the resulting value to `run` together with the engine and
+    /// globals it was evaluated against, so deferred `Map`/`Apply`/`Chain`/`Join` plans can apply
+    /// their continuations while the caller executes them.
+    pub fn with_task_value_by_owner<T>(
+        &self,
+        owner: hir::ItemId,
+        run: impl FnOnce(
+            RuntimeValue,
+            &mut dyn TaskFunctionApplier,
+            &BTreeMap<BackendItemId, RuntimeValue>,
+        ) -> T,
+    ) -> Result<T, BackendRuntimeError> {
         let binding = self
             .task_by_owner(owner)
             .cloned()
@@ -196,13 +225,16 @@ impl BackendLinkedRuntime {
             &snapshots,
         )?;
         let runtime_globals = materialize_detached_globals(&globals);
-        let value = self
+        let mut engine = self
             .executable_program()
             .with_execution_options(aivi_backend::BackendExecutionOptions {
                 prefer_interpreter: true,
+                trace_errors: self.error_traces,
+                allocation_budget: self.allocation_budget,
                 ..Default::default()
             })
-            .create_engine()
+            .create_engine();
+        let value = engine
             .evaluate_item(binding.backend_item, &runtime_globals)
             .map_err(|error| BackendRuntimeError::EvaluateTaskBody {
                 instance: binding.instance,
@@ -210,7 +242,7 @@ impl BackendLinkedRuntime {
                 backend_item: binding.backend_item,
                 error,
             })?;
-        Ok(DetachedRuntimeValue::from_runtime_owned(value))
+        Ok(run(value, &mut *engine, &runtime_globals))
     }
 
     pub fn tick(&mut self) -> Result<TickOutcome, BackendRuntimeError> {
@@ -488,7 +520,7 @@ impl BackendLinkedRuntime {
                     RuntimeValue::Signal(_) => value.clone(),
                     other => RuntimeValue::Signal(Box::new(other.clone())),
                 };
-                let detached = DetachedRuntimeValue::from_runtime_owned(public_value);
+                let detached = DetachedRuntimeValue::from_runtime_copy(&public_value);
                 snapshots.insert(item, detached.clone());
                 for &alias in self
                     .signal_alias_items_by_handle
@@ -189,6 +189,7 @@ fn execute_task_plan(
         })
         .create_engine();
     let runtime_globals = materialize_detached_globals(&globals);
+    let execution_context = execution_context.with_cancellation(completion.cancellation());
     let value = engine
         .evaluate_item(backend_item, &runtime_globals)
         .map_err(|error| LinkedTaskWorkerError::Evaluation {
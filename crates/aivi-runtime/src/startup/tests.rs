@@ -175,6 +175,8 @@ fn manual_task_linked_runtime(lowered: &LoweredStack, owner_name: &str) -> Backe
         temporal_triggers_bootstrapped: false,
         db_commit_invalidation_sink: None,
         execution_context: SourceProviderContext::current(),
+        error_traces: false,
+        allocation_budget: None,
     }
 }
 
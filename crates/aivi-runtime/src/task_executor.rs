@@ -30,12 +30,43 @@ use crate::providers::{
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RuntimeTaskExecutionError {
     message: Box<str>,
+    kind: RuntimeTaskErrorKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RuntimeTaskErrorKind {
+    /// An ordinary failure that `aivi.task.attempt` turns into an `Err` value.
+    Failure,
+    /// An intercepted `process.exit`. The program is not expected to continue after it, so
+    /// `aivi.task.attempt` must not catch it.
+    ProcessExit { code: i64 },
 }
 
 impl RuntimeTaskExecutionError {
     pub(crate) fn new(message: impl Into<String>) -> Self {
         Self {
             message: message.into().into_boxed_str(),
+            kind: RuntimeTaskErrorKind::Failure,
+        }
+    }
+
+    pub(crate) fn process_exit(code: i64) -> Self {
+        Self {
+            message: format!("process.exit({code}) was called").into_boxed_str(),
+            kind: RuntimeTaskErrorKind::ProcessExit { code },
+        }
+    }
+
+    /// Whether the error must escape `aivi.task.attempt` instead of becoming an `Err` value.
+    pub fn is_fatal(&self) -> bool {
+        self.process_exit_code().is_some()
+    }
+
+    /// The code passed to an intercepted `process.exit`, if that is what ended the task.
+    pub fn process_exit_code(&self) -> Option<i64> {
+        match self.kind {
+            RuntimeTaskErrorKind::Failure => None,
+            RuntimeTaskErrorKind::ProcessExit { code } => Some(code),
         }
     }
 }
@@ -199,6 +230,161 @@ pub fn execute_runtime_task_plan_with_context(
             })?;
             Ok(RuntimeValue::Unit)
         }
+        RuntimeTaskPlan::FsOpen { path, mode } => {
+            let id = context
+                .file_handles()
+                .open(&path, &mode)
+                .map_err(task_error)?;
+            Ok(RuntimeValue::Record(vec![
+                aivi_backend::RuntimeRecordField {
+                    label: "id".into(),
+                    value: RuntimeValue::Int(id),
+                },
+                aivi_backend::RuntimeRecordField {
+                    label: "path".into(),
+                    value: RuntimeValue::Text(path.into()),
+                },
+            ]))
+        }
+        RuntimeTaskPlan::FsReadLine { handle } => {
+            let line = context
+                .file_handles()
+                .read_line(handle)
+                .map_err(task_error)?;
+            Ok(match line {
+                Some(line) => RuntimeValue::OptionSome(Box::new(RuntimeValue::Text(line.into()))),
+                None => RuntimeValue::OptionNone,
+            })
+        }
+        RuntimeTaskPlan::FsWrite { handle, text } => {
+            context
+                .file_handles()
+                .write(handle, &text)
+                .map_err(task_error)?;
+            Ok(RuntimeValue::Unit)
+        }
+        RuntimeTaskPlan::FsClose { handle } => {
+            context.file_handles().close(handle).map_err(task_error)?;
+            Ok(RuntimeValue::Unit)
+        }
+        RuntimeTaskPlan::WsConnect { url } => {
+            context
+                .web_sockets()
+                .check_capacity(&url)
+                .map_err(task_error)?;
+            // The handshake runs without the table lock, so other sockets stay usable meanwhile.
+            let socket = crate::ws_handles::OpenSocket::connect(&url).map_err(task_error)?;
+            let id = context.web_sockets().insert(socket).map_err(task_error)?;
+            Ok(RuntimeValue::Record(vec![
+                aivi_backend::RuntimeRecordField {
+                    label: "id".into(),
+                    value: RuntimeValue::Int(id),
+                },
+                aivi_backend::RuntimeRecordField {
+                    label: "url".into(),
+                    value: RuntimeValue::Text(url.into()),
+                },
+            ]))
+        }
+        RuntimeTaskPlan::WsSend { handle, text } => {
+            let socket = context
+                .web_sockets()
+                .get("ws.send", handle)
+                .map_err(task_error)?;
+            crate::ws_handles::send(&socket, &text).map_err(task_error)?;
+            Ok(RuntimeValue::Unit)
+        }
+        RuntimeTaskPlan::WsRecv { handle } => {
+            let socket = context
+                .web_sockets()
+                .get("ws.recv", handle)
+                .map_err(task_error)?;
+            let message =
+                crate::ws_handles::recv(&socket, context.cancellation()).map_err(task_error)?;
+            Ok(match message {
+                Some(message) => {
+                    RuntimeValue::OptionSome(Box::new(RuntimeValue::Text(message.into())))
+                }
+                None => RuntimeValue::OptionNone,
+            })
+        }
+        RuntimeTaskPlan::WsRecvTimeout { handle, timeout_ms } => {
+            // A zero or negative timeout still gives a message already in flight a moment to land.
+            let timeout =
+                std::time::Duration::from_millis(u64::try_from(timeout_ms.max(1)).unwrap_or(1));
+            let socket = context
+                .web_sockets()
+                .get("ws.recvTimeout", handle)
+                .map_err(task_error)?;
+            let message = crate::ws_handles::recv_timeout(&socket, timeout, context.cancellation())
+                .map_err(task_error)?;
+            Ok(match message {
+                Some(Some(message)) => RuntimeValue::OptionSome(Box::new(
+                    RuntimeValue::OptionSome(Box::new(RuntimeValue::Text(message.into()))),
+                )),
+                Some(None) => RuntimeValue::OptionSome(Box::new(RuntimeValue::OptionNone)),
+                None => RuntimeValue::OptionNone,
+            })
+        }
+        RuntimeTaskPlan::WsClose { handle } => {
+            let socket = context
+                .web_sockets()
+                .remove("ws.close", handle)
+                .map_err(task_error)?;
+            crate::ws_handles::close(&socket, context.cancellation()).map_err(task_error)?;
+            Ok(RuntimeValue::Unit)
+        }
+        RuntimeTaskPlan::ProcessSpawn { command, args, env } => {
+            let id = context
+                .processes()
+                .spawn(&command, &args, &env)
+                .map_err(task_error)?;
+            Ok(RuntimeValue::Record(vec![
+                aivi_backend::RuntimeRecordField {
+                    label: "id".into(),
+                    value: RuntimeValue::Int(id),
+                },
+                aivi_backend::RuntimeRecordField {
+                    label: "command".into(),
+                    value: RuntimeValue::Text(command.into()),
+                },
+            ]))
+        }
+        RuntimeTaskPlan::ProcessWait { handle } => {
+            let process = context
+                .processes()
+                .get("process.wait", handle)
+                .map_err(task_error)?;
+            let code = process.wait(context.cancellation()).map_err(task_error)?;
+            context.processes().release(handle, &process);
+            Ok(RuntimeValue::Int(code))
+        }
+        RuntimeTaskPlan::ProcessKill { handle } => {
+            let process = context
+                .processes()
+                .remove("process.kill", handle)
+                .map_err(task_error)?;
+            process.kill().map_err(task_error)?;
+            Ok(RuntimeValue::Unit)
+        }
+        RuntimeTaskPlan::ProcessReadStdout { handle } => {
+            let process = context
+                .processes()
+                .get("process.readStdout", handle)
+                .map_err(task_error)?;
+            let output = process
+                .read_stdout(context.cancellation())
+                .map_err(task_error)?;
+            Ok(RuntimeValue::Text(output.into()))
+        }
+        RuntimeTaskPlan::ProcessWriteStdin { handle, text } => {
+            let process = context
+                .processes()
+                .get("process.writeStdin", handle)
+                .map_err(task_error)?;
+            process.write_stdin(&text).map_err(task_error)?;
+            Ok(RuntimeValue::Unit)
+        }
         RuntimeTaskPlan::JsonValidate { json } => {
             let valid = serde_json::from_str::<serde_json::Value>(&json).is_ok();
             Ok(RuntimeValue::Bool(valid))
@@ -254,8 +440,41 @@ pub fn execute_runtime_task_plan_with_context(
             })?;
             Ok(RuntimeValue::Text(minified.into()))
         }
+        RuntimeTaskPlan::JsonParseInto {
+            json,
+            item,
+            type_name,
+        } => {
+            let parsed: serde_json::Value = serde_json::from_str(&json)
+                .map_err(|error| task_error(format!("json.parse: invalid JSON: {error}")))?;
+            Ok(runtime_json_value(&parsed, item, &type_name))
+        }
+        RuntimeTaskPlan::JsonParseStreamInto {
+            json,
+            item,
+            type_name,
+        } => {
+            // Newline-delimited JSON: one document per non-blank line.
+            let mut values = Vec::new();
+            for (index, line) in json.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let parsed: serde_json::Value = serde_json::from_str(line).map_err(|error| {
+                    task_error(format!(
+                        "json.parseStream: invalid JSON on line {}: {error}",
+                        index + 1
+                    ))
+                })?;
+                values.push(runtime_json_value(&parsed, item, &type_name));
+            }
+            Ok(RuntimeValue::List(values))
+        }
         // Time intrinsics
         RuntimeTaskPlan::TimeNowMs => {
+            if let Some(clock) = context.virtual_clock() {
+                return Ok(RuntimeValue::Int(clock.now_ms()));
+            }
             use std::time::{SystemTime, UNIX_EPOCH};
             let ms = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
@@ -264,11 +483,22 @@ pub fn execute_runtime_task_plan_with_context(
             Ok(RuntimeValue::Int(ms))
         }
         RuntimeTaskPlan::TimeMonotonicMs => {
+            if let Some(clock) = context.virtual_clock() {
+                return Ok(RuntimeValue::Int(clock.monotonic_ms()));
+            }
             static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
             let start = START.get_or_init(std::time::Instant::now);
             let ms = start.elapsed().as_millis() as i64;
             Ok(RuntimeValue::Int(ms))
         }
+        RuntimeTaskPlan::TimeSleep { ms } => {
+            // Under a virtual clock, sleeping moves time forward instead of waiting for it.
+            match context.virtual_clock() {
+                Some(mut clock) => clock.advance(ms),
+                None => sleep_unless_cancelled(ms, context)?,
+            }
+            Ok(RuntimeValue::Unit)
+        }
         RuntimeTaskPlan::TimeFormat {
             epoch_ms,
             pattern: _,
@@ -283,35 +513,83 @@ pub fn execute_runtime_task_plan_with_context(
                 Err(_) => Err(task_error(format!("cannot parse timestamp: {}", text))),
             }
         }
+        // Clipboard intrinsics
+        RuntimeTaskPlan::ClipboardRead => {
+            runtime_clipboard_read().map(|text| RuntimeValue::Text(text.into()))
+        }
+        RuntimeTaskPlan::ClipboardWrite { text } => {
+            runtime_clipboard_write(&text)?;
+            Ok(RuntimeValue::Unit)
+        }
         // Env intrinsics
-        RuntimeTaskPlan::EnvGet { name } => Ok(match std::env::var(name.as_ref()) {
-            Ok(val) => RuntimeValue::OptionSome(Box::new(RuntimeValue::Text(val.into()))),
-            Err(_) => RuntimeValue::OptionNone,
-        }),
-        RuntimeTaskPlan::EnvList { prefix } => {
-            let pairs: Vec<RuntimeValue> = std::env::vars()
-                .filter(|(k, _)| prefix.is_empty() || k.starts_with(prefix.as_ref()))
-                .map(|(k, v)| {
-                    RuntimeValue::Tuple(vec![
-                        RuntimeValue::Text(k.into()),
-                        RuntimeValue::Text(v.into()),
-                    ])
-                })
-                .collect();
-            Ok(RuntimeValue::List(pairs))
+        RuntimeTaskPlan::EnvGet { name } => Ok(context.env_runtime_value(name.as_ref())),
+        RuntimeTaskPlan::EnvList { prefix } => Ok(context.env_entries_runtime_value(&prefix)),
+        // Process intrinsics
+        RuntimeTaskPlan::ProcessArgs => Ok(context.args_runtime_value()),
+        RuntimeTaskPlan::ProcessExit { code } => {
+            stdout
+                .flush()
+                .map_err(|error| task_error(format!("failed to flush stdout: {error}")))?;
+            if context.intercepts_process_exit() {
+                return Err(RuntimeTaskExecutionError::process_exit(code));
+            }
+            let status = i32::try_from(code).map_err(|_| {
+                task_error(format!(
+                    "process exit code must fit in a 32-bit integer, found {code}"
+                ))
+            })?;
+            std::process::exit(status)
+        }
+        // Test intrinsics
+        RuntimeTaskPlan::MatchesSnapshot { name, content } => {
+            let Some(directory) = context.snapshot_dir() else {
+                return Err(task_error(format!(
+                    "test.matchesSnapshot({name}) is only available under `aivi test`"
+                )));
+            };
+            crate::snapshots::match_snapshot(
+                directory,
+                &name,
+                &content,
+                context.updates_snapshots(),
+            )
+            .map_err(task_error)?;
+            Ok(RuntimeValue::Bool(true))
+        }
+        RuntimeTaskPlan::AdvanceClock { ms } => {
+            let Some(mut clock) = context.virtual_clock() else {
+                return Err(task_error(format!(
+                    "test.advanceClock({ms}) needs the virtual clock of `aivi test`"
+                )));
+            };
+            if ms < 0 {
+                return Err(task_error(format!(
+                    "test.advanceClock({ms}) cannot move the clock backwards; use test.setClock"
+                )));
+            }
+            clock.advance(ms);
+            Ok(RuntimeValue::Unit)
+        }
+        RuntimeTaskPlan::SetClock { epoch_ms } => {
+            let Some(mut clock) = context.virtual_clock() else {
+                return Err(task_error(format!(
+                    "test.setClock({epoch_ms}) needs the virtual clock of `aivi test`"
+                )));
+            };
+            clock.set(epoch_ms);
+            Ok(RuntimeValue::Unit)
         }
         // Log intrinsics
         RuntimeTaskPlan::LogEmit { level, message } => {
-            eprintln!("[{level}] {message}");
+            context.emit_log(&level, &message, &[]);
             Ok(RuntimeValue::Unit)
         }
         RuntimeTaskPlan::LogEmitContext {
             level,
             message,
-            context,
+            context: fields,
         } => {
-            let ctx: Vec<String> = context.iter().map(|(k, v)| format!("{k}={v}")).collect();
-            eprintln!("[{level}] {message} {{{}}}", ctx.join(", "));
+            context.emit_log(&level, &message, &fields);
             Ok(RuntimeValue::Unit)
         }
         // Random float
@@ -327,6 +605,22 @@ pub fn execute_runtime_task_plan_with_context(
                 .map(RuntimeValue::Float)
                 .ok_or_else(|| task_error("random float: result is not finite"))
         }
+        RuntimeTaskPlan::CryptoEd25519Keypair => {
+            use ring::signature::KeyPair as _;
+            let seed = read_os_random_bytes(32)?;
+            let pair = ring::signature::Ed25519KeyPair::from_seed_unchecked(&seed)
+                .map_err(|error| task_error(format!("ed25519 keypair: {error}")))?;
+            Ok(RuntimeValue::Record(vec![
+                aivi_backend::RuntimeRecordField {
+                    label: "publicKey".into(),
+                    value: RuntimeValue::Bytes(pair.public_key().as_ref().into()),
+                },
+                aivi_backend::RuntimeRecordField {
+                    label: "privateKey".into(),
+                    value: RuntimeValue::Bytes(seed),
+                },
+            ]))
+        }
         // Regex intrinsics
         RuntimeTaskPlan::RegexIsMatch { pattern, text } => {
             let re = Regex::new(pattern.as_ref()).map_err(|e| task_error(format!("regex: {e}")))?;
@@ -361,6 +655,23 @@ pub fn execute_runtime_task_plan_with_context(
                 .collect();
             Ok(RuntimeValue::List(matches))
         }
+        RuntimeTaskPlan::RegexCaptures { pattern, text } => {
+            let re = Regex::new(pattern.as_ref()).map_err(|e| task_error(format!("regex: {e}")))?;
+            match re.captures(text.as_ref()) {
+                Some(captures) => Ok(RuntimeValue::OptionSome(Box::new(runtime_regex_captures(
+                    &re, &captures,
+                )))),
+                None => Ok(RuntimeValue::OptionNone),
+            }
+        }
+        RuntimeTaskPlan::RegexCapturesAll { pattern, text } => {
+            let re = Regex::new(pattern.as_ref()).map_err(|e| task_error(format!("regex: {e}")))?;
+            let matches: Vec<RuntimeValue> = re
+                .captures_iter(text.as_ref())
+                .map(|captures| runtime_regex_captures(&re, &captures))
+                .collect();
+            Ok(RuntimeValue::List(matches))
+        }
         RuntimeTaskPlan::RegexReplace {
             pattern,
             replacement,
@@ -385,95 +696,16 @@ pub fn execute_runtime_task_plan_with_context(
                     .into(),
             ))
         }
-        RuntimeTaskPlan::HttpGet { url } => {
-            let body = ureq::get(url.as_ref())
-                .call()
-                .map_err(|e| task_error(format!("http get: {e}")))?
-                .into_string()
-                .map_err(|e| task_error(format!("http read: {e}")))?;
-            Ok(RuntimeValue::Text(body.into()))
-        }
-        RuntimeTaskPlan::HttpGetBytes { url } => {
-            let mut bytes = Vec::new();
-            ureq::get(url.as_ref())
-                .call()
-                .map_err(|e| task_error(format!("http get: {e}")))?
-                .into_reader()
-                .read_to_end(&mut bytes)
-                .map_err(|e| task_error(format!("http read: {e}")))?;
-            Ok(RuntimeValue::Bytes(bytes.into_boxed_slice()))
-        }
-        RuntimeTaskPlan::HttpGetStatus { url } => {
-            let status = ureq::get(url.as_ref())
-                .call()
-                .map(|r| r.status() as i64)
-                .unwrap_or_else(|e| match e {
-                    ureq::Error::Status(code, _) => code as i64,
-                    _ => 0,
-                });
-            Ok(RuntimeValue::Int(status))
-        }
-        RuntimeTaskPlan::HttpPost {
-            url,
-            content_type,
-            body,
-        } => {
-            let response = ureq::post(url.as_ref())
-                .set("Content-Type", content_type.as_ref())
-                .send_string(body.as_ref())
-                .map_err(|e| task_error(format!("http post: {e}")))?
-                .into_string()
-                .map_err(|e| task_error(format!("http read: {e}")))?;
-            Ok(RuntimeValue::Text(response.into()))
-        }
-        RuntimeTaskPlan::HttpPut {
-            url,
-            content_type,
-            body,
-        } => {
-            let response = ureq::put(url.as_ref())
-                .set("Content-Type", content_type.as_ref())
-                .send_string(body.as_ref())
-                .map_err(|e| task_error(format!("http put: {e}")))?
-                .into_string()
-                .map_err(|e| task_error(format!("http read: {e}")))?;
-            Ok(RuntimeValue::Text(response.into()))
-        }
-        RuntimeTaskPlan::HttpDelete { url } => {
-            let response = ureq::delete(url.as_ref())
-                .call()
-                .map_err(|e| task_error(format!("http delete: {e}")))?
-                .into_string()
-                .map_err(|e| task_error(format!("http read: {e}")))?;
-            Ok(RuntimeValue::Text(response.into()))
-        }
-        RuntimeTaskPlan::HttpHead { url } => {
-            let response = ureq::head(url.as_ref())
-                .call()
-                .map_err(|e| task_error(format!("http head: {e}")))?;
-            let names = response.headers_names();
-            let headers: Vec<RuntimeValue> = names
-                .iter()
-                .filter_map(|name| {
-                    response.header(name).map(|val| {
-                        RuntimeValue::Tuple(vec![
-                            RuntimeValue::Text(name.clone().into()),
-                            RuntimeValue::Text(val.into()),
-                        ])
-                    })
-                })
-                .collect();
-            Ok(RuntimeValue::List(headers))
-        }
-        RuntimeTaskPlan::HttpPostJson { url, body } => {
-            let response = ureq::post(url.as_ref())
-                .set("Content-Type", "application/json")
-                .send_string(body.as_ref())
-                .map_err(|e| task_error(format!("http post json: {e}")))?
-                .into_string()
-                .map_err(|e| task_error(format!("http read: {e}")))?;
-            Ok(RuntimeValue::Text(response.into()))
-        }
+        plan @ (RuntimeTaskPlan::HttpGet { .. }
+        | RuntimeTaskPlan::HttpGetBytes { .. }
+        | RuntimeTaskPlan::HttpGetStatus { .. }
+        | RuntimeTaskPlan::HttpPost { .. }
+        | RuntimeTaskPlan::HttpPut { .. }
+        | RuntimeTaskPlan::HttpDelete { .. }
+        | RuntimeTaskPlan::HttpHead { .. }
+        | RuntimeTaskPlan::HttpPostJson { .. }
+        | RuntimeTaskPlan::HttpRequest { .. }
+        | RuntimeTaskPlan::HttpDownload { .. }) => http_unless_cancelled(plan, context),
         RuntimeTaskPlan::DbusCall {
             destination,
             path,
@@ -566,6 +798,20 @@ pub fn execute_runtime_task_plan_with_context(
             };
             executor.execute(context, &plan, stdout, stderr)
         }
+        RuntimeTaskPlan::Attempt { inner } => attempt_task_result(
+            execute_runtime_task_plan_with_context(*inner, context, stdout, stderr),
+        ),
+        RuntimeTaskPlan::Finally { inner, cleanup } => {
+            let result = execute_runtime_task_plan_with_context(*inner, context, stdout, stderr);
+            finally_task_result(
+                result,
+                execute_runtime_task_plan_with_context(*cleanup, context, stdout, stderr),
+            )
+        }
+        RuntimeTaskPlan::Parallel { tasks } => {
+            crate::task_scheduler::run_concurrent(tasks, context, stdout, stderr)
+                .map(RuntimeValue::List)
+        }
         // Invariant: Map/Apply/Chain/Join are deferred composition plans that require a
         // TaskFunctionApplier (a Cranelift evaluator). They must only be executed via
         // execute_runtime_task_plan_with_applier, never via this bare executor.
@@ -679,11 +925,56 @@ pub(crate) fn execute_runtime_task_plan_with_applier(
                 )),
             }
         }
+        RuntimeTaskPlan::Attempt { inner } => {
+            attempt_task_result(execute_runtime_task_plan_with_applier(
+                *inner, context, stdout, stderr, applier, globals,
+            ))
+        }
+        RuntimeTaskPlan::Finally { inner, cleanup } => {
+            let result = execute_runtime_task_plan_with_applier(
+                *inner, context, stdout, stderr, applier, globals,
+            );
+            finally_task_result(
+                result,
+                execute_runtime_task_plan_with_applier(
+                    *cleanup, context, stdout, stderr, applier, globals,
+                ),
+            )
+        }
+        RuntimeTaskPlan::Parallel { tasks } => crate::task_scheduler::run_concurrent_with_applier(
+            tasks, context, stdout, stderr, applier, globals,
+        )
+        .map(RuntimeValue::List),
         // All other variants delegate to the non-applier executor.
         other => execute_runtime_task_plan_with_context(other, context, stdout, stderr),
     }
 }
 
+/// Turn the outcome of an attempted task into `Ok value` / `Err message`. Fatal errors keep
+/// propagating so that `attempt` cannot swallow them.
+fn attempt_task_result(
+    result: Result<RuntimeValue, RuntimeTaskExecutionError>,
+) -> Result<RuntimeValue, RuntimeTaskExecutionError> {
+    match result {
+        Ok(value) => Ok(RuntimeValue::ResultOk(Box::new(value))),
+        Err(error) if error.is_fatal() => Err(error),
+        Err(error) => Ok(RuntimeValue::ResultErr(Box::new(RuntimeValue::Text(
+            error.message.into(),
+        )))),
+    }
+}
+
+/// Combine the outcome of a task with the outcome of its cleanup. The task's own error wins, so a
+/// failing cleanup cannot hide why the task failed.
+fn finally_task_result(
+    result: Result<RuntimeValue, RuntimeTaskExecutionError>,
+    cleanup: Result<RuntimeValue, RuntimeTaskExecutionError>,
+) -> Result<RuntimeValue, RuntimeTaskExecutionError> {
+    let value = result?;
+    cleanup?;
+    Ok(value)
+}
+
 /// Execute a [`RuntimeValue`] with an applier callback. If the value is a `Task` with deferred
 /// composition plans, those are resolved using `applier` and `globals`.
 pub(crate) fn execute_runtime_value_with_context_effects_and_applier(
@@ -727,6 +1018,22 @@ pub fn execute_runtime_value_with_context(
     Ok(execute_runtime_value_with_context_effects(value, context, stdout, stderr)?.value)
 }
 
+/// Like [`execute_runtime_value_with_context`], but resolves deferred `Map`/`Apply`/`Chain`/`Join`
+/// plans through `applier`. Composed plans run their inner tasks strictly in source order.
+pub fn execute_runtime_value_with_context_and_applier(
+    value: RuntimeValue,
+    context: &SourceProviderContext,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+    applier: &mut dyn TaskFunctionApplier,
+    globals: &BTreeMap<ItemId, RuntimeValue>,
+) -> Result<RuntimeValue, RuntimeTaskExecutionError> {
+    Ok(execute_runtime_value_with_context_effects_and_applier(
+        value, context, stdout, stderr, applier, globals,
+    )?
+    .value)
+}
+
 #[cfg_attr(not(test), allow(dead_code))]
 pub(crate) fn execute_runtime_value_with_effects(
     value: RuntimeValue,
@@ -796,52 +1103,493 @@ fn task_error(message: impl Into<String>) -> RuntimeTaskExecutionError {
     RuntimeTaskExecutionError::new(message)
 }
 
-fn runtime_dbus_call_error(error: glib::Error) -> RuntimeTaskExecutionError {
-    use gio::DBusError;
+/// How often a sleeping task or one waiting on an HTTP exchange checks whether it was cancelled.
+const CANCELLATION_POLL: std::time::Duration = std::time::Duration::from_millis(10);
 
-    let message = error.message().to_string();
-    let value = match error.kind::<DBusError>() {
-        Some(DBusError::NameHasNoOwner) => {
-            runtime_dbus_error("NameNotOwned", vec![RuntimeValue::Text(message.into())])
-        }
-        Some(DBusError::ServiceUnknown) => {
-            runtime_dbus_error("ServiceUnknown", vec![RuntimeValue::Text(message.into())])
-        }
-        Some(DBusError::NoReply | DBusError::TimedOut | DBusError::Timeout) => {
-            runtime_dbus_error("NoReply", Vec::new())
-        }
-        Some(DBusError::AccessDenied) => {
-            runtime_dbus_error("AccessDenied", vec![RuntimeValue::Text(message.into())])
+/// Block for `ms` milliseconds in short slices so a cancelled task stops waiting promptly.
+/// Negative durations return at once.
+fn sleep_unless_cancelled(
+    ms: i64,
+    context: &SourceProviderContext,
+) -> Result<(), RuntimeTaskExecutionError> {
+    let duration = std::time::Duration::from_millis(u64::try_from(ms).unwrap_or(0));
+    let deadline = std::time::Instant::now() + duration;
+    loop {
+        if context.is_cancelled() {
+            return Err(task_error(format!("time.sleep({ms}) was cancelled")));
         }
-        Some(DBusError::InvalidArgs) => {
-            runtime_dbus_error("InvalidArgs", vec![RuntimeValue::Text(message.into())])
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        if remaining.is_zero() {
+            return Ok(());
         }
-        _ => runtime_dbus_error(
-            "DbusProtocolError",
-            vec![RuntimeValue::Text(message.into())],
+        std::thread::sleep(remaining.min(CANCELLATION_POLL));
+    }
+}
+
+/// The process-wide system clipboard handle. On X11 and Wayland the written text is only served
+/// while its owner lives, so one handle is kept for the rest of the run instead of one per task.
+#[cfg(feature = "clipboard")]
+static SYSTEM_CLIPBOARD: std::sync::Mutex<Option<arboard::Clipboard>> = std::sync::Mutex::new(None);
+
+#[cfg(feature = "clipboard")]
+fn with_system_clipboard<T>(
+    operation: &str,
+    run: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>,
+) -> Result<T, RuntimeTaskExecutionError> {
+    let mut clipboard = SYSTEM_CLIPBOARD
+        .lock()
+        .expect("system clipboard mutex should not be poisoned");
+    let clipboard = match clipboard.as_mut() {
+        Some(clipboard) => clipboard,
+        None => clipboard.insert(
+            arboard::Clipboard::new()
+                .map_err(|error| task_error(format!("{operation} failed: {error}")))?,
         ),
     };
-    RuntimeTaskExecutionError::new(value.to_string())
+    run(clipboard).map_err(|error| task_error(format!("{operation} failed: {error}")))
 }
 
-fn runtime_dbus_error(variant_name: &str, fields: Vec<RuntimeValue>) -> RuntimeValue {
+#[cfg(feature = "clipboard")]
+fn runtime_clipboard_read() -> Result<String, RuntimeTaskExecutionError> {
+    with_system_clipboard("clipboard.read", |clipboard| clipboard.get_text())
+}
+
+#[cfg(feature = "clipboard")]
+fn runtime_clipboard_write(text: &str) -> Result<(), RuntimeTaskExecutionError> {
+    with_system_clipboard("clipboard.write", |clipboard| clipboard.set_text(text))
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn runtime_clipboard_read() -> Result<String, RuntimeTaskExecutionError> {
+    Err(task_error("clipboard not supported"))
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn runtime_clipboard_write(_text: &str) -> Result<(), RuntimeTaskExecutionError> {
+    Err(task_error("clipboard not supported"))
+}
+
+/// Build an `aivi.data.json` `Json` value. `serde_json::Map` keeps keys sorted, so decoded
+/// objects list their entries in key order.
+/// Key every group by index (`"0"` is the whole match) and named groups also by name.
+/// Optional groups that did not participate in the match map to an empty `Text`.
+fn runtime_regex_captures(re: &Regex, captures: &regex::Captures<'_>) -> RuntimeValue {
+    let group_text = |group: Option<regex::Match<'_>>| {
+        RuntimeValue::Text(group.map_or("", |group| group.as_str()).into())
+    };
+    let mut entries = (0..captures.len())
+        .map(|index| RuntimeMapEntry {
+            key: RuntimeValue::Text(index.to_string().into()),
+            value: group_text(captures.get(index)),
+        })
+        .collect::<Vec<_>>();
+    entries.extend(re.capture_names().flatten().map(|name| RuntimeMapEntry {
+        key: RuntimeValue::Text(name.into()),
+        value: group_text(captures.name(name)),
+    }));
+    RuntimeValue::Map(RuntimeMap::from_entries(entries))
+}
+
+fn runtime_json_value(
+    value: &serde_json::Value,
+    item: aivi_hir::ItemId,
+    type_name: &str,
+) -> RuntimeValue {
+    let (variant_name, fields) = match value {
+        serde_json::Value::Null => ("JsonNull", Vec::new()),
+        serde_json::Value::Bool(flag) => ("JsonBool", vec![RuntimeValue::Bool(*flag)]),
+        serde_json::Value::Number(number) => (
+            "JsonNumber",
+            vec![RuntimeValue::Float(
+                number
+                    .as_f64()
+                    .and_then(RuntimeFloat::new)
+                    .expect("serde_json numbers are finite"),
+            )],
+        ),
+        serde_json::Value::String(text) => {
+            ("JsonString", vec![RuntimeValue::Text(text.as_str().into())])
+        }
+        serde_json::Value::Array(elements) => (
+            "JsonArray",
+            vec![RuntimeValue::List(
+                elements
+                    .iter()
+                    .map(|element| runtime_json_value(element, item, type_name))
+                    .collect(),
+            )],
+        ),
+        serde_json::Value::Object(object) => {
+            let entries = object
+                .iter()
+                .map(|(key, value)| {
+                    RuntimeValue::Record(vec![
+                        aivi_backend::RuntimeRecordField {
+                            label: "key".into(),
+                            value: RuntimeValue::Text(key.as_str().into()),
+                        },
+                        aivi_backend::RuntimeRecordField {
+                            label: "value".into(),
+                            value: runtime_json_value(value, item, type_name),
+                        },
+                    ])
+                })
+                .collect();
+            (
+                "JsonObject",
+                vec![RuntimeValue::Record(vec![
+                    aivi_backend::RuntimeRecordField {
+                        label: "entries".into(),
+                        value: RuntimeValue::List(entries),
+                    },
+                ])],
+            )
+        }
+    };
     RuntimeValue::Sum(aivi_backend::RuntimeSumValue {
-        item: aivi_hir::ItemId::from_raw(0),
-        type_name: "DbusError".into(),
+        item,
+        type_name: type_name.into(),
         variant_name: variant_name.into(),
         fields,
     })
 }
 
-const AIVI_SECRET_SERVICE_ATTRIBUTE: &str = "aivi.secret.service";
-const FREEDESKTOP_NOTIFICATIONS_DESTINATION: &str = "org.freedesktop.Notifications";
-const FREEDESKTOP_NOTIFICATIONS_PATH: &str = "/org/freedesktop/Notifications";
-const FREEDESKTOP_NOTIFICATIONS_INTERFACE: &str = "org.freedesktop.Notifications";
-const FREEDESKTOP_NOTIFICATIONS_DEFAULT_TIMEOUT_MS: i32 = -1;
-
-fn runtime_secret_lookup(
-    service: &str,
-    attributes: &[(Box<str>, Box<str>)],
+/// Run an HTTP plan on a helper thread so a cancelled task stops waiting promptly.
+///
+/// ureq blocks until the server answers, so the helper is abandoned on cancellation. Every body
+/// is read through [`CancellableBody`], which fails the next chunk once the task is cancelled, so
+/// the helper stops as soon as data arrives. Requests have no deadline unless they set
+/// `timeoutMs`: long polls and slow transfers are legitimate, so a server that never answers
+/// keeps the abandoned helper blocked until it closes the connection. Tasks that cannot be
+/// cancelled run on the calling thread.
+fn http_unless_cancelled(
+    plan: RuntimeTaskPlan,
+    context: &SourceProviderContext,
+) -> Result<RuntimeValue, RuntimeTaskExecutionError> {
+    let Some(cancellation) = context.cancellation().cloned() else {
+        return execute_runtime_http_plan(plan, None);
+    };
+    let label = plan.to_string();
+    let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+    let observer = cancellation.clone();
+    thread::spawn(move || {
+        let _ = sender.send(execute_runtime_http_plan(plan, Some(observer)));
+    });
+    loop {
+        if cancellation.is_cancelled() {
+            return Err(task_error(format!("{label} was cancelled")));
+        }
+        match receiver.recv_timeout(CANCELLATION_POLL) {
+            Ok(result) => return result,
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(task_error(format!("{label} stopped without a result")));
+            }
+        }
+    }
+}
+
+fn execute_runtime_http_plan(
+    plan: RuntimeTaskPlan,
+    cancellation: Option<crate::CancellationObserver>,
+) -> Result<RuntimeValue, RuntimeTaskExecutionError> {
+    let cancellation = cancellation.as_ref();
+    let agent = runtime_http_agent(None, None);
+    match plan {
+        RuntimeTaskPlan::HttpGet { url } => {
+            let response = agent
+                .get(url.as_ref())
+                .call()
+                .map_err(|e| task_error(format!("http get: {e}")))?;
+            Ok(RuntimeValue::Text(
+                read_http_text(response, cancellation)?.into(),
+            ))
+        }
+        RuntimeTaskPlan::HttpGetBytes { url } => {
+            let mut bytes = Vec::new();
+            let response = agent
+                .get(url.as_ref())
+                .call()
+                .map_err(|e| task_error(format!("http get: {e}")))?;
+            CancellableBody {
+                inner: response.into_reader(),
+                cancellation,
+            }
+            .read_to_end(&mut bytes)
+            .map_err(|e| task_error(format!("http read: {e}")))?;
+            Ok(RuntimeValue::Bytes(bytes.into_boxed_slice()))
+        }
+        RuntimeTaskPlan::HttpGetStatus { url } => {
+            let status = agent
+                .get(url.as_ref())
+                .call()
+                .map(|r| r.status() as i64)
+                .unwrap_or_else(|e| match e {
+                    ureq::Error::Status(code, _) => code as i64,
+                    _ => 0,
+                });
+            Ok(RuntimeValue::Int(status))
+        }
+        RuntimeTaskPlan::HttpPost {
+            url,
+            content_type,
+            body,
+        } => {
+            let response = agent
+                .post(url.as_ref())
+                .set("Content-Type", content_type.as_ref())
+                .send_string(body.as_ref())
+                .map_err(|e| task_error(format!("http post: {e}")))?;
+            Ok(RuntimeValue::Text(
+                read_http_text(response, cancellation)?.into(),
+            ))
+        }
+        RuntimeTaskPlan::HttpPut {
+            url,
+            content_type,
+            body,
+        } => {
+            let response = agent
+                .put(url.as_ref())
+                .set("Content-Type", content_type.as_ref())
+                .send_string(body.as_ref())
+                .map_err(|e| task_error(format!("http put: {e}")))?;
+            Ok(RuntimeValue::Text(
+                read_http_text(response, cancellation)?.into(),
+            ))
+        }
+        RuntimeTaskPlan::HttpDelete { url } => {
+            let response = agent
+                .delete(url.as_ref())
+                .call()
+                .map_err(|e| task_error(format!("http delete: {e}")))?;
+            Ok(RuntimeValue::Text(
+                read_http_text(response, cancellation)?.into(),
+            ))
+        }
+        RuntimeTaskPlan::HttpHead { url } => {
+            let response = agent
+                .head(url.as_ref())
+                .call()
+                .map_err(|e| task_error(format!("http head: {e}")))?;
+            let names = response.headers_names();
+            let headers: Vec<RuntimeValue> = names
+                .iter()
+                .filter_map(|name| {
+                    response.header(name).map(|val| {
+                        RuntimeValue::Tuple(vec![
+                            RuntimeValue::Text(name.clone().into()),
+                            RuntimeValue::Text(val.into()),
+                        ])
+                    })
+                })
+                .collect();
+            Ok(RuntimeValue::List(headers))
+        }
+        RuntimeTaskPlan::HttpPostJson { url, body } => {
+            let response = agent
+                .post(url.as_ref())
+                .set("Content-Type", "application/json")
+                .send_string(body.as_ref())
+                .map_err(|e| task_error(format!("http post json: {e}")))?;
+            Ok(RuntimeValue::Text(
+                read_http_text(response, cancellation)?.into(),
+            ))
+        }
+        RuntimeTaskPlan::HttpRequest {
+            method,
+            url,
+            headers,
+            body,
+            timeout_ms,
+            max_redirects,
+        } => runtime_http_request(
+            &method,
+            &url,
+            &headers,
+            body.as_deref(),
+            timeout_ms,
+            max_redirects,
+            cancellation,
+        ),
+        RuntimeTaskPlan::HttpDownload { url, path } => {
+            runtime_http_download(&agent, &url, &path, cancellation)?;
+            Ok(RuntimeValue::Unit)
+        }
+        other => unreachable!("{other} is not an HTTP plan"),
+    }
+}
+
+/// Read a whole response body as text, stopping between chunks once the task is cancelled.
+fn read_http_text(
+    response: ureq::Response,
+    cancellation: Option<&crate::CancellationObserver>,
+) -> Result<String, RuntimeTaskExecutionError> {
+    let mut text = String::new();
+    CancellableBody {
+        inner: response.into_reader(),
+        cancellation,
+    }
+    .read_to_string(&mut text)
+    .map_err(|e| task_error(format!("http read: {e}")))?;
+    Ok(text)
+}
+
+/// A response body that fails its next read once the task reading it is cancelled.
+struct CancellableBody<'a, R> {
+    inner: R,
+    cancellation: Option<&'a crate::CancellationObserver>,
+}
+
+impl<R: Read> Read for CancellableBody<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self
+            .cancellation
+            .is_some_and(crate::CancellationObserver::is_cancelled)
+        {
+            return Err(std::io::Error::other("cancelled"));
+        }
+        self.inner.read(buf)
+    }
+}
+
+/// ureq's own default, used when a request leaves `maxRedirects` as `None`.
+const HTTP_DEFAULT_MAX_REDIRECTS: u32 = 5;
+
+fn runtime_http_agent(timeout_ms: Option<i64>, max_redirects: Option<i64>) -> ureq::Agent {
+    let redirects = max_redirects.map_or(HTTP_DEFAULT_MAX_REDIRECTS, |limit| {
+        u32::try_from(limit.max(0)).unwrap_or(u32::MAX)
+    });
+    let mut builder = ureq::AgentBuilder::new().redirects(redirects);
+    if let Some(timeout_ms) = timeout_ms {
+        builder = builder.timeout(Duration::from_millis(timeout_ms.max(0) as u64));
+    }
+    builder.build()
+}
+
+fn runtime_http_request(
+    method: &str,
+    url: &str,
+    headers: &[(Box<str>, Box<str>)],
+    body: Option<&str>,
+    timeout_ms: Option<i64>,
+    max_redirects: Option<i64>,
+    cancellation: Option<&crate::CancellationObserver>,
+) -> Result<RuntimeValue, RuntimeTaskExecutionError> {
+    let mut request = runtime_http_agent(timeout_ms, max_redirects).request(method, url);
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+    let sent = match body {
+        Some(body) => request.send_string(body),
+        None => request.call(),
+    };
+    // Error statuses are still responses; only transport failures such as timeouts fail the task.
+    let response = match sent {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(error) => return Err(task_error(format!("http {method}: {error}"))),
+    };
+    let status = i64::from(response.status());
+    let headers = response
+        .headers_names()
+        .into_iter()
+        .filter_map(|name| {
+            let value = response.header(&name)?.to_owned();
+            Some(RuntimeValue::Tuple(vec![
+                RuntimeValue::Text(name.into()),
+                RuntimeValue::Text(value.into()),
+            ]))
+        })
+        .collect();
+    let body = read_http_text(response, cancellation)?;
+    Ok(RuntimeValue::Record(vec![
+        aivi_backend::RuntimeRecordField {
+            label: "status".into(),
+            value: RuntimeValue::Int(status),
+        },
+        aivi_backend::RuntimeRecordField {
+            label: "headers".into(),
+            value: RuntimeValue::List(headers),
+        },
+        aivi_backend::RuntimeRecordField {
+            label: "body".into(),
+            value: RuntimeValue::Text(body.into()),
+        },
+    ]))
+}
+
+/// Copy the response body to `path` as it arrives, so large downloads are never held in memory.
+/// A partially written file is removed when the transfer fails.
+fn runtime_http_download(
+    agent: &ureq::Agent,
+    url: &str,
+    path: &str,
+    cancellation: Option<&crate::CancellationObserver>,
+) -> Result<(), RuntimeTaskExecutionError> {
+    let response = agent
+        .get(url)
+        .call()
+        .map_err(|error| task_error(format!("http download: {error}")))?;
+    let mut file = fs::File::create(path)
+        .map_err(|error| task_error(format!("http download: cannot create {path}: {error}")))?;
+    let mut body = CancellableBody {
+        inner: response.into_reader(),
+        cancellation,
+    };
+    std::io::copy(&mut body, &mut file)
+        .map(drop)
+        .map_err(|error| {
+            let _ = fs::remove_file(path);
+            task_error(format!("http download {url}: {error}"))
+        })
+}
+
+fn runtime_dbus_call_error(error: glib::Error) -> RuntimeTaskExecutionError {
+    use gio::DBusError;
+
+    let message = error.message().to_string();
+    let value = match error.kind::<DBusError>() {
+        Some(DBusError::NameHasNoOwner) => {
+            runtime_dbus_error("NameNotOwned", vec![RuntimeValue::Text(message.into())])
+        }
+        Some(DBusError::ServiceUnknown) => {
+            runtime_dbus_error("ServiceUnknown", vec![RuntimeValue::Text(message.into())])
+        }
+        Some(DBusError::NoReply | DBusError::TimedOut | DBusError::Timeout) => {
+            runtime_dbus_error("NoReply", Vec::new())
+        }
+        Some(DBusError::AccessDenied) => {
+            runtime_dbus_error("AccessDenied", vec![RuntimeValue::Text(message.into())])
+        }
+        Some(DBusError::InvalidArgs) => {
+            runtime_dbus_error("InvalidArgs", vec![RuntimeValue::Text(message.into())])
+        }
+        _ => runtime_dbus_error(
+            "DbusProtocolError",
+            vec![RuntimeValue::Text(message.into())],
+        ),
+    };
+    RuntimeTaskExecutionError::new(value.to_string())
+}
+
+fn runtime_dbus_error(variant_name: &str, fields: Vec<RuntimeValue>) -> RuntimeValue {
+    RuntimeValue::Sum(aivi_backend::RuntimeSumValue {
+        item: aivi_hir::ItemId::from_raw(0),
+        type_name: "DbusError".into(),
+        variant_name: variant_name.into(),
+        fields,
+    })
+}
+
+const AIVI_SECRET_SERVICE_ATTRIBUTE: &str = "aivi.secret.service";
+const FREEDESKTOP_NOTIFICATIONS_DESTINATION: &str = "org.freedesktop.Notifications";
+const FREEDESKTOP_NOTIFICATIONS_PATH: &str = "/org/freedesktop/Notifications";
+const FREEDESKTOP_NOTIFICATIONS_INTERFACE: &str = "org.freedesktop.Notifications";
+const FREEDESKTOP_NOTIFICATIONS_DEFAULT_TIMEOUT_MS: i32 = -1;
+
+fn runtime_secret_lookup(
+    service: &str,
+    attributes: &[(Box<str>, Box<str>)],
 ) -> Result<RuntimeValue, RuntimeTaskExecutionError> {
     let ss = runtime_secret_service()?;
     let mut items = runtime_secret_search_items(&ss, service, attributes)?;
@@ -853,7 +1601,7 @@ fn runtime_secret_lookup(
         RuntimeTaskExecutionError::new(runtime_secret_protocol_error(error.to_string()).to_string())
     })?;
     Ok(RuntimeValue::OptionSome(Box::new(RuntimeValue::Text(
-        secret.into_boxed_str(),
+        secret.into(),
     ))))
 }
 
@@ -1109,7 +1857,7 @@ fn runtime_secret_error(error: secret_service::Error) -> RuntimeTaskExecutionErr
 fn runtime_secret_protocol_error(message: String) -> RuntimeValue {
     runtime_secret_sum(
         "SecretProtocolError",
-        vec![RuntimeValue::Text(message.into_boxed_str())],
+        vec![RuntimeValue::Text(message.into())],
     )
 }
 
@@ -1358,7 +2106,7 @@ fn parse_pkce_config(
     let scopes = scopes
         .iter()
         .map(|scope| match scope {
-            RuntimeValue::Text(text) => Ok(text.clone()),
+            RuntimeValue::Text(text) => Ok(Box::from(text.as_ref())),
             _ => Err(runtime_pkce_invalid_response(
                 "PKCE config scopes must be List Text".to_owned(),
             )),
@@ -1435,7 +2183,7 @@ fn runtime_record_text_field(
     label: &str,
 ) -> Result<Box<str>, RuntimeTaskExecutionError> {
     match runtime_record_field(fields, label) {
-        Some(RuntimeValue::Text(text)) => Ok(text.clone()),
+        Some(RuntimeValue::Text(text)) => Ok(Box::from(text.as_ref())),
         _ => Err(runtime_pkce_invalid_response(format!(
             "PKCE config field `{label}` must be Text"
         ))),
@@ -1448,7 +2196,7 @@ fn runtime_record_optional_text_field(
 ) -> Result<Option<Box<str>>, RuntimeTaskExecutionError> {
     match runtime_record_field(fields, label) {
         Some(RuntimeValue::OptionSome(value)) => match value.as_ref() {
-            RuntimeValue::Text(text) => Ok(Some(text.clone())),
+            RuntimeValue::Text(text) => Ok(Some(Box::from(text.as_ref()))),
             _ => Err(runtime_notification_error(format!(
                 "notification payload field `{label}` must be Option Text"
             ))),
@@ -1504,7 +2252,7 @@ fn current_unix_ms() -> i64 {
 fn runtime_pkce_network_error(error: impl fmt::Display) -> RuntimeTaskExecutionError {
     runtime_pkce_error(
         "NetworkError",
-        vec![RuntimeValue::Text(error.to_string().into_boxed_str())],
+        vec![RuntimeValue::Text(error.to_string().into())],
     )
 }
 
@@ -1513,10 +2261,7 @@ fn runtime_notification_error(error: impl fmt::Display) -> RuntimeTaskExecutionE
 }
 
 fn runtime_pkce_invalid_response(message: String) -> RuntimeTaskExecutionError {
-    runtime_pkce_error(
-        "InvalidResponse",
-        vec![RuntimeValue::Text(message.into_boxed_str())],
-    )
+    runtime_pkce_error("InvalidResponse", vec![RuntimeValue::Text(message.into())])
 }
 
 fn runtime_pkce_error(variant_name: &str, fields: Vec<RuntimeValue>) -> RuntimeTaskExecutionError {
@@ -1738,8 +2483,8 @@ fn decode_sqlite_rows(
         let entries = fields
             .into_iter()
             .map(|(key, value)| RuntimeMapEntry {
-                key: RuntimeValue::Text(key.into_boxed_str()),
-                value: RuntimeValue::Text(sqlite_json_value_to_text(value).into_boxed_str()),
+                key: RuntimeValue::Text(key.into()),
+                value: RuntimeValue::Text(sqlite_json_value_to_text(value).into()),
             })
             .collect::<Vec<_>>();
         decoded_rows.push(RuntimeValue::Map(RuntimeMap::from_entries(entries)));
@@ -1758,7 +2503,7 @@ fn sqlite_json_value_to_text(value: serde_json::Value) -> String {
 }
 
 fn db_task_error_value(message: String) -> RuntimeValue {
-    RuntimeValue::ResultErr(Box::new(RuntimeValue::Text(message.into_boxed_str())))
+    RuntimeValue::ResultErr(Box::new(RuntimeValue::Text(message.into())))
 }
 
 fn strip_runtime_signal(value: &RuntimeValue) -> &RuntimeValue {
@@ -1817,7 +2562,7 @@ fn read_os_random_bytes(count: usize) -> Result<Box<[u8]>, RuntimeTaskExecutionE
 #[cfg(test)]
 mod tests {
     use std::{
-        collections::BTreeSet,
+        collections::{BTreeMap, BTreeSet},
         fs,
         io::{BufRead, BufReader, Read, Write},
         net::TcpListener,
@@ -1827,15 +2572,16 @@ mod tests {
     };
 
     use aivi_backend::{
-        RuntimeCustomCapabilityCommandPlan, RuntimeDbCommitPlan, RuntimeDbConnection,
-        RuntimeDbQueryPlan, RuntimeDbStatement, RuntimeDbTaskPlan, RuntimeMap, RuntimeMapEntry,
-        RuntimeNamedValue, RuntimeTaskPlan, RuntimeValue,
+        EvaluationError, ItemId, RuntimeCustomCapabilityCommandPlan, RuntimeDbCommitPlan,
+        RuntimeDbConnection, RuntimeDbQueryPlan, RuntimeDbStatement, RuntimeDbTaskPlan,
+        RuntimeFloat, RuntimeMap, RuntimeMapEntry, RuntimeNamedValue, RuntimeTaskPlan,
+        RuntimeValue, TaskFunctionApplier,
     };
 
     use super::{
-        CustomCapabilityCommandExecutor, RuntimeDbCommitInvalidation, execute_runtime_task_plan,
-        execute_runtime_task_plan_with_context, execute_runtime_value,
-        execute_runtime_value_with_effects,
+        CustomCapabilityCommandExecutor, RuntimeDbCommitInvalidation, execute_runtime_http_plan,
+        execute_runtime_task_plan, execute_runtime_task_plan_with_context, execute_runtime_value,
+        execute_runtime_value_with_context_and_applier, execute_runtime_value_with_effects,
     };
     use crate::SourceProviderContext;
 
@@ -1991,75 +2737,1375 @@ mod tests {
         assert!(stderr.is_empty());
     }
 
-    #[test]
-    fn execute_runtime_task_plan_returns_pure_payload() {
-        let mut stdout = Vec::new();
-        let mut stderr = Vec::new();
+    fn json_sum(variant_name: &str, fields: Vec<RuntimeValue>) -> RuntimeValue {
+        RuntimeValue::Sum(aivi_backend::RuntimeSumValue {
+            item: aivi_hir::ItemId::from_raw(7),
+            type_name: "Json".into(),
+            variant_name: variant_name.into(),
+            fields,
+        })
+    }
 
-        let result = execute_runtime_task_plan(
-            RuntimeTaskPlan::Pure {
-                value: Box::new(RuntimeValue::Bool(true)),
+    fn json_entry(key: &str, value: RuntimeValue) -> RuntimeValue {
+        RuntimeValue::Record(vec![
+            aivi_backend::RuntimeRecordField {
+                label: "key".into(),
+                value: RuntimeValue::Text(key.into()),
             },
-            &mut stdout,
-            &mut stderr,
-        )
-        .expect("pure task should execute");
+            aivi_backend::RuntimeRecordField {
+                label: "value".into(),
+                value,
+            },
+        ])
+    }
 
-        assert_eq!(result, RuntimeValue::Bool(true));
-        assert!(stdout.is_empty());
-        assert!(stderr.is_empty());
+    fn json_object(entries: Vec<RuntimeValue>) -> RuntimeValue {
+        json_sum(
+            "JsonObject",
+            vec![RuntimeValue::Record(vec![
+                aivi_backend::RuntimeRecordField {
+                    label: "entries".into(),
+                    value: RuntimeValue::List(entries),
+                },
+            ])],
+        )
     }
 
     #[test]
-    fn execute_runtime_task_plan_reports_invalid_random_ranges() {
-        let mut stdout = Vec::new();
-        let mut stderr = Vec::new();
+    fn json_parse_builds_nested_values_with_sorted_keys() {
+        let result = execute_runtime_value(
+            RuntimeValue::Task(RuntimeTaskPlan::JsonParseInto {
+                json: r#"{"b":{"d":[1,"x"],"c":null},"a":false}"#.into(),
+                item: aivi_hir::ItemId::from_raw(7),
+                type_name: "Json".into(),
+            }),
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("valid JSON should parse");
 
-        let error = execute_runtime_task_plan(
-            RuntimeTaskPlan::RandomInt { low: 9, high: 3 },
-            &mut stdout,
-            &mut stderr,
+        assert_eq!(
+            result,
+            json_object(vec![
+                json_entry("a", json_sum("JsonBool", vec![RuntimeValue::Bool(false)])),
+                json_entry(
+                    "b",
+                    json_object(vec![
+                        json_entry("c", json_sum("JsonNull", Vec::new())),
+                        json_entry(
+                            "d",
+                            json_sum(
+                                "JsonArray",
+                                vec![RuntimeValue::List(vec![
+                                    json_sum(
+                                        "JsonNumber",
+                                        vec![RuntimeValue::Float(RuntimeFloat::new(1.0).unwrap())],
+                                    ),
+                                    json_sum("JsonString", vec![RuntimeValue::Text("x".into())]),
+                                ])],
+                            ),
+                        ),
+                    ]),
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn json_parse_stream_reads_newline_delimited_values() {
+        let result = execute_runtime_value(
+            RuntimeValue::Task(RuntimeTaskPlan::JsonParseStreamInto {
+                json: "true\n\n{\"k\":\"v\"}\n".into(),
+                item: aivi_hir::ItemId::from_raw(7),
+                type_name: "Json".into(),
+            }),
+            &mut Vec::new(),
+            &mut Vec::new(),
         )
-        .expect_err("invalid random ranges should fail");
+        .expect("NDJSON should parse");
 
         assert_eq!(
-            error.to_string(),
-            "randomInt requires `low <= high`, found low=9 and high=3"
+            result,
+            RuntimeValue::List(vec![
+                json_sum("JsonBool", vec![RuntimeValue::Bool(true)]),
+                json_object(vec![json_entry(
+                    "k",
+                    json_sum("JsonString", vec![RuntimeValue::Text("v".into())]),
+                )]),
+            ])
         );
-        assert!(stdout.is_empty());
-        assert!(stderr.is_empty());
     }
 
     #[test]
-    fn execute_runtime_task_plan_reports_missing_custom_command_executor() {
-        let mut stdout = Vec::new();
-        let mut stderr = Vec::new();
+    fn json_parse_reports_malformed_input() {
+        let error = execute_runtime_value(
+            RuntimeValue::Task(RuntimeTaskPlan::JsonParseStreamInto {
+                json: "1\n{\"open\":\n".into(),
+                item: aivi_hir::ItemId::from_raw(7),
+                type_name: "Json".into(),
+            }),
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect_err("malformed NDJSON should fail");
 
-        let error = execute_runtime_task_plan(
-            RuntimeTaskPlan::CustomCapabilityCommand(RuntimeCustomCapabilityCommandPlan {
-                provider_key: "custom.feed".into(),
-                command: "delete".into(),
-                provider_arguments: Box::new([]),
-                options: Box::new([]),
-                arguments: Box::new([]),
+        assert!(
+            error.to_string().contains("invalid JSON on line 2"),
+            "unexpected error: {error}"
+        );
+    }
+
+    fn regex_capture_entries(value: &RuntimeValue) -> Vec<(&str, &str)> {
+        let RuntimeValue::Map(map) = value else {
+            panic!("expected a capture map, found {value:?}");
+        };
+        map.iter()
+            .map(|(key, value)| match (key, value) {
+                (RuntimeValue::Text(key), RuntimeValue::Text(value)) => {
+                    (key.as_ref(), value.as_ref())
+                }
+                other => panic!("expected Text capture entries, found {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn regex_captures_keys_groups_by_index_and_name() {
+        let value = execute_runtime_value(
+            RuntimeValue::Task(RuntimeTaskPlan::RegexCaptures {
+                pattern: r"(?<key>\w+)=(\d+)(?<unit>ms)?".into(),
+                text: "timeout=30".into(),
             }),
-            &mut stdout,
-            &mut stderr,
+            &mut Vec::new(),
+            &mut Vec::new(),
         )
-        .expect_err("custom capability commands should fail clearly without a registered executor");
+        .expect("captures should run");
 
+        let RuntimeValue::OptionSome(captures) = value else {
+            panic!("expected Some captures, found {value:?}");
+        };
         assert_eq!(
-            error.to_string(),
-            "custom capability command `custom.feed.delete` has no registered executor"
+            regex_capture_entries(&captures),
+            vec![
+                ("0", "timeout=30"),
+                ("1", "timeout"),
+                ("2", "30"),
+                ("3", ""),
+                ("key", "timeout"),
+                ("unit", ""),
+            ]
         );
     }
 
     #[test]
-    fn execute_runtime_task_plan_runs_custom_capability_commands_through_registered_executor() {
-        let context = SourceProviderContext::current()
-            .with_custom_capability_command_executor(Arc::new(EchoCustomCapabilityCommandExecutor));
-        let mut stdout = Vec::new();
-        let mut stderr = Vec::new();
+    fn regex_captures_returns_none_without_a_match() {
+        let value = execute_runtime_value(
+            RuntimeValue::Task(RuntimeTaskPlan::RegexCaptures {
+                pattern: r"(?<year>\d{4})".into(),
+                text: "no digits here".into(),
+            }),
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("captures should run");
+
+        assert_eq!(value, RuntimeValue::OptionNone);
+    }
+
+    #[test]
+    fn regex_captures_all_returns_one_map_per_match() {
+        let value = execute_runtime_value(
+            RuntimeValue::Task(RuntimeTaskPlan::RegexCapturesAll {
+                pattern: r"(?<name>[a-z]+):(\d+)".into(),
+                text: "a:1, bc:22".into(),
+            }),
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("capturesAll should run");
+
+        let RuntimeValue::List(matches) = value else {
+            panic!("expected a list of captures, found {value:?}");
+        };
+        assert_eq!(
+            matches
+                .iter()
+                .map(regex_capture_entries)
+                .collect::<Vec<_>>(),
+            vec![
+                vec![("0", "a:1"), ("1", "a"), ("2", "1"), ("name", "a")],
+                vec![("0", "bc:22"), ("1", "bc"), ("2", "22"), ("name", "bc")],
+            ]
+        );
+    }
+
+    /// Continuations are `Text` values that become a stdout write; everything else is returned
+    /// unchanged.
+    struct WriteTextApplier;
+
+    impl TaskFunctionApplier for WriteTextApplier {
+        fn apply_task_function(
+            &mut self,
+            function: RuntimeValue,
+            _args: Vec<RuntimeValue>,
+            _globals: &BTreeMap<ItemId, RuntimeValue>,
+        ) -> Result<RuntimeValue, EvaluationError> {
+            Ok(match function {
+                RuntimeValue::Text(text) => RuntimeValue::Task(RuntimeTaskPlan::StdoutWrite {
+                    text: Box::from(text.as_ref()),
+                }),
+                other => other,
+            })
+        }
+    }
+
+    #[test]
+    fn composed_task_plans_run_effects_in_source_order() {
+        let write = |text: &str| RuntimeTaskPlan::StdoutWrite { text: text.into() };
+        let plan = RuntimeTaskPlan::Join {
+            outer: Box::new(RuntimeTaskPlan::Map {
+                function: Box::new(RuntimeValue::Text("5\n".into())),
+                inner: Box::new(RuntimeTaskPlan::Apply {
+                    function_task: Box::new(RuntimeTaskPlan::Chain {
+                        function: Box::new(RuntimeValue::Text("2\n".into())),
+                        inner: Box::new(write("1\n")),
+                    }),
+                    value_task: Box::new(RuntimeTaskPlan::Chain {
+                        function: Box::new(RuntimeValue::Text("4\n".into())),
+                        inner: Box::new(write("3\n")),
+                    }),
+                }),
+            }),
+        };
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let result = execute_runtime_value_with_context_and_applier(
+            RuntimeValue::Task(plan),
+            &SourceProviderContext::current(),
+            &mut stdout,
+            &mut stderr,
+            &mut WriteTextApplier,
+            &BTreeMap::new(),
+        )
+        .expect("composed task should execute");
+
+        assert_eq!(result, RuntimeValue::Unit);
+        assert_eq!(String::from_utf8(stdout).unwrap(), "1\n2\n3\n4\n5\n");
+        assert!(stderr.is_empty());
+    }
+
+    #[test]
+    fn execute_runtime_task_plan_returns_pure_payload() {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let result = execute_runtime_task_plan(
+            RuntimeTaskPlan::Pure {
+                value: Box::new(RuntimeValue::Bool(true)),
+            },
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect("pure task should execute");
+
+        assert_eq!(result, RuntimeValue::Bool(true));
+        assert!(stdout.is_empty());
+        assert!(stderr.is_empty());
+    }
+
+    #[test]
+    fn execute_runtime_task_plan_reports_invalid_random_ranges() {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let error = execute_runtime_task_plan(
+            RuntimeTaskPlan::RandomInt { low: 9, high: 3 },
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect_err("invalid random ranges should fail");
+
+        assert_eq!(
+            error.to_string(),
+            "randomInt requires `low <= high`, found low=9 and high=3"
+        );
+        assert!(stdout.is_empty());
+        assert!(stderr.is_empty());
+    }
+
+    #[test]
+    fn execute_runtime_task_plan_reads_args_and_env_from_context() {
+        let context = SourceProviderContext::new(
+            vec!["--verbose".to_owned(), "input.txt".to_owned()],
+            PathBuf::from("/tmp"),
+            BTreeMap::from([
+                ("AIVI_MODE".to_owned(), "test".to_owned()),
+                ("AIVI_LEVEL".to_owned(), "debug".to_owned()),
+                ("HOME".to_owned(), "/home/test".to_owned()),
+            ]),
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let args = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::ProcessArgs,
+            &context,
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect("process args task should execute");
+        let mode = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::EnvGet {
+                name: "AIVI_MODE".into(),
+            },
+            &context,
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect("env get task should execute");
+        let entries = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::EnvList {
+                prefix: "AIVI_".into(),
+            },
+            &context,
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect("env list task should execute");
+
+        assert_eq!(
+            args,
+            RuntimeValue::List(vec![
+                RuntimeValue::Text("--verbose".into()),
+                RuntimeValue::Text("input.txt".into()),
+            ])
+        );
+        assert_eq!(
+            mode,
+            RuntimeValue::OptionSome(Box::new(RuntimeValue::Text("test".into())))
+        );
+        assert_eq!(
+            entries,
+            RuntimeValue::List(vec![
+                RuntimeValue::Tuple(vec![
+                    RuntimeValue::Text("AIVI_LEVEL".into()),
+                    RuntimeValue::Text("debug".into()),
+                ]),
+                RuntimeValue::Tuple(vec![
+                    RuntimeValue::Text("AIVI_MODE".into()),
+                    RuntimeValue::Text("test".into()),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn execute_runtime_task_plan_reports_intercepted_process_exit() {
+        let context = SourceProviderContext::current().with_process_exit_intercepted();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let error = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::ProcessExit { code: 3 },
+            &context,
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect_err("intercepted exits should surface as task failures");
+
+        assert_eq!(error.process_exit_code(), Some(3));
+        assert_eq!(error.to_string(), "process.exit(3) was called");
+    }
+
+    #[test]
+    fn attempt_turns_builtin_task_failures_into_err_values() {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let result = execute_runtime_task_plan(
+            RuntimeTaskPlan::Attempt {
+                inner: Box::new(RuntimeTaskPlan::RandomInt { low: 9, high: 3 }),
+            },
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect("attempt should catch builtin task failures");
+
+        assert_eq!(
+            result,
+            RuntimeValue::ResultErr(Box::new(RuntimeValue::Text(
+                "randomInt requires `low <= high`, found low=9 and high=3".into()
+            )))
+        );
+    }
+
+    #[test]
+    fn attempt_wraps_composed_task_results_in_ok() {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let result = execute_runtime_value_with_context_and_applier(
+            RuntimeValue::Task(RuntimeTaskPlan::Attempt {
+                inner: Box::new(RuntimeTaskPlan::Chain {
+                    function: Box::new(RuntimeValue::Text("2\n".into())),
+                    inner: Box::new(RuntimeTaskPlan::StdoutWrite { text: "1\n".into() }),
+                }),
+            }),
+            &SourceProviderContext::current(),
+            &mut stdout,
+            &mut stderr,
+            &mut WriteTextApplier,
+            &BTreeMap::new(),
+        )
+        .expect("attempted composed task should execute");
+
+        assert_eq!(result, RuntimeValue::ResultOk(Box::new(RuntimeValue::Unit)));
+        assert_eq!(String::from_utf8(stdout).unwrap(), "1\n2\n");
+    }
+
+    #[test]
+    fn attempt_does_not_catch_intercepted_process_exit() {
+        let context = SourceProviderContext::current().with_process_exit_intercepted();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let error = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::Attempt {
+                inner: Box::new(RuntimeTaskPlan::ProcessExit { code: 3 }),
+            },
+            &context,
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect_err("intercepted exits should escape attempt");
+
+        assert!(error.is_fatal());
+        assert_eq!(error.process_exit_code(), Some(3));
+        assert_eq!(error.to_string(), "process.exit(3) was called");
+    }
+
+    #[test]
+    fn finally_runs_cleanup_after_successful_tasks() {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let result = execute_runtime_value_with_context_and_applier(
+            RuntimeValue::Task(RuntimeTaskPlan::Finally {
+                inner: Box::new(RuntimeTaskPlan::Chain {
+                    function: Box::new(RuntimeValue::Text("use\n".into())),
+                    inner: Box::new(RuntimeTaskPlan::StdoutWrite {
+                        text: "acquire\n".into(),
+                    }),
+                }),
+                cleanup: Box::new(RuntimeTaskPlan::StdoutWrite {
+                    text: "release\n".into(),
+                }),
+            }),
+            &SourceProviderContext::current(),
+            &mut stdout,
+            &mut stderr,
+            &mut WriteTextApplier,
+            &BTreeMap::new(),
+        )
+        .expect("task with cleanup should execute");
+
+        assert_eq!(result, RuntimeValue::Unit);
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            "acquire\nuse\nrelease\n"
+        );
+    }
+
+    #[test]
+    fn finally_runs_cleanup_when_the_task_fails() {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let error = execute_runtime_task_plan(
+            RuntimeTaskPlan::Finally {
+                inner: Box::new(RuntimeTaskPlan::RandomInt { low: 9, high: 3 }),
+                cleanup: Box::new(RuntimeTaskPlan::StdoutWrite {
+                    text: "release\n".into(),
+                }),
+            },
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect_err("the task failure should survive its cleanup");
+
+        assert_eq!(
+            error.to_string(),
+            "randomInt requires `low <= high`, found low=9 and high=3"
+        );
+        assert_eq!(String::from_utf8(stdout).unwrap(), "release\n");
+    }
+
+    #[test]
+    fn finally_runs_cleanup_on_fatal_errors() {
+        let context = SourceProviderContext::current().with_process_exit_intercepted();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let error = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::Finally {
+                inner: Box::new(RuntimeTaskPlan::ProcessExit { code: 3 }),
+                cleanup: Box::new(RuntimeTaskPlan::StdoutWrite {
+                    text: "release\n".into(),
+                }),
+            },
+            &context,
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect_err("fatal errors should still propagate after cleanup");
+
+        assert!(error.is_fatal());
+        assert_eq!(String::from_utf8(stdout).unwrap(), "release\n");
+    }
+
+    #[test]
+    fn finally_reports_cleanup_failures_after_successful_tasks() {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let error = execute_runtime_task_plan(
+            RuntimeTaskPlan::Finally {
+                inner: Box::new(RuntimeTaskPlan::StdoutWrite {
+                    text: "work\n".into(),
+                }),
+                cleanup: Box::new(RuntimeTaskPlan::RandomInt { low: 9, high: 3 }),
+            },
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect_err("a failed cleanup should fail the task");
+
+        assert_eq!(
+            error.to_string(),
+            "randomInt requires `low <= high`, found low=9 and high=3"
+        );
+        assert_eq!(String::from_utf8(stdout).unwrap(), "work\n");
+    }
+
+    #[test]
+    fn file_handles_write_then_read_lines() {
+        let context = SourceProviderContext::current();
+        let path = test_path("handle-lines").with_extension("txt");
+        let path_text: Box<str> = path.to_string_lossy().into();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut run = |plan| {
+            execute_runtime_task_plan_with_context(plan, &context, &mut stdout, &mut stderr)
+                .expect("file handle task should execute")
+        };
+
+        let writer = handle_id(run(RuntimeTaskPlan::FsOpen {
+            path: path_text.clone(),
+            mode: "w".into(),
+        }));
+        run(RuntimeTaskPlan::FsWrite {
+            handle: writer,
+            text: "first\r\nsecond\n".into(),
+        });
+        run(RuntimeTaskPlan::FsClose { handle: writer });
+
+        let reader = handle_id(run(RuntimeTaskPlan::FsOpen {
+            path: path_text,
+            mode: "r".into(),
+        }));
+        assert_ne!(reader, writer);
+        let lines = (0..3)
+            .map(|_| run(RuntimeTaskPlan::FsReadLine { handle: reader }))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                RuntimeValue::OptionSome(Box::new(RuntimeValue::Text("first".into()))),
+                RuntimeValue::OptionSome(Box::new(RuntimeValue::Text("second".into()))),
+                RuntimeValue::OptionNone,
+            ]
+        );
+        run(RuntimeTaskPlan::FsClose { handle: reader });
+
+        let error = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::FsClose { handle: reader },
+            &context,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect_err("closing a handle twice should fail");
+        assert_eq!(
+            error.to_string(),
+            format!("fs.close: file handle #{reader} is not open")
+        );
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn dropped_file_handles_are_flushed_and_closed() {
+        let path = test_path("handle-drop").with_extension("txt");
+        {
+            let context = SourceProviderContext::current();
+            let mut stdout = Vec::new();
+            let mut stderr = Vec::new();
+            let opened = execute_runtime_task_plan_with_context(
+                RuntimeTaskPlan::FsOpen {
+                    path: path.to_string_lossy().into(),
+                    mode: "w".into(),
+                },
+                &context,
+                &mut stdout,
+                &mut stderr,
+            )
+            .expect("opening a file for writing should succeed");
+            execute_runtime_task_plan_with_context(
+                RuntimeTaskPlan::FsWrite {
+                    handle: handle_id(opened),
+                    text: "unflushed".into(),
+                },
+                &context,
+                &mut stdout,
+                &mut stderr,
+            )
+            .expect("writing to an open handle should succeed");
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "unflushed");
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn file_handles_stop_at_the_open_file_cap() {
+        let context = SourceProviderContext::current();
+        let path = test_path("handle-cap").with_extension("txt");
+        fs::write(&path, "").expect("test file should be writable");
+        let path_text: Box<str> = path.to_string_lossy().into();
+        let open = || {
+            execute_runtime_task_plan_with_context(
+                RuntimeTaskPlan::FsOpen {
+                    path: path_text.clone(),
+                    mode: "r".into(),
+                },
+                &context,
+                &mut Vec::new(),
+                &mut Vec::new(),
+            )
+        };
+
+        let handles = (0..crate::file_handles::MAX_OPEN_FILES)
+            .map(|_| handle_id(open().expect("opening below the cap should succeed")))
+            .collect::<Vec<_>>();
+        let error = open().expect_err("opening past the cap should fail");
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "fs.open: {} files are already open; close one before opening {path_text}",
+                crate::file_handles::MAX_OPEN_FILES
+            )
+        );
+
+        execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::FsClose { handle: handles[0] },
+            &context,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("closing an open handle should succeed");
+        open().expect("closing a handle should make room for another");
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn file_handles_reject_unknown_modes() {
+        let error = execute_runtime_task_plan(
+            RuntimeTaskPlan::FsOpen {
+                path: "ignored.txt".into(),
+                mode: "rw".into(),
+            },
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect_err("unknown open modes should fail");
+
+        assert_eq!(
+            error.to_string(),
+            "fs.open mode must be \"r\", \"w\", or \"a\", found \"rw\""
+        );
+    }
+
+    #[test]
+    fn web_sockets_send_receive_and_close() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+        let url: Box<str> = format!("ws://{}", listener.local_addr().unwrap()).into();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("client should connect");
+            let mut socket = tungstenite::accept(stream).expect("handshake should succeed");
+            socket
+                .send(tungstenite::Message::Ping(Vec::new().into()))
+                .expect("ping should send");
+            while let Ok(message) = socket.read() {
+                if message.is_text() {
+                    socket.send(message).expect("echo should send");
+                }
+            }
+        });
+
+        let context = SourceProviderContext::current();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut run = |plan| {
+            execute_runtime_task_plan_with_context(plan, &context, &mut stdout, &mut stderr)
+                .expect("web socket task should execute")
+        };
+
+        let socket = handle_id(run(RuntimeTaskPlan::WsConnect { url }));
+        assert_eq!(
+            run(RuntimeTaskPlan::WsRecvTimeout {
+                handle: socket,
+                timeout_ms: 20,
+            }),
+            RuntimeValue::OptionNone
+        );
+        run(RuntimeTaskPlan::WsSend {
+            handle: socket,
+            text: "pong".into(),
+        });
+        assert_eq!(
+            run(RuntimeTaskPlan::WsRecvTimeout {
+                handle: socket,
+                timeout_ms: 5_000,
+            }),
+            RuntimeValue::OptionSome(Box::new(RuntimeValue::OptionSome(Box::new(
+                RuntimeValue::Text("pong".into())
+            ))))
+        );
+        run(RuntimeTaskPlan::WsSend {
+            handle: socket,
+            text: "ping".into(),
+        });
+        assert_eq!(
+            run(RuntimeTaskPlan::WsRecv { handle: socket }),
+            RuntimeValue::OptionSome(Box::new(RuntimeValue::Text("ping".into())))
+        );
+        run(RuntimeTaskPlan::WsClose { handle: socket });
+        server.join().expect("echo server should stop");
+
+        let error = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::WsRecv { handle: socket },
+            &context,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect_err("a closed socket should not receive");
+        assert_eq!(
+            error.to_string(),
+            format!("ws.recv: web socket #{socket} is not open")
+        );
+    }
+
+    #[test]
+    fn web_socket_receives_do_not_block_other_sockets() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+        let url: Box<str> = format!("ws://{}", listener.local_addr().unwrap()).into();
+        // The first connection waits for whatever the second one sends and passes it on.
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("first client should connect");
+            let mut waiting = tungstenite::accept(stream).expect("handshake should succeed");
+            let (stream, _) = listener.accept().expect("second client should connect");
+            let mut sending = tungstenite::accept(stream).expect("handshake should succeed");
+            let message = sending.read().expect("the second client should send");
+            waiting.send(message).expect("relay should send");
+            for mut socket in [waiting, sending] {
+                while socket.read().is_ok() {}
+            }
+        });
+
+        let cancelled = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let context = SourceProviderContext::current()
+            .with_cancellation(crate::CancellationObserver::from_flag(cancelled.clone()));
+        let run = |plan| {
+            execute_runtime_task_plan_with_context(plan, &context, &mut Vec::new(), &mut Vec::new())
+        };
+        let waiting = handle_id(run(RuntimeTaskPlan::WsConnect { url: url.clone() }).unwrap());
+        let sending = handle_id(run(RuntimeTaskPlan::WsConnect { url }).unwrap());
+
+        let receiver = {
+            let context = context.clone();
+            thread::spawn(move || {
+                execute_runtime_task_plan_with_context(
+                    RuntimeTaskPlan::WsRecv { handle: waiting },
+                    &context,
+                    &mut Vec::new(),
+                    &mut Vec::new(),
+                )
+            })
+        };
+        run(RuntimeTaskPlan::WsSend {
+            handle: sending,
+            text: "relayed".into(),
+        })
+        .expect("a send should not wait for a receive on another socket");
+        assert_eq!(
+            receiver.join().unwrap(),
+            Ok(RuntimeValue::OptionSome(Box::new(RuntimeValue::Text(
+                "relayed".into()
+            ))))
+        );
+
+        let receiver = {
+            let context = context.clone();
+            thread::spawn(move || {
+                execute_runtime_task_plan_with_context(
+                    RuntimeTaskPlan::WsRecv { handle: waiting },
+                    &context,
+                    &mut Vec::new(),
+                    &mut Vec::new(),
+                )
+            })
+        };
+        thread::sleep(std::time::Duration::from_millis(50));
+        cancelled.store(true, std::sync::atomic::Ordering::Release);
+        let error = receiver
+            .join()
+            .unwrap()
+            .expect_err("a cancelled receive should stop waiting");
+        assert_eq!(error.to_string(), "ws.recv was cancelled");
+
+        cancelled.store(false, std::sync::atomic::Ordering::Release);
+        run(RuntimeTaskPlan::WsClose { handle: waiting }).expect("close should succeed");
+        run(RuntimeTaskPlan::WsClose { handle: sending }).expect("close should succeed");
+        server.join().expect("relay server should stop");
+    }
+
+    #[test]
+    fn sleep_waits_for_the_requested_duration() {
+        let context =
+            SourceProviderContext::new(Vec::new(), PathBuf::from("/tmp"), BTreeMap::new());
+        let started = std::time::Instant::now();
+        assert_eq!(
+            execute_runtime_task_plan_with_context(
+                RuntimeTaskPlan::TimeSleep { ms: 30 },
+                &context,
+                &mut Vec::new(),
+                &mut Vec::new()
+            )
+            .expect("sleep should finish"),
+            RuntimeValue::Unit
+        );
+        let elapsed = started.elapsed();
+        assert!(
+            elapsed >= std::time::Duration::from_millis(30),
+            "slept {elapsed:?}"
+        );
+        assert!(
+            elapsed < std::time::Duration::from_secs(5),
+            "slept {elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn cancelled_sleep_returns_promptly() {
+        let cancelled = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let context =
+            SourceProviderContext::new(Vec::new(), PathBuf::from("/tmp"), BTreeMap::new())
+                .with_cancellation(crate::CancellationObserver::from_flag(cancelled.clone()));
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            cancelled.store(true, std::sync::atomic::Ordering::Release);
+        });
+        let started = std::time::Instant::now();
+        let error = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::TimeSleep { ms: 60_000 },
+            &context,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect_err("a cancelled sleep should fail");
+        canceller.join().expect("canceller thread should finish");
+        assert_eq!(error.to_string(), "time.sleep(60000) was cancelled");
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn virtual_clock_turns_sleeps_into_clock_advances() {
+        let context =
+            SourceProviderContext::new(Vec::new(), PathBuf::from("/tmp"), BTreeMap::new())
+                .with_virtual_clock(1_000);
+        let run = |plan| {
+            execute_runtime_task_plan_with_context(plan, &context, &mut Vec::new(), &mut Vec::new())
+                .expect("virtual clock task should succeed")
+        };
+        let started = std::time::Instant::now();
+        run(RuntimeTaskPlan::TimeSleep { ms: 10_000 });
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(run(RuntimeTaskPlan::TimeNowMs), RuntimeValue::Int(11_000));
+
+        run(RuntimeTaskPlan::AdvanceClock { ms: 500 });
+        assert_eq!(run(RuntimeTaskPlan::TimeNowMs), RuntimeValue::Int(11_500));
+        assert_eq!(
+            run(RuntimeTaskPlan::TimeMonotonicMs),
+            RuntimeValue::Int(10_500)
+        );
+
+        // Resetting the wall clock leaves the monotonic reading where it was.
+        run(RuntimeTaskPlan::SetClock { epoch_ms: 0 });
+        assert_eq!(run(RuntimeTaskPlan::TimeNowMs), RuntimeValue::Int(0));
+        assert_eq!(
+            run(RuntimeTaskPlan::TimeMonotonicMs),
+            RuntimeValue::Int(10_500)
+        );
+    }
+
+    #[test]
+    fn clock_control_tasks_need_a_virtual_clock() {
+        let context =
+            SourceProviderContext::new(Vec::new(), PathBuf::from("/tmp"), BTreeMap::new());
+        let error = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::AdvanceClock { ms: 10 },
+            &context,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect_err("advancing the real clock should fail");
+        assert_eq!(
+            error.to_string(),
+            "test.advanceClock(10) needs the virtual clock of `aivi test`"
+        );
+    }
+
+    #[test]
+    fn log_tasks_honor_the_level_filter_and_capture() {
+        let context = SourceProviderContext::new(
+            Vec::new(),
+            PathBuf::from("/tmp"),
+            BTreeMap::from([("AIVI_LOG".to_owned(), "warn".to_owned())]),
+        )
+        .with_log_capture();
+        for plan in [
+            RuntimeTaskPlan::LogEmit {
+                level: "INFO".into(),
+                message: "Started".into(),
+            },
+            RuntimeTaskPlan::LogEmitContext {
+                level: "WARN".into(),
+                message: "Slow query".into(),
+                context: vec![("mailbox".into(), "primary".into())].into(),
+            },
+        ] {
+            assert_eq!(
+                execute_runtime_task_plan_with_context(
+                    plan,
+                    &context,
+                    &mut Vec::new(),
+                    &mut Vec::new()
+                )
+                .expect("log task should execute"),
+                RuntimeValue::Unit
+            );
+        }
+
+        assert_eq!(
+            context.take_captured_log(),
+            "[WARN] Slow query {mailbox=primary}\n"
+        );
+        assert_eq!(context.take_captured_log(), "");
+    }
+
+    #[test]
+    fn processes_capture_stdout_and_report_exit_codes() {
+        let context = SourceProviderContext::current();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut run = |plan| {
+            execute_runtime_task_plan_with_context(plan, &context, &mut stdout, &mut stderr)
+                .expect("process task should execute")
+        };
+
+        let echo = handle_id(run(RuntimeTaskPlan::ProcessSpawn {
+            command: "echo".into(),
+            args: vec!["hello".into()].into(),
+            env: Box::new([]),
+        }));
+        assert_eq!(
+            run(RuntimeTaskPlan::ProcessReadStdout { handle: echo }),
+            RuntimeValue::Text("hello\n".into())
+        );
+        assert_eq!(
+            run(RuntimeTaskPlan::ProcessWait { handle: echo }),
+            RuntimeValue::Int(0)
+        );
+
+        let cat = handle_id(run(RuntimeTaskPlan::ProcessSpawn {
+            command: "sh".into(),
+            args: vec!["-c".into(), "cat; exit 3".into()].into(),
+            env: Box::new([]),
+        }));
+        run(RuntimeTaskPlan::ProcessWriteStdin {
+            handle: cat,
+            text: "piped".into(),
+        });
+        assert_eq!(
+            run(RuntimeTaskPlan::ProcessReadStdout { handle: cat }),
+            RuntimeValue::Text("piped".into())
+        );
+        assert_eq!(
+            run(RuntimeTaskPlan::ProcessWait { handle: cat }),
+            RuntimeValue::Int(3)
+        );
+
+        let error = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::ProcessKill { handle: cat },
+            &context,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect_err("a reaped process should not be killed again");
+        assert_eq!(
+            error.to_string(),
+            format!("process.kill: process #{cat} is not running")
+        );
+    }
+
+    #[test]
+    fn killing_a_process_ends_a_wait_on_it() {
+        let cancelled = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let context = SourceProviderContext::current()
+            .with_cancellation(crate::CancellationObserver::from_flag(cancelled.clone()));
+        let run = |plan| {
+            execute_runtime_task_plan_with_context(plan, &context, &mut Vec::new(), &mut Vec::new())
+        };
+        let spawn_sleep = || {
+            handle_id(
+                run(RuntimeTaskPlan::ProcessSpawn {
+                    command: "sleep".into(),
+                    args: vec!["30".into()].into(),
+                    env: Box::new([]),
+                })
+                .expect("sleep should spawn"),
+            )
+        };
+        let wait_on = |handle| {
+            let context = context.clone();
+            thread::spawn(move || {
+                execute_runtime_task_plan_with_context(
+                    RuntimeTaskPlan::ProcessWait { handle },
+                    &context,
+                    &mut Vec::new(),
+                    &mut Vec::new(),
+                )
+            })
+        };
+
+        // Spawn, wait, and kill on timeout: the kill must not queue behind the wait.
+        let sleeper = spawn_sleep();
+        let waiter = wait_on(sleeper);
+        thread::sleep(std::time::Duration::from_millis(50));
+        run(RuntimeTaskPlan::ProcessKill { handle: sleeper })
+            .expect("a waited-on process should still be killable");
+        assert_eq!(waiter.join().unwrap(), Ok(RuntimeValue::Int(128 + 9)));
+
+        let sleeper = spawn_sleep();
+        let waiter = wait_on(sleeper);
+        thread::sleep(std::time::Duration::from_millis(50));
+        cancelled.store(true, std::sync::atomic::Ordering::Release);
+        let error = waiter
+            .join()
+            .unwrap()
+            .expect_err("a cancelled wait should stop waiting");
+        assert_eq!(error.to_string(), "process.wait was cancelled");
+        cancelled.store(false, std::sync::atomic::Ordering::Release);
+        run(RuntimeTaskPlan::ProcessKill { handle: sleeper })
+            .expect("a cancelled wait should leave the process running");
+    }
+
+    /// Answer one connection per entry of `responses`: `Some` writes the raw response, `None`
+    /// holds the connection open without answering. Returns the base URL and the requests seen.
+    fn serve_http(
+        responses: Vec<Option<&'static str>>,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("test server should bind");
+        let base = format!(
+            "http://{}",
+            listener
+                .local_addr()
+                .expect("test server should have an address")
+        );
+        let server = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|response| {
+                    let (mut stream, _) = listener.accept().expect("client should connect");
+                    let request = read_http_request(&mut stream);
+                    match response {
+                        Some(response) => stream
+                            .write_all(response.as_bytes())
+                            .expect("response should write"),
+                        None => thread::sleep(std::time::Duration::from_millis(500)),
+                    }
+                    request
+                })
+                .collect()
+        });
+        (base, server)
+    }
+
+    fn http_request_plan(
+        url: String,
+        timeout_ms: Option<i64>,
+        max_redirects: Option<i64>,
+    ) -> RuntimeTaskPlan {
+        RuntimeTaskPlan::HttpRequest {
+            method: "POST".into(),
+            url: url.into(),
+            headers: vec![("X-Token".into(), "abc".into())].into(),
+            body: Some("ping".into()),
+            timeout_ms,
+            max_redirects,
+        }
+    }
+
+    #[test]
+    fn http_request_returns_error_statuses_as_responses() {
+        let (base, server) = serve_http(vec![Some(
+            "HTTP/1.1 418 I'm a teapot\r\nX-Kind: teapot\r\nContent-Length: 5\r\n\r\nshort",
+        )]);
+        let response = execute_runtime_value(
+            RuntimeValue::Task(http_request_plan(format!("{base}/brew"), Some(2_000), None)),
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("an error status should still be a response");
+        let requests = server.join().expect("test server should stop");
+
+        assert!(requests[0].starts_with("POST /brew HTTP/1.1\r\n"));
+        assert!(requests[0].contains("X-Token: abc\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nping"));
+        let RuntimeValue::Record(fields) = response else {
+            panic!("expected a response record, found {response:?}");
+        };
+        assert_eq!(fields[0].value, RuntimeValue::Int(418));
+        let RuntimeValue::List(headers) = &fields[1].value else {
+            panic!("expected a header list, found {:?}", fields[1].value);
+        };
+        assert!(headers.contains(&RuntimeValue::Tuple(vec![
+            RuntimeValue::Text("x-kind".into()),
+            RuntimeValue::Text("teapot".into()),
+        ])));
+        assert_eq!(fields[2].value, RuntimeValue::Text("short".into()));
+    }
+
+    #[test]
+    fn http_request_follows_redirects_up_to_the_limit() {
+        const REDIRECT: &str =
+            "HTTP/1.1 302 Found\r\nLocation: /final\r\nContent-Length: 0\r\n\r\n";
+        const FINAL: &str = "HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\ndone";
+        let status = |response: RuntimeValue| match response {
+            RuntimeValue::Record(fields) => fields[0].value.clone(),
+            other => panic!("expected a response record, found {other:?}"),
+        };
+
+        let (base, server) = serve_http(vec![Some(REDIRECT), Some(FINAL)]);
+        let followed = execute_runtime_task_plan(
+            http_request_plan(format!("{base}/start"), Some(2_000), None),
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("the redirect should be followed");
+        let requests = server.join().expect("test server should stop");
+        assert_eq!(status(followed), RuntimeValue::Int(200));
+        assert!(requests[1].starts_with("GET /final HTTP/1.1\r\n"));
+
+        let (base, server) = serve_http(vec![Some(REDIRECT)]);
+        let stopped = execute_runtime_task_plan(
+            http_request_plan(format!("{base}/start"), Some(2_000), Some(0)),
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("a redirect past the limit should be returned as is");
+        server.join().expect("test server should stop");
+        assert_eq!(status(stopped), RuntimeValue::Int(302));
+    }
+
+    #[test]
+    fn http_request_timeouts_fail_the_task() {
+        let (base, server) = serve_http(vec![None]);
+        let error = execute_runtime_task_plan(
+            http_request_plan(format!("{base}/slow"), Some(100), None),
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect_err("an unanswered request should time out");
+        server.join().expect("test server should stop");
+        assert!(!error.is_fatal());
+        assert!(error.to_string().contains("timed out"), "{error}");
+    }
+
+    #[test]
+    fn cancelled_http_requests_stop_waiting_for_the_server() {
+        let (base, server) = serve_http(vec![None]);
+        let url = format!("{base}/slow");
+        let cancelled = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let context =
+            SourceProviderContext::new(Vec::new(), PathBuf::from("/tmp"), BTreeMap::new())
+                .with_cancellation(crate::CancellationObserver::from_flag(cancelled.clone()));
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            cancelled.store(true, std::sync::atomic::Ordering::Release);
+        });
+        let error = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::HttpGet {
+                url: url.clone().into(),
+            },
+            &context,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect_err("a cancelled request should fail");
+        canceller.join().expect("canceller thread should finish");
+        server.join().expect("test server should stop");
+        // The server holds the connection for 500ms, so only cancellation can fail it this way.
+        assert_eq!(error.to_string(), format!("http.get({url}) was cancelled"));
+    }
+
+    #[test]
+    fn cancellable_http_requests_still_return_the_body() {
+        let (base, server) = serve_http(vec![Some(
+            "HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello",
+        )]);
+        let context =
+            SourceProviderContext::new(Vec::new(), PathBuf::from("/tmp"), BTreeMap::new())
+                .with_cancellation(crate::CancellationObserver::default());
+        let body = execute_runtime_task_plan_with_context(
+            RuntimeTaskPlan::HttpGet {
+                url: format!("{base}/greeting").into(),
+            },
+            &context,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("an uncancelled request should succeed");
+        server.join().expect("test server should stop");
+        assert_eq!(body, RuntimeValue::Text("hello".into()));
+    }
+
+    #[test]
+    fn abandoned_http_helpers_stop_reading_once_cancelled() {
+        let (base, server) = serve_http(vec![Some(
+            "HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello",
+        )]);
+        let cancelled = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+        // This is the helper thread's half of `http_unless_cancelled`: once the task is cancelled
+        // it gives up on the body instead of reading it to the end.
+        let error = execute_runtime_http_plan(
+            RuntimeTaskPlan::HttpGet {
+                url: format!("{base}/greeting").into(),
+            },
+            Some(crate::CancellationObserver::from_flag(cancelled)),
+        )
+        .expect_err("a cancelled helper should not read the body");
+        server.join().expect("test server should stop");
+        assert_eq!(error.to_string(), "http read: cancelled");
+    }
+
+    #[test]
+    fn http_download_writes_the_body_to_a_file() {
+        let (base, server) = serve_http(vec![Some(
+            "HTTP/1.1 200 OK\r\nContent-Length: 11\r\n\r\nhello world",
+        )]);
+        let path = test_path("http-download").with_extension("txt");
+        let result = execute_runtime_task_plan(
+            RuntimeTaskPlan::HttpDownload {
+                url: format!("{base}/file").into(),
+                path: path.to_string_lossy().into(),
+            },
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("download should succeed");
+        server.join().expect("test server should stop");
+
+        assert_eq!(result, RuntimeValue::Unit);
+        assert_eq!(
+            fs::read_to_string(&path).expect("downloaded file should exist"),
+            "hello world"
+        );
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn ed25519_keypair_generates_a_matching_public_key() {
+        use ring::signature::{ED25519, Ed25519KeyPair, UnparsedPublicKey};
+
+        let RuntimeValue::Record(fields) = execute_runtime_task_plan(
+            RuntimeTaskPlan::CryptoEd25519Keypair,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("key generation should succeed") else {
+            panic!("expected a key pair record");
+        };
+        let key = |label: &str| match fields.iter().find(|field| field.label.as_ref() == label) {
+            Some(aivi_backend::RuntimeRecordField {
+                value: RuntimeValue::Bytes(bytes),
+                ..
+            }) => bytes.clone(),
+            other => panic!("expected {label} bytes, found {other:?}"),
+        };
+        let (public_key, private_key) = (key("publicKey"), key("privateKey"));
+        assert_eq!((public_key.len(), private_key.len()), (32, 32));
+
+        let pair = Ed25519KeyPair::from_seed_unchecked(&private_key)
+            .expect("the private key should be a valid seed");
+        let signature = pair.sign(b"message");
+        UnparsedPublicKey::new(&ED25519, &public_key)
+            .verify(b"message", signature.as_ref())
+            .expect("the public key should verify signatures from the private key");
+    }
+
+    fn handle_id(handle: RuntimeValue) -> i64 {
+        let RuntimeValue::Record(fields) = handle else {
+            panic!("expected a handle record, found {handle:?}");
+        };
+        match fields.iter().find(|field| field.label.as_ref() == "id") {
+            Some(aivi_backend::RuntimeRecordField {
+                value: RuntimeValue::Int(id),
+                ..
+            }) => *id,
+            _ => panic!("handle record should carry an Int id"),
+        }
+    }
+
+    #[test]
+    fn execute_runtime_task_plan_reports_missing_custom_command_executor() {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let error = execute_runtime_task_plan(
+            RuntimeTaskPlan::CustomCapabilityCommand(RuntimeCustomCapabilityCommandPlan {
+                provider_key: "custom.feed".into(),
+                command: "delete".into(),
+                provider_arguments: Box::new([]),
+                options: Box::new([]),
+                arguments: Box::new([]),
+            }),
+            &mut stdout,
+            &mut stderr,
+        )
+        .expect_err("custom capability commands should fail clearly without a registered executor");
+
+        assert_eq!(
+            error.to_string(),
+            "custom capability command `custom.feed.delete` has no registered executor"
+        );
+    }
+
+    #[test]
+    fn execute_runtime_task_plan_runs_custom_capability_commands_through_registered_executor() {
+        let context = SourceProviderContext::current()
+            .with_custom_capability_command_executor(Arc::new(EchoCustomCapabilityCommandExecutor));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
 
         let value = execute_runtime_task_plan_with_context(
             RuntimeTaskPlan::CustomCapabilityCommand(RuntimeCustomCapabilityCommandPlan {
@@ -2287,6 +4333,85 @@ mod tests {
         let _ = fs::remove_file(&database);
     }
 
+    #[test]
+    fn execute_runtime_value_rolls_back_every_statement_of_a_failing_db_commit() {
+        let database = test_path("db-commit-rollback");
+        let connection = RuntimeDbConnection {
+            database: database.to_string_lossy().into_owned().into_boxed_str(),
+        };
+        let statement = |sql: &str, arguments: Vec<RuntimeValue>| RuntimeDbStatement {
+            sql: sql.into(),
+            arguments,
+        };
+        let run = |statements| {
+            execute_runtime_value(
+                RuntimeValue::DbTask(RuntimeDbTaskPlan::Commit(RuntimeDbCommitPlan {
+                    connection: connection.clone(),
+                    statements,
+                    changed_tables: BTreeSet::from(["users".into()]),
+                })),
+                &mut Vec::new(),
+                &mut Vec::new(),
+            )
+            .expect("db commit task should return a result value")
+        };
+
+        assert_eq!(
+            run(vec![
+                statement(
+                    "create table users(id integer primary key, name text not null)",
+                    Vec::new(),
+                ),
+                statement(
+                    "insert into users(id, name) values (?, ?)",
+                    vec![RuntimeValue::Int(1), RuntimeValue::Text("Ada".into())],
+                ),
+            ]),
+            RuntimeValue::ResultOk(Box::new(RuntimeValue::Unit))
+        );
+        let failed = run(vec![
+            statement(
+                "insert into users(id, name) values (?, ?)",
+                vec![RuntimeValue::Int(2), RuntimeValue::Text("Linus".into())],
+            ),
+            statement(
+                "insert into users(id, name) values (?, ?)",
+                vec![RuntimeValue::Int(1), RuntimeValue::Text("Grace".into())],
+            ),
+        ]);
+        assert!(
+            matches!(failed, RuntimeValue::ResultErr(_)),
+            "a constraint failure should fail the commit, found {failed:?}"
+        );
+
+        let rows = execute_runtime_value(
+            RuntimeValue::DbTask(RuntimeDbTaskPlan::Query(RuntimeDbQueryPlan {
+                connection: connection.clone(),
+                statement: statement("select id, name from users order by id", Vec::new()),
+            })),
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("db query task should execute");
+        assert_eq!(
+            rows,
+            RuntimeValue::ResultOk(Box::new(RuntimeValue::List(vec![RuntimeValue::Map(
+                RuntimeMap::from_entries(vec![
+                    RuntimeMapEntry {
+                        key: RuntimeValue::Text("id".into()),
+                        value: RuntimeValue::Text("1".into()),
+                    },
+                    RuntimeMapEntry {
+                        key: RuntimeValue::Text("name".into()),
+                        value: RuntimeValue::Text("Ada".into()),
+                    },
+                ])
+            )]))),
+            "the insert before the failing statement should have been rolled back"
+        );
+        let _ = fs::remove_file(&database);
+    }
+
     #[test]
     fn execute_runtime_task_plan_calls_dbus_methods_and_decodes_reply_values() {
         if std::env::var("DBUS_SESSION_BUS_ADDRESS").is_err() {
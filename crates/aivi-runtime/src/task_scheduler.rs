@@ -0,0 +1,368 @@
+//! Concurrent execution of independent task plans, as requested by `aivi.task.parallel`.
+//!
+//! Each task runs on a worker thread with its own output buffers, so interleaved writes from
+//! different tasks never mix; the buffers are copied to the caller's stdout/stderr in task order
+//! once every task has finished. Tasks that need a [`TaskFunctionApplier`] (deferred `map`,
+//! `apply`, `chain`, and `join` plans) cannot leave the calling thread, so they run there while
+//! the workers handle the rest.
+
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+};
+
+use aivi_backend::{ItemId, RuntimeTaskPlan, RuntimeValue, TaskFunctionApplier};
+
+use crate::{
+    providers::SourceProviderContext,
+    task_executor::{
+        RuntimeTaskExecutionError, execute_runtime_task_plan_with_applier,
+        execute_runtime_task_plan_with_context,
+    },
+};
+
+/// Upper bound on worker threads for one parallel plan. Further tasks wait for a free worker.
+const MAX_CONCURRENT_TASKS: usize = 32;
+
+/// Execute `tasks` concurrently and return their results in task order.
+///
+/// The first task to fail cancels its siblings through the context's cancellation observer, and
+/// its error is returned once the running tasks have stopped. Tasks that have not started by then
+/// are skipped. Cancelling `context` cancels every task.
+pub fn run_concurrent(
+    tasks: Vec<RuntimeTaskPlan>,
+    context: &SourceProviderContext,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+) -> Result<Vec<RuntimeValue>, RuntimeTaskExecutionError> {
+    run_concurrent_tasks(tasks, context, stdout, stderr, None)
+}
+
+/// Like [`run_concurrent`], but tasks with deferred composition plans run on the calling thread
+/// through `applier`.
+pub(crate) fn run_concurrent_with_applier(
+    tasks: Vec<RuntimeTaskPlan>,
+    context: &SourceProviderContext,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+    applier: &mut dyn TaskFunctionApplier,
+    globals: &BTreeMap<ItemId, RuntimeValue>,
+) -> Result<Vec<RuntimeValue>, RuntimeTaskExecutionError> {
+    run_concurrent_tasks(tasks, context, stdout, stderr, Some((applier, globals)))
+}
+
+type LocalApplier<'a> = (
+    &'a mut dyn TaskFunctionApplier,
+    &'a BTreeMap<ItemId, RuntimeValue>,
+);
+
+struct TaskOutcome {
+    index: usize,
+    result: Result<RuntimeValue, RuntimeTaskExecutionError>,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+/// Remembers which task failed first and cancels the others when it does.
+struct FirstFailure {
+    cancelled: Arc<AtomicBool>,
+    index: Mutex<Option<usize>>,
+}
+
+impl FirstFailure {
+    fn is_set(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    fn record(&self, index: usize) {
+        let mut first = self
+            .index
+            .lock()
+            .expect("parallel task failure mutex should not be poisoned");
+        if first.is_none() {
+            *first = Some(index);
+            self.cancelled.store(true, Ordering::Release);
+        }
+    }
+}
+
+fn run_concurrent_tasks(
+    tasks: Vec<RuntimeTaskPlan>,
+    context: &SourceProviderContext,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+    mut local: Option<LocalApplier<'_>>,
+) -> Result<Vec<RuntimeValue>, RuntimeTaskExecutionError> {
+    let task_count = tasks.len();
+    let failure = FirstFailure {
+        cancelled: Arc::new(AtomicBool::new(false)),
+        index: Mutex::new(None),
+    };
+    let context = context.with_nested_cancellation(failure.cancelled.clone());
+    let (local_tasks, worker_tasks): (Vec<_>, Vec<_>) = tasks
+        .into_iter()
+        .enumerate()
+        .partition(|(_, plan)| local.is_some() && needs_applier(plan));
+    let worker_count = worker_tasks.len().min(MAX_CONCURRENT_TASKS);
+    let queue = Mutex::new(worker_tasks.into_iter());
+    let finished = Mutex::new(Vec::with_capacity(task_count));
+
+    thread::scope(|scope| {
+        for _ in 0..worker_count {
+            scope.spawn(|| {
+                loop {
+                    let next = queue
+                        .lock()
+                        .expect("parallel task queue mutex should not be poisoned")
+                        .next();
+                    let Some((index, plan)) = next else {
+                        break;
+                    };
+                    if failure.is_set() {
+                        break;
+                    }
+                    let outcome = run_task(index, &failure, |stdout, stderr| {
+                        execute_runtime_task_plan_with_context(plan, &context, stdout, stderr)
+                    });
+                    finished
+                        .lock()
+                        .expect("parallel task results mutex should not be poisoned")
+                        .push(outcome);
+                }
+            });
+        }
+        if let Some((applier, globals)) = local.as_mut() {
+            for (index, plan) in local_tasks {
+                if failure.is_set() {
+                    break;
+                }
+                let outcome = run_task(index, &failure, |stdout, stderr| {
+                    execute_runtime_task_plan_with_applier(
+                        plan,
+                        &context,
+                        stdout,
+                        stderr,
+                        &mut **applier,
+                        globals,
+                    )
+                });
+                finished
+                    .lock()
+                    .expect("parallel task results mutex should not be poisoned")
+                    .push(outcome);
+            }
+        }
+    });
+
+    let mut outcomes = finished
+        .into_inner()
+        .expect("parallel task results mutex should not be poisoned");
+    outcomes.sort_by_key(|outcome| outcome.index);
+    for outcome in &outcomes {
+        stdout
+            .write_all(&outcome.stdout)
+            .map_err(|error| RuntimeTaskExecutionError::new(format!("stdout write: {error}")))?;
+        stderr
+            .write_all(&outcome.stderr)
+            .map_err(|error| RuntimeTaskExecutionError::new(format!("stderr write: {error}")))?;
+    }
+    let first_failure = failure
+        .index
+        .into_inner()
+        .expect("parallel task failure mutex should not be poisoned");
+    if let Some(failed) = first_failure {
+        let outcome = outcomes
+            .into_iter()
+            .find(|outcome| outcome.index == failed)
+            .expect("the first failed task should have an outcome");
+        return Err(outcome
+            .result
+            .expect_err("the first failed task should have failed"));
+    }
+    // Without a failure nothing was skipped, so every task has an outcome.
+    debug_assert_eq!(outcomes.len(), task_count);
+    outcomes.into_iter().map(|outcome| outcome.result).collect()
+}
+
+fn run_task(
+    index: usize,
+    failure: &FirstFailure,
+    run: impl FnOnce(&mut Vec<u8>, &mut Vec<u8>) -> Result<RuntimeValue, RuntimeTaskExecutionError>,
+) -> TaskOutcome {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let result = run(&mut stdout, &mut stderr);
+    if result.is_err() {
+        failure.record(index);
+    }
+    TaskOutcome {
+        index,
+        result,
+        stdout,
+        stderr,
+    }
+}
+
+/// Whether executing `plan` calls back into user code through a [`TaskFunctionApplier`].
+fn needs_applier(plan: &RuntimeTaskPlan) -> bool {
+    match plan {
+        RuntimeTaskPlan::Map { .. }
+        | RuntimeTaskPlan::Apply { .. }
+        | RuntimeTaskPlan::Chain { .. }
+        | RuntimeTaskPlan::Join { .. } => true,
+        RuntimeTaskPlan::Attempt { inner } => needs_applier(inner),
+        RuntimeTaskPlan::Finally { inner, cleanup } => {
+            needs_applier(inner) || needs_applier(cleanup)
+        }
+        RuntimeTaskPlan::Parallel { tasks } => tasks.iter().any(needs_applier),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        path::PathBuf,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use aivi_backend::{RuntimeTaskPlan, RuntimeValue};
+
+    use super::run_concurrent;
+    use crate::SourceProviderContext;
+
+    /// Answer every connection after `delay` with a body naming the requested path.
+    fn serve_slow_http(connections: usize, delay: Duration) -> (String, thread::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("test server should bind");
+        let base = format!(
+            "http://{}",
+            listener
+                .local_addr()
+                .expect("test server should have an address")
+        );
+        let server = thread::spawn(move || {
+            let handlers = (0..connections)
+                .map(|_| {
+                    let (mut stream, _) = listener.accept().expect("client should connect");
+                    thread::spawn(move || {
+                        let mut reader = BufReader::new(&mut stream);
+                        let mut request_line = String::new();
+                        reader
+                            .read_line(&mut request_line)
+                            .expect("request line should read");
+                        loop {
+                            let mut line = String::new();
+                            reader.read_line(&mut line).expect("header should read");
+                            if line == "\r\n" || line.is_empty() {
+                                break;
+                            }
+                        }
+                        let path = request_line
+                            .split_whitespace()
+                            .nth(1)
+                            .expect("request line should name a path")
+                            .to_owned();
+                        thread::sleep(delay);
+                        write!(
+                            stream,
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{path}",
+                            path.len()
+                        )
+                        .expect("response should write");
+                    })
+                })
+                .collect::<Vec<_>>();
+            for handler in handlers {
+                handler.join().expect("connection handler should finish");
+            }
+        });
+        (base, server)
+    }
+
+    #[test]
+    fn runs_ten_http_requests_concurrently_and_keeps_their_order() {
+        let delay = Duration::from_millis(300);
+        let (base, server) = serve_slow_http(10, delay);
+        let tasks = (0..10)
+            .map(|index| RuntimeTaskPlan::HttpGet {
+                url: format!("{base}/item/{index}").into(),
+            })
+            .collect();
+
+        let started = Instant::now();
+        let results = run_concurrent(
+            tasks,
+            &SourceProviderContext::current(),
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect("every request should succeed");
+        let elapsed = started.elapsed();
+        server.join().expect("test server should stop");
+
+        assert_eq!(
+            results,
+            (0..10)
+                .map(|index| RuntimeValue::Text(format!("/item/{index}").into()))
+                .collect::<Vec<_>>()
+        );
+        // Ten sequential requests would take at least ten delays.
+        assert!(elapsed < delay * 5, "parallel requests took {elapsed:?}");
+    }
+
+    #[test]
+    fn writes_task_output_in_task_order() {
+        let mut stdout = Vec::new();
+        let results = run_concurrent(
+            vec![
+                RuntimeTaskPlan::Finally {
+                    inner: Box::new(RuntimeTaskPlan::TimeSleep { ms: 50 }),
+                    cleanup: Box::new(RuntimeTaskPlan::StdoutWrite {
+                        text: "slow\n".into(),
+                    }),
+                },
+                RuntimeTaskPlan::StdoutWrite {
+                    text: "fast\n".into(),
+                },
+            ],
+            &SourceProviderContext::current(),
+            &mut stdout,
+            &mut Vec::new(),
+        )
+        .expect("both tasks should succeed");
+
+        assert_eq!(results, vec![RuntimeValue::Unit, RuntimeValue::Unit]);
+        assert_eq!(String::from_utf8(stdout).unwrap(), "slow\nfast\n");
+    }
+
+    #[test]
+    fn first_failure_cancels_sibling_tasks() {
+        let context =
+            SourceProviderContext::new(Vec::new(), PathBuf::from("/tmp"), Default::default());
+        let started = Instant::now();
+        let error = run_concurrent(
+            vec![
+                RuntimeTaskPlan::TimeSleep { ms: 60_000 },
+                RuntimeTaskPlan::RandomInt { low: 9, high: 3 },
+            ],
+            &context,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .expect_err("the failing task should fail the parallel plan");
+
+        assert_eq!(
+            error.to_string(),
+            "randomInt requires `low <= high`, found low=9 and high=3"
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
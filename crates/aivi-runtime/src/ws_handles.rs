@@ -0,0 +1,242 @@
+//! WebSocket connections behind `aivi.ws.connect`.
+//!
+//! Like file handles, a socket handle is a plain `{ id, url }` record and the connection itself
+//! lives in a [`WebSocketTable`] owned by the run's `SourceProviderContext`. Ids are drawn at
+//! random and the table holds at most [`MAX_OPEN_SOCKETS`] entries, as for files.
+//!
+//! The table lock is only held to look a socket up, store it, or remove it; every socket has a
+//! lock of its own. A receive takes that lock for one short poll at a time, so a task waiting for
+//! a message blocks neither other sockets nor a `send` on the same socket, and it notices when its
+//! task is cancelled. `close` performs the closing handshake and releases one entry; any socket
+//! the program never closes is dropped with the table.
+
+use std::{
+    collections::BTreeMap,
+    net::TcpStream,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use tungstenite::{Message, WebSocket, stream::MaybeTlsStream};
+
+use crate::CancellationObserver;
+
+/// Sockets one run may hold open through `aivi.ws.connect` at the same time.
+pub(crate) const MAX_OPEN_SOCKETS: usize = 256;
+
+/// The longest a receive holds a socket's lock before it checks for cancellation and lets other
+/// tasks use the socket.
+const RECV_POLL: Duration = Duration::from_millis(10);
+
+pub(crate) type SharedSocket = Arc<Mutex<OpenSocket>>;
+
+#[derive(Default)]
+pub(crate) struct WebSocketTable {
+    open: BTreeMap<i64, SharedSocket>,
+}
+
+pub(crate) struct OpenSocket {
+    url: Box<str>,
+    socket: WebSocket<MaybeTlsStream<TcpStream>>,
+}
+
+impl WebSocketTable {
+    /// Fail when the table is full, so no handshake is spent on a socket that could not be stored.
+    pub(crate) fn check_capacity(&self, url: &str) -> Result<(), String> {
+        if self.open.len() >= MAX_OPEN_SOCKETS {
+            return Err(format!(
+                "ws.connect: {MAX_OPEN_SOCKETS} web sockets are already open; close one before connecting to {url}"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Store a connected socket under a fresh id. The capacity is checked again because other
+    /// tasks may have filled the table while the handshake ran.
+    pub(crate) fn insert(&mut self, socket: OpenSocket) -> Result<i64, String> {
+        self.check_capacity(&socket.url)?;
+        let id = loop {
+            let id = fastrand::i64(0..);
+            if !self.open.contains_key(&id) {
+                break id;
+            }
+        };
+        self.open.insert(id, Arc::new(Mutex::new(socket)));
+        Ok(id)
+    }
+
+    pub(crate) fn get(&self, operation: &str, id: i64) -> Result<SharedSocket, String> {
+        self.open
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| closed_socket_error(operation, id))
+    }
+
+    /// Release one handle. Removing a handle twice is an error.
+    pub(crate) fn remove(&mut self, operation: &str, id: i64) -> Result<SharedSocket, String> {
+        self.open
+            .remove(&id)
+            .ok_or_else(|| closed_socket_error(operation, id))
+    }
+}
+
+impl OpenSocket {
+    /// Open a `ws://` or `wss://` connection and complete the upgrade handshake.
+    pub(crate) fn connect(url: &str) -> Result<Self, String> {
+        let (socket, _response) = tungstenite::connect(url)
+            .map_err(|error| format!("failed to connect to {url}: {error}"))?;
+        Ok(Self {
+            url: url.into(),
+            socket,
+        })
+    }
+
+    /// Wait at most `wait` for a message. Returns `None` when nothing but control frames arrived
+    /// in that time, `Some(Some(text))` for a data message, and `Some(None)` once the peer has
+    /// closed the connection.
+    fn poll(&mut self, wait: Duration) -> Result<Option<Option<String>>, String> {
+        let Self { url, socket } = self;
+        tcp_stream(socket)
+            .set_read_timeout(Some(wait))
+            .map_err(|error| format!("failed to receive from {url}: {error}"))?;
+        match read_data(socket) {
+            Ok(received) => Ok(received),
+            Err(tungstenite::Error::Io(error)) if is_timeout(&error) => Ok(None),
+            Err(error) => Err(format!("failed to receive from {url}: {error}")),
+        }
+    }
+}
+
+pub(crate) fn send(socket: &Mutex<OpenSocket>, text: &str) -> Result<(), String> {
+    let mut open = lock(socket);
+    let OpenSocket { url, socket } = &mut *open;
+    socket
+        .send(Message::text(text.to_owned()))
+        .map_err(|error| format!("failed to send to {url}: {error}"))
+}
+
+/// Wait for the next data message, or return `None` once the peer has closed the connection.
+/// Binary messages are decoded as lossy UTF-8; pings are answered in passing.
+pub(crate) fn recv(
+    socket: &Mutex<OpenSocket>,
+    cancellation: Option<&CancellationObserver>,
+) -> Result<Option<String>, String> {
+    // Without a deadline the wait only ends with a message or a closed connection.
+    recv_until(socket, "ws.recv", None, cancellation).map(Option::flatten)
+}
+
+/// Like [`recv`], but give up after `timeout`. Returns `None` when no message arrived in time and
+/// `Some` of what `recv` would have returned otherwise, so programs can poll.
+pub(crate) fn recv_timeout(
+    socket: &Mutex<OpenSocket>,
+    timeout: Duration,
+    cancellation: Option<&CancellationObserver>,
+) -> Result<Option<Option<String>>, String> {
+    recv_until(
+        socket,
+        "ws.recvTimeout",
+        Some(Instant::now() + timeout),
+        cancellation,
+    )
+}
+
+fn recv_until(
+    socket: &Mutex<OpenSocket>,
+    operation: &str,
+    deadline: Option<Instant>,
+    cancellation: Option<&CancellationObserver>,
+) -> Result<Option<Option<String>>, String> {
+    loop {
+        if cancellation.is_some_and(CancellationObserver::is_cancelled) {
+            return Err(format!("{operation} was cancelled"));
+        }
+        let wait = match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Ok(None);
+                }
+                remaining.min(RECV_POLL)
+            }
+            None => RECV_POLL,
+        };
+        if let Some(received) = lock(socket).poll(wait)? {
+            return Ok(Some(received));
+        }
+    }
+}
+
+/// Send a close frame and wait for the peer to acknowledge it. The caller removes the socket from
+/// the table first, so other tasks cannot use it meanwhile.
+pub(crate) fn close(
+    socket: &Mutex<OpenSocket>,
+    cancellation: Option<&CancellationObserver>,
+) -> Result<(), String> {
+    let mut open = lock(socket);
+    let OpenSocket { url, socket } = &mut *open;
+    match socket.close(None) {
+        Ok(()) => {}
+        Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+            return Ok(());
+        }
+        Err(error) => return Err(format!("failed to close {url}: {error}")),
+    }
+    tcp_stream(socket)
+        .set_read_timeout(Some(RECV_POLL))
+        .map_err(|error| format!("failed to close {url}: {error}"))?;
+    loop {
+        if cancellation.is_some_and(CancellationObserver::is_cancelled) {
+            return Err("ws.close was cancelled".to_owned());
+        }
+        match socket.read() {
+            Ok(_) => {}
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+                return Ok(());
+            }
+            Err(tungstenite::Error::Io(error)) if is_timeout(&error) => {}
+            Err(error) => return Err(format!("failed to close {url}: {error}")),
+        }
+    }
+}
+
+fn lock(socket: &Mutex<OpenSocket>) -> MutexGuard<'_, OpenSocket> {
+    socket
+        .lock()
+        .expect("web socket mutex should not be poisoned")
+}
+
+/// Read one message. Control frames produce `Ok(None)`; data messages produce `Some(Some(text))`
+/// and a closed connection `Some(None)`.
+fn read_data(
+    socket: &mut WebSocket<MaybeTlsStream<TcpStream>>,
+) -> Result<Option<Option<String>>, tungstenite::Error> {
+    match socket.read() {
+        Ok(Message::Text(text)) => Ok(Some(Some(text.as_str().to_owned()))),
+        Ok(Message::Binary(bytes)) => Ok(Some(Some(String::from_utf8_lossy(&bytes).into_owned()))),
+        Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_)) => Ok(None),
+        Ok(Message::Close(_))
+        | Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+            Ok(Some(None))
+        }
+        Err(error) => Err(error),
+    }
+}
+
+fn is_timeout(error: &std::io::Error) -> bool {
+    matches!(
+        error.kind(),
+        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+    )
+}
+
+fn tcp_stream(socket: &WebSocket<MaybeTlsStream<TcpStream>>) -> &TcpStream {
+    match socket.get_ref() {
+        MaybeTlsStream::Plain(stream) => stream,
+        MaybeTlsStream::NativeTls(stream) => stream.get_ref(),
+        _ => unreachable!("only plain and native-tls web sockets are enabled"),
+    }
+}
+
+fn closed_socket_error(operation: &str, id: i64) -> String {
+    format!("{operation}: web socket #{id} is not open")
+}
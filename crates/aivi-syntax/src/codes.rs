@@ -9,16 +9,22 @@ pub const DIRECT_FUNCTION_PARAMETER_ANNOTATION: DiagnosticCode =
 pub const DUPLICATE_STANDALONE_TYPE_ANNOTATION: DiagnosticCode =
     DiagnosticCode::new("syntax", "duplicate-standalone-type-annotation");
 pub const EMPTY_RESULT_BLOCK: DiagnosticCode = DiagnosticCode::new("syntax", "empty-result-block");
+pub const FLOAT_LITERAL_OUT_OF_RANGE: DiagnosticCode =
+    DiagnosticCode::new("syntax", "float-literal-out-of-range");
 pub const INVALID_DISCARD_EXPR: DiagnosticCode =
     DiagnosticCode::new("syntax", "invalid-discard-expr");
 pub const INVALID_SUBJECT_PICK: DiagnosticCode =
     DiagnosticCode::new("syntax", "invalid-subject-pick");
+pub const INVALID_CHAR_LITERAL: DiagnosticCode =
+    DiagnosticCode::new("syntax", "invalid-char-literal");
 pub const INVALID_ESCAPE_SEQUENCE: DiagnosticCode =
     DiagnosticCode::new("syntax", "invalid-escape-sequence");
 pub const INVALID_MARKUP_CHILD_CONTENT: DiagnosticCode =
     DiagnosticCode::new("syntax", "invalid-markup-child-content");
 pub const INVALID_TEXT_INTERPOLATION: DiagnosticCode =
     DiagnosticCode::new("syntax", "invalid-text-interpolation");
+pub const MALFORMED_FLOAT_EXPONENT: DiagnosticCode =
+    DiagnosticCode::new("syntax", "malformed-float-exponent");
 pub const MISMATCHED_MARKUP_CLOSE: DiagnosticCode =
     DiagnosticCode::new("syntax", "mismatched-markup-close");
 pub const MISSING_CLASS_MEMBER_TYPE: DiagnosticCode =
@@ -143,6 +143,15 @@ pub struct TextInterpolation {
     pub span: SourceSpan,
 }
 
+/// Char literal such as `'a'` or `'\u{1F600}'`, preserved in surface form next to its decoded
+/// character.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CharLiteral {
+    pub raw: String,
+    pub value: char,
+    pub span: SourceSpan,
+}
+
 /// Regex literal preserved in surface form.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RegexLiteral {
@@ -496,6 +505,7 @@ fn expr_contains_self(expr: &Expr) -> bool {
         | ExprKind::Decimal(_)
         | ExprKind::BigInt(_)
         | ExprKind::SuffixedInteger(_)
+        | ExprKind::Char(_)
         | ExprKind::Regex(_)
         | ExprKind::SubjectPlaceholder
         | ExprKind::AmbientProjection(_)
@@ -615,6 +625,7 @@ pub enum ExprKind {
     BigInt(BigIntLiteral),
     SuffixedInteger(SuffixedIntegerLiteral),
     Text(TextLiteral),
+    Char(CharLiteral),
     Regex(RegexLiteral),
     Group(Box<Expr>),
     Tuple(Vec<Expr>),
@@ -760,6 +771,11 @@ pub enum PatternKind {
     Name(Identifier),
     Integer(IntegerLiteral),
     Text(TextLiteral),
+    /// `"prefix" ++ rest`: text starting with `prefix`, with the remainder matched by `rest`.
+    TextPrefix {
+        prefix: TextLiteral,
+        rest: Box<Pattern>,
+    },
     Group(Box<Pattern>),
     Tuple(Vec<Pattern>),
     List {
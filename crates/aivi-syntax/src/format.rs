@@ -1,4 +1,6 @@
-use std::fmt::Write;
+use std::{fmt::Write, ops::Range};
+
+use aivi_base::{SourceFile, Span};
 
 use crate::cst::{
     BinaryOperator, ClassMember, ClassMemberName, Decorator, DecoratorArguments, DecoratorPayload,
@@ -16,21 +18,25 @@ use crate::cst::{
 
 const INDENT_WIDTH: usize = 4;
 const INLINE_LIMIT: usize = 32;
+const DEFAULT_MAX_WIDTH: usize = 100;
+const CODE_COMMENT_INDENT: usize = 4;
 const TYPE_VARIANT_INDENT: usize = 2;
 const PIPE_STAGE_INDENT: usize = 1;
 const SIGNAL_REACTIVE_ARM_INDENT: usize = 2;
 
-const EXPR_LAMBDA_PREC: u8 = 0;
-const EXPR_PIPE_PREC: u8 = 0;
-const EXPR_RANGE_PREC: u8 = 1;
-const EXPR_OR_PREC: u8 = 2;
-const EXPR_AND_PREC: u8 = 3;
-const EXPR_COMPARE_PREC: u8 = 4;
-const EXPR_ADD_PREC: u8 = 5;
-const EXPR_MUL_PREC: u8 = 6;
-const EXPR_APPLY_PREC: u8 = 7;
-const EXPR_PROJECTION_PREC: u8 = 8;
-const EXPR_PREFIX_PREC: u8 = 9;
+// Inline expression precedence, loosest first. A sub-expression is parenthesised when its level
+// is below its context's. Public so other surface renderers parenthesise the same way.
+pub const EXPR_LAMBDA_PREC: u8 = 0;
+pub const EXPR_PIPE_PREC: u8 = 0;
+pub const EXPR_RANGE_PREC: u8 = 1;
+pub const EXPR_OR_PREC: u8 = 2;
+pub const EXPR_AND_PREC: u8 = 3;
+pub const EXPR_COMPARE_PREC: u8 = 4;
+pub const EXPR_ADD_PREC: u8 = 5;
+pub const EXPR_MUL_PREC: u8 = 6;
+pub const EXPR_APPLY_PREC: u8 = 7;
+pub const EXPR_PROJECTION_PREC: u8 = 8;
+pub const EXPR_PREFIX_PREC: u8 = 9;
 const TYPE_ARROW_PREC: u8 = 0;
 const TYPE_PIPE_PREC: u8 = 0;
 const TYPE_APPLY_PREC: u8 = 1;
@@ -40,44 +46,220 @@ const PATTERN_APPLY_PREC: u8 = 1;
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Formatter;
 
+/// Optional formatter behaviour; [`Formatter::format`] uses the defaults.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Column limit used when reflowing comments.
+    pub max_width: usize,
+    /// Normalize `//` prefixes and rewrap the prose of comment blocks attached to top-level
+    /// items. Code fences and lines indented by four or more spaces are kept verbatim.
+    pub reflow_comments: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            max_width: DEFAULT_MAX_WIDTH,
+            reflow_comments: false,
+        }
+    }
+}
+
+/// Replacement for part of a source file, produced by [`Formatter::format_range`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeFormatResult {
+    /// Byte range of the original source to replace.
+    pub span: Span,
+    /// Canonical text for the top-level items inside `span`, without a trailing newline.
+    pub text: String,
+}
+
+/// What kind of deviation a [`StyleFinding`] reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StyleFindingKind {
+    /// The line's text is canonical but its leading whitespace is not.
+    Indentation,
+    /// Blank lines are missing or superfluous.
+    BlankLines,
+    /// Only the whitespace inside lines differs, e.g. around operators.
+    Spacing,
+    /// The formatter would break or join lines differently.
+    Layout,
+}
+
+/// One place where a file differs from its canonical formatting, produced by
+/// [`Formatter::check_style`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StyleFinding {
+    pub kind: StyleFindingKind,
+    /// Byte range of the deviating source lines. Missing blank lines get an empty span where
+    /// they belong.
+    pub span: Span,
+    /// The canonical text for `span`, without a trailing newline.
+    pub expected: String,
+}
+
 impl Formatter {
     pub fn format(&self, module: &Module) -> String {
-        let formatted_items: Vec<_> = module
-            .items()
+        self.format_with_options(module, &FormatOptions::default())
+    }
+
+    pub fn format_with_options(&self, module: &Module, options: &FormatOptions) -> String {
+        let lines = self.format_items(module.items(), options);
+        if lines.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", lines.join("\n"))
+        }
+    }
+
+    /// Format only the top-level items that touch the zero-based lines `start_line..=end_line`.
+    ///
+    /// A range that starts or ends inside an item widens to the whole item, so the replacement
+    /// always matches what [`Formatter::format_with_options`] produces for those items. Whitespace between
+    /// the covered items and their neighbours is left alone. Returns `None` when no item
+    /// touches the range.
+    pub fn format_range(
+        &self,
+        source: &SourceFile,
+        module: &Module,
+        start_line: usize,
+        end_line: usize,
+        options: &FormatOptions,
+    ) -> Option<RangeFormatResult> {
+        let items = module.items();
+        let spans = items
             .iter()
-            .map(|item| self.format_item(item))
+            .map(|item| item_source_span(source, item))
+            .collect::<Vec<_>>();
+        let line_of = |offset| source.offset_to_lsp_position(offset).line as usize;
+        let touches_range =
+            |span: &Span| line_of(span.start()) <= end_line && line_of(span.end()) >= start_line;
+        let first = spans.iter().position(touches_range)?;
+        let last = spans.iter().rposition(touches_range)?;
+
+        Some(RangeFormatResult {
+            span: spans[first].join(spans[last]),
+            text: self.format_items(&items[first..=last], options).join("\n"),
+        })
+    }
+
+    /// Report where `source` deviates from [`Formatter::format_with_options`] without rewriting
+    /// it.
+    ///
+    /// Lines are paired with the formatted text by their non-whitespace content. A paired line
+    /// whose whitespace differs is reported on its own; runs of unpaired lines become blank-line
+    /// or layout findings.
+    pub fn check_style(
+        &self,
+        source: &SourceFile,
+        module: &Module,
+        options: &FormatOptions,
+    ) -> Vec<StyleFinding> {
+        let formatted = self.format_with_options(module, options);
+        let expected = formatted.lines().collect::<Vec<_>>();
+        let mut actual = (0..source.line_count())
+            .map(|line| source.line_text(line).unwrap_or_default())
+            .collect::<Vec<_>>();
+        // A trailing newline leaves an empty last line that the formatted text does not have.
+        if source.text().ends_with('\n') && actual.last().is_some_and(|last| last.is_empty()) {
+            actual.pop();
+        }
+        let line_spans = (0..actual.len())
+            .map(|line| source.line_span(line).expect("line index is in range"))
+            .collect::<Vec<_>>();
+        let lines_span = |lines: Range<usize>| {
+            if lines.is_empty() {
+                let offset = line_spans
+                    .get(lines.start)
+                    .map_or(source.len(), |span| span.start().as_usize());
+                Span::from(offset..offset)
+            } else {
+                line_spans[lines.start].join(line_spans[lines.end - 1])
+            }
+        };
+
+        let actual_keys = actual
+            .iter()
+            .map(|line| without_whitespace(line))
+            .collect::<Vec<_>>();
+        let expected_keys = expected
+            .iter()
+            .map(|line| without_whitespace(line))
+            .collect::<Vec<_>>();
+        let mut findings = Vec::new();
+        let mut unpaired_from = (0, 0);
+        let pairs = matching_lines(&actual_keys, &expected_keys)
+            .into_iter()
+            .chain(std::iter::once((actual.len(), expected.len())));
+        for (actual_line, expected_line) in pairs {
+            for (kind, actual_lines, expected_lines) in classify_unpaired_lines(
+                &actual,
+                &expected,
+                unpaired_from,
+                (actual_line, expected_line),
+            ) {
+                findings.push(StyleFinding {
+                    kind,
+                    span: lines_span(actual_lines),
+                    expected: expected[expected_lines].join("\n"),
+                });
+            }
+            if actual_line < actual.len() && actual[actual_line] != expected[expected_line] {
+                let kind =
+                    if actual[actual_line].trim_start() == expected[expected_line].trim_start() {
+                        StyleFindingKind::Indentation
+                    } else {
+                        StyleFindingKind::Spacing
+                    };
+                findings.push(StyleFinding {
+                    kind,
+                    span: line_spans[actual_line],
+                    expected: expected[expected_line].to_owned(),
+                });
+            }
+            unpaired_from = (actual_line + 1, expected_line + 1);
+        }
+        findings
+    }
+
+    fn format_leading_comments(&self, item: &Item, options: &FormatOptions) -> Vec<String> {
+        let comments = &item.base().leading_comments;
+        if options.reflow_comments {
+            reflow_comment_block(comments, options.max_width)
+        } else {
+            comments.clone()
+        }
+    }
+
+    fn format_items(&self, items: &[Item], options: &FormatOptions) -> Vec<String> {
+        let formatted_items: Vec<_> = items
+            .iter()
+            .map(|item| self.format_item(item, options))
             .collect();
         let mut lines = Vec::new();
-        for (index, item) in module.items().iter().enumerate() {
+        for (index, item) in items.iter().enumerate() {
             lines.extend(formatted_items[index].iter().cloned());
-            if index + 1 < module.items().len()
+            if index + 1 < items.len()
                 && self.needs_blank_line_between(
                     item,
                     &formatted_items[index],
-                    &module.items()[index + 1],
+                    &items[index + 1],
                     &formatted_items[index + 1],
                 )
             {
                 lines.push(String::new());
             }
         }
-
-        if lines.is_empty() {
-            String::new()
-        } else {
-            format!("{}\n", lines.join("\n"))
-        }
+        lines
     }
 
-    fn format_item(&self, item: &Item) -> Vec<String> {
+    fn format_item(&self, item: &Item, options: &FormatOptions) -> Vec<String> {
         if let Item::Fun(fun) = item
             && fun.annotation.is_some()
         {
             let rendered = self.format_fun_item(fun);
-            let mut lines = Vec::new();
-            for comment in &item.base().leading_comments {
-                lines.push(comment.clone());
-            }
+            let mut lines = self.format_leading_comments(item, options);
             if let Some((type_line, rest)) = rendered.split_first() {
                 lines.push(type_line.clone());
                 for decorator in item.decorators() {
@@ -88,10 +270,7 @@ impl Formatter {
             }
         }
 
-        let mut lines = Vec::new();
-        for comment in &item.base().leading_comments {
-            lines.push(comment.clone());
-        }
+        let mut lines = self.format_leading_comments(item, options);
         for decorator in item.decorators() {
             lines.extend(self.format_decorator(decorator).into_lines());
         }
@@ -1581,6 +1760,7 @@ impl Formatter {
             ExprKind::BigInt(bigint) => bigint.raw.clone(),
             ExprKind::SuffixedInteger(literal) => self.format_suffixed_integer_inline(literal),
             ExprKind::Text(text) => self.format_text_literal(text),
+            ExprKind::Char(literal) => literal.raw.clone(),
             ExprKind::Regex(regex) => regex.raw.clone(),
             ExprKind::Group(inner) => format!("({})", self.format_expr_inline(inner, 0)),
             ExprKind::Tuple(elements) => self.format_expr_tuple_inline(elements),
@@ -1641,17 +1821,17 @@ impl Formatter {
                 operator,
                 right,
             } => {
-                let precedence = self.binary_precedence(*operator);
+                let precedence = binary_operator_precedence(*operator);
                 let rendered = format!(
                     "{} {} {}",
                     self.format_expr_inline(left, precedence),
-                    self.format_binary_operator(*operator),
+                    binary_operator_text(*operator),
                     self.format_expr_inline(right, precedence + 1)
                 );
                 wrap_if_needed(rendered, precedence, parent_prec)
             }
             ExprKind::OperatorSection(op) => {
-                format!("({})", self.format_binary_operator(*op))
+                format!("({})", binary_operator_text(*op))
             }
             ExprKind::ResultBlock(block) => self.format_result_block_inline(block),
             ExprKind::Pipe(pipe) => {
@@ -2515,6 +2695,15 @@ impl Formatter {
             PatternKind::Name(name) => name.text.clone(),
             PatternKind::Integer(integer) => integer.raw.clone(),
             PatternKind::Text(text) => self.format_text_literal(text),
+            PatternKind::TextPrefix { prefix, rest } => wrap_if_needed(
+                format!(
+                    "{} ++ {}",
+                    self.format_text_literal(prefix),
+                    self.format_pattern_inline(rest, 0)
+                ),
+                0,
+                parent_prec,
+            ),
             PatternKind::Group(inner) => format!("({})", self.format_pattern_inline(inner, 0)),
             PatternKind::Tuple(elements) => self.format_pattern_tuple_inline(elements),
             PatternKind::List { elements, rest } => {
@@ -2626,41 +2815,6 @@ impl Formatter {
         }
     }
 
-    fn format_binary_operator(&self, operator: BinaryOperator) -> &'static str {
-        match operator {
-            BinaryOperator::Add => "+",
-            BinaryOperator::Subtract => "-",
-            BinaryOperator::GreaterThan => ">",
-            BinaryOperator::LessThan => "<",
-            BinaryOperator::GreaterThanOrEqual => ">=",
-            BinaryOperator::LessThanOrEqual => "<=",
-            BinaryOperator::Equals => "==",
-            BinaryOperator::NotEquals => "!=",
-            BinaryOperator::And => "and",
-            BinaryOperator::Or => "or",
-            BinaryOperator::Multiply => "*",
-            BinaryOperator::Divide => "/",
-            BinaryOperator::Modulo => "%",
-        }
-    }
-
-    fn binary_precedence(&self, operator: BinaryOperator) -> u8 {
-        match operator {
-            BinaryOperator::Or => EXPR_OR_PREC,
-            BinaryOperator::And => EXPR_AND_PREC,
-            BinaryOperator::GreaterThan
-            | BinaryOperator::LessThan
-            | BinaryOperator::GreaterThanOrEqual
-            | BinaryOperator::LessThanOrEqual
-            | BinaryOperator::Equals
-            | BinaryOperator::NotEquals => EXPR_COMPARE_PREC,
-            BinaryOperator::Add | BinaryOperator::Subtract => EXPR_ADD_PREC,
-            BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => {
-                EXPR_MUL_PREC
-            }
-        }
-    }
-
     fn should_force_expr_break(&self, prefix_width: usize, expr: &Expr) -> bool {
         self.expr_can_break(expr)
             && prefix_width + display_width(&self.format_expr_inline(expr, 0)) > INLINE_LIMIT
@@ -2717,6 +2871,93 @@ impl Formatter {
     }
 }
 
+/// Rewrap the prose paragraphs of one comment block to `max_width` columns.
+///
+/// Blank comment lines and list items start new paragraphs. Fenced blocks and lines indented by
+/// [`CODE_COMMENT_INDENT`] or more spaces are copied unchanged, and words are never split, so a
+/// URL longer than the limit stays on a line of its own.
+fn reflow_comment_block(comments: &[String], max_width: usize) -> Vec<String> {
+    let text_width = max_width.saturating_sub("// ".len()).max(1);
+    let mut lines = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+    for comment in comments {
+        let body = comment.strip_prefix("//").unwrap_or(comment);
+        let indent = body.len() - body.trim_start_matches(' ').len();
+        let text = body.trim();
+        let is_fence_marker = text.starts_with("```");
+        if in_fence || is_fence_marker || indent >= CODE_COMMENT_INDENT {
+            flush_comment_paragraph(&mut lines, &mut paragraph, text_width);
+            lines.push(comment.clone());
+            if is_fence_marker {
+                in_fence = !in_fence;
+            }
+            continue;
+        }
+        if text.is_empty() || starts_comment_list_item(text) {
+            flush_comment_paragraph(&mut lines, &mut paragraph, text_width);
+        }
+        if text.is_empty() {
+            lines.push("//".to_owned());
+        } else {
+            paragraph.extend(text.split_whitespace());
+        }
+    }
+    flush_comment_paragraph(&mut lines, &mut paragraph, text_width);
+    lines
+}
+
+fn flush_comment_paragraph(lines: &mut Vec<String>, words: &mut Vec<&str>, text_width: usize) {
+    let mut current = String::new();
+    for word in words.drain(..) {
+        if !current.is_empty() && display_width(&current) + 1 + display_width(word) > text_width {
+            lines.push(format!("// {current}"));
+            current.clear();
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(format!("// {current}"));
+    }
+}
+
+fn starts_comment_list_item(text: &str) -> bool {
+    if text.starts_with("- ") || text.starts_with("* ") {
+        return true;
+    }
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    digits > 0 && text[digits..].starts_with(". ")
+}
+
+/// Source text that an item's formatted lines replace: the item itself, a standalone `type`
+/// annotation line above it, and the comment lines the parser attached to it.
+fn item_source_span(source: &SourceFile, item: &Item) -> Span {
+    let span = item.span().span();
+    let mut start = span.start();
+    if let Item::Fun(named) | Item::Value(named) | Item::Signal(named) = item
+        && let Some(annotation) = &named.annotation
+    {
+        start = start.min(annotation.span.span().start());
+    }
+
+    let mut line = source.offset_to_lsp_position(start).line as usize;
+    let mut comments = item.base().leading_comments.len();
+    while comments > 0
+        && line > 0
+        && source
+            .line_text(line - 1)
+            .is_some_and(|text| text.trim_start().starts_with("//"))
+    {
+        line -= 1;
+        comments -= 1;
+    }
+    let line_start = source.line_span(line).map_or(start, |line| line.start());
+    Span::new(line_start.min(start), span.end())
+}
+
 fn escape_text_fragment(raw: &str) -> String {
     let mut escaped = String::with_capacity(raw.len());
     for ch in raw.chars() {
@@ -2800,6 +3041,41 @@ impl Block {
     }
 }
 
+/// Surface spelling of a binary operator.
+pub fn binary_operator_text(operator: BinaryOperator) -> &'static str {
+    match operator {
+        BinaryOperator::Add => "+",
+        BinaryOperator::Subtract => "-",
+        BinaryOperator::GreaterThan => ">",
+        BinaryOperator::LessThan => "<",
+        BinaryOperator::GreaterThanOrEqual => ">=",
+        BinaryOperator::LessThanOrEqual => "<=",
+        BinaryOperator::Equals => "==",
+        BinaryOperator::NotEquals => "!=",
+        BinaryOperator::And => "and",
+        BinaryOperator::Or => "or",
+        BinaryOperator::Multiply => "*",
+        BinaryOperator::Divide => "/",
+        BinaryOperator::Modulo => "%",
+    }
+}
+
+/// Precedence level of a binary operator; see [`EXPR_OR_PREC`] and its neighbours.
+pub fn binary_operator_precedence(operator: BinaryOperator) -> u8 {
+    match operator {
+        BinaryOperator::Or => EXPR_OR_PREC,
+        BinaryOperator::And => EXPR_AND_PREC,
+        BinaryOperator::GreaterThan
+        | BinaryOperator::LessThan
+        | BinaryOperator::GreaterThanOrEqual
+        | BinaryOperator::LessThanOrEqual
+        | BinaryOperator::Equals
+        | BinaryOperator::NotEquals => EXPR_COMPARE_PREC,
+        BinaryOperator::Add | BinaryOperator::Subtract => EXPR_ADD_PREC,
+        BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => EXPR_MUL_PREC,
+    }
+}
+
 fn wrap_if_needed(rendered: String, current_prec: u8, parent_prec: u8) -> String {
     if current_prec < parent_prec {
         format!("({rendered})")
@@ -2848,13 +3124,123 @@ fn format_prefixed_record_like(prefix: &str, fields: Vec<String>) -> String {
     }
 }
 
+/// Indices of a longest common subsequence of `actual` and `expected`, in order. Equal leading
+/// and trailing lines are paired directly, so mostly formatted files keep the table small.
+fn matching_lines(actual: &[String], expected: &[String]) -> Vec<(usize, usize)> {
+    let prefix = actual
+        .iter()
+        .zip(expected)
+        .take_while(|(actual, expected)| actual == expected)
+        .count();
+    let suffix = actual[prefix..]
+        .iter()
+        .rev()
+        .zip(expected[prefix..].iter().rev())
+        .take_while(|(actual, expected)| actual == expected)
+        .count();
+    let middle_actual = &actual[prefix..actual.len() - suffix];
+    let middle_expected = &expected[prefix..expected.len() - suffix];
+
+    // common[i * width + j] is the LCS length of middle_actual[i..] and middle_expected[j..].
+    let width = middle_expected.len() + 1;
+    let mut common = vec![0usize; (middle_actual.len() + 1) * width];
+    for i in (0..middle_actual.len()).rev() {
+        for j in (0..middle_expected.len()).rev() {
+            common[i * width + j] = if middle_actual[i] == middle_expected[j] {
+                common[(i + 1) * width + j + 1] + 1
+            } else {
+                common[(i + 1) * width + j].max(common[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut pairs = (0..prefix).map(|line| (line, line)).collect::<Vec<_>>();
+    let (mut i, mut j) = (0, 0);
+    while i < middle_actual.len() && j < middle_expected.len() {
+        if middle_actual[i] == middle_expected[j] {
+            pairs.push((prefix + i, prefix + j));
+            i += 1;
+            j += 1;
+        } else if common[(i + 1) * width + j] >= common[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs.extend((0..suffix).map(|offset| {
+        (
+            actual.len() - suffix + offset,
+            expected.len() - suffix + offset,
+        )
+    }));
+    pairs
+}
+
+/// Split the unpaired lines `from.0..to.0` of `actual` and `from.1..to.1` of `expected` into
+/// findings: surplus or missing blank lines at either end, and the content between them.
+fn classify_unpaired_lines(
+    actual: &[&str],
+    expected: &[&str],
+    from: (usize, usize),
+    to: (usize, usize),
+) -> Vec<(StyleFindingKind, Range<usize>, Range<usize>)> {
+    let (actual_lines, expected_lines) = (from.0..to.0, from.1..to.1);
+    if actual_lines.is_empty() && expected_lines.is_empty() {
+        return Vec::new();
+    }
+    let is_blank = |line: &&str| line.trim().is_empty();
+    if actual[actual_lines.clone()].iter().all(is_blank)
+        && expected[expected_lines.clone()].iter().all(is_blank)
+    {
+        return vec![(StyleFindingKind::BlankLines, actual_lines, expected_lines)];
+    }
+
+    let leading = |lines: &[&str]| lines.iter().take_while(|line| is_blank(line)).count();
+    let trailing = |lines: &[&str]| lines.iter().rev().take_while(|line| is_blank(line)).count();
+    let (actual_leading, expected_leading) = (
+        leading(&actual[actual_lines.clone()]),
+        leading(&expected[expected_lines.clone()]),
+    );
+    let (actual_trailing, expected_trailing) = (
+        trailing(&actual[actual_lines.clone()]),
+        trailing(&expected[expected_lines.clone()]),
+    );
+    let mut findings = Vec::new();
+    if actual_leading != expected_leading {
+        findings.push((
+            StyleFindingKind::BlankLines,
+            from.0..from.0 + actual_leading,
+            from.1..from.1 + expected_leading,
+        ));
+    }
+    findings.push((
+        StyleFindingKind::Layout,
+        from.0 + actual_leading..to.0 - actual_trailing,
+        from.1 + expected_leading..to.1 - expected_trailing,
+    ));
+    if actual_trailing != expected_trailing {
+        findings.push((
+            StyleFindingKind::BlankLines,
+            to.0 - actual_trailing..to.0,
+            to.1 - expected_trailing..to.1,
+        ));
+    }
+    findings
+}
+
+fn without_whitespace(line: &str) -> String {
+    line.chars()
+        .filter(|character| !character.is_whitespace())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs, path::PathBuf};
 
     use aivi_base::SourceDatabase;
 
-    use super::Formatter;
+    use super::{FormatOptions, Formatter, StyleFinding, StyleFindingKind};
     use crate::parse::parse_module;
 
     fn fixture_root() -> PathBuf {
@@ -2873,6 +3259,59 @@ mod tests {
         Formatter.format(&parsed.module)
     }
 
+    fn format_reflowed(input: &str, max_width: usize) -> String {
+        let mut sources = SourceDatabase::new();
+        let file_id = sources.add_file("test.aivi", input.to_owned());
+        let parsed = parse_module(&sources[file_id]);
+        assert!(!parsed.has_errors(), "expected reflow test input to parse");
+        let options = FormatOptions {
+            max_width,
+            reflow_comments: true,
+        };
+        let formatted = Formatter.format_with_options(&parsed.module, &options);
+
+        let file_id = sources.add_file("reflowed.aivi", formatted.clone());
+        let reparsed = parse_module(&sources[file_id]);
+        assert_eq!(
+            Formatter.format_with_options(&reparsed.module, &options),
+            formatted,
+            "comment reflow should be idempotent"
+        );
+        formatted
+    }
+
+    fn format_range_text(input: &str, start_line: usize, end_line: usize) -> Option<String> {
+        let mut sources = SourceDatabase::new();
+        let file_id = sources.add_file("test.aivi", input.to_owned());
+        let parsed = parse_module(&sources[file_id]);
+        assert!(!parsed.has_errors(), "expected range test input to parse");
+        let result = Formatter.format_range(
+            &sources[file_id],
+            &parsed.module,
+            start_line,
+            end_line,
+            &FormatOptions::default(),
+        )?;
+        let mut spliced = input.to_owned();
+        spliced.replace_range(
+            result.span.start().as_usize()..result.span.end().as_usize(),
+            &result.text,
+        );
+        Some(spliced)
+    }
+
+    fn check_style_text(input: &str) -> Vec<StyleFinding> {
+        let mut sources = SourceDatabase::new();
+        let file_id = sources.add_file("test.aivi", input.to_owned());
+        let parsed = parse_module(&sources[file_id]);
+        assert!(!parsed.has_errors(), "expected style check input to parse");
+        Formatter.check_style(&sources[file_id], &parsed.module, &FormatOptions::default())
+    }
+
+    fn span_text(input: &str, finding: &StyleFinding) -> String {
+        input[finding.span.start().as_usize()..finding.span.end().as_usize()].to_owned()
+    }
+
     fn format_fixture(relative_path: &str) -> String {
         let path = fixture_root().join(relative_path);
         let input = fs::read_to_string(&path).expect("fixture must load");
@@ -2933,6 +3372,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn formatter_preserves_float_exponent_notation() {
+        assert_eq!(
+            format_text("value avogadro:Float=6.022e23\nvalue small:Float=1.5E-3\n"),
+            "value avogadro : Float = 6.022e23\nvalue small : Float = 1.5E-3\n"
+        );
+    }
+
     #[test]
     fn formatter_aligns_short_pipe_operators_with_three_char_stages() {
         let formatted = format_text(
@@ -3790,4 +4237,194 @@ value view =
         let formatted = format_text("type Pair = Pair first:Text Int\n");
         assert_eq!(formatted, "type Pair = Pair first:Text Int\n");
     }
+
+    #[test]
+    fn format_range_leaves_sibling_items_untouched() {
+        let input = concat!(
+            "value first=1\n",
+            "\n",
+            "\n",
+            "// Doubles its argument.\n",
+            "type Int -> Int\n",
+            "func double = n=>n*2\n",
+            "value  third =  3\n",
+        );
+
+        let spliced = format_range_text(input, 5, 5).expect("range should touch `double`");
+        assert_eq!(
+            spliced,
+            concat!(
+                "value first=1\n",
+                "\n",
+                "\n",
+                "// Doubles its argument.\n",
+                "type Int -> Int\n",
+                "func double = n =>\n",
+                "    n * 2\n",
+                "value  third =  3\n",
+            )
+        );
+        assert!(format_text(input).contains(concat!(
+            "// Doubles its argument.\n",
+            "type Int -> Int\n",
+            "func double = n =>\n",
+            "    n * 2\n",
+        )));
+    }
+
+    #[test]
+    fn format_range_widens_to_whole_items_and_matches_full_formatting() {
+        let input = concat!(
+            "value  first=1\n",
+            "type Pair = Pair first:Text Int\n",
+            "value second=[1,\n",
+            "  2]\n",
+            "value  last=3\n",
+        );
+
+        // Lines 1..=2 cut through `second`, which spans lines 2 and 3.
+        let spliced = format_range_text(input, 1, 2).expect("range should touch two items");
+        let formatted = format_text(input);
+        assert_eq!(
+            spliced,
+            concat!(
+                "value  first=1\n",
+                "type Pair = Pair first:Text Int\n",
+                "\n",
+                "value second = [1, 2]\n",
+                "value  last=3\n",
+            )
+        );
+        assert!(formatted.contains("type Pair = Pair first:Text Int\n\nvalue second = [1, 2]\n"));
+    }
+
+    #[test]
+    fn format_range_outside_any_item_is_none() {
+        assert_eq!(
+            format_range_text("value a = 1\n\n\nvalue b = 2\n", 1, 2),
+            None
+        );
+    }
+
+    #[test]
+    fn check_style_reports_a_misindented_line_at_its_span() {
+        let input = concat!("type Int -> Int\n", "func double = n =>\n", "  n * 2\n",);
+        assert_eq!(format_text(input), input.replace("  n", "    n"));
+
+        let findings = check_style_text(input);
+        assert_eq!(findings.len(), 1, "unexpected findings: {findings:?}");
+        assert_eq!(findings[0].kind, StyleFindingKind::Indentation);
+        assert_eq!(span_text(input, &findings[0]), "  n * 2");
+        assert_eq!(findings[0].expected, "    n * 2");
+    }
+
+    #[test]
+    fn check_style_classifies_spacing_blank_lines_and_layout() {
+        let input = concat!(
+            "value first=1+2\n",
+            "\n",
+            "\n",
+            "\n",
+            "value second = [1,\n",
+            "  2]\n",
+        );
+        let findings = check_style_text(input);
+        let summary = findings
+            .iter()
+            .map(|finding| (finding.kind, span_text(input, finding)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (StyleFindingKind::Spacing, "value first=1+2".to_owned()),
+                (StyleFindingKind::BlankLines, "\n\n".to_owned()),
+                (
+                    StyleFindingKind::Layout,
+                    "value second = [1,\n  2]".to_owned()
+                ),
+            ]
+        );
+        assert_eq!(findings[0].expected, "value first = 1 + 2");
+        assert_eq!(findings[2].expected, "value second = [1, 2]");
+    }
+
+    #[test]
+    fn check_style_accepts_formatted_source() {
+        let formatted = format_text("value first=1\ntype Int -> Int\nfunc double = n=>n*2\n");
+        assert_eq!(check_style_text(&formatted), Vec::new());
+    }
+
+    #[test]
+    fn reflow_comments_normalizes_prefixes_and_wraps_paragraphs() {
+        let input = concat!(
+            "//Adds the two configured offsets together and returns the combined offset\n",
+            "//   so that callers never have to.\n",
+            "//\n",
+            "// - first item\n",
+            "// - second item\n",
+            "value offset = 1\n",
+        );
+
+        assert_eq!(
+            format_reflowed(input, 40),
+            concat!(
+                "// Adds the two configured offsets\n",
+                "// together and returns the combined\n",
+                "// offset so that callers never have to.\n",
+                "//\n",
+                "// - first item\n",
+                "// - second item\n",
+                "value offset = 1\n",
+            )
+        );
+    }
+
+    #[test]
+    fn reflow_comments_keeps_code_fences_verbatim() {
+        let input = concat!(
+            "// Example usage of the helper that is being documented right here:\n",
+            "// ```aivi\n",
+            "//value   total=offset+offset\n",
+            "// ```\n",
+            "//     indented  code  stays  too\n",
+            "value offset = 1\n",
+        );
+
+        assert_eq!(
+            format_reflowed(input, 40),
+            concat!(
+                "// Example usage of the helper that is\n",
+                "// being documented right here:\n",
+                "// ```aivi\n",
+                "//value   total=offset+offset\n",
+                "// ```\n",
+                "//     indented  code  stays  too\n",
+                "value offset = 1\n",
+            )
+        );
+    }
+
+    #[test]
+    fn reflow_comments_never_split_urls() {
+        let input = concat!(
+            "// See https://example.com/a/very/long/path/that/exceeds/the/limit for details.\n",
+            "value offset = 1\n",
+        );
+
+        assert_eq!(
+            format_reflowed(input, 30),
+            concat!(
+                "// See\n",
+                "// https://example.com/a/very/long/path/that/exceeds/the/limit\n",
+                "// for details.\n",
+                "value offset = 1\n",
+            )
+        );
+    }
+
+    #[test]
+    fn comments_are_left_alone_without_reflow() {
+        let input = "//Keep   this   exactly.\nvalue offset = 1\n";
+        assert_eq!(format_text(input), input);
+    }
 }
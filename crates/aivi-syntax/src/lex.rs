@@ -1,7 +1,8 @@
 use aivi_base::{Diagnostic, SourceFile, Span};
 
 use crate::codes::{
-    INVALID_ESCAPE_SEQUENCE, UNEXPECTED_CHARACTER, UNTERMINATED_REGEX, UNTERMINATED_STRING,
+    FLOAT_LITERAL_OUT_OF_RANGE, INVALID_CHAR_LITERAL, INVALID_ESCAPE_SEQUENCE,
+    MALFORMED_FLOAT_EXPONENT, UNEXPECTED_CHARACTER, UNTERMINATED_REGEX, UNTERMINATED_STRING,
 };
 
 /// Token kinds required for the Milestone 1 surface grammar.
@@ -21,6 +22,7 @@ pub enum TokenKind {
     Decimal,
     BigInt,
     StringLiteral,
+    CharLiteral,
     RegexLiteral,
     At,
     Hash,
@@ -396,40 +398,77 @@ fn lex_range(source: &SourceFile, range: std::ops::Range<usize>) -> LexedModule
             while cursor < range.end && bytes[cursor].is_ascii_digit() {
                 cursor += 1;
             }
-            let kind = if cursor < range.end && bytes[cursor] == b'.' {
-                let fractional_start = cursor + 1;
-                if fractional_start < range.end && bytes[fractional_start].is_ascii_digit() {
-                    cursor = fractional_start + 1;
-                    while cursor < range.end && bytes[cursor].is_ascii_digit() {
-                        cursor += 1;
-                    }
-                    if cursor < range.end
-                        && bytes[cursor] == b'd'
-                        && !starts_identifier_continue(text, cursor + 1, range.end)
-                    {
-                        cursor += 1;
-                        TokenKind::Decimal
-                    } else {
-                        TokenKind::Float
-                    }
-                } else {
-                    TokenKind::Integer
+            let mut kind = TokenKind::Integer;
+            let fractional_start = cursor + 1;
+            if cursor < range.end
+                && bytes[cursor] == b'.'
+                && fractional_start < range.end
+                && bytes[fractional_start].is_ascii_digit()
+            {
+                cursor = fractional_start + 1;
+                while cursor < range.end && bytes[cursor].is_ascii_digit() {
+                    cursor += 1;
                 }
-            } else if cursor < range.end
+                kind = TokenKind::Float;
+            }
+            let exponent = scan_exponent(text, bytes, cursor, range.end);
+            match exponent {
+                ExponentScan::Absent => {}
+                ExponentScan::Complete(end) => {
+                    cursor = end;
+                    kind = TokenKind::Float;
+                }
+                ExponentScan::Malformed(end) => {
+                    diagnostics.push(
+                        Diagnostic::error(format!(
+                            "malformed exponent in float literal `{}`",
+                            &text[start..end]
+                        ))
+                        .with_code(MALFORMED_FLOAT_EXPONENT)
+                        .with_primary_label(
+                            source.source_span(cursor..end),
+                            "expected digits after the exponent marker",
+                        )
+                        .with_help("write the exponent as digits, as in `1e9` or `1.5e-3`"),
+                    );
+                    cursor = end;
+                    kind = TokenKind::Float;
+                }
+            }
+            if exponent == ExponentScan::Absent
+                && cursor < range.end
                 && bytes[cursor] == b'd'
                 && !starts_identifier_continue(text, cursor + 1, range.end)
             {
                 cursor += 1;
-                TokenKind::Decimal
-            } else if cursor < range.end
+                kind = TokenKind::Decimal;
+            } else if kind == TokenKind::Integer
+                && cursor < range.end
                 && bytes[cursor] == b'n'
                 && !starts_identifier_continue(text, cursor + 1, range.end)
             {
                 cursor += 1;
-                TokenKind::BigInt
-            } else {
-                TokenKind::Integer
-            };
+                kind = TokenKind::BigInt;
+            }
+            if kind == TokenKind::Float
+                && !matches!(exponent, ExponentScan::Malformed(_))
+                && !text[start..cursor]
+                    .parse::<f64>()
+                    .is_ok_and(|value| value.is_finite())
+            {
+                diagnostics.push(
+                    Diagnostic::error(format!(
+                        "float literal `{}` is out of range",
+                        &text[start..cursor]
+                    ))
+                    .with_code(FLOAT_LITERAL_OUT_OF_RANGE)
+                    .with_primary_label(
+                        source.source_span(start..cursor),
+                        "this value does not fit in a 64-bit float",
+                    )
+                    .with_help("`Float` literals must be finite, at most about `1.8e308`"),
+                );
+            }
             tokens.push(Token::new(kind, source.span(start..cursor), line_start));
             at_line_start = false;
             continue;
@@ -502,6 +541,55 @@ fn lex_range(source: &SourceFile, range: std::ops::Range<usize>) -> LexedModule
             continue;
         }
 
+        if character == '\'' {
+            let start = cursor;
+            let (end, terminated) = scan_char_literal(text, cursor, range.end);
+            cursor = end;
+            tokens.push(Token::new(
+                TokenKind::CharLiteral,
+                source.span(start..cursor),
+                line_start,
+            ));
+            let decoded = if terminated {
+                decode_char_literal(&text[start..cursor])
+            } else {
+                Err(CharLiteralError::Unterminated)
+            };
+            if let Err(error) = decoded {
+                let (message, code) = match error {
+                    CharLiteralError::Unterminated => (
+                        "char literal is not terminated before the end of the line or file"
+                            .to_owned(),
+                        INVALID_CHAR_LITERAL,
+                    ),
+                    CharLiteralError::Empty => {
+                        ("char literal is empty".to_owned(), INVALID_CHAR_LITERAL)
+                    }
+                    CharLiteralError::TooLong => (
+                        "char literal contains more than one character".to_owned(),
+                        INVALID_CHAR_LITERAL,
+                    ),
+                    CharLiteralError::InvalidEscape(escape) => (
+                        format!("invalid escape sequence `{escape}` in char literal"),
+                        INVALID_ESCAPE_SEQUENCE,
+                    ),
+                };
+                diagnostics.push(
+                    Diagnostic::error(message)
+                        .with_code(code)
+                        .with_primary_label(
+                            source.source_span(start..cursor),
+                            "expected one character between `'` quotes",
+                        )
+                        .with_help(
+                            "write a single character or escape such as 'a', '\\n', or '\\u{1F600}'; use \"...\" for text",
+                        ),
+                );
+            }
+            at_line_start = false;
+            continue;
+        }
+
         let kind = match character {
             '@' => TokenKind::At,
             '#' => TokenKind::Hash,
@@ -623,6 +711,125 @@ fn is_identifier_continue(character: char) -> bool {
     is_identifier_start(character) || character.is_ascii_digit()
 }
 
+/// Find the end of a `'x'` literal starting at `start`: just past the closing quote, or the end
+/// of the line when the literal is not closed. The flag reports whether the quote was found.
+fn scan_char_literal(text: &str, start: usize, end: usize) -> (usize, bool) {
+    let mut cursor = start + 1;
+    while cursor < end {
+        let next = text[cursor..]
+            .chars()
+            .next()
+            .expect("char literal scan must stay on a UTF-8 boundary");
+        match next {
+            '\n' | '\r' => return (cursor, false),
+            '\'' => return (cursor + 1, true),
+            '\\' => {
+                cursor += 1;
+                if let Some(escaped) = text[cursor..end].chars().next()
+                    && escaped != '\n'
+                    && escaped != '\r'
+                {
+                    cursor += escaped.len_utf8();
+                }
+            }
+            other => cursor += other.len_utf8(),
+        }
+    }
+    (cursor, false)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum CharLiteralError {
+    Unterminated,
+    Empty,
+    TooLong,
+    InvalidEscape(String),
+}
+
+/// Decode a whole `'x'` literal, quotes included, into the one character it denotes.
+///
+/// Escapes match text literals: `\n`, `\t`, `\r`, `\\`, `\'`, `\"`, `\0`, `\xNN` and `\u{N..}`.
+pub(crate) fn decode_char_literal(raw: &str) -> Result<char, CharLiteralError> {
+    let body = raw
+        .strip_prefix('\'')
+        .and_then(|rest| rest.strip_suffix('\''))
+        .ok_or(CharLiteralError::Unterminated)?;
+    let mut chars = body.chars();
+    let decoded = match chars.next().ok_or(CharLiteralError::Empty)? {
+        '\\' => {
+            let escape = chars.as_str();
+            let invalid = || CharLiteralError::InvalidEscape(format!("\\{escape}"));
+            match chars.next().ok_or_else(invalid)? {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                '\\' => '\\',
+                '\'' => '\'',
+                '"' => '"',
+                '0' => '\0',
+                'x' => {
+                    let digits = chars.as_str().get(..2).ok_or_else(invalid)?;
+                    chars = chars.as_str()[2..].chars();
+                    u8::from_str_radix(digits, 16)
+                        .ok()
+                        .filter(u8::is_ascii)
+                        .map(char::from)
+                        .ok_or_else(invalid)?
+                }
+                'u' => {
+                    let rest = chars.as_str().strip_prefix('{').ok_or_else(invalid)?;
+                    let close = rest.find('}').ok_or_else(invalid)?;
+                    let decoded = u32::from_str_radix(&rest[..close], 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(invalid)?;
+                    chars = rest[close + 1..].chars();
+                    decoded
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        other => other,
+    };
+    if chars.next().is_some() {
+        return Err(CharLiteralError::TooLong);
+    }
+    Ok(decoded)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ExponentScan {
+    Absent,
+    Complete(usize),
+    Malformed(usize),
+}
+
+/// Scan an `e`/`E` exponent with an optional sign after the digits of a number literal.
+///
+/// An `e` followed by other identifier characters is left alone so suffix literals such as `2em`
+/// keep lexing as a number followed by its suffix.
+fn scan_exponent(text: &str, bytes: &[u8], cursor: usize, end: usize) -> ExponentScan {
+    if cursor >= end || !matches!(bytes[cursor], b'e' | b'E') {
+        return ExponentScan::Absent;
+    }
+    let mut digits_start = cursor + 1;
+    let signed = digits_start < end && matches!(bytes[digits_start], b'+' | b'-');
+    if signed {
+        digits_start += 1;
+    }
+    let mut digits_end = digits_start;
+    while digits_end < end && bytes[digits_end].is_ascii_digit() {
+        digits_end += 1;
+    }
+    if digits_end > digits_start {
+        ExponentScan::Complete(digits_end)
+    } else if signed || !starts_identifier_continue(text, cursor + 1, end) {
+        ExponentScan::Malformed(digits_end)
+    } else {
+        ExponentScan::Absent
+    }
+}
+
 fn starts_identifier_continue(text: &str, cursor: usize, end: usize) -> bool {
     if cursor >= end {
         return false;
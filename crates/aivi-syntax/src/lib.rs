@@ -9,10 +9,10 @@ pub mod lex;
 pub mod parse;
 
 pub use cst::{
-    BigIntLiteral, BinaryOperator, ClassBody, ClassMember, ClassMemberName, DecimalLiteral,
-    Decorator, DecoratorArguments, DecoratorPayload, DomainBody, DomainItem, DomainMember,
-    DomainMemberName, ErrorItem, ExportItem, Expr, ExprKind, FloatLiteral, FromEntry, FromItem,
-    FunctionParam, HoistItem, HoistKindFilter, Identifier, InstanceBody, InstanceItem,
+    BigIntLiteral, BinaryOperator, CharLiteral, ClassBody, ClassMember, ClassMemberName,
+    DecimalLiteral, Decorator, DecoratorArguments, DecoratorPayload, DomainBody, DomainItem,
+    DomainMember, DomainMemberName, ErrorItem, ExportItem, Expr, ExprKind, FloatLiteral, FromEntry,
+    FromItem, FunctionParam, HoistItem, HoistKindFilter, Identifier, InstanceBody, InstanceItem,
     InstanceMember, IntegerLiteral, Item, ItemBase, ItemKind, MapExpr, MapExprEntry,
     MarkupAttribute, MarkupAttributeValue, MarkupNode, Module, NamedItem, NamedItemBody,
     OperatorName, PatchBlock, PatchEntry, PatchInstruction, PatchInstructionKind, PatchSelector,
@@ -25,6 +25,6 @@ pub use cst::{
     TypeDeclBody, TypeExpr, TypeExprKind, TypeField, TypeSumBody, TypeVariant, UnaryOperator,
     UseImport, UseItem,
 };
-pub use format::Formatter;
+pub use format::{FormatOptions, Formatter, RangeFormatResult, StyleFinding, StyleFindingKind};
 pub use lex::{LexedModule, Token, TokenKind, lex_module};
 pub use parse::{ParsedModule, parse_module};
@@ -816,6 +816,21 @@ impl<'a> Parser<'a> {
                     kind: ExprKind::Text(literal),
                 })
             }
+            TokenKind::CharLiteral => {
+                *cursor = index + 1;
+                let span = self.source_span_of_token(index);
+                let raw = self.tokens[index].text(self.source);
+                // Malformed literals were already reported by the lexer.
+                let value = decode_char_literal(raw).unwrap_or(char::REPLACEMENT_CHARACTER);
+                Some(Expr {
+                    span,
+                    kind: ExprKind::Char(CharLiteral {
+                        raw: raw.to_owned(),
+                        value,
+                        span,
+                    }),
+                })
+            }
             TokenKind::RegexLiteral => {
                 *cursor = index + 1;
                 let span = self.source_span_of_token(index);
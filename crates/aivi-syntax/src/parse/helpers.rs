@@ -137,6 +137,7 @@ impl<'a> Parser<'a> {
                     | TokenKind::Decimal
                     | TokenKind::BigInt
                     | TokenKind::StringLiteral
+                    | TokenKind::CharLiteral
                     | TokenKind::RegexLiteral
                     | TokenKind::Dot
                     | TokenKind::LParen
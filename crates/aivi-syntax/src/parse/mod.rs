@@ -2,24 +2,25 @@ use aivi_base::{Diagnostic, Severity, SourceFile, SourceSpan, Span};
 
 use crate::{
     cst::{
-        BigIntLiteral, BinaryOperator, ClassBody, ClassMember, ClassMemberName, ClassRequireDecl,
-        ClassWithDecl, DecimalLiteral, Decorator, DecoratorArguments, DecoratorPayload, DomainBody,
-        DomainItem, DomainMember, DomainMemberName, ErrorItem, ExportItem, Expr, ExprKind,
-        FloatLiteral, FromEntry, FromItem, FunctionParam, FunctionSurfaceForm, Identifier,
-        InstanceBody, InstanceItem, InstanceMember, IntegerLiteral, Item, ItemBase, MapExpr,
-        MapExprEntry, MarkupAttribute, MarkupAttributeValue, MarkupNode, Module, NamedItem,
-        NamedItemBody, OperatorName, PatchBlock, PatchEntry, PatchInstruction,
-        PatchInstructionKind, PatchSelector, PatchSelectorSegment, Pattern, PatternKind,
-        PipeCaseArm, PipeExpr, PipeStage, PipeStageKind, ProjectionPath, QualifiedName, RecordExpr,
-        RecordField, RecordPatternField, RegexLiteral, ResultBinding, ResultBlockExpr,
-        SignalMergeBody, SignalReactiveArm, SourceDecorator, SourceProviderContractBody,
-        SourceProviderContractFieldValue, SourceProviderContractItem, SourceProviderContractMember,
+        BigIntLiteral, BinaryOperator, CharLiteral, ClassBody, ClassMember, ClassMemberName,
+        ClassRequireDecl, ClassWithDecl, DecimalLiteral, Decorator, DecoratorArguments,
+        DecoratorPayload, DomainBody, DomainItem, DomainMember, DomainMemberName, ErrorItem,
+        ExportItem, Expr, ExprKind, FloatLiteral, FromEntry, FromItem, FunctionParam,
+        FunctionSurfaceForm, Identifier, InstanceBody, InstanceItem, InstanceMember,
+        IntegerLiteral, Item, ItemBase, MapExpr, MapExprEntry, MarkupAttribute,
+        MarkupAttributeValue, MarkupNode, Module, NamedItem, NamedItemBody, OperatorName,
+        PatchBlock, PatchEntry, PatchInstruction, PatchInstructionKind, PatchSelector,
+        PatchSelectorSegment, Pattern, PatternKind, PipeCaseArm, PipeExpr, PipeStage,
+        PipeStageKind, ProjectionPath, QualifiedName, RecordExpr, RecordField, RecordPatternField,
+        RegexLiteral, ResultBinding, ResultBlockExpr, SignalMergeBody, SignalReactiveArm,
+        SourceDecorator, SourceProviderContractBody, SourceProviderContractFieldValue,
+        SourceProviderContractItem, SourceProviderContractMember,
         SourceProviderContractSchemaMember, SuffixedIntegerLiteral, TextFragment,
         TextInterpolation, TextLiteral, TextSegment, TokenRange, TypeCompanionMember, TypeDeclBody,
         TypeExpr, TypeExprKind, TypeField, TypeSumBody, TypeVariant, TypeVariantField,
         UnaryOperator, UseImport, UseItem,
     },
-    lex::{LexedModule, Token, TokenKind, lex_fragment, lex_module},
+    lex::{LexedModule, Token, TokenKind, decode_char_literal, lex_fragment, lex_module},
 };
 
 use crate::codes::*;
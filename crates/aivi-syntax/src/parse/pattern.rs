@@ -32,6 +32,19 @@ impl<'a> Parser<'a> {
         }
         let mut pattern = self.parse_pattern_atom(cursor, end, stop);
         let result = pattern.take().and_then(|mut p| {
+            if let PatternKind::Text(prefix) = &p.kind
+                && let Some(concat) = self.text_concat_operator(*cursor, end)
+            {
+                *cursor = concat + 2;
+                let rest = self.parse_pattern(cursor, end, stop)?;
+                return Some(Pattern {
+                    span: p.span.join(rest.span).unwrap_or(p.span),
+                    kind: PatternKind::TextPrefix {
+                        prefix: prefix.clone(),
+                        rest: Box::new(rest),
+                    },
+                });
+            }
             while let Some(index) = self.peek_nontrivia(*cursor, end) {
                 if self.pattern_should_stop(index, stop) || !self.starts_pattern(index) {
                     break;
@@ -122,6 +135,17 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Index of the first `+` when the next tokens spell `++` with no space between them.
+    fn text_concat_operator(&self, cursor: usize, end: usize) -> Option<usize> {
+        let first = self.peek_nontrivia(cursor, end)?;
+        let second = first + 1;
+        (second < end
+            && self.tokens[first].kind() == TokenKind::Plus
+            && self.tokens[second].kind() == TokenKind::Plus
+            && self.tokens_are_adjacent(first, second))
+        .then_some(first)
+    }
+
     fn parse_grouped_pattern(&mut self, cursor: &mut usize, end: usize) -> Option<Pattern> {
         let start = self.consume_kind(cursor, end, TokenKind::LParen)?;
         let mut elements = Vec::new();
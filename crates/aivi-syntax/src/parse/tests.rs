@@ -1562,6 +1562,114 @@ fn parser_distinguishes_builtin_noninteger_literals_from_suffix_candidates() {
     expect_suffixed(&parsed.module.items[4], "0", "xFF");
 }
 
+#[test]
+fn lexer_scans_exponents_as_float_literals() {
+    let (_, parsed) = load(
+        "value avogadro = 6.022e23\nvalue billion = 1e9\nvalue small = 1.5E-3\nvalue width = 2em\n",
+    );
+
+    assert!(!parsed.has_errors());
+    let bodies = parsed
+        .module
+        .items
+        .iter()
+        .map(|item| match item {
+            Item::Value(item) => item.expr_body().map(|expr| &expr.kind),
+            other => panic!("expected value item, got {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert!(matches!(bodies[0], Some(ExprKind::Float(literal)) if literal.raw == "6.022e23"));
+    assert!(matches!(bodies[1], Some(ExprKind::Float(literal)) if literal.raw == "1e9"));
+    assert!(matches!(bodies[2], Some(ExprKind::Float(literal)) if literal.raw == "1.5E-3"));
+    assert!(matches!(
+        bodies[3],
+        Some(ExprKind::SuffixedInteger(literal)) if literal.suffix.text == "em"
+    ));
+}
+
+#[test]
+fn lexer_reports_malformed_float_exponents() {
+    for input in [
+        "value bad = 1e+\n",
+        "value bad = 1e\n",
+        "value bad = 2.5e-x\n",
+    ] {
+        let (_, parsed) = load(input);
+        let diagnostic = parsed
+            .all_diagnostics()
+            .find(|diagnostic| diagnostic.code == Some(crate::codes::MALFORMED_FLOAT_EXPONENT))
+            .unwrap_or_else(|| panic!("expected a malformed exponent diagnostic for {input:?}"));
+        assert!(
+            diagnostic
+                .message
+                .starts_with("malformed exponent in float literal")
+        );
+    }
+}
+
+#[test]
+fn lexer_decodes_char_literals_and_escapes() {
+    let (_, parsed) = load(
+        "value letter = 'a'\nvalue smiley = '\\u{1F600}'\nvalue newline = '\\n'\nvalue quote = '\\''\nvalue lambda = 'λ'\n",
+    );
+
+    assert!(!parsed.has_errors());
+    let chars = parsed
+        .module
+        .items
+        .iter()
+        .map(|item| match item {
+            Item::Value(item) => match item.expr_body().map(|expr| &expr.kind) {
+                Some(ExprKind::Char(literal)) => literal.value,
+                other => panic!("expected char literal, got {other:?}"),
+            },
+            other => panic!("expected value item, got {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(chars, vec!['a', '\u{1F600}', '\n', '\'', 'λ']);
+}
+
+#[test]
+fn lexer_reports_malformed_char_literals() {
+    for (input, code) in [
+        ("value bad = ''\n", crate::codes::INVALID_CHAR_LITERAL),
+        ("value bad = 'ab'\n", crate::codes::INVALID_CHAR_LITERAL),
+        ("value bad = 'a\n", crate::codes::INVALID_CHAR_LITERAL),
+        ("value bad = '\\q'\n", crate::codes::INVALID_ESCAPE_SEQUENCE),
+        (
+            "value bad = '\\u{D800}'\n",
+            crate::codes::INVALID_ESCAPE_SEQUENCE,
+        ),
+    ] {
+        let (_, parsed) = load(input);
+        assert!(
+            parsed
+                .all_diagnostics()
+                .any(|diagnostic| diagnostic.code == Some(code)),
+            "expected {code:?} for {input:?}"
+        );
+    }
+}
+
+#[test]
+fn lexer_reports_out_of_range_float_literals() {
+    let (_, parsed) = load("value huge = 1e999\nvalue tiny = 1e-999\nvalue max = 1.7e308\n");
+
+    let diagnostics = parsed
+        .all_diagnostics()
+        .filter(|diagnostic| diagnostic.code == Some(crate::codes::FLOAT_LITERAL_OUT_OF_RANGE))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        diagnostics.len(),
+        1,
+        "only `1e999` overflows; underflow rounds to zero: {diagnostics:?}"
+    );
+    assert_eq!(
+        diagnostics[0].message,
+        "float literal `1e999` is out of range"
+    );
+}
+
 #[test]
 fn parser_accepts_adjacent_negative_numeric_literals() {
     fn expect_integer(item: &Item, raw: &str) {
@@ -1729,6 +1837,38 @@ fn parser_accepts_negative_integer_patterns() {
     ));
 }
 
+#[test]
+fn parser_accepts_text_prefix_patterns() {
+    let (_, parsed) =
+        load("fun flag:Text = arg:Text => arg\n  ||> \"--\" ++ rest -> rest\n  ||> _ -> arg\n");
+
+    assert!(
+        !parsed.has_errors(),
+        "text prefix patterns should parse cleanly: {:?}",
+        parsed.all_diagnostics().collect::<Vec<_>>()
+    );
+    let Item::Fun(item) = &parsed.module.items[0] else {
+        panic!("expected a function item");
+    };
+    let ExprKind::Pipe(pipe) = &item.expr_body().expect("function should carry a body").kind else {
+        panic!("expected the function body to remain a pipe");
+    };
+    let PipeStageKind::Case(first_case) = &pipe.stages[0].kind else {
+        panic!("expected first stage to be a case arm");
+    };
+    let PatternKind::TextPrefix { prefix, rest } = &first_case.pattern.kind else {
+        panic!(
+            "expected a text prefix pattern, found {:?}",
+            first_case.pattern.kind
+        );
+    };
+    assert!(matches!(
+        prefix.segments.as_slice(),
+        [TextSegment::Text(fragment)] if fragment.raw == "--"
+    ));
+    assert!(matches!(rest.kind, PatternKind::Name(ref name) if name.text == "rest"));
+}
+
 #[test]
 fn parser_accepts_domain_member_bindings_after_type_annotation() {
     let (_, parsed) = load(
@@ -104,6 +104,7 @@ pub enum PrimitiveType {
     Text,
     Unit,
     Bytes,
+    Char,
 }
 
 #[derive(
@@ -1296,6 +1296,7 @@ fn primitive_name(primitive: PrimitiveType) -> &'static str {
         PrimitiveType::Text => "Text",
         PrimitiveType::Unit => "Unit",
         PrimitiveType::Bytes => "Bytes",
+        PrimitiveType::Char => "Char",
     }
 }
 
@@ -142,6 +142,7 @@ const stdlibSections: DocSection[] = [
       { text: 'Either Values', link: '/stdlib/either' },
       { text: 'Lists', link: '/stdlib/list' },
       { text: 'Matrices', link: '/stdlib/matrix' },
+      { text: 'Graphs', link: '/stdlib/graph' },
       { text: 'Non-Empty Lists', link: '/stdlib/nonEmpty' },
       { text: 'Pairs', link: '/stdlib/pair' },
       { text: 'Ordering & Comparison', link: '/stdlib/order' },
@@ -160,10 +161,14 @@ const stdlibSections: DocSection[] = [
       { text: 'Math', link: '/stdlib/math' },
       { text: 'Floating-Point Numbers', link: '/stdlib/float' },
       { text: 'Big Integers', link: '/stdlib/bigint' },
+      { text: 'Decimals', link: '/stdlib/decimal' },
       { text: 'JSON', link: '/stdlib/json' },
+      { text: 'TOML', link: '/stdlib/toml' },
       { text: 'Text Processing', link: '/stdlib/text' },
+      { text: 'Characters', link: '/stdlib/char' },
       { text: 'Regular Expressions', link: '/stdlib/regex' },
       { text: 'Byte Buffers', link: '/stdlib/bytes' },
+      { text: 'Message Authentication & Signatures', link: '/stdlib/crypto' },
     ],
   },
   {
@@ -187,6 +192,9 @@ const stdlibSections: DocSection[] = [
       { text: 'Standard I/O', link: '/stdlib/stdio' },
       { text: 'Logging', link: '/stdlib/log' },
       { text: 'Processes', link: '/stdlib/process' },
+      { text: 'Task Failures', link: '/stdlib/task' },
+      { text: 'Testing', link: '/stdlib/test' },
+      { text: 'Instance Laws', link: '/stdlib/laws' },
     ],
   },
   {
@@ -195,6 +203,7 @@ const stdlibSections: DocSection[] = [
     items: [
       { text: 'URLs', link: '/stdlib/url' },
       { text: 'HTTP', link: '/stdlib/http' },
+      { text: 'WebSockets', link: '/stdlib/ws' },
       { text: 'API Vocabulary', link: '/stdlib/api' },
       { text: 'Authentication', link: '/stdlib/auth' },
       { text: 'Databases', link: '/stdlib/db' },
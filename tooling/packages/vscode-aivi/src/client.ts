@@ -54,13 +54,16 @@ export function createClient(
     documentSelector: [{ language: "aivi" }],
     synchronize: {
       fileEvents: vscode.workspace.createFileSystemWatcher("**/*.aivi"),
+      configurationSection: "aivi",
     },
     initializationOptions: {
       diagnosticsDebounceMs: config.diagnosticsDebounceMs,
+      maxErrors: config.diagnosticsMaxErrors,
       inlayHintsEnabled: config.inlayHintsEnabled,
       inlayHintsMaxLength: config.inlayHintsMaxLength,
       codeLensEnabled: config.codeLensEnabled,
       completionAutoImport: config.completionAutoImport,
+      strictMode: config.strictMode,
     },
     outputChannel,
     traceOutputChannel,
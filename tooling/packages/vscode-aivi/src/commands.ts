@@ -1,5 +1,6 @@
 import * as vscode from "vscode";
 import type { LanguageClient } from "vscode-languageclient/node";
+import { runTests } from "./testResults";
 
 export function registerCommands(
   context: vscode.ExtensionContext,
@@ -51,11 +52,7 @@ export function registerCommands(
     vscode.commands.registerCommand(
       "aivi.runTest",
       async (fileUri: string, testName: string) => {
-        const config = vscode.workspace.getConfiguration("aivi");
-        const aiviPath = config.get<string>("compiler.path") ?? "aivi";
-        const terminal = vscode.window.createTerminal(`AIVI Test: ${testName}`);
-        terminal.sendText(`${aiviPath} test "${fileUri}" "${testName}"`);
-        terminal.show();
+        await runTests(fileUri, testName, getClient(), outputChannel);
       }
     )
   );
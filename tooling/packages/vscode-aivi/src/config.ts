@@ -5,10 +5,12 @@ export interface AiviConfig {
   compilerArgs: string[];
   compilerTimeout: number;
   diagnosticsDebounceMs: number;
+  diagnosticsMaxErrors: number;
   inlayHintsEnabled: boolean;
   inlayHintsMaxLength: number;
   codeLensEnabled: boolean;
   completionAutoImport: boolean;
+  strictMode: boolean;
   traceServer: "off" | "messages" | "verbose";
   formatOnSave: boolean;
 }
@@ -20,10 +22,12 @@ export function getConfig(): AiviConfig {
     compilerArgs: cfg.get<string[]>("compiler.args") ?? [],
     compilerTimeout: cfg.get<number>("compiler.timeout") ?? 5000,
     diagnosticsDebounceMs: cfg.get<number>("diagnostics.debounceMs") ?? 200,
+    diagnosticsMaxErrors: cfg.get<number>("diagnostics.maxErrors") ?? 100,
     inlayHintsEnabled: cfg.get<boolean>("inlayHints.enabled") ?? true,
     inlayHintsMaxLength: cfg.get<number>("inlayHints.maxLength") ?? 30,
     codeLensEnabled: cfg.get<boolean>("codeLens.enabled") ?? true,
     completionAutoImport: cfg.get<boolean>("completion.autoImport") ?? true,
+    strictMode: cfg.get<boolean>("strictMode") ?? false,
     traceServer: cfg.get<"off" | "messages" | "verbose">("trace.server") ?? "off",
     formatOnSave: cfg.get<boolean>("format.onSave") ?? false,
   };
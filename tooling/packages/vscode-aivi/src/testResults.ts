@@ -0,0 +1,83 @@
+import { execFile } from "node:child_process";
+import * as vscode from "vscode";
+import type { LanguageClient } from "vscode-languageclient/node";
+
+/** Custom notification the language server turns into inline `aivi-test` diagnostics. */
+export const TEST_RESULTS_METHOD = "aivi/testResults";
+
+export interface TestResult {
+  name: string;
+  passed: boolean;
+  message?: string;
+}
+
+// `aivi test` prints one `ok   <path>:<line>:<col>::<name>[: detail]` or `fail ...` line per test.
+const OUTCOME_LINE = /^(ok|fail)\s+(.+):\d+:\d+::([^:\s]+)(?:: (.*))?$/;
+
+/** Group the outcome lines of an `aivi test` run by the file declaring each test. */
+export function parseTestOutput(output: string): Map<string, TestResult[]> {
+  const byFile = new Map<string, TestResult[]>();
+  for (const line of output.split(/\r?\n/)) {
+    const match = OUTCOME_LINE.exec(line);
+    if (!match) continue;
+    const [, status, path, name, message] = match;
+    const results = byFile.get(path) ?? [];
+    results.push({ name, passed: status === "ok", message });
+    byFile.set(path, results);
+  }
+  return byFile;
+}
+
+/**
+ * Run `aivi test` for the workspace containing `fileUri`, echo its output, and report every
+ * outcome to the language server so each `@test` shows a pass/fail marker.
+ */
+export async function runTests(
+  fileUri: string,
+  testName: string,
+  client: LanguageClient | undefined,
+  outputChannel: vscode.OutputChannel
+): Promise<void> {
+  const file = vscode.Uri.parse(fileUri);
+  const config = vscode.workspace.getConfiguration("aivi");
+  const aiviPath = config.get<string>("compiler.path") ?? "aivi";
+  const cwd = vscode.workspace.getWorkspaceFolder(file)?.uri.fsPath;
+
+  outputChannel.appendLine(`[aivi] ${aiviPath} test ${file.fsPath}`);
+  const output = await new Promise<string>((resolve) => {
+    // A failing run exits non-zero; its outcome lines are still what we want.
+    execFile(aiviPath, ["test", file.fsPath], { cwd }, (error, stdout, stderr) => {
+      if (error && !stdout && !stderr) {
+        resolve(`${error.message}\n`);
+        return;
+      }
+      resolve(`${stdout}${stderr}`);
+    });
+  });
+  outputChannel.append(output);
+
+  const byFile = parseTestOutput(output);
+  if (client) {
+    for (const [path, results] of byFile) {
+      await client.sendNotification(TEST_RESULTS_METHOD, {
+        uri: vscode.Uri.file(path).toString(),
+        results,
+      });
+    }
+  }
+
+  const outcome = byFile
+    .get(file.fsPath)
+    ?.find((result) => result.name === testName);
+  if (!outcome) {
+    vscode.window.showWarningMessage(
+      `No result for test \`${testName}\`; see the AIVI output channel.`
+    );
+  } else if (outcome.passed) {
+    vscode.window.showInformationMessage(`Test \`${testName}\` passed.`);
+  } else {
+    vscode.window.showErrorMessage(
+      `Test \`${testName}\` failed${outcome.message ? `: ${outcome.message}` : "."}`
+    );
+  }
+}